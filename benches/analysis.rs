@@ -0,0 +1,113 @@
+//! Analysis benchmarks on a generated 100-file project (see `tests/common/solgen.rs`).
+//!
+//! ```sh
+//! cargo bench --bench analysis               # all benchmarks
+//! cargo bench --bench analysis -- detector/  # names containing "detector/"
+//! ```
+//!
+//! Criterion samples each benchmark and reports its estimate and the change since the last
+//! saved run under `target/criterion`. Without `--bench` (e.g. `cargo test --all-targets`)
+//! every benchmark runs once, as a check that it still works.
+//!
+//! The single-detector benchmarks cover the detectors with the highest traversal time on
+//! this project when they were measured: `prefer-concat`, `truncating-assignment` and
+//! `balance-equality`.
+
+#[path = "../tests/common/solgen.rs"]
+mod solgen;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use weasel::config::Config;
+use weasel::core::engine::AnalysisEngine;
+use weasel::core::processor::Processor;
+use weasel::core::visitor::ASTVisitor;
+
+const FILES: usize = 100;
+const SEED: u64 = 0x5eed;
+const HEAVY_DETECTORS: [&str; 3] = ["prefer-concat", "truncating-assignment", "balance-equality"];
+// Loading and analyzing the whole project takes long enough that criterion's default of
+// 100 samples would stretch a run to minutes
+const PROJECT_SAMPLES: usize = 10;
+
+fn config(dir: &Path) -> Config {
+    Config {
+        scope: vec![dir.join("src")],
+        exclude: Vec::new(),
+        ..Config::default()
+    }
+}
+
+fn loaded_engine(config: &Config) -> AnalysisEngine {
+    let mut engine = AnalysisEngine::new(config);
+    engine.register_built_in_detectors();
+    engine
+        .load_project()
+        .expect("Failed to load synthetic project");
+    engine
+}
+
+fn analysis(c: &mut Criterion) {
+    let dir: PathBuf = env::temp_dir().join(format!("weasel-bench-{}", std::process::id()));
+    let bytes = solgen::write_project(&dir, FILES, SEED);
+    println!("synthetic project: {} files, {} KiB", FILES, bytes / 1024);
+    let config = config(&dir);
+
+    let mut project = c.benchmark_group("parse_and_metadata");
+    project.sample_size(PROJECT_SAMPLES);
+    project.bench_function("100_files", |b| {
+        b.iter(|| {
+            let mut engine = AnalysisEngine::new(&config);
+            engine
+                .load_project()
+                .expect("Failed to load synthetic project");
+            engine
+        })
+    });
+    project.finish();
+
+    // Traversal only: the project is loaded once and shared by every sample
+    let engine = loaded_engine(&config);
+    let context = engine.context();
+    let processor = Processor::new();
+
+    let mut visitor = ASTVisitor::new();
+    for detector in engine.registry().get_all() {
+        detector.register_callbacks(&mut visitor);
+    }
+    c.bench_function("traverse/all_detectors", |b| {
+        b.iter(|| processor.process_files(&context.files, &visitor, context))
+    });
+
+    let mut detectors = c.benchmark_group("detector");
+    for id in HEAVY_DETECTORS {
+        let detector = engine
+            .registry()
+            .get(id)
+            .unwrap_or_else(|| panic!("Unknown detector: {}", id));
+        let mut visitor = ASTVisitor::new();
+        detector.register_callbacks(&mut visitor);
+        detectors.bench_function(id, |b| {
+            b.iter(|| processor.process_files(&context.files, &visitor, context))
+        });
+    }
+    detectors.finish();
+
+    let mut analyze = c.benchmark_group("analyze");
+    analyze.sample_size(PROJECT_SAMPLES);
+    analyze.bench_function("100_files", |b| {
+        b.iter(|| {
+            let mut engine = AnalysisEngine::new(&config);
+            engine.register_built_in_detectors();
+            engine.analyze().expect("Analysis failed")
+        })
+    });
+    analyze.finish();
+
+    fs::remove_dir_all(&dir).ok();
+}
+
+criterion_group!(benches, analysis);
+criterion_main!(benches);
@@ -0,0 +1,24 @@
+use std::path::Path;
+use std::process::Command;
+
+fn main() {
+    // Embed `git describe` so `weasel --version --json` tells builds of the same version apart
+    let describe = Command::new("git")
+        .args(["describe", "--tags", "--always", "--dirty"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|describe| describe.trim().to_string())
+        .filter(|describe| !describe.is_empty());
+    if let Some(describe) = describe {
+        println!("cargo:rustc-env=WEASEL_GIT_DESCRIBE={}", describe);
+    }
+
+    println!("cargo:rerun-if-changed=build.rs");
+    for path in [".git/HEAD", ".git/index", ".git/refs"] {
+        if Path::new(path).exists() {
+            println!("cargo:rerun-if-changed={}", path);
+        }
+    }
+}
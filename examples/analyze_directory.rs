@@ -0,0 +1,32 @@
+//! Analyze a directory of Solidity files with weasel as a library and print the JSON report.
+//!
+//! ```sh
+//! cargo run --example analyze_directory -- path/to/contracts
+//! ```
+
+use std::env;
+use std::error::Error;
+use std::path::PathBuf;
+use weasel::output::JsonReport;
+use weasel::{AnalysisEngine, Config};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let dir = env::args()
+        .nth(1)
+        .ok_or("usage: analyze_directory <directory>")?;
+
+    let config = Config {
+        scope: vec![PathBuf::from(dir)],
+        quiet: true,
+        ..Config::default()
+    };
+    let mut engine = AnalysisEngine::new(&config);
+    engine.register_built_in_detectors();
+    let report = engine.analyze()?;
+
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&JsonReport::new(&report))?
+    );
+    Ok(())
+}
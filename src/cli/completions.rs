@@ -0,0 +1,112 @@
+use crate::cli::Cli;
+use crate::config::Config;
+use crate::core::engine::AnalysisEngine;
+use crate::models::Severity;
+use crate::output::style::errln;
+use clap::{Command, CommandFactory};
+use clap_complete::Shell;
+use clap_mangen::Man;
+use std::io::{self, Write};
+
+const REPORT_FORMATS: [&str; 5] = ["md", "json", "sarif", "jsonl", "html"];
+
+pub fn handle_completions_command(shell: Shell) {
+    let mut cmd = completion_command();
+    let name = cmd.get_name().to_string();
+    clap_complete::generate(shell, &mut cmd, name, &mut io::stdout());
+}
+
+pub fn handle_man_command() {
+    if let Err(e) = write_man_page(&mut io::stdout()) {
+        errln!("Error writing man page: {}", e);
+        std::process::exit(1);
+    }
+}
+
+/// The CLI with value lists attached for completion only. The real flags stay free-form so
+/// `--format markdown` or `--min-severity High` keep parsing the way they always have.
+fn completion_command() -> Command {
+    let detector_ids = registered_detector_ids();
+
+    Cli::command()
+        .mut_subcommand("run", |run| {
+            run.mut_arg("format", |arg| arg.value_parser(REPORT_FORMATS))
+                .mut_arg("min_severity", |arg| {
+                    arg.value_parser(Severity::ALL.map(|s| s.as_str()))
+                })
+                .mut_arg("exclude_detectors", |arg| {
+                    arg.value_parser(detector_ids.clone())
+                })
+        })
+        .mut_subcommand("detectors", |detectors| {
+            detectors
+                .mut_arg("severity", |arg| {
+                    arg.value_parser(Severity::ALL.map(|s| s.as_str()))
+                })
+                .mut_arg("details", |arg| arg.value_parser(detector_ids.clone()))
+        })
+}
+
+/// Ids of every built-in detector, taken from the registry so completions can't go stale.
+fn registered_detector_ids() -> Vec<&'static str> {
+    let config = Config::default();
+    let mut engine = AnalysisEngine::new(&config);
+    engine.register_built_in_detectors();
+
+    let mut ids: Vec<_> = engine.registry().get_all().iter().map(|d| d.id()).collect();
+    ids.sort_unstable();
+    ids
+}
+
+/// Render the top-level page followed by one page per subcommand (`weasel-run(1)`, ...).
+pub fn write_man_page(out: &mut dyn Write) -> io::Result<()> {
+    let mut cmd = Cli::command();
+    cmd.build();
+    let source = format!("weasel {}", crate::core::version());
+    render_man_pages(&cmd, &source, out)
+}
+
+fn render_man_pages(cmd: &Command, source: &str, out: &mut dyn Write) -> io::Result<()> {
+    Man::new(cmd.clone()).source(source).render(out)?;
+    for subcommand in cmd.get_subcommands() {
+        if subcommand.get_name() != "help" {
+            render_man_pages(subcommand, source, out)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bash_completions_include_detector_ids() {
+        let mut cmd = completion_command();
+        let mut script = Vec::new();
+        clap_complete::generate(Shell::Bash, &mut cmd, "weasel", &mut script);
+        let script = String::from_utf8(script).unwrap();
+
+        for id in [
+            "unchecked-low-level-call",
+            "floating-pragma",
+            "arithmetic-without-safemath",
+        ] {
+            assert!(script.contains(id), "missing detector id `{}`", id);
+        }
+        assert!(script.contains("md json sarif"));
+        assert!(script.contains("critical high medium low gas nc"));
+    }
+
+    #[test]
+    fn test_man_page_covers_every_subcommand() {
+        let mut page = Vec::new();
+        write_man_page(&mut page).unwrap();
+        let page = String::from_utf8(page).unwrap();
+
+        for subcommand in Cli::command().get_subcommands() {
+            let title = format!(".TH weasel-{} 1", subcommand.get_name());
+            assert!(page.contains(&title), "missing man page for `{}`", title);
+        }
+    }
+}
@@ -1,17 +1,45 @@
 use crate::config::Config;
 use crate::core::engine::AnalysisEngine;
+use crate::core::registry::DetectorRegistry;
+use crate::detectors::Detector;
+use crate::models::Severity;
+use crate::output::style::{errln, outln};
+use std::cmp::Reverse;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
-pub fn handle_detectors_command(severity: Option<String>, details: Option<String>) {
+pub fn handle_detectors_command(
+    severity: Option<String>,
+    details: Option<String>,
+    export_docs: Option<PathBuf>,
+) {
     let config = Config::default();
     let mut engine = AnalysisEngine::new(&config);
     engine.register_built_in_detectors();
     let registry = engine.registry();
 
+    if let Some(dir) = export_docs {
+        match export_detector_docs(registry, &dir) {
+            Ok(count) => outln!("Exported docs for {} detectors to {}", count, dir.display()),
+            Err(e) => {
+                errln!(
+                    "Error exporting detector docs to '{}': {}",
+                    dir.display(),
+                    e
+                );
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
     if let Some(detector_id) = details {
         if let Some(detector) = registry.get(&detector_id) {
-            println!("{}", detector);
+            outln!("{}", detector);
         } else {
-            eprintln!("Error: Detector with ID '{}' not found.", detector_id);
+            errln!("Error: Detector with ID '{}' not found.", detector_id);
         }
         return;
     }
@@ -19,30 +47,136 @@ pub fn handle_detectors_command(severity: Option<String>, details: Option<String
     let detectors = if let Some(sev_str) = &severity {
         match sev_str.parse() {
             Ok(sev) => {
-                println!("\nAvailable detectors filtered by severity: {}", sev);
+                outln!("\nAvailable detectors filtered by severity: {}", sev);
                 registry.get_by_severity(&sev)
             }
             Err(e) => {
-                eprintln!("Error: {}", e);
-                eprintln!("Acceptable values: high, medium, low, gas, nc");
+                errln!("Error: {}", e);
+                errln!(
+                    "Acceptable values: {}",
+                    Severity::ALL.map(|s| s.as_str()).join(", ")
+                );
                 std::process::exit(1);
             }
         }
     } else {
-        println!("\nAvailable detectors (Total: {}):", registry.count());
+        outln!("\nAvailable detectors (Total: {}):", registry.count());
         registry.get_all()
     };
 
     if detectors.is_empty() {
-        println!("No detectors found");
+        outln!("No detectors found");
     } else {
         for detector in detectors {
-            println!(
-                "({}) - {}: {}",
-                detector.severity(),
-                detector.id(),
-                detector.name(),
-            );
+            let chains = detector.relevant_chains();
+            if chains.is_empty() {
+                outln!(
+                    "({}) - {}: {}",
+                    detector.severity(),
+                    detector.id(),
+                    detector.name(),
+                );
+            } else {
+                outln!(
+                    "({}) - {}: {} [{}]",
+                    detector.severity(),
+                    detector.id(),
+                    detector.name(),
+                    chains.join(", "),
+                );
+            }
         }
     }
 }
+
+/// Write `<id>.md` for every registered detector and an `index.md` grouped by severity.
+/// Detectors are ordered by severity, then id, so the output is stable across runs.
+pub fn export_detector_docs(registry: &DetectorRegistry, dir: &Path) -> io::Result<usize> {
+    let mut detectors = registry.get_all();
+    detectors.sort_by_key(|d| (Reverse(d.severity().as_value()), d.id()));
+
+    fs::create_dir_all(dir)?;
+
+    let mut index = String::from("# Weasel Detectors\n\n");
+    index.push_str(&format!("Total: {}\n", detectors.len()));
+
+    let mut current_severity = None;
+    for detector in &detectors {
+        let severity = detector.severity();
+        if current_severity.as_ref() != Some(&severity) {
+            index.push_str(&format!("\n## {}\n\n", severity));
+            current_severity = Some(severity);
+        }
+        index.push_str(&format!(
+            "- [`{}`]({}.md) - {}\n",
+            detector.id(),
+            detector.id(),
+            detector.name()
+        ));
+
+        fs::write(
+            dir.join(format!("{}.md", detector.id())),
+            detector_doc(detector),
+        )?;
+    }
+
+    fs::write(dir.join("index.md"), index)?;
+    Ok(detectors.len())
+}
+
+fn detector_doc(detector: &Arc<dyn Detector>) -> String {
+    let mut doc = format!("# {}\n\n", detector.name());
+    doc.push_str(&format!("- **ID**: `{}`\n", detector.id()));
+    doc.push_str(&format!("- **Severity**: {}\n", detector.severity()));
+    doc.push_str(&format!("- **Category**: {}\n", detector.category()));
+    if !detector.relevant_chains().is_empty() {
+        doc.push_str(&format!(
+            "- **Chains**: {}\n",
+            detector.relevant_chains().join(", ")
+        ));
+    }
+    doc.push_str(&format!("\n## Description\n\n{}\n", detector.description()));
+    if let Some(example) = detector.example() {
+        doc.push_str(&format!("\n## Example\n\n{}\n", example));
+    }
+    doc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_detector_docs() {
+        let mut engine = AnalysisEngine::new(&Config::default());
+        engine.register_built_in_detectors();
+        let registry = engine.registry();
+
+        let dir = std::env::temp_dir().join(format!("weasel-docs-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        let count = export_detector_docs(registry, &dir).unwrap();
+        assert_eq!(count, registry.count());
+
+        let detector_files = fs::read_dir(&dir)
+            .unwrap()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_name() != "index.md")
+            .count();
+        assert_eq!(detector_files, registry.count());
+
+        let doc = fs::read_to_string(dir.join("deprecated-constructs.md")).unwrap();
+        assert!(doc.contains("- **Severity**: NC"));
+        assert!(doc.contains("```solidity"));
+
+        let index = fs::read_to_string(dir.join("index.md")).unwrap();
+        assert!(index.find("## High").unwrap() < index.find("## NC").unwrap());
+        assert!(index.contains("[`deprecated-constructs`](deprecated-constructs.md)"));
+
+        let first_run = index;
+        export_detector_docs(registry, &dir).unwrap();
+        assert_eq!(fs::read_to_string(dir.join("index.md")).unwrap(), first_run);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,355 @@
+use crate::cli::load_config_or_exit;
+use crate::config::ConfigOverrides;
+use crate::core::context::AnalysisContext;
+use crate::core::engine::AnalysisEngine;
+use crate::models::SolidityFile;
+use crate::output::style::{self, errln, outln};
+use crate::utils::path::{normalize_report_path, PathBase};
+use clap::Args;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashSet, VecDeque};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Args, Debug, Default)]
+pub struct GraphArgs {
+    #[arg(short, long)]
+    pub scope: Option<Vec<PathBuf>>,
+
+    #[arg(short, long)]
+    pub exclude: Option<Vec<PathBuf>>,
+
+    #[arg(short, long, value_name = "PATH_TO_CONFIG")]
+    pub config: Option<PathBuf>,
+
+    #[arg(short, long)]
+    pub remappings: Option<Vec<String>>,
+
+    /// Output format: dot (Graphviz, default) or json
+    #[arg(short, long)]
+    pub format: Option<String>,
+}
+
+/// File-level import graph of the scope and every file it imports, as printed by `weasel graph`.
+#[derive(Debug, Serialize)]
+pub struct ImportGraph {
+    /// Files by project-relative path
+    pub files: BTreeMap<String, GraphFile>,
+    /// Each import cycle as the files along it, ending with the file it started from
+    pub cycles: Vec<Vec<String>>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct GraphFile {
+    /// Part of the scope, rather than pulled in by an import (usually from a library path)
+    pub in_scope: bool,
+    /// Contracts, interfaces and libraries declared in the file
+    pub contracts: Vec<String>,
+    /// Files it imports, by project-relative path
+    pub imports: Vec<String>,
+    /// Import paths, as written, that did not resolve to a file
+    pub unresolved: Vec<String>,
+}
+
+pub fn handle_graph_command(args: GraphArgs) {
+    let as_json = match args.format.as_deref().map(str::to_lowercase).as_deref() {
+        None | Some("dot") => false,
+        Some("json") => true,
+        Some(other) => {
+            errln!("Error: Unknown format '{}'. Use 'dot' or 'json'.", other);
+            std::process::exit(1);
+        }
+    };
+
+    let config = load_config_or_exit(ConfigOverrides {
+        scope: args.scope,
+        exclude: args.exclude,
+        remappings: args.remappings,
+        config_path: args.config,
+        ..ConfigOverrides::default()
+    });
+
+    let mut engine = AnalysisEngine::new(&config);
+    let scope = engine.configure_project();
+    if let Err(e) = engine.load_scope(&scope) {
+        errln!("Error loading files: {}", e);
+        std::process::exit(1);
+    }
+
+    let graph = import_graph(engine.context(), engine.project_root());
+    if as_json {
+        if let Err(e) = serde_json::to_writer_pretty(style::json_stdout(), &graph) {
+            errln!("Error serializing graph: {}", e);
+            std::process::exit(1);
+        }
+        outln!();
+    } else {
+        outln!("{}", graph.to_dot());
+    }
+}
+
+/// Resolve the imports of every loaded file, following resolved imports outside the scope until
+/// every reachable file is in the graph. Files that cannot be parsed are kept without edges.
+pub fn import_graph(context: &AnalysisContext, project_root: &Path) -> ImportGraph {
+    let mut pending: VecDeque<(PathBuf, bool)> = context
+        .files
+        .iter()
+        .map(|file| (file.path.clone(), true))
+        .collect();
+    let mut seen: HashSet<PathBuf> = pending.iter().map(|(path, _)| canonical(path)).collect();
+    let mut files = BTreeMap::new();
+    // Same paths as in reports
+    let root = PathBase::Project.resolve_root(project_root);
+    let display_path =
+        |path: &Path| normalize_report_path(&path.to_string_lossy(), root.as_deref());
+
+    while let Some((path, in_scope)) = pending.pop_front() {
+        let loaded = context.files.iter().find(|file| file.path == path);
+        let parsed = match loaded {
+            Some(_) => None,
+            None => SolidityFile::from_path(&path).ok(),
+        };
+        let mut node = GraphFile {
+            in_scope,
+            ..GraphFile::default()
+        };
+        if let Some(file) = loaded.or(parsed.as_ref()) {
+            node.contracts = file
+                .contract_definitions
+                .iter()
+                .map(|contract| contract.name.clone())
+                .collect();
+            for import in &file.imports {
+                let resolved = context
+                    .get_import_resolver()
+                    .and_then(|resolver| resolver.resolve_import(&import.import_path, &path).ok());
+                let Some(resolved) = resolved else {
+                    node.unresolved.push(import.import_path.clone());
+                    continue;
+                };
+                node.imports.push(display_path(&resolved));
+                if seen.insert(canonical(&resolved)) {
+                    pending.push_back((resolved, false));
+                }
+            }
+        }
+        node.imports.sort();
+        node.imports.dedup();
+        node.unresolved.sort();
+        node.unresolved.dedup();
+        files.insert(display_path(&path), node);
+    }
+
+    let cycles = find_cycles(&files);
+    ImportGraph { files, cycles }
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// One cycle per import that leads back to a file still being visited, in path order.
+fn find_cycles(files: &BTreeMap<String, GraphFile>) -> Vec<Vec<String>> {
+    fn visit<'a>(
+        path: &'a str,
+        files: &'a BTreeMap<String, GraphFile>,
+        stack: &mut Vec<&'a str>,
+        done: &mut HashSet<&'a str>,
+        cycles: &mut Vec<Vec<String>>,
+    ) {
+        stack.push(path);
+        for next in files.get(path).map_or(&[][..], |file| &file.imports) {
+            if let Some(start) = stack.iter().position(|on_stack| on_stack == next) {
+                let mut cycle: Vec<String> = stack[start..].iter().map(|p| p.to_string()).collect();
+                cycle.push(next.clone());
+                cycles.push(cycle);
+            } else if !done.contains(next.as_str()) {
+                visit(next, files, stack, done, cycles);
+            }
+        }
+        stack.pop();
+        done.insert(path);
+    }
+
+    let mut cycles = Vec::new();
+    let mut done = HashSet::new();
+    for path in files.keys() {
+        if !done.contains(path.as_str()) {
+            visit(path, files, &mut Vec::new(), &mut done, &mut cycles);
+        }
+    }
+    cycles
+}
+
+impl ImportGraph {
+    /// Graphviz source: scope files as boxes, other files shaded, unresolved imports as dashed
+    /// edges to placeholder nodes, and edges along a cycle in red.
+    pub fn to_dot(&self) -> String {
+        let cycle_edges: HashSet<(&str, &str)> = self
+            .cycles
+            .iter()
+            .flat_map(|cycle| {
+                cycle
+                    .windows(2)
+                    .map(|edge| (edge[0].as_str(), edge[1].as_str()))
+            })
+            .collect();
+        let unresolved: BTreeSet<&str> = self
+            .files
+            .values()
+            .flat_map(|file| file.unresolved.iter().map(String::as_str))
+            .collect();
+
+        let mut dot = String::from("digraph imports {\n");
+        dot.push_str("    rankdir=LR;\n    node [shape=box, fontname=\"monospace\"];\n");
+        for cycle in &self.cycles {
+            let _ = writeln!(dot, "    // cycle: {}", cycle.join(" -> "));
+        }
+        for (path, file) in &self.files {
+            let mut label = path.clone();
+            if !file.contracts.is_empty() {
+                label = format!("{}\n{}", label, file.contracts.join(", "));
+            }
+            let style = if file.in_scope {
+                ""
+            } else {
+                ", style=filled, fillcolor=\"#eeeeee\""
+            };
+            let _ = writeln!(
+                dot,
+                "    {} [label={}{}];",
+                quote(path),
+                quote(&label),
+                style
+            );
+        }
+        for import in &unresolved {
+            let _ = writeln!(
+                dot,
+                "    {} [label={}, shape=note, style=dashed];",
+                quote(&format!("unresolved:{}", import)),
+                quote(import)
+            );
+        }
+        for (path, file) in &self.files {
+            for import in &file.imports {
+                let color = if cycle_edges.contains(&(path.as_str(), import.as_str())) {
+                    " [color=red]"
+                } else {
+                    ""
+                };
+                let _ = writeln!(dot, "    {} -> {}{};", quote(path), quote(import), color);
+            }
+            for import in &file.unresolved {
+                let _ = writeln!(
+                    dot,
+                    "    {} -> {} [style=dashed];",
+                    quote(path),
+                    quote(&format!("unresolved:{}", import))
+                );
+            }
+        }
+        dot.push('}');
+        dot
+    }
+}
+
+/// A DOT string literal, with line breaks as `\n`.
+fn quote(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{}\"", escaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::utils::test_utils::write_fixture_project;
+    use serde_json::json;
+
+    #[test]
+    fn test_import_graph_follows_library_imports() {
+        let root = write_fixture_project(
+            "import-graph",
+            &[
+                ("foundry.toml", "[profile.default]\n"),
+                ("remappings.txt", "@oz/=lib/oz/contracts/\n"),
+                (
+                    "lib/oz/contracts/Ownable.sol",
+                    "import \"./Context.sol\";\ncontract Ownable is Context {}\n",
+                ),
+                (
+                    "lib/oz/contracts/Context.sol",
+                    "abstract contract Context {}\n",
+                ),
+                (
+                    "src/Vault.sol",
+                    "import \"./Math.sol\";\nimport \"@solmate/ERC20.sol\";\n\
+                     contract Vault {}\ninterface IVault {}\n",
+                ),
+                (
+                    "src/Math.sol",
+                    "import \"@oz/Ownable.sol\";\nimport \"./Vault.sol\";\nlibrary Math {}\n",
+                ),
+            ],
+        );
+        let config = Config {
+            scope: vec![root.join("src")],
+            ..Config::default()
+        };
+        let mut engine = AnalysisEngine::new(&config);
+        let scope = engine.configure_project();
+        engine.load_scope(&scope).unwrap();
+
+        let graph = import_graph(engine.context(), engine.project_root());
+        assert_eq!(
+            serde_json::to_value(&graph).unwrap(),
+            json!({
+                "files": {
+                    "lib/oz/contracts/Context.sol": {
+                        "in_scope": false,
+                        "contracts": ["Context"],
+                        "imports": [],
+                        "unresolved": []
+                    },
+                    "lib/oz/contracts/Ownable.sol": {
+                        "in_scope": false,
+                        "contracts": ["Ownable"],
+                        "imports": ["lib/oz/contracts/Context.sol"],
+                        "unresolved": []
+                    },
+                    "src/Math.sol": {
+                        "in_scope": true,
+                        "contracts": ["Math"],
+                        "imports": ["lib/oz/contracts/Ownable.sol", "src/Vault.sol"],
+                        "unresolved": []
+                    },
+                    "src/Vault.sol": {
+                        "in_scope": true,
+                        "contracts": ["Vault", "IVault"],
+                        "imports": ["src/Math.sol"],
+                        "unresolved": ["@solmate/ERC20.sol"]
+                    }
+                },
+                "cycles": [["src/Math.sol", "src/Vault.sol", "src/Math.sol"]]
+            })
+        );
+
+        let dot = graph.to_dot();
+        assert!(dot.contains("    // cycle: src/Math.sol -> src/Vault.sol -> src/Math.sol\n"));
+        assert!(dot.contains(
+            "    \"lib/oz/contracts/Ownable.sol\" [label=\"lib/oz/contracts/Ownable.sol\\nOwnable\", \
+             style=filled, fillcolor=\"#eeeeee\"];\n"
+        ));
+        assert!(dot.contains("    \"src/Vault.sol\" -> \"src/Math.sol\" [color=red];\n"));
+        assert!(dot.contains(
+            "    \"src/Vault.sol\" -> \"unresolved:@solmate/ERC20.sol\" [style=dashed];\n"
+        ));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,394 @@
+use crate::cli::load_config_or_exit;
+use crate::config::ConfigOverrides;
+use crate::core::context::AnalysisContext;
+use crate::core::engine::AnalysisEngine;
+use crate::models::{ContractInfo, FunctionVisibility};
+use crate::output::style::{errln, outln};
+use clap::Args;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+#[derive(Args, Debug, Default)]
+pub struct InspectArgs {
+    /// Contract name, or `path/to/File.sol:Name` when several files declare it
+    pub contract: String,
+
+    #[arg(short, long)]
+    pub scope: Option<Vec<PathBuf>>,
+
+    #[arg(short, long)]
+    pub exclude: Option<Vec<PathBuf>>,
+
+    #[arg(short, long, value_name = "PATH_TO_CONFIG")]
+    pub config: Option<PathBuf>,
+
+    #[arg(short, long)]
+    pub remappings: Option<Vec<String>>,
+
+    /// Output format: text (default) or json
+    #[arg(short, long)]
+    pub format: Option<String>,
+}
+
+/// A contract's surface after inheritance, as printed by `weasel inspect`.
+#[derive(Debug, Serialize)]
+pub struct ContractInspection {
+    pub name: String,
+    pub contract_type: String,
+    pub file: String,
+    /// Linearized bases, most base first
+    pub inheritance: Vec<String>,
+    /// In storage layout order, base contracts first
+    pub state_variables: Vec<InspectedVariable>,
+    /// External and public functions, one per signature, with the implementation that wins
+    pub functions: Vec<InspectedFunction>,
+    pub modifiers: Vec<InspectedMember>,
+    pub events: Vec<InspectedMember>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InspectedVariable {
+    pub name: String,
+    pub type_name: String,
+    pub visibility: String,
+    /// Position among storage variables; `None` for constants and immutables
+    pub storage_index: Option<usize>,
+    pub declared_in: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InspectedFunction {
+    pub signature: String,
+    pub visibility: String,
+    pub mutability: String,
+    pub modifiers: Vec<String>,
+    pub declared_in: String,
+    /// Bases whose implementations this one overrides, most base first
+    pub overrides: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InspectedMember {
+    pub signature: String,
+    pub declared_in: String,
+}
+
+pub fn handle_inspect_command(args: InspectArgs) {
+    let as_json = match args.format.as_deref().map(str::to_lowercase).as_deref() {
+        None | Some("text") => false,
+        Some("json") => true,
+        Some(other) => {
+            errln!("Error: Unknown format '{}'. Use 'text' or 'json'.", other);
+            std::process::exit(1);
+        }
+    };
+
+    let config = load_config_or_exit(ConfigOverrides {
+        scope: args.scope,
+        exclude: args.exclude,
+        remappings: args.remappings,
+        config_path: args.config,
+        ..ConfigOverrides::default()
+    });
+
+    let mut engine = AnalysisEngine::new(&config);
+    if let Err(e) = engine.load_project() {
+        errln!("Error loading project: {}", e);
+        std::process::exit(1);
+    }
+
+    let Some(inspection) =
+        inspect_contract(engine.context(), &args.contract, engine.project_root())
+    else {
+        errln!("Error: Contract '{}' not found in scope", args.contract);
+        std::process::exit(1);
+    };
+
+    if as_json {
+        match serde_json::to_string_pretty(&inspection) {
+            Ok(json) => outln!("{}", json),
+            Err(e) => {
+                errln!("Error serializing inspection: {}", e);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        print_inspection(&inspection);
+    }
+}
+
+pub fn inspect_contract(
+    context: &AnalysisContext,
+    contract: &str,
+    project_root: &Path,
+) -> Option<ContractInspection> {
+    let qualified_name = context.get_qualified_name_for_contract(contract);
+    let info = context.get_contract(&qualified_name)?;
+
+    // Each base with its own declarations, most base first, then the contract itself
+    let definers: Vec<&ContractInfo> = info
+        .inheritance_chain
+        .iter()
+        .filter_map(|base| context.get_contract(base))
+        .chain(std::iter::once(info))
+        .collect();
+
+    let mut storage_index = 0;
+    let mut state_variables = Vec::new();
+    for definer in &definers {
+        for var in &definer.state_variables {
+            let index = (!var.is_constant && !var.is_immutable).then(|| {
+                storage_index += 1;
+                storage_index - 1
+            });
+            state_variables.push(InspectedVariable {
+                name: var.name.clone(),
+                type_name: var.type_info.to_string(),
+                visibility: format!("{:?}", var.visibility).to_lowercase(),
+                storage_index: index,
+                declared_in: definer.name.clone(),
+            });
+        }
+    }
+
+    let functions = context
+        .get_effective_functions(&qualified_name)
+        .into_iter()
+        .filter(|f| {
+            matches!(
+                f.function.visibility,
+                FunctionVisibility::External | FunctionVisibility::Public
+            )
+        })
+        .map(|f| InspectedFunction {
+            signature: f.signature,
+            visibility: format!("{:?}", f.function.visibility).to_lowercase(),
+            mutability: format!("{:?}", f.function.mutability).to_lowercase(),
+            modifiers: f.function.modifiers.clone(),
+            declared_in: contract_name(&f.defined_in),
+            overrides: f.overrides.iter().map(|q| contract_name(q)).collect(),
+        })
+        .collect();
+
+    let modifiers = definers
+        .iter()
+        .flat_map(|definer| {
+            definer.modifiers.iter().map(|m| InspectedMember {
+                signature: member_signature(&m.name, m.parameters.iter().map(|p| &p.type_name)),
+                declared_in: definer.name.clone(),
+            })
+        })
+        .collect();
+
+    let events = definers
+        .iter()
+        .flat_map(|definer| {
+            definer.events.iter().map(|e| InspectedMember {
+                signature: member_signature(&e.name, e.parameters.iter().map(|p| &p.type_name)),
+                declared_in: definer.name.clone(),
+            })
+        })
+        .collect();
+
+    let file = Path::new(&info.file_path);
+    Some(ContractInspection {
+        name: info.name.clone(),
+        contract_type: format!("{:?}", info.contract_type).to_lowercase(),
+        file: file
+            .strip_prefix(project_root)
+            .unwrap_or(file)
+            .display()
+            .to_string(),
+        inheritance: info
+            .inheritance_chain
+            .iter()
+            .map(|q| contract_name(q))
+            .collect(),
+        state_variables,
+        functions,
+        modifiers,
+        events,
+    })
+}
+
+fn print_inspection(inspection: &ContractInspection) {
+    outln!(
+        "{} {} ({})",
+        inspection.contract_type,
+        inspection.name,
+        inspection.file
+    );
+    if inspection.inheritance.is_empty() {
+        outln!("  inherits: none");
+    } else {
+        outln!(
+            "  inherits (most base first): {}",
+            inspection.inheritance.join(" -> ")
+        );
+    }
+
+    outln!(
+        "\n  state variables ({}):",
+        inspection.state_variables.len()
+    );
+    for var in &inspection.state_variables {
+        let slot = var
+            .storage_index
+            .map_or_else(|| "[-]".to_string(), |i| format!("[{}]", i));
+        outln!(
+            "    {:<5} {} {} {} from {}",
+            slot,
+            var.type_name,
+            var.visibility,
+            var.name,
+            var.declared_in
+        );
+    }
+
+    outln!(
+        "\n  external/public functions ({}):",
+        inspection.functions.len()
+    );
+    for function in &inspection.functions {
+        let mut line = format!(
+            "    {} {} {}",
+            function.signature, function.visibility, function.mutability
+        );
+        if !function.modifiers.is_empty() {
+            line.push_str(&format!(" [{}]", function.modifiers.join(", ")));
+        }
+        line.push_str(&format!(" from {}", function.declared_in));
+        if !function.overrides.is_empty() {
+            line.push_str(&format!(", overrides {}", function.overrides.join(", ")));
+        }
+        outln!("{}", line);
+    }
+
+    for (title, members) in [
+        ("modifiers", &inspection.modifiers),
+        ("events", &inspection.events),
+    ] {
+        outln!("\n  {} ({}):", title, members.len());
+        for member in members {
+            outln!("    {} from {}", member.signature, member.declared_in);
+        }
+    }
+}
+
+fn member_signature<'a>(name: &str, types: impl Iterator<Item = &'a String>) -> String {
+    let types: Vec<&str> = types.map(String::as_str).collect();
+    format!("{}({})", name, types.join(","))
+}
+
+/// `path/to/File.sol:Name` -> `Name`
+fn contract_name(qualified_name: &str) -> String {
+    qualified_name
+        .rsplit(':')
+        .next()
+        .unwrap_or(qualified_name)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::utils::test_utils::write_fixture_project;
+
+    const VAULT: &str = r#"
+        pragma solidity ^0.8.0;
+
+        contract Ownable {
+            address owner;
+            event OwnershipTransferred(address previous, address next);
+            modifier onlyOwner() { _; }
+            function transferOwnership(address next) public virtual onlyOwner {}
+            function _checkOwner() internal view {}
+        }
+
+        contract Pausable {
+            bool paused;
+            modifier whenNotPaused() { _; }
+            function pause() external virtual {}
+            function paused_() external view returns (bool) {}
+        }
+
+        contract Vault is Ownable, Pausable {
+            uint256 constant FEE = 30;
+            uint256 total;
+            constructor() {}
+            function pause() external override onlyOwner {}
+            function transferOwnership(address next) public override {}
+            function deposit() external payable whenNotPaused {}
+        }
+    "#;
+
+    #[test]
+    fn test_inspect_resolves_overrides() {
+        let root = write_fixture_project(
+            "inspect-project",
+            &[
+                ("foundry.toml", "[profile.default]\n"),
+                ("src/Vault.sol", VAULT),
+            ],
+        );
+        let config = Config {
+            scope: vec![root.join("src")],
+            ..Config::default()
+        };
+        let mut engine = AnalysisEngine::new(&config);
+        engine.load_project().unwrap();
+
+        let inspection = inspect_contract(engine.context(), "Vault", &root).unwrap();
+        assert_eq!(inspection.file, "src/Vault.sol");
+        assert_eq!(inspection.inheritance, ["Ownable", "Pausable"]);
+
+        let functions: Vec<_> = inspection
+            .functions
+            .iter()
+            .map(|f| {
+                (
+                    f.signature.as_str(),
+                    f.declared_in.as_str(),
+                    f.overrides.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            functions,
+            [
+                (
+                    "transferOwnership(address)",
+                    "Vault",
+                    vec!["Ownable".to_string()]
+                ),
+                ("pause()", "Vault", vec!["Pausable".to_string()]),
+                ("paused_()", "Pausable", vec![]),
+                ("deposit()", "Vault", vec![]),
+            ]
+        );
+        assert_eq!(inspection.functions[1].modifiers, ["onlyOwner"]);
+
+        let variables: Vec<_> = inspection
+            .state_variables
+            .iter()
+            .map(|v| (v.name.as_str(), v.storage_index, v.declared_in.as_str()))
+            .collect();
+        assert_eq!(
+            variables,
+            [
+                ("owner", Some(0), "Ownable"),
+                ("paused", Some(1), "Pausable"),
+                ("FEE", None, "Vault"),
+                ("total", Some(2), "Vault"),
+            ]
+        );
+        assert_eq!(inspection.modifiers[1].signature, "whenNotPaused()");
+        assert_eq!(
+            inspection.events[0].signature,
+            "OwnershipTransferred(address,address)"
+        );
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -1,11 +1,12 @@
 use super::tools::AiTool;
+use crate::output::style::{errln, outln};
 use serde_json::{json, Map, Value};
 use std::fs;
 use std::path::Path;
 use toml;
 
 pub fn handle_add(target: Option<String>) {
-    println!("Detecting AI tools...\n");
+    outln!("Detecting AI tools...\n");
 
     let tools_to_configure = match &target {
         Some(target_id) => {
@@ -14,13 +15,13 @@ pub fn handle_add(target: Option<String>) {
                     if tool.is_installed() {
                         vec![tool]
                     } else {
-                        eprintln!("  [x] {} - not installed", tool.name());
+                        errln!("  [x] {} - not installed", tool.name());
                         std::process::exit(1);
                     }
                 }
                 None => {
-                    eprintln!("Unknown target: {}", target_id);
-                    eprintln!("Available targets: claude, cursor, windsurf, codex, gemini");
+                    errln!("Unknown target: {}", target_id);
+                    errln!("Available targets: claude, cursor, windsurf, codex, gemini");
                     std::process::exit(1);
                 }
             }
@@ -32,18 +33,18 @@ pub fn handle_add(target: Option<String>) {
             for tool in all_tools {
                 if installed.contains(tool) {
                     if let Some(path) = tool.config_path() {
-                        println!("  [+] {} ({})", tool.name(), path.display());
+                        outln!("  [+] {} ({})", tool.name(), path.display());
                     } else {
-                        println!("  [+] {}", tool.name());
+                        outln!("  [+] {}", tool.name());
                     }
                 } else {
-                    println!("  [x] {} - not installed", tool.name());
+                    outln!("  [x] {} - not installed", tool.name());
                 }
             }
 
             if installed.is_empty() {
-                eprintln!("\nNo supported AI tools detected.");
-                eprintln!("Supported tools: Claude Code, Cursor, Windsurf, OpenAI Codex, Gemini CLI");
+                errln!("\nNo supported AI tools detected.");
+                errln!("Supported tools: Claude Code, Cursor, Windsurf, OpenAI Codex, Gemini CLI");
                 std::process::exit(1);
             }
 
@@ -51,12 +52,12 @@ pub fn handle_add(target: Option<String>) {
         }
     };
 
-    println!("\nAdding Weasel MCP server...\n");
+    outln!("\nAdding Weasel MCP server...\n");
 
     let weasel_path = match std::env::current_exe() {
         Ok(path) => path,
         Err(e) => {
-            eprintln!("Failed to get weasel executable path: {}", e);
+            errln!("Failed to get weasel executable path: {}", e);
             std::process::exit(1);
         }
     };
@@ -66,22 +67,22 @@ pub fn handle_add(target: Option<String>) {
     for tool in &tools_to_configure {
         match add_to_tool(tool, &weasel_path) {
             Ok(()) => {
-                println!("  [+] {} - added", tool.name());
+                outln!("  [+] {} - added", tool.name());
                 success_count += 1;
             }
             Err(e) => {
-                eprintln!("  [x] {} - failed: {}", tool.name(), e);
+                errln!("  [x] {} - failed: {}", tool.name(), e);
             }
         }
     }
 
     if success_count > 0 {
-        println!("\nDone! Restart your AI tools to use Weasel.");
-        println!("\nAvailable commands in AI chat:");
-        println!("  - \"analyze this contract with weasel\"");
-        println!("  - \"run weasel on ./src\"");
+        outln!("\nDone! Restart your AI tools to use Weasel.");
+        outln!("\nAvailable commands in AI chat:");
+        outln!("  - \"analyze this contract with weasel\"");
+        outln!("  - \"run weasel on ./src\"");
     } else {
-        eprintln!("\nFailed to add Weasel to any AI tool.");
+        errln!("\nFailed to add Weasel to any AI tool.");
         std::process::exit(1);
     }
 }
@@ -1,9 +1,27 @@
-use crate::config::load_config;
+use super::schema::{
+    parse_args, response_budget, AnalyzeArgs, AnalyzeResponse, Delta, DetailLocation,
+    DetectorEntry, DetectorsArgs, DetectorsResponse, FindingDetail, FindingDetailsArgs,
+    FindingDetailsResponse, FindingEntry, ResponseFormat, SeverityCounts, ToolResult,
+    SCHEMA_VERSION,
+};
+use crate::config::{load_config, ConfigOverrides};
 use crate::core::engine::AnalysisEngine;
+use crate::core::error::WeaselError;
+use crate::models::severity::Severity;
+use crate::output::generate_fingerprint;
+use fnv::FnvHasher;
 use serde::Serialize;
-use serde_json::{json, Value};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// How many earlier `weasel_analyze` results are kept for `previous_token` lookups
+const RESULTS_CACHE_SIZE: usize = 16;
+
+/// Earlier `weasel_analyze` results, oldest first: results token -> fingerprint -> summary line
+static RESULTS_CACHE: Mutex<Vec<(String, BTreeMap<String, String>)>> = Mutex::new(Vec::new());
 
 #[derive(Debug, Serialize)]
 pub struct JsonRpcError {
@@ -14,263 +32,470 @@ pub struct JsonRpcError {
 }
 
 pub fn execute_analyze(arguments: &Value) -> Result<Value, JsonRpcError> {
-    let path = arguments
-        .get("path")
-        .and_then(|v| v.as_str())
-        .map(PathBuf::from);
-
-    // Validate path exists if provided
+    let args: AnalyzeArgs = parse_args(arguments)?;
+    let path = args.path.map(PathBuf::from);
     if let Some(ref p) = path {
-        if !p.exists() {
-            return Err(JsonRpcError {
-                code: -32602,
-                message: format!("Path not found: {}", p.display()),
-                data: None,
-            });
-        }
+        check_path(p)?;
     }
-
-    let severity = arguments
-        .get("severity")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string());
-
-    let exclude: Option<Vec<PathBuf>> = arguments
-        .get("exclude")
-        .and_then(|v| v.as_array())
-        .map(|arr| {
-            arr.iter()
-                .filter_map(|v| v.as_str().map(PathBuf::from))
-                .collect()
-        });
-
-    let exclude_detectors: Option<Vec<String>> = arguments
-        .get("exclude_detectors")
-        .and_then(|v| v.as_array())
-        .map(|arr| {
-            arr.iter()
-                .filter_map(|v| v.as_str().map(String::from))
-                .collect()
-        });
+    let format = args.response_format.unwrap_or(ResponseFormat::Compact);
 
     let scope = path.map(|p| vec![p]);
-    let config = load_config(scope, exclude, severity, None, None, None, exclude_detectors);
+    let config = load_config(ConfigOverrides {
+        scope,
+        exclude: args
+            .exclude
+            .map(|paths| paths.into_iter().map(PathBuf::from).collect()),
+        min_severity: args.severity,
+        exclude_detectors: args.exclude_detectors,
+        // A required_version mismatch must not take the server down; it is logged instead
+        no_version_check: Some(true),
+        ..Default::default()
+    })
+    .map_err(invalid_config)?;
 
     let mut engine = AnalysisEngine::new(&config);
     engine.register_built_in_detectors();
 
-    match engine.analyze() {
-        Ok(report) => {
-            // Count by severity
-            let mut counts: HashMap<String, usize> = HashMap::new();
-            for finding in &report.findings {
-                *counts
-                    .entry(format!("{:?}", finding.severity))
-                    .or_default() += finding.locations.len();
-            }
-
-            // Build compact output
-            let mut output = String::new();
-
-            // Summary line
-            if report.findings.is_empty() {
-                output.push_str("Found: 0 issues\n");
-            } else {
-                let mut parts = Vec::new();
-                for sev in &["High", "Medium", "Low", "Gas", "NC"] {
-                    if let Some(count) = counts.get(*sev) {
-                        if *count > 0 {
-                            parts.push(format!("{} {}", count, sev));
-                        }
-                    }
-                }
-                output.push_str(&format!("Found: {}\n\n", parts.join(", ")));
-
-                // Compact one-liner per finding
-                for finding in &report.findings {
-                    let sev_char = match format!("{:?}", finding.severity).as_str() {
-                        "High" => "H",
-                        "Medium" => "M",
-                        "Low" => "L",
-                        "Gas" => "G",
-                        "NC" => "NC",
-                        _ => "?",
-                    };
-
-                    let detector_id = &finding.detector_id;
-
-                    // Short description (first 60 chars)
-                    let short_desc: String = finding
-                        .description
-                        .chars()
-                        .take(60)
-                        .collect::<String>()
-                        .split('\n')
-                        .next()
-                        .unwrap_or("")
-                        .to_string();
-
-                    for location in &finding.locations {
-                        output.push_str(&format!(
-                            "[{}] {} | {}:{} | {}\n",
-                            sev_char,
-                            detector_id,
-                            location.file,
-                            location.line,
-                            short_desc
-                        ));
-                    }
-                }
-            }
-
-            Ok(json!({
-                "content": [
-                    {
-                        "type": "text",
-                        "text": output
-                    }
-                ]
-            }))
+    let report = engine.analyze().map_err(analysis_failed)?;
+
+    let mut counts = SeverityCounts::default();
+    let mut findings = Vec::new();
+    for finding in &report.findings {
+        counts.add(&finding.severity, finding.locations.len());
+
+        // Short description (first 60 chars)
+        let short_desc: String = finding
+            .description
+            .chars()
+            .take(60)
+            .collect::<String>()
+            .split('\n')
+            .next()
+            .unwrap_or("")
+            .to_string();
+
+        for location in &finding.locations {
+            let full = format == ResponseFormat::Full;
+            findings.push(FindingEntry {
+                detector: finding.detector_id.clone(),
+                severity: finding.severity.clone(),
+                file: location.file.clone(),
+                line: location.line,
+                message: location.message.clone().unwrap_or_else(|| short_desc.clone()),
+                fingerprint: generate_fingerprint(
+                    &finding.detector_id,
+                    &location.file,
+                    location.line,
+                    location.snippet.as_deref(),
+                ),
+                title: full.then(|| finding.title.clone()),
+                snippet: location.snippet.clone().filter(|_| full),
+            });
         }
-        Err(e) => Err(JsonRpcError {
-            code: -32000,
-            message: format!("Analysis failed: {}", e),
-            data: None,
-        }),
+    }
+
+    // The delta covers every finding, whatever the response budget keeps
+    let current: BTreeMap<String, String> = findings
+        .iter()
+        .map(|f| (f.fingerprint.clone(), f.summary_line()))
+        .collect();
+    let delta = args
+        .previous_token
+        .map(|previous_token| compute_delta(previous_token, &current));
+    let results_token = cache_results(current);
+
+    let mut response = AnalyzeResponse {
+        schema_version: SCHEMA_VERSION,
+        format,
+        counts,
+        findings,
+        truncated: None,
+        delta,
+        results_token,
+        results_cache_size: RESULTS_CACHE_SIZE,
+    };
+    response.fit(response_budget(args.max_response_bytes));
+    Ok(ToolResult::new(response).into_value())
+}
+
+fn check_path(path: &Path) -> Result<(), JsonRpcError> {
+    if path.exists() {
+        return Ok(());
+    }
+    Err(JsonRpcError {
+        code: -32602,
+        message: format!("Path not found: {}", path.display()),
+        data: None,
+    })
+}
+
+fn analysis_failed(e: WeaselError) -> JsonRpcError {
+    JsonRpcError {
+        code: -32000,
+        message: format!("Analysis failed: {}", e),
+        data: None,
+    }
+}
+
+fn invalid_config(e: WeaselError) -> JsonRpcError {
+    JsonRpcError {
+        code: -32000,
+        message: format!("Invalid configuration: {}", e),
+        data: None,
+    }
+}
+
+/// Store one analysis result and return its token. The token is derived from the findings, so
+/// re-running over an unchanged tree returns the same token.
+fn cache_results(findings: BTreeMap<String, String>) -> String {
+    let mut hasher = FnvHasher::default();
+    for fingerprint in findings.keys() {
+        fingerprint.hash(&mut hasher);
+    }
+    let token = format!("{:016x}", hasher.finish());
+
+    let mut cache = RESULTS_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    cache.retain(|(cached, _)| *cached != token);
+    if cache.len() >= RESULTS_CACHE_SIZE {
+        cache.remove(0);
+    }
+    cache.push((token.clone(), findings));
+    token
+}
+
+/// Findings added and resolved since the result stored under `previous_token`.
+fn compute_delta(previous_token: String, current: &BTreeMap<String, String>) -> Delta {
+    let cache = RESULTS_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    let Some((_, previous)) = cache.iter().find(|(token, _)| *token == previous_token) else {
+        return Delta {
+            previous_token,
+            known: false,
+            added: Vec::new(),
+            resolved: Vec::new(),
+        };
+    };
+
+    let lines = |from: &BTreeMap<String, String>, other: &BTreeMap<String, String>| {
+        let mut lines: Vec<String> = from
+            .iter()
+            .filter(|(fingerprint, _)| !other.contains_key(*fingerprint))
+            .map(|(_, line)| line.trim_end().to_string())
+            .collect();
+        lines.sort();
+        lines
+    };
+    Delta {
+        added: lines(current, previous),
+        resolved: lines(previous, current),
+        previous_token,
+        known: true,
     }
 }
 
 pub fn execute_finding_details(arguments: &Value) -> Result<Value, JsonRpcError> {
-    let detector = arguments
-        .get("detector")
-        .and_then(|v| v.as_str())
-        .ok_or_else(|| JsonRpcError {
-            code: -32602,
-            message: "Missing 'detector' parameter".to_string(),
-            data: None,
-        })?;
-
-    let path = arguments
-        .get("path")
-        .and_then(|v| v.as_str())
-        .map(PathBuf::from);
-
-    // Validate path exists if provided
+    let args: FindingDetailsArgs = parse_args(arguments)?;
+    let path = args.path.map(PathBuf::from);
     if let Some(ref p) = path {
-        if !p.exists() {
-            return Err(JsonRpcError {
-                code: -32602,
-                message: format!("Path not found: {}", p.display()),
-                data: None,
-            });
-        }
+        check_path(p)?;
     }
+    // Details are asked for one detector at a time, so snippets are included by default
+    let format = args.response_format.unwrap_or(ResponseFormat::Full);
+    let full = format == ResponseFormat::Full;
 
     let scope = path.map(|p| vec![p]);
-    let config = load_config(scope, None, None, None, None, None, None);
+    let config = load_config(ConfigOverrides {
+        scope,
+        explain: Some(full),
+        no_version_check: Some(true),
+        ..Default::default()
+    })
+    .map_err(invalid_config)?;
 
     let mut engine = AnalysisEngine::new(&config);
     engine.register_built_in_detectors();
 
-    match engine.analyze() {
-        Ok(report) => {
-            // Find matching finding by detector_id
-            let matching: Vec<_> = report
-                .findings
+    let report = engine.analyze().map_err(analysis_failed)?;
+
+    let findings = report
+        .findings
+        .iter()
+        .filter(|f| f.detector_id == args.detector)
+        .map(|finding| FindingDetail {
+            title: finding.title.clone(),
+            severity: finding.severity.clone(),
+            description: finding.description.clone(),
+            example: finding.example.clone().filter(|_| full),
+            locations: finding
+                .locations
                 .iter()
-                .filter(|f| f.detector_id == detector)
-                .collect();
-
-            if matching.is_empty() {
-                return Ok(json!({
-                    "content": [{
-                        "type": "text",
-                        "text": format!("No findings found for detector: {}", detector)
-                    }]
-                }));
-            }
-
-            let mut output = format!("# Finding Details: {}\n\n", detector);
-
-            for finding in matching {
-                output.push_str(&format!("## {}\n\n", finding.title));
-                output.push_str(&format!("**Severity:** {:?}\n\n", finding.severity));
-                output.push_str(&format!("**Description:** {}\n\n", finding.description));
-
-                if let Some(example) = &finding.example {
-                    output.push_str(&format!(
-                        "**Example:**\n```solidity\n{}\n```\n\n",
-                        example
-                    ));
-                }
-
-                output.push_str("### Locations\n\n");
-                for location in &finding.locations {
-                    output.push_str(&format!("**{}:{}**\n", location.file, location.line));
-                    if let Some(snippet) = &location.snippet {
-                        output.push_str(&format!("```solidity\n{}\n```\n\n", snippet.trim()));
-                    }
-                }
-            }
-
-            Ok(json!({
-                "content": [{
-                    "type": "text",
-                    "text": output
-                }]
-            }))
-        }
-        Err(e) => Err(JsonRpcError {
-            code: -32000,
-            message: format!("Analysis failed: {}", e),
-            data: None,
-        }),
-    }
+                .map(|location| DetailLocation {
+                    file: location.file.clone(),
+                    line: location.line,
+                    message: location.message.clone(),
+                    snippet: location.snippet.clone().filter(|_| full),
+                })
+                .collect(),
+        })
+        .collect();
+
+    let mut response = FindingDetailsResponse {
+        schema_version: SCHEMA_VERSION,
+        format,
+        detector: args.detector,
+        findings,
+        truncated: None,
+    };
+    response.fit(response_budget(args.max_response_bytes));
+    Ok(ToolResult::new(response).into_value())
 }
 
 pub fn execute_detectors(arguments: &Value) -> Result<Value, JsonRpcError> {
-    let severity_filter = arguments
-        .get("severity")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string());
+    let args: DetectorsArgs = parse_args(arguments)?;
+    let format = args.response_format.unwrap_or(ResponseFormat::Compact);
 
     // Create a temporary engine to get detector list
-    let config = load_config(None, None, severity_filter, None, None, None, None);
+    let config = load_config(ConfigOverrides {
+        min_severity: args.severity,
+        no_version_check: Some(true),
+        ..Default::default()
+    })
+    .map_err(invalid_config)?;
     let mut engine = AnalysisEngine::new(&config);
     engine.register_built_in_detectors();
 
-    let detectors = engine.get_detector_info();
+    let mut detectors: Vec<DetectorEntry> = engine
+        .get_detector_info()
+        .into_iter()
+        .filter_map(|d| {
+            Some(DetectorEntry {
+                severity: d.severity.parse::<Severity>().ok()?,
+                description: (format == ResponseFormat::Full).then_some(d.description),
+                id: d.id,
+                name: d.name,
+            })
+        })
+        .collect();
+    // Group by severity, highest first
+    detectors.sort_by_key(|d| std::cmp::Reverse(d.severity.as_value()));
+
+    let mut response = DetectorsResponse {
+        schema_version: SCHEMA_VERSION,
+        format,
+        total: detectors.len(),
+        detectors,
+        truncated: None,
+    };
+    response.fit(response_budget(args.max_response_bytes));
+    Ok(ToolResult::new(response).into_value())
+}
 
-    // Group by severity
-    let mut by_severity: HashMap<String, Vec<_>> = HashMap::new();
-    for detector in &detectors {
-        by_severity
-            .entry(detector.severity.clone())
-            .or_default()
-            .push(detector);
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::write_fixture_project;
+    use serde_json::json;
+    use std::fs;
 
-    // Compact format: ID + short name grouped by severity
-    let mut output = format!("Detectors: {}\n\n", detectors.len());
+    const VAULT: &str = r#"
+        pragma solidity ^0.8.0;
 
-    for severity in &["High", "Medium", "Low", "Gas", "NC"] {
-        if let Some(dets) = by_severity.get(*severity) {
-            output.push_str(&format!("[{}]\n", severity));
-            for d in dets {
-                output.push_str(&format!("  {}: {}\n", d.id, d.name));
-            }
-            output.push('\n');
+        contract Vault {
+            bool public paused;
+            uint256 public open;
         }
+    "#;
+
+    fn analyze_text(arguments: Value) -> String {
+        let result = execute_analyze(&arguments).unwrap();
+        result["content"][0]["text"].as_str().unwrap().to_string()
     }
 
-    output.push_str("Use weasel_finding_details with detector ID for full description.");
+    #[test]
+    fn test_analyze_delta_against_previous_token() {
+        let root = write_fixture_project(
+            "mcp-delta",
+            &[
+                ("foundry.toml", "[profile.default]\n"),
+                ("src/Vault.sol", VAULT),
+            ],
+        );
+        let path = root.join("src").display().to_string();
+
+        let first = analyze_text(json!({ "path": path, "severity": "Gas" }));
+        let token = first
+            .lines()
+            .find_map(|line| line.strip_prefix("Results token: "))
+            .unwrap()
+            .to_string();
+        assert!(!first.contains("Delta:"));
+
+        // Fix the finding on line 5 and introduce the same issue on line 6
+        fs::write(
+            root.join("src/Vault.sol"),
+            VAULT
+                .replace("bool public paused;", "uint256 public paused;")
+                .replace("uint256 public open;", "bool public open;"),
+        )
+        .unwrap();
+
+        let second = analyze_text(json!({
+            "path": path,
+            "severity": "Gas",
+            "previous_token": token
+        }));
+        let delta: Vec<String> = second
+            .split("Delta: ")
+            .nth(1)
+            .unwrap()
+            .lines()
+            .take_while(|line| !line.starts_with("Results token"))
+            .filter(|line| !line.is_empty())
+            .map(|line| line.split(" | ").take(2).collect::<Vec<_>>().join(" | "))
+            .collect();
+        assert_eq!(
+            delta,
+            [
+                "1 new, 1 resolved",
+                "New:",
+                "[G] bool-storage | src/Vault.sol:6",
+                "Resolved:",
+                "[G] bool-storage | src/Vault.sol:5",
+            ]
+        );
+
+        let unknown = analyze_text(json!({ "path": path, "previous_token": "missing" }));
+        assert!(unknown.contains("Delta: unknown previous_token 'missing'"));
+
+        let _ = fs::remove_dir_all(&root);
+    }
 
-    Ok(json!({
-        "content": [
-            {
-                "type": "text",
-                "text": output
-            }
-        ]
-    }))
+    fn bool_storage(structured: &Value) -> &Value {
+        structured["findings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|f| f["detector"] == "bool-storage")
+            .unwrap()
+    }
+
+    #[test]
+    fn test_analyze_response_formats() {
+        let root = write_fixture_project(
+            "mcp-formats",
+            &[
+                ("foundry.toml", "[profile.default]\n"),
+                ("src/Vault.sol", VAULT),
+            ],
+        );
+        let path = root.join("src").display().to_string();
+
+        let compact = execute_analyze(&json!({ "path": path, "severity": "Gas" })).unwrap();
+        let structured = &compact["structuredContent"];
+        assert_eq!(structured["schema_version"], SCHEMA_VERSION);
+        assert_eq!(structured["format"], "compact");
+        assert_eq!(structured["counts"]["gas"], 1);
+        let finding = bool_storage(structured);
+        assert_eq!(finding["severity"], "Gas");
+        assert_eq!(finding["line"], 5);
+        assert!(finding.get("snippet").is_none() && finding.get("title").is_none());
+        assert!(structured.get("truncated").is_none());
+        let text = compact["content"][0]["text"].as_str().unwrap();
+        assert!(text.contains("1 Gas\n"));
+        assert!(text.contains("\n[G] bool-storage | src/Vault.sol:5 | "));
+        assert!(text.contains(&format!(
+            "Results token: {}",
+            structured["results_token"].as_str().unwrap()
+        )));
+
+        let full = execute_analyze(&json!({
+            "path": path,
+            "severity": "Gas",
+            "response_format": "full"
+        }))
+        .unwrap();
+        let finding = bool_storage(&full["structuredContent"]);
+        assert_eq!(full["structuredContent"]["format"], "full");
+        assert!(finding["title"].is_string());
+        assert!(finding["snippet"]
+            .as_str()
+            .unwrap()
+            .contains("bool public paused"));
+        let text = full["content"][0]["text"].as_str().unwrap();
+        let snippet = text
+            .lines()
+            .skip_while(|line| !line.starts_with("[G] bool-storage"))
+            .nth(1);
+        assert_eq!(snippet, Some("    bool public paused"));
+
+        let truncated = execute_analyze(&json!({
+            "path": path,
+            "severity": "Gas",
+            "max_response_bytes": 0
+        }))
+        .unwrap();
+        let total = full["structuredContent"]["findings"].as_array().unwrap().len();
+        let structured = &truncated["structuredContent"];
+        assert_eq!(structured["truncated"]["shown"], 0);
+        assert_eq!(structured["truncated"]["total"], total);
+        assert_eq!(structured["findings"], json!([]));
+        assert_eq!(structured["counts"]["gas"], 1);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_finding_details_and_detectors_formats() {
+        let root = write_fixture_project(
+            "mcp-details-formats",
+            &[
+                ("foundry.toml", "[profile.default]\n"),
+                ("src/Vault.sol", VAULT),
+            ],
+        );
+        let path = root.join("src").display().to_string();
+
+        let full = execute_finding_details(&json!({ "detector": "bool-storage", "path": path }))
+            .unwrap();
+        let finding = &full["structuredContent"]["findings"][0];
+        assert_eq!(full["structuredContent"]["format"], "full");
+        assert_eq!(finding["locations"][0]["line"], 5);
+        assert!(finding["locations"][0]["snippet"].is_string());
+        assert!(finding["example"].is_string());
+
+        let compact = execute_finding_details(&json!({
+            "detector": "bool-storage",
+            "path": path,
+            "response_format": "compact"
+        }))
+        .unwrap();
+        let finding = &compact["structuredContent"]["findings"][0];
+        assert!(finding["locations"][0].get("snippet").is_none());
+        assert!(finding.get("example").is_none());
+
+        let none = execute_finding_details(&json!({ "detector": "missing", "path": path }))
+            .unwrap();
+        assert_eq!(none["structuredContent"]["findings"], json!([]));
+        assert_eq!(
+            none["content"][0]["text"],
+            "No findings found for detector: missing"
+        );
+
+        let compact = execute_detectors(&json!({ "severity": "High" })).unwrap();
+        let structured = &compact["structuredContent"];
+        assert_eq!(structured["schema_version"], SCHEMA_VERSION);
+        let detectors = structured["detectors"].as_array().unwrap();
+        assert_eq!(detectors.len(), structured["total"].as_u64().unwrap() as usize);
+        assert!(detectors
+            .iter()
+            .all(|d| d["severity"] == "High" && d.get("description").is_none()));
+
+        let full = execute_detectors(&json!({ "severity": "High", "response_format": "full" }))
+            .unwrap();
+        assert!(full["structuredContent"]["detectors"][0]["description"].is_string());
+
+        let invalid = execute_detectors(&json!({ "response_format": "verbose" }));
+        assert_eq!(invalid.unwrap_err().code, -32602);
+
+        let _ = fs::remove_dir_all(&root);
+    }
 }
@@ -1,5 +1,6 @@
 pub mod add;
 mod executors;
+mod schema;
 pub mod remove;
 pub mod serve;
 pub mod tools;
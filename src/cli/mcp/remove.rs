@@ -1,17 +1,18 @@
 use super::tools::AiTool;
+use crate::output::style::{errln, outln};
 use serde_json::Value;
 use std::fs;
 use toml;
 
 pub fn handle_remove(target: Option<String>) {
-    println!("Removing Weasel MCP server...\n");
+    outln!("Removing Weasel MCP server...\n");
 
     let tools_to_configure = match &target {
         Some(target_id) => match AiTool::from_id(target_id) {
             Some(tool) => vec![tool],
             None => {
-                eprintln!("Unknown target: {}", target_id);
-                eprintln!("Available targets: claude, cursor, windsurf, codex, gemini");
+                errln!("Unknown target: {}", target_id);
+                errln!("Available targets: claude, cursor, windsurf, codex, gemini");
                 std::process::exit(1);
             }
         },
@@ -19,7 +20,7 @@ pub fn handle_remove(target: Option<String>) {
     };
 
     if tools_to_configure.is_empty() {
-        println!("No AI tools with Weasel configuration found.");
+        outln!("No AI tools with Weasel configuration found.");
         return;
     }
 
@@ -29,22 +30,22 @@ pub fn handle_remove(target: Option<String>) {
         match remove_from_tool(tool) {
             Ok(removed) => {
                 if removed {
-                    println!("  [+] {} - removed", tool.name());
+                    outln!("  [+] {} - removed", tool.name());
                     success_count += 1;
                 } else {
-                    println!("  [-] {} - not configured", tool.name());
+                    outln!("  [-] {} - not configured", tool.name());
                 }
             }
             Err(e) => {
-                eprintln!("  [x] {} - failed: {}", tool.name(), e);
+                errln!("  [x] {} - failed: {}", tool.name(), e);
             }
         }
     }
 
     if success_count > 0 {
-        println!("\nDone! Restart your AI tools to apply changes.");
+        outln!("\nDone! Restart your AI tools to apply changes.");
     } else {
-        println!("\nNo Weasel configurations found to remove.");
+        outln!("\nNo Weasel configurations found to remove.");
     }
 }
 
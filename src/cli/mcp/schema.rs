@@ -0,0 +1,692 @@
+//! Typed arguments and responses of the MCP tools.
+//!
+//! Every tool result carries the same data twice: as `structuredContent`, a JSON object whose
+//! shape is fixed by the types below and versioned by `schema_version`, and as a text rendering
+//! of it for clients that only read `content`. Bump [`SCHEMA_VERSION`] whenever a field is
+//! renamed, removed or changes meaning; adding an optional field does not need a bump.
+
+use super::executors::JsonRpcError;
+use crate::models::severity::Severity;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::env;
+
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Response size budget when neither `max_response_bytes` nor the environment sets one
+pub const DEFAULT_MAX_RESPONSE_BYTES: usize = 100_000;
+
+/// Overrides [`DEFAULT_MAX_RESPONSE_BYTES`] for every call that doesn't pass `max_response_bytes`
+const MAX_RESPONSE_BYTES_ENV: &str = "WEASEL_MCP_MAX_RESPONSE_BYTES";
+
+/// Room kept for the `content`/`structuredContent` wrapper and for the truncation marker, whose
+/// counts are only known after fitting
+const TRUNCATION_MARKER_RESERVE: usize = 128;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResponseFormat {
+    /// One line per finding or detector
+    Compact,
+    /// Adds titles, snippets, descriptions and examples
+    Full,
+}
+
+/// Parse tool arguments, rejecting unknown keys and mistyped values.
+pub fn parse_args<T: DeserializeOwned>(arguments: &Value) -> Result<T, JsonRpcError> {
+    serde_json::from_value(arguments.clone()).map_err(|e| JsonRpcError {
+        code: -32602,
+        message: format!("Invalid arguments: {}", e),
+        data: None,
+    })
+}
+
+/// The response budget for a call: its `max_response_bytes`, then the environment, then the
+/// default.
+pub fn response_budget(requested: Option<usize>) -> usize {
+    requested
+        .or_else(|| {
+            env::var(MAX_RESPONSE_BYTES_ENV)
+                .ok()
+                .and_then(|v| v.trim().parse().ok())
+        })
+        .unwrap_or(DEFAULT_MAX_RESPONSE_BYTES)
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AnalyzeArgs {
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(default)]
+    pub severity: Option<String>,
+    #[serde(default)]
+    pub exclude: Option<Vec<String>>,
+    #[serde(default)]
+    pub exclude_detectors: Option<Vec<String>>,
+    #[serde(default)]
+    pub previous_token: Option<String>,
+    #[serde(default)]
+    pub response_format: Option<ResponseFormat>,
+    #[serde(default)]
+    pub max_response_bytes: Option<usize>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FindingDetailsArgs {
+    pub detector: String,
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(default)]
+    pub response_format: Option<ResponseFormat>,
+    #[serde(default)]
+    pub max_response_bytes: Option<usize>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DetectorsArgs {
+    #[serde(default)]
+    pub severity: Option<String>,
+    #[serde(default)]
+    pub response_format: Option<ResponseFormat>,
+    #[serde(default)]
+    pub max_response_bytes: Option<usize>,
+}
+
+/// A response that can be rendered as the text content of a tool result.
+pub trait ToolResponse: Serialize {
+    fn to_text(&self) -> String;
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TextContent {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub text: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolResult<T> {
+    pub content: Vec<TextContent>,
+    pub structured_content: T,
+}
+
+impl<T: ToolResponse> ToolResult<T> {
+    pub fn new(response: T) -> Self {
+        Self {
+            content: vec![TextContent {
+                kind: "text".to_string(),
+                text: response.to_text(),
+            }],
+            structured_content: response,
+        }
+    }
+
+    pub fn into_value(self) -> Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+}
+
+/// Serialized size of the tool result built from `response`.
+fn result_size<T: ToolResponse>(response: &T) -> usize {
+    let text = response.to_text();
+    serde_json::to_string(response).map_or(0, |json| json.len()) + escaped_len(&text)
+}
+
+fn escaped_len(text: &str) -> usize {
+    serde_json::to_string(text).map_or(0, |json| json.len())
+}
+
+/// Bytes one list entry adds to a result: its JSON plus its text rendering.
+fn entry_size<T: Serialize>(entry: &T, text: &str) -> usize {
+    serde_json::to_string(entry).map_or(0, |json| json.len() + 1) + escaped_len(text) - 2
+}
+
+/// Why a list was cut short, and where to get the rest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Truncation {
+    pub shown: usize,
+    pub total: usize,
+    pub max_response_bytes: usize,
+    pub hint: String,
+}
+
+impl Truncation {
+    fn to_text(&self, noun: &str) -> String {
+        format!(
+            "Truncated: showing {} of {} {} to stay under {} bytes. {}\n",
+            self.shown, self.total, noun, self.max_response_bytes, self.hint
+        )
+    }
+}
+
+/// Cut `items` to the longest prefix that keeps the result of `response` under `budget`.
+/// `take` moves the list out of the response and `put` puts the kept part back.
+fn truncate_to_budget<R, T>(
+    response: &mut R,
+    budget: usize,
+    hint: &str,
+    take: impl Fn(&mut R) -> Vec<T>,
+    put: impl Fn(&mut R, Vec<T>, Option<Truncation>),
+    entry_text: impl Fn(&T) -> String,
+) where
+    R: ToolResponse,
+    T: Serialize,
+{
+    if result_size(response) <= budget {
+        return;
+    }
+    let mut items = take(response);
+    let total = items.len();
+    let marker = Truncation {
+        shown: 0,
+        total,
+        max_response_bytes: budget,
+        hint: hint.to_string(),
+    };
+    put(response, Vec::new(), Some(marker.clone()));
+    let mut used = result_size(response) + TRUNCATION_MARKER_RESERVE;
+
+    let mut shown = 0;
+    for item in &items {
+        used += entry_size(item, &entry_text(item));
+        if used > budget {
+            break;
+        }
+        shown += 1;
+    }
+    items.truncate(shown);
+    put(response, items, Some(Truncation { shown, ..marker }));
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeverityCounts {
+    #[serde(default)]
+    pub critical: usize,
+    pub high: usize,
+    pub medium: usize,
+    pub low: usize,
+    pub gas: usize,
+    pub nc: usize,
+}
+
+impl SeverityCounts {
+    pub fn add(&mut self, severity: &Severity, count: usize) {
+        let slot = match severity {
+            Severity::Critical => &mut self.critical,
+            Severity::High => &mut self.high,
+            Severity::Medium => &mut self.medium,
+            Severity::Low => &mut self.low,
+            Severity::Gas => &mut self.gas,
+            Severity::NC => &mut self.nc,
+        };
+        *slot += count;
+    }
+
+    pub fn total(&self) -> usize {
+        self.critical + self.high + self.medium + self.low + self.gas + self.nc
+    }
+}
+
+/// One location of a finding in a `weasel_analyze` response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FindingEntry {
+    pub detector: String,
+    pub severity: Severity,
+    pub file: String,
+    pub line: usize,
+    pub message: String,
+    pub fingerprint: String,
+    /// Full format only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Full format only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<String>,
+}
+
+impl FindingEntry {
+    /// The compact one-line form, also used for delta listings.
+    pub fn summary_line(&self) -> String {
+        format!(
+            "[{}] {} | {}:{} | {}\n",
+            severity_tag(&self.severity),
+            self.detector,
+            self.file,
+            self.line,
+            self.message
+        )
+    }
+
+    fn to_text(&self) -> String {
+        let mut text = self.summary_line();
+        if let Some(snippet) = &self.snippet {
+            for line in snippet.trim().lines() {
+                text.push_str(&format!("    {}\n", line));
+            }
+        }
+        text
+    }
+}
+
+fn severity_tag(severity: &Severity) -> &'static str {
+    match severity {
+        Severity::Critical => "C",
+        Severity::High => "H",
+        Severity::Medium => "M",
+        Severity::Low => "L",
+        Severity::Gas => "G",
+        Severity::NC => "NC",
+    }
+}
+
+/// Findings added and resolved since an earlier `weasel_analyze` result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Delta {
+    pub previous_token: String,
+    /// `false` when the previous result has expired; the lists are then empty
+    pub known: bool,
+    pub added: Vec<String>,
+    pub resolved: Vec<String>,
+}
+
+impl Delta {
+    fn to_text(&self, cache_size: usize) -> String {
+        if !self.known {
+            return format!(
+                "Delta: unknown previous_token '{}' (results expire after {} analyses or a \
+                 server restart)\n",
+                self.previous_token, cache_size
+            );
+        }
+        let mut output = format!(
+            "Delta: {} new, {} resolved\n",
+            self.added.len(),
+            self.resolved.len()
+        );
+        for (title, lines) in [("New", &self.added), ("Resolved", &self.resolved)] {
+            if !lines.is_empty() {
+                output.push_str(&format!("\n{}:\n", title));
+                for line in lines {
+                    output.push_str(line);
+                    output.push('\n');
+                }
+            }
+        }
+        output
+    }
+}
+
+/// `weasel_analyze` response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalyzeResponse {
+    pub schema_version: u32,
+    pub format: ResponseFormat,
+    pub counts: SeverityCounts,
+    /// Sorted by severity, highest first; cut short when `truncated` is set
+    pub findings: Vec<FindingEntry>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub truncated: Option<Truncation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delta: Option<Delta>,
+    /// Pass back as `previous_token` to get a delta against this result
+    pub results_token: String,
+    /// How many results are kept for `previous_token` lookups
+    pub results_cache_size: usize,
+}
+
+impl AnalyzeResponse {
+    pub fn fit(&mut self, budget: usize) {
+        truncate_to_budget(
+            self,
+            budget,
+            "Call weasel_finding_details with a detector ID for its locations, or narrow the \
+             result with severity or exclude_detectors.",
+            |r| std::mem::take(&mut r.findings),
+            |r, findings, truncated| {
+                r.findings = findings;
+                r.truncated = truncated;
+            },
+            FindingEntry::to_text,
+        );
+    }
+}
+
+impl ToolResponse for AnalyzeResponse {
+    fn to_text(&self) -> String {
+        let mut output = String::new();
+        if self.counts.total() == 0 {
+            output.push_str("Found: 0 issues\n");
+        } else {
+            let counts = [
+                ("Critical", self.counts.critical),
+                ("High", self.counts.high),
+                ("Medium", self.counts.medium),
+                ("Low", self.counts.low),
+                ("Gas", self.counts.gas),
+                ("NC", self.counts.nc),
+            ];
+            let parts: Vec<String> = counts
+                .iter()
+                .filter(|(_, count)| *count > 0)
+                .map(|(severity, count)| format!("{} {}", count, severity))
+                .collect();
+            output.push_str(&format!("Found: {}\n\n", parts.join(", ")));
+            for finding in &self.findings {
+                output.push_str(&finding.to_text());
+            }
+        }
+        if let Some(truncated) = &self.truncated {
+            output.push('\n');
+            output.push_str(&truncated.to_text("findings"));
+        }
+        if let Some(delta) = &self.delta {
+            output.push('\n');
+            output.push_str(&delta.to_text(self.results_cache_size));
+        }
+        output.push_str(&format!("\nResults token: {}\n", self.results_token));
+        output
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetailLocation {
+    pub file: String,
+    pub line: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Full format only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<String>,
+}
+
+impl DetailLocation {
+    fn to_text(&self) -> String {
+        let mut text = format!("**{}:{}**\n", self.file, self.line);
+        if let Some(message) = &self.message {
+            text.push_str(&format!("{}\n", message));
+        }
+        if let Some(snippet) = &self.snippet {
+            text.push_str(&format!("```solidity\n{}\n```\n\n", snippet.trim()));
+        }
+        text
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FindingDetail {
+    pub title: String,
+    pub severity: Severity,
+    pub description: String,
+    /// Full format only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub example: Option<String>,
+    pub locations: Vec<DetailLocation>,
+}
+
+/// `weasel_finding_details` response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FindingDetailsResponse {
+    pub schema_version: u32,
+    pub format: ResponseFormat,
+    pub detector: String,
+    /// Empty when the detector reported nothing
+    pub findings: Vec<FindingDetail>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub truncated: Option<Truncation>,
+}
+
+impl FindingDetailsResponse {
+    /// Truncates the locations of the findings, in order.
+    pub fn fit(&mut self, budget: usize) {
+        let shape: Vec<usize> = self.findings.iter().map(|f| f.locations.len()).collect();
+        truncate_to_budget(
+            self,
+            budget,
+            "Narrow the analysis with path, or use response_format \"compact\" to drop snippets.",
+            |r| {
+                r.findings
+                    .iter_mut()
+                    .flat_map(|f| std::mem::take(&mut f.locations))
+                    .collect()
+            },
+            |r, locations, truncated| {
+                let mut locations = locations.into_iter();
+                for (finding, count) in r.findings.iter_mut().zip(&shape) {
+                    finding.locations = locations.by_ref().take(*count).collect();
+                }
+                r.truncated = truncated;
+            },
+            DetailLocation::to_text,
+        );
+    }
+}
+
+impl ToolResponse for FindingDetailsResponse {
+    fn to_text(&self) -> String {
+        if self.findings.is_empty() {
+            return format!("No findings found for detector: {}", self.detector);
+        }
+        let mut output = format!("# Finding Details: {}\n\n", self.detector);
+        for finding in &self.findings {
+            output.push_str(&format!("## {}\n\n", finding.title));
+            output.push_str(&format!("**Severity:** {:?}\n\n", finding.severity));
+            output.push_str(&format!("**Description:** {}\n\n", finding.description));
+            if let Some(example) = &finding.example {
+                output.push_str(&format!("**Example:**\n```solidity\n{}\n```\n\n", example));
+            }
+            output.push_str("### Locations\n\n");
+            for location in &finding.locations {
+                output.push_str(&location.to_text());
+            }
+        }
+        if let Some(truncated) = &self.truncated {
+            output.push('\n');
+            output.push_str(&truncated.to_text("locations"));
+        }
+        output
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectorEntry {
+    pub id: String,
+    pub name: String,
+    pub severity: Severity,
+    /// Full format only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl DetectorEntry {
+    fn to_text(&self) -> String {
+        let mut text = format!("  {}: {}\n", self.id, self.name);
+        if let Some(description) = &self.description {
+            text.push_str(&format!("    {}\n", description));
+        }
+        text
+    }
+}
+
+/// `weasel_detectors` response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectorsResponse {
+    pub schema_version: u32,
+    pub format: ResponseFormat,
+    pub total: usize,
+    /// Grouped by severity, highest first
+    pub detectors: Vec<DetectorEntry>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub truncated: Option<Truncation>,
+}
+
+impl DetectorsResponse {
+    pub fn fit(&mut self, budget: usize) {
+        truncate_to_budget(
+            self,
+            budget,
+            "Filter with severity, or use response_format \"compact\" to drop descriptions.",
+            |r| std::mem::take(&mut r.detectors),
+            |r, detectors, truncated| {
+                r.detectors = detectors;
+                r.truncated = truncated;
+            },
+            DetectorEntry::to_text,
+        );
+    }
+}
+
+impl ToolResponse for DetectorsResponse {
+    fn to_text(&self) -> String {
+        let mut output = format!("Detectors: {}\n\n", self.total);
+        let mut current = None;
+        for detector in &self.detectors {
+            if current != Some(&detector.severity) {
+                if current.is_some() {
+                    output.push('\n');
+                }
+                output.push_str(&format!("[{:?}]\n", detector.severity));
+                current = Some(&detector.severity);
+            }
+            output.push_str(&detector.to_text());
+        }
+        if current.is_some() {
+            output.push('\n');
+        }
+        if let Some(truncated) = &self.truncated {
+            output.push_str(&truncated.to_text("detectors"));
+        }
+        output.push_str("Use weasel_finding_details with detector ID for full description.");
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(line: usize) -> FindingEntry {
+        FindingEntry {
+            detector: "bool-storage".to_string(),
+            severity: Severity::Gas,
+            file: "src/Vault.sol".to_string(),
+            line,
+            message: "Using bools for storage incurs overhead".to_string(),
+            fingerprint: format!("{:016x}", line),
+            title: None,
+            snippet: None,
+        }
+    }
+
+    fn analyze_response(findings: usize) -> AnalyzeResponse {
+        let mut counts = SeverityCounts::default();
+        counts.add(&Severity::Gas, findings);
+        AnalyzeResponse {
+            schema_version: SCHEMA_VERSION,
+            format: ResponseFormat::Compact,
+            counts,
+            findings: (1..=findings).map(entry).collect(),
+            truncated: None,
+            delta: None,
+            results_token: "0123456789abcdef".to_string(),
+            results_cache_size: 16,
+        }
+    }
+
+    fn serialized_len<T: ToolResponse>(response: T) -> usize {
+        serde_json::to_string(&ToolResult::new(response))
+            .unwrap()
+            .len()
+    }
+
+    #[test]
+    fn test_analyze_response_fits_budget() {
+        let mut response = analyze_response(5_000);
+        assert!(serialized_len(response.clone()) > 20_000);
+
+        response.fit(20_000);
+        let truncated = response.truncated.clone().unwrap();
+        assert_eq!(truncated.total, 5_000);
+        assert_eq!(truncated.shown, response.findings.len());
+        assert!(truncated.shown > 0);
+        // Highest-severity findings come first, so the prefix is kept
+        assert_eq!(response.findings[0].line, 1);
+        assert_eq!(response.counts.gas, 5_000);
+
+        let text = response.to_text();
+        assert!(text.contains(&format!(
+            "Truncated: showing {} of 5000 findings to stay under 20000 bytes. Call \
+             weasel_finding_details",
+            truncated.shown
+        )));
+        let len = serialized_len(response);
+        assert!(len <= 20_000, "{} bytes", len);
+    }
+
+    #[test]
+    fn test_small_response_is_not_truncated() {
+        let mut response = analyze_response(3);
+        response.fit(DEFAULT_MAX_RESPONSE_BYTES);
+        assert!(response.truncated.is_none());
+        assert_eq!(response.findings.len(), 3);
+
+        let value = ToolResult::new(response).into_value();
+        assert_eq!(value["structuredContent"]["schema_version"], SCHEMA_VERSION);
+        assert!(value["structuredContent"].get("truncated").is_none());
+    }
+
+    #[test]
+    fn test_finding_details_truncates_locations_in_order() {
+        let location = |line| DetailLocation {
+            file: "src/Vault.sol".to_string(),
+            line,
+            message: None,
+            snippet: Some("bool public paused;".repeat(10)),
+        };
+        let finding = |lines: std::ops::Range<usize>| FindingDetail {
+            title: "Bool storage".to_string(),
+            severity: Severity::Gas,
+            description: "Use uint256".to_string(),
+            example: None,
+            locations: lines.map(location).collect(),
+        };
+        let mut response = FindingDetailsResponse {
+            schema_version: SCHEMA_VERSION,
+            format: ResponseFormat::Full,
+            detector: "bool-storage".to_string(),
+            findings: vec![finding(0..100), finding(100..200)],
+            truncated: None,
+        };
+
+        response.fit(10_000);
+        let shown = response.truncated.as_ref().unwrap().shown;
+        assert!(shown < 100);
+        assert_eq!(response.findings[0].locations.len(), shown);
+        assert!(response.findings[1].locations.is_empty());
+        assert!(serialized_len(response) <= 10_000);
+    }
+
+    #[test]
+    fn test_args_reject_unknown_and_mistyped_fields() {
+        let args: AnalyzeArgs =
+            parse_args(&serde_json::json!({ "response_format": "full" })).unwrap();
+        assert_eq!(args.response_format, Some(ResponseFormat::Full));
+
+        let unknown = parse_args::<AnalyzeArgs>(&serde_json::json!({ "paths": "src" }));
+        assert!(unknown
+            .unwrap_err()
+            .message
+            .contains("unknown field `paths`"));
+        let mistyped = parse_args::<AnalyzeArgs>(&serde_json::json!({ "exclude": "test" }));
+        assert_eq!(mistyped.unwrap_err().code, -32602);
+        let missing = parse_args::<FindingDetailsArgs>(&serde_json::json!({}));
+        assert!(missing
+            .unwrap_err()
+            .message
+            .contains("missing field `detector`"));
+    }
+}
@@ -1,4 +1,5 @@
 use super::executors::{execute_analyze, execute_detectors, execute_finding_details, JsonRpcError};
+use crate::models::Severity;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::io::{self, BufRead, Write};
@@ -200,11 +201,12 @@ fn handle_initialize() -> Result<Value, JsonRpcError> {
 }
 
 fn handle_tools_list() -> Result<Value, JsonRpcError> {
+    let severities: Vec<String> = Severity::ALL.iter().map(|s| s.to_string()).collect();
     Ok(json!({
         "tools": [
             {
                 "name": "weasel_analyze",
-                "description": "Run Weasel static analysis on Solidity smart contracts. Returns compact summary of all findings. Use weasel_finding_details to get full details for specific issues.",
+                "description": "Run Weasel static analysis on Solidity smart contracts. Returns a summary of all findings and a results token; structuredContent holds the same data as versioned JSON (schema_version). Pass the token of an earlier call as previous_token to also get the findings added and resolved since then. Use weasel_finding_details to get full details for specific issues.",
                 "inputSchema": {
                     "type": "object",
                     "properties": {
@@ -214,7 +216,7 @@ fn handle_tools_list() -> Result<Value, JsonRpcError> {
                         },
                         "severity": {
                             "type": "string",
-                            "enum": ["High", "Medium", "Low", "Gas", "NC"],
+                            "enum": severities,
                             "description": "Minimum severity level to report. NC includes all issues."
                         },
                         "exclude": {
@@ -226,6 +228,20 @@ fn handle_tools_list() -> Result<Value, JsonRpcError> {
                             "type": "array",
                             "items": {"type": "string"},
                             "description": "Detector IDs to exclude from analysis (e.g., ['floating-pragma', 'unused-import'])"
+                        },
+                        "previous_token": {
+                            "type": "string",
+                            "description": "Results token from an earlier weasel_analyze call. Adds a delta section listing new and resolved findings."
+                        },
+                        "response_format": {
+                            "type": "string",
+                            "enum": ["compact", "full"],
+                            "description": "compact (default): one line per finding. full: adds each finding's title and code snippet."
+                        },
+                        "max_response_bytes": {
+                            "type": "integer",
+                            "minimum": 0,
+                            "description": "Response size budget in bytes (default 100000, or WEASEL_MCP_MAX_RESPONSE_BYTES). Longer finding lists are cut and marked truncated."
                         }
                     },
                     "required": []
@@ -233,7 +249,7 @@ fn handle_tools_list() -> Result<Value, JsonRpcError> {
             },
             {
                 "name": "weasel_finding_details",
-                "description": "Get detailed information about a specific finding type, including description, code snippets, and fix suggestions.",
+                "description": "Get detailed information about a specific finding type, including description, code snippets, and fix suggestions. structuredContent holds the same data as versioned JSON (schema_version).",
                 "inputSchema": {
                     "type": "object",
                     "properties": {
@@ -244,6 +260,16 @@ fn handle_tools_list() -> Result<Value, JsonRpcError> {
                         "path": {
                             "type": "string",
                             "description": "Path that was analyzed (to retrieve cached results)"
+                        },
+                        "response_format": {
+                            "type": "string",
+                            "enum": ["compact", "full"],
+                            "description": "full (default): description, example and code snippets. compact: description and locations only."
+                        },
+                        "max_response_bytes": {
+                            "type": "integer",
+                            "minimum": 0,
+                            "description": "Response size budget in bytes (default 100000, or WEASEL_MCP_MAX_RESPONSE_BYTES). Longer location lists are cut and marked truncated."
                         }
                     },
                     "required": ["detector"]
@@ -251,14 +277,24 @@ fn handle_tools_list() -> Result<Value, JsonRpcError> {
             },
             {
                 "name": "weasel_detectors",
-                "description": "List all available Weasel detectors with their descriptions and severity levels.",
+                "description": "List all available Weasel detectors with their descriptions and severity levels. structuredContent holds the same data as versioned JSON (schema_version).",
                 "inputSchema": {
                     "type": "object",
                     "properties": {
                         "severity": {
                             "type": "string",
-                            "enum": ["High", "Medium", "Low", "Gas", "NC"],
+                            "enum": severities,
                             "description": "Filter detectors by severity level."
+                        },
+                        "response_format": {
+                            "type": "string",
+                            "enum": ["compact", "full"],
+                            "description": "compact (default): ID and name. full: adds each detector's description."
+                        },
+                        "max_response_bytes": {
+                            "type": "integer",
+                            "minimum": 0,
+                            "description": "Response size budget in bytes (default 100000, or WEASEL_MCP_MAX_RESPONSE_BYTES). Longer detector lists are cut and marked truncated."
                         }
                     },
                     "required": []
@@ -294,7 +330,10 @@ fn handle_tools_call(params: &Value) -> Result<Value, JsonRpcError> {
 
 #[cfg(test)]
 mod tests {
+    use super::super::schema::{parse_args, AnalyzeArgs, DetectorsArgs, FindingDetailsArgs};
     use super::*;
+    use serde_json::Map;
+    use std::collections::BTreeSet;
 
     fn make_request(method: &str, id: Option<i32>) -> JsonRpcRequest {
         JsonRpcRequest {
@@ -380,4 +419,76 @@ mod tests {
         // Should only have 2 responses (notification filtered out)
         assert_eq!(responses.len(), 2);
     }
+
+    type ParseArgs = fn(&Value) -> Result<(), JsonRpcError>;
+
+    /// A value of the type a tools/list property advertises.
+    fn sample_value(property: &Value) -> Value {
+        match property["type"].as_str() {
+            Some("string") => property["enum"]
+                .get(0)
+                .cloned()
+                .unwrap_or_else(|| json!("x")),
+            Some("array") => json!(["x"]),
+            Some("integer") => json!(1),
+            other => panic!("unexpected property type {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_input_schemas_match_tool_arguments() {
+        let result = handle_tools_list().unwrap();
+        for tool in result["tools"].as_array().unwrap() {
+            let name = tool["name"].as_str().unwrap();
+            let (fields, parse): (Value, ParseArgs) = match name {
+                "weasel_analyze" => (serde_json::to_value(AnalyzeArgs::default()).unwrap(), |a| {
+                    parse_args::<AnalyzeArgs>(a).map(drop)
+                }),
+                "weasel_finding_details" => (
+                    serde_json::to_value(FindingDetailsArgs::default()).unwrap(),
+                    |a| parse_args::<FindingDetailsArgs>(a).map(drop),
+                ),
+                "weasel_detectors" => (
+                    serde_json::to_value(DetectorsArgs::default()).unwrap(),
+                    |a| parse_args::<DetectorsArgs>(a).map(drop),
+                ),
+                _ => panic!("no argument type for {}", name),
+            };
+
+            let properties = tool["inputSchema"]["properties"].as_object().unwrap();
+            let advertised: BTreeSet<&String> = properties.keys().collect();
+            let accepted: BTreeSet<&String> = fields.as_object().unwrap().keys().collect();
+            assert_eq!(advertised, accepted, "{}", name);
+
+            let required: Vec<&str> = tool["inputSchema"]["required"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|r| r.as_str().unwrap())
+                .collect();
+            let minimal: Map<String, Value> = required
+                .iter()
+                .map(|key| (key.to_string(), sample_value(&properties[*key])))
+                .collect();
+            assert!(parse(&Value::Object(minimal.clone())).is_ok(), "{}", name);
+
+            // Each advertised property is accepted with a value of its advertised type
+            for (key, property) in properties {
+                let mut arguments = minimal.clone();
+                arguments.insert(key.clone(), sample_value(property));
+                assert!(parse(&Value::Object(arguments)).is_ok(), "{}.{}", name, key);
+            }
+            // And each required one is actually required
+            for key in required {
+                let mut arguments = minimal.clone();
+                arguments.remove(key);
+                assert!(
+                    parse(&Value::Object(arguments)).is_err(),
+                    "{}.{}",
+                    name,
+                    key
+                );
+            }
+        }
+    }
 }
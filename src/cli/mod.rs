@@ -1,4 +1,93 @@
+pub mod completions;
 pub mod detectors;
+pub mod graph;
 pub mod init;
+pub mod inspect;
 pub mod mcp;
+pub mod resolve;
 pub mod run;
+pub mod tui;
+pub mod verify;
+pub mod version;
+pub mod watch;
+
+use crate::config::{Config, ConfigOverrides};
+use crate::core::EXIT_ERROR;
+use crate::output::style::errln;
+use clap::{Parser, Subcommand};
+use clap_complete::Shell;
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(name = "weasel")]
+#[command(about = "Smart Contract Static Analysis Tool for Solidity")]
+#[command(version = crate::core::version())]
+#[command(disable_version_flag = true, arg_required_else_help = true)]
+pub struct Cli {
+    /// Print version
+    #[arg(short = 'V', long)]
+    pub version: bool,
+
+    /// With --version: print the version, git describe and detector ids as JSON
+    #[arg(long, requires = "version")]
+    pub json: bool,
+
+    /// ASCII-only, uncolored output on stdout and stderr (also WEASEL_PLAIN=1)
+    #[arg(long, global = true)]
+    pub plain: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Commands>,
+}
+
+#[derive(Subcommand)]
+pub enum Commands {
+    Init,
+    Run(Box<run::RunArgs>),
+    Detectors {
+        #[arg(short, long)]
+        severity: Option<String>,
+
+        #[arg(short, long)]
+        details: Option<String>,
+
+        /// Write one markdown file per detector plus an index.md into this directory
+        #[arg(long, value_name = "DIR")]
+        export_docs: Option<PathBuf>,
+    },
+    Mcp {
+        #[command(subcommand)]
+        command: mcp::McpCommands,
+    },
+    /// Check that a report still matches the tool state and input files recorded in a manifest
+    Verify {
+        /// Manifest written by `weasel run --emit-manifest`
+        #[arg(long, value_name = "MANIFEST_FILE")]
+        manifest: PathBuf,
+
+        /// Report the manifest was written for
+        #[arg(long, value_name = "REPORT_FILE")]
+        report: PathBuf,
+    },
+    /// Show a contract's inheritance, storage layout and effective external/public functions
+    Inspect(inspect::InspectArgs),
+    /// Trace how an import is resolved, or list every unresolved import in scope with `--all`
+    Resolve(resolve::ResolveArgs),
+    /// Print the file-level import graph of the scope as Graphviz DOT or JSON
+    Graph(graph::GraphArgs),
+    /// Print a shell completion script (bash, zsh, fish, powershell, elvish)
+    Completions {
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+    /// Print the weasel man page in roff format
+    Man,
+}
+
+/// Load the config for a command, exiting with `EXIT_ERROR` if it cannot be read or is invalid.
+pub fn load_config_or_exit(overrides: ConfigOverrides) -> Config {
+    crate::config::load_config(overrides).unwrap_or_else(|e| {
+        errln!("Error: {}", e);
+        std::process::exit(EXIT_ERROR);
+    })
+}
@@ -0,0 +1,204 @@
+use crate::cli::load_config_or_exit;
+use crate::config::ConfigOverrides;
+use crate::core::engine::AnalysisEngine;
+use crate::core::import_resolver::ImportTrace;
+use crate::output::style::{errln, outln};
+use crate::utils::path::{normalize_report_path, PathBase};
+use clap::Args;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+#[derive(Args, Debug, Default)]
+pub struct ResolveArgs {
+    /// Import string exactly as written in the `import` statement
+    #[arg(required_unless_present = "all", requires = "from")]
+    pub import: Option<String>,
+
+    /// File containing the import
+    #[arg(long, value_name = "FILE")]
+    pub from: Option<PathBuf>,
+
+    /// Resolve every import in every in-scope file and summarize the unresolved ones
+    #[arg(long, conflicts_with = "import")]
+    pub all: bool,
+
+    #[arg(short, long)]
+    pub scope: Option<Vec<PathBuf>>,
+
+    #[arg(short, long)]
+    pub exclude: Option<Vec<PathBuf>>,
+
+    #[arg(short, long, value_name = "PATH_TO_CONFIG")]
+    pub config: Option<PathBuf>,
+
+    #[arg(short, long)]
+    pub remappings: Option<Vec<String>>,
+}
+
+pub fn handle_resolve_command(args: ResolveArgs) {
+    let config = load_config_or_exit(ConfigOverrides {
+        scope: args.scope,
+        exclude: args.exclude,
+        remappings: args.remappings,
+        config_path: args.config,
+        ..ConfigOverrides::default()
+    });
+
+    let mut engine = AnalysisEngine::new(&config);
+    let scope = engine.configure_project();
+
+    let resolved = if args.all {
+        if let Err(e) = engine.load_scope(&scope) {
+            errln!("Error loading files: {}", e);
+            std::process::exit(1);
+        }
+        print_unresolved_summary(&engine)
+    } else {
+        let (Some(import), Some(from)) = (args.import, args.from) else {
+            unreachable!("clap requires an import and --from without --all");
+        };
+        let Some(resolver) = engine.context().get_import_resolver() else {
+            unreachable!("configure_project sets up the import resolver");
+        };
+        let trace = resolver.trace_import(&import, &from);
+        print_trace(&trace, engine.project_root());
+        trace.result.is_ok()
+    };
+
+    if !resolved {
+        std::process::exit(1);
+    }
+}
+
+fn print_trace(trace: &ImportTrace, project_root: &Path) {
+    outln!(
+        "Resolving \"{}\" from {}",
+        trace.import_path,
+        display_path(&trace.importing_file, project_root)
+    );
+    if trace.candidates.is_empty() {
+        outln!("  (no candidate paths)");
+    }
+    for candidate in &trace.candidates {
+        outln!(
+            "  {:<9} {:<40} {}",
+            if candidate.exists {
+                "[found]"
+            } else {
+                "[missing]"
+            },
+            candidate.source.to_string(),
+            display_path(&candidate.path, project_root)
+        );
+    }
+    match &trace.result {
+        Ok(path) => outln!("Resolved: {}", display_path(path, project_root)),
+        Err(e) => outln!("Unresolved: {}", e),
+    }
+}
+
+/// Print unresolved imports grouped by import path; returns true when everything resolved.
+fn print_unresolved_summary(engine: &AnalysisEngine) -> bool {
+    let root = engine.project_root();
+    let (checked, unresolved) = unresolved_imports(engine);
+
+    outln!(
+        "Checked {} imports in {} files: {} unresolved",
+        checked,
+        engine.context().files.len(),
+        unresolved.values().map(Vec::len).sum::<usize>()
+    );
+    for (import_path, files) in &unresolved {
+        outln!("\n\"{}\" ({} files)", import_path, files.len());
+        for file in files {
+            outln!("  - {}", display_path(file, root));
+        }
+    }
+
+    unresolved.is_empty()
+}
+
+/// Number of imports checked, and the importing files of each import path that failed.
+fn unresolved_imports(engine: &AnalysisEngine) -> (usize, BTreeMap<String, Vec<PathBuf>>) {
+    let mut unresolved: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+    let Some(resolver) = engine.context().get_import_resolver() else {
+        return (0, unresolved);
+    };
+
+    let mut checked = 0;
+    for file in &engine.context().files {
+        for import in &file.imports {
+            checked += 1;
+            let trace = resolver.trace_import(&import.import_path, &file.path);
+            if trace.result.is_err() {
+                unresolved
+                    .entry(trace.import_path)
+                    .or_default()
+                    .push(file.path.clone());
+            }
+        }
+    }
+    for files in unresolved.values_mut() {
+        files.sort();
+    }
+
+    (checked, unresolved)
+}
+
+/// `path` as reports print it: relative to the project root, absolute when outside of it.
+fn display_path(path: &Path, project_root: &Path) -> String {
+    let root = PathBase::Project.resolve_root(project_root);
+    normalize_report_path(&path.to_string_lossy(), root.as_deref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::utils::test_utils::write_fixture_project;
+
+    #[test]
+    fn test_unresolved_imports_grouped_by_path() {
+        let root = write_fixture_project(
+            "resolve-all",
+            &[
+                ("foundry.toml", "[profile.default]\n"),
+                ("remappings.txt", "@oz/=lib/oz/contracts/\n"),
+                ("lib/oz/contracts/Ownable.sol", "contract Ownable {}\n"),
+                (
+                    "src/Vault.sol",
+                    "import \"@oz/Ownable.sol\";\nimport \"./Math.sol\";\ncontract Vault {}\n",
+                ),
+                (
+                    "src/Token.sol",
+                    "import \"./Math.sol\";\nimport \"@solmate/ERC20.sol\";\ncontract Token {}\n",
+                ),
+            ],
+        );
+        let config = Config {
+            scope: vec![root.join("src")],
+            ..Config::default()
+        };
+        let mut engine = AnalysisEngine::new(&config);
+        let scope = engine.configure_project();
+        engine.load_scope(&scope).unwrap();
+
+        let (checked, unresolved) = unresolved_imports(&engine);
+        assert_eq!(checked, 4);
+        assert_eq!(
+            unresolved,
+            BTreeMap::from([
+                (
+                    "./Math.sol".to_string(),
+                    vec![root.join("src/Token.sol"), root.join("src/Vault.sol")]
+                ),
+                (
+                    "@solmate/ERC20.sol".to_string(),
+                    vec![root.join("src/Token.sol")]
+                ),
+            ])
+        );
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -1,45 +1,499 @@
-use crate::config::load_config;
+use crate::cli::{tui, watch};
+use crate::cli::load_config_or_exit;
+use crate::config::{Config, ConfigOverrides};
 use crate::core::engine::AnalysisEngine;
-use crate::output;
-use std::path::PathBuf;
-
-pub fn handle_run_command(
-    scope: Option<Vec<PathBuf>>,
-    exclude: Option<Vec<PathBuf>>,
-    min_severity: Option<String>,
-    format: Option<String>,
-    output: Option<PathBuf>,
-    config_path: Option<PathBuf>,
-    remappings: Option<Vec<String>>,
-    exclude_detectors: Option<Vec<String>>,
-) {
-    let config = load_config(
-        scope,
-        exclude,
-        min_severity,
-        format,
-        remappings,
-        config_path,
-        exclude_detectors,
-    );
+use crate::core::manifest::RunManifest;
+use crate::core::{EXIT_ERROR, EXIT_FINDINGS};
+use crate::models::{Category, Report, Severity, StreamedFinding};
+use crate::output::style::{self, errln, outln};
+use crate::output::{self, JsonlWriter, ReportFormat};
+use crate::utils::fsx::{write_atomic, AtomicFile, LockError, ProjectLock};
+use clap::Args;
+use std::env;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// How long a run waits for another weasel process on the same project, unless
+/// `WEASEL_LOCK_TIMEOUT` sets it in seconds
+const LOCK_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Args, Debug, Default)]
+pub struct RunArgs {
+    #[arg(short, long)]
+    pub scope: Option<Vec<PathBuf>>,
+
+    #[arg(short, long)]
+    pub exclude: Option<Vec<PathBuf>>,
+
+    /// Also walk paths matched by the project's .gitignore files
+    #[arg(long)]
+    pub no_gitignore: bool,
+
+    #[arg(short, long)]
+    pub min_severity: Option<String>,
+
+    #[arg(short, long)]
+    pub format: Option<String>,
+
+    /// Organize the markdown report by finding or by file
+    #[arg(long, value_name = "GROUPING")]
+    pub group_by: Option<String>,
+
+    #[arg(short, long, value_name = "REPORT_FILE_NAME")]
+    pub output: Option<PathBuf>,
+
+    #[arg(short, long, value_name = "PATH_TO_CONFIG")]
+    pub config: Option<PathBuf>,
+
+    /// Merge the [profile.<NAME>] section of the config file over its top-level settings
+    #[arg(long, value_name = "NAME")]
+    pub profile: Option<String>,
+
+    /// Only warn when the config's required_version does not match this weasel version
+    #[arg(long)]
+    pub no_version_check: bool,
+
+    #[arg(short, long)]
+    pub remappings: Option<Vec<String>>,
+
+    /// Only print the report, warnings and errors: no notes, summary or progress on stderr
+    #[arg(short, long)]
+    pub quiet: bool,
+
+    /// Also print the resolved remappings and the inheritance of every contract on stderr
+    #[arg(short, long, conflicts_with = "quiet")]
+    pub verbose: bool,
+
+    #[arg(short = 'x', long, value_name = "DETECTOR_ID")]
+    pub exclude_detectors: Option<Vec<String>>,
+
+    /// Base for file paths in the report: project, cwd or absolute
+    #[arg(long, value_name = "BASE")]
+    pub path_base: Option<String>,
+
+    /// Source of contract metadata: solang or solc (build artifacts or solc on PATH)
+    #[arg(long, value_name = "SOURCE")]
+    pub ast_source: Option<String>,
+
+    /// Re-parse files one at a time instead of keeping all sources in memory
+    #[arg(long)]
+    pub low_memory: bool,
+
+    /// Restore findings of files unchanged since the last cached run from .weasel-cache
+    #[arg(long)]
+    pub cache: bool,
+
+    /// Analyze every file from scratch even if the config enables the cache
+    #[arg(long, conflicts_with = "cache")]
+    pub no_cache: bool,
+
+    /// Append a table of in-scope contracts with nSLOC and function counts to the report
+    #[arg(long)]
+    pub scope_appendix: bool,
+
+    /// Include each detector's remediation example under its findings
+    #[arg(long)]
+    pub explain: bool,
+
+    /// Show this many source lines before and after each reported line
+    #[arg(long, value_name = "LINES")]
+    pub context_lines: Option<usize>,
+
+    /// Only report findings in this category (e.g. oracle, access-control); repeatable
+    #[arg(long, value_name = "CATEGORY")]
+    pub category: Option<Vec<String>>,
+
+    /// Only report findings inside this contract, still analyzing the whole scope; repeatable
+    #[arg(long, value_name = "NAME")]
+    pub contract: Option<Vec<String>>,
+
+    /// Report timestamp: now, fixed (from SOURCE_DATE_EPOCH) or none
+    #[arg(long, value_name = "MODE")]
+    pub timestamp: Option<String>,
+
+    /// Exit with code 1 if a finding of this severity or higher is reported (errors exit with 2)
+    #[arg(long, value_name = "SEVERITY")]
+    pub fail_on: Option<String>,
+
+    /// Replace file paths with pseudonyms and strip code from the report, for sharing externally
+    #[arg(long)]
+    pub redact: bool,
+
+    /// Write the pseudonym to file path mapping of a redacted report to this JSON file
+    #[arg(long, value_name = "MAP_FILE", requires = "redact")]
+    pub redact_map: Option<PathBuf>,
+
+    /// Browse the findings in a terminal UI after the analysis (needs the `tui` feature)
+    #[arg(long, conflicts_with = "redact")]
+    pub interactive: bool,
+
+    /// Keep running and re-analyze when a Solidity file in the scope changes, printing the
+    /// findings that appeared or disappeared; the report is only written with --output
+    #[arg(long, conflicts_with_all = ["interactive", "redact", "emit_manifest"])]
+    pub watch: bool,
+
+    /// Write the version, effective config, detectors, input file hashes and report hash to this JSON file
+    #[arg(long, value_name = "MANIFEST_FILE", requires = "output")]
+    pub emit_manifest: Option<PathBuf>,
+
+    /// Don't wait for another weasel process running on the same project; run without the
+    /// project lock and skip cache writes instead
+    #[arg(long)]
+    pub allow_concurrent: bool,
+}
+
+impl RunArgs {
+    fn config_overrides(&self) -> ConfigOverrides {
+        ConfigOverrides {
+            scope: self.scope.clone(),
+            exclude: self.exclude.clone(),
+            ignore_gitignore: self.no_gitignore.then_some(true),
+            min_severity: self.min_severity.clone(),
+            format: self.format.clone(),
+            group_by: self.group_by.clone(),
+            remappings: self.remappings.clone(),
+            config_path: self.config.clone(),
+            exclude_detectors: self.exclude_detectors.clone(),
+            path_base: self.path_base.clone(),
+            low_memory: self.low_memory.then_some(true),
+            cache: if self.no_cache { Some(false) } else { self.cache.then_some(true) },
+            ast_source: self.ast_source.clone(),
+            scope_appendix: self.scope_appendix.then_some(true),
+            explain: self.explain.then_some(true),
+            context_lines: self.context_lines,
+            profile: self.profile.clone(),
+            no_version_check: self.no_version_check.then_some(true),
+            quiet: self.quiet.then_some(true),
+            verbose: self.verbose.then_some(true),
+            timestamp: self.timestamp.clone(),
+            fail_on: self.fail_on.clone(),
+        }
+    }
+}
+
+pub fn handle_run_command(args: RunArgs) {
+    let config = load_config_or_exit(args.config_overrides());
+    let categories = args.category.as_deref().map(parse_categories);
+    if args.interactive {
+        if let Err(e) = tui::ensure_available() {
+            errln!("Error: {}", e);
+            std::process::exit(EXIT_ERROR);
+        }
+    }
 
     let mut engine = AnalysisEngine::new(&config);
     engine.register_built_in_detectors();
+    if let Some(contracts) = &args.contract {
+        engine.set_contract_filter(contracts.clone());
+    }
+    if args.watch {
+        watch(&mut engine, &config, &args, categories.as_deref());
+        return;
+    }
+    let lock = lock_project(&engine.detect_project_root(), args.allow_concurrent);
+    engine.set_cache_writable(lock.is_some());
+
+    // Redaction assigns pseudonyms over the whole report, so redacted JSONL is written at the end
+    if config.format == ReportFormat::Jsonl && !args.redact && !args.interactive {
+        let (written, report) = match stream_jsonl(&mut engine, args.output, categories.as_deref())
+        {
+            Ok(streamed) => streamed,
+            Err(e) => {
+                errln!("Error during analysis: {}", e);
+                std::process::exit(EXIT_ERROR);
+            }
+        };
+        if let (Some(manifest_path), Some(report_path)) = (&args.emit_manifest, written) {
+            emit_manifest(&engine, &report_path, manifest_path);
+        }
+        if let Some(threshold) = &config.fail_on {
+            report_fail_threshold(threshold, report.count_at_or_above(threshold));
+        }
+        return;
+    }
 
     match engine.analyze() {
-        Ok(report) => {
-            println!(
-                "Analysis complete: {} findings",
-                report.findings.iter().map(|f| f.locations.len()).sum::<usize>()
-            );
-            if let Err(e) = output::generate_report(&report, &config.format, output) {
-                eprintln!("Error generating report: {}", e);
-                std::process::exit(1);
+        Ok(mut report) => {
+            if let Some(categories) = &categories {
+                report.retain_categories(categories);
+            }
+            status(&config, &completion_summary(&report));
+            let failing = config
+                .fail_on
+                .as_ref()
+                .map(|threshold| (threshold, report.count_at_or_above(threshold)));
+            let report = if args.redact {
+                redact(&config, &report, args.redact_map.as_deref())
+            } else {
+                report
+            };
+            // The terminal UI takes over stdout, so an interactive run only writes report files
+            if !args.interactive || args.output.is_some() {
+                let written = match output::generate_report(
+                    &report,
+                    &config.format,
+                    config.group_by,
+                    args.output,
+                ) {
+                    Ok(written) => written,
+                    Err(e) => {
+                        errln!("Error generating report: {}", e);
+                        std::process::exit(EXIT_ERROR);
+                    }
+                };
+                if let Some(path) = &written {
+                    status(&config, &format!("Report saved: {}", path.display()));
+                }
+                if let (Some(manifest_path), Some(report_path)) = (&args.emit_manifest, written) {
+                    emit_manifest(&engine, &report_path, manifest_path);
+                }
+            }
+            if args.interactive {
+                // Browsing can take a while; other runs only need to wait for the analysis
+                drop(lock);
+                if let Err(e) = tui::run_interactive(report, engine.project_root()) {
+                    errln!("Error: {}", e);
+                    std::process::exit(EXIT_ERROR);
+                }
+            }
+            if let Some((threshold, count)) = failing {
+                report_fail_threshold(threshold, count);
             }
         }
         Err(e) => {
-            eprintln!("Error during analysis: {}", e);
-            std::process::exit(1);
+            errln!("Error during analysis: {}", e);
+            std::process::exit(EXIT_ERROR);
+        }
+    }
+}
+
+/// Analyze, then again on every change to the scope until Ctrl-C. Each run takes the project
+/// lock for its own duration, and failures are reported without ending the loop.
+fn watch(
+    engine: &mut AnalysisEngine,
+    config: &Config,
+    args: &RunArgs,
+    categories: Option<&[Category]>,
+) {
+    let stop = watch::stop_on_interrupt();
+    let mut previous: Option<Report> = None;
+    loop {
+        let lock = lock_project(&engine.detect_project_root(), args.allow_concurrent);
+        engine.set_cache_writable(lock.is_some());
+        match engine.analyze() {
+            Ok(mut report) => {
+                if let Some(categories) = categories {
+                    report.retain_categories(categories);
+                }
+                if let Some(previous) = &previous {
+                    watch::print_diff(&watch::diff_findings(previous, &report));
+                }
+                status(config, &completion_summary(&report));
+                if args.output.is_some() {
+                    match output::generate_report(
+                        &report,
+                        &config.format,
+                        config.group_by,
+                        args.output.clone(),
+                    ) {
+                        Ok(Some(path)) => {
+                            status(config, &format!("Report saved: {}", path.display()));
+                        }
+                        Ok(None) => {}
+                        Err(e) => errln!("Error generating report: {}", e),
+                    }
+                }
+                previous = Some(report);
+            }
+            Err(e) => errln!("Error during analysis: {}", e),
+        }
+        drop(lock);
+
+        match watch::wait_for_change(engine, &stop) {
+            Ok(true) => outln!("Change detected, re-analyzing"),
+            Ok(false) => break,
+            Err(e) => {
+                errln!("Error: {}", e);
+                std::process::exit(EXIT_ERROR);
+            }
+        }
+    }
+    outln!("Stopped watching");
+}
+
+/// Print a status line to stderr unless `--quiet`. Stdout only ever carries the report.
+fn status(config: &Config, message: &str) {
+    if !config.quiet {
+        errln!("{}", message);
+    }
+}
+
+/// `Analysis complete: N findings`, noting how many `weasel-disable` comments silenced.
+fn completion_summary(report: &Report) -> String {
+    let mut summary = format!(
+        "Analysis complete: {} findings",
+        report.findings.iter().map(|f| f.locations.len()).sum::<usize>()
+    );
+    if let Some(suppressed) = report
+        .metadata
+        .as_ref()
+        .and_then(|metadata| metadata.get("Suppressed Findings:"))
+    {
+        summary.push_str(&format!(" ({} suppressed inline)", suppressed));
+    }
+    summary
+}
+
+/// Print how many findings met the `--fail-on` severity, exiting with `EXIT_FINDINGS` if any did.
+fn report_fail_threshold(threshold: &Severity, count: usize) {
+    errln!("{} findings at or above {} (--fail-on)", count, threshold);
+    if count > 0 {
+        std::process::exit(EXIT_FINDINGS);
+    }
+}
+
+/// Run the analysis writing each file's findings to `output` (or stdout) as soon as it is
+/// traversed, followed by a summary line. Returns the path of the written file and the report.
+fn stream_jsonl(
+    engine: &mut AnalysisEngine,
+    output: Option<PathBuf>,
+    categories: Option<&[Category]>,
+) -> Result<(Option<PathBuf>, Report), String> {
+    let path = output.map(|path| path.with_extension("jsonl"));
+    let report = match &path {
+        Some(path) => {
+            let file = AtomicFile::create(path)
+                .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+            let (file, report) = stream_findings(engine, file, categories)?;
+            file.commit().map_err(|e| format!("Failed to write report: {}", e))?;
+            report
+        }
+        None => stream_findings(engine, style::json_stdout(), categories)?.1,
+    };
+
+    status(engine.config(), &completion_summary(&report));
+    if let Some(path) = &path {
+        status(
+            engine.config(),
+            &format!("Report saved: {}", path.display()),
+        );
+    }
+    Ok((path, report))
+}
+
+/// Stream the findings of each file to `out` as JSONL, returning the writer and the report.
+fn stream_findings<W: Write + Send>(
+    engine: &mut AnalysisEngine,
+    out: W,
+    categories: Option<&[Category]>,
+) -> Result<(W, Report), String> {
+    let writer = Mutex::new(JsonlWriter::new(out));
+    let write_error: Mutex<Option<io::Error>> = Mutex::new(None);
+    let on_file = |findings: &[StreamedFinding]| {
+        let findings: Vec<StreamedFinding> = findings
+            .iter()
+            .filter(|finding| categories.is_none_or(|c| c.contains(&finding.category)))
+            .cloned()
+            .collect();
+        if let Err(e) = writer.lock().unwrap().write_findings(&findings) {
+            write_error.lock().unwrap().get_or_insert(e);
+        }
+    };
+    let mut report = engine
+        .analyze_streaming(&on_file)
+        .map_err(|e| e.to_string())?;
+    if let Some(categories) = categories {
+        report.retain_categories(categories);
+    }
+
+    if let Some(e) = write_error.into_inner().unwrap() {
+        return Err(format!("Failed to write report: {}", e));
+    }
+    let out = writer
+        .into_inner()
+        .unwrap()
+        .finish()
+        .map_err(|e| format!("Failed to write report: {}", e))?;
+    Ok((out, report))
+}
+
+/// Take the project lock, waiting for another weasel process on the same project to finish.
+/// With `allow_concurrent` a held lock is skipped instead, and the run writes no cache.
+fn lock_project(root: &Path, allow_concurrent: bool) -> Option<ProjectLock> {
+    let wait = env::var("WEASEL_LOCK_TIMEOUT")
+        .ok()
+        .and_then(|secs| secs.trim().parse().ok())
+        .map_or(LOCK_TIMEOUT, Duration::from_secs);
+    let timeout = if allow_concurrent { Duration::ZERO } else { wait };
+    match ProjectLock::acquire(root, timeout) {
+        Ok(lock) => Some(lock),
+        Err(e @ LockError::Busy { .. }) if allow_concurrent => {
+            errln!("Note: {}; running without it (--allow-concurrent).", e);
+            None
+        }
+        Err(e @ LockError::Busy { .. }) => {
+            errln!(
+                "Error: {} after waiting {}s. Wait for it to finish, or pass --allow-concurrent.",
+                e,
+                wait.as_secs()
+            );
+            std::process::exit(EXIT_ERROR);
+        }
+        // A read-only checkout can't hold the lock, but can still be analyzed
+        Err(e @ LockError::Io(..)) => {
+            errln!("Warning: {}; running without the project lock.", e);
+            None
         }
     }
 }
+
+/// Parse `--category` values, exiting with the list of valid names on an unknown one.
+fn parse_categories(values: &[String]) -> Vec<Category> {
+    values
+        .iter()
+        .map(|value| {
+            value.parse().unwrap_or_else(|e| {
+                errln!("Error: {}", e);
+                std::process::exit(EXIT_ERROR);
+            })
+        })
+        .collect()
+}
+
+fn emit_manifest(engine: &AnalysisEngine, report_path: &Path, manifest_path: &Path) {
+    let result = RunManifest::from_engine(engine).and_then(|mut manifest| {
+        manifest.record_report(report_path)?;
+        manifest.write(manifest_path)
+    });
+    if let Err(e) = result {
+        errln!(
+            "Error writing manifest '{}': {}",
+            manifest_path.display(),
+            e
+        );
+        std::process::exit(EXIT_ERROR);
+    }
+    status(
+        engine.config(),
+        &format!("Manifest saved: {}", manifest_path.display()),
+    );
+}
+
+fn redact(config: &Config, report: &Report, map_path: Option<&Path>) -> Report {
+    let (redacted, mapping) = output::redact_report(report);
+
+    if let Some(path) = map_path {
+        let result = serde_json::to_vec_pretty(&mapping)
+            .map_err(Into::into)
+            .and_then(|json| write_atomic(path, json));
+        if let Err(e) = result {
+            errln!("Error writing redaction map '{}': {}", path.display(), e);
+            std::process::exit(EXIT_ERROR);
+        }
+        status(config, &format!("Redaction map saved: {}", path.display()));
+    }
+
+    redacted
+}
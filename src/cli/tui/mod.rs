@@ -0,0 +1,109 @@
+//! `weasel run --interactive`: browse the findings of an analysis in a terminal UI, open
+//! instances in `$EDITOR` and mark them as reviewed or suppressed. The browsing logic in
+//! [`state`] and the marks in [`triage`] are always built; the terminal layer needs the
+//! `tui` feature.
+
+pub mod state;
+#[cfg(feature = "tui")]
+mod terminal;
+pub mod triage;
+
+use crate::models::Report;
+use std::path::{Path, PathBuf};
+
+/// Whether this build has the terminal UI, with instructions to get it if not.
+pub fn ensure_available() -> Result<(), String> {
+    if cfg!(feature = "tui") {
+        Ok(())
+    } else {
+        Err(
+            "this weasel was built without the terminal UI; reinstall with \
+             `cargo install weasel --features tui` to use --interactive"
+                .to_string(),
+        )
+    }
+}
+
+/// Browse `report` until the user quits. Marks are read from and saved to the triage file
+/// in `project_root`.
+pub fn run_interactive(report: Report, project_root: &Path) -> Result<(), String> {
+    #[cfg(feature = "tui")]
+    {
+        let triage_path = project_root.join(triage::TRIAGE_FILE);
+        let triage = triage::Triage::load(&triage_path)?;
+        let state = state::BrowserState::new(report.findings, triage);
+        terminal::run(state, project_root, &triage_path)
+            .map_err(|e| format!("Terminal UI failed: {}", e))
+    }
+    #[cfg(not(feature = "tui"))]
+    {
+        let _ = (report, project_root);
+        ensure_available()
+    }
+}
+
+/// Path of a reported file: reports hold paths relative to the project root or the working
+/// directory, or absolute ones, depending on `path_base`.
+pub fn resolve_report_path(project_root: &Path, file: &str) -> PathBuf {
+    let path = Path::new(file);
+    if path.is_absolute() || path.exists() {
+        return path.to_path_buf();
+    }
+    project_root.join(path)
+}
+
+/// Program and arguments opening `file` at `line` with `editor` (`$VISUAL`/`$EDITOR`, which
+/// may carry its own arguments, like `code -w`). Editors that take `file:line` get that,
+/// everything else the `+line file` form of vi, Emacs and nano.
+pub fn editor_command(editor: &str, file: &Path, line: usize) -> Option<(String, Vec<String>)> {
+    let mut words = editor.split_whitespace().map(str::to_string);
+    let program = words.next()?;
+    let mut args: Vec<String> = words.collect();
+
+    let name = Path::new(&program)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let file = file.display();
+    match name.as_str() {
+        "code" | "code-insiders" | "codium" | "cursor" | "windsurf" => {
+            args.push("--goto".to_string());
+            args.push(format!("{}:{}", file, line));
+        }
+        "hx" | "helix" | "subl" | "zed" => args.push(format!("{}:{}", file, line)),
+        _ => {
+            args.push(format!("+{}", line));
+            args.push(file.to_string());
+        }
+    }
+    Some((program, args))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_editor_command() {
+        let file = Path::new("/work/src/Vault.sol");
+        assert_eq!(
+            editor_command("vim", file, 12),
+            Some((
+                "vim".to_string(),
+                vec!["+12".to_string(), "/work/src/Vault.sol".to_string()]
+            ))
+        );
+        assert_eq!(
+            editor_command("/usr/bin/code -w", file, 12),
+            Some((
+                "/usr/bin/code".to_string(),
+                vec![
+                    "-w".to_string(),
+                    "--goto".to_string(),
+                    "/work/src/Vault.sol:12".to_string()
+                ]
+            ))
+        );
+        assert_eq!(editor_command("  ", file, 12), None);
+    }
+}
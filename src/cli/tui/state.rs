@@ -0,0 +1,523 @@
+//! Browsing state of `weasel run --interactive`, independent of the terminal: filtering,
+//! navigation between findings and instances, and triage marks. Keys go in, and the
+//! returned [`Action`] tells the terminal layer what to do beyond redrawing.
+
+use crate::cli::tui::triage::{Mark, Triage};
+use crate::models::{Finding, Location, Severity};
+
+/// Keys the browser reacts to, decoupled from the terminal backend's key events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Up,
+    Down,
+    Tab,
+    BackTab,
+    Enter,
+    Esc,
+    Backspace,
+    /// Ctrl-C: quit from any mode
+    Interrupt,
+    Char(char),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    None,
+    Quit,
+    /// Open the file (as reported) at the line in an editor
+    Open {
+        file: String,
+        line: usize,
+    },
+    /// Triage marks changed and should be saved
+    MarksChanged,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mode {
+    Browse,
+    /// Editing the filter; Esc restores `previous`
+    Search {
+        previous: String,
+    },
+}
+
+/// One term of the filter query. `sev:`, `det:` and `file:` restrict one property, bare
+/// words match the detector id, the title or the file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Term {
+    Severity(Severity),
+    Detector(String),
+    File(String),
+    Text(String),
+}
+
+impl Term {
+    fn parse_query(query: &str) -> Vec<Term> {
+        query
+            .split_whitespace()
+            .map(|word| {
+                let lower = word.to_lowercase();
+                let (key, value) = lower.split_once(':').unwrap_or(("", lower.as_str()));
+                match key {
+                    "sev" | "severity" => match value.parse() {
+                        Ok(severity) => Term::Severity(severity),
+                        Err(_) => Term::Text(lower.clone()),
+                    },
+                    "det" | "detector" => Term::Detector(value.to_string()),
+                    "file" => Term::File(value.to_string()),
+                    _ => Term::Text(lower.clone()),
+                }
+            })
+            .collect()
+    }
+
+    fn matches(&self, finding: &Finding, location: &Location) -> bool {
+        match self {
+            Term::Severity(severity) => finding.severity == *severity,
+            Term::Detector(id) => finding.detector_id.to_lowercase().contains(id),
+            Term::File(file) => location.file.to_lowercase().contains(file),
+            Term::Text(text) => {
+                finding.detector_id.to_lowercase().contains(text)
+                    || finding.title.to_lowercase().contains(text)
+                    || location.file.to_lowercase().contains(text)
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct BrowserState {
+    /// Sorted by severity, then detector id
+    findings: Vec<Finding>,
+    triage: Triage,
+    query: String,
+    hide_suppressed: bool,
+    mode: Mode,
+    /// Findings with at least one instance passing the filter, with those instances
+    visible: Vec<(usize, Vec<usize>)>,
+    /// Index into `visible`
+    selected: usize,
+    /// Index into the selected finding's visible instances
+    instance: usize,
+    status: Option<String>,
+}
+
+impl BrowserState {
+    pub fn new(mut findings: Vec<Finding>, triage: Triage) -> Self {
+        findings.sort_by(|a, b| {
+            b.severity
+                .as_value()
+                .cmp(&a.severity.as_value())
+                .then_with(|| a.detector_id.cmp(&b.detector_id))
+        });
+        let mut state = Self {
+            findings,
+            triage,
+            query: String::new(),
+            hide_suppressed: false,
+            mode: Mode::Browse,
+            visible: Vec::new(),
+            selected: 0,
+            instance: 0,
+            status: None,
+        };
+        state.refilter();
+        state
+    }
+
+    pub fn handle_key(&mut self, key: Key) -> Action {
+        self.status = None;
+        if key == Key::Interrupt {
+            return Action::Quit;
+        }
+        match self.mode.clone() {
+            Mode::Search { previous } => {
+                match key {
+                    Key::Enter => self.mode = Mode::Browse,
+                    Key::Esc => {
+                        self.query = previous;
+                        self.mode = Mode::Browse;
+                        self.refilter();
+                    }
+                    Key::Backspace => {
+                        self.query.pop();
+                        self.refilter();
+                    }
+                    Key::Char(c) => {
+                        self.query.push(c);
+                        self.refilter();
+                    }
+                    _ => {}
+                }
+                Action::None
+            }
+            Mode::Browse => match key {
+                Key::Char('q') | Key::Esc => Action::Quit,
+                Key::Down | Key::Char('j') => {
+                    self.select_finding(self.selected + 1);
+                    Action::None
+                }
+                Key::Up | Key::Char('k') => {
+                    self.select_finding(self.selected.saturating_sub(1));
+                    Action::None
+                }
+                Key::Tab | Key::Char('n') => {
+                    self.next_instance();
+                    Action::None
+                }
+                Key::BackTab | Key::Char('p') => {
+                    self.previous_instance();
+                    Action::None
+                }
+                Key::Enter | Key::Char('e') => match self.current() {
+                    Some((_, location)) => Action::Open {
+                        file: location.file.clone(),
+                        line: location.line,
+                    },
+                    None => Action::None,
+                },
+                Key::Char('r') => self.toggle_mark(Mark::Reviewed),
+                Key::Char('s') => self.toggle_mark(Mark::Suppressed),
+                Key::Char('h') => {
+                    self.hide_suppressed = !self.hide_suppressed;
+                    self.refilter();
+                    Action::None
+                }
+                Key::Char('/') => {
+                    self.mode = Mode::Search {
+                        previous: self.query.clone(),
+                    };
+                    Action::None
+                }
+                Key::Char('c') => {
+                    self.query.clear();
+                    self.refilter();
+                    Action::None
+                }
+                _ => Action::None,
+            },
+        }
+    }
+
+    /// Visible findings with their number of visible instances, in display order.
+    pub fn visible_findings(&self) -> impl Iterator<Item = (&Finding, usize)> {
+        self.visible
+            .iter()
+            .map(|(index, instances)| (&self.findings[*index], instances.len()))
+    }
+
+    /// Index of the selected finding among `visible_findings`.
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn selected_finding(&self) -> Option<&Finding> {
+        let (index, _) = self.visible.get(self.selected)?;
+        Some(&self.findings[*index])
+    }
+
+    /// Visible instances of the selected finding with their marks, and the current one's index.
+    pub fn instances(&self) -> (Vec<(&Location, Option<Mark>)>, usize) {
+        let Some((index, instances)) = self.visible.get(self.selected) else {
+            return (Vec::new(), 0);
+        };
+        let finding = &self.findings[*index];
+        let instances = instances
+            .iter()
+            .map(|i| {
+                let location = &finding.locations[*i];
+                (location, self.triage.mark(&finding.detector_id, location))
+            })
+            .collect();
+        (instances, self.instance)
+    }
+
+    pub fn current(&self) -> Option<(&Finding, &Location)> {
+        let (index, instances) = self.visible.get(self.selected)?;
+        let finding = &self.findings[*index];
+        Some((finding, &finding.locations[*instances.get(self.instance)?]))
+    }
+
+    pub fn triage(&self) -> &Triage {
+        &self.triage
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    pub fn mode(&self) -> &Mode {
+        &self.mode
+    }
+
+    pub fn hide_suppressed(&self) -> bool {
+        self.hide_suppressed
+    }
+
+    pub fn status(&self) -> Option<&str> {
+        self.status.as_deref()
+    }
+
+    pub fn set_status(&mut self, status: impl Into<String>) {
+        self.status = Some(status.into());
+    }
+
+    fn select_finding(&mut self, index: usize) {
+        let index = index.min(self.visible.len().saturating_sub(1));
+        if index != self.selected {
+            self.selected = index;
+            self.instance = 0;
+        }
+    }
+
+    /// Next instance, continuing with the first instance of the next finding.
+    fn next_instance(&mut self) {
+        let Some((_, instances)) = self.visible.get(self.selected) else {
+            return;
+        };
+        if self.instance + 1 < instances.len() {
+            self.instance += 1;
+        } else if self.selected + 1 < self.visible.len() {
+            self.selected += 1;
+            self.instance = 0;
+        }
+    }
+
+    /// Previous instance, continuing with the last instance of the previous finding.
+    fn previous_instance(&mut self) {
+        if self.instance > 0 {
+            self.instance -= 1;
+        } else if self.selected > 0 {
+            self.selected -= 1;
+            self.instance = self.visible[self.selected].1.len() - 1;
+        }
+    }
+
+    fn toggle_mark(&mut self, mark: Mark) -> Action {
+        let Some((finding, location)) = self.current() else {
+            return Action::None;
+        };
+        let (detector_id, location) = (finding.detector_id.clone(), location.clone());
+        self.triage.toggle(&detector_id, &location, mark);
+        if self.hide_suppressed {
+            self.refilter();
+        }
+        Action::MarksChanged
+    }
+
+    /// Recompute the visible instances, keeping the selection on the same instance when it
+    /// is still visible.
+    fn refilter(&mut self) {
+        let previous = self
+            .visible
+            .get(self.selected)
+            .and_then(|(index, instances)| Some((*index, *instances.get(self.instance)?)));
+
+        let terms = Term::parse_query(&self.query);
+        self.visible = self
+            .findings
+            .iter()
+            .enumerate()
+            .filter_map(|(index, finding)| {
+                let instances: Vec<usize> = finding
+                    .locations
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, location)| {
+                        terms.iter().all(|term| term.matches(finding, location))
+                            && !(self.hide_suppressed
+                                && self.triage.mark(&finding.detector_id, location)
+                                    == Some(Mark::Suppressed))
+                    })
+                    .map(|(i, _)| i)
+                    .collect();
+                (!instances.is_empty()).then_some((index, instances))
+            })
+            .collect();
+
+        let restored = previous.and_then(|(finding, instance)| {
+            let selected = self.visible.iter().position(|(i, _)| *i == finding)?;
+            let position = self.visible[selected].1.iter().position(|i| *i >= instance);
+            Some((
+                selected,
+                position.unwrap_or(self.visible[selected].1.len() - 1),
+            ))
+        });
+        (self.selected, self.instance) = restored.unwrap_or_else(|| {
+            let selected = previous
+                .and_then(|(finding, _)| self.visible.iter().position(|(i, _)| *i > finding))
+                .unwrap_or(0);
+            (selected.min(self.visible.len().saturating_sub(1)), 0)
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Category;
+
+    fn location(file: &str, line: usize) -> Location {
+        Location {
+            file: file.to_string(),
+            line,
+            column: None,
+            line_end: None,
+            column_end: None,
+            snippet: Some(format!("line {}", line)),
+            message: None,
+            url: None,
+            context: None,
+        }
+    }
+
+    fn finding(detector_id: &str, severity: Severity, locations: Vec<Location>) -> Finding {
+        Finding {
+            detector_id: detector_id.to_string(),
+            severity,
+            category: Category::default(),
+            title: format!("{} title", detector_id),
+            description: String::new(),
+            example: None,
+            locations,
+        }
+    }
+
+    fn state() -> BrowserState {
+        BrowserState::new(
+            vec![
+                finding(
+                    "magic-numbers",
+                    Severity::NC,
+                    vec![location("src/Vault.sol", 3)],
+                ),
+                finding(
+                    "reentrancy",
+                    Severity::High,
+                    vec![location("src/Vault.sol", 10), location("src/Pool.sol", 20)],
+                ),
+                finding(
+                    "unsafe-downcast",
+                    Severity::Low,
+                    vec![location("src/Pool.sol", 30)],
+                ),
+            ],
+            Triage::default(),
+        )
+    }
+
+    fn ids(state: &BrowserState) -> Vec<&str> {
+        state
+            .visible_findings()
+            .map(|(f, _)| f.detector_id.as_str())
+            .collect()
+    }
+
+    fn current(state: &BrowserState) -> Option<(&str, usize)> {
+        state
+            .current()
+            .map(|(f, l)| (f.detector_id.as_str(), l.line))
+    }
+
+    #[test]
+    fn test_navigation_across_findings_and_instances() {
+        let mut state = state();
+        assert_eq!(
+            ids(&state),
+            ["reentrancy", "unsafe-downcast", "magic-numbers"]
+        );
+        assert_eq!(current(&state), Some(("reentrancy", 10)));
+
+        state.handle_key(Key::Tab);
+        assert_eq!(current(&state), Some(("reentrancy", 20)));
+        state.handle_key(Key::Tab);
+        assert_eq!(current(&state), Some(("unsafe-downcast", 30)));
+        state.handle_key(Key::BackTab);
+        assert_eq!(current(&state), Some(("reentrancy", 20)));
+
+        state.handle_key(Key::Down);
+        state.handle_key(Key::Down);
+        state.handle_key(Key::Down);
+        assert_eq!(current(&state), Some(("magic-numbers", 3)));
+        state.handle_key(Key::Char('k'));
+        assert_eq!(current(&state), Some(("unsafe-downcast", 30)));
+
+        assert_eq!(
+            state.handle_key(Key::Enter),
+            Action::Open {
+                file: "src/Pool.sol".to_string(),
+                line: 30
+            }
+        );
+        assert_eq!(state.handle_key(Key::Char('q')), Action::Quit);
+    }
+
+    #[test]
+    fn test_search_filters_by_severity_detector_and_file() {
+        let mut state = state();
+        state.handle_key(Key::Char('/'));
+        for c in "file:pool".chars() {
+            state.handle_key(Key::Char(c));
+        }
+        state.handle_key(Key::Enter);
+        assert_eq!(state.mode(), &Mode::Browse);
+        assert_eq!(ids(&state), ["reentrancy", "unsafe-downcast"]);
+        assert_eq!(current(&state), Some(("reentrancy", 20)));
+
+        state.handle_key(Key::Char('/'));
+        for c in " sev:low".chars() {
+            state.handle_key(Key::Char(c));
+        }
+        assert_eq!(ids(&state), ["unsafe-downcast"]);
+        state.handle_key(Key::Esc);
+        assert_eq!(state.query(), "file:pool");
+        assert_eq!(ids(&state), ["reentrancy", "unsafe-downcast"]);
+
+        state.handle_key(Key::Char('c'));
+        state.handle_key(Key::Char('/'));
+        for c in "magic".chars() {
+            state.handle_key(Key::Char(c));
+        }
+        assert_eq!(ids(&state), ["magic-numbers"]);
+        assert_eq!(current(&state), Some(("magic-numbers", 3)));
+        state.handle_key(Key::Backspace);
+        assert_eq!(state.query(), "magi");
+    }
+
+    #[test]
+    fn test_marks_and_hiding_suppressed() {
+        let mut state = state();
+        assert_eq!(state.handle_key(Key::Char('s')), Action::MarksChanged);
+        state.handle_key(Key::Tab);
+        assert_eq!(state.handle_key(Key::Char('r')), Action::MarksChanged);
+
+        let (instances, current) = state.instances();
+        let marks: Vec<_> = instances.iter().map(|(_, mark)| *mark).collect();
+        assert_eq!(marks, [Some(Mark::Suppressed), Some(Mark::Reviewed)]);
+        assert_eq!(current, 1);
+
+        state.handle_key(Key::Char('h'));
+        assert!(state.hide_suppressed());
+        let (instances, _) = state.instances();
+        assert_eq!(instances.len(), 1);
+        assert_eq!(current_line(&state), Some(20));
+
+        // Suppressing the last visible instance moves on to the next finding
+        state.handle_key(Key::Char('s'));
+        assert_eq!(ids(&state), ["unsafe-downcast", "magic-numbers"]);
+        assert_eq!(state.selected(), 0);
+        assert_eq!(state.triage().count(Mark::Suppressed), 2);
+
+        state.handle_key(Key::Char('h'));
+        assert_eq!(
+            ids(&state),
+            ["reentrancy", "unsafe-downcast", "magic-numbers"]
+        );
+        assert_eq!(state.handle_key(Key::Interrupt), Action::Quit);
+    }
+
+    fn current_line(state: &BrowserState) -> Option<usize> {
+        state.current().map(|(_, l)| l.line)
+    }
+}
@@ -0,0 +1,210 @@
+//! Terminal layer of the interactive mode: maps crossterm key events onto [`Key`]s, draws
+//! [`BrowserState`] with ratatui and carries out the returned [`Action`]s.
+
+use crate::cli::tui::state::{Action, BrowserState, Key, Mode};
+use crate::cli::tui::triage::Mark;
+use crate::cli::tui::{editor_command, resolve_report_path};
+use crate::models::Severity;
+use crate::output::style;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+const HELP: &str = "j/k finding  n/p instance  enter open  r reviewed  s suppressed  \
+                    h hide suppressed  / filter  c clear  q quit";
+
+pub fn run(mut state: BrowserState, project_root: &Path, triage_path: &Path) -> io::Result<()> {
+    let mut terminal = ratatui::try_init()?;
+    let result = event_loop(&mut terminal, &mut state, project_root, triage_path);
+    ratatui::try_restore()?;
+    result
+}
+
+fn event_loop(
+    terminal: &mut DefaultTerminal,
+    state: &mut BrowserState,
+    project_root: &Path,
+    triage_path: &Path,
+) -> io::Result<()> {
+    loop {
+        terminal.draw(|frame| draw(frame, state))?;
+        let Event::Key(key_event) = event::read()? else {
+            continue;
+        };
+        let Some(key) = map_key(key_event) else {
+            continue;
+        };
+
+        match state.handle_key(key) {
+            Action::None => {}
+            Action::Quit => return Ok(()),
+            Action::MarksChanged => {
+                if let Err(e) = state.triage().save(triage_path) {
+                    state.set_status(format!("Failed to save {}: {}", triage_path.display(), e));
+                }
+            }
+            Action::Open { file, line } => {
+                ratatui::try_restore()?;
+                let result = open_in_editor(&resolve_report_path(project_root, &file), line);
+                *terminal = ratatui::try_init()?;
+                if let Err(e) = result {
+                    state.set_status(e);
+                }
+            }
+        }
+    }
+}
+
+fn map_key(event: KeyEvent) -> Option<Key> {
+    if event.kind != KeyEventKind::Press {
+        return None;
+    }
+    Some(match event.code {
+        KeyCode::Char('c') if event.modifiers.contains(KeyModifiers::CONTROL) => Key::Interrupt,
+        KeyCode::Char(c) => Key::Char(c),
+        KeyCode::Up => Key::Up,
+        KeyCode::Down => Key::Down,
+        KeyCode::Tab => Key::Tab,
+        KeyCode::BackTab => Key::BackTab,
+        KeyCode::Enter => Key::Enter,
+        KeyCode::Esc => Key::Esc,
+        KeyCode::Backspace => Key::Backspace,
+        _ => return None,
+    })
+}
+
+fn open_in_editor(file: &Path, line: usize) -> Result<(), String> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    let (program, args) =
+        editor_command(&editor, file, line).ok_or("$EDITOR is empty".to_string())?;
+    let status = Command::new(&program)
+        .args(&args)
+        .status()
+        .map_err(|e| format!("Failed to start {}: {}", program, e))?;
+    if !status.success() {
+        return Err(format!("{} exited with {}", program, status));
+    }
+    Ok(())
+}
+
+fn severity_style(severity: &Severity) -> Style {
+    if style::is_plain() {
+        return Style::default();
+    }
+    let color = match severity {
+        Severity::Critical => Color::Magenta,
+        Severity::High => Color::Red,
+        Severity::Medium => Color::LightRed,
+        Severity::Low => Color::Yellow,
+        Severity::Gas => Color::Cyan,
+        Severity::NC => Color::Gray,
+    };
+    Style::default().fg(color)
+}
+
+fn draw(frame: &mut Frame, state: &BrowserState) {
+    let [main, footer] =
+        Layout::vertical([Constraint::Min(1), Constraint::Length(2)]).areas(frame.area());
+    let [left, right] =
+        Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)]).areas(main);
+
+    // Findings, with a header line before each severity
+    let mut items = Vec::new();
+    let mut selected_row = None;
+    let mut current_severity = None;
+    for (index, (finding, count)) in state.visible_findings().enumerate() {
+        if current_severity != Some(&finding.severity) {
+            current_severity = Some(&finding.severity);
+            items.push(ListItem::new(Line::from(Span::styled(
+                finding.severity.to_string(),
+                severity_style(&finding.severity).add_modifier(Modifier::BOLD),
+            ))));
+        }
+        if index == state.selected() {
+            selected_row = Some(items.len());
+        }
+        items.push(ListItem::new(format!("  {} ({})", finding.title, count)));
+    }
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(" Findings "))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    let mut list_state = ListState::default().with_selected(selected_row);
+    frame.render_stateful_widget(list, left, &mut list_state);
+
+    // Description and instances of the selected finding
+    let mut lines = Vec::new();
+    if let Some(finding) = state.selected_finding() {
+        lines.push(Line::from(Span::styled(
+            finding.title.clone(),
+            Style::default().add_modifier(Modifier::BOLD),
+        )));
+        lines.push(Line::from(vec![
+            Span::styled(
+                finding.severity.to_string(),
+                severity_style(&finding.severity),
+            ),
+            Span::raw(format!(" | {} | {}", finding.category, finding.detector_id)),
+        ]));
+        lines.push(Line::default());
+        lines.push(Line::from(finding.description.clone()));
+        lines.push(Line::default());
+
+        let (instances, current) = state.instances();
+        for (index, (location, mark)) in instances.iter().enumerate() {
+            let marker = if index == current { "> " } else { "  " };
+            let tag = match mark {
+                Some(Mark::Reviewed) => "[reviewed] ",
+                Some(Mark::Suppressed) => "[suppressed] ",
+                None => "",
+            };
+            let mut text = format!("{}{}{}:{}", marker, tag, location.file, location.line);
+            if let Some(message) = &location.message {
+                text.push_str(&format!(" - {}", message));
+            }
+            let line_style = if index == current {
+                Style::default().add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            lines.push(Line::from(Span::styled(text, line_style)));
+            if let Some(snippet) = &location.snippet {
+                lines.push(Line::from(format!("      {}", snippet.trim())));
+            }
+        }
+    } else {
+        lines.push(Line::from("No findings match the filter"));
+    }
+    let details = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title(" Details "))
+        .wrap(Wrap { trim: false });
+    frame.render_widget(details, right);
+
+    let filter_line = match state.mode() {
+        Mode::Search { .. } => format!("/{}", state.query()),
+        Mode::Browse if !state.query().is_empty() => format!("filter: {}", state.query()),
+        Mode::Browse => String::new(),
+    };
+    let summary = format!(
+        "{} reviewed, {} suppressed{}",
+        state.triage().count(Mark::Reviewed),
+        state.triage().count(Mark::Suppressed),
+        if state.hide_suppressed() {
+            " (hidden)"
+        } else {
+            ""
+        }
+    );
+    let footer_lines = vec![
+        Line::from(state.status().map_or(HELP.to_string(), str::to_string)),
+        Line::from(format!("{}  {}", summary, filter_line)),
+    ];
+    frame.render_widget(Paragraph::new(footer_lines), footer);
+}
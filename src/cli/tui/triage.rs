@@ -0,0 +1,151 @@
+//! Triage marks set in `weasel run --interactive`, kept in `.weasel-triage.json` at the
+//! project root. Instances are keyed by their SARIF fingerprint (detector, file, line and
+//! snippet), so a mark stays attached to its instance across runs while the line is unchanged.
+
+use crate::models::Location;
+use crate::output::generate_fingerprint;
+use crate::utils::fsx::write_atomic;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+pub const TRIAGE_FILE: &str = ".weasel-triage.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Mark {
+    /// Looked at and confirmed, or still to be fixed
+    Reviewed,
+    /// False positive or accepted risk
+    Suppressed,
+}
+
+/// A marked instance. Detector, file and line are informational, for reading the file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TriageEntry {
+    pub mark: Mark,
+    pub detector: String,
+    pub file: String,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Triage {
+    /// Instance fingerprint -> mark
+    marks: BTreeMap<String, TriageEntry>,
+}
+
+impl Triage {
+    /// Read a triage file; a missing file is an empty triage.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|e| format!("Failed to parse triage file '{}': {}", path.display(), e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!(
+                "Failed to read triage file '{}': {}",
+                path.display(),
+                e
+            )),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        write_atomic(path, json + "\n")
+    }
+
+    pub fn mark(&self, detector_id: &str, location: &Location) -> Option<Mark> {
+        self.marks
+            .get(&Self::fingerprint(detector_id, location))
+            .map(|entry| entry.mark)
+    }
+
+    /// Set `mark` on the instance, or clear it if the instance already has that mark.
+    pub fn toggle(&mut self, detector_id: &str, location: &Location, mark: Mark) {
+        let key = Self::fingerprint(detector_id, location);
+        if self.marks.get(&key).is_some_and(|entry| entry.mark == mark) {
+            self.marks.remove(&key);
+            return;
+        }
+        self.marks.insert(
+            key,
+            TriageEntry {
+                mark,
+                detector: detector_id.to_string(),
+                file: location.file.clone(),
+                line: location.line,
+            },
+        );
+    }
+
+    /// Number of instances with `mark`.
+    pub fn count(&self, mark: Mark) -> usize {
+        self.marks
+            .values()
+            .filter(|entry| entry.mark == mark)
+            .count()
+    }
+
+    fn fingerprint(detector_id: &str, location: &Location) -> String {
+        generate_fingerprint(
+            detector_id,
+            &location.file,
+            location.line,
+            location.snippet.as_deref(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::write_fixture_project;
+
+    #[test]
+    fn test_triage_toggle_and_persist() {
+        let location = Location {
+            file: "src/Vault.sol".to_string(),
+            line: 12,
+            column: None,
+            line_end: None,
+            column_end: None,
+            snippet: Some("owner = newOwner;".to_string()),
+            message: None,
+            url: None,
+            context: None,
+        };
+
+        let mut triage = Triage::default();
+        triage.toggle("missing-zero-address-validation", &location, Mark::Reviewed);
+        triage.toggle(
+            "missing-zero-address-validation",
+            &location,
+            Mark::Suppressed,
+        );
+        assert_eq!(
+            triage.mark("missing-zero-address-validation", &location),
+            Some(Mark::Suppressed)
+        );
+        assert_eq!(triage.mark("reentrancy", &location), None);
+
+        let root = write_fixture_project("triage", &[("foundry.toml", "")]);
+        let path = root.join(TRIAGE_FILE);
+        assert_eq!(Triage::load(&path).unwrap(), Triage::default());
+        triage.save(&path).unwrap();
+        let loaded = Triage::load(&path).unwrap();
+        assert_eq!(loaded, triage);
+        assert_eq!(loaded.count(Mark::Suppressed), 1);
+
+        triage.toggle(
+            "missing-zero-address-validation",
+            &location,
+            Mark::Suppressed,
+        );
+        assert_eq!(triage.count(Mark::Suppressed), 0);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
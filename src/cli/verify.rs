@@ -0,0 +1,34 @@
+use crate::core::manifest::RunManifest;
+use crate::output::style::{errln, outln};
+use std::path::Path;
+
+pub fn handle_verify_command(manifest_path: &Path, report_path: &Path) {
+    let manifest = match RunManifest::load(manifest_path) {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            errln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let changes = manifest.verify(report_path);
+    if changes.is_empty() {
+        outln!(
+            "Verified: weasel {}, {} detectors, {} files and {} match the manifest",
+            manifest.weasel_version,
+            manifest.detectors.len(),
+            manifest.files.len(),
+            report_path.display()
+        );
+        return;
+    }
+
+    outln!(
+        "Verification failed: {} change(s) since the manifest was written",
+        changes.len()
+    );
+    for change in &changes {
+        outln!("  - {}", change);
+    }
+    std::process::exit(1);
+}
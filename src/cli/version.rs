@@ -0,0 +1,102 @@
+use crate::config::Config;
+use crate::core::engine::AnalysisEngine;
+use crate::output::style::{errln, outln};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+/// Build identity printed by `weasel --version --json`, for CI to assert that every machine
+/// runs the same toolchain.
+#[derive(Debug, Serialize)]
+pub struct VersionInfo {
+    pub version: &'static str,
+    /// `git describe` of the source checkout, when built from one
+    pub git_describe: Option<&'static str>,
+    /// Built-in detector ids, sorted
+    pub detectors: Vec<&'static str>,
+    /// SHA-256 of the sorted `id:severity:version` lines of the built-in detectors
+    pub detectors_hash: String,
+}
+
+impl VersionInfo {
+    pub fn current() -> Self {
+        let config = Config::default();
+        let mut engine = AnalysisEngine::new(&config);
+        engine.register_built_in_detectors();
+
+        let mut detectors: Vec<_> = engine
+            .registry()
+            .get_all()
+            .iter()
+            .map(|d| (d.id(), d.severity(), d.version()))
+            .collect();
+        detectors.sort_by_key(|(id, _, _)| *id);
+
+        let mut hasher = Sha256::new();
+        for (id, severity, version) in &detectors {
+            hasher.update(format!("{}:{}:{}\n", id, severity, version));
+        }
+
+        Self {
+            version: crate::core::version(),
+            git_describe: crate::core::git_describe(),
+            detectors: detectors.into_iter().map(|(id, _, _)| id).collect(),
+            detectors_hash: format!("{:x}", hasher.finalize()),
+        }
+    }
+}
+
+pub fn handle_version_command(json: bool) {
+    if !json {
+        outln!("weasel {}", crate::core::version());
+        return;
+    }
+
+    match serde_json::to_string_pretty(&VersionInfo::current()) {
+        Ok(output) => outln!("{}", output),
+        Err(e) => {
+            errln!("Error serializing version info: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+
+    #[test]
+    fn test_version_info_lists_detectors() {
+        let info = VersionInfo::current();
+        assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+        assert!(info.detectors.contains(&"tx-origin-usage"));
+        assert!(info.detectors.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(info.detectors_hash.len(), 64);
+        assert_eq!(info.detectors_hash, VersionInfo::current().detectors_hash);
+
+        let json = serde_json::to_value(&info).unwrap();
+        assert!(json.get("git_describe").is_some());
+        assert_eq!(
+            json["detectors"].as_array().unwrap().len(),
+            info.detectors.len()
+        );
+    }
+
+    #[test]
+    fn test_git_describe_embedded_in_checkouts() {
+        let in_checkout = Command::new("git")
+            .arg("-C")
+            .arg(env!("CARGO_MANIFEST_DIR"))
+            .args(["rev-parse", "--git-dir"])
+            .output()
+            .is_ok_and(|output| output.status.success());
+        if !in_checkout {
+            assert_eq!(crate::core::git_describe(), None);
+            return;
+        }
+
+        let describe = crate::core::git_describe().expect("build.rs should embed git describe");
+        assert!(!describe.is_empty());
+        assert!(!describe.contains(char::is_whitespace));
+    }
+}
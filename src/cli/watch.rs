@@ -0,0 +1,214 @@
+//! `weasel run --watch`: re-run the analysis whenever a Solidity file in the scope is added,
+//! changed or removed, and print which findings appeared or disappeared.
+
+use crate::core::engine::AnalysisEngine;
+use crate::models::Report;
+use crate::output::style::{errln, outln};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// How often the scope is checked for changes
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// What identifies a finding across runs.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FindingKey {
+    pub detector_id: String,
+    pub file: String,
+    pub line: usize,
+    pub snippet: Option<String>,
+}
+
+impl fmt::Display for FindingKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}:{}", self.detector_id, self.file, self.line)
+    }
+}
+
+/// Findings present in only one of two reports, each sorted.
+#[derive(Debug, Default, PartialEq)]
+pub struct FindingsDiff {
+    pub added: Vec<FindingKey>,
+    pub removed: Vec<FindingKey>,
+}
+
+impl FindingsDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Compare the findings of two runs by detector id, file, line and snippet.
+pub fn diff_findings(previous: &Report, current: &Report) -> FindingsDiff {
+    let previous = finding_keys(previous);
+    let current = finding_keys(current);
+    FindingsDiff {
+        added: current.difference(&previous).cloned().collect(),
+        removed: previous.difference(&current).cloned().collect(),
+    }
+}
+
+fn finding_keys(report: &Report) -> BTreeSet<FindingKey> {
+    report
+        .findings
+        .iter()
+        .flat_map(|finding| {
+            finding.locations.iter().map(|location| FindingKey {
+                detector_id: finding.detector_id.clone(),
+                file: location.file.clone(),
+                line: location.line,
+                snippet: location.snippet.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Modification times of the files in the scope, to notice changes by polling.
+type Snapshot = HashMap<PathBuf, Option<SystemTime>>;
+
+fn snapshot(engine: &AnalysisEngine) -> Result<Snapshot, String> {
+    Ok(engine
+        .scope_files()?
+        .into_iter()
+        .map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+            (path, modified)
+        })
+        .collect())
+}
+
+/// Stops the watch loop on Ctrl-C. A second Ctrl-C exits right away, even mid-analysis.
+pub fn stop_on_interrupt() -> Arc<AtomicBool> {
+    let stop = Arc::new(AtomicBool::new(false));
+    let flag = Arc::clone(&stop);
+    let result = ctrlc::set_handler(move || {
+        if flag.swap(true, Ordering::SeqCst) {
+            std::process::exit(0);
+        }
+    });
+    if let Err(e) = result {
+        errln!("Warning: Failed to handle Ctrl-C: {}", e);
+    }
+    stop
+}
+
+/// Block until a file in the scope is added, changed or removed (true), or until `stop` is
+/// set (false).
+pub fn wait_for_change(engine: &AnalysisEngine, stop: &AtomicBool) -> Result<bool, String> {
+    let before = snapshot(engine)?;
+    outln!(
+        "Watching {} files for changes (Ctrl-C to stop)",
+        before.len()
+    );
+    while !stop.load(Ordering::SeqCst) {
+        thread::sleep(POLL_INTERVAL);
+        if snapshot(engine)? != before {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Print the findings that appeared (`+`) and disappeared (`-`) since the previous run.
+pub fn print_diff(diff: &FindingsDiff) {
+    if diff.is_empty() {
+        outln!("No new or resolved findings");
+        return;
+    }
+    for key in &diff.added {
+        outln!("+ {}", key);
+    }
+    for key in &diff.removed {
+        outln!("- {}", key);
+    }
+    outln!("{} new, {} resolved", diff.added.len(), diff.removed.len());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Finding, Location, Severity};
+
+    fn finding(detector_id: &str, locations: &[(&str, usize, &str)]) -> Finding {
+        Finding {
+            detector_id: detector_id.to_string(),
+            severity: Severity::Low,
+            category: Default::default(),
+            title: String::new(),
+            description: String::new(),
+            example: None,
+            locations: locations
+                .iter()
+                .map(|(file, line, snippet)| Location {
+                    file: file.to_string(),
+                    line: *line,
+                    column: None,
+                    line_end: None,
+                    column_end: None,
+                    snippet: Some(snippet.to_string()),
+                    message: None,
+                    url: None,
+                    context: None,
+                })
+                .collect(),
+        }
+    }
+
+    fn report(findings: Vec<Finding>) -> Report {
+        let mut report = Report::new();
+        report.findings = findings;
+        report
+    }
+
+    #[test]
+    fn test_diff_findings() {
+        let previous = report(vec![
+            finding("tx-origin", &[("src/Vault.sol", 8, "tx.origin == owner")]),
+            finding(
+                "magic-numbers",
+                &[
+                    ("src/Vault.sol", 12, "x * 100"),
+                    ("src/Pool.sol", 3, "y / 1e4"),
+                ],
+            ),
+        ]);
+        let current = report(vec![
+            finding(
+                "magic-numbers",
+                &[
+                    // Same line, edited code
+                    ("src/Vault.sol", 12, "x * 1000"),
+                    ("src/Pool.sol", 3, "y / 1e4"),
+                ],
+            ),
+            finding("unused-error", &[("src/Pool.sol", 9, "error Nope();")]),
+        ]);
+
+        let diff = diff_findings(&previous, &current);
+        let keys =
+            |keys: &[FindingKey]| -> Vec<String> { keys.iter().map(ToString::to_string).collect() };
+        assert_eq!(
+            keys(&diff.added),
+            [
+                "magic-numbers src/Vault.sol:12",
+                "unused-error src/Pool.sol:9"
+            ]
+        );
+        assert_eq!(
+            keys(&diff.removed),
+            [
+                "magic-numbers src/Vault.sol:12",
+                "tx-origin src/Vault.sol:8"
+            ]
+        );
+
+        assert!(diff_findings(&current, &current).is_empty());
+        assert_eq!(diff_findings(&report(Vec::new()), &current).added.len(), 3);
+    }
+}
@@ -0,0 +1,100 @@
+use crate::output::style::errln;
+use serde::{Deserialize, Serialize};
+
+/// Options for a single detector, read from a `[detectors.<id>]` table in `weasel.toml`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct DetectorSettings(toml::Table);
+
+impl DetectorSettings {
+    /// Read a non-negative integer option. Invalid values warn and return `None`.
+    pub fn get_usize(&self, detector_id: &str, key: &str) -> Option<usize> {
+        let value = self.0.get(key)?;
+        match value.as_integer().and_then(|v| usize::try_from(v).ok()) {
+            Some(v) => Some(v),
+            None => {
+                errln!(
+                    "Warning: [detectors.{}] {} must be a non-negative integer, got {}. Using default.",
+                    detector_id, key, value
+                );
+                None
+            }
+        }
+    }
+
+    /// Read a string option. Non-string values warn and return `None`.
+    pub fn get_str(&self, detector_id: &str, key: &str) -> Option<&str> {
+        let value = self.0.get(key)?;
+        match value.as_str() {
+            Some(v) => Some(v),
+            None => {
+                errln!(
+                    "Warning: [detectors.{}] {} must be a string, got {}. Using default.",
+                    detector_id,
+                    key,
+                    value
+                );
+                None
+            }
+        }
+    }
+
+    /// Read an array-of-strings option. Anything else warns and returns `None`.
+    pub fn get_str_list(&self, detector_id: &str, key: &str) -> Option<Vec<String>> {
+        let value = self.0.get(key)?;
+        let list = value.as_array().and_then(|items| {
+            items
+                .iter()
+                .map(|item| item.as_str().map(str::to_string))
+                .collect::<Option<Vec<_>>>()
+        });
+        if list.is_none() {
+            errln!(
+                "Warning: [detectors.{}] {} must be an array of strings, got {}. Using default.",
+                detector_id,
+                key,
+                value
+            );
+        }
+        list
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detector_settings_get_usize() {
+        let settings: DetectorSettings =
+            toml::from_str("max_depth = 3\nbad = -1\nname = \"x\"").unwrap();
+
+        assert_eq!(settings.get_usize("test", "max_depth"), Some(3));
+        assert_eq!(settings.get_usize("test", "bad"), None);
+        assert_eq!(settings.get_usize("test", "missing"), None);
+        assert_eq!(settings.get_usize("test", "name"), None);
+    }
+
+    #[test]
+    fn test_detector_settings_get_str() {
+        let settings: DetectorSettings = toml::from_str("prefix = \"_\"\nbad = 1").unwrap();
+
+        assert_eq!(settings.get_str("test", "prefix"), Some("_"));
+        assert_eq!(settings.get_str("test", "bad"), None);
+        assert_eq!(settings.get_str("test", "missing"), None);
+    }
+
+    #[test]
+    fn test_detector_settings_get_str_list() {
+        let settings: DetectorSettings =
+            toml::from_str("names = [\"a\", \"b\"]\nmixed = [\"a\", 1]\nbad = \"a\"").unwrap();
+
+        assert_eq!(
+            settings.get_str_list("test", "names"),
+            Some(vec!["a".to_string(), "b".to_string()])
+        );
+        assert_eq!(settings.get_str_list("test", "mixed"), None);
+        assert_eq!(settings.get_str_list("test", "bad"), None);
+        assert_eq!(settings.get_str_list("test", "missing"), None);
+    }
+}
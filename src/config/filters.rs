@@ -0,0 +1,239 @@
+use crate::models::{Location, Severity};
+use globset::{Glob, GlobMatcher};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Drops one detector's findings whose snippet matches a regex, read from a
+/// `[[finding_filters]]` entry in `weasel.toml`. The regex is compiled when the config is
+/// loaded, so an invalid pattern is a config error.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "RawFindingFilter", into = "RawFindingFilter")]
+pub struct FindingFilter {
+    pub detector: String,
+    pub snippet_regex: Regex,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawFindingFilter {
+    detector: String,
+    snippet_regex: String,
+}
+
+impl TryFrom<RawFindingFilter> for FindingFilter {
+    type Error = String;
+
+    fn try_from(raw: RawFindingFilter) -> Result<Self, Self::Error> {
+        let snippet_regex = Regex::new(&raw.snippet_regex).map_err(|e| {
+            format!(
+                "finding filter for '{}': invalid snippet_regex '{}': {}",
+                raw.detector, raw.snippet_regex, e
+            )
+        })?;
+        Ok(Self {
+            detector: raw.detector,
+            snippet_regex,
+        })
+    }
+}
+
+impl From<FindingFilter> for RawFindingFilter {
+    fn from(filter: FindingFilter) -> Self {
+        Self {
+            detector: filter.detector,
+            snippet_regex: filter.snippet_regex.as_str().to_string(),
+        }
+    }
+}
+
+impl FindingFilter {
+    pub fn new(detector: &str, snippet_regex: &str) -> Result<Self, String> {
+        Self::try_from(RawFindingFilter {
+            detector: detector.to_string(),
+            snippet_regex: snippet_regex.to_string(),
+        })
+    }
+
+    /// Whether this filter drops `location` reported by `detector_id`. Locations without a
+    /// snippet are kept.
+    pub fn matches(&self, detector_id: &str, location: &Location) -> bool {
+        self.detector == detector_id
+            && location
+                .snippet
+                .as_deref()
+                .is_some_and(|snippet| self.snippet_regex.is_match(snippet))
+    }
+}
+
+/// Severity floor and detector exclusions for the files matching a glob, read from a
+/// `[[path_rules]]` entry in `weasel.toml`. Only the most specific rule matching a file
+/// applies, see [`PathRule::for_file`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "RawPathRule", into = "RawPathRule")]
+pub struct PathRule {
+    pub path: String,
+    pub min_severity: Option<Severity>,
+    pub exclude_detectors: Vec<String>,
+    matcher: GlobMatcher,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawPathRule {
+    path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    min_severity: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    exclude_detectors: Vec<String>,
+}
+
+impl TryFrom<RawPathRule> for PathRule {
+    type Error = String;
+
+    fn try_from(raw: RawPathRule) -> Result<Self, Self::Error> {
+        let pattern = raw.path.strip_prefix("./").unwrap_or(&raw.path);
+        let pattern = pattern.trim_end_matches('/');
+        // A plain directory covers everything below it
+        let pattern = if is_literal(pattern) {
+            format!("{{{0},{0}/**}}", pattern)
+        } else {
+            pattern.to_string()
+        };
+        let matcher = Glob::new(&pattern)
+            .map_err(|e| format!("path rule '{}': invalid path glob: {}", raw.path, e))?
+            .compile_matcher();
+        let min_severity = raw
+            .min_severity
+            .map(|severity| severity.parse::<Severity>())
+            .transpose()
+            .map_err(|e| format!("path rule '{}': {}", raw.path, e))?;
+        Ok(Self {
+            path: raw.path,
+            min_severity,
+            exclude_detectors: raw.exclude_detectors,
+            matcher,
+        })
+    }
+}
+
+impl From<PathRule> for RawPathRule {
+    fn from(rule: PathRule) -> Self {
+        Self {
+            path: rule.path,
+            min_severity: rule.min_severity.map(|severity| severity.to_string()),
+            exclude_detectors: rule.exclude_detectors,
+        }
+    }
+}
+
+fn is_literal(pattern: &str) -> bool {
+    !pattern.contains(['*', '?', '[', '{'])
+}
+
+impl PathRule {
+    pub fn new(
+        path: &str,
+        min_severity: Option<Severity>,
+        exclude_detectors: &[&str],
+    ) -> Result<Self, String> {
+        Self::try_from(RawPathRule {
+            path: path.to_string(),
+            min_severity: min_severity.map(|severity| severity.to_string()),
+            exclude_detectors: exclude_detectors.iter().map(|id| id.to_string()).collect(),
+        })
+    }
+
+    /// The rule applying to `file` (project-relative, forward slashes): among the matching
+    /// rules, the one whose path has the longest literal prefix before any wildcard, so
+    /// `src/core/**` wins over `src/**`, and an exact file path over both. On a tie the rule
+    /// written last wins.
+    pub fn for_file<'a>(rules: &'a [PathRule], file: &str) -> Option<&'a PathRule> {
+        rules
+            .iter()
+            .enumerate()
+            .filter(|(_, rule)| rule.matcher.is_match(file))
+            .max_by_key(|(index, rule)| (rule.specificity(), *index))
+            .map(|(_, rule)| rule)
+    }
+
+    fn specificity(&self) -> usize {
+        let path = self.path.strip_prefix("./").unwrap_or(&self.path);
+        path.find(['*', '?', '[', '{']).unwrap_or(path.len())
+    }
+
+    /// Whether this rule drops a finding of `detector_id` with `severity`. Without a
+    /// `min_severity` the global floor, which already decided what ran, stays in effect.
+    pub fn drops(&self, detector_id: &str, severity: &Severity) -> bool {
+        self.min_severity
+            .as_ref()
+            .is_some_and(|floor| severity.as_value() < floor.as_value())
+            || self.exclude_detectors.iter().any(|id| id == detector_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::Config;
+
+    #[test]
+    fn test_invalid_snippet_regex_names_the_entry() {
+        let content = r#"
+            [[finding_filters]]
+            detector = "magic-numbers"
+            snippet_regex = "10_?000"
+
+            [[finding_filters]]
+            detector = "line-length"
+            snippet_regex = "(unclosed"
+        "#;
+
+        let err = toml::from_str::<Config>(content).unwrap_err().to_string();
+        assert!(err.contains("finding filter for 'line-length'"), "{}", err);
+        assert!(err.contains("invalid snippet_regex '(unclosed'"), "{}", err);
+    }
+
+    #[test]
+    fn test_most_specific_path_rule_wins() {
+        use super::PathRule;
+        use crate::models::Severity;
+
+        let rules = vec![
+            PathRule::new("src/core/**", Some(Severity::NC), &[]).unwrap(),
+            PathRule::new("src/**", Some(Severity::Medium), &["magic-numbers"]).unwrap(),
+            PathRule::new("./src/periphery", Some(Severity::Medium), &[]).unwrap(),
+            PathRule::new("src/core/Vault.sol", None, &["reentrancy"]).unwrap(),
+        ];
+        let rule = |file: &str| PathRule::for_file(&rules, file).map(|rule| rule.path.as_str());
+
+        assert_eq!(rule("src/core/Pool.sol"), Some("src/core/**"));
+        assert_eq!(rule("src/core/Vault.sol"), Some("src/core/Vault.sol"));
+        assert_eq!(rule("src/periphery/Router.sol"), Some("./src/periphery"));
+        assert_eq!(rule("src/Token.sol"), Some("src/**"));
+        assert_eq!(rule("contracts/Token.sol"), None);
+
+        // A Gas finding is kept in core and dropped in periphery
+        let core = PathRule::for_file(&rules, "src/core/Pool.sol").unwrap();
+        let periphery = PathRule::for_file(&rules, "src/periphery/Router.sol").unwrap();
+        assert!(!core.drops("unsafe-array-access", &Severity::Gas));
+        assert!(periphery.drops("unsafe-array-access", &Severity::Gas));
+        assert!(!periphery.drops("unsafe-array-access", &Severity::High));
+
+        // Only the winning rule applies: src/** excludes magic-numbers, src/core/** does not
+        assert!(!core.drops("magic-numbers", &Severity::NC));
+        let vault = PathRule::for_file(&rules, "src/core/Vault.sol").unwrap();
+        assert!(vault.drops("reentrancy", &Severity::High));
+        assert!(!vault.drops("magic-numbers", &Severity::NC));
+
+        let content = r#"
+            [[path_rules]]
+            path = "src/[core"
+            min_severity = "medium"
+        "#;
+        let err = toml::from_str::<Config>(content).unwrap_err().to_string();
+        assert!(
+            err.contains("path rule 'src/[core': invalid path glob"),
+            "{}",
+            err
+        );
+    }
+}
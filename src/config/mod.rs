@@ -1,36 +1,73 @@
+mod detectors;
+mod filters;
 mod protocol;
+mod provenance;
+mod report;
 
+pub use detectors::DetectorSettings;
+pub use filters::{FindingFilter, PathRule};
 pub use protocol::ProtocolConfig;
+pub use provenance::{ConfigProvenance, OverriddenValue, ValueSource};
+pub use report::ReportSettings;
 
 use crate::models::Severity;
-use crate::output::ReportFormat;
-use serde::Deserialize;
+use crate::output::style::{errln, outln};
+use crate::output::{GroupBy, ReportFormat, TimestampMode, Verbosity};
+use crate::core::error::WeaselError;
+use crate::core::solc_ast::AstSource;
+use crate::utils::path::PathBase;
+use crate::utils::source_link;
+use crate::utils::version;
+use provenance::display_paths;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::env;
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
 pub const DEFAULT_CONFIG_CONTENT: &str = r#"# weasel.toml
 
+# Precedence: command-line flags > WEASEL_* environment variables > this file > defaults.
+# Supported variables: WEASEL_SCOPE and WEASEL_EXCLUDE (paths separated like PATH,
+# e.g. "src:contracts"), WEASEL_MIN_SEVERITY, WEASEL_FORMAT, WEASEL_FAIL_ON, WEASEL_REMAPPINGS
+# (comma-separated, e.g. "@openzeppelin/=lib/openzeppelin-contracts/,@solmate/=lib/solmate/src/")
+# and WEASEL_PROFILE (see [profile.<name>] below).
+# Invalid values are reported and ignored.
+
 # Paths to include in the analysis.
+# Entries with `*`, `?`, `[` or `{` are glob patterns, e.g. "src/**/*.sol".
 # If omitted, it defaults to ["src"]
 # scope = ["src"]
 
 # Paths to exclude from the analysis.
-# These can be directories (including subdirectories) or specific files.
+# These can be directories (including subdirectories), specific files or glob patterns:
+# "**/mocks/**" matches at any depth, "src/**/test/*.sol" only files directly in a test dir.
 # If omitted, it defaults to [] (no exclusions)
 # exclude = ["lib", "test"]
 
+# Directories in scope are walked without the paths matched by the project's .gitignore
+# files and without its build output (Foundry `out/` and `cache/`, Hardhat `artifacts/`,
+# `node_modules/`). Set to true to still walk paths matched by .gitignore.
+# ignore_gitignore = false
+
 # Minimum severity level of detectors to *run* during analysis.
 # Only detectors with this severity or higher will be executed.
-# Options: "High", "Medium", "Low", "Gas", "NC" (case-insensitive)
+# Options: "Critical", "High", "Medium", "Low", "Gas", "NC" (case-insensitive)
 # If omitted, it defaults to "NC" (run all detectors).
 # min_severity = "NC"
 
 # Output format for the report.
-# Options: "json", "md" (or "markdown")
+# Options: "json", "md" (or "markdown"), "sarif", "jsonl", "html"
 # If omitted, it defaults to "md".
 # output_format = "md"
 
+# How the markdown report is organized.
+# Options: "finding" (one section per finding, listing the files it occurs in), "file" (one
+# section per file with the findings in it, and a table of finding counts per file)
+# If omitted, it defaults to "finding".
+# group_by = "finding"
+
 # Manual remappings for import resolution
 # Format: "prefix=target_path"
 # Example: remappings = ["@openzeppelin/=lib/openzeppelin-contracts/contracts/", "@solmate/=lib/solmate/src/"]
@@ -39,10 +76,135 @@ pub const DEFAULT_CONFIG_CONTENT: &str = r#"# weasel.toml
 #     "@solmate/=lib/solmate/src/"
 # ]
 
+# Base directory for file paths in reports.
+# Options: "project" (detected project root), "cwd" (current directory), "absolute"
+# If omitted, it defaults to "project".
+# path_base = "project"
+
 # Explicitly exclude specific detectors by ID.
 # Run `weasel detectors` to see all available detector IDs.
 # exclude_detectors = ["floating-pragma", "line-length"]
 
+# Chains the code will be deployed to. Detectors tagged for other chains only
+# (e.g. L2-specific checks) are skipped. If omitted, all detectors run.
+# Run `weasel detectors` to see chain tags.
+# target_chains = ["mainnet", "arbitrum"]
+
+# Keep only metadata in memory and re-parse files one at a time during analysis.
+# Slower, but bounds memory use on very large scopes.
+# low_memory = false
+
+# Keep each file's findings in `.weasel-cache/` keyed by a hash of its content, and
+# restore them on the next run when neither the file nor anything it imports or
+# inherits from has changed. Files are still parsed; only detection is skipped.
+# cache = false
+
+# Source of contract, function and state variable metadata.
+# "solc" reads the compiler AST from Foundry `out/` or Hardhat `artifacts/`
+# (or runs `solc` from PATH) for exact locations, falling back to solang-parser
+# per file. If omitted, it defaults to "solang".
+# ast_source = "solang"
+
+# Append a table of every in-scope contract with its nSLOC (non-empty, non-comment
+# lines) and external/public/internal function counts to the report.
+# scope_appendix = false
+
+# Include each detector's remediation example in the report: under every finding in
+# markdown, and as an `example` field in JSON. When off, markdown points to
+# `weasel detectors --details <id>` instead.
+# explain = false
+
+# Source lines shown before and after each reported line (marked with `>`) in markdown and
+# HTML, and as a `context` field in JSON. Tabs are expanded and lines longer than
+# `context_width` characters are truncated (0 for no limit).
+# If omitted, it defaults to 0 (only the reported code).
+# context_lines = 2
+# context_width = 120
+
+# Link every reported instance to its line in the hosted repository. Placeholders:
+# {commit} (HEAD of the git checkout, or `source_link_ref` outside git), {path}
+# (repository-relative, forward slashes) and {line}.
+# source_link_template = "https://github.com/org/repo/blob/{commit}/{path}#L{line}"
+# source_link_ref = "main"
+
+# Weasel versions this project's reports are expected from (semver requirement,
+# e.g. "^0.5" or ">=0.5.0, <0.7"). Other versions refuse to run unless
+# `--no-version-check` is passed, so every machine produces the same findings.
+# required_version = "^0.5"
+
+# Don't print notes (such as one for each setting from this file that a WEASEL_*
+# variable or command-line flag overrides), the completion summary or progress
+# counters on stderr. Warnings and errors are still printed.
+# quiet = false
+
+# Also print the resolved remappings, the number of files in scope and the
+# inheritance chain of every contract on stderr. `quiet` takes precedence.
+# verbose = false
+
+# Timestamp in the report metadata, in RFC 3339 (ISO 8601) form.
+# Options: "now", "fixed" (the SOURCE_DATE_EPOCH environment variable, for reproducible
+# reports), "none" or false (omitted). If omitted, it defaults to "now".
+# timestamp = "now"
+
+# Exit with code 1 when a finding of this severity or higher is reported, for CI gates.
+# Options: "Critical", "High", "Medium", "Low", "Gas", "NC"
+# If omitted, findings never fail the run. Errors always exit with code 2.
+# fail_on = "High"
+
+# Named profiles: any subset of the keys above, merged over this file by
+# `weasel run --profile <name>` (or WEASEL_PROFILE). Tables are merged key by key.
+# [profile.ci]
+# min_severity = "Medium"
+#
+# [profile.gas]
+# min_severity = "Gas"
+# exclude_detectors = ["line-length"]
+
+# Drop a detector's findings whose code snippet matches a regex, e.g. an idiom the
+# codebase uses everywhere. Applied to every finding before the report is built.
+# [[finding_filters]]
+# detector = "magic-numbers"
+# snippet_regex = "10_?000"
+
+# Stricter or looser reporting for parts of the codebase. `path` is a glob relative to
+# the project root (a plain directory covers everything below it). A finding in a
+# matching file is dropped when it is below the rule's `min_severity` or its detector is
+# in the rule's `exclude_detectors`. Only the most specific matching rule applies: the
+# one with the longest path before the first wildcard (`src/core/Vault.sol` beats
+# `src/core/**`, which beats `src/**`); on a tie, the rule written last. Rules only
+# filter findings: detectors below the global `min_severity` or in `exclude_detectors`
+# never run, so a rule cannot bring their findings back.
+# [[path_rules]]
+# path = "src/core/**"
+# min_severity = "NC"
+#
+# [[path_rules]]
+# path = "src/periphery/**"
+# min_severity = "Medium"
+# exclude_detectors = ["magic-numbers"]
+
+# Report branding: the heading (instead of "Weasel Static Analysis Report", and `title`
+# in JSON), an Overview section, a closing Note and extra metadata entries.
+# [report]
+# title = "Acme Protocol - Automated Findings"
+# overview = "Static analysis of the Acme vault contracts at commit 4f2a9c1."
+# footnote = "Findings are unverified and need manual review."
+#
+# [report.metadata]
+# Client = "Acme"
+# Auditor = "Example Security"
+
+# Per-detector options, keyed by detector ID.
+# [detectors.function-complexity]
+# max_params = 6
+# max_depth = 4
+#
+# [detectors.naming-convention]
+# internal_prefix = "_"
+#
+# [detectors.outdated-compiler-version]
+# min_version = "0.8.0"
+
 # Protocol Features
 # By default, all protocol features are enabled.
 [protocol]
@@ -53,80 +215,280 @@ pub const DEFAULT_CONFIG_CONTENT: &str = r#"# weasel.toml
 # uses_nft = false             # NFT collections (ERC721, ERC1155)
 "#;
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     #[serde(default)]
     pub scope: Vec<PathBuf>,
     #[serde(default = "default_exclude")]
     pub exclude: Vec<PathBuf>,
     #[serde(default)]
+    pub ignore_gitignore: bool,
+    #[serde(default)]
     pub min_severity: Severity,
     #[serde(default)]
     pub format: ReportFormat,
     #[serde(default)]
+    pub group_by: GroupBy,
+    #[serde(default)]
     pub remappings: Vec<String>,
     #[serde(default)]
     pub exclude_detectors: Vec<String>,
     #[serde(default)]
+    pub path_base: PathBase,
+    #[serde(default)]
+    pub target_chains: Vec<String>,
+    #[serde(default)]
+    pub low_memory: bool,
+    #[serde(default)]
+    pub cache: bool,
+    #[serde(default)]
+    pub ast_source: AstSource,
+    #[serde(default)]
+    pub scope_appendix: bool,
+    #[serde(default)]
+    pub explain: bool,
+    #[serde(default)]
+    pub context_lines: usize,
+    #[serde(default = "default_context_width")]
+    pub context_width: usize,
+    #[serde(default)]
+    pub source_link_template: Option<String>,
+    #[serde(default = "default_source_link_ref")]
+    pub source_link_ref: String,
+    #[serde(default)]
+    pub required_version: Option<String>,
+    #[serde(default)]
+    pub quiet: bool,
+    #[serde(default)]
+    pub verbose: bool,
+    #[serde(default)]
+    pub timestamp: TimestampMode,
+    #[serde(default)]
+    pub fail_on: Option<Severity>,
+    #[serde(default)]
+    pub finding_filters: Vec<FindingFilter>,
+    #[serde(default)]
+    pub path_rules: Vec<PathRule>,
+    #[serde(default)]
+    pub report: ReportSettings,
+    #[serde(default)]
     pub protocol: ProtocolConfig,
+    #[serde(default)]
+    pub detectors: HashMap<String, DetectorSettings>,
+    /// Source of each value, filled in by `load_config`
+    #[serde(skip)]
+    pub provenance: ConfigProvenance,
+}
+
+/// Top-level config file keys, as recorded in `ConfigProvenance`.
+const CONFIG_KEYS: [&str; 29] = [
+    "scope",
+    "exclude",
+    "ignore_gitignore",
+    "min_severity",
+    "format",
+    "group_by",
+    "remappings",
+    "exclude_detectors",
+    "path_base",
+    "target_chains",
+    "low_memory",
+    "cache",
+    "ast_source",
+    "scope_appendix",
+    "explain",
+    "context_lines",
+    "context_width",
+    "source_link_template",
+    "source_link_ref",
+    "required_version",
+    "quiet",
+    "verbose",
+    "timestamp",
+    "fail_on",
+    "finding_filters",
+    "path_rules",
+    "report",
+    "protocol",
+    "detectors",
+];
+
+/// Values supplied on the command line (or by the MCP server) that take
+/// precedence over the config file.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigOverrides {
+    pub scope: Option<Vec<PathBuf>>,
+    pub exclude: Option<Vec<PathBuf>>,
+    /// Walk paths matched by .gitignore files
+    pub ignore_gitignore: Option<bool>,
+    pub min_severity: Option<String>,
+    pub format: Option<String>,
+    pub group_by: Option<String>,
+    pub remappings: Option<Vec<String>>,
+    pub config_path: Option<PathBuf>,
+    pub exclude_detectors: Option<Vec<String>>,
+    pub path_base: Option<String>,
+    pub low_memory: Option<bool>,
+    /// Restore findings of unchanged files from `.weasel-cache/`
+    pub cache: Option<bool>,
+    pub ast_source: Option<String>,
+    pub scope_appendix: Option<bool>,
+    pub explain: Option<bool>,
+    /// Source lines to show around each reported line
+    pub context_lines: Option<usize>,
+    /// `[profile.<name>]` section of the config file to merge over its top-level keys
+    pub profile: Option<String>,
+    /// Warn instead of exiting when `required_version` does not match this build
+    pub no_version_check: Option<bool>,
+    /// Don't print notes, status lines or progress on stderr
+    pub quiet: Option<bool>,
+    /// Also print the project setup and contract inheritance on stderr
+    pub verbose: Option<bool>,
+    pub timestamp: Option<String>,
+    /// Minimum severity of a finding that makes the run exit with code 1
+    pub fail_on: Option<String>,
 }
 
 fn default_exclude() -> Vec<PathBuf> {
     vec![PathBuf::from("lib"), PathBuf::from("test")]
 }
 
+fn default_context_width() -> usize {
+    120
+}
+
+fn default_source_link_ref() -> String {
+    "main".to_string()
+}
+
 impl Default for Config {
     fn default() -> Self {
         Config {
             scope: Vec::new(),
             exclude: default_exclude(),
+            ignore_gitignore: false,
             min_severity: Severity::default(),
             format: ReportFormat::default(),
+            group_by: GroupBy::default(),
             remappings: Vec::new(),
             exclude_detectors: Vec::new(),
+            path_base: PathBase::default(),
+            target_chains: Vec::new(),
+            low_memory: false,
+            cache: false,
+            ast_source: AstSource::default(),
+            scope_appendix: false,
+            explain: false,
+            context_lines: 0,
+            context_width: default_context_width(),
+            source_link_template: None,
+            source_link_ref: default_source_link_ref(),
+            required_version: None,
+            quiet: false,
+            verbose: false,
+            timestamp: TimestampMode::default(),
+            fail_on: None,
+            finding_filters: Vec::new(),
+            path_rules: Vec::new(),
+            report: ReportSettings::default(),
             protocol: ProtocolConfig::default(),
+            detectors: HashMap::new(),
+            provenance: ConfigProvenance::default(),
+        }
+    }
+}
+
+impl Config {
+    /// Diagnostics to print on stderr; `quiet` takes precedence over `verbose`.
+    pub fn verbosity(&self) -> Verbosity {
+        if self.quiet {
+            Verbosity::Quiet
+        } else if self.verbose {
+            Verbosity::Verbose
+        } else {
+            Verbosity::Normal
         }
     }
 }
 
-pub fn load_config(
-    scope: Option<Vec<PathBuf>>,
-    exclude: Option<Vec<PathBuf>>,
-    min_severity: Option<String>,
-    format: Option<String>,
-    remappings: Option<Vec<String>>,
-    config_path: Option<PathBuf>,
-    exclude_detectors: Option<Vec<String>>,
-) -> Config {
+pub fn load_config(overrides: ConfigOverrides) -> Result<Config, WeaselError> {
+    let ConfigOverrides {
+        scope,
+        exclude,
+        ignore_gitignore,
+        min_severity,
+        format,
+        group_by,
+        remappings,
+        config_path,
+        exclude_detectors,
+        path_base,
+        low_memory,
+        cache,
+        ast_source,
+        scope_appendix,
+        explain,
+        context_lines,
+        profile,
+        no_version_check,
+        quiet,
+        verbose,
+        timestamp,
+        fail_on,
+    } = overrides;
+
     let default_path = PathBuf::from("weasel.toml");
     let config_path = config_path.unwrap_or(default_path);
+    let profile = profile.or_else(|| env_var("WEASEL_PROFILE"));
+    let mut provenance = ConfigProvenance::new(config_path.clone());
 
-    let config = if !config_path.exists() {
+    let mut config = if !config_path.exists() {
+        if let Some(name) = &profile {
+            return Err(WeaselError::Config(format!(
+                "profile '{}' selected but config file '{}' does not exist",
+                name,
+                config_path.display()
+            )));
+        }
         Config::default()
     } else {
-        let content = match fs::read_to_string(&config_path) {
-            Ok(c) => c,
-            Err(e) => {
-                eprintln!(
-                    "Error reading config file '{}': {}",
-                    config_path.display(),
-                    e
-                );
-                std::process::exit(1);
-            }
-        };
-        match toml::from_str::<Config>(&content) {
-            Ok(config) => config,
-            Err(e) => {
-                eprintln!(
-                    "Error parsing config file '{}': {}",
-                    config_path.display(),
+        let content = fs::read_to_string(&config_path).map_err(|e| {
+            WeaselError::Config(format!(
+                "Failed to read config file '{}': {}",
+                config_path.display(),
+                e
+            ))
+        })?;
+        let table =
+            toml::from_str::<toml::Table>(&content).map_err(|e| parse_error(&config_path, e))?;
+        let table = apply_profile(table, profile.as_deref())
+            .map_err(|e| invalid_config(&config_path, e))?;
+        for key in CONFIG_KEYS.iter().filter(|key| table.contains_key(**key)) {
+            provenance.set(key, ValueSource::Config);
+        }
+        toml::Value::Table(table)
+            .try_into::<Config>()
+            .map_err(|e| parse_error(&config_path, e))?
+    };
+
+    apply_env_overrides(&mut config, &mut provenance);
+
+    if let Some(template) = &config.source_link_template {
+        source_link::validate_template(template).map_err(|e| invalid_config(&config_path, e))?;
+    }
+
+    if let Some(required) = &config.required_version {
+        if let Err(e) = version::check_required_version(required, crate::core::version()) {
+            if no_version_check.unwrap_or(false) {
+                errln!("Warning: {}", e);
+            } else {
+                return Err(WeaselError::Config(format!(
+                    "{} (or pass --no-version-check to run anyway)",
                     e
-                );
-                std::process::exit(1);
+                )));
             }
         }
-    };
+    }
 
     // Merge exclude_detectors: CLI args extend config file list
     let final_exclude_detectors = {
@@ -137,43 +499,309 @@ pub fn load_config(
         from_config
     };
 
-    Config {
+    let min_severity = min_severity.map(|s| {
+        s.parse().unwrap_or_else(|e| {
+            errln!("Warning: {}. Using default severity.", e);
+            Severity::default()
+        })
+    });
+    let format = format.map(|s| {
+        s.parse().unwrap_or_else(|e| {
+            errln!("Warning: {}. Using default format.", e);
+            ReportFormat::default()
+        })
+    });
+    let group_by = group_by.map(|s| {
+        s.parse().unwrap_or_else(|e| {
+            errln!("Warning: {}. Using default grouping.", e);
+            GroupBy::default()
+        })
+    });
+    let path_base = path_base.map(|s| {
+        s.parse().unwrap_or_else(|e| {
+            errln!("Warning: {}. Using default path base.", e);
+            PathBase::default()
+        })
+    });
+    let ast_source = ast_source.map(|s| {
+        s.parse().unwrap_or_else(|e| {
+            errln!("Warning: {}. Using default AST source.", e);
+            AstSource::default()
+        })
+    });
+    let timestamp = timestamp.map(|s| {
+        s.parse().unwrap_or_else(|e| {
+            errln!("Warning: {}. Using default timestamp.", e);
+            TimestampMode::default()
+        })
+    });
+    let fail_on = fail_on
+        .map(|s| s.parse())
+        .transpose()
+        .map_err(|e| WeaselError::Config(format!("--fail-on: {}", e)))?;
+
+    let cli = ValueSource::Cli;
+    if let Some(scope) = &scope {
+        provenance.record("scope", cli, display_paths(&config.scope), display_paths(scope));
+    }
+    if let Some(exclude) = &exclude {
+        provenance.record("exclude", cli, display_paths(&config.exclude), display_paths(exclude));
+    }
+    if let Some(ignore_gitignore) = ignore_gitignore {
+        provenance.record("ignore_gitignore", cli, config.ignore_gitignore, ignore_gitignore);
+    }
+    if let Some(min_severity) = &min_severity {
+        provenance.record("min_severity", cli, &config.min_severity, min_severity);
+    }
+    if let Some(format) = &format {
+        provenance.record("format", cli, &config.format, format);
+    }
+    if let Some(group_by) = group_by {
+        provenance.record("group_by", cli, config.group_by, group_by);
+    }
+    if let Some(remappings) = &remappings {
+        provenance.record("remappings", cli, config.remappings.join(", "), remappings.join(", "));
+    }
+    if let Some(path_base) = &path_base {
+        provenance.record("path_base", cli, config.path_base, path_base);
+    }
+    if let Some(low_memory) = low_memory {
+        provenance.record("low_memory", cli, config.low_memory, low_memory);
+    }
+    if let Some(cache) = cache {
+        provenance.record("cache", cli, config.cache, cache);
+    }
+    if let Some(ast_source) = &ast_source {
+        provenance.record("ast_source", cli, config.ast_source, ast_source);
+    }
+    if let Some(scope_appendix) = scope_appendix {
+        provenance.record("scope_appendix", cli, config.scope_appendix, scope_appendix);
+    }
+    if let Some(explain) = explain {
+        provenance.record("explain", cli, config.explain, explain);
+    }
+    if let Some(context_lines) = context_lines {
+        provenance.record("context_lines", cli, config.context_lines, context_lines);
+    }
+    if let Some(quiet) = quiet {
+        provenance.record("quiet", cli, config.quiet, quiet);
+    }
+    if let Some(verbose) = verbose {
+        provenance.record("verbose", cli, config.verbose, verbose);
+    }
+    if let Some(timestamp) = &timestamp {
+        provenance.record("timestamp", cli, config.timestamp, timestamp);
+    }
+    if let Some(fail_on) = &fail_on {
+        let previous = config.fail_on.as_ref().map_or("never".to_string(), ToString::to_string);
+        provenance.record("fail_on", cli, previous, fail_on);
+    }
+
+    let timestamp = timestamp.unwrap_or(config.timestamp);
+    timestamp.resolve().map_err(WeaselError::Config)?;
+
+    let quiet = quiet.unwrap_or(config.quiet);
+    if !quiet {
+        for notice in provenance.notices() {
+            errln!("Note: {}", notice);
+        }
+    }
+
+    Ok(Config {
         scope: scope.unwrap_or(config.scope),
         exclude: exclude.unwrap_or(config.exclude),
-        min_severity: min_severity.map_or(config.min_severity, |s| {
-            s.parse().unwrap_or_else(|e| {
-                eprintln!("Warning: {}. Using default severity.", e);
-                Severity::default()
-            })
-        }),
-        format: format.map_or(config.format, |s| {
-            s.parse().unwrap_or_else(|e| {
-                eprintln!("Warning: {}. Using default format.", e);
-                ReportFormat::default()
-            })
-        }),
+        ignore_gitignore: ignore_gitignore.unwrap_or(config.ignore_gitignore),
+        min_severity: min_severity.unwrap_or(config.min_severity),
+        format: format.unwrap_or(config.format),
+        group_by: group_by.unwrap_or(config.group_by),
         remappings: remappings.unwrap_or(config.remappings),
         exclude_detectors: final_exclude_detectors,
+        path_base: path_base.unwrap_or(config.path_base),
+        target_chains: config.target_chains,
+        low_memory: low_memory.unwrap_or(config.low_memory),
+        cache: cache.unwrap_or(config.cache),
+        ast_source: ast_source.unwrap_or(config.ast_source),
+        scope_appendix: scope_appendix.unwrap_or(config.scope_appendix),
+        explain: explain.unwrap_or(config.explain),
+        context_lines: context_lines.unwrap_or(config.context_lines),
+        context_width: config.context_width,
+        source_link_template: config.source_link_template,
+        source_link_ref: config.source_link_ref,
+        required_version: config.required_version,
+        quiet,
+        verbose: verbose.unwrap_or(config.verbose),
+        timestamp,
+        fail_on: fail_on.or(config.fail_on),
+        finding_filters: config.finding_filters,
+        path_rules: config.path_rules,
+        report: config.report,
         protocol: config.protocol,
+        detectors: config.detectors,
+        provenance,
+    })
+}
+
+fn parse_error(config_path: &Path, e: impl std::fmt::Display) -> WeaselError {
+    WeaselError::Config(format!(
+        "Failed to parse config file '{}': {}",
+        config_path.display(),
+        e
+    ))
+}
+
+fn invalid_config(config_path: &Path, e: impl std::fmt::Display) -> WeaselError {
+    WeaselError::Config(format!(
+        "Invalid config file '{}': {}",
+        config_path.display(),
+        e
+    ))
+}
+
+/// Take the `[profile.*]` sections out of a parsed config file and, if `profile` is set, merge
+/// the selected one over the remaining keys.
+fn apply_profile(mut table: toml::Table, profile: Option<&str>) -> Result<toml::Table, String> {
+    let profiles = match table.remove("profile") {
+        Some(toml::Value::Table(profiles)) => profiles,
+        Some(_) => return Err("`profile` must contain [profile.<name>] sections".to_string()),
+        None => toml::Table::new(),
+    };
+    let Some(name) = profile else {
+        return Ok(table);
+    };
+
+    match profiles.get(name) {
+        Some(toml::Value::Table(overlay)) => {
+            merge_tables(&mut table, overlay.clone());
+            Ok(table)
+        }
+        Some(_) => Err(format!("[profile.{}] must be a table", name)),
+        None if profiles.is_empty() => Err(format!(
+            "Unknown profile '{}'; no [profile.<name>] sections are defined",
+            name
+        )),
+        None => Err(format!(
+            "Unknown profile '{}'; available profiles: {}",
+            name,
+            profiles.keys().cloned().collect::<Vec<_>>().join(", ")
+        )),
     }
 }
 
+/// Merge `overlay` into `base`: nested tables are merged key by key, other values replaced.
+fn merge_tables(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base_table)), toml::Value::Table(overlay_table)) => {
+                merge_tables(base_table, overlay_table)
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Apply `WEASEL_*` environment variables on top of the config file. Invalid values are
+/// reported and leave the config file value in place.
+fn apply_env_overrides(config: &mut Config, provenance: &mut ConfigProvenance) {
+    if let Some(value) = env_var("WEASEL_SCOPE") {
+        let scope = split_env_paths(&value);
+        let source = ValueSource::Env("WEASEL_SCOPE");
+        provenance.record("scope", source, display_paths(&config.scope), display_paths(&scope));
+        config.scope = scope;
+    }
+    if let Some(value) = env_var("WEASEL_EXCLUDE") {
+        let exclude = split_env_paths(&value);
+        let (source, previous) = (ValueSource::Env("WEASEL_EXCLUDE"), &config.exclude);
+        provenance.record("exclude", source, display_paths(previous), display_paths(&exclude));
+        config.exclude = exclude;
+    }
+    if let Some(value) = env_var("WEASEL_MIN_SEVERITY") {
+        match value.parse() {
+            Ok(severity) => {
+                let source = ValueSource::Env("WEASEL_MIN_SEVERITY");
+                provenance.record("min_severity", source, &config.min_severity, &severity);
+                config.min_severity = severity;
+            }
+            Err(e) => errln!("Warning: WEASEL_MIN_SEVERITY: {}. Ignoring it.", e),
+        }
+    }
+    if let Some(value) = env_var("WEASEL_FORMAT") {
+        match value.parse() {
+            Ok(format) => {
+                let source = ValueSource::Env("WEASEL_FORMAT");
+                provenance.record("format", source, &config.format, &format);
+                config.format = format;
+            }
+            Err(e) => errln!("Warning: WEASEL_FORMAT: {}. Ignoring it.", e),
+        }
+    }
+    if let Some(value) = env_var("WEASEL_FAIL_ON") {
+        match value.parse::<Severity>() {
+            Ok(severity) => {
+                let source = ValueSource::Env("WEASEL_FAIL_ON");
+                let previous = config
+                    .fail_on
+                    .as_ref()
+                    .map_or("never".to_string(), ToString::to_string);
+                provenance.record("fail_on", source, previous, &severity);
+                config.fail_on = Some(severity);
+            }
+            Err(e) => errln!("Warning: WEASEL_FAIL_ON: {}. Ignoring it.", e),
+        }
+    }
+    if let Some(value) = env_var("WEASEL_REMAPPINGS") {
+        let remappings: Vec<String> = value
+            .split(',')
+            .map(str::trim)
+            .filter(|r| !r.is_empty())
+            .map(String::from)
+            .collect();
+        match remappings.iter().find(|r| !r.contains('=')) {
+            Some(invalid) => errln!(
+                "Warning: WEASEL_REMAPPINGS: '{}' is not in prefix=target form. Ignoring it.",
+                invalid
+            ),
+            None => {
+                provenance.record(
+                    "remappings",
+                    ValueSource::Env("WEASEL_REMAPPINGS"),
+                    config.remappings.join(", "),
+                    remappings.join(", "),
+                );
+                config.remappings = remappings;
+            }
+        }
+    }
+}
+
+/// Value of a set, non-blank environment variable.
+fn env_var(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|v| !v.trim().is_empty())
+}
+
+fn split_env_paths(value: &str) -> Vec<PathBuf> {
+    env::split_paths(value)
+        .filter(|p| !p.as_os_str().is_empty())
+        .collect()
+}
+
 pub fn initialize_config_file(config_path_override: Option<&Path>) -> Result<(), String> {
     let default_path = Path::new("weasel.toml");
     let config_path = config_path_override.unwrap_or(default_path);
 
     if config_path.exists() {
-        println!("INFO: '{}' already exists.", config_path.display());
+        outln!("INFO: '{}' already exists.", config_path.display());
         Ok(())
     } else {
-        println!(
+        outln!(
             "Creating default config file at '{}'",
             config_path.display()
         );
         match fs::File::create(config_path) {
             Ok(mut file) => match file.write_all(DEFAULT_CONFIG_CONTENT.as_bytes()) {
                 Ok(_) => {
-                    println!(
+                    outln!(
                         "SUCCESS: Created default '{}' configuration file.",
                         config_path.display()
                     );
@@ -189,3 +817,385 @@ pub fn initialize_config_file(config_path_override: Option<&Path>) -> Result<(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::write_fixture_project;
+    use std::sync::Mutex;
+
+    /// Environment variables are process-wide; tests that set them must not overlap.
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    const ENV_VARS: [&str; 7] = [
+        "WEASEL_SCOPE",
+        "WEASEL_EXCLUDE",
+        "WEASEL_MIN_SEVERITY",
+        "WEASEL_FORMAT",
+        "WEASEL_FAIL_ON",
+        "WEASEL_REMAPPINGS",
+        "WEASEL_PROFILE",
+    ];
+
+    fn with_env<F: FnOnce()>(vars: &[(&str, &str)], test: F) {
+        let _guard = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        for (name, value) in vars {
+            env::set_var(name, value);
+        }
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(test));
+        for name in ENV_VARS {
+            env::remove_var(name);
+        }
+        if let Err(panic) = result {
+            std::panic::resume_unwind(panic);
+        }
+    }
+
+    fn no_config_file() -> ConfigOverrides {
+        ConfigOverrides {
+            config_path: Some(PathBuf::from("does-not-exist/weasel.toml")),
+            ..ConfigOverrides::default()
+        }
+    }
+
+    #[test]
+    fn test_env_overrides_apply_below_cli_flags() {
+        let scope = env::join_paths(["src", "contracts"]).unwrap();
+        let exclude = env::join_paths(["test", "", "script"]).unwrap();
+        let vars = [
+            ("WEASEL_SCOPE", scope.to_str().unwrap()),
+            ("WEASEL_EXCLUDE", exclude.to_str().unwrap()),
+            ("WEASEL_MIN_SEVERITY", "medium"),
+            ("WEASEL_FORMAT", "json"),
+            ("WEASEL_FAIL_ON", "low"),
+            (
+                "WEASEL_REMAPPINGS",
+                "@oz/=lib/oz/, @solmate/=lib/solmate/src/,",
+            ),
+        ];
+
+        with_env(&vars, || {
+            let config = load_config(no_config_file()).unwrap();
+            assert_eq!(
+                config.scope,
+                [PathBuf::from("src"), PathBuf::from("contracts")]
+            );
+            assert_eq!(
+                config.exclude,
+                [PathBuf::from("test"), PathBuf::from("script")]
+            );
+            assert_eq!(config.min_severity, Severity::Medium);
+            assert_eq!(config.format, ReportFormat::Json);
+            assert_eq!(config.fail_on, Some(Severity::Low));
+            assert_eq!(
+                config.remappings,
+                ["@oz/=lib/oz/", "@solmate/=lib/solmate/src/"]
+            );
+
+            let config = load_config(ConfigOverrides {
+                scope: Some(vec![PathBuf::from("cli")]),
+                min_severity: Some("high".to_string()),
+                fail_on: Some("critical".to_string()),
+                ..no_config_file()
+            })
+            .unwrap();
+            assert_eq!(config.scope, [PathBuf::from("cli")]);
+            assert_eq!(config.min_severity, Severity::High);
+            assert_eq!(config.fail_on, Some(Severity::Critical));
+            assert_eq!(config.format, ReportFormat::Json);
+        });
+    }
+
+    #[test]
+    fn test_invalid_env_values_fall_back_to_config_file() {
+        let root = write_fixture_project(
+            "env-config",
+            &[(
+                "weasel.toml",
+                "min_severity = \"Low\"\nfail_on = \"High\"\nremappings = [\"@oz/=lib/oz/\"]\n",
+            )],
+        );
+        let vars = [
+            ("WEASEL_MIN_SEVERITY", "urgent"),
+            ("WEASEL_FORMAT", "pdf"),
+            ("WEASEL_FAIL_ON", "severe"),
+            (
+                "WEASEL_REMAPPINGS",
+                "@solmate/=lib/solmate/src/,lib/forge-std",
+            ),
+        ];
+
+        with_env(&vars, || {
+            let config = load_config(ConfigOverrides {
+                config_path: Some(root.join("weasel.toml")),
+                ..ConfigOverrides::default()
+            })
+            .unwrap();
+            assert_eq!(config.min_severity, Severity::Low);
+            assert_eq!(config.format, ReportFormat::default());
+            assert_eq!(config.fail_on, Some(Severity::High));
+            assert_eq!(config.remappings, ["@oz/=lib/oz/"]);
+        });
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_required_version_pin() {
+        let current = format!("required_version = \"={}\"\n", crate::core::version());
+        let root = write_fixture_project(
+            "required-version",
+            &[
+                ("current.toml", current.as_str()),
+                ("stale.toml", "required_version = \">=99.0\"\n"),
+            ],
+        );
+
+        with_env(&[], || {
+            let config = load_config(ConfigOverrides {
+                config_path: Some(root.join("current.toml")),
+                ..ConfigOverrides::default()
+            })
+            .unwrap();
+            assert!(config.required_version.is_some());
+
+            let error = load_config(ConfigOverrides {
+                config_path: Some(root.join("stale.toml")),
+                ..ConfigOverrides::default()
+            })
+            .unwrap_err();
+            assert!(matches!(error, WeaselError::Config(_)));
+            assert!(
+                error.to_string().contains("--no-version-check"),
+                "{}",
+                error
+            );
+
+            // A mismatch only warns with --no-version-check
+            let config = load_config(ConfigOverrides {
+                config_path: Some(root.join("stale.toml")),
+                no_version_check: Some(true),
+                ..ConfigOverrides::default()
+            })
+            .unwrap();
+            assert_eq!(config.required_version.as_deref(), Some(">=99.0"));
+        });
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    const PROFILES: &str = r#"
+scope = ["src"]
+min_severity = "NC"
+exclude_detectors = ["floating-pragma"]
+
+[protocol]
+uses_nft = false
+
+[detectors.function-complexity]
+max_params = 6
+
+[profile.audit]
+scope_appendix = true
+
+[profile.ci]
+min_severity = "Medium"
+format = "Sarif"
+
+[profile.gas]
+min_severity = "Gas"
+exclude_detectors = ["line-length"]
+
+[profile.gas.detectors.function-complexity]
+max_depth = 3
+"#;
+
+    #[test]
+    fn test_profiles_merge_over_base_config() {
+        let root = write_fixture_project("profiles", &[("weasel.toml", PROFILES)]);
+        let load = |profile: Option<&str>| {
+            load_config(ConfigOverrides {
+                config_path: Some(root.join("weasel.toml")),
+                profile: profile.map(String::from),
+                ..ConfigOverrides::default()
+            })
+            .unwrap()
+        };
+
+        with_env(&[], || {
+            let base = load(None);
+            assert_eq!(base.min_severity, Severity::NC);
+            assert!(!base.scope_appendix);
+
+            let audit = load(Some("audit"));
+            assert!(audit.scope_appendix);
+            assert_eq!(audit.min_severity, Severity::NC);
+            assert_eq!(audit.exclude_detectors, ["floating-pragma"]);
+
+            let ci = load(Some("ci"));
+            assert_eq!(ci.min_severity, Severity::Medium);
+            assert_eq!(ci.format, ReportFormat::Sarif);
+            assert_eq!(ci.scope, [PathBuf::from("src")]);
+            assert!(!ci.protocol.uses_nft);
+
+            let gas = load(Some("gas"));
+            assert_eq!(gas.min_severity, Severity::Gas);
+            assert_eq!(gas.exclude_detectors, ["line-length"]);
+            let complexity = &gas.detectors["function-complexity"];
+            let option = |key| complexity.get_usize("function-complexity", key);
+            assert_eq!(option("max_params"), Some(6));
+            assert_eq!(option("max_depth"), Some(3));
+        });
+
+        // The environment selects a profile, command-line flags still win over it
+        with_env(&[("WEASEL_PROFILE", "ci")], || {
+            assert_eq!(load(None).min_severity, Severity::Medium);
+
+            let config = load_config(ConfigOverrides {
+                config_path: Some(root.join("weasel.toml")),
+                min_severity: Some("low".to_string()),
+                format: Some("json".to_string()),
+                ..ConfigOverrides::default()
+            })
+            .unwrap();
+            assert_eq!(config.min_severity, Severity::Low);
+            assert_eq!(config.format, ReportFormat::Json);
+        });
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_report_section_and_disabled_timestamp() {
+        let root = write_fixture_project(
+            "report-section",
+            &[(
+                "weasel.toml",
+                r#"
+timestamp = false
+
+[report]
+title = "Acme Vault Review"
+overview = "Contracts at commit 4f2a9c1."
+
+[report.metadata]
+Client = "Acme"
+"#,
+            )],
+        );
+
+        with_env(&[], || {
+            let config = load_config(ConfigOverrides {
+                config_path: Some(root.join("weasel.toml")),
+                ..ConfigOverrides::default()
+            })
+            .unwrap();
+            assert_eq!(config.timestamp, TimestampMode::None);
+            assert_eq!(config.report.title.as_deref(), Some("Acme Vault Review"));
+            assert_eq!(
+                config.report.overview.as_deref(),
+                Some("Contracts at commit 4f2a9c1.")
+            );
+            assert_eq!(config.report.footnote, None);
+            assert_eq!(config.report.metadata["Client"], "Acme");
+        });
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_unknown_profile_lists_available_profiles() {
+        let table: toml::Table = toml::from_str(PROFILES).unwrap();
+        assert_eq!(
+            apply_profile(table, Some("release")).unwrap_err(),
+            "Unknown profile 'release'; available profiles: audit, ci, gas"
+        );
+
+        let table: toml::Table = toml::from_str("min_severity = \"Low\"\n").unwrap();
+        assert!(apply_profile(table.clone(), None).is_ok());
+        assert_eq!(
+            apply_profile(table, Some("ci")).unwrap_err(),
+            "Unknown profile 'ci'; no [profile.<name>] sections are defined"
+        );
+    }
+
+    #[test]
+    fn test_provenance_records_overridden_values() {
+        let root = write_fixture_project(
+            "config-provenance",
+            &[(
+                "weasel.toml",
+                "min_severity = \"NC\"\nformat = \"Json\"\nexplain = true\n",
+            )],
+        );
+
+        with_env(&[("WEASEL_MIN_SEVERITY", "medium")], || {
+            let config = load_config(ConfigOverrides {
+                config_path: Some(root.join("weasel.toml")),
+                min_severity: Some("high".to_string()),
+                format: Some("json".to_string()),
+                low_memory: Some(true),
+                ..ConfigOverrides::default()
+            })
+            .unwrap();
+            let provenance = &config.provenance;
+
+            assert_eq!(config.min_severity, Severity::High);
+            assert_eq!(provenance.source("min_severity"), ValueSource::Cli);
+            assert_eq!(provenance.source("format"), ValueSource::Cli);
+            assert_eq!(provenance.source("explain"), ValueSource::Config);
+            assert_eq!(provenance.source("low_memory"), ValueSource::Cli);
+            assert_eq!(provenance.source("scope"), ValueSource::Default);
+
+            // Same format from both sources and a default replaced by a flag are not notices
+            assert_eq!(
+                provenance.notices(),
+                [
+                    "min_severity from weasel.toml (NC) overridden by WEASEL_MIN_SEVERITY (Medium)",
+                    "min_severity from WEASEL_MIN_SEVERITY (Medium) overridden by CLI (High)",
+                ]
+            );
+        });
+
+        with_env(&[("WEASEL_FORMAT", "sarif")], || {
+            let config = load_config(ConfigOverrides {
+                config_path: Some(root.join("weasel.toml")),
+                quiet: Some(true),
+                ..ConfigOverrides::default()
+            })
+            .unwrap();
+            assert!(config.quiet);
+            assert_eq!(
+                config.provenance.source("format"),
+                ValueSource::Env("WEASEL_FORMAT")
+            );
+            assert_eq!(
+                config.provenance.notices(),
+                ["format from weasel.toml (Json) overridden by WEASEL_FORMAT (Sarif)"]
+            );
+        });
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_verbosity_from_quiet_and_verbose() {
+        let root =
+            write_fixture_project("config-verbosity", &[("weasel.toml", "verbose = true\n")]);
+        let load = |quiet| {
+            load_config(ConfigOverrides {
+                config_path: Some(root.join("weasel.toml")),
+                quiet,
+                ..ConfigOverrides::default()
+            })
+            .unwrap()
+        };
+
+        assert_eq!(Config::default().verbosity(), Verbosity::Normal);
+        assert_eq!(load(None).verbosity(), Verbosity::Verbose);
+        // A quiet run stays quiet whatever the config file asks for
+        assert_eq!(load(Some(true)).verbosity(), Verbosity::Quiet);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
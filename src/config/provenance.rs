@@ -0,0 +1,125 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::PathBuf;
+
+/// Where an effective config value came from, in increasing precedence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ValueSource {
+    #[default]
+    Default,
+    /// The config file, including a selected `[profile.<name>]` section
+    Config,
+    /// The named `WEASEL_*` environment variable
+    Env(&'static str),
+    /// A command-line flag (or MCP tool argument)
+    Cli,
+}
+
+/// A value from one source replaced by a different value from a higher-precedence source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OverriddenValue {
+    pub key: &'static str,
+    pub replaced: ValueSource,
+    pub replaced_value: String,
+    pub source: ValueSource,
+    pub value: String,
+}
+
+/// Source of every config value set by something other than the defaults, recorded by
+/// `load_config`, and the values that were overridden on the way.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigProvenance {
+    config_file: PathBuf,
+    sources: BTreeMap<&'static str, ValueSource>,
+    overridden: Vec<OverriddenValue>,
+}
+
+impl ConfigProvenance {
+    pub fn new(config_file: PathBuf) -> Self {
+        Self {
+            config_file,
+            ..Self::default()
+        }
+    }
+
+    /// Record the source of `key` without comparing values.
+    pub fn set(&mut self, key: &'static str, source: ValueSource) {
+        self.sources.insert(key, source);
+    }
+
+    /// Record that `key` now holds `value` from `source`, replacing `previous`.
+    pub fn record(
+        &mut self,
+        key: &'static str,
+        source: ValueSource,
+        previous: impl fmt::Display,
+        value: impl fmt::Display,
+    ) {
+        let replaced = self.source(key);
+        let (replaced_value, value) = (previous.to_string(), value.to_string());
+        if replaced != ValueSource::Default && replaced != source && replaced_value != value {
+            self.overridden.push(OverriddenValue {
+                key,
+                replaced,
+                replaced_value,
+                source,
+                value,
+            });
+        }
+        self.set(key, source);
+    }
+
+    /// Source of the effective value of `key`.
+    pub fn source(&self, key: &str) -> ValueSource {
+        self.sources
+            .get(key)
+            .copied()
+            .unwrap_or(ValueSource::Default)
+    }
+
+    pub fn overridden(&self) -> &[OverriddenValue] {
+        &self.overridden
+    }
+
+    /// Human-readable name of a source: the config file name, variable name or "CLI".
+    pub fn describe(&self, source: ValueSource) -> String {
+        match source {
+            ValueSource::Default => "defaults".to_string(),
+            ValueSource::Config => self
+                .config_file
+                .file_name()
+                .unwrap_or(self.config_file.as_os_str())
+                .to_string_lossy()
+                .into_owned(),
+            ValueSource::Env(name) => name.to_string(),
+            ValueSource::Cli => "CLI".to_string(),
+        }
+    }
+
+    /// One line per overridden value, e.g.
+    /// `min_severity from weasel.toml (NC) overridden by CLI (High)`.
+    pub fn notices(&self) -> Vec<String> {
+        self.overridden
+            .iter()
+            .map(|o| {
+                format!(
+                    "{} from {} ({}) overridden by {} ({})",
+                    o.key,
+                    self.describe(o.replaced),
+                    o.replaced_value,
+                    self.describe(o.source),
+                    o.value
+                )
+            })
+            .collect()
+    }
+}
+
+/// Display a path list the way it is written in the config file.
+pub(crate) fn display_paths(paths: &[PathBuf]) -> String {
+    paths
+        .iter()
+        .map(|p| p.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Report branding, read from the `[report]` table in `weasel.toml`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReportSettings {
+    /// Heading of markdown and HTML reports, and `title` in JSON
+    pub title: Option<String>,
+    /// Text of the Overview section
+    pub overview: Option<String>,
+    /// Text of the closing Note section
+    pub footnote: Option<String>,
+    /// Entries added to the report metadata next to the version and timestamp
+    pub metadata: BTreeMap<String, String>,
+}
@@ -0,0 +1,327 @@
+//! Incremental analysis: each file's findings are kept in `.weasel-cache/findings.json`
+//! together with a hash of its content and of every file it depends on, and restored on the
+//! next run instead of traversing the file again while none of them has changed. Findings are
+//! kept per detector with the detector's version, so a new or changed detector is run on the
+//! unchanged files alone while the other detectors' findings are still restored.
+
+use crate::config::Config;
+use crate::core::context::AnalysisContext;
+use crate::core::registry::DetectorRegistry;
+use crate::models::{FindingData, Location, SolidityFile};
+use crate::utils::fsx::{create_cache_dir, AtomicFile, CACHE_DIR};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+const CACHE_FILE: &str = "findings.json";
+
+/// Detector id -> number of its findings dropped by `weasel-disable` comments in a file.
+pub type SuppressedCounts = BTreeMap<&'static str, usize>;
+
+/// Findings restored for a file, and the suppressed counts that go with them.
+pub type RestoredFindings = (Vec<FindingData>, SuppressedCounts);
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheContents {
+    /// Hash of the settings besides the sources and detectors that decide a file's findings
+    key: String,
+    /// Detector id -> version of the detector the cached findings come from
+    detectors: BTreeMap<String, u32>,
+    /// File path, as analyzed -> its cached findings
+    files: BTreeMap<String, CachedFile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct FileState {
+    /// SHA-256 of the file's content
+    hash: String,
+    /// Imported files (transitively), files of inherited contracts and files of contracts
+    /// inheriting from this one -> SHA-256 of their content
+    dependencies: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedFile {
+    #[serde(flatten)]
+    state: FileState,
+    /// Detector id -> its findings dropped by the file's `weasel-disable` comments
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    suppressed: BTreeMap<String, usize>,
+    /// Findings left after suppressions, before overlapping ones are dropped
+    findings: Vec<CachedFinding>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedFinding {
+    detector: String,
+    location: Location,
+}
+
+/// Findings of a previous run, and those of the current run to be written back by `save`.
+#[derive(Debug)]
+pub struct FindingsCache {
+    path: PathBuf,
+    key: String,
+    previous: BTreeMap<String, CachedFile>,
+    previous_detectors: BTreeMap<String, u32>,
+    current: HashMap<PathBuf, FileState>,
+    detectors: BTreeMap<String, u32>,
+    detector_ids: HashMap<String, &'static str>,
+    stale: HashSet<&'static str>,
+    fresh: Mutex<BTreeMap<String, CachedFile>>,
+    analyzed: AtomicUsize,
+    restored: AtomicUsize,
+}
+
+impl FindingsCache {
+    /// Read the cache of the project at `root`. Entries written under a different `key` (another
+    /// configuration) are discarded.
+    pub fn load(root: &Path, key: String) -> Self {
+        let path = root.join(CACHE_DIR).join(CACHE_FILE);
+        let contents = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<CacheContents>(&content).ok())
+            .filter(|contents| contents.key == key)
+            .unwrap_or_default();
+
+        Self {
+            path,
+            key,
+            previous: contents.files,
+            previous_detectors: contents.detectors,
+            current: HashMap::new(),
+            detectors: BTreeMap::new(),
+            detector_ids: HashMap::new(),
+            stale: HashSet::new(),
+            fresh: Mutex::new(BTreeMap::new()),
+            analyzed: AtomicUsize::new(0),
+            restored: AtomicUsize::new(0),
+        }
+    }
+
+    /// Hash every loaded file and its dependencies. Must be called once the context's contract
+    /// cache is built and the detectors are registered.
+    pub fn prepare(&mut self, context: &AnalysisContext, registry: &DetectorRegistry) {
+        for detector in registry.get_all() {
+            let id = detector.id();
+            if self.previous_detectors.get(id) != Some(&detector.version()) {
+                self.stale.insert(id);
+            }
+            self.detectors.insert(id.to_string(), detector.version());
+            self.detector_ids.insert(id.to_string(), id);
+        }
+
+        let mut hashes = HashMap::new();
+        for file in context.files.iter() {
+            let Some(hash) = content_hash(&file.path, &mut hashes) else {
+                continue;
+            };
+            let mut dependencies = BTreeMap::new();
+            for dependency in dependencies_of(file, context) {
+                let hash = content_hash(&dependency, &mut hashes).unwrap_or_default();
+                dependencies.insert(path_key(&dependency), hash);
+            }
+            self.current
+                .insert(file.path.clone(), FileState { hash, dependencies });
+        }
+    }
+
+    /// Registered detectors whose findings are not in the cache, or come from another version of
+    /// the detector. They have to run on files whose other findings are restored.
+    pub fn stale_detectors(&self) -> &HashSet<&'static str> {
+        &self.stale
+    }
+
+    /// Cached findings of `path`, before overlapping ones are dropped, and the number of
+    /// suppressed ones per detector, if neither the file nor its dependencies changed since they
+    /// were recorded. Findings of stale detectors are left out; once they have run on the file,
+    /// `record_rerun` stores the merged findings. `None` as well when every detector is stale.
+    pub fn restore(&self, path: &Path) -> Option<RestoredFindings> {
+        let state = self.current.get(path)?;
+        let cached = self.previous.get(&path_key(path))?;
+        if cached.state != *state || self.stale.len() == self.detector_ids.len() {
+            return None;
+        }
+        let current = |detector: &str| {
+            self.detector_ids
+                .get(detector)
+                .copied()
+                .filter(|id| !self.stale.contains(id))
+        };
+        let findings = cached
+            .findings
+            .iter()
+            .filter_map(|finding| {
+                Some(FindingData {
+                    detector_id: current(&finding.detector)?,
+                    location: finding.location.clone(),
+                })
+            })
+            .collect();
+        let suppressed = cached
+            .suppressed
+            .iter()
+            .filter_map(|(detector, count)| Some((current(detector)?, *count)))
+            .collect();
+
+        if self.stale.is_empty() {
+            self.fresh
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(path_key(path), cached.clone());
+        }
+        self.restored.fetch_add(1, Ordering::Relaxed);
+        Some((findings, suppressed))
+    }
+
+    /// Remember the findings of a file that was analyzed in this run.
+    pub fn record(&self, path: &Path, findings: &[FindingData], suppressed: &SuppressedCounts) {
+        self.analyzed.fetch_add(1, Ordering::Relaxed);
+        self.store(path, findings, suppressed);
+    }
+
+    /// Remember the findings of a restored file merged with those of the stale detectors.
+    pub fn record_rerun(
+        &self,
+        path: &Path,
+        findings: &[FindingData],
+        suppressed: &SuppressedCounts,
+    ) {
+        self.store(path, findings, suppressed);
+    }
+
+    fn store(&self, path: &Path, findings: &[FindingData], suppressed: &SuppressedCounts) {
+        let Some(state) = self.current.get(path) else {
+            return;
+        };
+        let cached = CachedFile {
+            state: state.clone(),
+            suppressed: suppressed
+                .iter()
+                .filter(|(_, count)| **count > 0)
+                .map(|(detector, count)| (detector.to_string(), *count))
+                .collect(),
+            findings: findings
+                .iter()
+                .map(|finding| CachedFinding {
+                    detector: finding.detector_id.to_string(),
+                    location: finding.location.clone(),
+                })
+                .collect(),
+        };
+        self.fresh
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(path_key(path), cached);
+    }
+
+    /// Number of files analyzed and restored from the cache in this run.
+    pub fn counts(&self) -> (usize, usize) {
+        (
+            self.analyzed.load(Ordering::Relaxed),
+            self.restored.load(Ordering::Relaxed),
+        )
+    }
+
+    /// Replace the cache file with the entries of this run. Files no longer analyzed are
+    /// dropped.
+    pub fn save(self) -> io::Result<()> {
+        if let Some(root) = self.path.parent().and_then(Path::parent) {
+            create_cache_dir(root)?;
+        }
+        let contents = CacheContents {
+            key: self.key,
+            detectors: self.detectors,
+            files: self.fresh.into_inner().unwrap_or_else(|e| e.into_inner()),
+        };
+        let mut file = AtomicFile::create(&self.path)?;
+        serde_json::to_writer(&mut file, &contents)?;
+        file.commit()
+    }
+}
+
+/// Hash of the settings that change what detectors report or how files are parsed and
+/// resolved. Detectors are versioned one by one in the cache instead, and settings applied to
+/// the findings afterwards (filters, path rules, report options) are not part of it.
+pub fn cache_key(config: &Config, remappings: &HashMap<String, PathBuf>) -> String {
+    let remappings: BTreeMap<&String, &PathBuf> = remappings.iter().collect();
+    let key = serde_json::json!({
+        "ast_source": config.ast_source,
+        "protocol": config.protocol,
+        "detector_settings": config.detectors,
+        "remappings": remappings,
+    });
+    format!("{:x}", Sha256::digest(key.to_string()))
+}
+
+/// Files whose content can change the findings of `file`, besides the file itself.
+fn dependencies_of(file: &SolidityFile, context: &AnalysisContext) -> HashSet<PathBuf> {
+    let mut dependencies = HashSet::new();
+
+    // Imports, followed through the files that were loaded
+    let mut pending = vec![file];
+    let mut visited = HashSet::from([file.path.clone()]);
+    while let Some(current) = pending.pop() {
+        let Some(resolver) = context.get_import_resolver() else {
+            break;
+        };
+        for import in &current.imports {
+            let Ok(resolved) = resolver.resolve_import(&import.import_path, &current.path) else {
+                continue;
+            };
+            if !visited.insert(resolved.clone()) {
+                continue;
+            }
+            if let Some(imported) = context.get_file_by_path(&resolved) {
+                pending.push(imported);
+            }
+            dependencies.insert(resolved);
+        }
+    }
+
+    // Contracts this file's contracts inherit from, and contracts inheriting from them
+    let file_path = file.path.to_string_lossy();
+    let own: HashSet<&String> = context
+        .contracts
+        .iter()
+        .filter(|(_, info)| info.file_path == file_path)
+        .map(|(name, _)| name)
+        .collect();
+    for info in context.contracts.values() {
+        if info.file_path == file_path {
+            dependencies.extend(
+                info.inheritance_chain
+                    .iter()
+                    .filter_map(|base| context.contracts.get(base))
+                    .map(|base| PathBuf::from(&base.file_path)),
+            );
+        } else if info.inheritance_chain.iter().any(|base| own.contains(base)) {
+            dependencies.insert(PathBuf::from(&info.file_path));
+        }
+    }
+
+    dependencies.remove(&file.path);
+    dependencies
+}
+
+/// SHA-256 of a file read from disk (contexts in low-memory mode hold no content), memoized
+/// across files.
+fn content_hash(path: &Path, hashes: &mut HashMap<PathBuf, Option<String>>) -> Option<String> {
+    hashes
+        .entry(path.to_path_buf())
+        .or_insert_with(|| {
+            fs::read(path)
+                .ok()
+                .map(|content| format!("{:x}", Sha256::digest(content)))
+        })
+        .clone()
+}
+
+fn path_key(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
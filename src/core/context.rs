@@ -1,10 +1,17 @@
 use crate::core::c3_linearization::c3_linearize;
 use crate::core::import_resolver::ImportResolver;
+use crate::core::project_detector::SkipGlobs;
+use crate::core::solc_ast::{self, SolcAstIndex};
 use crate::models::{
-    ContractInfo, EnumInfo, ErrorInfo, EventInfo, FunctionInfo, ModifierInfo, ScopeFiles,
-    SolidityFile, StateVariableInfo, StructInfo, TypeDefinitionInfo, UsingDirectiveInfo,
+    ContractInfo, ContractType, EnumInfo, ErrorInfo, EventInfo, FunctionInfo, FunctionType,
+    ModifierInfo, ScopeFiles, SolidityFile, StateVariableInfo, StructInfo, TypeDefinitionInfo,
+    UsingDirectiveInfo,
 };
-use solang_parser::parse;
+use crate::output::style::errln;
+use crate::output::{Progress, Verbosity};
+use crate::utils::gitignore::Gitignore;
+use crate::utils::path::{glob_base, is_glob_pattern, PathPatterns};
+use rayon::prelude::*;
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -15,6 +22,20 @@ pub struct AnalysisContext {
     pub contracts: HashMap<String, ContractInfo>, // "file_path:contract_name" -> info
     pub missing_contracts: HashSet<String>,
     import_resolver: Option<ImportResolver>,
+    low_memory: bool,
+    verbosity: Verbosity,
+    solc_asts: Option<SolcAstIndex>,
+    skip: Option<SkipGlobs>,
+    /// Canonical build output directories left out of directory walks
+    build_dirs: Vec<PathBuf>,
+    /// Canonical project root whose `.gitignore` files apply to directory walks
+    gitignore_root: Option<PathBuf>,
+}
+
+impl Default for AnalysisContext {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl AnalysisContext {
@@ -24,9 +45,52 @@ impl AnalysisContext {
             contracts: HashMap::new(),
             missing_contracts: HashSet::new(),
             import_resolver: None,
+            low_memory: false,
+            verbosity: Verbosity::default(),
+            solc_asts: None,
+            skip: None,
+            build_dirs: Vec::new(),
+            gitignore_root: None,
         }
     }
 
+    /// Keep only metadata for loaded files, dropping their source text and AST.
+    pub fn set_low_memory(&mut self, enabled: bool) {
+        self.low_memory = enabled;
+    }
+
+    /// Show a parsing counter while loading files, and with `Verbose` print the inheritance of
+    /// every contract once it is resolved.
+    pub fn set_verbosity(&mut self, verbosity: Verbosity) {
+        self.verbosity = verbosity;
+    }
+
+    /// Take contract metadata from compiler ASTs where available.
+    pub fn set_solc_asts(&mut self, index: SolcAstIndex) {
+        self.solc_asts = Some(index);
+    }
+
+    /// Leave files matching the project's `skip` globs out of directory walks. Files passed
+    /// to [`load_files`](Self::load_files) by name are loaded regardless.
+    pub fn set_skip_globs(&mut self, skip: Option<SkipGlobs>) {
+        self.skip = skip;
+    }
+
+    /// Leave these directories (build output, installed packages) out of directory walks. A
+    /// walk starting inside one still covers it.
+    pub fn set_build_dirs(&mut self, dirs: &[PathBuf]) {
+        self.build_dirs = dirs
+            .iter()
+            .filter_map(|d| fs::canonicalize(d).ok())
+            .collect();
+    }
+
+    /// Leave paths matched by the `.gitignore` files at and below `root` out of directory
+    /// walks, or stop doing so with `None`.
+    pub fn set_gitignore_root(&mut self, root: Option<&Path>) {
+        self.gitignore_root = root.and_then(|root| fs::canonicalize(root).ok());
+    }
+
     /// Set up import resolver with remappings
     pub fn set_import_resolver(
         &mut self,
@@ -38,90 +102,213 @@ impl AnalysisContext {
         self.import_resolver = Some(resolver);
     }
 
+    pub fn get_import_resolver(&self) -> Option<&ImportResolver> {
+        self.import_resolver.as_ref()
+    }
+
     /// Get mutable reference to import resolver
     pub fn get_import_resolver_mut(&mut self) -> Option<&mut ImportResolver> {
         self.import_resolver.as_mut()
     }
 
     /// Loads files from specified paths, handling directories recursively.
-    /// Excludes paths that match any of the exclude patterns.
+    /// Scope and exclude entries may be glob patterns (see `PathPatterns`). Excludes paths
+    /// that match any of the exclude entries, and files in directories that match the skip
+    /// globs, build directories or `.gitignore` rules.
+    ///
+    /// Files are read and parsed in parallel, then added in path order so contract
+    /// registration and inheritance resolution do not depend on thread scheduling. Files
+    /// that cannot be read or parsed are skipped with a warning.
     pub fn load_files(&mut self, paths: &[PathBuf], exclude: &[PathBuf]) -> Result<(), String> {
-        // Canonicalize exclude paths for consistent matching
-        let canonical_exclude: Vec<PathBuf> = exclude
-            .iter()
-            .filter_map(|p| fs::canonicalize(p).ok())
-            .collect();
+        let candidates = self.collect_files(paths, exclude)?;
+        self.load_paths(&candidates, true);
+        Ok(())
+    }
 
+    /// Solidity files under `paths`, sorted and deduplicated.
+    pub fn collect_files(
+        &self,
+        paths: &[PathBuf],
+        exclude: &[PathBuf],
+    ) -> Result<Vec<PathBuf>, String> {
+        let exclude = PathPatterns::new(exclude, "exclude")?;
+        let mut gitignore = self.gitignore_root.as_deref().map(Gitignore::new);
+
+        let mut files = Vec::new();
         for path in paths {
+            if is_glob_pattern(path) {
+                self.collect_glob(path, &exclude, &mut gitignore, &mut files)?;
+                continue;
+            }
             if !path.exists() {
-                eprintln!("Warning: Path does not exist: {}", path.display());
+                errln!("Warning: Path does not exist: {}", path.display());
                 continue;
             }
 
-            if self.is_excluded(path, &canonical_exclude) {
+            if self.is_excluded(path, &exclude) {
                 continue;
             }
 
             if path.is_dir() {
-                self.load_directory(path, &canonical_exclude)?;
+                self.collect_directory(path, &exclude, &mut gitignore, &mut files)?;
             } else if path.is_file() && is_solidity_file(path) {
-                self.load_file(path)?;
+                files.push(path.clone());
             }
         }
+
+        files.sort();
+        files.dedup();
+        Ok(files)
+    }
+
+    /// Collects the Solidity files matching a scope glob, or inside directories matching it.
+    fn collect_glob(
+        &self,
+        pattern: &Path,
+        exclude: &PathPatterns,
+        gitignore: &mut Option<Gitignore>,
+        files: &mut Vec<PathBuf>,
+    ) -> Result<(), String> {
+        let scope = PathPatterns::new(&[pattern.to_path_buf()], "scope")?;
+        let base = glob_base(pattern);
+        let mut found = Vec::new();
+        if base.is_dir() && !self.is_excluded(&base, exclude) {
+            self.collect_directory(&base, exclude, gitignore, &mut found)?;
+        }
+        found.retain(|file| {
+            file.ancestors()
+                .take_while(|dir| dir.starts_with(&base))
+                .any(|dir| scope.is_match(dir))
+        });
+        if found.is_empty() {
+            errln!(
+                "Warning: No Solidity files match scope pattern: {}",
+                pattern.display()
+            );
+        }
+        files.append(&mut found);
         Ok(())
     }
 
-    /// Recursively loads Solidity files from a directory.
-    /// Excludes paths that match any of the exclude patterns.
-    fn load_directory(&mut self, dir_path: &Path, exclude: &[PathBuf]) -> Result<(), String> {
+    /// Recursively collects Solidity files from a directory.
+    /// Excludes paths that match any of the exclude entries.
+    fn collect_directory(
+        &self,
+        dir_path: &Path,
+        exclude: &PathPatterns,
+        gitignore: &mut Option<Gitignore>,
+        files: &mut Vec<PathBuf>,
+    ) -> Result<(), String> {
+        let canonical_dir = fs::canonicalize(dir_path).ok();
+        if let (Some(gitignore), Some(dir)) = (gitignore.as_mut(), &canonical_dir) {
+            gitignore.load_dir(dir);
+        }
         let entries =
             fs::read_dir(dir_path).map_err(|e| format!("Failed to read directory: {}", e))?;
         for entry in entries {
             let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
             let path = entry.path();
+            let is_dir = path.is_dir();
 
-            if self.is_excluded(&path, exclude) {
+            if self.is_excluded(&path, exclude) || self.is_skipped(&path) {
                 continue;
             }
+            if let Some(dir) = &canonical_dir {
+                if self.is_ignored(&dir.join(entry.file_name()), is_dir, gitignore.as_ref()) {
+                    continue;
+                }
+            }
 
-            if path.is_dir() {
-                self.load_directory(&path, exclude)?;
+            if is_dir {
+                self.collect_directory(&path, exclude, gitignore, files)?;
             } else if path.is_file() && is_solidity_file(&path) {
-                self.load_file(&path)?;
+                files.push(path);
             }
         }
         Ok(())
     }
 
-    /// Returns true if the path matches any exclude pattern.
-    fn is_excluded(&self, path: &Path, exclude: &[PathBuf]) -> bool {
-        let canonical_path = match fs::canonicalize(path) {
-            Ok(p) => p,
-            Err(_) => return false,
+    /// Returns true if the path matches any exclude entry.
+    fn is_excluded(&self, path: &Path, exclude: &PathPatterns) -> bool {
+        exclude.is_match(path)
+    }
+
+    /// Returns true if the path matches a skip glob.
+    fn is_skipped(&self, path: &Path) -> bool {
+        let Some(skip) = &self.skip else {
+            return false;
         };
-        exclude
-            .iter()
-            .any(|exclude_pattern| canonical_path.starts_with(exclude_pattern))
+        fs::canonicalize(path).is_ok_and(|canonical_path| skip.is_match(&canonical_path))
     }
 
-    /// Loads and parses a single Solidity file, extracting metadata.
-    fn load_file(&mut self, file_path: &Path) -> Result<(), String> {
-        let content = fs::read_to_string(file_path)
-            .map_err(|e| format!("Failed to read file '{}': {}", file_path.display(), e))?;
+    /// Returns true if a directory walk leaves out this canonical path: a build directory, or
+    /// a path matched by the project's `.gitignore` files.
+    fn is_ignored(&self, canonical: &Path, is_dir: bool, gitignore: Option<&Gitignore>) -> bool {
+        (is_dir && self.build_dirs.iter().any(|dir| dir == canonical))
+            || gitignore.is_some_and(|gitignore| gitignore.is_ignored(canonical, is_dir))
+    }
 
-        let parse_result = parse(&content, 0);
-        if let Err(errors) = &parse_result {
-            return Err(format!(
-                "Failed to parse '{}': {:?}",
-                file_path.display(),
-                errors
-            ));
+    /// Reads and parses `paths` (on the rayon pool when `parallel`), then adds them in order.
+    /// Paths that fail to load are skipped with a warning.
+    fn load_paths(&mut self, paths: &[PathBuf], parallel: bool) {
+        // Compiler metadata is applied while adding, and needs the source to map locations
+        let release_early = self.low_memory && self.solc_asts.is_none();
+        let progress = Progress::new("Parsing", paths.len(), self.verbosity);
+        let parse = |path: &PathBuf| {
+            let parsed = SolidityFile::from_path(path).map(|mut file| {
+                if release_early {
+                    file.release_source();
+                }
+                file
+            });
+            progress.tick();
+            parsed
+        };
+        let parsed: Vec<Result<SolidityFile, String>> = if parallel {
+            paths.par_iter().map(parse).collect()
+        } else {
+            paths.iter().map(parse).collect()
+        };
+        drop(progress);
+
+        for (path, file) in paths.iter().zip(parsed) {
+            if let Err(e) = self.add_file(path, file) {
+                errln!("Warning: {}. Skipping the file.", e);
+            }
         }
+    }
 
-        let (source_unit, _comments) = parse_result.unwrap();
+    /// Loads and parses a single Solidity file, extracting metadata.
+    fn load_file(&mut self, file_path: &Path) -> Result<(), String> {
+        self.add_file(file_path, SolidityFile::from_path(file_path))
+    }
 
-        let mut solidity_file = SolidityFile::new(file_path.to_path_buf(), content, source_unit);
-        solidity_file.extract_metadata();
+    /// Adds a parsed Solidity file, taking contract metadata from its compiler AST if available.
+    fn add_file(
+        &mut self,
+        file_path: &Path,
+        parsed: Result<SolidityFile, String>,
+    ) -> Result<(), String> {
+        let solc_ast = self
+            .solc_asts
+            .as_mut()
+            .and_then(|index| index.ast_for(file_path));
+
+        let mut solidity_file = match (parsed, solc_ast) {
+            (Ok(mut file), Some(ast)) => {
+                solc_ast::apply_metadata(&mut file, ast);
+                file
+            }
+            (Ok(file), None) => file,
+            (Err(e), Some(ast)) => {
+                errln!("Warning: {}. Using solc AST metadata only.", e);
+                solc_ast::metadata_only_file(file_path, ast)?
+            }
+            (Err(e), None) => return Err(e),
+        };
+        if self.low_memory {
+            solidity_file.release_source();
+        }
         self.files.push(solidity_file);
         Ok(())
     }
@@ -139,9 +326,36 @@ impl AnalysisContext {
         }
 
         self.resolve_inheritance()?;
+        if self.verbosity == Verbosity::Verbose {
+            errln!("Inheritance:");
+            for line in self.inheritance_summary() {
+                errln!("  {}", line);
+            }
+        }
         Ok(())
     }
 
+    /// `file:Contract -> Base, ...` for every contract with bases, most derived base first,
+    /// sorted by qualified name.
+    pub fn inheritance_summary(&self) -> Vec<String> {
+        let mut lines: Vec<String> = self
+            .contracts
+            .iter()
+            .filter(|(_, contract)| !contract.inheritance_chain.is_empty())
+            .map(|(qualified_name, contract)| {
+                let bases: Vec<&str> = contract
+                    .inheritance_chain
+                    .iter()
+                    .rev()
+                    .map(|base| self.contracts.get(base).map_or(base.as_str(), |c| &c.name))
+                    .collect();
+                format!("{} -> {}", qualified_name, bases.join(", "))
+            })
+            .collect();
+        lines.sort();
+        lines
+    }
+
     fn resolve_inheritance(&mut self) -> Result<(), String> {
         let mut visited = HashSet::new();
         let mut temp_visited = HashSet::new();
@@ -424,6 +638,55 @@ impl AnalysisContext {
         result
     }
 
+    /// Functions available on a contract after inheritance, one per signature, each resolved
+    /// to the most derived implementation in the linearized chain. Inherited constructors
+    /// are skipped.
+    pub fn get_effective_functions(&self, qualified_name: &str) -> Vec<EffectiveFunction<'_>> {
+        let mut result: Vec<EffectiveFunction> = Vec::new();
+
+        let contract = match self.contracts.get(qualified_name) {
+            Some(c) => c,
+            None => return result,
+        };
+
+        // Most base first, so later definitions override earlier ones
+        let definers = contract
+            .inheritance_chain
+            .iter()
+            .map(String::as_str)
+            .chain(std::iter::once(qualified_name));
+        for defined_in in definers {
+            let Some(definer) = self.contracts.get(defined_in) else {
+                continue;
+            };
+            for function in &definer.function_definitions {
+                if function.function_type == FunctionType::Constructor
+                    && defined_in != qualified_name
+                {
+                    continue;
+                }
+
+                let signature = function_signature(function);
+                match result.iter_mut().find(|f| f.signature == signature) {
+                    Some(effective) => {
+                        let replaced =
+                            std::mem::replace(&mut effective.defined_in, defined_in.to_string());
+                        effective.overrides.push(replaced);
+                        effective.function = function;
+                    }
+                    None => result.push(EffectiveFunction {
+                        signature,
+                        function,
+                        defined_in: defined_in.to_string(),
+                        overrides: Vec::new(),
+                    }),
+                }
+            }
+        }
+
+        result
+    }
+
     /// Get all enums from a contract including inherited ones.
     pub fn get_all_enums(&self, qualified_name: &str) -> Vec<&EnumInfo> {
         let mut result = Vec::new();
@@ -557,6 +820,74 @@ impl AnalysisContext {
         result
     }
 
+    /// Functions that `value.member(...)` inside a contract can resolve to through `using ...
+    /// for` directives: the contract's own and inherited directives, plus the file-level ones
+    /// of its file. `using L for T` attaches the functions of library `L` when it is among the
+    /// loaded files, `using {f} for T` the free function `f` of the contract's file. The
+    /// receiver's type is not checked against the directives' target types.
+    pub fn resolve_using_function(
+        &self,
+        qualified_name: &str,
+        member: &str,
+    ) -> Vec<UsingFunction<'_>> {
+        let mut result: Vec<UsingFunction> = Vec::new();
+
+        let contract = match self.contracts.get(qualified_name) {
+            Some(c) => c,
+            None => return result,
+        };
+        let file = self.get_file_by_path(Path::new(&contract.file_path));
+
+        let mut directives = self.get_all_using_directives(qualified_name);
+        if let Some(file) = file {
+            directives.extend(file.using_directives.iter());
+        }
+
+        for directive in directives {
+            let candidates: Vec<(&FunctionInfo, Option<&str>)> = match &directive.library_name {
+                Some(library_name) => {
+                    let library = self
+                        .contracts
+                        .get(&self.get_qualified_name_for_contract(library_name))
+                        .filter(|c| matches!(c.contract_type, ContractType::Library));
+                    library
+                        .into_iter()
+                        .flat_map(|library| {
+                            library
+                                .function_definitions
+                                .iter()
+                                .map(move |f| (f, Some(library.name.as_str())))
+                        })
+                        .filter(|(f, _)| f.name == member)
+                        .collect()
+                }
+                None if directive.functions.iter().any(|f| f == member) => file
+                    .into_iter()
+                    .flat_map(|file| file.functions.iter())
+                    .filter(|f| f.name == member)
+                    .map(|f| (f, None))
+                    .collect(),
+                None => Vec::new(),
+            };
+
+            for (function, library) in candidates {
+                if result
+                    .iter()
+                    .any(|existing| std::ptr::eq(existing.function, function))
+                {
+                    continue;
+                }
+                result.push(UsingFunction {
+                    function,
+                    library,
+                    target_type: directive.target_type.as_deref(),
+                });
+            }
+        }
+
+        result
+    }
+
     pub fn get_file_by_path(&self, path: &Path) -> Option<&SolidityFile> {
         self.files.iter().find(|f| f.path == path)
     }
@@ -613,9 +944,222 @@ impl AnalysisContext {
     }
 }
 
+/// A function as seen on a derived contract, after override resolution.
+#[derive(Debug, Clone)]
+pub struct EffectiveFunction<'a> {
+    /// Canonical signature, e.g. `transfer(address,uint256)`
+    pub signature: String,
+    /// The implementation that wins
+    pub function: &'a FunctionInfo,
+    /// Qualified name of the contract the winning implementation is declared in
+    pub defined_in: String,
+    /// Qualified names of bases whose versions it replaces, most base first
+    pub overrides: Vec<String>,
+}
+
+/// A library or free function attached to a type by a `using ... for` directive.
+#[derive(Debug, Clone)]
+pub struct UsingFunction<'a> {
+    pub function: &'a FunctionInfo,
+    /// Library declaring the function, `None` for free functions
+    pub library: Option<&'a str>,
+    /// Type named in the directive, `None` for `using ... for *`
+    pub target_type: Option<&'a str>,
+}
+
+/// `name(type,...)`, with `constructor`, `receive` and `fallback` for the special functions.
+pub fn function_signature(function: &FunctionInfo) -> String {
+    let name = match function.function_type {
+        FunctionType::Function => function.name.as_str(),
+        FunctionType::Constructor => "constructor",
+        FunctionType::Receive => "receive",
+        FunctionType::Fallback => "fallback",
+    };
+    let types: Vec<&str> = function
+        .parameters
+        .iter()
+        .map(|p| p.type_name.as_str())
+        .collect();
+    format!("{}({})", name, types.join(","))
+}
+
 /// Checks if a path points to a Solidity file.
 fn is_solidity_file(path: &Path) -> bool {
     path.extension()
         .map(|ext| ext.to_string_lossy().to_lowercase() == "sol")
         .unwrap_or(false)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::path::to_forward_slashes;
+    use crate::utils::test_utils::write_fixture_project;
+
+    fn load(root: &Path, parallel: bool) -> AnalysisContext {
+        let mut context = AnalysisContext::new();
+        let paths = context.collect_files(&[root.to_path_buf()], &[]).unwrap();
+        context.load_paths(&paths, parallel);
+        context.build_cache().unwrap();
+        context
+    }
+
+    #[test]
+    fn test_parallel_load_matches_serial() {
+        // 200 files in nested directories, each contract inheriting the previous one and a
+        // shared base, so inheritance chains span many files
+        let sources: Vec<(String, String)> = (0..200)
+            .map(|i| {
+                let parent = if i == 0 {
+                    "Base".to_string()
+                } else {
+                    format!("Base, C{}", i - 1)
+                };
+                let source = format!(
+                    "pragma solidity ^0.8.0;\n\ncontract C{i} is {parent} {{\n    uint256 v{i};\n\n    function f{i}() external {{ v{i} += 1; }}\n}}\n"
+                );
+                (format!("src/m{}/C{}.sol", i % 7, i), source)
+            })
+            .chain(std::iter::once((
+                "src/Base.sol".to_string(),
+                "pragma solidity ^0.8.0;\n\ncontract Base {}\n".to_string(),
+            )))
+            .collect();
+        let files: Vec<(&str, &str)> = sources
+            .iter()
+            .map(|(path, source)| (path.as_str(), source.as_str()))
+            .collect();
+        let root = write_fixture_project("parallel-load", &files);
+
+        let serial = load(&root, false);
+        let parallel = load(&root, true);
+
+        assert_eq!(parallel.files.len(), 201);
+        let paths = |context: &AnalysisContext| -> Vec<PathBuf> {
+            context.files.iter().map(|f| f.path.clone()).collect()
+        };
+        assert_eq!(paths(&parallel), paths(&serial));
+
+        assert_eq!(parallel.contracts.len(), 201);
+        assert_eq!(parallel.contracts.len(), serial.contracts.len());
+        for (name, contract) in &serial.contracts {
+            let other = &parallel.contracts[name];
+            assert_eq!(
+                other.inheritance_chain, contract.inheritance_chain,
+                "{}",
+                name
+            );
+            assert_eq!(
+                other.function_definitions.len(),
+                contract.function_definitions.len()
+            );
+        }
+        let last = parallel.get_qualified_name_for_contract("C199");
+        assert_eq!(parallel.contracts[&last].inheritance_chain.len(), 200);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_collect_files_with_globs() {
+        let root = write_fixture_project(
+            "collect-globs",
+            &[
+                ("src/Vault.sol", ""),
+                ("src/mocks/MockToken.sol", ""),
+                ("src/core/Pool.sol", ""),
+                ("src/core/test/Helper.sol", ""),
+                ("src/core/test/nested/Other.sol", ""),
+                ("contracts/Router.sol", ""),
+                ("contracts/Router.t.sol", ""),
+            ],
+        );
+        let relative = |files: Vec<PathBuf>| -> Vec<String> {
+            files
+                .iter()
+                .map(|f| to_forward_slashes(f.strip_prefix(&root).unwrap()))
+                .collect()
+        };
+        let context = AnalysisContext::new();
+
+        // A literal scope entry next to a glob one, excludes mixing both kinds
+        let files = context
+            .collect_files(
+                &[root.join("src"), root.join("contracts/*.sol")],
+                &[
+                    root.join("**/mocks/**"),
+                    root.join("src/**/test/*.sol"),
+                    root.join("contracts/Router.t.sol"),
+                ],
+            )
+            .unwrap();
+        assert_eq!(
+            relative(files),
+            [
+                "contracts/Router.sol",
+                "src/Vault.sol",
+                "src/core/Pool.sol",
+                "src/core/test/nested/Other.sol",
+            ]
+        );
+
+        // Directories matched by a scope glob contribute all their files
+        let files = context
+            .collect_files(&[root.join("src/**/test")], &[])
+            .unwrap();
+        assert_eq!(
+            relative(files),
+            ["src/core/test/Helper.sol", "src/core/test/nested/Other.sol"]
+        );
+
+        let err = context
+            .collect_files(&[root.join("src")], &[PathBuf::from("src/**/[")])
+            .unwrap_err();
+        assert!(err.starts_with("Invalid exclude pattern"), "{}", err);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_load_files_skips_ignored_and_unparsable_files() {
+        let root = write_fixture_project(
+            "ignored-files",
+            &[
+                (".gitignore", "generated/\n"),
+                ("src/Vault.sol", "contract Vault {}"),
+                ("src/Broken.sol", "contract Broken {"),
+                ("src/generated/Gen.sol", "contract Gen {}"),
+                ("out/Vault.sol", "contract Artifact {}"),
+                ("lib/dep/.gitignore", "*.sol\n!Dep.sol\n"),
+                ("lib/dep/Dep.sol", "contract Dep {}"),
+                ("lib/dep/Other.sol", "contract Other {}"),
+            ],
+        );
+        let names = |context: &AnalysisContext| -> Vec<String> {
+            let mut names: Vec<String> =
+                context.contracts.values().map(|c| c.name.clone()).collect();
+            names.sort();
+            names
+        };
+
+        let mut context = AnalysisContext::new();
+        context.set_build_dirs(&[root.join("out")]);
+        context.set_gitignore_root(Some(&root));
+        context
+            .load_files(std::slice::from_ref(&root), &[])
+            .unwrap();
+        context.build_cache().unwrap();
+        assert_eq!(names(&context), ["Dep", "Vault"]);
+
+        // A walk starting inside a build directory still covers it
+        let mut context = AnalysisContext::new();
+        context.set_build_dirs(&[root.join("out")]);
+        context
+            .load_files(&[root.join("out"), root.join("src")], &[])
+            .unwrap();
+        context.build_cache().unwrap();
+        assert_eq!(names(&context), ["Artifact", "Gen", "Vault"]);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
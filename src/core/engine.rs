@@ -1,15 +1,32 @@
-use crate::config::Config;
+use crate::config::{Config, PathRule};
+use crate::core::cache::{cache_key, FindingsCache};
 use crate::core::context::AnalysisContext;
+use crate::core::error::WeaselError;
 use crate::core::processor::{AnalysisResults, Processor};
-use crate::core::project_detector::{ProjectConfig, ProjectType};
+use crate::core::project_detector::{ProjectConfig, ProjectType, SkipGlobs};
 use crate::core::registry::DetectorRegistry;
+use crate::core::solc_ast::{AstSource, SolcAstIndex};
 use crate::core::visitor::ASTVisitor;
 use crate::detectors::Detector;
-use crate::models::{Finding, Report};
+use crate::models::{
+    ContractInfo, Finding, FindingData, FunctionType, FunctionVisibility, Location, Report,
+    ScopeEntry, StreamedFinding,
+};
+use crate::output::style::errln;
+use crate::output::{Progress, Verbosity};
+use crate::utils::fsx::CACHE_DIR;
+use crate::utils::nsloc::{code_lines, count_nsloc};
+use crate::utils::path::{glob_base, normalize_report_path, PathBase};
+use crate::utils::source_context::SourceContext;
+use crate::utils::source_link::SourceLinker;
 use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+/// Receives one file's findings in report form, see [`AnalysisEngine::analyze_streaming`].
+pub type StreamedFindingsSink<'a> = dyn Fn(&[StreamedFinding]) + Sync + 'a;
+
 pub struct AnalysisEngine {
     context: AnalysisContext,
     registry: DetectorRegistry,
@@ -17,6 +34,20 @@ pub struct AnalysisEngine {
     processor: Processor,
     config: Config,
     excluded_detectors: HashSet<String>,
+    project_root: PathBuf,
+    remappings: HashMap<String, PathBuf>,
+    /// Configured excludes plus the project's test/script directories
+    exclude: Vec<PathBuf>,
+    /// Effective scope of the last `load_project`
+    scope: Vec<PathBuf>,
+    /// Whether a run with `cache` enabled may write `.weasel-cache/findings.json`
+    cache_writable: bool,
+    /// Files analyzed and restored from the cache by the last run, if it used the cache
+    cache_counts: Option<(usize, usize)>,
+    /// Names of the contracts findings are reported in; empty reports every contract
+    contract_filter: Vec<String>,
+    /// Definitions of the `contract_filter` contracts, resolved by the last run
+    contract_locations: Vec<Location>,
 }
 
 impl AnalysisEngine {
@@ -28,621 +59,379 @@ impl AnalysisEngine {
             processor: Processor::new(),
             config: config.clone(),
             excluded_detectors: Self::compute_excluded_detectors(config),
+            project_root: PathBuf::from("."),
+            remappings: HashMap::new(),
+            exclude: config.exclude.clone(),
+            scope: Vec::new(),
+            cache_writable: true,
+            cache_counts: None,
+            contract_filter: Vec::new(),
+            contract_locations: Vec::new(),
         }
     }
 
+    /// Allow or forbid writing the findings cache, e.g. when another process holds the
+    /// project lock. A read-only cache is still used to restore findings.
+    pub fn set_cache_writable(&mut self, writable: bool) {
+        self.cache_writable = writable;
+    }
+
+    /// Only report findings inside the contracts with these names. The whole scope is still
+    /// analyzed, so inherited code and cross-contract context are unchanged. A name that
+    /// matches no loaded contract fails the analysis.
+    pub fn set_contract_filter(&mut self, names: Vec<String>) {
+        self.contract_filter = names;
+    }
+
     fn compute_excluded_detectors(config: &Config) -> HashSet<String> {
         let mut excluded = config.protocol.get_excluded_detectors();
         excluded.extend(config.exclude_detectors.iter().cloned());
         excluded
     }
 
-    pub fn register_detector(&mut self, detector: Arc<dyn Detector>) {
+    /// Register a detector unless `min_severity`, `exclude_detectors` or `target_chains` leave
+    /// it out. Fails when a detector with the same id is already registered.
+    pub fn register_detector(&mut self, detector: Arc<dyn Detector>) -> Result<(), WeaselError> {
+        if self.is_enabled(detector.as_ref()) {
+            self.registry.register(detector)?;
+        }
+        Ok(())
+    }
+
+    /// `register_detector`, replacing a registered detector with the same id instead of
+    /// failing, e.g. to override a built-in one.
+    pub fn register_detector_replacing(&mut self, detector: Arc<dyn Detector>) {
+        if self.is_enabled(detector.as_ref()) {
+            self.registry.register_replacing(detector);
+        }
+    }
+
+    fn is_enabled(&self, detector: &dyn Detector) -> bool {
         let passes_severity = detector.severity().as_value() >= self.config.min_severity.as_value();
         let not_excluded = !self.excluded_detectors.contains(detector.id());
+        passes_severity && not_excluded && self.is_relevant_for_target_chains(detector)
+    }
+
+    /// A detector runs when no `target_chains` are configured, when it applies to every
+    /// chain, or when one of its relevant chains is targeted.
+    fn is_relevant_for_target_chains(&self, detector: &dyn Detector) -> bool {
+        let relevant = detector.relevant_chains();
+        self.config.target_chains.is_empty()
+            || relevant.is_empty()
+            || relevant.iter().any(|chain| {
+                self.config
+                    .target_chains
+                    .iter()
+                    .any(|target| target.eq_ignore_ascii_case(chain))
+            })
+    }
 
-        if passes_severity && not_excluded {
-            self.registry.register(detector);
+    /// Register a detector after applying its `[detectors.<id>]` settings, if any.
+    pub fn register_configurable_detector<D: Detector>(
+        &mut self,
+        mut detector: D,
+    ) -> Result<(), WeaselError> {
+        if let Some(settings) = self.config.detectors.get(detector.id()) {
+            detector.configure(settings);
         }
+        self.register_detector(Arc::new(detector))
     }
 
+    /// Register every built-in detector, applying its `[detectors.<id>]` settings, if any.
+    /// Built-ins whose id is already taken are skipped with a warning; use
+    /// `register_detector_replacing` to override one on purpose.
     pub fn register_built_in_detectors(&mut self) {
-        // High severity detectors
-        self.register_detector(Arc::new(
-            crate::detectors::high::ComparisonWithoutEffectDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::high::DelegatecallInLoopDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::high::CurveSpotPriceOracleDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::high::MsgValueInLoopDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::high::WstethStethPerTokenUsageDetector::default(),
-        ));
-
-        // Medium severity detectors
-        self.register_detector(Arc::new(
-            crate::detectors::medium::BlockNumberL2Detector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::medium::CentralizationRiskDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::medium::ChainlinkStalePriceDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::medium::DeprecatedChainlinkFunctionDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::medium::DeprecatedTransferDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::medium::DirectSupportsInterfaceDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::medium::Eip712ComplianceDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::medium::FeeOnTransferDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::medium::L2SequencerCheckDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::medium::LibraryFunctionVisibilityDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::medium::NftMintAsymmetryDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::medium::SoladySafeTransferDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::medium::SolmateSafeTransferDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::medium::TxOriginUsageDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::medium::UnboundedFeeDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::medium::UncheckedLowLevelCallDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::medium::UncheckedTransferDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::medium::UnsafeApproveDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::medium::UnsafeErc20OperationsDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::medium::UnsafeMintDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::medium::UnsafeTransferFromDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::medium::UsdtAllowanceDetector::default(),
-        ));
-
-        // Low severity detectors
-        self.register_detector(Arc::new(
-            crate::detectors::low::AssemblyOptimizerBugDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::BlockTimestampDeadlineDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::ConstantDecimalsDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::CurveCalcTokenAmountDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::DecimalsTypeDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::DeprecatedAbiEncoderV2Detector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::DeprecatedApproveDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::DeprecatedSafeApproveDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::DeprecatedSetupRoleDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::DivisionBeforeMultiplicationDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::DivisionByZeroDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::DivisionRoundingDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::DomainSeparatorReplayDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::DuplicateImportDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::EcrecoverMalleabilityDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::EmptyFunctionBodyDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::EmptyEtherReceiverDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::Erc20DecimalsDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::Erc20SymbolNotStandardDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::ExternalCallInLoopDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::FallbackLackingPayableDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::InitializerFrontrunDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::InitializerOnInternalDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::LackOfSlippageCheckDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::LargeApprovalDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::LowLevelCallGasGriefDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::MintBurnAddressValidationDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::MissingGapStorageDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::MissingZeroAddressValidationDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::NftHardForkDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::Push0OpcodeDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::RenounceWhilePausedDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::SweepTokenAccountingDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::TwoStepOwnershipTransferDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::UnlimitedGasCallDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::UnspecificPragmaDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::UnsafeAbiEncodePackedDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::UnsafeDowncastDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::UnsafeIntCastDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::UnsafeIntToUintCastDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::UninitializedImplementationDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::UninitializedUpgradeableDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::UnsafeLowLevelCallDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::UpgradableTokenInterfaceDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::Year365DaysDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::low::ZeroValueTransferDetector::default(),
-        ));
-
-        // Gas detectors
-        self.register_detector(Arc::new(
-            crate::detectors::gas::AddressThisPrecalculationDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::gas::AssemblyAbiDecodeDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::gas::AddressZeroCheckDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::gas::AssemblyStorageWriteDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::gas::ArrayCompoundAssignmentDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::gas::ArrayLengthInLoopDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::gas::BooleanComparisonDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::gas::CompoundAssignmentDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::gas::MsgSenderUsageDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::gas::UnsafeArrayAccessDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::gas::VariableInsideLoopDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::gas::BoolStorageDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::gas::CachedConstantDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::gas::CachedImmutableDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::gas::CachedMsgSenderDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::gas::CalldataInsteadOfMemoryDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::gas::CombineMappingsDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::gas::CountDownLoopDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::gas::UncheckedLoopIncrementDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::gas::CustomErrorsInsteadOfRevertStringsDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::gas::CacheStateVariablesDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::gas::AvoidContractExistenceChecksDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::gas::UnnecessaryVariableCacheDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::gas::UseErc721aDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::gas::DefaultValueInitializationDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::gas::InternalFunctionNotCalledDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::gas::LongRevertStringDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::gas::PayableFunctionDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::gas::PostIncrementDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::gas::PrivateConstantsDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::gas::ShiftInsteadOfMulDivDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::gas::SplitRequireDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::gas::SuperfluousEventFieldsDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::gas::ShouldBeImmutableDetector::default(),
-        ));
-        self.register_detector(Arc::new(crate::detectors::gas::ThisUsageDetector::default()));
-        self.register_detector(Arc::new(
-            crate::detectors::gas::UintGtZeroDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::gas::Uint256ToBoolMappingDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::gas::WethAddressDefinitionDetector::default(),
-        ));
-
-        // NC detectors
-        self.register_detector(Arc::new(
-            crate::detectors::nc::AbstractInSeparateFileDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::ArrayIndicesDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::ArrayRangedGetterDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::BoolInitFalseDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::CombineMappingsDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::ComplexRequireDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::AbiEncodeCallDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::UnnecessaryAbiCoderV2Detector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::ConstantCaseDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::ConstantExpressionDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::ConstructorEmitEventDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::InitializerEmitEventDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::InitialismCapitalizationDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::ControlStructureStyleDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::ContractLayoutDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::CustomErrorNoArgsDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::MagicNumberDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::ManyFunctionParamsDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::ManyReturnValuesDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::MappingStyleDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::MissingErrorMessageDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::MissingEventSetterDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::MissingSpdxDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::MixedIntUintStyleDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::MultipleAbstractContractsDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::MultipleContractsDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::MultipleInterfacesDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::MultipleLibrariesDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::NamedFunctionArgsDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::NamedMappingsDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::NamedReturnsDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::NonReentrantBeforeModifiersDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::NumericUnderscoresDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::TwoStepCriticalChangesDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::TypeMaxLiteralDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::TypeMaxValueDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::UppercaseNonConstantDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::UnderscorePrefixDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::UnnamedRevertDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::UnusedOverrideParamsDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::UnusedPrivateFunctionDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::WhileTrueLoopDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::ZeroArgumentDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::ZeroInitializationDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::PreferRequireDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::PublicToExternalDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::PreferConcatDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::PreferCustomErrorsDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::PreferModifierDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::DefaultVisibilityDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::DeleteInsteadOfFalseDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::DeleteInsteadOfZeroDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::DeprecatedSafeMathDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::ConsoleLogImportDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::RenounceOwnershipDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::ScientificNotationDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::SensitiveTermsDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::SetterEventOldValueDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::SetterNoCheckDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::StringQuotesDetector::default(),
-        ));
-        self.register_detector(Arc::new(crate::detectors::nc::TimeUnitsDetector::default()));
-        self.register_detector(Arc::new(crate::detectors::nc::TodoLeftDetector::default()));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::DraftDependencyDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::DuplicateRequireDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::DuplicateStringLiteralDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::EcrecoverVCheckDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::EmptyBlocksDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::ErrorDefinitionNoArgsDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::RedundantElseDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::RedundantReturnDetector::default(),
-        ));
-        self.register_detector(Arc::new(crate::detectors::nc::EventArgsDetector::default()));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::EventMissingIndexedArgsDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::ExternalCallInModifierDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::FloatingPragmaDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::FunctionLengthDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::FunctionOrderDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::HardcodedAddressDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::InterfaceInSeparateFileDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::InterfaceNamingDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::InterfacesContractsSameFileDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::LargeLiteralDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::LibraryInSeparateFileDetector::default(),
-        ));
-        self.register_detector(Arc::new(crate::detectors::nc::LineLengthDetector::default()));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::LongCalculationsDetector::default(),
-        ));
-        self.register_detector(Arc::new(
-            crate::detectors::nc::ExplicitNumTypesDetector::default(),
-        ));
-    }
-
-    pub fn analyze(&mut self) -> Result<Report, String> {
-        // Determine project root - look for project markers
-        let project_root = self
+        use crate::detectors::{gas, high, low, medium, nc};
+
+        let detectors = [
+            high::all(),
+            medium::all(),
+            low::all(),
+            gas::all(),
+            nc::all(),
+        ];
+        for mut detector in detectors.into_iter().flatten() {
+            if let Some(settings) = self.config.detectors.get(detector.id()) {
+                detector.configure(settings);
+            }
+            if let Err(e) = self.register_detector(Arc::from(detector)) {
+                errln!("Warning: {}. Skipping the built-in detector.", e);
+            }
+        }
+    }
+
+    pub fn analyze(&mut self) -> Result<Report, WeaselError> {
+        self.run_analysis(None).map_err(WeaselError::Analysis)
+    }
+
+    /// `analyze`, also handing every file's findings to `on_file` in report form as soon as the
+    /// file has been traversed. Files complete in parallel and reach `on_file` in no particular
+    /// order (in file order with `low_memory`); within a file, findings are ordered by line.
+    pub fn analyze_streaming(
+        &mut self,
+        on_file: &StreamedFindingsSink,
+    ) -> Result<Report, WeaselError> {
+        self.run_analysis(Some(on_file))
+            .map_err(WeaselError::Analysis)
+    }
+
+    fn run_analysis(
+        &mut self,
+        on_file: Option<&StreamedFindingsSink>,
+    ) -> Result<Report, String> {
+        self.load_project()?;
+        self.resolve_contract_filter()?;
+        self.visitor = ASTVisitor::new();
+
+        if self.config.verbosity() == Verbosity::Verbose {
+            errln!(
+                "Files in scope: {}, contracts: {}",
+                self.context.files.len(),
+                self.context.contracts.len()
+            );
+        }
+
+        if !self.context.missing_contracts.is_empty() {
+            errln!(
+                "Warning: {} missing contracts detected:",
+                self.context.missing_contracts.len()
+            );
+            for missing in &self.context.missing_contracts {
+                errln!("  - {}", missing);
+            }
+            errln!("Run `weasel resolve --all` to trace the imports that failed to resolve.");
+        }
+
+        let detectors = self.registry.get_all();
+        for detector_arc in detectors.clone() {
+            detector_arc.register_callbacks(&mut self.visitor);
+        }
+
+        if self.config.cache {
+            let key = cache_key(&self.config, &self.remappings);
+            let mut cache = FindingsCache::load(&self.project_root, key);
+            cache.prepare(&self.context, &self.registry);
+            // New or changed detectors run on the files whose other findings are restored
+            let stale = cache.stale_detectors();
+            let stale_visitor = (!stale.is_empty()).then(|| {
+                let mut visitor = ASTVisitor::new();
+                for detector in detectors.iter().filter(|d| stale.contains(d.id())) {
+                    detector.clone().register_callbacks(&mut visitor);
+                }
+                visitor
+            });
+            self.processor.set_cache(Some(cache), stale_visitor);
+        }
+
+        let path_root = self.config.path_base.resolve_root(&self.project_root);
+        let linker = self.source_linker();
+        let source_context = self.source_context();
+        let progress = Progress::new(
+            "Analyzing",
+            self.context.files.len(),
+            self.config.verbosity(),
+        );
+        let sink = |_: usize, findings: &[FindingData]| {
+            progress.tick();
+            let Some(on_file) = on_file else {
+                return;
+            };
+            let streamed: Vec<StreamedFinding> = findings
+                .iter()
+                .filter(|finding| {
+                    self.in_contract_filter(&finding.location)
+                        && !self.is_filtered(finding.detector_id, &finding.location)
+                })
+                .filter_map(|finding| {
+                    let detector = self.registry.get(finding.detector_id)?;
+                    let location = &finding.location;
+                    Some(StreamedFinding {
+                        detector_id: finding.detector_id.to_string(),
+                        severity: detector.severity(),
+                        category: detector.category(),
+                        location: Location {
+                            file: normalize_report_path(&location.file, path_root.as_deref()),
+                            url: linker.as_ref().and_then(|l| l.url(&location.file, location.line)),
+                            context: source_context
+                                .as_ref()
+                                .and_then(|c| c.window(&location.file, location.line)),
+                            ..location.clone()
+                        },
+                    })
+                })
+                .collect();
+            on_file(&streamed);
+        };
+
+        let mut results = if self.config.low_memory {
+            self.processor.process_files_low_memory_streaming(
+                &self.context.files,
+                &self.visitor,
+                &self.context,
+                &sink,
+            )
+        } else {
+            self.processor.process_files_streaming(
+                &self.context.files,
+                &self.visitor,
+                &self.context,
+                &sink,
+            )
+        };
+        drop(progress);
+
+        if let Some(cache) = self.processor.take_cache() {
+            let (analyzed, restored) = cache.counts();
+            self.cache_counts = Some((analyzed, restored));
+            if !self.config.quiet {
+                errln!(
+                    "Cache: {} files analyzed, {} restored from {}",
+                    analyzed,
+                    restored,
+                    CACHE_DIR
+                );
+            }
+            if self.cache_writable {
+                if let Err(e) = cache.save() {
+                    errln!("Warning: Failed to write the analysis cache: {}", e);
+                }
+            }
+        }
+
+        let filtered = self.apply_finding_filters(&mut results);
+        let mut report =
+            self.generate_report_from_results(&results, linker.as_ref(), source_context.as_ref());
+        if !self.config.finding_filters.is_empty() || !self.config.path_rules.is_empty() {
+            report.add_metadata("Filtered Findings:", &filtered.to_string());
+        }
+        if results.suppressed > 0 {
+            report.add_metadata("Suppressed Findings:", &results.suppressed.to_string());
+        }
+        if !self.contract_filter.is_empty() {
+            report.add_metadata("Contracts:", &self.contract_filter.join(", "));
+        }
+
+        Ok(report)
+    }
+
+    /// Find the definitions of the `contract_filter` contracts among the loaded contracts.
+    fn resolve_contract_filter(&mut self) -> Result<(), String> {
+        self.contract_locations.clear();
+        for name in &self.contract_filter {
+            let before = self.contract_locations.len();
+            self.contract_locations.extend(
+                self.context
+                    .contracts
+                    .values()
+                    .filter(|contract| &contract.name == name)
+                    .map(|contract| contract.loc.clone()),
+            );
+            if self.contract_locations.len() == before {
+                let mut available: Vec<&str> = self
+                    .context
+                    .contracts
+                    .values()
+                    .map(|contract| contract.name.as_str())
+                    .collect();
+                available.sort_unstable();
+                available.dedup();
+                return Err(format!(
+                    "Unknown contract '{}'. Available contracts: {}",
+                    name,
+                    available.join(", ")
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether `location` is inside one of the `contract_filter` contracts, or no filter is set.
+    fn in_contract_filter(&self, location: &Location) -> bool {
+        self.contract_filter.is_empty()
+            || self.contract_locations.iter().any(|contract| {
+                contract.file == location.file
+                    && (contract.line..=contract.line_end.unwrap_or(contract.line))
+                        .contains(&location.line)
+            })
+    }
+
+    /// Whether a `[[finding_filters]]` entry, or the `[[path_rules]]` entry applying to its
+    /// file, drops this finding.
+    fn is_filtered(&self, detector_id: &str, location: &Location) -> bool {
+        if self
             .config
+            .finding_filters
+            .iter()
+            .any(|filter| filter.matches(detector_id, location))
+        {
+            return true;
+        }
+        if self.config.path_rules.is_empty() {
+            return false;
+        }
+        let Some(detector) = self.registry.get(detector_id) else {
+            return false;
+        };
+        let root = PathBase::Project.resolve_root(&self.project_root);
+        let file = normalize_report_path(&location.file, root.as_deref());
+        PathRule::for_file(&self.config.path_rules, &file)
+            .is_some_and(|rule| rule.drops(detector_id, &detector.severity()))
+    }
+
+    /// Drop findings outside the `contract_filter` contracts and those matched by
+    /// `[[finding_filters]]` and `[[path_rules]]`, before the report is assembled (and so before
+    /// sorting, metadata, source links and redaction). Returns how many the filters and rules
+    /// dropped.
+    fn apply_finding_filters(&self, results: &mut AnalysisResults) -> usize {
+        if !self.contract_filter.is_empty() {
+            for locations in results.findings_by_detector.values_mut() {
+                locations.retain(|location| self.in_contract_filter(location));
+            }
+            results
+                .findings_by_detector
+                .retain(|_, locations| !locations.is_empty());
+        }
+        if self.config.finding_filters.is_empty() && self.config.path_rules.is_empty() {
+            return 0;
+        }
+        let before = results.total_findings();
+        for (detector_id, locations) in results.findings_by_detector.iter_mut() {
+            locations.retain(|location| !self.is_filtered(detector_id, location));
+        }
+        results
+            .findings_by_detector
+            .retain(|_, locations| !locations.is_empty());
+        before - results.total_findings()
+    }
+
+    /// Load the project's files and build the contract cache, without running detectors.
+    /// Files loaded by an earlier call are dropped, so every analysis reads the sources anew.
+    pub fn load_project(&mut self) -> Result<(), String> {
+        self.context = AnalysisContext::new();
+        let scope = self.configure_project();
+        self.load_scope(&scope)?;
+        self.scope = scope;
+        self.context.build_cache()
+    }
+
+    /// Solidity files currently in the scope of the last `load_project`, after excludes, for
+    /// noticing files that were added or removed since.
+    pub fn scope_files(&self) -> Result<Vec<PathBuf>, String> {
+        self.context.collect_files(&self.scope, &self.exclude)
+    }
+
+    /// The directory holding a project marker (`foundry.toml`, a Hardhat or Truffle config) at
+    /// or above the first scope entry, else that entry's directory. Used by `configure_project`,
+    /// and by callers that need the root before it runs, like the project lock.
+    pub fn detect_project_root(&self) -> PathBuf {
+        self.config
             .scope
             .first()
             .and_then(|p| {
+                // Glob entries start from their literal leading directories. Relative
+                // entries are made absolute first: the parent of `src` is the empty path
+                let base = glob_base(p);
+                let p = &std::path::absolute(&base).unwrap_or(base);
                 // Start from the scope path and walk up to find project root
                 let mut current = if p.is_dir() {
                     p.clone()
@@ -677,11 +466,19 @@ impl AnalysisEngine {
                     p.parent().map(|parent| parent.to_path_buf())
                 }
             })
-            .unwrap_or_else(|| PathBuf::from("."));
+            .unwrap_or_else(|| PathBuf::from("."))
+    }
+
+    /// Detect the project root and type, set up remappings and the import resolver, and
+    /// return the effective scope. Called by `analyze`; exposed for import diagnostics.
+    pub fn configure_project(&mut self) -> Vec<PathBuf> {
+        let project_root = self.detect_project_root();
 
         // Auto-detect project configuration
         let project_config = ProjectConfig::auto_detect(&project_root).unwrap_or_else(|e| {
-            eprintln!("Note: Could not auto-detect project type: {}", e);
+            if !self.config.quiet {
+                errln!("Note: Could not auto-detect project type: {}", e);
+            }
             // Fallback to custom config
             ProjectConfig::from_manual_config(
                 project_root.clone(),
@@ -693,10 +490,24 @@ impl AnalysisEngine {
 
         // Use project's default scope if user didn't specify one
         let scope = if self.config.scope.is_empty() {
-            &project_config.default_scope
+            project_config.default_scope.clone()
         } else {
-            &self.config.scope
+            self.config.scope.clone()
         };
+        if self.config.scope.is_empty()
+            && !self.config.quiet
+            && project_config.project_type == ProjectType::Hardhat
+            && project_config.default_scope != [PathBuf::from("contracts")]
+        {
+            if let Some(file) = &project_config.config_file {
+                errln!(
+                    "Note: scope from {} paths.sources: {}",
+                    file,
+                    project_config.default_scope[0].display()
+                );
+            }
+        }
+        self.apply_project_excludes(&project_config, &scope);
 
         // Build remappings with proper precedence
         let final_remappings = if project_config.project_type == ProjectType::Foundry {
@@ -717,7 +528,7 @@ impl AnalysisEngine {
                 &cli_remappings,
             )
             .unwrap_or_else(|e| {
-                eprintln!("Warning: Failed to load remappings: {}", e);
+                errln!("Warning: Failed to load remappings: {}", e);
                 project_config.remappings.clone()
             })
         } else {
@@ -731,6 +542,60 @@ impl AnalysisEngine {
             remappings
         };
 
+        if !self.config.quiet && !self.config.remappings.is_empty() {
+            // Everything below the configured remappings in the precedence order
+            let detected = if project_config.project_type == ProjectType::Foundry {
+                ProjectConfig::load_remappings_with_precedence(
+                    &project_config.project_root,
+                    &HashMap::new(),
+                )
+                .unwrap_or_else(|_| project_config.remappings.clone())
+            } else {
+                project_config.remappings.clone()
+            };
+            let provenance = &self.config.provenance;
+            let source = provenance.describe(provenance.source("remappings"));
+            for notice in remapping_override_notices(
+                &project_config.project_root,
+                &detected,
+                &self.config.remappings,
+                &source,
+            ) {
+                errln!("Note: {}", notice);
+            }
+        }
+
+        self.project_root = project_config.project_root.clone();
+        self.remappings = final_remappings.clone();
+
+        if self.config.verbosity() == Verbosity::Verbose {
+            errln!(
+                "Project: {} ({:?})",
+                self.project_root.display(),
+                project_config.project_type
+            );
+            let mut remappings: Vec<_> = final_remappings.iter().collect();
+            remappings.sort();
+            errln!(
+                "Remappings:{}",
+                if remappings.is_empty() { " none" } else { "" }
+            );
+            for (from, to) in remappings {
+                errln!("  {}={}", from, to.display());
+            }
+        }
+
+        if self.config.ast_source == AstSource::Solc {
+            let remappings = final_remappings
+                .iter()
+                .map(|(from, to)| format!("{}={}", from, to.display()))
+                .collect();
+            self.context.set_solc_asts(SolcAstIndex::discover(
+                &project_config.project_root,
+                remappings,
+            ));
+        }
+
         self.context
             .set_import_resolver(final_remappings, project_config.project_root.clone());
 
@@ -739,72 +604,252 @@ impl AnalysisEngine {
             resolver.add_library_paths(project_config.library_paths.clone());
         }
 
-        self.context.load_files(&scope, &self.config.exclude)?;
+        scope
+    }
 
-        self.context.build_cache()?;
+    /// Load the Solidity files in `scope`, skipping configured and project excludes.
+    pub fn load_scope(&mut self, scope: &[PathBuf]) -> Result<(), String> {
+        self.context.set_low_memory(self.config.low_memory);
+        self.context.set_verbosity(self.config.verbosity());
+        self.context.load_files(scope, &self.exclude)
+    }
 
-        if !self.context.missing_contracts.is_empty() {
-            eprintln!(
-                "Warning: {} missing contracts detected:",
-                self.context.missing_contracts.len()
-            );
-            for missing in &self.context.missing_contracts {
-                eprintln!("  - {}", missing);
-            }
-        }
+    /// Add what the build config leaves out of the sources (Foundry `test`/`script` directories
+    /// and `skip` globs) to the configured excludes. Directories are only added when they lie
+    /// in the scope, and not when an explicit scope entry is inside them; files named in the
+    /// scope are loaded even if a skip glob matches them. Build directories and paths matched
+    /// by `.gitignore` are left out of directory walks.
+    fn apply_project_excludes(&mut self, project_config: &ProjectConfig, scope: &[PathBuf]) {
+        self.exclude = self.config.exclude.clone();
+        let explicit_scope = !self.config.scope.is_empty();
+        let canonical = |paths: &[PathBuf]| -> Vec<PathBuf> {
+            paths.iter().filter_map(|p| fs::canonicalize(p).ok()).collect()
+        };
+        let scope_bases: Vec<PathBuf> = scope.iter().map(|p| glob_base(p)).collect();
+        let scope_paths = canonical(&scope_bases);
+        let configured = canonical(&self.exclude);
 
-        let detectors = self.registry.get_all();
-        for detector_arc in detectors.clone() {
-            detector_arc.register_callbacks(&mut self.visitor);
+        let mut added = Vec::new();
+        for dir in &project_config.default_exclude {
+            let Ok(path) = fs::canonicalize(project_config.project_root.join(dir)) else {
+                continue;
+            };
+            let in_scope = scope_paths.iter().any(|s| path.starts_with(s) && path != *s);
+            let scope_inside = explicit_scope && scope_paths.iter().any(|s| s.starts_with(&path));
+            let already_excluded = configured.iter().any(|e| path.starts_with(e));
+            if in_scope && !scope_inside && !already_excluded {
+                added.push(dir.display().to_string());
+                self.exclude.push(path);
+            }
         }
 
-        let results =
-            self.processor
-                .process_files(&self.context.files, &self.visitor, &self.context);
+        let skip = if project_config.skip.is_empty() {
+            None
+        } else {
+            match SkipGlobs::new(&project_config.project_root, &project_config.skip) {
+                Ok(skip) => {
+                    added.push(format!("skip {}", project_config.skip.join(", ")));
+                    Some(skip)
+                }
+                Err(e) => {
+                    errln!("Warning: {}. Ignoring the skip patterns.", e);
+                    None
+                }
+            }
+        };
+        self.context.set_skip_globs(skip);
 
-        let report = self.generate_report_from_results(&results);
+        let root = &project_config.project_root;
+        let build_dirs: Vec<PathBuf> = project_config
+            .build_dirs
+            .iter()
+            .map(|dir| root.join(dir))
+            .collect();
+        self.context.set_build_dirs(&build_dirs);
+        self.context
+            .set_gitignore_root((!self.config.ignore_gitignore).then_some(root.as_path()));
 
-        Ok(report)
+        if !self.config.quiet && !added.is_empty() {
+            let source = project_config.config_file.as_deref().unwrap_or("the project config");
+            errln!("Note: exclude extended from {}: {}", source, added.join("; "));
+        }
     }
 
-    fn generate_report_from_results(&self, results: &AnalysisResults) -> Report {
+    fn generate_report_from_results(
+        &self,
+        results: &AnalysisResults,
+        linker: Option<&SourceLinker>,
+        source_context: Option<&SourceContext>,
+    ) -> Report {
         let mut report = Report::new();
 
+        // Report paths relative to the configured base so reports are portable
+        let path_root = self.config.path_base.resolve_root(&self.project_root);
+        let mut normalized_paths: HashMap<String, String> = HashMap::new();
+
         for (detector_id, locations) in &results.findings_by_detector {
             if let Some(detector) = self.registry.get(detector_id) {
+                let locations = locations
+                    .iter()
+                    .map(|loc| {
+                        let file = normalized_paths
+                            .entry(loc.file.clone())
+                            .or_insert_with(|| normalize_report_path(&loc.file, path_root.as_deref()))
+                            .clone();
+                        let url = linker.and_then(|l| l.url(&loc.file, loc.line));
+                        let context = source_context.and_then(|c| c.window(&loc.file, loc.line));
+                        Location {
+                            file,
+                            url,
+                            context,
+                            ..loc.clone()
+                        }
+                    })
+                    .collect();
+
                 let finding = Finding {
                     detector_id: detector_id.to_string(),
                     severity: detector.severity(),
+                    category: detector.category(),
                     title: detector.name().to_string(),
                     description: detector.description().to_string(),
-                    example: detector.example(),
-                    locations: locations.clone(),
+                    example: if self.config.explain {
+                        detector.example()
+                    } else {
+                        None
+                    },
+                    locations,
                 };
                 report.add_finding(finding);
             }
         }
 
-        // Sort findings by severity (High -> Medium -> Low -> Gas -> NC)
-        report
-            .findings
-            .sort_by(|a, b| b.severity.as_value().cmp(&a.severity.as_value()));
+        // Sort findings by severity (High -> Medium -> Low -> Gas -> NC), then detector id
+        report.findings.sort_by(|a, b| {
+            b.severity
+                .as_value()
+                .cmp(&a.severity.as_value())
+                .then_with(|| a.detector_id.cmp(&b.detector_id))
+        });
+
+        let settings = &self.config.report;
+        report.title = settings.title.clone();
+        if let Some(overview) = &settings.overview {
+            report.comment = overview.clone();
+        }
+        if let Some(footnote) = &settings.footnote {
+            report.footnote = footnote.clone();
+        }
 
-        // Add metadata
+        // Add metadata; configured entries first so the built-in ones take precedence
+        for (key, value) in &settings.metadata {
+            report.add_metadata(key, value);
+        }
         report.add_metadata("Version:", crate::core::version());
-        report.add_metadata(
-            "Timestamp:",
-            &chrono::Utc::now().format("%d/%m/%Y %H:%M:%S").to_string(),
-        );
+        match self.config.timestamp.resolve() {
+            Ok(Some(timestamp)) => report.add_metadata("Timestamp:", &timestamp),
+            Ok(None) => {}
+            Err(e) => errln!("Warning: {}. Omitting the report timestamp.", e),
+        }
         report.add_metadata("Total Findings:", &results.total_findings().to_string());
 
+        if self.config.scope_appendix {
+            report.scope_appendix = Some(self.scope_appendix(path_root.as_deref()));
+        }
+
         report
     }
 
+    fn source_linker(&self) -> Option<SourceLinker> {
+        self.config
+            .source_link_template
+            .as_deref()
+            .map(|template| {
+                SourceLinker::new(template, &self.project_root, &self.config.source_link_ref)
+            })
+    }
+
+    fn source_context(&self) -> Option<SourceContext> {
+        (self.config.context_lines > 0)
+            .then(|| SourceContext::new(self.config.context_lines, self.config.context_width))
+    }
+
+    /// Every in-scope contract with its nSLOC and function counts, in file and line order.
+    fn scope_appendix(&self, path_root: Option<&Path>) -> Vec<ScopeEntry> {
+        let mut code_lines_by_file: HashMap<&str, Vec<bool>> = HashMap::new();
+        let mut contracts: Vec<&ContractInfo> = self.context.contracts.values().collect();
+        contracts.sort_by(|a, b| (&a.file_path, a.loc.line).cmp(&(&b.file_path, b.loc.line)));
+
+        contracts
+            .into_iter()
+            .map(|contract| {
+                // Read from disk so low-memory runs, which drop file contents, count the same
+                let code_lines = code_lines_by_file
+                    .entry(contract.file_path.as_str())
+                    .or_insert_with(|| {
+                        fs::read_to_string(&contract.file_path)
+                            .map(|content| code_lines(&content))
+                            .unwrap_or_default()
+                    });
+                let last_line = contract.loc.line_end.unwrap_or(contract.loc.line);
+                let nsloc = count_nsloc(code_lines, contract.loc.line, last_line);
+
+                let count_functions = |visibilities: &[FunctionVisibility]| {
+                    contract
+                        .function_definitions
+                        .iter()
+                        .filter(|f| f.function_type == FunctionType::Function)
+                        .filter(|f| visibilities.contains(&f.visibility))
+                        .count()
+                };
+
+                ScopeEntry {
+                    contract: contract.name.clone(),
+                    file: normalize_report_path(&contract.file_path, path_root),
+                    nsloc,
+                    external_functions: count_functions(&[FunctionVisibility::External]),
+                    public_functions: count_functions(&[FunctionVisibility::Public]),
+                    internal_functions: count_functions(&[
+                        FunctionVisibility::Internal,
+                        FunctionVisibility::Private,
+                    ]),
+                    has_inheritance: !contract.direct_bases.is_empty(),
+                }
+            })
+            .collect()
+    }
+
     // Getters
     pub fn registry(&self) -> &DetectorRegistry {
         &self.registry
     }
 
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Number of files analyzed and restored from the cache by the last run with `cache` on.
+    pub fn cache_counts(&self) -> Option<(usize, usize)> {
+        self.cache_counts
+    }
+
+    pub fn project_root(&self) -> &Path {
+        &self.project_root
+    }
+
+    pub fn remappings(&self) -> &HashMap<String, PathBuf> {
+        &self.remappings
+    }
+
+    pub fn context(&self) -> &AnalysisContext {
+        &self.context
+    }
+
+    /// Paths of every file loaded by the last `analyze`, including resolved imports.
+    pub fn analyzed_files(&self) -> impl Iterator<Item = &Path> {
+        self.context.files.iter().map(|f| f.path.as_path())
+    }
+
     pub fn get_detector_info(&self) -> Vec<DetectorInfo> {
         self.registry
             .get_all()
@@ -826,3 +871,1158 @@ pub struct DetectorInfo {
     pub severity: String,
     pub description: String,
 }
+
+/// Notices for configured remappings that replace an auto-detected one with the same prefix,
+/// or that an auto-detected remapping with a longer prefix shadows for part of their imports.
+fn remapping_override_notices(
+    project_root: &Path,
+    detected: &HashMap<String, PathBuf>,
+    configured: &[String],
+    source: &str,
+) -> Vec<String> {
+    let mut detected: Vec<_> = detected.iter().collect();
+    detected.sort();
+
+    let mut notices = Vec::new();
+    for (prefix, target) in configured.iter().filter_map(|r| r.split_once('=')) {
+        for (detected_prefix, detected_target) in &detected {
+            let same_target = **detected_target == Path::new(target)
+                || **detected_target == project_root.join(target);
+            if detected_prefix.as_str() == prefix && !same_target {
+                notices.push(format!(
+                    "remapping {} from auto-detection ({}) overridden by {} ({})",
+                    prefix,
+                    detected_target.display(),
+                    source,
+                    target
+                ));
+            } else if detected_prefix.len() > prefix.len() && detected_prefix.starts_with(prefix) {
+                notices.push(format!(
+                    "remapping {} from {} ({}) does not apply to imports under {}: the \
+                     auto-detected remapping to {} has a longer prefix",
+                    prefix,
+                    source,
+                    target,
+                    detected_prefix,
+                    detected_target.display()
+                ));
+            }
+        }
+    }
+    notices
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{FindingFilter, ReportSettings};
+    use crate::models::{Category, Severity};
+    use crate::utils::test_utils::write_fixture_project;
+    use std::path::Path;
+
+    const VAULT: &str = r#"
+pragma solidity ^0.8.0;
+
+contract Vault {
+    address owner;
+
+    function withdraw() external {
+        require(tx.origin == owner);
+        payable(msg.sender).transfer(address(this).balance);
+    }
+}
+"#;
+
+    fn analyze_fixture(root: &Path, path_base: PathBase) -> Report {
+        let config = Config {
+            scope: vec![root.join("src")],
+            path_base,
+            ..Config::default()
+        };
+        let mut engine = AnalysisEngine::new(&config);
+        engine.register_built_in_detectors();
+        engine.analyze().expect("Analysis failed")
+    }
+
+    fn finding_keys(report: &Report) -> Vec<(String, String, usize)> {
+        let mut keys: Vec<_> = report
+            .findings
+            .iter()
+            .flat_map(|f| {
+                f.locations
+                    .iter()
+                    .map(|l| (f.detector_id.clone(), l.file.clone(), l.line))
+            })
+            .collect();
+        keys.sort();
+        keys
+    }
+
+    #[test]
+    fn test_report_paths_are_project_relative() {
+        let root = write_fixture_project(
+            "paths-project",
+            &[("foundry.toml", "[profile.default]\n"), ("src/Vault.sol", VAULT)],
+        );
+
+        let report = analyze_fixture(&root, PathBase::Project);
+        assert!(!report.findings.is_empty());
+        for finding in &report.findings {
+            for location in &finding.locations {
+                assert_eq!(location.file, "src/Vault.sol");
+            }
+        }
+
+        let report = analyze_fixture(&root, PathBase::Absolute);
+        let file = &report.findings[0].locations[0].file;
+        assert_eq!(Path::new(file), root.join("src").join("Vault.sol"));
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_report_paths_match_across_checkouts() {
+        let files = [("foundry.toml", "[profile.default]\n"), ("src/Vault.sol", VAULT)];
+        let first = write_fixture_project("paths-checkout-a", &files);
+        let second = write_fixture_project("paths-checkout-b", &files);
+
+        let first_keys = finding_keys(&analyze_fixture(&first, PathBase::Project));
+        let second_keys = finding_keys(&analyze_fixture(&second, PathBase::Project));
+
+        assert!(!first_keys.is_empty());
+        assert_eq!(first_keys, second_keys);
+
+        let _ = std::fs::remove_dir_all(&first);
+        let _ = std::fs::remove_dir_all(&second);
+    }
+
+    fn registered_ids(target_chains: &[&str]) -> HashSet<String> {
+        let config = Config {
+            target_chains: target_chains.iter().map(|c| c.to_string()).collect(),
+            ..Config::default()
+        };
+        let mut engine = AnalysisEngine::new(&config);
+        engine.register_built_in_detectors();
+        engine
+            .registry()
+            .get_all()
+            .iter()
+            .map(|d| d.id().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_target_chains_filter_detectors() {
+        let l2_only = ["block-number-l2", "l2-sequencer-check", "push0-opcode"];
+        let all = registered_ids(&[]);
+        let mainnet = registered_ids(&["mainnet"]);
+        let arbitrum = registered_ids(&["Mainnet", "Arbitrum"]);
+
+        for id in l2_only {
+            assert!(all.contains(id), "{} should run without target_chains", id);
+            assert!(!mainnet.contains(id), "{} should be skipped on mainnet", id);
+            assert!(arbitrum.contains(id), "{} should run on arbitrum", id);
+        }
+
+        assert_eq!(mainnet.len(), all.len() - l2_only.len());
+        assert_eq!(arbitrum, all);
+    }
+
+    #[test]
+    fn test_every_detector_is_categorized() {
+        let mut engine = AnalysisEngine::new(&Config::default());
+        engine.register_built_in_detectors();
+
+        let uncategorized: Vec<&str> = engine
+            .registry()
+            .get_all()
+            .iter()
+            .filter(|d| d.category() == Category::Uncategorized)
+            .map(|d| d.id())
+            .collect();
+        assert!(
+            uncategorized.is_empty(),
+            "detectors without a category: {:?}",
+            uncategorized
+        );
+    }
+
+    #[test]
+    fn test_low_memory_matches_default_analysis() {
+        let sources: Vec<(String, String)> = (0..20)
+            .map(|i| {
+                (
+                    format!("src/Vault{}.sol", i),
+                    VAULT.replace("contract Vault", &format!("contract Vault{}", i)),
+                )
+            })
+            .collect();
+        let mut files = vec![("foundry.toml", "[profile.default]\n")];
+        files.extend(sources.iter().map(|(p, c)| (p.as_str(), c.as_str())));
+        let root = write_fixture_project("low-memory-project", &files);
+
+        let run = |low_memory: bool| {
+            let config = Config {
+                scope: vec![root.join("src")],
+                low_memory,
+                ..Config::default()
+            };
+            let mut engine = AnalysisEngine::new(&config);
+            engine.register_built_in_detectors();
+            let report = engine.analyze().expect("Analysis failed");
+            let retained: usize = engine.context.files.iter().map(|f| f.content.len()).sum();
+            (report, retained)
+        };
+
+        let (default_report, default_retained) = run(false);
+        let (low_memory_report, low_memory_retained) = run(true);
+
+        assert!(!default_report.findings.is_empty());
+        assert_eq!(
+            finding_keys(&default_report),
+            finding_keys(&low_memory_report)
+        );
+        let snippets = |report: &Report| -> Vec<Option<String>> {
+            let mut snippets: Vec<_> = report
+                .findings
+                .iter()
+                .flat_map(|f| f.locations.iter().map(|l| l.snippet.clone()))
+                .collect();
+            snippets.sort();
+            snippets
+        };
+        assert_eq!(snippets(&default_report), snippets(&low_memory_report));
+
+        assert!(default_retained > 0);
+        assert_eq!(low_memory_retained, 0);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_repeated_analysis_reads_sources_anew() {
+        let root = write_fixture_project(
+            "repeated-analysis",
+            &[
+                ("foundry.toml", "[profile.default]\n"),
+                ("src/Vault.sol", VAULT),
+            ],
+        );
+        let config = Config {
+            scope: vec![root.join("src")],
+            timestamp: crate::output::TimestampMode::None,
+            ..Config::default()
+        };
+        let mut engine = AnalysisEngine::new(&config);
+        engine.register_built_in_detectors();
+        let json = |report: &Report| serde_json::to_string(report).unwrap();
+
+        let first = engine.analyze().expect("Analysis failed");
+        let again = engine.analyze().expect("Analysis failed");
+        assert_eq!(json(&again), json(&first));
+        assert_eq!(engine.scope_files().unwrap().len(), 1);
+
+        let edited = VAULT.replace("tx.origin", "msg.sender");
+        fs::write(root.join("src/Vault.sol"), edited).unwrap();
+        fs::write(root.join("src/Token.sol"), "contract Token {}\n").unwrap();
+        let report = engine.analyze().expect("Analysis failed");
+        assert!(!report
+            .findings
+            .iter()
+            .any(|f| f.detector_id == "tx-origin-usage"));
+        assert_eq!(engine.scope_files().unwrap().len(), 2);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_cached_findings_match_fresh_analysis() {
+        let base = r#"
+pragma solidity ^0.8.0;
+
+contract Base {
+    address owner;
+
+    function isOwner() internal view returns (bool) {
+        return tx.origin == owner;
+    }
+}
+"#;
+        let vault = r#"
+pragma solidity ^0.8.0;
+
+import "./Base.sol";
+
+contract Vault is Base {
+    function withdraw() external {
+        require(isOwner());
+        payable(msg.sender).transfer(address(this).balance);
+    }
+}
+"#;
+        let root = write_fixture_project(
+            "cache-project",
+            &[
+                ("foundry.toml", "[profile.default]\n"),
+                ("src/Base.sol", base),
+                ("src/Vault.sol", vault),
+                ("src/Other.sol", VAULT),
+            ],
+        );
+
+        let run = |cache: bool| {
+            let config = Config {
+                scope: vec![root.join("src")],
+                cache,
+                timestamp: crate::output::TimestampMode::None,
+                ..Config::default()
+            };
+            let mut engine = AnalysisEngine::new(&config);
+            engine.register_built_in_detectors();
+            let report = engine.analyze().expect("Analysis failed");
+            let json = serde_json::to_string_pretty(&report).unwrap();
+            let html = crate::output::generate_html_report(&report);
+            ((json, html), engine.cache_counts())
+        };
+
+        let (fresh, counts) = run(true);
+        assert_eq!(counts, Some((3, 0)));
+        assert!(root.join(".weasel-cache/findings.json").exists());
+
+        let (cached, counts) = run(true);
+        assert_eq!(counts, Some((0, 3)));
+        assert!(
+            cached == fresh,
+            "cached report differs from the analyzed one"
+        );
+
+        // A change to a base contract invalidates the files inheriting from it
+        std::fs::write(
+            root.join("src/Base.sol"),
+            base.replace("tx.origin", "msg.sender"),
+        )
+        .unwrap();
+        let (cached, counts) = run(true);
+        assert_eq!(counts, Some((2, 1)));
+        let (fresh, counts) = run(false);
+        assert_eq!(counts, None);
+        assert!(
+            cached == fresh,
+            "report after invalidation differs from a fresh one"
+        );
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    /// Reports `tx.origin` and counts the expressions it was called on.
+    #[derive(Debug)]
+    struct CountingTxOriginDetector {
+        id: &'static str,
+        version: u32,
+        calls: Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    impl Detector for CountingTxOriginDetector {
+        fn id(&self) -> &'static str {
+            self.id
+        }
+
+        fn name(&self) -> &str {
+            "tx.origin used"
+        }
+
+        fn severity(&self) -> Severity {
+            Severity::Low
+        }
+
+        fn description(&self) -> &str {
+            "tx.origin used"
+        }
+
+        fn example(&self) -> Option<String> {
+            None
+        }
+
+        fn version(&self) -> u32 {
+            self.version
+        }
+
+        fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+            visitor.on_expression(move |expr, file, _context| {
+                self.calls
+                    .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                match expr {
+                    solang_parser::pt::Expression::MemberAccess(loc, base, member)
+                        if member.name == "origin" && base.to_string() == "tx" =>
+                    {
+                        FindingData {
+                            detector_id: self.id(),
+                            location: crate::utils::location::loc_to_location(loc, file),
+                        }
+                        .into()
+                    }
+                    _ => Vec::new(),
+                }
+            });
+        }
+    }
+
+    #[test]
+    fn test_cache_reruns_only_changed_detectors() {
+        let root = write_fixture_project(
+            "cache-versions",
+            &[
+                ("foundry.toml", "[profile.default]\n"),
+                ("src/Vault.sol", VAULT),
+            ],
+        );
+        let run = |versions: [u32; 2]| {
+            let config = Config {
+                scope: vec![root.join("src")],
+                cache: true,
+                quiet: true,
+                ..Config::default()
+            };
+            let mut engine = AnalysisEngine::new(&config);
+            let mut calls = Vec::new();
+            for (id, version) in ["tx-origin-a", "tx-origin-b"].into_iter().zip(versions) {
+                let counter = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+                engine
+                    .register_detector(Arc::new(CountingTxOriginDetector {
+                        id,
+                        version,
+                        calls: counter.clone(),
+                    }))
+                    .unwrap();
+                calls.push(counter);
+            }
+            let report = engine.analyze().expect("Analysis failed");
+            let mut ids: Vec<_> = report
+                .findings
+                .iter()
+                .map(|f| f.detector_id.clone())
+                .collect();
+            ids.sort();
+            let calls: Vec<_> = calls
+                .iter()
+                .map(|c| c.load(std::sync::atomic::Ordering::Relaxed))
+                .collect();
+            (ids, calls, engine.cache_counts())
+        };
+
+        let (ids, calls, counts) = run([1, 1]);
+        assert_eq!(ids, ["tx-origin-a", "tx-origin-b"]);
+        assert!(calls.iter().all(|&c| c > 0));
+        assert_eq!(counts, Some((1, 0)));
+
+        // Only the bumped detector runs; the other one's findings come from the cache
+        let (ids, calls, counts) = run([1, 2]);
+        assert_eq!(ids, ["tx-origin-a", "tx-origin-b"]);
+        assert_eq!(calls[0], 0);
+        assert!(calls[1] > 0);
+        assert_eq!(counts, Some((0, 1)));
+
+        let (ids, calls, counts) = run([1, 2]);
+        assert_eq!(ids, ["tx-origin-a", "tx-origin-b"]);
+        assert_eq!(calls, [0, 0]);
+        assert_eq!(counts, Some((0, 1)));
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_solc_ast_source_loads_unparseable_files() {
+        macro_rules! fixture {
+            ($path:literal) => {
+                (
+                    $path,
+                    include_str!(concat!("../../tests/fixtures/solc-ast/", $path)),
+                )
+            };
+        }
+        let root = write_fixture_project(
+            "solc-ast-project",
+            &[
+                ("foundry.toml", "[profile.default]\n"),
+                fixture!("src/Vault.sol"),
+                fixture!("src/Lock.sol"),
+                fixture!("out/Vault.sol/Vault.json"),
+                fixture!("out/Lock.sol/Lock.json"),
+            ],
+        );
+
+        let engine_for = |ast_source: AstSource| {
+            let config = Config {
+                scope: vec![root.join("src")],
+                ast_source,
+                ..Config::default()
+            };
+            let mut engine = AnalysisEngine::new(&config);
+            engine.register_built_in_detectors();
+            engine
+        };
+
+        // solang-parser does not support `transient` storage, so Lock.sol is skipped
+        let mut engine = engine_for(AstSource::Solang);
+        engine.analyze().expect("Analysis failed");
+        let names: Vec<&str> = engine
+            .context
+            .contracts
+            .values()
+            .map(|c| c.name.as_str())
+            .collect();
+        assert!(names.contains(&"Vault"));
+        assert!(!names.contains(&"Lock"));
+
+        let mut engine = engine_for(AstSource::Solc);
+        let report = engine.analyze().expect("Analysis failed");
+        assert!(!report.findings.is_empty());
+
+        let lock = engine
+            .context
+            .contracts
+            .values()
+            .find(|c| c.name == "Lock")
+            .expect("Lock should be loaded from the solc AST");
+        assert_eq!(lock.state_variables[0].name, "locked");
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_scope_appendix_counts_contracts() {
+        let token = r#"// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+/// @notice Minimal token
+interface IToken {
+    function transfer(address to, uint256 amount) external returns (bool);
+}
+
+contract Token is IToken {
+    mapping(address => uint256) balances; // holder => amount
+
+    /*
+     * Moves tokens.
+     */
+    function transfer(address to, uint256 amount) external returns (bool) {
+        _move(msg.sender, to, amount);
+        return true;
+    }
+
+    function balanceOf(address holder) public view returns (uint256) {
+        return balances[holder];
+    }
+
+    function _move(address from, address to, uint256 amount) private {
+        balances[from] -= amount;
+        balances[to] += amount;
+    }
+}
+"#;
+        let root = write_fixture_project(
+            "scope-appendix-project",
+            &[
+                ("foundry.toml", "[profile.default]\n"),
+                ("src/Token.sol", token),
+                ("src/Vault.sol", VAULT),
+            ],
+        );
+        let config = Config {
+            scope: vec![root.join("src")],
+            scope_appendix: true,
+            ..Config::default()
+        };
+        let mut engine = AnalysisEngine::new(&config);
+        engine.register_built_in_detectors();
+        let report = engine.analyze().expect("Analysis failed");
+
+        let entries = report.scope_appendix.expect("Scope appendix missing");
+        let rows: Vec<_> = entries
+            .iter()
+            .map(|e| {
+                (
+                    e.contract.as_str(),
+                    e.file.as_str(),
+                    e.nsloc,
+                    e.external_functions,
+                    e.public_functions,
+                    e.internal_functions,
+                    e.has_inheritance,
+                )
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                ("IToken", "src/Token.sol", 3, 1, 0, 0, false),
+                ("Token", "src/Token.sol", 14, 1, 1, 1, true),
+                ("Vault", "src/Vault.sol", 7, 1, 0, 0, false),
+            ]
+        );
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_source_links_fall_back_to_configured_ref() {
+        // Temp fixtures are outside any git checkout, so `{commit}` becomes `source_link_ref`
+        let root = write_fixture_project(
+            "source-link-project",
+            &[("foundry.toml", "[profile.default]\n"), ("src/Vault.sol", VAULT)],
+        );
+        let config = Config {
+            scope: vec![root.join("src")],
+            source_link_template: Some(
+                "https://github.com/org/repo/blob/{commit}/{path}#L{line}".to_string(),
+            ),
+            source_link_ref: "release".to_string(),
+            ..Config::default()
+        };
+        let mut engine = AnalysisEngine::new(&config);
+        engine.register_built_in_detectors();
+        let report = engine.analyze().expect("Analysis failed");
+
+        let tx_origin = report
+            .findings
+            .iter()
+            .find(|f| f.detector_id == "tx-origin-usage")
+            .expect("tx-origin-usage finding missing");
+        assert_eq!(
+            tx_origin.locations[0].url.as_deref(),
+            Some("https://github.com/org/repo/blob/release/src/Vault.sol#L8")
+        );
+        assert!(report
+            .findings
+            .iter()
+            .flat_map(|f| &f.locations)
+            .all(|l| l.url.is_some()));
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_configured_report_branding() {
+        let root = write_fixture_project(
+            "report-branding-project",
+            &[
+                ("foundry.toml", "[profile.default]\n"),
+                ("src/Vault.sol", VAULT),
+            ],
+        );
+        let config = Config {
+            scope: vec![root.join("src")],
+            timestamp: crate::output::TimestampMode::None,
+            report: ReportSettings {
+                title: Some("Acme Vault Review".to_string()),
+                overview: Some("Contracts at commit 4f2a9c1.".to_string()),
+                footnote: Some("Unverified findings.".to_string()),
+                metadata: [("Client".to_string(), "Acme".to_string())].into(),
+            },
+            ..Config::default()
+        };
+        let mut engine = AnalysisEngine::new(&config);
+        engine.register_built_in_detectors();
+        let report = engine.analyze().expect("Analysis failed");
+
+        assert_eq!(report.title(), "Acme Vault Review");
+        assert_eq!(report.comment, "Contracts at commit 4f2a9c1.");
+        assert_eq!(report.footnote, "Unverified findings.");
+        let metadata = report.metadata.as_ref().unwrap();
+        assert_eq!(metadata["Client"], "Acme");
+        assert!(metadata.contains_key("Version:"));
+        assert!(!metadata.contains_key("Timestamp:"));
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_remapping_override_notices() {
+        let root = Path::new("/project");
+        let detected = HashMap::from([
+            ("@oz/".to_string(), root.join("lib/oz/")),
+            ("@solmate/utils/".to_string(), root.join("lib/solmate/src/utils/")),
+            ("forge-std/".to_string(), root.join("lib/forge-std/src/")),
+        ]);
+        let configured = [
+            "@oz/=lib/oz-upgradeable/".to_string(),
+            "@solmate/=lib/solmate-fork/src/".to_string(),
+            "forge-std/=lib/forge-std/src/".to_string(),
+        ];
+
+        let notices = remapping_override_notices(root, &detected, &configured, "CLI");
+        assert_eq!(
+            notices,
+            [
+                "remapping @oz/ from auto-detection (/project/lib/oz/) overridden by CLI \
+                 (lib/oz-upgradeable/)",
+                "remapping @solmate/ from CLI (lib/solmate-fork/src/) does not apply to imports \
+                 under @solmate/utils/: the auto-detected remapping to \
+                 /project/lib/solmate/src/utils/ has a longer prefix",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_finding_filters_drop_matching_snippets() {
+        let fees = r#"
+pragma solidity ^0.8.0;
+
+contract Fees {
+    function fee(uint256 amount, uint256 bps) external pure returns (uint256) {
+        return mulDiv(amount, bps, 10_000);
+    }
+
+    function rebate(uint256 amount) external pure returns (uint256) {
+        return mulDiv(amount, 3, 7);
+    }
+
+    function mulDiv(uint256 a, uint256 b, uint256 c) internal pure returns (uint256) {
+        return a * b / c;
+    }
+}
+"#;
+        let root = write_fixture_project(
+            "finding-filters-project",
+            &[("foundry.toml", "[profile.default]\n"), ("src/Fees.sol", fees)],
+        );
+        let magic_number_lines = |config: &Config| {
+            let mut engine = AnalysisEngine::new(config);
+            engine.register_built_in_detectors();
+            let report = engine.analyze().expect("Analysis failed");
+            let lines: Vec<usize> = report
+                .findings
+                .iter()
+                .filter(|f| f.detector_id == "magic-numbers")
+                .flat_map(|f| f.locations.iter().map(|l| l.line))
+                .collect();
+            (lines, report.metadata.unwrap())
+        };
+
+        let config = Config {
+            scope: vec![root.join("src")],
+            ..Config::default()
+        };
+        let (lines, metadata) = magic_number_lines(&config);
+        assert_eq!(lines, [6, 10, 10]);
+        assert!(!metadata.contains_key("Filtered Findings:"));
+
+        let config = Config {
+            finding_filters: vec![
+                FindingFilter::new("magic-numbers", r"\b10_?000\b").unwrap(),
+                // Filters only apply to their own detector
+                FindingFilter::new("line-length", "mulDiv").unwrap(),
+            ],
+            ..config
+        };
+        let (lines, metadata) = magic_number_lines(&config);
+        assert_eq!(lines, [10, 10]);
+        assert_eq!(metadata["Filtered Findings:"], "1");
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_inline_suppression_comments() {
+        let fees = r#"
+pragma solidity ^0.8.0;
+
+contract Fees {
+    function fee(uint256 amount, uint256 bps) external pure returns (uint256) {
+        // weasel-disable-next-line magic-numbers
+        return mulDiv(amount, bps, 10_000);
+    }
+
+    function rebate(uint256 amount) external pure returns (uint256) {
+        return mulDiv(amount, 3, 7); // weasel-disable-line
+    }
+
+    function share(uint256 amount) external pure returns (uint256) {
+        return mulDiv(amount, 1, 3);
+    }
+}
+"#;
+        let root = write_fixture_project(
+            "suppression-project",
+            &[("foundry.toml", "[profile.default]\n"), ("src/Fees.sol", fees)],
+        );
+        for low_memory in [false, true] {
+            let config = Config {
+                scope: vec![root.join("src")],
+                low_memory,
+                ..Config::default()
+            };
+            let mut engine = AnalysisEngine::new(&config);
+            engine.register_built_in_detectors();
+            let report = engine.analyze().expect("Analysis failed");
+
+            let magic_number_lines: Vec<usize> = report
+                .findings
+                .iter()
+                .filter(|f| f.detector_id == "magic-numbers")
+                .flat_map(|f| f.locations.iter().map(|l| l.line))
+                .collect();
+            assert_eq!(magic_number_lines, [15, 15], "low_memory: {}", low_memory);
+            assert!(report
+                .findings
+                .iter()
+                .all(|f| f.locations.iter().all(|l| l.line != 11)));
+            let suppressed: usize = report.metadata.unwrap()["Suppressed Findings:"]
+                .parse()
+                .unwrap();
+            // magic-numbers on line 7, and every finding on line 11
+            assert!(suppressed >= 3, "{}", suppressed);
+        }
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_overlapping_detectors_report_a_line_once() {
+        let zap = r#"
+pragma solidity ^0.8.0;
+
+contract Zap {
+    IERC20 token;
+
+    function approveRouter(address router, uint256 amount) external {
+        token.approve(router, amount);
+        token.approve(router, 0);
+        token.approve(router, amount);
+        // weasel-disable-next-line approve-race
+        token.approve(address(this), amount);
+    }
+}
+"#;
+        let root = write_fixture_project(
+            "overlap-project",
+            &[
+                ("foundry.toml", "[profile.default]\n"),
+                ("src/Zap.sol", zap),
+            ],
+        );
+        let config = Config {
+            scope: vec![root.join("src")],
+            ..Config::default()
+        };
+        let mut engine = AnalysisEngine::new(&config);
+        engine.register_built_in_detectors();
+        let report = engine.analyze().expect("Analysis failed");
+
+        let lines = |detector_id: &str| -> Vec<usize> {
+            report
+                .findings
+                .iter()
+                .filter(|f| f.detector_id == detector_id)
+                .flat_map(|f| f.locations.iter().map(|l| l.line))
+                .collect()
+        };
+        assert_eq!(lines("approve-race"), [8]);
+        // The reset, the call after it, and the line approve-race is silenced on
+        assert_eq!(lines("unsafe-approve"), [9, 10, 12]);
+        // Other detectors are not part of the group
+        assert_eq!(lines("deprecated-approve"), [8, 9, 10, 12]);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_underscore_prefix_defers_to_naming_convention() {
+        let vault = r#"
+pragma solidity ^0.8.0;
+
+contract Vault {
+    uint256 internal total;
+
+    function helper() internal {}
+}
+"#;
+        let root = write_fixture_project(
+            "naming-overlap",
+            &[
+                ("foundry.toml", "[profile.default]\n"),
+                ("src/Vault.sol", vault),
+            ],
+        );
+        let lines = |exclude_detectors: Vec<String>| {
+            let config = Config {
+                scope: vec![root.join("src")],
+                exclude_detectors,
+                ..Config::default()
+            };
+            let mut engine = AnalysisEngine::new(&config);
+            engine.register_built_in_detectors();
+            let report = engine.analyze().expect("Analysis failed");
+            let mut lines: Vec<(String, usize)> = report
+                .findings
+                .iter()
+                .filter(|f| {
+                    ["naming-convention", "underscore-prefix"].contains(&f.detector_id.as_str())
+                })
+                .flat_map(|f| f.locations.iter().map(|l| (f.detector_id.clone(), l.line)))
+                .collect();
+            lines.sort();
+            lines
+        };
+
+        assert_eq!(
+            lines(Vec::new()),
+            [
+                ("naming-convention".to_string(), 7),
+                ("underscore-prefix".to_string(), 5),
+            ]
+        );
+        assert_eq!(
+            lines(vec!["naming-convention".to_string()]),
+            [
+                ("underscore-prefix".to_string(), 5),
+                ("underscore-prefix".to_string(), 7),
+            ]
+        );
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_foundry_skip_and_test_dirs_are_excluded() {
+        let contract = "pragma solidity ^0.8.0;\n\ncontract C {}\n";
+        let root = write_fixture_project(
+            "foundry-skip-project",
+            &[
+                (
+                    "foundry.toml",
+                    "[profile.default]\ntest = \"src/t\"\nskip = [\"src/legacy/**\", \"*/Mock*.sol\"]\n",
+                ),
+                ("src/Vault.sol", VAULT),
+                ("src/MockToken.sol", contract),
+                ("src/legacy/Old.sol", contract),
+                ("src/t/Vault.t.sol", contract),
+            ],
+        );
+        let loaded_files = |scope: Vec<PathBuf>| {
+            let config = Config {
+                scope,
+                quiet: true,
+                ..Config::default()
+            };
+            let mut engine = AnalysisEngine::new(&config);
+            engine.load_project().expect("Loading failed");
+            let mut files: Vec<String> = engine
+                .analyzed_files()
+                .map(|f| normalize_report_path(&f.to_string_lossy(), Some(&root)))
+                .collect();
+            files.sort();
+            files
+        };
+
+        assert_eq!(loaded_files(vec![root.join("src")]), ["src/Vault.sol"]);
+        // Naming a skipped file in the scope analyzes it
+        assert_eq!(
+            loaded_files(vec![root.join("src"), root.join("src/legacy/Old.sol")]),
+            ["src/Vault.sol", "src/legacy/Old.sol"]
+        );
+        assert_eq!(loaded_files(vec![root.join("src/t")]), ["src/t/Vault.t.sol"]);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_path_rules_set_severity_floors_per_file() {
+        let root = write_fixture_project(
+            "path-rules-project",
+            &[
+                ("foundry.toml", "[profile.default]\n"),
+                ("src/core/Vault.sol", VAULT),
+                ("src/periphery/Vault.sol", VAULT),
+            ],
+        );
+        let config = Config {
+            scope: vec![root.join("src")],
+            path_rules: vec![
+                PathRule::new("src/**", Some(Severity::NC), &[]).unwrap(),
+                PathRule::new("src/periphery/**", Some(Severity::Medium), &[]).unwrap(),
+            ],
+            ..Config::default()
+        };
+        let mut engine = AnalysisEngine::new(&config);
+        engine.register_built_in_detectors();
+        let report = engine.analyze().expect("Analysis failed");
+
+        let severities_in = |file: &str| -> Vec<Severity> {
+            report
+                .findings
+                .iter()
+                .filter(|f| f.locations.iter().any(|l| l.file == file))
+                .map(|f| f.severity.clone())
+                .collect()
+        };
+        let core = severities_in("src/core/Vault.sol");
+        let periphery = severities_in("src/periphery/Vault.sol");
+        assert!(core.contains(&Severity::Gas), "{:?}", core);
+        assert!(core.contains(&Severity::Medium), "{:?}", core);
+        assert!(!periphery.contains(&Severity::Gas), "{:?}", periphery);
+        assert!(periphery.contains(&Severity::Medium), "{:?}", periphery);
+        assert!(periphery
+            .iter()
+            .all(|severity| severity.as_value() >= Severity::Medium.as_value()));
+        assert_ne!(report.metadata.unwrap()["Filtered Findings:"], "0");
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_contract_filter_reports_only_named_contracts() {
+        let root = write_fixture_project(
+            "contract-filter",
+            &[
+                ("foundry.toml", "[profile.default]\n"),
+                ("src/Vault.sol", VAULT),
+                (
+                    "src/Pools.sol",
+                    "pragma solidity ^0.8.0;\n\ncontract Pool {\n    address owner;\n\n    \
+                     function sweep() external {\n        require(tx.origin == owner);\n    }\n}\n\n\
+                     contract VaultManager {\n    address owner;\n\n    \
+                     function pause() external {\n        require(tx.origin == owner);\n    }\n}\n",
+                ),
+            ],
+        );
+        let config = Config {
+            scope: vec![root.join("src")],
+            ..Config::default()
+        };
+        let analyze = |contracts: &[&str]| {
+            let mut engine = AnalysisEngine::new(&config);
+            engine.register_built_in_detectors();
+            engine.set_contract_filter(contracts.iter().map(|c| c.to_string()).collect());
+            engine.analyze()
+        };
+
+        let report = analyze(&["VaultManager", "Vault"]).expect("Analysis failed");
+        let keys = finding_keys(&report);
+        assert!(keys
+            .iter()
+            .any(|(_, file, line)| file == "src/Pools.sol" && *line == 15));
+        assert!(keys.iter().any(|(_, file, _)| file == "src/Vault.sol"));
+        assert!(keys
+            .iter()
+            .all(|(_, file, line)| file == "src/Vault.sol" || *line >= 11));
+        assert_eq!(report.summary().total, report.findings.len());
+        assert_eq!(
+            report.metadata.unwrap()["Contracts:"],
+            "VaultManager, Vault"
+        );
+
+        let error = analyze(&["Manager"]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Unknown contract 'Manager'. Available contracts: Pool, Vault, VaultManager"
+        );
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    /// Reports `tx.origin` as Critical, like a severity override or custom detector would.
+    #[derive(Debug)]
+    struct CriticalTxOriginDetector;
+
+    impl Detector for CriticalTxOriginDetector {
+        fn id(&self) -> &'static str {
+            "critical-tx-origin"
+        }
+
+        fn name(&self) -> &str {
+            "tx.origin used"
+        }
+
+        fn severity(&self) -> Severity {
+            Severity::Critical
+        }
+
+        fn description(&self) -> &str {
+            "tx.origin used"
+        }
+
+        fn example(&self) -> Option<String> {
+            None
+        }
+
+        fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+            visitor.on_expression(move |expr, file, _context| {
+                match expr {
+                    solang_parser::pt::Expression::MemberAccess(loc, base, member)
+                        if member.name == "origin" && base.to_string() == "tx" =>
+                    {
+                        FindingData {
+                            detector_id: self.id(),
+                            location: crate::utils::location::loc_to_location(loc, file),
+                        }
+                        .into()
+                    }
+                    _ => Vec::new(),
+                }
+            });
+        }
+    }
+
+    #[test]
+    fn test_critical_findings_are_filtered_ordered_and_counted() {
+        let root = write_fixture_project(
+            "critical-project",
+            &[("foundry.toml", "[profile.default]\n"), ("src/Vault.sol", VAULT)],
+        );
+        let analyze = |min_severity: Severity| {
+            let config = Config {
+                scope: vec![root.join("src")],
+                min_severity,
+                ..Config::default()
+            };
+            let mut engine = AnalysisEngine::new(&config);
+            engine.register_built_in_detectors();
+            engine
+                .register_detector(Arc::new(CriticalTxOriginDetector))
+                .unwrap();
+            engine.analyze().expect("Analysis failed")
+        };
+
+        let report = analyze(Severity::NC);
+        assert!(report.findings.len() > 1);
+        assert_eq!(report.findings[0].detector_id, "critical-tx-origin");
+        assert_eq!(report.findings[0].severity, Severity::Critical);
+        assert!(report
+            .findings
+            .windows(2)
+            .all(|pair| pair[0].severity.as_value() >= pair[1].severity.as_value()));
+        let summary = report.summary();
+        assert_eq!(summary.critical, 1);
+        assert_eq!(summary.total, report.findings.len());
+
+        let report = analyze(Severity::Critical);
+        let ids: Vec<_> = report.findings.iter().map(|f| f.detector_id.as_str()).collect();
+        assert_eq!(ids, ["critical-tx-origin"]);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_register_detector_rejects_duplicate_ids() {
+        let mut engine = AnalysisEngine::new(&Config::default());
+        engine.register_built_in_detectors();
+        let count = engine.registry().len();
+
+        engine
+            .register_detector(Arc::new(CriticalTxOriginDetector))
+            .unwrap();
+        let error = engine
+            .register_detector(Arc::new(CriticalTxOriginDetector))
+            .unwrap_err();
+        assert!(matches!(error, WeaselError::DuplicateDetector(id) if id == "critical-tx-origin"));
+        assert_eq!(engine.registry().len(), count + 1);
+
+        engine.register_detector_replacing(Arc::new(CriticalTxOriginDetector));
+        assert_eq!(engine.registry().len(), count + 1);
+
+        // Built-ins registered again are skipped rather than duplicated
+        engine.register_built_in_detectors();
+        assert_eq!(engine.registry().len(), count + 1);
+    }
+}
@@ -0,0 +1,35 @@
+use std::fmt;
+
+/// Errors returned by the library API instead of printing and exiting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WeaselError {
+    /// The source did not parse; holds the parser diagnostics
+    Parse(String),
+    /// The id is not a registered detector
+    UnknownDetector(String),
+    /// A detector with this id is already registered
+    DuplicateDetector(String),
+    /// The config file or an override could not be read or is invalid
+    Config(String),
+    /// Loading or analyzing the project's files failed
+    Analysis(String),
+}
+
+impl fmt::Display for WeaselError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WeaselError::Parse(diagnostics) => {
+                write!(f, "Failed to parse snippet: {}", diagnostics)
+            }
+            WeaselError::UnknownDetector(id) => write!(f, "Unknown detector: {}", id),
+            WeaselError::DuplicateDetector(id) => {
+                write!(f, "A detector with id '{}' is already registered", id)
+            }
+            WeaselError::Config(message) | WeaselError::Analysis(message) => {
+                write!(f, "{}", message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for WeaselError {}
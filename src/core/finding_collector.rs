@@ -4,15 +4,26 @@ use std::collections::HashMap;
 #[derive(Debug, Default)]
 pub struct FindingCollector {
     findings_by_detector: HashMap<&'static str, Vec<Location>>,
+    suppressed: usize,
 }
 
 impl FindingCollector {
     pub fn new() -> Self {
         Self {
             findings_by_detector: HashMap::new(),
+            suppressed: 0,
         }
     }
 
+    /// Count findings dropped by inline suppression comments
+    pub fn record_suppressed(&mut self, count: usize) {
+        self.suppressed += count;
+    }
+
+    pub fn suppressed(&self) -> usize {
+        self.suppressed
+    }
+
     /// Report a finding from a detector
     pub fn report_finding(&mut self, detector_id: &'static str, location: Location) {
         self.findings_by_detector
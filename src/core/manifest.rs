@@ -0,0 +1,319 @@
+use crate::config::Config;
+use crate::core::engine::AnalysisEngine;
+use crate::core::registry::DetectorRegistry;
+use crate::models::severity::Severity;
+use crate::utils::fsx::AtomicFile;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Tool state and inputs a report was produced from, written by `--emit-manifest` and
+/// checked by `weasel verify`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunManifest {
+    pub weasel_version: String,
+    /// Effective configuration after CLI flags, `weasel.toml` and defaults were merged
+    pub config: serde_json::Value,
+    /// Registered detector id -> severity
+    pub detectors: BTreeMap<String, Severity>,
+    /// Registered detector id -> `Detector::version`
+    #[serde(default)]
+    pub detector_versions: BTreeMap<String, u32>,
+    pub project_root: PathBuf,
+    pub remappings: BTreeMap<String, PathBuf>,
+    /// Analyzed file, relative to `project_root` when inside it -> SHA-256 of its content
+    pub files: BTreeMap<String, String>,
+    /// SHA-256 of the generated report file
+    pub report_hash: Option<String>,
+}
+
+/// A difference between a manifest and the current tool state or tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ManifestChange {
+    Version {
+        recorded: String,
+        current: String,
+    },
+    DetectorAdded(String),
+    DetectorRemoved(String),
+    SeverityChanged {
+        id: String,
+        recorded: Severity,
+        current: Severity,
+    },
+    DetectorVersionChanged {
+        id: String,
+        recorded: u32,
+        current: u32,
+    },
+    FileChanged(String),
+    FileMissing(String),
+    ReportChanged,
+    InvalidConfig(String),
+}
+
+impl fmt::Display for ManifestChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ManifestChange::Version { recorded, current } => {
+                write!(f, "weasel version: {} -> {}", recorded, current)
+            }
+            ManifestChange::DetectorAdded(id) => write!(f, "detector added: {}", id),
+            ManifestChange::DetectorRemoved(id) => write!(f, "detector removed: {}", id),
+            ManifestChange::SeverityChanged {
+                id,
+                recorded,
+                current,
+            } => write!(f, "detector severity: {} {} -> {}", id, recorded, current),
+            ManifestChange::DetectorVersionChanged {
+                id,
+                recorded,
+                current,
+            } => write!(f, "detector version: {} {} -> {}", id, recorded, current),
+            ManifestChange::FileChanged(path) => write!(f, "file changed: {}", path),
+            ManifestChange::FileMissing(path) => write!(f, "file missing: {}", path),
+            ManifestChange::ReportChanged => write!(f, "report does not match the recorded hash"),
+            ManifestChange::InvalidConfig(e) => write!(f, "recorded config is invalid: {}", e),
+        }
+    }
+}
+
+impl RunManifest {
+    /// Record the engine's state after `analyze`. The report hash is added by `record_report`.
+    pub fn from_engine(engine: &AnalysisEngine) -> io::Result<Self> {
+        let project_root = fs::canonicalize(engine.project_root())?;
+
+        let mut files = BTreeMap::new();
+        for file in engine.analyzed_files() {
+            let path = fs::canonicalize(file)?;
+            let key = match path.strip_prefix(&project_root) {
+                Ok(relative) => relative.to_string_lossy().replace('\\', "/"),
+                Err(_) => path.to_string_lossy().to_string(),
+            };
+            files.insert(key, hash_file(&path)?);
+        }
+
+        Ok(Self {
+            weasel_version: crate::core::version().to_string(),
+            config: serde_json::to_value(engine.config())?,
+            detectors: Self::detector_severities(engine.registry()),
+            detector_versions: Self::detector_versions(engine.registry()),
+            project_root,
+            remappings: engine.remappings().clone().into_iter().collect(),
+            files,
+            report_hash: None,
+        })
+    }
+
+    pub fn record_report(&mut self, report: &Path) -> io::Result<()> {
+        self.report_hash = Some(hash_file(report)?);
+        Ok(())
+    }
+
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        let mut file = AtomicFile::create(path)?;
+        serde_json::to_writer_pretty(&mut file, self)?;
+        file.commit()
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read manifest '{}': {}", path.display(), e))?;
+        serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse manifest '{}': {}", path.display(), e))
+    }
+
+    /// Recompute everything the manifest recorded against the current build and tree.
+    /// An empty result means the report still corresponds to this tool state and input.
+    pub fn verify(&self, report: &Path) -> Vec<ManifestChange> {
+        let mut changes = Vec::new();
+
+        if self.weasel_version != crate::core::version() {
+            changes.push(ManifestChange::Version {
+                recorded: self.weasel_version.clone(),
+                current: crate::core::version().to_string(),
+            });
+        }
+
+        // Detector selection depends on the config, so rebuild the registry from the recorded one
+        match serde_json::from_value::<Config>(self.config.clone()) {
+            Ok(config) => {
+                let mut engine = AnalysisEngine::new(&config);
+                engine.register_built_in_detectors();
+                changes.extend(self.detector_changes(engine.registry()));
+            }
+            Err(e) => changes.push(ManifestChange::InvalidConfig(e.to_string())),
+        }
+
+        for (file, recorded) in &self.files {
+            match hash_file(&self.project_root.join(file)) {
+                Ok(current) if &current == recorded => {}
+                Ok(_) => changes.push(ManifestChange::FileChanged(file.clone())),
+                Err(_) => changes.push(ManifestChange::FileMissing(file.clone())),
+            }
+        }
+
+        if self.report_hash.as_ref() != hash_file(report).ok().as_ref() {
+            changes.push(ManifestChange::ReportChanged);
+        }
+
+        changes
+    }
+
+    fn detector_changes(&self, registry: &DetectorRegistry) -> Vec<ManifestChange> {
+        let current = Self::detector_severities(registry);
+        let mut changes = Vec::new();
+
+        for (id, recorded) in &self.detectors {
+            match current.get(id) {
+                None => changes.push(ManifestChange::DetectorRemoved(id.clone())),
+                Some(severity) if severity != recorded => {
+                    changes.push(ManifestChange::SeverityChanged {
+                        id: id.clone(),
+                        recorded: recorded.clone(),
+                        current: severity.clone(),
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+        for id in current.keys() {
+            if !self.detectors.contains_key(id) {
+                changes.push(ManifestChange::DetectorAdded(id.clone()));
+            }
+        }
+
+        // Manifests written before detectors were versioned have no versions to compare
+        let current_versions = Self::detector_versions(registry);
+        for (id, recorded) in &self.detector_versions {
+            match current_versions.get(id) {
+                Some(version) if version != recorded => {
+                    changes.push(ManifestChange::DetectorVersionChanged {
+                        id: id.clone(),
+                        recorded: *recorded,
+                        current: *version,
+                    })
+                }
+                _ => {}
+            }
+        }
+
+        changes
+    }
+
+    fn detector_severities(registry: &DetectorRegistry) -> BTreeMap<String, Severity> {
+        registry
+            .get_all()
+            .iter()
+            .map(|d| (d.id().to_string(), d.severity()))
+            .collect()
+    }
+
+    fn detector_versions(registry: &DetectorRegistry) -> BTreeMap<String, u32> {
+        registry
+            .get_all()
+            .iter()
+            .map(|d| (d.id().to_string(), d.version()))
+            .collect()
+    }
+}
+
+/// Hex-encoded SHA-256 of a file's content.
+pub fn hash_file(path: &Path) -> io::Result<String> {
+    let content = fs::read(path)?;
+    Ok(format!("{:x}", Sha256::digest(&content)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::write_fixture_project;
+
+    const VAULT: &str = r#"
+        pragma solidity ^0.8.0;
+
+        contract Vault {
+            address owner;
+
+            function setOwner(address newOwner) external {
+                owner = newOwner;
+            }
+        }
+    "#;
+
+    const TOKEN: &str = r#"
+        pragma solidity ^0.8.0;
+
+        contract Token {
+            function pay(address payable to) external {
+                to.transfer(1 ether);
+            }
+        }
+    "#;
+
+    #[test]
+    fn test_manifest_verify_detects_changed_file() {
+        let root = write_fixture_project(
+            "manifest-project",
+            &[
+                ("foundry.toml", "[profile.default]\n"),
+                ("src/Vault.sol", VAULT),
+                ("src/Token.sol", TOKEN),
+            ],
+        );
+        let config = Config {
+            scope: vec![root.join("src")],
+            ..Config::default()
+        };
+        let mut engine = AnalysisEngine::new(&config);
+        engine.register_built_in_detectors();
+        let report = engine.analyze().expect("Analysis failed");
+
+        let report_path = root.join("report.json");
+        fs::write(&report_path, serde_json::to_string_pretty(&report).unwrap()).unwrap();
+        let mut manifest = RunManifest::from_engine(&engine).unwrap();
+        manifest.record_report(&report_path).unwrap();
+
+        let manifest_path = root.join("manifest.json");
+        manifest.write(&manifest_path).unwrap();
+        let manifest = RunManifest::load(&manifest_path).unwrap();
+
+        assert_eq!(
+            manifest.files.keys().collect::<Vec<_>>(),
+            ["src/Token.sol", "src/Vault.sol"]
+        );
+        assert_eq!(manifest.detectors.len(), engine.registry().count());
+        assert_eq!(manifest.detector_versions.len(), engine.registry().count());
+        assert_eq!(manifest.verify(&report_path), []);
+
+        let mut bumped = manifest.clone();
+        bumped
+            .detector_versions
+            .insert("comparison-without-effect".to_string(), 0);
+        assert!(bumped
+            .verify(&report_path)
+            .contains(&ManifestChange::DetectorVersionChanged {
+                id: "comparison-without-effect".to_string(),
+                recorded: 0,
+                current: 1,
+            }));
+
+        fs::write(root.join("src/Vault.sol"), VAULT.replace("owner", "admin")).unwrap();
+        assert_eq!(
+            manifest.verify(&report_path),
+            [ManifestChange::FileChanged("src/Vault.sol".to_string())]
+        );
+
+        fs::write(&report_path, "{}").unwrap();
+        assert_eq!(
+            manifest.verify(&report_path).last(),
+            Some(&ManifestChange::ReportChanged)
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -1,13 +1,28 @@
 pub mod c3_linearization;
+pub mod cache;
 pub mod context;
 pub mod engine;
+pub mod error;
 pub mod finding_collector;
 pub mod import_resolver;
+pub mod manifest;
 pub mod processor;
 pub mod project_detector;
 pub mod registry;
+pub mod snippet;
+pub mod solc_ast;
 pub mod visitor;
 
+/// Exit code of a `weasel run` that reported findings at or above its `fail_on` severity
+pub const EXIT_FINDINGS: i32 = 1;
+/// Exit code for configuration, parse and I/O errors
+pub const EXIT_ERROR: i32 = 2;
+
 pub fn version() -> &'static str {
     env!("CARGO_PKG_VERSION")
 }
+
+/// `git describe` of the checkout weasel was built from, if it was built from one.
+pub fn git_describe() -> Option<&'static str> {
+    option_env!("WEASEL_GIT_DESCRIBE")
+}
@@ -1,17 +1,51 @@
+use crate::core::cache::{FindingsCache, SuppressedCounts};
 use crate::core::context::AnalysisContext;
 use crate::core::finding_collector::FindingCollector;
 use crate::core::visitor::ASTVisitor;
-use crate::models::Location;
+use crate::detectors::OVERLAP_GROUPS;
 use crate::models::SolidityFile;
+use crate::models::{FindingData, Location};
+use crate::output::style::errln;
 use rayon::prelude::*;
 use std::collections::HashMap;
 
-pub struct Processor {}
+/// Receives the findings of each file, ordered by line, as soon as that file has been traversed,
+/// with the file's index in the processed slice. Parallel traversal completes files in no
+/// particular order; the collected `AnalysisResults` are still in file order.
+pub type FileFindingsSink<'a> = dyn Fn(usize, &[FindingData]) + Sync + 'a;
+
+pub struct Processor {
+    cache: Option<FindingsCache>,
+    /// Callbacks of the cache's stale detectors, run on files whose other findings are restored
+    stale_visitor: Option<ASTVisitor>,
+}
+
+impl Default for Processor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 #[allow(dead_code)]
 impl Processor {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            cache: None,
+            stale_visitor: None,
+        }
+    }
+
+    /// Restore the findings of unchanged files from `cache`, and record the others in it.
+    /// `stale_visitor` holds the callbacks of the cache's stale detectors, if there are any.
+    pub fn set_cache(&mut self, cache: Option<FindingsCache>, stale_visitor: Option<ASTVisitor>) {
+        self.cache = cache;
+        self.stale_visitor = stale_visitor;
+    }
+
+    /// Take back the cache set with `set_cache`, holding the findings of the last run.
+    pub fn take_cache(&mut self) -> Option<FindingsCache> {
+        self.stale_visitor = None;
+        self.cache.take()
     }
 
     pub fn process_files(
@@ -19,6 +53,17 @@ impl Processor {
         files: &[SolidityFile],
         visitor: &ASTVisitor,
         context: &AnalysisContext,
+    ) -> AnalysisResults {
+        self.process_files_streaming(files, visitor, context, &|_, _| {})
+    }
+
+    /// `process_files`, handing each file's findings to `sink` as soon as it is traversed.
+    pub fn process_files_streaming(
+        &self,
+        files: &[SolidityFile],
+        visitor: &ASTVisitor,
+        context: &AnalysisContext,
+        sink: &FileFindingsSink,
     ) -> AnalysisResults {
         // Configure Rayon thread pool
         let _ = rayon::ThreadPoolBuilder::new()
@@ -28,11 +73,16 @@ impl Processor {
         // Process files - each file gets its own collector
         let collectors: Vec<FindingCollector> = files
             .par_iter()
-            .map(|file| {
+            .enumerate()
+            .map(|(index, file)| {
                 let mut collector = FindingCollector::new();
 
-                // Run traverse on each file and collect findings
-                let findings = visitor.traverse(file, context);
+                // Run traverse on each file (unless cached) and collect findings
+                let findings = match self.restore_cached(file, context, false, &mut collector) {
+                    Some(findings) => findings,
+                    None => self.analyze_file(file, visitor, context, &mut collector),
+                };
+                sink(index, &findings);
 
                 // Collect findings into thread context
                 for finding in findings {
@@ -46,9 +96,164 @@ impl Processor {
         self.merge_results(collectors)
     }
 
+    /// Traverse files one at a time, re-parsing each from disk and dropping it once its
+    /// findings (with snippets) are collected. Used when the context holds only metadata.
+    pub fn process_files_low_memory(
+        &self,
+        files: &[SolidityFile],
+        visitor: &ASTVisitor,
+        context: &AnalysisContext,
+    ) -> AnalysisResults {
+        self.process_files_low_memory_streaming(files, visitor, context, &|_, _| {})
+    }
+
+    /// `process_files_low_memory`, handing each file's findings to `sink` in file order.
+    pub fn process_files_low_memory_streaming(
+        &self,
+        files: &[SolidityFile],
+        visitor: &ASTVisitor,
+        context: &AnalysisContext,
+        sink: &FileFindingsSink,
+    ) -> AnalysisResults {
+        let mut collector = FindingCollector::new();
+
+        for (index, file) in files.iter().enumerate() {
+            let findings = match self.restore_cached(file, context, true, &mut collector) {
+                Some(findings) => findings,
+                None => {
+                    let loaded = match SolidityFile::from_path(&file.path) {
+                        Ok(loaded) => loaded,
+                        Err(e) => {
+                            errln!("Warning: Skipping file: {}", e);
+                            continue;
+                        }
+                    };
+                    self.analyze_file(&loaded, visitor, context, &mut collector)
+                }
+            };
+            sink(index, &findings);
+            for finding in findings {
+                collector.report_finding(finding.detector_id, finding.location);
+            }
+        }
+
+        self.merge_results(vec![collector])
+    }
+
+    /// Cached findings of a file that has not changed since the cache was written, together
+    /// with those of the stale detectors run on it, counting its suppressed findings in
+    /// `collector`. `reparse` reads the file from disk for them, for contexts holding metadata.
+    fn restore_cached(
+        &self,
+        file: &SolidityFile,
+        context: &AnalysisContext,
+        reparse: bool,
+        collector: &mut FindingCollector,
+    ) -> Option<Vec<FindingData>> {
+        let cache = self.cache.as_ref()?;
+        let (mut findings, mut suppressed) = cache.restore(&file.path)?;
+        if let Some(stale_visitor) = &self.stale_visitor {
+            let loaded;
+            let file = if reparse {
+                loaded = SolidityFile::from_path(&file.path).ok()?;
+                &loaded
+            } else {
+                file
+            };
+            let mut rerun = Self::traverse_sorted(file, stale_visitor, context);
+            suppressed.extend(Self::apply_suppressions(file, &mut rerun));
+            findings.extend(rerun);
+            findings.sort_by_key(|finding| finding.location.line);
+            cache.record_rerun(&file.path, &findings, &suppressed);
+        }
+        collector.record_suppressed(suppressed.values().sum());
+        Some(Self::drop_overlapping(findings))
+    }
+
+    /// Traverse a file, apply its suppressions and drop overlapping findings. The cache records
+    /// the findings before the overlap pass, for merging with detectors run on a later restore.
+    fn analyze_file(
+        &self,
+        file: &SolidityFile,
+        visitor: &ASTVisitor,
+        context: &AnalysisContext,
+        collector: &mut FindingCollector,
+    ) -> Vec<FindingData> {
+        let mut findings = Self::traverse_sorted(file, visitor, context);
+        let suppressed = Self::apply_suppressions(file, &mut findings);
+        collector.record_suppressed(suppressed.values().sum());
+        if let Some(cache) = &self.cache {
+            cache.record(&file.path, &findings, &suppressed);
+        }
+        Self::drop_overlapping(findings)
+    }
+
+    /// Findings of one file, stably ordered by line.
+    fn traverse_sorted(
+        file: &SolidityFile,
+        visitor: &ASTVisitor,
+        context: &AnalysisContext,
+    ) -> Vec<FindingData> {
+        let mut findings = visitor.traverse(file, context);
+        findings.sort_by_key(|finding| finding.location.line);
+        findings
+    }
+
+    /// Drop the findings silenced by the file's `weasel-disable` comments, returning how many of
+    /// each detector's findings were dropped.
+    fn apply_suppressions(
+        file: &SolidityFile,
+        findings: &mut Vec<FindingData>,
+    ) -> SuppressedCounts {
+        let mut suppressed = SuppressedCounts::new();
+        if file.suppressions.is_empty() {
+            return suppressed;
+        }
+        findings.retain(|finding| {
+            let silenced = file
+                .suppressions
+                .suppresses(finding.detector_id, finding.location.line);
+            if silenced {
+                *suppressed.entry(finding.detector_id).or_default() += 1;
+            }
+            !silenced
+        });
+        suppressed
+    }
+
+    /// Keep, for each line and group of [`OVERLAP_GROUPS`], only the findings of the first
+    /// listed detector that reported the line. Runs after suppressions, so silencing one
+    /// detector of a group leaves the others' findings.
+    fn drop_overlapping(mut findings: Vec<FindingData>) -> Vec<FindingData> {
+        let group_of = |detector_id: &str| {
+            OVERLAP_GROUPS
+                .iter()
+                .enumerate()
+                .find_map(|(group, members)| {
+                    let rank = members.iter().position(|member| *member == detector_id)?;
+                    Some((group, rank))
+                })
+        };
+        let mut first: HashMap<(usize, usize), usize> = HashMap::new();
+        for finding in &findings {
+            if let Some((group, rank)) = group_of(finding.detector_id) {
+                let best = first.entry((finding.location.line, group)).or_insert(rank);
+                *best = (*best).min(rank);
+            }
+        }
+        if !first.is_empty() {
+            findings.retain(|finding| {
+                group_of(finding.detector_id)
+                    .is_none_or(|(group, rank)| first[&(finding.location.line, group)] == rank)
+            });
+        }
+        findings
+    }
+
     /// Merge collectors into final results
     fn merge_results(&self, collectors: Vec<FindingCollector>) -> AnalysisResults {
         let mut findings_by_detector: HashMap<&'static str, Vec<Location>> = HashMap::new();
+        let suppressed = collectors.iter().map(|c| c.suppressed()).sum();
 
         for collector in collectors {
             for detector_id in collector.detector_ids_with_findings() {
@@ -63,6 +268,7 @@ impl Processor {
 
         AnalysisResults {
             findings_by_detector,
+            suppressed,
         }
     }
 
@@ -77,6 +283,8 @@ impl Processor {
 #[derive(Debug)]
 pub struct AnalysisResults {
     pub findings_by_detector: HashMap<&'static str, Vec<Location>>,
+    /// Findings dropped by `weasel-disable` comments
+    pub suppressed: usize,
 }
 
 impl AnalysisResults {
@@ -1,3 +1,5 @@
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use regex::Regex;
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::fs;
@@ -18,6 +20,16 @@ pub struct ProjectConfig {
     pub library_paths: Vec<PathBuf>,
     pub project_root: PathBuf,
     pub default_scope: Vec<PathBuf>,
+    /// Directories the build tool keeps out of the sources (Foundry `test` and `script`),
+    /// relative to the project root. Added to the configured excludes.
+    pub default_exclude: Vec<PathBuf>,
+    /// Foundry `skip` globs, relative to the project root.
+    pub skip: Vec<String>,
+    /// Build output and installed package directories, relative to the project root. Directory
+    /// walks skip them when they reach them.
+    pub build_dirs: Vec<PathBuf>,
+    /// Build config file the defaults above were read from, for notices.
+    pub config_file: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Default)]
@@ -42,6 +54,16 @@ struct FoundryProfile {
     libs: Vec<String>,
     #[serde(default)]
     remappings: Vec<String>,
+    #[serde(default = "default_test")]
+    test: String,
+    #[serde(default = "default_script")]
+    script: String,
+    #[serde(default)]
+    skip: Vec<String>,
+    #[serde(default = "default_out")]
+    out: String,
+    #[serde(default = "default_cache_path")]
+    cache_path: String,
 }
 
 fn default_src() -> String {
@@ -52,13 +74,66 @@ fn default_libs() -> Vec<String> {
     vec!["lib".to_string()]
 }
 
+fn default_test() -> String {
+    "test".to_string()
+}
+
+fn default_script() -> String {
+    "script".to_string()
+}
+
+fn default_out() -> String {
+    "out".to_string()
+}
+
+fn default_cache_path() -> String {
+    "cache".to_string()
+}
+
 impl Default for FoundryProfile {
     fn default() -> Self {
         Self {
             src: default_src(),
             libs: default_libs(),
             remappings: Vec::new(),
+            test: default_test(),
+            script: default_script(),
+            skip: Vec::new(),
+            out: default_out(),
+            cache_path: default_cache_path(),
+        }
+    }
+}
+
+/// Foundry `skip` globs, matched against paths relative to the project root.
+#[derive(Debug, Clone)]
+pub struct SkipGlobs {
+    root: PathBuf,
+    globs: GlobSet,
+}
+
+impl SkipGlobs {
+    pub fn new(root: &Path, patterns: &[String]) -> Result<Self, String> {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in patterns {
+            let pattern = pattern.strip_prefix("./").unwrap_or(pattern);
+            let glob = Glob::new(pattern)
+                .map_err(|e| format!("Invalid skip pattern '{}': {}", pattern, e))?;
+            builder.add(glob);
         }
+        let globs = builder
+            .build()
+            .map_err(|e| format!("Invalid skip patterns: {}", e))?;
+        Ok(Self {
+            root: fs::canonicalize(root).unwrap_or_else(|_| root.to_path_buf()),
+            globs,
+        })
+    }
+
+    /// Whether a canonical path under the project root matches any pattern.
+    pub fn is_match(&self, path: &Path) -> bool {
+        path.strip_prefix(&self.root)
+            .is_ok_and(|relative| self.globs.is_match(relative))
     }
 }
 
@@ -89,6 +164,10 @@ impl ProjectConfig {
             library_paths,
             project_root,
             default_scope,
+            default_exclude: Vec::new(),
+            skip: Vec::new(),
+            build_dirs: vec![PathBuf::from("node_modules")],
+            config_file: None,
         }
     }
 
@@ -190,19 +269,45 @@ impl ProjectConfig {
             vec![PathBuf::from(&profile.src)]
         };
 
+        let default_exclude = [&profile.test, &profile.script]
+            .into_iter()
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .collect();
+        let build_dirs = [&profile.out, &profile.cache_path]
+            .into_iter()
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .collect();
+
         Ok(ProjectConfig {
             project_type: ProjectType::Foundry,
             remappings,
             library_paths,
             project_root: project_root.clone(),
             default_scope,
+            default_exclude,
+            skip: profile.skip.clone(),
+            build_dirs,
+            config_file: Some("foundry.toml".to_string()),
         })
     }
 
     /// Load Hardhat project configuration
     fn load_hardhat_config(project_root: &PathBuf) -> Result<ProjectConfig, String> {
         let library_paths = vec![PathBuf::from("node_modules")];
-        let default_scope = vec![PathBuf::from("contracts")];
+
+        // `paths.sources` can only be read from the config source, which is JS or TS
+        let mut config_file = None;
+        let mut sources = None;
+        for name in ["hardhat.config.ts", "hardhat.config.js"] {
+            if let Ok(content) = fs::read_to_string(project_root.join(name)) {
+                config_file = Some(name.to_string());
+                sources = Self::parse_hardhat_sources(&content);
+                break;
+            }
+        }
+        let default_scope = vec![PathBuf::from(sources.as_deref().unwrap_or("contracts"))];
 
         let mut remappings = HashMap::new();
         let openzeppelin_path = project_root.join("node_modules/@openzeppelin");
@@ -216,6 +321,12 @@ impl ProjectConfig {
             library_paths,
             project_root: project_root.clone(),
             default_scope,
+            default_exclude: Vec::new(),
+            skip: Vec::new(),
+            build_dirs: ["artifacts", "cache", "node_modules"]
+                .map(PathBuf::from)
+                .to_vec(),
+            config_file,
         })
     }
 
@@ -231,6 +342,10 @@ impl ProjectConfig {
             library_paths,
             project_root: project_root.clone(),
             default_scope,
+            default_exclude: Vec::new(),
+            skip: Vec::new(),
+            build_dirs: ["build", "node_modules"].map(PathBuf::from).to_vec(),
+            config_file: None,
         })
     }
 
@@ -246,9 +361,22 @@ impl ProjectConfig {
             library_paths,
             project_root: project_root.clone(),
             default_scope,
+            default_exclude: Vec::new(),
+            skip: Vec::new(),
+            build_dirs: vec![PathBuf::from("node_modules")],
+            config_file: None,
         })
     }
 
+    /// `paths: { sources: "..." }` of a Hardhat config, without a leading `./`.
+    fn parse_hardhat_sources(content: &str) -> Option<String> {
+        let pattern = Regex::new(r#"paths\s*:\s*\{[^}]*?\bsources\s*:\s*["'`]([^"'`]+)["'`]"#)
+            .expect("valid hardhat sources pattern");
+        let sources = pattern.captures(content)?.get(1)?.as_str();
+        let sources = sources.strip_prefix("./").unwrap_or(sources);
+        (!sources.is_empty()).then(|| sources.to_string())
+    }
+
     /// Parse default remappings (common conventions)
     fn parse_default_remappings(project_root: &Path) -> Result<HashMap<String, PathBuf>, String> {
         let mut remappings = HashMap::new();
@@ -344,8 +472,47 @@ impl ProjectConfig {
     }
 }
 
-// Note: Project detection and configuration loading is tested through
-// integration tests when AnalysisEngine initializes projects. Direct
-// unit tests would require creating temporary project structures with
-// the tempfile crate (not currently a dependency). The functionality
-// is validated through actual project analysis in the engine.
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::write_fixture_project;
+
+    #[test]
+    fn test_build_config_scope_defaults() {
+        let root = write_fixture_project(
+            "project-detector-foundry",
+            &[(
+                "foundry.toml",
+                "[profile.default]\nsrc = \"contracts\"\nscript = \"scripts\"\nskip = [\"test/**\"]\n",
+            )],
+        );
+        let config = ProjectConfig::auto_detect(&root).unwrap();
+        assert_eq!(config.default_scope, [PathBuf::from("contracts")]);
+        assert_eq!(
+            config.default_exclude,
+            [PathBuf::from("test"), PathBuf::from("scripts")]
+        );
+        assert_eq!(config.skip, ["test/**"]);
+
+        let skip = SkipGlobs::new(&root, &["./src/legacy/**".to_string()]).unwrap();
+        assert!(skip.is_match(&root.join("src/legacy/v1/Old.sol")));
+        assert!(!skip.is_match(&root.join("src/Vault.sol")));
+        assert!(SkipGlobs::new(&root, &["src/[".to_string()]).is_err());
+        let _ = std::fs::remove_dir_all(&root);
+
+        let config = r#"
+            module.exports = {
+              solidity: "0.8.24",
+              paths: { artifacts: "./build", sources: "./src" },
+            };
+        "#;
+        assert_eq!(
+            ProjectConfig::parse_hardhat_sources(config).as_deref(),
+            Some("src")
+        );
+        assert_eq!(
+            ProjectConfig::parse_hardhat_sources("module.exports = { solidity: \"0.8.24\" };"),
+            None
+        );
+    }
+}
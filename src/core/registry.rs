@@ -1,3 +1,4 @@
+use crate::core::error::WeaselError;
 use crate::detectors::Detector;
 use crate::models::Severity;
 use std::collections::HashMap;
@@ -9,6 +10,12 @@ pub struct DetectorRegistry {
     detectors_by_severity: HashMap<Severity, Vec<Arc<dyn Detector>>>,
 }
 
+impl Default for DetectorRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl DetectorRegistry {
     pub fn new() -> Self {
         Self {
@@ -18,8 +25,13 @@ impl DetectorRegistry {
         }
     }
 
-    pub fn register(&mut self, detector: Arc<dyn Detector>) {
+    /// Add a detector. Fails, leaving the registry unchanged, when a detector with the same id
+    /// is already registered.
+    pub fn register(&mut self, detector: Arc<dyn Detector>) -> Result<(), WeaselError> {
         let id = detector.id().to_string();
+        if self.detectors_by_id.contains_key(&id) {
+            return Err(WeaselError::DuplicateDetector(id));
+        }
         let severity = detector.severity();
 
         // Store detector Arc
@@ -30,6 +42,30 @@ impl DetectorRegistry {
             .entry(severity)
             .or_default()
             .push(detector);
+        Ok(())
+    }
+
+    /// Add a detector, replacing the one registered under the same id, if any, at its position
+    /// in the registration order. Returns the replaced detector.
+    pub fn register_replacing(&mut self, detector: Arc<dyn Detector>) -> Option<Arc<dyn Detector>> {
+        let id = detector.id();
+        let Some(replaced) = self.detectors_by_id.get(id).cloned() else {
+            let _ = self.register(detector);
+            return None;
+        };
+
+        if let Some(slot) = self.detectors.iter_mut().find(|d| d.id() == id) {
+            *slot = detector.clone();
+        }
+        if let Some(by_severity) = self.detectors_by_severity.get_mut(&replaced.severity()) {
+            by_severity.retain(|d| d.id() != id);
+        }
+        self.detectors_by_severity
+            .entry(detector.severity())
+            .or_default()
+            .push(detector.clone());
+        self.detectors_by_id.insert(id.to_string(), detector);
+        Some(replaced)
     }
 
     pub fn get(&self, id: &str) -> Option<Arc<dyn Detector>> {
@@ -47,7 +83,106 @@ impl DetectorRegistry {
         self.detectors.iter().cloned().collect()
     }
 
-    pub fn count(&self) -> usize {
+    /// Ids of the registered detectors, in registration order.
+    pub fn ids(&self) -> Vec<&'static str> {
+        self.detectors.iter().map(|d| d.id()).collect()
+    }
+
+    pub fn len(&self) -> usize {
         self.detectors.len()
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.detectors.is_empty()
+    }
+
+    pub fn count(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::visitor::ASTVisitor;
+
+    struct TestDetector {
+        id: &'static str,
+        name: &'static str,
+        severity: Severity,
+    }
+
+    impl Detector for TestDetector {
+        fn id(&self) -> &'static str {
+            self.id
+        }
+
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn severity(&self) -> Severity {
+            self.severity.clone()
+        }
+
+        fn description(&self) -> &str {
+            self.name
+        }
+
+        fn example(&self) -> Option<String> {
+            None
+        }
+
+        fn register_callbacks(self: Arc<Self>, _visitor: &mut ASTVisitor) {}
+    }
+
+    fn detector(id: &'static str, name: &'static str, severity: Severity) -> Arc<dyn Detector> {
+        Arc::new(TestDetector { id, name, severity })
+    }
+
+    #[test]
+    fn test_register_rejects_duplicate_ids() {
+        let mut registry = DetectorRegistry::new();
+        registry
+            .register(detector("magic-numbers", "Built-in", Severity::NC))
+            .unwrap();
+        registry
+            .register(detector("tx-origin", "Other", Severity::Low))
+            .unwrap();
+
+        let error = registry
+            .register(detector("magic-numbers", "Fork", Severity::Low))
+            .unwrap_err();
+        assert!(matches!(&error, WeaselError::DuplicateDetector(id) if id == "magic-numbers"));
+        assert_eq!(registry.len(), 2);
+        assert_eq!(registry.ids(), ["magic-numbers", "tx-origin"]);
+        assert_eq!(registry.get("magic-numbers").unwrap().name(), "Built-in");
+        assert_eq!(registry.get_by_severity(&Severity::Low).len(), 1);
+    }
+
+    #[test]
+    fn test_register_replacing_overrides_in_place() {
+        let mut registry = DetectorRegistry::new();
+        registry
+            .register(detector("magic-numbers", "Built-in", Severity::NC))
+            .unwrap();
+        registry
+            .register(detector("tx-origin", "Other", Severity::Low))
+            .unwrap();
+
+        let replaced =
+            registry.register_replacing(detector("magic-numbers", "Fork", Severity::Low));
+        assert_eq!(replaced.unwrap().name(), "Built-in");
+        assert_eq!(registry.len(), 2);
+        assert_eq!(registry.ids(), ["magic-numbers", "tx-origin"]);
+        assert_eq!(registry.get("magic-numbers").unwrap().name(), "Fork");
+        assert_eq!(registry.get_all()[0].name(), "Fork");
+        assert!(registry.get_by_severity(&Severity::NC).is_empty());
+        assert_eq!(registry.get_by_severity(&Severity::Low).len(), 2);
+
+        assert!(registry
+            .register_replacing(detector("new-id", "New", Severity::Gas))
+            .is_none());
+        assert_eq!(registry.ids(), ["magic-numbers", "tx-origin", "new-id"]);
+    }
 }
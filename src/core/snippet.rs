@@ -0,0 +1,168 @@
+use crate::config::Config;
+use crate::core::context::AnalysisContext;
+use crate::core::engine::AnalysisEngine;
+use crate::core::error::WeaselError;
+use crate::core::visitor::ASTVisitor;
+use crate::models::finding::Location;
+use crate::models::SolidityFile;
+use solang_parser::parse;
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+/// File name used in the locations of findings reported for a snippet.
+pub const SNIPPET_FILE_NAME: &str = "snippet.sol";
+
+/// A single location reported by a detector.
+#[derive(Debug, Clone)]
+pub struct FindingInstance {
+    pub detector_id: &'static str,
+    pub location: Location,
+}
+
+/// Run built-in detectors on a Solidity source string, without a config file or filesystem access.
+///
+/// The source is analyzed as a standalone file named [`SNIPPET_FILE_NAME`] with default detector
+/// settings. Imports are not resolved, so inheritance chains and cross-contract lookups only see
+/// contracts declared in the snippet itself; detectors that rely on base contracts from other
+/// files run with that partial context and can report less than `weasel run` on the full project.
+///
+/// Findings from all requested detectors are returned in source order.
+///
+/// ```
+/// let source = r#"
+///     pragma solidity ^0.8.0;
+///     contract Shop {
+///         function buy(uint256 price) external payable {
+///             payable(msg.sender).transfer(msg.value - price);
+///         }
+///     }
+/// "#;
+///
+/// let findings = weasel::analyze_snippet(source, &["deprecated-transfer", "msg-value-refund"])?;
+/// for finding in &findings {
+///     println!("{} at line {}", finding.detector_id, finding.location.line);
+/// }
+/// assert_eq!(findings.len(), 3);
+/// # Ok::<(), weasel::WeaselError>(())
+/// ```
+pub fn analyze_snippet(
+    source: &str,
+    detectors: &[&str],
+) -> Result<Vec<FindingInstance>, WeaselError> {
+    let config = Config::default();
+    let mut engine = AnalysisEngine::new(&config);
+    engine.register_built_in_detectors();
+    let registry = engine.registry();
+
+    let mut visitor = ASTVisitor::new();
+    let mut registered = HashSet::new();
+    for id in detectors {
+        let detector = registry
+            .get(id)
+            .ok_or_else(|| WeaselError::UnknownDetector(id.to_string()))?;
+        if registered.insert(detector.id()) {
+            detector.register_callbacks(&mut visitor);
+        }
+    }
+
+    let (source_unit, _comments) =
+        parse(source, 0).map_err(|errors| WeaselError::Parse(format!("{:?}", errors)))?;
+    let mut file = SolidityFile::new(
+        PathBuf::from(SNIPPET_FILE_NAME),
+        source.to_string(),
+        source_unit,
+    );
+    file.extract_metadata();
+
+    let mut context = AnalysisContext::new();
+    context.files.push(file.clone());
+    // Missing bases only leave inheritance chains incomplete, which detectors already tolerate
+    let _ = context.build_cache();
+
+    let mut findings: Vec<FindingInstance> = visitor
+        .traverse(&file, &context)
+        .into_iter()
+        .map(|finding| FindingInstance {
+            detector_id: finding.detector_id,
+            location: finding.location,
+        })
+        .collect();
+    findings.sort_by_key(|f| (f.location.line, f.location.column));
+
+    Ok(findings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CODE: &str = r#"
+        pragma solidity ^0.8.0;
+
+        contract Shop {
+            uint256 price;
+            address payable treasury;
+
+            function buy() external payable {
+                (bool ok, ) = msg.sender.call{value: msg.value - price}("");
+                require(ok);
+                treasury.transfer(price);
+            }
+
+            function buyFor(address to, uint256 fee) external payable {
+                (bool ok, ) = to.call{value: msg.value - fee}("");
+                require(ok);
+            }
+        }
+    "#;
+
+    #[test]
+    fn test_analyze_snippet_interleaves_detectors() {
+        let findings = analyze_snippet(CODE, &["msg-value-refund", "deprecated-transfer"]).unwrap();
+
+        let found: Vec<_> = findings
+            .iter()
+            .map(|f| (f.detector_id, f.location.line))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("msg-value-refund", 9),
+                ("deprecated-transfer", 11),
+                ("msg-value-refund", 15),
+            ]
+        );
+        assert!(findings
+            .iter()
+            .all(|f| f.location.file == SNIPPET_FILE_NAME));
+    }
+
+    #[test]
+    fn test_analyze_snippet_errors() {
+        assert_eq!(
+            analyze_snippet(CODE, &["deprecated-transfer", "no-such-detector"]).unwrap_err(),
+            WeaselError::UnknownDetector("no-such-detector".to_string())
+        );
+        assert!(matches!(
+            analyze_snippet("contract {", &["deprecated-transfer"]),
+            Err(WeaselError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_analyze_snippet_without_imported_bases() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            import "./Base.sol";
+
+            contract Vault is Base {
+                function _withdraw() external {}
+            }
+        "#;
+
+        let findings = analyze_snippet(code, &["naming-convention"]).unwrap();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].location.line, 7);
+    }
+}
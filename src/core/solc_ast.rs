@@ -0,0 +1,656 @@
+use crate::models::finding::Location;
+use crate::models::scope::{
+    ContractInfo, ContractType, FunctionInfo, FunctionMutability, FunctionParameter, FunctionType,
+    FunctionVisibility, ImportInfo, StateVariableInfo, TypeInfo, VariableMutability,
+    VariableVisibility,
+};
+use crate::models::SolidityFile;
+use crate::output::style::errln;
+use crate::utils::location::loc_to_location;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use solang_parser::pt::{Loc, SourceUnit, Type};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::str::FromStr;
+
+/// Which parser provides contract, function and state variable metadata.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AstSource {
+    #[default]
+    Solang,
+    Solc,
+}
+
+impl FromStr for AstSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "solang" => Ok(AstSource::Solang),
+            "solc" => Ok(AstSource::Solc),
+            _ => Err(format!("Invalid AST source: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for AstSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AstSource::Solang => write!(f, "solang"),
+            AstSource::Solc => write!(f, "solc"),
+        }
+    }
+}
+
+/// Compiler-produced ASTs for the project, keyed by canonical source path.
+///
+/// ASTs are read from Foundry (`out/`) or Hardhat (`artifacts/build-info/`) build output.
+/// Without artifacts, a `solc` binary on `PATH` compiles files on demand.
+#[derive(Debug)]
+pub struct SolcAstIndex {
+    project_root: PathBuf,
+    remappings: Vec<String>,
+    asts: HashMap<PathBuf, Value>,
+    solc: Option<PathBuf>,
+}
+
+impl SolcAstIndex {
+    pub fn discover(project_root: &Path, remappings: Vec<String>) -> Self {
+        let mut index = Self {
+            project_root: project_root.to_path_buf(),
+            remappings,
+            asts: HashMap::new(),
+            solc: find_in_path("solc"),
+        };
+
+        index.load_foundry_artifacts(&project_root.join("out"));
+        index.load_hardhat_build_info(&project_root.join("artifacts").join("build-info"));
+
+        if index.asts.is_empty() && index.solc.is_none() {
+            errln!(
+                "Warning: --ast-source solc: no build artifacts or solc binary found, using solang-parser"
+            );
+        }
+
+        index
+    }
+
+    /// AST for a source file, or `None` when it is missing, stale or fails to compile.
+    pub fn ast_for(&mut self, path: &Path) -> Option<&Value> {
+        let key = fs::canonicalize(path).ok()?;
+
+        let cached = match self.asts.get(&key) {
+            Some(ast) if is_stale(&key, ast) => {
+                errln!(
+                    "Warning: build artifact for '{}' is out of date",
+                    path.display()
+                );
+                self.asts.remove(&key);
+                false
+            }
+            cached => cached.is_some(),
+        };
+        if !cached {
+            self.compile(&key);
+        }
+
+        self.asts.get(&key)
+    }
+
+    fn insert(&mut self, source_path: &str, ast: Value) {
+        let path = self.project_root.join(source_path);
+        let key = fs::canonicalize(&path).unwrap_or(path);
+        self.asts.entry(key).or_insert(ast);
+    }
+
+    /// `out/<File>.sol/<Contract>.json`, each carrying the AST of its whole source file.
+    fn load_foundry_artifacts(&mut self, dir: &Path) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                self.load_foundry_artifacts(&path);
+                continue;
+            }
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            let Some(mut artifact) = read_json(&path) else {
+                continue;
+            };
+            let ast = artifact["ast"].take();
+            if let Some(source_path) = ast["absolutePath"].as_str().map(str::to_string) {
+                self.insert(&source_path, ast);
+            }
+        }
+    }
+
+    /// `artifacts/build-info/<hash>.json`, holding the full standard JSON output.
+    fn load_hardhat_build_info(&mut self, dir: &Path) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some(mut build_info) = read_json(&path) else {
+                continue;
+            };
+            if let Value::Object(sources) = build_info["output"]["sources"].take() {
+                for (source_path, mut source) in sources {
+                    self.insert(&source_path, source["ast"].take());
+                }
+            }
+        }
+    }
+
+    /// Compile a single file with `solc --standard-json`, caching the ASTs of it and its imports.
+    /// Does nothing when no `solc` binary was found.
+    fn compile(&mut self, path: &Path) {
+        let Some(solc) = &self.solc else {
+            return;
+        };
+        let source_name = path
+            .strip_prefix(&self.project_root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/");
+
+        let input = json!({
+            "language": "Solidity",
+            "sources": { &source_name: { "urls": [path] } },
+            "settings": {
+                "remappings": self.remappings,
+                "outputSelection": { "*": { "": ["ast"] } }
+            }
+        });
+
+        let output = match run_solc(solc, &self.project_root, &input) {
+            Ok(output) => output,
+            Err(e) => {
+                errln!("Warning: solc failed for '{}': {}", path.display(), e);
+                return;
+            }
+        };
+
+        let errors: Vec<&str> = output["errors"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|e| e["severity"] == "error")
+            .filter_map(|e| e["formattedMessage"].as_str())
+            .collect();
+        if !errors.is_empty() {
+            errln!(
+                "Warning: solc could not compile '{}', using solang-parser:\n{}",
+                path.display(),
+                errors.join("\n")
+            );
+            return;
+        }
+
+        if let Some(sources) = output["sources"].as_object() {
+            for (name, source) in sources {
+                self.insert(name, source["ast"].clone());
+            }
+        }
+    }
+}
+
+fn run_solc(solc: &Path, project_root: &Path, input: &Value) -> Result<Value, String> {
+    let mut child = Command::new(solc)
+        .arg("--standard-json")
+        .arg("--base-path")
+        .arg(project_root)
+        .arg("--allow-paths")
+        .arg(project_root)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(input.to_string().as_bytes())
+            .map_err(|e| e.to_string())?;
+    }
+
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
+    serde_json::from_slice(&output.stdout).map_err(|e| e.to_string())
+}
+
+fn find_in_path(binary: &str) -> Option<PathBuf> {
+    let path = std::env::var_os("PATH")?;
+    std::env::split_paths(&path)
+        .flat_map(|dir| [dir.join(binary), dir.join(format!("{}.exe", binary))])
+        .find(|candidate| candidate.is_file())
+}
+
+fn read_json(path: &Path) -> Option<Value> {
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// The source unit spans the whole file, so a length mismatch means the artifact was
+/// built from a different version of it.
+fn is_stale(source: &Path, ast: &Value) -> bool {
+    match (src_range(ast), fs::metadata(source)) {
+        (Some((start, end)), Ok(metadata)) => start != 0 || end as u64 != metadata.len(),
+        _ => true,
+    }
+}
+
+/// Replace solang-derived contract, function and state variable metadata with the
+/// compiler's. Other contract members (events, errors, structs, ...) are kept.
+pub fn apply_metadata(file: &mut SolidityFile, ast: &Value) {
+    for node in nodes_of_type(ast, "ContractDefinition") {
+        let Some(contract) = contract_info(node, file) else {
+            continue;
+        };
+        match file
+            .contract_definitions
+            .iter_mut()
+            .find(|c| c.name == contract.name)
+        {
+            Some(existing) => {
+                existing.loc = contract.loc;
+                existing.contract_type = contract.contract_type;
+                existing.direct_bases = contract.direct_bases;
+                existing.state_variables = contract.state_variables;
+                existing.function_definitions = contract.function_definitions;
+            }
+            None => file.contract_definitions.push(contract),
+        }
+    }
+}
+
+/// Build a file from the compiler AST alone, for sources solang-parser cannot parse.
+/// The file has no solang AST, so only cross-contract lookups see it; it is not traversed.
+pub fn metadata_only_file(path: &Path, ast: &Value) -> Result<SolidityFile, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read file '{}': {}", path.display(), e))?;
+    let mut file = SolidityFile::new(path.to_path_buf(), content, SourceUnit(Vec::new()));
+
+    for pragma in nodes_of_type(ast, "PragmaDirective") {
+        // Reuse solang's version formatting on the pragma statement alone
+        let Some(text) = src_range(pragma).and_then(|(start, end)| file.content.get(start..end))
+        else {
+            continue;
+        };
+        if let Ok((source_unit, _)) = solang_parser::parse(text, 0) {
+            let mut pragma_file = SolidityFile::new(path.to_path_buf(), String::new(), source_unit);
+            pragma_file.extract_metadata();
+            if pragma_file.solidity_version.is_some() {
+                file.solidity_version = pragma_file.solidity_version;
+            }
+        }
+    }
+
+    file.imports = nodes_of_type(ast, "ImportDirective")
+        .filter_map(|import| {
+            Some(ImportInfo {
+                loc: src_location(import, &file)?,
+                import_path: import["file"].as_str()?.to_string(),
+                resolved_path: None,
+                symbols: import_symbols(import),
+            })
+        })
+        .collect();
+
+    apply_metadata(&mut file, ast);
+    Ok(file)
+}
+
+fn nodes_of_type<'a>(ast: &'a Value, node_type: &'a str) -> impl Iterator<Item = &'a Value> {
+    ast["nodes"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(move |node| node["nodeType"] == node_type)
+}
+
+fn import_symbols(import: &Value) -> Vec<String> {
+    if let Some(alias) = import["unitAlias"].as_str().filter(|a| !a.is_empty()) {
+        return vec![alias.to_string()];
+    }
+    import["symbolAliases"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|alias| {
+            alias["local"]
+                .as_str()
+                .or_else(|| alias["foreign"]["name"].as_str())
+                .map(str::to_string)
+        })
+        .collect()
+}
+
+/// Byte range of a node's `src` attribute (`start:length:fileIndex`).
+fn src_range(node: &Value) -> Option<(usize, usize)> {
+    let mut parts = node["src"].as_str()?.split(':');
+    let start: usize = parts.next()?.parse().ok()?;
+    let length: usize = parts.next()?.parse().ok()?;
+    Some((start, start + length))
+}
+
+fn src_location(node: &Value, file: &SolidityFile) -> Option<Location> {
+    let (start, end) = src_range(node)?;
+    if end > file.content.len() {
+        return None;
+    }
+    Some(loc_to_location(&Loc::File(0, start, end), file))
+}
+
+/// Last segment of a possibly qualified name (`IdentifierPath` or legacy `UserDefinedTypeName`).
+fn path_name(node: &Value) -> Option<&str> {
+    node["name"]
+        .as_str()
+        .or_else(|| node["namePath"].as_str())
+        .or_else(|| node["pathNode"]["name"].as_str())
+}
+
+fn contract_info(node: &Value, file: &SolidityFile) -> Option<ContractInfo> {
+    let contract_type = match node["contractKind"].as_str()? {
+        "interface" => ContractType::Interface,
+        "library" => ContractType::Library,
+        _ if node["abstract"] == true => ContractType::Abstract,
+        _ => ContractType::Contract,
+    };
+
+    let direct_bases = node["baseContracts"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|base| path_name(&base["baseName"]))
+        .map(|name| name.rsplit('.').next().unwrap_or(name).to_string())
+        .collect();
+
+    let state_variables = nodes_of_type(node, "VariableDeclaration")
+        .filter_map(|var| state_variable_info(var, file))
+        .collect();
+
+    let function_definitions = nodes_of_type(node, "FunctionDefinition")
+        .filter_map(|func| function_info(func, file))
+        .collect();
+
+    Some(ContractInfo {
+        loc: src_location(node, file)?,
+        name: node["name"].as_str()?.to_string(),
+        contract_type,
+        file_path: file.path.to_string_lossy().to_string(),
+        direct_bases,
+        inheritance_chain: Vec::new(),
+        state_variables,
+        function_definitions,
+        enums: Vec::new(),
+        errors: Vec::new(),
+        events: Vec::new(),
+        structs: Vec::new(),
+        modifiers: Vec::new(),
+        type_definitions: Vec::new(),
+        using_directives: Vec::new(),
+    })
+}
+
+fn state_variable_info(node: &Value, file: &SolidityFile) -> Option<StateVariableInfo> {
+    let visibility = match node["visibility"].as_str() {
+        Some("public") => VariableVisibility::Public,
+        Some("private") => VariableVisibility::Private,
+        Some("external") => VariableVisibility::External,
+        _ => VariableVisibility::Internal,
+    };
+    let mutability = match node["mutability"].as_str() {
+        Some("constant") => VariableMutability::Constant,
+        Some("immutable") => VariableMutability::Immutable,
+        _ if node["constant"] == true => VariableMutability::Constant,
+        _ => VariableMutability::Mutable,
+    };
+
+    Some(StateVariableInfo {
+        loc: src_location(node, file)?,
+        name: node["name"].as_str()?.to_string(),
+        type_info: type_info(&node["typeName"]),
+        visibility,
+        is_constant: mutability == VariableMutability::Constant,
+        is_immutable: mutability == VariableMutability::Immutable,
+        mutability,
+    })
+}
+
+fn function_info(node: &Value, file: &SolidityFile) -> Option<FunctionInfo> {
+    let function_type = match node["kind"].as_str() {
+        Some("constructor") => FunctionType::Constructor,
+        Some("fallback") => FunctionType::Fallback,
+        Some("receive") => FunctionType::Receive,
+        _ => FunctionType::Function,
+    };
+    let name = match node["name"].as_str() {
+        Some(name) if !name.is_empty() => name.to_string(),
+        _ => "Unnamed".to_string(),
+    };
+    let visibility = match node["visibility"].as_str() {
+        Some("public") => FunctionVisibility::Public,
+        Some("private") => FunctionVisibility::Private,
+        Some("external") => FunctionVisibility::External,
+        _ => FunctionVisibility::Internal,
+    };
+    let mutability = match node["stateMutability"].as_str() {
+        Some("pure") => FunctionMutability::Pure,
+        Some("view") => FunctionMutability::View,
+        Some("payable") => FunctionMutability::Payable,
+        _ => FunctionMutability::Nonpayable,
+    };
+    let modifiers = node["modifiers"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|m| path_name(&m["modifierName"]))
+        .map(|name| name.rsplit('.').next().unwrap_or(name).to_string())
+        .collect();
+
+    Some(FunctionInfo {
+        loc: src_location(node, file)?,
+        name,
+        parameters: parameters(&node["parameters"]),
+        return_parameters: parameters(&node["returnParameters"]),
+        visibility,
+        mutability,
+        function_type,
+        modifiers,
+        is_virtual: node["virtual"] == true,
+        is_override: !node["overrides"].is_null(),
+    })
+}
+
+fn parameters(list: &Value) -> Vec<FunctionParameter> {
+    list["parameters"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|param| FunctionParameter {
+            name: param["name"]
+                .as_str()
+                .filter(|name| !name.is_empty())
+                .map(str::to_string),
+            type_name: type_name(&param["typeName"]),
+            storage_location: param["storageLocation"]
+                .as_str()
+                .filter(|location| *location != "default")
+                .map(str::to_string),
+        })
+        .collect()
+}
+
+/// solang `Type` for an `ElementaryTypeName`, so both sources render type names alike.
+fn elementary_type(node: &Value) -> Option<Type> {
+    let name = node["name"].as_str()?;
+    let bits = |prefix: &str| -> Option<u16> {
+        let size = name.strip_prefix(prefix)?;
+        if size.is_empty() {
+            Some(256)
+        } else {
+            size.parse().ok()
+        }
+    };
+
+    match name {
+        "address" if node["stateMutability"] == "payable" => Some(Type::AddressPayable),
+        "address" => Some(Type::Address),
+        "bool" => Some(Type::Bool),
+        "string" => Some(Type::String),
+        "bytes" => Some(Type::DynamicBytes),
+        "byte" => Some(Type::Bytes(1)),
+        _ if name.starts_with("bytes") => name[5..].parse().ok().map(Type::Bytes),
+        _ if name.starts_with("uint") => bits("uint").map(Type::Uint),
+        _ if name.starts_with("int") => bits("int").map(Type::Int),
+        _ => None,
+    }
+}
+
+fn type_name(node: &Value) -> String {
+    match node["nodeType"].as_str() {
+        Some("ElementaryTypeName") => elementary_type(node)
+            .map(|ty| ty.to_string())
+            .unwrap_or_else(|| "unknown".to_string()),
+        Some("UserDefinedTypeName") => path_name(node).unwrap_or("unknown").to_string(),
+        Some("ArrayTypeName") => format!(
+            "{}[{}]",
+            type_name(&node["baseType"]),
+            array_length(node).unwrap_or_default()
+        ),
+        Some("Mapping") => format!(
+            "mapping({} => {})",
+            type_name(&node["keyType"]),
+            type_name(&node["valueType"])
+        ),
+        Some("FunctionTypeName") => "function".to_string(),
+        _ => "unknown".to_string(),
+    }
+}
+
+fn type_info(node: &Value) -> TypeInfo {
+    match node["nodeType"].as_str() {
+        Some("ElementaryTypeName") => elementary_type(node)
+            .map(|ty| TypeInfo::from_solang_type(&ty))
+            .unwrap_or(TypeInfo::Unknown),
+        Some("UserDefinedTypeName") => path_name(node)
+            .map(|name| TypeInfo::UserDefined(name.to_string()))
+            .unwrap_or(TypeInfo::Unknown),
+        Some("ArrayTypeName") => TypeInfo::Array {
+            base: Box::new(type_info(&node["baseType"])),
+            size: array_length(node).and_then(|len| len.parse().ok()),
+        },
+        Some("Mapping") => TypeInfo::Mapping {
+            key: Box::new(type_info(&node["keyType"])),
+            value: Box::new(type_info(&node["valueType"])),
+        },
+        Some("FunctionTypeName") => TypeInfo::Function,
+        _ => TypeInfo::Unknown,
+    }
+}
+
+fn array_length(node: &Value) -> Option<String> {
+    let length = &node["length"];
+    length["value"]
+        .as_str()
+        .or_else(|| length["name"].as_str())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/solc-ast");
+
+    fn fixture_index() -> SolcAstIndex {
+        SolcAstIndex::discover(Path::new(FIXTURE), Vec::new())
+    }
+
+    /// Metadata that both AST sources must agree on; end positions and snippets differ
+    /// because solc ranges include initializers and bodies exactly.
+    fn summary(file: &SolidityFile) -> Vec<String> {
+        let mut lines = Vec::new();
+        for contract in &file.contract_definitions {
+            lines.push(format!(
+                "{} {:?} line {} bases {:?}",
+                contract.name, contract.contract_type, contract.loc.line, contract.direct_bases
+            ));
+            for var in &contract.state_variables {
+                lines.push(format!(
+                    "  var {} {} {:?} {:?} line {}",
+                    var.name, var.type_info, var.visibility, var.mutability, var.loc.line
+                ));
+            }
+            for func in &contract.function_definitions {
+                lines.push(format!(
+                    "  fn {} {:?} {:?} {:?} params {:?} returns {:?} modifiers {:?} virtual {} override {} line {}",
+                    func.name,
+                    func.function_type,
+                    func.visibility,
+                    func.mutability,
+                    func.parameters,
+                    func.return_parameters,
+                    func.modifiers,
+                    func.is_virtual,
+                    func.is_override,
+                    func.loc.line
+                ));
+            }
+        }
+        lines
+    }
+
+    #[test]
+    fn test_solc_metadata_matches_solang() {
+        let path = Path::new(FIXTURE).join("src/Vault.sol");
+        let solang = SolidityFile::from_path(&path).unwrap();
+
+        let mut solc = SolidityFile::from_path(&path).unwrap();
+        let mut index = fixture_index();
+        let ast = index.ast_for(&path).expect("artifact AST should be found");
+        apply_metadata(&mut solc, ast);
+
+        assert_eq!(summary(&solc), summary(&solang));
+        assert_eq!(solc.contract_definitions.len(), 3);
+
+        // solc ranges cover the whole declaration, initializer included
+        let salt = &solc.contract_definitions[2].state_variables[3];
+        assert_eq!(
+            salt.loc.snippet.as_deref(),
+            Some("bytes32 immutable salt = keccak256(\"vault\")")
+        );
+    }
+
+    #[test]
+    fn test_solc_metadata_for_unparseable_file() {
+        let path = Path::new(FIXTURE).join("src/Lock.sol");
+        let error = SolidityFile::from_path(&path).unwrap_err();
+        assert!(
+            error.starts_with(&format!("Failed to parse {}:5: ", path.display())),
+            "{}",
+            error
+        );
+
+        let mut index = fixture_index();
+        let file = metadata_only_file(&path, index.ast_for(&path).unwrap()).unwrap();
+
+        assert_eq!(file.solidity_version.as_deref(), Some("^0.8.28"));
+        let lock = &file.contract_definitions[0];
+        assert_eq!(lock.name, "Lock");
+        assert_eq!(lock.state_variables[0].name, "locked");
+        assert_eq!(lock.state_variables[0].type_info, TypeInfo::Uint(256));
+        assert_eq!(lock.state_variables[0].loc.line, 5);
+        assert_eq!(lock.function_definitions[0].name, "enter");
+        assert_eq!(lock.function_definitions[0].loc.line, 7);
+    }
+}
@@ -51,6 +51,12 @@ pub struct ASTVisitor {
     >,
 }
 
+impl Default for ASTVisitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ASTVisitor {
     pub fn new() -> Self {
         Self {
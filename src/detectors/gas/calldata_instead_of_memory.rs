@@ -1,13 +1,14 @@
 use crate::core::visitor::ASTVisitor;
 use crate::detectors::Detector;
 use crate::models::severity::Severity;
-use crate::models::{FindingData, SolidityFile};
-use crate::utils::ast_utils::find_locations_in_statement;
+use crate::models::{FindingData, SolidityFile, StructInfo};
+use crate::utils::ast_utils::{find_locations_in_statement, find_statement_types};
 use crate::utils::location::loc_to_location;
 use solang_parser::pt::{
-    Expression, FunctionAttribute, FunctionDefinition, FunctionTy, Loc, Statement, StorageLocation,
-    Type, Visibility,
+    ContractPart, Expression, FunctionAttribute, FunctionDefinition, FunctionTy, Loc, Statement,
+    StorageLocation, Type, Visibility,
 };
+use std::collections::HashSet;
 use std::sync::Arc;
 
 #[derive(Debug, Default)]
@@ -55,59 +56,94 @@ function processData(uint256[] calldata data) external {
     }
 
     fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
-        visitor.on_function(move |func_def, file, _context| {
-            // Skip constructors - they cannot use calldata
-            if matches!(func_def.ty, FunctionTy::Constructor) {
-                return Vec::new();
-            }
+        visitor.on_contract(move |contract_def, file, context| {
+            let qualified_name = contract_def
+                .name
+                .as_ref()
+                .map(|name| context.get_qualified_name_for_contract(&name.name))
+                .unwrap_or_default();
+
+            // Structs visible to the contract first, then file-level and other contracts' structs
+            // for qualified (`Lib.Order`) or imported types
+            let structs: Vec<&StructInfo> = context
+                .get_all_structs(&qualified_name)
+                .into_iter()
+                .chain(file.structs.iter())
+                .chain(context.contracts.values().flat_map(|c| c.structs.iter()))
+                .collect();
+            let state_vars: HashSet<String> = context
+                .get_all_state_variables(&qualified_name)
+                .into_iter()
+                .map(|v| v.name.clone())
+                .collect();
 
-            // Only check external and public functions
-            if !self.is_external_or_public(func_def) {
-                return Vec::new();
+            let mut findings = Vec::new();
+            for part in &contract_def.parts {
+                if let ContractPart::FunctionDefinition(func_def) = part {
+                    findings.extend(self.check_function(func_def, file, &structs, &state_vars));
+                }
             }
+            findings
+        });
+    }
+}
 
-            let mut findings = Vec::new();
+impl CalldataInsteadOfMemoryDetector {
+    fn check_function(
+        &self,
+        func_def: &FunctionDefinition,
+        file: &SolidityFile,
+        structs: &[&StructInfo],
+        state_vars: &HashSet<String>,
+    ) -> Vec<FindingData> {
+        // Skip constructors - they cannot use calldata
+        if matches!(func_def.ty, FunctionTy::Constructor) {
+            return Vec::new();
+        }
 
-            // Get function body for modification analysis
-            let body = match &func_def.body {
-                Some(body) => body,
-                None => return Vec::new(), // Interface functions have no body
-            };
-
-            // Check each parameter
-            for (loc, param_opt) in &func_def.params {
-                if let Some(param) = param_opt {
-                    // Check if parameter uses memory storage
-                    if let Some(StorageLocation::Memory(_)) = &param.storage {
-                        // Check if type is a reference type (arrays, bytes, string, structs)
-                        if self.is_reference_type(&param.ty) {
-                            // Get parameter name
-                            let param_name = param
-                                .name
-                                .as_ref()
-                                .map(|id| id.name.as_str())
-                                .unwrap_or("");
-
-                            // Check if parameter is modified in function body
-                            if !param_name.is_empty()
-                                && !self.is_modified_in_body(param_name, body, file)
-                            {
-                                findings.push(FindingData {
-                                    detector_id: self.id(),
-                                    location: loc_to_location(loc, file),
-                                });
-                            }
+        // Only check external and public functions
+        if !self.is_external_or_public(func_def) {
+            return Vec::new();
+        }
+
+        let mut findings = Vec::new();
+
+        // Get function body for modification analysis
+        let body = match &func_def.body {
+            Some(body) => body,
+            None => return Vec::new(), // Interface functions have no body
+        };
+
+        // Check each parameter
+        for (loc, param_opt) in &func_def.params {
+            if let Some(param) = param_opt {
+                // Check if parameter uses memory storage
+                if let Some(StorageLocation::Memory(_)) = &param.storage {
+                    // Check if type is a reference type (arrays, bytes, string, structs)
+                    if self.is_reference_type(&param.ty)
+                        && !Self::contains_mapping(&param.ty, structs)
+                    {
+                        // Get parameter name
+                        let param_name =
+                            param.name.as_ref().map(|id| id.name.as_str()).unwrap_or("");
+
+                        // Check if parameter is modified in function body
+                        if !param_name.is_empty()
+                            && !self.is_modified_in_body(param_name, body, file, state_vars)
+                        {
+                            findings.push(FindingData {
+                                detector_id: self.id(),
+                                location: loc_to_location(loc, file),
+                            });
                         }
                     }
                 }
             }
+        }
 
-            findings
-        });
+        findings
     }
-}
 
-impl CalldataInsteadOfMemoryDetector {
     fn is_external_or_public(&self, func_def: &FunctionDefinition) -> bool {
         func_def.attributes.iter().any(|attr| {
             matches!(
@@ -127,16 +163,72 @@ impl CalldataInsteadOfMemoryDetector {
                 ty,
                 Type::String | Type::DynamicBytes | Type::Bytes(_) | Type::Mapping { .. }
             ),
-            // User-defined types (structs, etc.) - identified by variable name
-            Expression::Variable(_) => true,
+            // User-defined types (structs, etc.) - identified by name, possibly qualified
+            Expression::Variable(_) | Expression::MemberAccess(..) => true,
             _ => false,
         }
     }
 
-    fn is_modified_in_body(&self, param_name: &str, body: &Statement, file: &SolidityFile) -> bool {
+    /// Whether the parameter type is (an array of) a struct that contains a mapping, directly or
+    /// through nested structs. Such structs cannot live in calldata.
+    fn contains_mapping(type_expr: &Expression, structs: &[&StructInfo]) -> bool {
+        let name = match type_expr {
+            Expression::ArraySubscript(_, base, _) => return Self::contains_mapping(base, structs),
+            Expression::Variable(id) => id.name.as_str(),
+            Expression::MemberAccess(_, _, member) => member.name.as_str(),
+            _ => return false,
+        };
+        Self::struct_contains_mapping(name, structs, &mut HashSet::new())
+    }
+
+    fn struct_contains_mapping<'a>(
+        name: &'a str,
+        structs: &[&'a StructInfo],
+        visited: &mut HashSet<&'a str>,
+    ) -> bool {
+        if !visited.insert(name) {
+            return false;
+        }
+        let Some(info) = structs.iter().find(|s| s.name == name) else {
+            return false;
+        };
+        info.fields.iter().any(|field| {
+            let type_name = field.type_name.as_str();
+            // `Order[]` or `Lib.Order` refer to the struct `Order`
+            let base = type_name.split('[').next().unwrap_or(type_name);
+            let base = base.rsplit('.').next().unwrap_or(base);
+            type_name.starts_with("mapping")
+                || Self::struct_contains_mapping(base, structs, visited)
+        })
+    }
+
+    /// Whether the parameter, or a memory alias of it, is written to, or the parameter is
+    /// copied into a state variable.
+    fn is_modified_in_body(
+        &self,
+        param_name: &str,
+        body: &Statement,
+        file: &SolidityFile,
+        state_vars: &HashSet<String>,
+    ) -> bool {
+        // `User memory u = user;` shares the parameter's memory, so writes to `u` count
+        let mut names = HashSet::from([param_name.to_string()]);
+        find_statement_types(body, file, self.id(), |stmt| {
+            if let Statement::VariableDefinition(_, decl, Some(init)) = stmt {
+                if let (Some(name), Expression::Variable(source)) = (&decl.name, init) {
+                    if names.contains(&source.name) {
+                        names.insert(name.name.clone());
+                    }
+                }
+            }
+            false
+        });
+
         let mut found = Vec::new();
         let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
-            if Self::is_assignment_to_param(param_name, expr) {
+            if Self::is_assignment_to_param(&names, expr)
+                || Self::is_copy_to_storage(&names, state_vars, expr)
+            {
                 Some(Loc::Implicit)
             } else {
                 None
@@ -146,7 +238,7 @@ impl CalldataInsteadOfMemoryDetector {
         !found.is_empty()
     }
 
-    fn is_assignment_to_param(param_name: &str, expr: &Expression) -> bool {
+    fn is_assignment_to_param(names: &HashSet<String>, expr: &Expression) -> bool {
         match expr {
             Expression::Assign(_, left, _)
             | Expression::AssignAdd(_, left, _)
@@ -158,32 +250,46 @@ impl CalldataInsteadOfMemoryDetector {
             | Expression::AssignAnd(_, left, _)
             | Expression::AssignXor(_, left, _)
             | Expression::AssignShiftLeft(_, left, _)
-            | Expression::AssignShiftRight(_, left, _) => Self::is_param_target(param_name, left),
+            | Expression::AssignShiftRight(_, left, _)
+            | Expression::PreIncrement(_, left)
+            | Expression::PreDecrement(_, left)
+            | Expression::PostIncrement(_, left)
+            | Expression::PostDecrement(_, left)
+            | Expression::Delete(_, left) => Self::is_param_target(names, left),
             _ => false,
         }
     }
 
-    fn is_param_target(param_name: &str, expr: &Expression) -> bool {
+    /// `stored = user;` - copying a whole struct from calldata to storage is not supported by
+    /// every compiler version, so keep such parameters in memory.
+    fn is_copy_to_storage(
+        names: &HashSet<String>,
+        state_vars: &HashSet<String>,
+        expr: &Expression,
+    ) -> bool {
         match expr {
-            // Array index access: param[i]
-            Expression::ArraySubscript(_, base, _) => {
-                if let Expression::Variable(ident) = base.as_ref() {
-                    ident.name == param_name
-                } else {
-                    Self::is_param_target(param_name, base)
-                }
-            }
-            // Member access: param.field
-            Expression::MemberAccess(_, base, _) => {
-                if let Expression::Variable(ident) = base.as_ref() {
-                    ident.name == param_name
-                } else {
-                    Self::is_param_target(param_name, base)
-                }
+            Expression::Assign(_, left, right) => {
+                matches!(right.as_ref(), Expression::Variable(id) if names.contains(&id.name))
+                    && Self::target_root(left).is_some_and(|root| state_vars.contains(root))
             }
             _ => false,
         }
     }
+
+    fn is_param_target(names: &HashSet<String>, expr: &Expression) -> bool {
+        Self::target_root(expr).is_some_and(|root| names.contains(root))
+    }
+
+    /// Variable at the root of `param`, `param[i]`, `param.field` or `param.a.b[i].c`.
+    fn target_root(expr: &Expression) -> Option<&str> {
+        match expr {
+            Expression::Variable(ident) => Some(&ident.name),
+            Expression::ArraySubscript(_, base, _)
+            | Expression::MemberAccess(_, base, _)
+            | Expression::Parenthesis(_, base) => Self::target_root(base),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -274,4 +380,73 @@ mod tests {
 
         assert_eq!(locations.len(), 0);
     }
+
+    #[test]
+    fn test_struct_params() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            library Types {
+                struct Profile {
+                    string name;
+                    uint256[] scores;
+                }
+            }
+
+            contract Registry {
+                struct User {
+                    uint256 id;
+                    Types.Profile profile;
+                }
+
+                struct Account {
+                    uint256 id;
+                    mapping(address => uint256) allowances;
+                }
+
+                struct Wrapper {
+                    Account account;
+                }
+
+                User stored;
+
+                function readNested(User memory user) external pure returns (string memory) {
+                    return user.profile.name;                               // Positive
+                }
+
+                function readQualified(Types.Profile memory profile) external pure returns (uint256) {
+                    return profile.scores.length;                           // Positive
+                }
+
+                function writeNested(User memory user) external pure returns (User memory) {
+                    user.profile.scores[0] = 1;                             // Negative: nested write
+                    return user;
+                }
+
+                function writeAlias(User memory user) external pure returns (uint256) {
+                    User memory copy = user;
+                    copy.profile.name = "alias";                            // Negative: same memory
+                    return copy.id;
+                }
+
+                function bump(User memory user) external pure returns (uint256) {
+                    user.id++;                                              // Negative: increment
+                    return user.id;
+                }
+
+                function save(User memory user) external {
+                    stored = user;                                          // Negative: storage copy
+                }
+
+                function withMapping(Account memory account) external {}    // Negative: mapping
+                function nestedMapping(Wrapper[] memory wrappers) external {}  // Negative: nested mapping
+            }
+        "#;
+
+        let detector = Arc::new(CalldataInsteadOfMemoryDetector::default());
+        let locations = run_detector_on_code(detector, code, "registry.sol");
+
+        let lines: Vec<_> = locations.iter().map(|l| l.line).collect();
+        assert_eq!(lines, [28, 32]);
+    }
 }
@@ -0,0 +1,359 @@
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::severity::Severity;
+use crate::models::{FindingData, SolidityFile};
+use crate::utils::ast_utils::{
+    collect_local_declarations, find_locations_in_expression, find_locations_in_statement,
+    is_external_call,
+};
+use crate::utils::location::loc_to_location;
+use solang_parser::pt::{CodeLocation, Expression, Loc, Statement};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Calls that change state even when their result is used, so repeating them is intended.
+const STATE_CHANGING_METHODS: [&str; 10] = [
+    "transfer",
+    "transferFrom",
+    "safeTransfer",
+    "safeTransferFrom",
+    "approve",
+    "send",
+    "mint",
+    "burn",
+    "deposit",
+    "withdraw",
+];
+
+#[derive(Debug, Default)]
+pub struct LoopInvariantExternalCallDetector;
+
+impl Detector for LoopInvariantExternalCallDetector {
+    fn id(&self) -> &'static str {
+        "loop-invariant-external-call"
+    }
+
+    fn name(&self) -> &str {
+        "Loop-invariant external call can be hoisted out of the loop"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Gas
+    }
+
+    fn description(&self) -> &str {
+        "An external call inside a loop does not depend on the loop variable or on anything \
+         assigned in the loop, so every iteration repeats the same call (at least 100 gas for \
+         the warm `STATICCALL`, plus the callee's work). Call it once before the loop and reuse \
+         the cached result. Calls whose result is discarded and well-known state-changing \
+         methods are not reported."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad - the oracle is queried on every iteration
+for (uint256 i; i < amounts.length; i++) {
+    total += amounts[i] * oracle.getPrice(token);
+}
+
+// Good - query once
+uint256 price = oracle.getPrice(token);
+for (uint256 i; i < amounts.length; i++) {
+    total += amounts[i] * price;
+}
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_statement(move |stmt, file, _context| {
+            let (body, mut variant) = match stmt {
+                Statement::For(_, init, _, next, Some(body)) => {
+                    let mut variant = HashSet::new();
+                    if let Some(init) = init {
+                        collect_local_declarations(init, &mut variant);
+                        Self::collect_assigned_in_statement(init, file, &mut variant);
+                    }
+                    if let Some(next) = next {
+                        Self::collect_assigned_in_expression(next, file, &mut variant);
+                    }
+                    (body.as_ref(), variant)
+                }
+                Statement::While(_, _, body) | Statement::DoWhile(_, body, _) => {
+                    (body.as_ref(), HashSet::new())
+                }
+                _ => return Vec::new(),
+            };
+
+            collect_local_declarations(body, &mut variant);
+            Self::collect_assigned_in_statement(body, file, &mut variant);
+
+            // Nested loops report their own invariant calls
+            let mut calls = Vec::new();
+            Self::collect_calls(body, &mut calls);
+
+            calls
+                .into_iter()
+                .filter(|call| {
+                    let Expression::FunctionCall(_, func, _) = call else {
+                        return false;
+                    };
+                    let Expression::MemberAccess(_, _, member) = func.as_ref() else {
+                        return false;
+                    };
+                    !STATE_CHANGING_METHODS.contains(&member.name.as_str())
+                        && !Self::references_any(call, &variant, file)
+                })
+                .map(|call| FindingData {
+                    detector_id: self.id(),
+                    location: loc_to_location(&call.loc(), file).with_message(format!(
+                        "`{}` does not depend on the loop; call it once before the loop \
+                         and reuse the result",
+                        call
+                    )),
+                })
+                .collect()
+        });
+    }
+}
+
+impl LoopInvariantExternalCallDetector {
+    /// External calls whose result is used, outside nested loops.
+    fn collect_calls<'a>(stmt: &'a Statement, calls: &mut Vec<&'a Expression>) {
+        match stmt {
+            Statement::Block { statements, .. } => {
+                for s in statements {
+                    Self::collect_calls(s, calls);
+                }
+            }
+            Statement::If(_, condition, then_stmt, else_stmt) => {
+                Self::collect_calls_in_expression(condition, calls);
+                Self::collect_calls(then_stmt, calls);
+                if let Some(else_stmt) = else_stmt {
+                    Self::collect_calls(else_stmt, calls);
+                }
+            }
+            Statement::VariableDefinition(_, _, Some(init)) => {
+                Self::collect_calls_in_expression(init, calls);
+            }
+            // A call used as a statement is run for its effects, but its operands may still
+            // contain invariant calls
+            Statement::Expression(_, Expression::FunctionCall(_, func, args)) => {
+                if let Expression::MemberAccess(_, base, _) = func.as_ref() {
+                    Self::collect_calls_in_expression(base, calls);
+                }
+                for arg in args {
+                    Self::collect_calls_in_expression(arg, calls);
+                }
+            }
+            Statement::Expression(_, expr) | Statement::Return(_, Some(expr)) => {
+                Self::collect_calls_in_expression(expr, calls);
+            }
+            Statement::Emit(_, expr) => Self::collect_calls_in_expression(expr, calls),
+            Statement::Revert(_, _, args) => {
+                for arg in args {
+                    Self::collect_calls_in_expression(arg, calls);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn collect_calls_in_expression<'a>(expr: &'a Expression, calls: &mut Vec<&'a Expression>) {
+        if is_external_call(expr) {
+            calls.push(expr);
+        }
+        match expr {
+            Expression::FunctionCall(_, func, args) => {
+                Self::collect_calls_in_expression(func, calls);
+                for arg in args {
+                    Self::collect_calls_in_expression(arg, calls);
+                }
+            }
+            Expression::MemberAccess(_, base, _)
+            | Expression::Parenthesis(_, base)
+            | Expression::Not(_, base)
+            | Expression::Negate(_, base) => Self::collect_calls_in_expression(base, calls),
+            Expression::ArraySubscript(_, base, index) => {
+                Self::collect_calls_in_expression(base, calls);
+                if let Some(index) = index {
+                    Self::collect_calls_in_expression(index, calls);
+                }
+            }
+            Expression::ConditionalOperator(_, condition, left, right) => {
+                Self::collect_calls_in_expression(condition, calls);
+                Self::collect_calls_in_expression(left, calls);
+                Self::collect_calls_in_expression(right, calls);
+            }
+            Expression::Assign(_, left, right)
+            | Expression::AssignAdd(_, left, right)
+            | Expression::AssignSubtract(_, left, right)
+            | Expression::AssignMultiply(_, left, right)
+            | Expression::AssignDivide(_, left, right)
+            | Expression::Add(_, left, right)
+            | Expression::Subtract(_, left, right)
+            | Expression::Multiply(_, left, right)
+            | Expression::Divide(_, left, right)
+            | Expression::Modulo(_, left, right)
+            | Expression::Power(_, left, right)
+            | Expression::Less(_, left, right)
+            | Expression::More(_, left, right)
+            | Expression::LessEqual(_, left, right)
+            | Expression::MoreEqual(_, left, right)
+            | Expression::Equal(_, left, right)
+            | Expression::NotEqual(_, left, right)
+            | Expression::And(_, left, right)
+            | Expression::Or(_, left, right) => {
+                Self::collect_calls_in_expression(left, calls);
+                Self::collect_calls_in_expression(right, calls);
+            }
+            Expression::List(_, params) => {
+                for (_, param) in params
+                    .iter()
+                    .filter_map(|(loc, p)| p.as_ref().map(|p| (loc, p)))
+                {
+                    Self::collect_calls_in_expression(&param.ty, calls);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn collect_assigned_in_statement(
+        stmt: &Statement,
+        file: &SolidityFile,
+        variant: &mut HashSet<String>,
+    ) {
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            Self::assigned_root(expr).map(|name| {
+                variant.insert(name.to_string());
+                expr.loc()
+            })
+        };
+        let mut found = Vec::new();
+        find_locations_in_statement(stmt, file, &mut predicate, &mut found);
+    }
+
+    fn collect_assigned_in_expression(
+        expr: &Expression,
+        file: &SolidityFile,
+        variant: &mut HashSet<String>,
+    ) {
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            Self::assigned_root(expr).map(|name| {
+                variant.insert(name.to_string());
+                expr.loc()
+            })
+        };
+        let mut found = Vec::new();
+        find_locations_in_expression(expr, file, &mut predicate, &mut found);
+    }
+
+    /// Variable written by an assignment, increment or `delete`, e.g. `totals[i] += x` -> `totals`.
+    fn assigned_root(expr: &Expression) -> Option<&str> {
+        let target = match expr {
+            Expression::Assign(_, target, _)
+            | Expression::AssignAdd(_, target, _)
+            | Expression::AssignSubtract(_, target, _)
+            | Expression::AssignMultiply(_, target, _)
+            | Expression::AssignDivide(_, target, _)
+            | Expression::AssignModulo(_, target, _)
+            | Expression::AssignOr(_, target, _)
+            | Expression::AssignAnd(_, target, _)
+            | Expression::AssignXor(_, target, _)
+            | Expression::AssignShiftLeft(_, target, _)
+            | Expression::AssignShiftRight(_, target, _)
+            | Expression::PreIncrement(_, target)
+            | Expression::PreDecrement(_, target)
+            | Expression::PostIncrement(_, target)
+            | Expression::PostDecrement(_, target)
+            | Expression::Delete(_, target) => target,
+            _ => return None,
+        };
+        Self::root_name(target)
+    }
+
+    fn root_name(expr: &Expression) -> Option<&str> {
+        match expr {
+            Expression::Variable(id) => Some(&id.name),
+            Expression::Parenthesis(_, inner)
+            | Expression::ArraySubscript(_, inner, _)
+            | Expression::MemberAccess(_, inner, _) => Self::root_name(inner),
+            _ => None,
+        }
+    }
+
+    fn references_any(expr: &Expression, names: &HashSet<String>, file: &SolidityFile) -> bool {
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            match expr {
+                Expression::Variable(id) if names.contains(&id.name) => Some(id.loc),
+                _ => None,
+            }
+        };
+        let mut found = Vec::new();
+        find_locations_in_expression(expr, file, &mut predicate, &mut found);
+        !found.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::run_detector_on_code;
+
+    #[test]
+    fn test_loop_invariant_external_call() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            contract Portfolio {
+                function value(IOracle oracle, IERC20[] memory tokens, address token, address user)
+                    external view returns (uint256 total)
+                {
+                    for (uint256 i; i < tokens.length; i++) {
+                        total += tokens[i].balanceOf(user) * oracle.getPrice(token); // Positive: getPrice
+                    }
+
+                    uint256 price = oracle.getPrice(token);
+                    for (uint256 i; i < tokens.length; i++) {
+                        total += tokens[i].balanceOf(user) * price;                  // Negative: hoisted
+                    }
+                }
+
+                function payout(IERC20 token, address[] calldata users, IVault vault) external {
+                    uint256 share;
+                    for (uint256 i; i < users.length; i++) {
+                        share = vault.shareOf(users[i]);                             // Negative: loop variable
+                        token.transfer(users[i], share * vault.rate());              // Positive: rate
+                        vault.sync();                                                // Negative: result unused
+                    }
+                }
+
+                function drain(IVault vault) external {
+                    uint256 left = 10;
+                    while (left > 0) {
+                        left = vault.remaining(left);                                // Negative: assigned in loop
+                        require(vault.isOpen(), "closed");                           // Positive
+                    }
+                }
+            }
+        "#;
+
+        let detector = Arc::new(LoopInvariantExternalCallDetector::default());
+        let locations = run_detector_on_code(detector, code, "portfolio.sol");
+
+        assert_eq!(locations.len(), 3, "Should detect 3 invariant calls");
+        assert_eq!(locations[0].line, 9);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some(
+                "`oracle.getPrice(token)` does not depend on the loop; call it once before the \
+                 loop and reuse the result"
+            )
+        );
+        assert_eq!(locations[1].line, 22);
+        assert_eq!(locations[2].line, 31);
+    }
+}
@@ -19,6 +19,7 @@ pub mod custom_errors_instead_of_revert_strings;
 pub mod default_value_initialization;
 pub mod internal_function_not_called;
 pub mod long_revert_string;
+pub mod loop_invariant_external_call;
 pub mod msg_sender_usage;
 pub mod payable_function;
 pub mod post_increment;
@@ -37,6 +38,8 @@ pub mod should_be_immutable;
 pub mod use_erc721a;
 pub mod weth_address_definition;
 
+use crate::detectors::Detector;
+
 pub use address_this_precalculation::AddressThisPrecalculationDetector;
 pub use assembly_abi_decode::AssemblyAbiDecodeDetector;
 pub use address_zero_check::AddressZeroCheckDetector;
@@ -58,6 +61,7 @@ pub use custom_errors_instead_of_revert_strings::CustomErrorsInsteadOfRevertStri
 pub use default_value_initialization::DefaultValueInitializationDetector;
 pub use internal_function_not_called::InternalFunctionNotCalledDetector;
 pub use long_revert_string::LongRevertStringDetector;
+pub use loop_invariant_external_call::LoopInvariantExternalCallDetector;
 pub use msg_sender_usage::MsgSenderUsageDetector;
 pub use payable_function::PayableFunctionDetector;
 pub use post_increment::PostIncrementDetector;
@@ -75,3 +79,49 @@ pub use variable_inside_loop::VariableInsideLoopDetector;
 pub use should_be_immutable::ShouldBeImmutableDetector;
 pub use use_erc721a::UseErc721aDetector;
 pub use weth_address_definition::WethAddressDefinitionDetector;
+
+/// Every Gas detector, in registration order. A detector exported above but missing
+/// here never runs, which `test_every_exported_detector_is_built_in` catches.
+pub fn all() -> Vec<Box<dyn Detector>> {
+    vec![
+        Box::new(AddressThisPrecalculationDetector::default()),
+        Box::new(AddressZeroCheckDetector::default()),
+        Box::new(ArrayCompoundAssignmentDetector::default()),
+        Box::new(ArrayLengthInLoopDetector::default()),
+        Box::new(AssemblyAbiDecodeDetector::default()),
+        Box::new(AssemblyStorageWriteDetector::default()),
+        Box::new(AvoidContractExistenceChecksDetector::default()),
+        Box::new(BoolStorageDetector::default()),
+        Box::new(BooleanComparisonDetector::default()),
+        Box::new(CacheStateVariablesDetector::default()),
+        Box::new(CachedConstantDetector::default()),
+        Box::new(CachedImmutableDetector::default()),
+        Box::new(CachedMsgSenderDetector::default()),
+        Box::new(CalldataInsteadOfMemoryDetector::default()),
+        Box::new(CombineMappingsDetector::default()),
+        Box::new(CompoundAssignmentDetector::default()),
+        Box::new(CountDownLoopDetector::default()),
+        Box::new(CustomErrorsInsteadOfRevertStringsDetector::default()),
+        Box::new(DefaultValueInitializationDetector::default()),
+        Box::new(InternalFunctionNotCalledDetector::default()),
+        Box::new(LongRevertStringDetector::default()),
+        Box::new(LoopInvariantExternalCallDetector::default()),
+        Box::new(MsgSenderUsageDetector::default()),
+        Box::new(PayableFunctionDetector::default()),
+        Box::new(PostIncrementDetector::default()),
+        Box::new(PrivateConstantsDetector::default()),
+        Box::new(ShiftInsteadOfMulDivDetector::default()),
+        Box::new(ShouldBeImmutableDetector::default()),
+        Box::new(SplitRequireDetector::default()),
+        Box::new(SuperfluousEventFieldsDetector::default()),
+        Box::new(ThisUsageDetector::default()),
+        Box::new(Uint256ToBoolMappingDetector::default()),
+        Box::new(UintGtZeroDetector::default()),
+        Box::new(UncheckedLoopIncrementDetector::default()),
+        Box::new(UnnecessaryVariableCacheDetector::default()),
+        Box::new(UnsafeArrayAccessDetector::default()),
+        Box::new(UseErc721aDetector::default()),
+        Box::new(VariableInsideLoopDetector::default()),
+        Box::new(WethAddressDefinitionDetector::default()),
+    ]
+}
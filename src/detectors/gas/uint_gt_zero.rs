@@ -1,8 +1,10 @@
+use crate::core::visitor::ASTVisitor;
 use crate::detectors::Detector;
 use crate::models::severity::Severity;
-use crate::utils::ast_utils::find_in_statement;
-use crate::core::visitor::ASTVisitor;
-use solang_parser::pt::Expression;
+use crate::models::{FindingData, TypeInfo};
+use crate::utils::ast_utils::{build_variable_type_map, find_in_statement};
+use solang_parser::pt::{ContractPart, Expression, Type};
+use std::collections::HashMap;
 use std::sync::Arc;
 
 #[derive(Debug, Default)]
@@ -14,7 +16,7 @@ impl Detector for UintGtZeroDetector {
     }
 
     fn name(&self) -> &str {
-        "Use != 0 Instead of > 0 for Unsigned Integer"
+        "Use != 0 Instead of > 0 for Unsigned Integer, and Strict Comparisons Instead of ± 1"
     }
 
     fn severity(&self) -> Severity {
@@ -24,7 +26,10 @@ impl Detector for UintGtZeroDetector {
     fn description(&self) -> &str {
         "For unsigned integers, using != 0 is more gas efficient than > 0 in comparisons. \
          The != 0 operation is cheaper because it's a simple bitwise check, while > 0 \
-         requires additional comparison logic."
+         requires additional comparison logic. Only operands whose type resolves to an unsigned \
+         integer are reported. Likewise, `i <= n - 1` and `a >= b + 1` spend an extra \
+         arithmetic operation (and the subtraction reverts when `n` is 0) where `i < n` and \
+         `a > b` say the same thing."
     }
 
     fn example(&self) -> Option<String> {
@@ -34,38 +39,138 @@ impl Detector for UintGtZeroDetector {
 if (balance > 0) {
     transfer(balance);
 }
+for (uint256 i; i <= holders.length - 1; i++) {}
 
 // Good - more efficient
 if (balance != 0) {
     transfer(balance);
 }
+for (uint256 i; i < holders.length; i++) {}
 ```"#
                 .to_string(),
         )
     }
 
     fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
-        visitor.on_function(move |func, file, _context| {
-            let Some(body) = &func.body else {
+        visitor.on_contract(move |contract_def, file, context| {
+            let Some(contract_name) = &contract_def.name else {
                 return Vec::new();
             };
 
-            find_in_statement(body, file, self.id(), |expr| {
-                match expr {
-                    // Pattern: x > 0
-                    Expression::More(_, _left, right) => Self::is_zero_literal(right),
-                    // Pattern: 0 < x
-                    Expression::Less(_, left, _right) => Self::is_zero_literal(left),
-                    _ => false,
-                }
-            })
+            let qualified_name = context.get_qualified_name_for_contract(&contract_name.name);
+            let state_var_types: HashMap<String, TypeInfo> = context
+                .get_all_state_variables(&qualified_name)
+                .into_iter()
+                .map(|v| (v.name.clone(), v.type_info.clone()))
+                .collect();
+
+            let mut all_findings = Vec::new();
+
+            for part in &contract_def.parts {
+                let ContractPart::FunctionDefinition(func_def) = part else {
+                    continue;
+                };
+                let Some(body) = &func_def.body else {
+                    continue;
+                };
+
+                let var_types = build_variable_type_map(func_def, body, &state_var_types);
+                let mut messages = Vec::new();
+                let findings =
+                    find_in_statement(
+                        body,
+                        file,
+                        self.id(),
+                        |expr| match Self::suggest_replacement(expr, &var_types) {
+                            Some(message) => {
+                                messages.push(message);
+                                true
+                            }
+                            None => false,
+                        },
+                    );
+
+                all_findings.extend(findings.into_iter().zip(messages).map(
+                    |(finding, message)| FindingData {
+                        location: finding.location.with_message(message),
+                        ..finding
+                    },
+                ));
+            }
+
+            all_findings
         });
     }
 }
 
 impl UintGtZeroDetector {
-    fn is_zero_literal(expr: &Expression) -> bool {
-        matches!(expr, Expression::NumberLiteral(_, value, _, _) if value == "0")
+    fn suggest_replacement(
+        expr: &Expression,
+        var_types: &HashMap<String, TypeInfo>,
+    ) -> Option<String> {
+        let replacement = match expr {
+            // Pattern: x > 0
+            Expression::More(_, left, right)
+                if Self::is_literal(right, "0") && Self::is_unsigned(left, var_types) =>
+            {
+                format!("{} != 0", left)
+            }
+            // Pattern: 0 < x
+            Expression::Less(_, left, right)
+                if Self::is_literal(left, "0") && Self::is_unsigned(right, var_types) =>
+            {
+                format!("{} != 0", right)
+            }
+            // Pattern: i <= n - 1
+            Expression::LessEqual(_, left, right) => match right.as_ref() {
+                Expression::Subtract(_, bound, one) if Self::is_literal(one, "1") => {
+                    format!("{} < {}", left, bound)
+                }
+                _ => return None,
+            },
+            // Pattern: a >= b + 1
+            Expression::MoreEqual(_, left, right) => match right.as_ref() {
+                Expression::Add(_, bound, one) if Self::is_literal(one, "1") => {
+                    format!("{} > {}", left, bound)
+                }
+                _ => return None,
+            },
+            _ => return None,
+        };
+        Some(format!("`{}` can be `{}`", expr, replacement))
+    }
+
+    fn is_literal(expr: &Expression, value: &str) -> bool {
+        matches!(expr, Expression::NumberLiteral(_, v, exp, None) if v == value && exp.is_empty())
+    }
+
+    fn is_unsigned(expr: &Expression, var_types: &HashMap<String, TypeInfo>) -> bool {
+        matches!(Self::resolve_type(expr, var_types), Some(TypeInfo::Uint(_)))
+    }
+
+    /// Best-effort static type of an operand; `None` when it can't be determined.
+    fn resolve_type(expr: &Expression, var_types: &HashMap<String, TypeInfo>) -> Option<TypeInfo> {
+        match expr {
+            Expression::Variable(id) => var_types.get(&id.name).cloned(),
+            Expression::Parenthesis(_, inner) => Self::resolve_type(inner, var_types),
+            Expression::MemberAccess(_, _, member) if member.name == "length" => {
+                Some(TypeInfo::Uint(256))
+            }
+            Expression::ArraySubscript(_, base, Some(_)) => {
+                match Self::resolve_type(base, var_types)? {
+                    TypeInfo::Mapping { value, .. } => Some(*value),
+                    TypeInfo::Array { base, .. } => Some(*base),
+                    _ => None,
+                }
+            }
+            // Explicit casts such as `uint256(x)`
+            Expression::FunctionCall(_, func, args) if args.len() == 1 => match func.as_ref() {
+                Expression::Type(_, Type::Uint(size)) => Some(TypeInfo::Uint(*size)),
+                Expression::Type(_, Type::Int(size)) => Some(TypeInfo::Int(*size)),
+                _ => None,
+            },
+            _ => None,
+        }
     }
 }
 
@@ -112,4 +217,47 @@ mod tests {
         let locations = run_detector_on_code(detector, code, "test.sol");
         assert_eq!(locations.len(), 0);
     }
+
+    #[test]
+    fn test_resolves_operand_types() {
+        let code = r#"
+            type SD59x18 is int256;
+
+            contract Test {
+                mapping(address => uint256) balances;
+                int256 delta;
+                address[] holders;
+
+                function check(SD59x18 price, uint128 amount) public {
+                    require(balances[msg.sender] > 0);
+                    require(delta > 0);
+                    require(SD59x18.unwrap(price) > 0);
+                    if (uint256(amount) > 0) {}
+                    for (uint256 i; i <= holders.length - 1; i++) {}
+                    require(amount >= balances[msg.sender] + 1);
+                }
+            }
+        "#;
+        let detector = Arc::new(UintGtZeroDetector::default());
+        let locations = run_detector_on_code(detector, code, "test.sol");
+        let found: Vec<_> = locations
+            .iter()
+            .map(|l| (l.line, l.message.as_deref().unwrap()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (
+                    10,
+                    "`balances[msg.sender] > 0` can be `balances[msg.sender] != 0`"
+                ),
+                (13, "`uint256(amount) > 0` can be `uint256(amount) != 0`"),
+                (14, "`i <= holders.length - 1` can be `i < holders.length`"),
+                (
+                    15,
+                    "`amount >= balances[msg.sender] + 1` can be `amount > balances[msg.sender]`"
+                ),
+            ]
+        );
+    }
 }
@@ -0,0 +1,288 @@
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::category::Category;
+use crate::models::severity::Severity;
+use crate::models::{FindingData, SolidityFile};
+use crate::utils::ast_utils::{find_locations_in_statement, get_local_variable_names};
+use crate::utils::version::solidity_version_req_matches;
+use solang_parser::pt::{
+    ContractDefinition, ContractPart, ContractTy, Expression, FunctionAttribute, FunctionTy, Loc,
+    Mutability,
+};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Method-style calls that indicate a SafeMath-like library is in use.
+const SAFE_MATH_METHODS: &[&str] = &["add", "sub", "mul"];
+
+#[derive(Debug, Default)]
+pub struct ArithmeticWithoutSafeMathDetector;
+
+impl Detector for ArithmeticWithoutSafeMathDetector {
+    fn id(&self) -> &'static str {
+        "arithmetic-without-safemath"
+    }
+
+    fn name(&self) -> &str {
+        "Unchecked arithmetic without SafeMath in Solidity < 0.8"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::High
+    }
+
+    fn category(&self) -> Category {
+        Category::Arithmetic
+    }
+
+    fn description(&self) -> &str {
+        "Before Solidity 0.8.0 arithmetic silently wraps on overflow and underflow. Contracts \
+         compiled with an older pragma that do raw `*`, `+` or `-` on state variables or \
+         function inputs (e.g. constant-product `x * y` invariants, price or reward math) can \
+         be pushed into wrapped values that break accounting. Use SafeMath for every \
+         state-affecting operation or upgrade to Solidity 0.8."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+pragma solidity ^0.7.6;
+
+// Bad
+function swap(uint256 amountIn) external {
+    uint256 k = reserve0 * reserve1;
+    reserve0 = reserve0 + amountIn;
+}
+
+// Good
+using SafeMath for uint256;
+
+function swap(uint256 amountIn) external {
+    uint256 k = reserve0.mul(reserve1);
+    reserve0 = reserve0.add(amountIn);
+}
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_contract(move |contract_def, file, context| {
+            if matches!(
+                contract_def.ty,
+                ContractTy::Interface(_) | ContractTy::Library(_)
+            ) {
+                return Vec::new();
+            }
+
+            let Some(version) = &file.solidity_version else {
+                return Vec::new();
+            };
+            if solidity_version_req_matches(version, ">=0.8.0") {
+                return Vec::new();
+            }
+
+            let Some(contract_name) = &contract_def.name else {
+                return Vec::new();
+            };
+            let qualified_name = context.get_qualified_name_for_contract(&contract_name.name);
+
+            let uses_safe_math = context
+                .get_all_using_directives(&qualified_name)
+                .iter()
+                .any(|using| {
+                    using
+                        .library_name
+                        .as_ref()
+                        .is_some_and(|name| name.contains("SafeMath"))
+                });
+            if uses_safe_math || Self::has_safe_math_calls(contract_def, file) {
+                return Vec::new();
+            }
+
+            let state_vars: HashSet<&str> = context
+                .get_all_state_variables(&qualified_name)
+                .into_iter()
+                .map(|v| v.name.as_str())
+                .collect();
+
+            let mut findings = Vec::new();
+
+            for part in &contract_def.parts {
+                let ContractPart::FunctionDefinition(func_def) = part else {
+                    continue;
+                };
+                if matches!(func_def.ty, FunctionTy::Modifier) || Self::is_read_only(func_def) {
+                    continue;
+                }
+                let Some(body) = &func_def.body else {
+                    continue;
+                };
+
+                let params: HashSet<String> = func_def
+                    .params
+                    .iter()
+                    .filter_map(|(_, param)| param.as_ref()?.name.as_ref())
+                    .map(|name| name.name.clone())
+                    .collect();
+                let locals = get_local_variable_names(func_def, body);
+
+                let is_tracked = |name: &str| {
+                    params.contains(name) || (state_vars.contains(name) && !locals.contains(name))
+                };
+
+                let mut operators = Vec::new();
+                let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+                    let (loc, operator, left, right) =
+                        match expr {
+                            Expression::Multiply(loc, l, r)
+                            | Expression::AssignMultiply(loc, l, r) => (loc, "*", l, r),
+                            Expression::Add(loc, l, r) | Expression::AssignAdd(loc, l, r) => {
+                                (loc, "+", l, r)
+                            }
+                            Expression::Subtract(loc, l, r)
+                            | Expression::AssignSubtract(loc, l, r) => (loc, "-", l, r),
+                            _ => return None,
+                        };
+                    let operand = [left, right]
+                        .into_iter()
+                        .filter_map(|operand| Self::base_name(operand))
+                        .find(|name| is_tracked(name))?;
+                    operators.push((operator, operand.to_string()));
+                    Some(*loc)
+                };
+
+                let mut locations = Vec::new();
+                find_locations_in_statement(body, file, &mut predicate, &mut locations);
+
+                for (location, (operator, operand)) in locations.into_iter().zip(operators) {
+                    findings.push(FindingData {
+                        detector_id: self.id(),
+                        location: location.with_message(format!(
+                            "unchecked `{}` on `{}` under `pragma solidity {}`",
+                            operator, operand, version
+                        )),
+                    });
+                }
+            }
+
+            findings
+        });
+    }
+}
+
+impl ArithmeticWithoutSafeMathDetector {
+    fn is_read_only(func_def: &solang_parser::pt::FunctionDefinition) -> bool {
+        func_def.attributes.iter().any(|attr| {
+            matches!(
+                attr,
+                FunctionAttribute::Mutability(
+                    Mutability::View(_) | Mutability::Pure(_) | Mutability::Constant(_)
+                )
+            )
+        })
+    }
+
+    /// Variable an operand reads from, e.g. `reserves[token].amount` -> `reserves`.
+    fn base_name(expr: &Expression) -> Option<&str> {
+        match expr {
+            Expression::Variable(id) => Some(&id.name),
+            Expression::Parenthesis(_, inner)
+            | Expression::ArraySubscript(_, inner, _)
+            | Expression::MemberAccess(_, inner, _) => Self::base_name(inner),
+            _ => None,
+        }
+    }
+
+    /// `a.add(b)` / `a.mul(b)` style calls mean arithmetic goes through a checked library.
+    fn has_safe_math_calls(contract_def: &ContractDefinition, file: &SolidityFile) -> bool {
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            match expr {
+                Expression::FunctionCall(loc, func, _) => match func.as_ref() {
+                    Expression::MemberAccess(_, _, member)
+                        if SAFE_MATH_METHODS.contains(&member.name.as_str()) =>
+                    {
+                        Some(*loc)
+                    }
+                    _ => None,
+                },
+                _ => None,
+            }
+        };
+
+        contract_def.parts.iter().any(|part| {
+            let ContractPart::FunctionDefinition(func_def) = part else {
+                return false;
+            };
+            let Some(body) = &func_def.body else {
+                return false;
+            };
+            let mut calls = Vec::new();
+            find_locations_in_statement(body, file, &mut predicate, &mut calls);
+            !calls.is_empty()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::run_detector_on_code;
+
+    const AMM: &str = r#"
+        contract Pair {
+            uint256 reserve0;
+            uint256 reserve1;
+
+            function swap(uint256 amountIn) external {
+                uint256 k = reserve0 * reserve1;                // Positive
+                uint256 next = reserve0 + amountIn;             // Positive
+                for (uint256 i; i < 3; i++) {
+                    k = i + 1;                                  // Negative: locals only
+                }
+                reserve1 = k / next;
+            }
+
+            function quote(uint256 a) external view returns (uint256) {
+                return a * reserve0;                            // Negative: view
+            }
+        }
+    "#;
+
+    #[test]
+    fn test_flags_raw_math_before_0_8() {
+        let code = format!("pragma solidity 0.7.6;\n{}", AMM);
+        let detector = Arc::new(ArithmeticWithoutSafeMathDetector::default());
+        let locations = run_detector_on_code(detector, &code, "pair.sol");
+
+        assert_eq!(locations.len(), 2, "Should detect 2 unchecked operations");
+        assert_eq!(locations[0].line, 8);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some("unchecked `*` on `reserve0` under `pragma solidity 0.7.6`")
+        );
+        assert_eq!(locations[1].line, 9);
+    }
+
+    #[test]
+    fn test_skips_safemath_using_directive() {
+        let code = format!(
+            "pragma solidity ^0.7.6;\n{}",
+            AMM.replace(
+                "contract Pair {",
+                "contract Pair {\n            using SafeMath for uint256;"
+            )
+        );
+        let detector = Arc::new(ArithmeticWithoutSafeMathDetector::default());
+        let locations = run_detector_on_code(detector, &code, "pair.sol");
+        assert_eq!(locations.len(), 0);
+    }
+
+    #[test]
+    fn test_skips_0_8_contracts() {
+        let code = format!("pragma solidity ^0.8.0;\n{}", AMM);
+        let detector = Arc::new(ArithmeticWithoutSafeMathDetector::default());
+        let locations = run_detector_on_code(detector, &code, "pair.sol");
+        assert_eq!(locations.len(), 0);
+    }
+}
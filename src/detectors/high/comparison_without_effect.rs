@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::location::loc_to_location;
 use crate::{core::visitor::ASTVisitor, models::FindingData};
@@ -21,6 +22,10 @@ impl Detector for ComparisonWithoutEffectDetector {
         Severity::High
     }
 
+    fn category(&self) -> Category {
+        Category::Validation
+    }
+
     fn description(&self) -> &str {
         "A comparison operation (e.g., `a > b`) is used as a standalone statement. \
         This has no effect and likely indicates a missing `require`, `if`, or `assert` statement, \
@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::location::loc_to_location;
 use crate::{core::visitor::ASTVisitor, models::FindingData};
@@ -21,6 +22,10 @@ impl Detector for CurveSpotPriceOracleDetector {
         Severity::High
     }
 
+    fn category(&self) -> Category {
+        Category::Oracle
+    }
+
     fn description(&self) -> &str {
         "Using `get_dy_underlying` from Curve pools as a price oracle is vulnerable to flash loan manipulation. \
         Attackers can skew pool reserves within a single transaction to get a manipulated price, leading to potential loss of funds. \
@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::ast_utils;
 use crate::{core::visitor::ASTVisitor, models::FindingData};
@@ -21,6 +22,10 @@ impl Detector for DelegatecallInLoopDetector {
         Severity::High
     }
 
+    fn category(&self) -> Category {
+        Category::ExternalCall
+    }
+
     fn description(&self) -> &str {
         "Executing `delegatecall` inside a loop is highly dangerous. It multiplies reentrancy risks, as external code runs with the caller's storage and permissions repeatedly. Malicious targets or manipulated loop iterations can corrupt state or cause denial of service via gas exhaustion. Refactor to avoid `delegatecall` in loops unless the targets and loop bounds are strictly controlled and understood."
     }
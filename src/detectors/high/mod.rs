@@ -1,11 +1,40 @@
+pub mod arithmetic_without_safemath;
 pub mod comparison_without_effect;
 pub mod curve_spot_price_oracle;
 pub mod delegatecall_in_loop;
 pub mod msg_value_in_loop;
+pub mod reentrancy;
+pub mod unchecked_low_level_call;
+pub mod unprotected_selfdestruct_upgrade;
+pub mod unvalidated_cross_chain_message;
 pub mod wsteth_stethpertoken_usage;
 
+use crate::detectors::Detector;
+
+pub use arithmetic_without_safemath::ArithmeticWithoutSafeMathDetector;
 pub use comparison_without_effect::ComparisonWithoutEffectDetector;
 pub use curve_spot_price_oracle::CurveSpotPriceOracleDetector;
 pub use delegatecall_in_loop::DelegatecallInLoopDetector;
 pub use msg_value_in_loop::MsgValueInLoopDetector;
+pub use reentrancy::ReentrancyDetector;
+pub use unchecked_low_level_call::UncheckedLowLevelCallDetector;
+pub use unprotected_selfdestruct_upgrade::UnprotectedSelfdestructUpgradeDetector;
+pub use unvalidated_cross_chain_message::UnvalidatedCrossChainMessageDetector;
 pub use wsteth_stethpertoken_usage::WstethStethPerTokenUsageDetector;
+
+/// Every High detector, in registration order. A detector exported above but missing
+/// here never runs, which `test_every_exported_detector_is_built_in` catches.
+pub fn all() -> Vec<Box<dyn Detector>> {
+    vec![
+        Box::new(ArithmeticWithoutSafeMathDetector::default()),
+        Box::new(ComparisonWithoutEffectDetector::default()),
+        Box::new(CurveSpotPriceOracleDetector::default()),
+        Box::new(DelegatecallInLoopDetector::default()),
+        Box::new(MsgValueInLoopDetector::default()),
+        Box::new(ReentrancyDetector::default()),
+        Box::new(UncheckedLowLevelCallDetector::default()),
+        Box::new(UnprotectedSelfdestructUpgradeDetector::default()),
+        Box::new(UnvalidatedCrossChainMessageDetector::default()),
+        Box::new(WstethStethPerTokenUsageDetector::default()),
+    ]
+}
@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::ast_utils;
 use crate::{core::visitor::ASTVisitor, models::FindingData};
@@ -21,6 +22,10 @@ impl Detector for MsgValueInLoopDetector {
         Severity::High
     }
 
+    fn category(&self) -> Category {
+        Category::Validation
+    }
+
     fn description(&self) -> &str {
         "Reading `msg.value` inside a loop is dangerous. If the loop executes multiple times, \
         the full `msg.value` might be credited or used in calculations repeatedly, \
@@ -0,0 +1,507 @@
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::category::Category;
+use crate::models::finding::Location;
+use crate::models::severity::Severity;
+use crate::models::{FindingData, SolidityFile, TypeInfo};
+use crate::utils::ast_utils::{
+    collect_local_variables, find_locations_in_expression, get_local_variable_names,
+    has_reentrancy_guard, is_external_call, is_function_readonly,
+};
+use solang_parser::pt::{
+    CatchClause, CodeLocation, ContractDefinition, ContractPart, ContractTy, Expression,
+    FunctionAttribute, FunctionDefinition, FunctionTy, Loc, Statement, Type, Visibility,
+};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+#[derive(Debug, Default)]
+pub struct ReentrancyDetector;
+
+impl Detector for ReentrancyDetector {
+    fn id(&self) -> &'static str {
+        "reentrancy"
+    }
+
+    fn name(&self) -> &str {
+        "State variable written after an external call without a reentrancy guard"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::High
+    }
+
+    fn category(&self) -> Category {
+        Category::ExternalCall
+    }
+
+    fn description(&self) -> &str {
+        "An external call hands control to the callee, which can call back into the contract \
+         before the function finishes. When state is only updated after the call, the re-entered \
+         function still sees the old state: the classic withdraw that sends ETH before zeroing \
+         the balance can be drained by calling it again from the recipient's `receive`. Update \
+         state before making external calls (checks-effects-interactions), or add a \
+         `nonReentrant` guard. Public and external functions are checked statement by statement; \
+         `if`/`else` branches are followed separately, loops and internal calls are not, and \
+         contracts inheriting `ReentrancyGuard` are skipped."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad - the balance is cleared after the call
+function withdraw() external {
+    uint256 amount = balances[msg.sender];
+    (bool success,) = msg.sender.call{value: amount}("");
+    require(success);
+    balances[msg.sender] = 0;
+}
+
+// Good - checks-effects-interactions
+function withdraw() external {
+    uint256 amount = balances[msg.sender];
+    balances[msg.sender] = 0;
+    (bool success,) = msg.sender.call{value: amount}("");
+    require(success);
+}
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_contract(move |contract_def, file, context| {
+            let Some(contract_name) = contract_def.name.as_ref() else {
+                return Vec::new();
+            };
+            if matches!(
+                contract_def.ty,
+                ContractTy::Interface(_) | ContractTy::Library(_)
+            ) || Self::inherits_guard(contract_def)
+                || context.contract_inherits_from(contract_def, file, "ReentrancyGuard")
+            {
+                return Vec::new();
+            }
+
+            let qualified_name = context.get_qualified_name_for_contract(&contract_name.name);
+            let state_vars = context.get_all_state_variables(&qualified_name);
+            let storage: HashSet<&str> = state_vars
+                .iter()
+                .filter(|v| !v.is_constant && !v.is_immutable)
+                .map(|v| v.name.as_str())
+                .collect();
+            if storage.is_empty() {
+                return Vec::new();
+            }
+            let value_state_vars: HashSet<&str> = state_vars
+                .iter()
+                .filter(|v| Self::is_value_type_info(&v.type_info))
+                .map(|v| v.name.as_str())
+                .collect();
+
+            let mut findings = Vec::new();
+            for part in &contract_def.parts {
+                let ContractPart::FunctionDefinition(func_def) = part else {
+                    continue;
+                };
+                let Some(body) = &func_def.body else {
+                    continue;
+                };
+                if !Self::is_entry_point(func_def)
+                    || is_function_readonly(func_def)
+                    || has_reentrancy_guard(func_def)
+                {
+                    continue;
+                }
+
+                let locals = get_local_variable_names(func_def, body);
+                let mut walker = Walker {
+                    file,
+                    state_vars: storage
+                        .iter()
+                        .copied()
+                        .filter(|name| !locals.contains(*name))
+                        .collect(),
+                    value_vars: Self::value_typed_locals(func_def, body)
+                        .into_iter()
+                        .chain(
+                            value_state_vars
+                                .iter()
+                                .filter(|name| !locals.contains(**name))
+                                .map(|name| name.to_string()),
+                        )
+                        .collect(),
+                    writes: Vec::new(),
+                };
+                walker.statement(body, None);
+
+                findings.extend(walker.writes.into_iter().map(|write| FindingData {
+                    detector_id: self.id(),
+                    location: write.location.with_message(format!(
+                        "`{}` is written after the external call on line {}, and the function \
+                         has no reentrancy guard",
+                        write.variable, write.call_line
+                    )),
+                }));
+            }
+            findings
+        });
+    }
+}
+
+impl ReentrancyDetector {
+    /// Whether a direct base is named like `ReentrancyGuard`, even when it did not resolve.
+    fn inherits_guard(contract_def: &ContractDefinition) -> bool {
+        contract_def.base.iter().any(|base| {
+            base.name
+                .identifiers
+                .last()
+                .is_some_and(|id| id.name.contains("ReentrancyGuard"))
+        })
+    }
+
+    /// Functions callable from outside: public and external ones, `receive` and `fallback`.
+    fn is_entry_point(func_def: &FunctionDefinition) -> bool {
+        match func_def.ty {
+            FunctionTy::Receive | FunctionTy::Fallback => true,
+            FunctionTy::Function => func_def.attributes.iter().any(|attr| {
+                matches!(
+                    attr,
+                    FunctionAttribute::Visibility(Visibility::Public(_) | Visibility::External(_))
+                )
+            }),
+            _ => false,
+        }
+    }
+
+    fn is_value_type_info(type_info: &TypeInfo) -> bool {
+        matches!(
+            type_info,
+            TypeInfo::Bool
+                | TypeInfo::Int(_)
+                | TypeInfo::Uint(_)
+                | TypeInfo::Bytes(_)
+                | TypeInfo::String
+                | TypeInfo::DynamicBytes
+        )
+    }
+
+    /// Parameters and locals of value types, whose member calls (`amount.mul(x)`) are library
+    /// calls rather than calls to another contract.
+    fn value_typed_locals(func_def: &FunctionDefinition, body: &Statement) -> HashSet<String> {
+        let is_value_type = |ty: &Expression| {
+            matches!(
+                ty,
+                Expression::Type(
+                    _,
+                    Type::Bool
+                        | Type::Int(_)
+                        | Type::Uint(_)
+                        | Type::Bytes(_)
+                        | Type::String
+                        | Type::DynamicBytes
+                )
+            )
+        };
+        let mut names: HashSet<String> = func_def
+            .params
+            .iter()
+            .chain(&func_def.returns)
+            .filter_map(|(_, param)| param.as_ref())
+            .filter(|param| is_value_type(&param.ty))
+            .filter_map(|param| param.name.as_ref())
+            .map(|name| name.name.clone())
+            .collect();
+        collect_local_variables(body, &mut |decl| {
+            if let Some(name) = &decl.name {
+                if is_value_type(&decl.ty) {
+                    names.insert(name.name.clone());
+                }
+            }
+        });
+        names
+    }
+}
+
+/// A state write following an external call.
+struct LateWrite {
+    location: Location,
+    variable: String,
+    call_line: usize,
+}
+
+/// Walks a function body in execution order, recording state writes that follow an
+/// external call.
+struct Walker<'a> {
+    file: &'a SolidityFile,
+    state_vars: HashSet<&'a str>,
+    value_vars: HashSet<String>,
+    writes: Vec<LateWrite>,
+}
+
+impl Walker<'_> {
+    /// Walk `stmt` given the line of an earlier external call, if any, returning the line of
+    /// the first external call made on the way out of it.
+    fn statement(&mut self, stmt: &Statement, call: Option<usize>) -> Option<usize> {
+        match stmt {
+            Statement::Block { statements, .. } => statements
+                .iter()
+                .fold(call, |call, stmt| self.statement(stmt, call)),
+            Statement::If(_, cond, then, otherwise) => {
+                let call = self.expression(cond, call);
+                let after_then = self.statement(then, call);
+                let after_else = match otherwise {
+                    Some(otherwise) => self.statement(otherwise, call),
+                    None => call,
+                };
+                call.or(after_then).or(after_else)
+            }
+            Statement::While(_, cond, body) => {
+                let call = self.expression(cond, call);
+                self.statement(body, call)
+            }
+            Statement::DoWhile(_, body, cond) => {
+                let call = self.statement(body, call);
+                self.expression(cond, call)
+            }
+            Statement::For(_, init, cond, next, body) => {
+                let mut call = match init {
+                    Some(init) => self.statement(init, call),
+                    None => call,
+                };
+                if let Some(cond) = cond {
+                    call = self.expression(cond, call);
+                }
+                if let Some(body) = body {
+                    call = self.statement(body, call);
+                }
+                match next {
+                    Some(next) => self.expression(next, call),
+                    None => call,
+                }
+            }
+            Statement::Try(_, expr, returns, catches) => {
+                // The try call itself is external, whichever way it resolves
+                let call = self.expression(expr, call).or(self.call_line(expr));
+                let mut after = call;
+                if let Some((_, block)) = returns {
+                    after = after.or(self.statement(block, call));
+                }
+                for catch in catches {
+                    let (CatchClause::Simple(_, _, block) | CatchClause::Named(_, _, _, block)) =
+                        catch;
+                    after = after.or(self.statement(block, call));
+                }
+                after
+            }
+            Statement::Expression(_, expr)
+            | Statement::VariableDefinition(_, _, Some(expr))
+            | Statement::Return(_, Some(expr))
+            | Statement::Emit(_, expr) => self.expression(expr, call),
+            Statement::Revert(_, _, args) => args
+                .iter()
+                .fold(call, |call, arg| self.expression(arg, call)),
+            _ => call,
+        }
+    }
+
+    /// Record the state writes in `expr` if a call came before it, and return the line of the
+    /// earlier call or else of the first external call in `expr`.
+    fn expression(&mut self, expr: &Expression, call: Option<usize>) -> Option<usize> {
+        if let Some(call_line) = call {
+            let state_vars = &self.state_vars;
+            let mut variables = Vec::new();
+            let mut predicate = |e: &Expression, _: &SolidityFile| -> Option<Loc> {
+                let target = match e {
+                    Expression::Assign(_, target, _)
+                    | Expression::AssignOr(_, target, _)
+                    | Expression::AssignAnd(_, target, _)
+                    | Expression::AssignXor(_, target, _)
+                    | Expression::AssignShiftLeft(_, target, _)
+                    | Expression::AssignShiftRight(_, target, _)
+                    | Expression::AssignAdd(_, target, _)
+                    | Expression::AssignSubtract(_, target, _)
+                    | Expression::AssignMultiply(_, target, _)
+                    | Expression::AssignDivide(_, target, _)
+                    | Expression::AssignModulo(_, target, _)
+                    | Expression::PreIncrement(_, target)
+                    | Expression::PostIncrement(_, target)
+                    | Expression::PreDecrement(_, target)
+                    | Expression::PostDecrement(_, target)
+                    | Expression::Delete(_, target) => target.as_ref(),
+                    Expression::FunctionCall(_, func, _) => match func.as_ref() {
+                        Expression::MemberAccess(_, base, member)
+                            if member.name == "push" || member.name == "pop" =>
+                        {
+                            base.as_ref()
+                        }
+                        _ => return None,
+                    },
+                    _ => return None,
+                };
+                let root = root_variable(target).filter(|root| state_vars.contains(root))?;
+                variables.push(root.to_string());
+                Some(e.loc())
+            };
+            let mut writes = Vec::new();
+            find_locations_in_expression(expr, self.file, &mut predicate, &mut writes);
+            self.writes.extend(
+                writes
+                    .into_iter()
+                    .zip(variables)
+                    .map(|(location, variable)| LateWrite {
+                        location,
+                        variable,
+                        call_line,
+                    }),
+            );
+            return call;
+        }
+        self.call_line(expr)
+    }
+
+    /// Line of the first call to another contract in `expr`.
+    fn call_line(&self, expr: &Expression) -> Option<usize> {
+        let value_vars = &self.value_vars;
+        let mut predicate = |e: &Expression, _: &SolidityFile| -> Option<Loc> {
+            let Expression::FunctionCall(loc, func, _) = e else {
+                return None;
+            };
+            let callee = match func.as_ref() {
+                // `target.call{value: v}(data)`
+                Expression::FunctionCallBlock(_, callee, _) => callee.as_ref(),
+                _ if is_external_call(e) => func.as_ref(),
+                _ => return None,
+            };
+            let Expression::MemberAccess(_, base, _) = callee else {
+                return None;
+            };
+            match root_variable(base) {
+                Some(root) if value_vars.contains(root) => None,
+                _ => Some(*loc),
+            }
+        };
+        let mut calls = Vec::new();
+        find_locations_in_expression(expr, self.file, &mut predicate, &mut calls);
+        calls.first().map(|call| call.line)
+    }
+}
+
+/// `balances` in `balances[user].amount`.
+fn root_variable(expr: &Expression) -> Option<&str> {
+    match expr {
+        Expression::Variable(id) => Some(&id.name),
+        Expression::ArraySubscript(_, base, _)
+        | Expression::ArraySlice(_, base, _, _)
+        | Expression::MemberAccess(_, base, _)
+        | Expression::Parenthesis(_, base) => root_variable(base),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::run_detector_on_code;
+
+    #[test]
+    fn test_reentrancy() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            interface IERC20 {
+                function transfer(address to, uint256 amount) external returns (bool);
+            }
+
+            contract Bank {
+                mapping(address => uint256) public balances;
+                uint256 public totalDeposits;
+                IERC20 public token;
+
+                function withdraw() external {
+                    uint256 amount = balances[msg.sender];
+                    (bool success, ) = msg.sender.call{value: amount}("");
+                    require(success, "failed");
+                    balances[msg.sender] = 0;                           // Positive
+                    totalDeposits -= amount;                            // Positive
+                }
+
+                function withdrawToken(uint256 amount) public {
+                    if (amount > 0) {
+                        token.transfer(msg.sender, amount);
+                    } else {
+                        totalDeposits += 1;                             // Negative: other branch
+                    }
+                    balances[msg.sender] -= amount;                     // Positive
+                }
+
+                function withdrawChecked() external {
+                    uint256 amount = balances[msg.sender];
+                    balances[msg.sender] = 0;                           // Negative: before the call
+                    totalDeposits -= amount;
+                    payable(msg.sender).transfer(amount);
+                    uint256 remaining = amount - 1;                     // Negative: local write
+                    remaining = 0;
+                }
+
+                function withdrawGuarded() external nonReentrant {
+                    (bool success, ) = msg.sender.call{value: balances[msg.sender]}("");
+                    require(success, "failed");
+                    balances[msg.sender] = 0;                           // Negative: guarded
+                }
+
+                function _settle(address user) internal {
+                    token.transfer(user, balances[user]);
+                    balances[user] = 0;                                 // Negative: internal
+                }
+
+                modifier nonReentrant() {
+                    _;
+                }
+            }
+
+            contract GuardedBank is ReentrancyGuard {
+                mapping(address => uint256) public balances;
+
+                function withdraw() external {
+                    (bool success, ) = msg.sender.call{value: balances[msg.sender]}("");
+                    require(success, "failed");
+                    balances[msg.sender] = 0;                           // Negative: inherits the guard
+                }
+            }
+
+            abstract contract ReentrancyGuard {}
+
+            abstract contract VaultBase is ReentrancyGuard {}
+
+            contract Vault is VaultBase {
+                mapping(address => uint256) public balances;
+
+                function withdraw() external {
+                    (bool success, ) = msg.sender.call{value: balances[msg.sender]}("");
+                    require(success, "failed");
+                    balances[msg.sender] = 0;                           // Negative: inherits it indirectly
+                }
+            }
+        "#;
+        let detector = Arc::new(ReentrancyDetector::default());
+        let locations = run_detector_on_code(detector, code, "bank.sol");
+
+        let lines: Vec<_> = locations.iter().map(|l| l.line).collect();
+        assert_eq!(lines, [17, 18, 27], "{:?}", locations);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some(
+                "`balances` is written after the external call on line 15, and the function has \
+                 no reentrancy guard"
+            )
+        );
+        assert_eq!(
+            locations[2].message.as_deref(),
+            Some(
+                "`balances` is written after the external call on line 23, and the function has \
+                 no reentrancy guard"
+            )
+        );
+    }
+}
@@ -1,10 +1,12 @@
 use crate::core::visitor::ASTVisitor;
 use crate::detectors::Detector;
+use crate::models::category::Category;
+use crate::models::finding::Location;
 use crate::models::severity::Severity;
-use crate::models::FindingData;
+use crate::models::{FindingData, SolidityFile};
 use crate::utils::ast_utils::find_variable_uses;
 use crate::utils::location::loc_to_location;
-use solang_parser::pt::{Expression, Identifier, Parameter, Statement};
+use solang_parser::pt::{CodeLocation, Expression, Identifier, Loc, Parameter, Statement};
 use std::sync::Arc;
 
 #[derive(Debug, Default)]
@@ -20,13 +22,23 @@ impl Detector for UncheckedLowLevelCallDetector {
     }
 
     fn severity(&self) -> Severity {
-        Severity::Medium
+        Severity::High
+    }
+
+    fn category(&self) -> Category {
+        Category::ExternalCall
+    }
+
+    fn version(&self) -> u32 {
+        2
     }
 
     fn description(&self) -> &str {
         "The return value of `call()`, `delegatecall()`, or `staticcall()` should always be checked to ensure \
         the call was successful. Unchecked return values allow execution to continue even if the call failed, \
-        which can lead to unexpected behavior, loss of funds, or security vulnerabilities."
+        which can lead to unexpected behavior, loss of funds, or security vulnerabilities. A captured \
+        `success` counts as checked only when it is later read in a `require`/`assert`, an `if` or loop \
+        condition, a `revert` or a `return` (which leaves the check to the caller)."
     }
 
     fn example(&self) -> Option<String> {
@@ -42,10 +54,14 @@ payable(addr).call{value: 1 ether}("");
 (bool success, ) = addr.call{value: 1 ether}("");
 require(success, "Transfer failed");
 
-// Good - success checked with if or custom handler
+// Bad - success only logged, the failure is ignored
+(bool success, ) = addr.call{value: 1 ether}("");
+emit Called(success);
+
+// Good - success checked with if, or returned to the caller
 (bool success, ) = addr.call{value: 1 ether}("");
 if (!success) revert TransferFailed();
-handleCallResult(success); // Custom handler is also fine
+return success;
 ```"#
                 .to_string(),
         )
@@ -68,7 +84,7 @@ handleCallResult(success); // Custom handler is also fine
 fn find_unchecked_low_level_calls(
     stmt: &Statement,
     full_body: &Statement,
-    file: &crate::models::SolidityFile,
+    file: &SolidityFile,
     detector_id: &'static str,
     findings: &mut Vec<FindingData>,
 ) {
@@ -93,17 +109,10 @@ fn find_unchecked_low_level_calls(
         Statement::Expression(loc, Expression::Assign(_, left, right)) => {
             if let Expression::FunctionCall(_, func_expr, _) = right.as_ref() {
                 if is_low_level_call(func_expr) {
-                    if let Some(success_var) = get_success_variable(left) {
-                        // Check if success variable is used anywhere in function body
-                        let uses = find_variable_uses(&success_var, full_body, file);
-                        if uses.is_empty() {
-                            findings.push(FindingData {
-                                detector_id,
-                                location: loc_to_location(loc, file),
-                            });
-                        }
-                    } else {
-                        // No success variable captured - flag it
+                    let checked = get_success_variable(left).is_some_and(|success_var| {
+                        is_success_checked(&success_var, loc, full_body, file)
+                    });
+                    if !checked {
                         findings.push(FindingData {
                             detector_id,
                             location: loc_to_location(loc, file),
@@ -148,16 +157,99 @@ fn is_low_level_call(expr: &Expression) -> bool {
 }
 
 /// Get the success variable name from tuple assignment
-/// e.g., (bool success, ) = ... -> returns "success"
+/// e.g., (bool success, ) = ... or (ok, ) = ... -> returns "success" / "ok"
 fn get_success_variable(left: &Expression) -> Option<String> {
-    if let Expression::List(_, params) = left {
-        if let Some((_, Some(param))) = params.first() {
-            if let Parameter { name: Some(id), .. } = param {
-                return Some(id.name.clone());
+    let Expression::List(_, params) = left else {
+        return None;
+    };
+    match params.first() {
+        Some((_, Some(Parameter { name: Some(id), .. }))) => Some(id.name.clone()),
+        // Assignment to an already declared variable
+        Some((
+            _,
+            Some(Parameter {
+                ty: Expression::Variable(id),
+                name: None,
+                ..
+            }),
+        )) => Some(id.name.clone()),
+        _ => None,
+    }
+}
+
+/// Whether `success_var` is read after the assignment at `assignment` in a position that
+/// decides on it: a `require`/`assert` argument, an `if` or loop condition, a `revert`
+/// argument or a returned value.
+fn is_success_checked(
+    success_var: &str,
+    assignment: &Loc,
+    full_body: &Statement,
+    file: &SolidityFile,
+) -> bool {
+    let assigned = loc_to_location(assignment, file);
+    let assignment_end = (
+        assigned.line_end.unwrap_or(assigned.line),
+        assigned.column_end.unwrap_or_default(),
+    );
+
+    let mut sites = Vec::new();
+    collect_check_sites(full_body, &mut sites);
+    let sites: Vec<Location> = sites.iter().map(|loc| loc_to_location(loc, file)).collect();
+
+    find_variable_uses(success_var, full_body, file)
+        .iter()
+        .filter(|use_loc| (use_loc.line, use_loc.column.unwrap_or_default()) > assignment_end)
+        .any(|use_loc| sites.iter().any(|site| contains(site, use_loc)))
+}
+
+/// Locations of expressions whose value decides control flow
+fn collect_check_sites(stmt: &Statement, sites: &mut Vec<Loc>) {
+    match stmt {
+        Statement::Block { statements, .. } => {
+            for s in statements {
+                collect_check_sites(s, sites);
+            }
+        }
+        Statement::Expression(_, Expression::FunctionCall(_, func, args)) if matches!(func.as_ref(), Expression::Variable(id) if id.name == "require" || id.name == "assert") =>
+        {
+            sites.extend(args.iter().map(|arg| arg.loc()));
+        }
+        Statement::If(_, condition, then_stmt, else_stmt) => {
+            sites.push(condition.loc());
+            collect_check_sites(then_stmt, sites);
+            if let Some(else_s) = else_stmt {
+                collect_check_sites(else_s, sites);
             }
         }
+        Statement::While(_, condition, body) | Statement::DoWhile(_, body, condition) => {
+            sites.push(condition.loc());
+            collect_check_sites(body, sites);
+        }
+        Statement::For(_, _, condition, _, body) => {
+            if let Some(condition) = condition {
+                sites.push(condition.loc());
+            }
+            if let Some(body) = body {
+                collect_check_sites(body, sites);
+            }
+        }
+        Statement::Revert(_, _, args) => {
+            sites.extend(args.iter().map(|arg| arg.loc()));
+        }
+        Statement::Return(_, Some(expr)) => sites.push(expr.loc()),
+        _ => {}
     }
-    None
+}
+
+/// Whether `inner` starts within `outer`
+fn contains(outer: &Location, inner: &Location) -> bool {
+    let start = (outer.line, outer.column.unwrap_or_default());
+    let end = (
+        outer.line_end.unwrap_or(outer.line),
+        outer.column_end.unwrap_or(usize::MAX),
+    );
+    let position = (inner.line, inner.column.unwrap_or_default());
+    start <= position && position < end
 }
 
 #[cfg(test)]
@@ -224,4 +316,52 @@ mod tests {
 
         assert_eq!(locations.len(), 0);
     }
+
+    #[test]
+    fn test_detects_destructured_but_unchecked() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            contract Test {
+                event Called(bool ok);
+                bool public lastOk;
+
+                function logged(address addr) external {
+                    (bool success, ) = addr.call("");
+                    emit Called(success);
+                }
+
+                function stored(address addr) external {
+                    (bool success, bytes memory data) = addr.staticcall("");
+                    lastOk = success;
+                    require(data.length > 0);
+                }
+
+                function checkedTooEarly(address addr) external {
+                    bool ok = true;
+                    require(ok);
+                    (ok, ) = addr.delegatecall("");
+                }
+
+                function checkedExisting(address addr) external returns (bool) {
+                    bool ok;
+                    (ok, ) = addr.delegatecall("");
+                    return ok;
+                }
+
+                function checkedInLoop(address[] calldata targets) external {
+                    for (uint256 i; i < targets.length; i++) {
+                        (bool success, ) = targets[i].call("");
+                        if (!success) revert();
+                    }
+                }
+            }
+        "#;
+
+        let detector = Arc::new(UncheckedLowLevelCallDetector::default());
+        let locations = run_detector_on_code(detector, code, "test.sol");
+
+        let lines: Vec<_> = locations.iter().map(|l| l.line).collect();
+        assert_eq!(lines, [9, 14, 22], "{:?}", locations);
+    }
 }
@@ -0,0 +1,341 @@
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::category::Category;
+use crate::models::finding::Location;
+use crate::models::severity::Severity;
+use crate::models::{FindingData, SolidityFile};
+use crate::utils::ast_utils::{
+    find_locations_in_statement, get_function_visibility, get_local_variable_names,
+    has_privileged_modifier,
+};
+use crate::utils::location::loc_to_location;
+use solang_parser::pt::{ContractPart, Expression, FunctionTy, Loc, Visibility};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Calls that destroy the contract or replace its code.
+const DANGEROUS_CALLS: [&str; 5] = [
+    "selfdestruct",
+    "suicide",
+    "_authorizeUpgrade",
+    "upgradeTo",
+    "upgradeToAndCall",
+];
+
+#[derive(Debug, Default)]
+pub struct UnprotectedSelfdestructUpgradeDetector;
+
+impl Detector for UnprotectedSelfdestructUpgradeDetector {
+    fn id(&self) -> &'static str {
+        "unprotected-selfdestruct-upgrade"
+    }
+
+    fn name(&self) -> &str {
+        "Anyone can destroy or upgrade the contract"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::High
+    }
+
+    fn category(&self) -> Category {
+        Category::AccessControl
+    }
+
+    fn description(&self) -> &str {
+        "A public or external function calls `selfdestruct`/`suicide`, or upgrades the contract \
+         through `_authorizeUpgrade`, `upgradeTo` or `upgradeToAndCall` (or their internal \
+         `_upgradeTo*` forms), without an access-control modifier and without comparing \
+         `msg.sender` to a state variable or constant before the call. Any account can then \
+         destroy the contract or point it at code of its choosing. Restrict the function with a \
+         modifier such as `onlyOwner`, or check `msg.sender` before the call."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad - anyone can kill the contract or replace its implementation
+function kill() external {
+    selfdestruct(payable(msg.sender));
+}
+
+function upgrade(address newImplementation) external {
+    upgradeToAndCall(newImplementation, "");
+}
+
+// Good
+function kill() external onlyOwner {
+    selfdestruct(payable(owner));
+}
+
+function upgrade(address newImplementation) external {
+    require(msg.sender == admin, "not admin");
+    upgradeToAndCall(newImplementation, "");
+}
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_contract(move |contract_def, file, context| {
+            let Some(contract_name) = contract_def.name.as_ref() else {
+                return Vec::new();
+            };
+
+            let qualified_name = context.get_qualified_name_for_contract(&contract_name.name);
+            let state_vars: HashSet<&str> = context
+                .get_all_state_variables(&qualified_name)
+                .into_iter()
+                .map(|v| v.name.as_str())
+                .collect();
+
+            let mut findings = Vec::new();
+
+            for part in &contract_def.parts {
+                let ContractPart::FunctionDefinition(func_def) = part else {
+                    continue;
+                };
+                let Some(body) = &func_def.body else {
+                    continue;
+                };
+                let callable = match func_def.ty {
+                    FunctionTy::Fallback | FunctionTy::Receive => true,
+                    // Functions without a visibility (before 0.5.0) are public
+                    FunctionTy::Function => !matches!(
+                        get_function_visibility(func_def),
+                        Some(Visibility::Internal(_) | Visibility::Private(_))
+                    ),
+                    _ => false,
+                };
+                if !callable || has_privileged_modifier(func_def) {
+                    continue;
+                }
+
+                let mut calls = Vec::new();
+                let mut names = Vec::new();
+                let mut call_predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+                    let Expression::FunctionCall(loc, callee, _) = expr else {
+                        return None;
+                    };
+                    let name = Self::dangerous_call_name(callee)?;
+                    names.push(name);
+                    Some(*loc)
+                };
+                find_locations_in_statement(body, file, &mut call_predicate, &mut calls);
+                let Some((first_call, call_name)) = calls
+                    .iter()
+                    .zip(names)
+                    .min_by_key(|(location, _)| Self::position(location))
+                else {
+                    continue;
+                };
+
+                let mut trusted = state_vars.clone();
+                for local in get_local_variable_names(func_def, body) {
+                    trusted.remove(local.as_str());
+                }
+                let mut checks = Vec::new();
+                let mut check_predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+                    match expr {
+                        Expression::Equal(loc, left, right)
+                        | Expression::NotEqual(loc, left, right)
+                            if (Self::is_msg_sender(left) && Self::is_trusted(right, &trusted))
+                                || (Self::is_msg_sender(right)
+                                    && Self::is_trusted(left, &trusted)) =>
+                        {
+                            Some(*loc)
+                        }
+                        _ => None,
+                    }
+                };
+                find_locations_in_statement(body, file, &mut check_predicate, &mut checks);
+                if checks
+                    .iter()
+                    .any(|check| Self::position(check) < Self::position(first_call))
+                {
+                    continue;
+                }
+
+                let function_name = func_def
+                    .name
+                    .as_ref()
+                    .map_or_else(|| func_def.ty.to_string(), |n| n.name.clone());
+                findings.push(FindingData {
+                    detector_id: self.id(),
+                    location: loc_to_location(&func_def.loc, file).with_message(format!(
+                        "`{}` calls `{}` without restricting the caller",
+                        function_name, call_name
+                    )),
+                });
+            }
+
+            findings
+        });
+    }
+}
+
+impl UnprotectedSelfdestructUpgradeDetector {
+    fn position(location: &Location) -> (usize, usize) {
+        (location.line, location.column.unwrap_or(0))
+    }
+
+    /// Name of the called function if it is in `DANGEROUS_CALLS`, or an internal
+    /// `_upgradeTo`/`_upgradeToAndCall`.
+    fn dangerous_call_name(callee: &Expression) -> Option<String> {
+        let name = match callee {
+            Expression::Variable(id) | Expression::MemberAccess(_, _, id) => &id.name,
+            _ => return None,
+        };
+        let upgrade = name.trim_start_matches('_');
+        (DANGEROUS_CALLS.contains(&name.as_str())
+            || upgrade == "upgradeTo"
+            || upgrade == "upgradeToAndCall")
+            .then(|| name.clone())
+    }
+
+    fn is_msg_sender(expr: &Expression) -> bool {
+        matches!(
+            expr,
+            Expression::MemberAccess(_, base, member)
+                if member.name == "sender"
+                    && matches!(base.as_ref(), Expression::Variable(id) if id.name == "msg")
+        )
+    }
+
+    /// A value the caller cannot choose: a state variable, a constant, an address literal, a
+    /// getter call without arguments such as `owner()`, or an `address(...)` conversion of one.
+    fn is_trusted(expr: &Expression, state_vars: &HashSet<&str>) -> bool {
+        match expr {
+            Expression::Variable(id) => {
+                state_vars.contains(id.name.as_str())
+                    || id
+                        .name
+                        .chars()
+                        .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+            }
+            Expression::HexNumberLiteral(..) | Expression::AddressLiteral(..) => true,
+            Expression::Parenthesis(_, inner) => Self::is_trusted(inner, state_vars),
+            Expression::FunctionCall(_, callee, args) => match (callee.as_ref(), args.as_slice()) {
+                (Expression::Type(..), [inner]) => Self::is_trusted(inner, state_vars),
+                (Expression::Variable(id), [inner]) if id.name == "payable" => {
+                    Self::is_trusted(inner, state_vars)
+                }
+                (Expression::Variable(_), []) => true,
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::run_detector_on_code;
+
+    #[test]
+    fn test_detects_unprotected_selfdestruct_and_upgrade() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            contract Vault {
+                address owner;
+
+                function kill() external {
+                    selfdestruct(payable(msg.sender));
+                }
+
+                function upgrade(address impl, address caller) public {
+                    require(caller == owner);
+                    upgradeToAndCall(impl, "");
+                }
+
+                function migrate(address impl) external {
+                    _upgradeTo(impl);
+                    require(msg.sender == owner);
+                }
+
+                function destroy() external {
+                    address sender = msg.sender;
+                    if (msg.sender == sender) {
+                        suicide(owner);
+                    }
+                }
+
+                function _kill() internal {
+                    selfdestruct(payable(owner));
+                }
+            }
+        "#;
+        let detector = Arc::new(UnprotectedSelfdestructUpgradeDetector::default());
+        let locations = run_detector_on_code(detector, code, "test.sol");
+        let lines: Vec<usize> = locations.iter().map(|l| l.line).collect();
+        assert_eq!(lines, [7, 11, 16, 21]);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some("`kill` calls `selfdestruct` without restricting the caller")
+        );
+        assert_eq!(
+            locations[1].message.as_deref(),
+            Some("`upgrade` calls `upgradeToAndCall` without restricting the caller")
+        );
+    }
+
+    #[test]
+    fn test_skips_function_with_access_control_modifier() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            contract Vault {
+                address owner;
+
+                modifier onlyOwner() {
+                    require(msg.sender == owner);
+                    _;
+                }
+
+                function kill() external onlyOwner {
+                    selfdestruct(payable(owner));
+                }
+
+                function upgradeTo(address impl) external onlyRole(UPGRADER_ROLE) {
+                    _authorizeUpgrade(impl);
+                }
+            }
+        "#;
+        let detector = Arc::new(UnprotectedSelfdestructUpgradeDetector::default());
+        let locations = run_detector_on_code(detector, code, "test.sol");
+        assert_eq!(locations.len(), 0);
+    }
+
+    #[test]
+    fn test_skips_function_with_sender_check() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            contract Vault is Ownable {
+                address admin;
+                address constant ADMIN = address(0x1234);
+
+                function kill() external {
+                    require(msg.sender == admin, "not admin");
+                    selfdestruct(payable(admin));
+                }
+
+                function upgrade(address impl) external {
+                    if (msg.sender != ADMIN) revert();
+                    upgradeToAndCall(impl, "");
+                }
+
+                function upgradeOwned(address impl) external {
+                    require(owner() == msg.sender);
+                    upgradeTo(impl);
+                }
+            }
+        "#;
+        let detector = Arc::new(UnprotectedSelfdestructUpgradeDetector::default());
+        let locations = run_detector_on_code(detector, code, "test.sol");
+        assert_eq!(locations.len(), 0);
+    }
+}
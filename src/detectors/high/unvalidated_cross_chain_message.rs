@@ -0,0 +1,526 @@
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::category::Category;
+use crate::models::severity::Severity;
+use crate::models::{FindingData, SolidityFile, TypeInfo};
+use crate::utils::ast_utils::{
+    find_locations_in_expression, find_locations_in_statement, find_statement_types,
+};
+use crate::utils::location::loc_to_location;
+use solang_parser::pt::{
+    ContractDefinition, ContractPart, Expression, FunctionAttribute, FunctionDefinition, Loc,
+    Statement,
+};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// A receive-side entry point of a cross-chain messaging protocol.
+struct MessageHandler {
+    name: &'static str,
+    /// Parameter types, compared by their last `.` segment (`Client.Any2EVMMessage`)
+    params: &'static [&'static str],
+    /// Indexes of the parameters that identify the sending chain and contract
+    source_params: &'static [usize],
+    /// Base contracts whose entry point checks `msg.sender` before calling this handler
+    caller_checked_by: &'static [&'static str],
+    /// Base contracts whose entry point checks the source before calling this handler
+    source_checked_by: &'static [&'static str],
+}
+
+const MESSAGE_HANDLERS: &[MessageHandler] = &[
+    // LayerZero v1
+    MessageHandler {
+        name: "lzReceive",
+        params: &["uint16", "bytes", "uint64", "bytes"],
+        source_params: &[0, 1],
+        caller_checked_by: &[],
+        source_checked_by: &[],
+    },
+    MessageHandler {
+        name: "_nonblockingLzReceive",
+        params: &["uint16", "bytes", "uint64", "bytes"],
+        source_params: &[0, 1],
+        caller_checked_by: &["LzApp", "NonblockingLzApp"],
+        source_checked_by: &["LzApp", "NonblockingLzApp"],
+    },
+    MessageHandler {
+        name: "_blockingLzReceive",
+        params: &["uint16", "bytes", "uint64", "bytes"],
+        source_params: &[0, 1],
+        caller_checked_by: &["LzApp"],
+        source_checked_by: &["LzApp"],
+    },
+    // LayerZero v2
+    MessageHandler {
+        name: "lzReceive",
+        params: &["Origin", "bytes32", "bytes", "address", "bytes"],
+        source_params: &[0],
+        caller_checked_by: &[],
+        source_checked_by: &[],
+    },
+    MessageHandler {
+        name: "_lzReceive",
+        params: &["Origin", "bytes32", "bytes", "address", "bytes"],
+        source_params: &[0],
+        caller_checked_by: &["OApp", "OAppReceiver"],
+        source_checked_by: &["OApp", "OAppReceiver"],
+    },
+    // Chainlink CCIP
+    MessageHandler {
+        name: "ccipReceive",
+        params: &["Any2EVMMessage"],
+        source_params: &[0],
+        caller_checked_by: &[],
+        source_checked_by: &[],
+    },
+    MessageHandler {
+        name: "_ccipReceive",
+        params: &["Any2EVMMessage"],
+        source_params: &[0],
+        caller_checked_by: &["CCIPReceiver"],
+        source_checked_by: &[],
+    },
+    // Axelar
+    MessageHandler {
+        name: "execute",
+        params: &["bytes32", "string", "string", "bytes"],
+        source_params: &[1, 2],
+        caller_checked_by: &[],
+        source_checked_by: &[],
+    },
+    MessageHandler {
+        name: "_execute",
+        params: &["bytes32", "string", "string", "bytes"],
+        source_params: &[1, 2],
+        caller_checked_by: &["AxelarExecutable"],
+        source_checked_by: &[],
+    },
+    MessageHandler {
+        name: "_execute",
+        params: &["string", "string", "bytes"],
+        source_params: &[0, 1],
+        caller_checked_by: &["AxelarExecutable"],
+        source_checked_by: &[],
+    },
+    // Hyperlane
+    MessageHandler {
+        name: "handle",
+        params: &["uint32", "bytes32", "bytes"],
+        source_params: &[0, 1],
+        caller_checked_by: &[],
+        source_checked_by: &[],
+    },
+    MessageHandler {
+        name: "_handle",
+        params: &["uint32", "bytes32", "bytes"],
+        source_params: &[0, 1],
+        caller_checked_by: &["Router", "GasRouter"],
+        source_checked_by: &["Router", "GasRouter"],
+    },
+    // Wormhole relayer
+    MessageHandler {
+        name: "receiveWormholeMessages",
+        params: &["bytes", "bytes[]", "bytes32", "uint16", "bytes32"],
+        source_params: &[2, 3],
+        caller_checked_by: &[],
+        source_checked_by: &[],
+    },
+];
+
+/// Gateway calls that authenticate the caller and the message together.
+const MESSAGE_VALIDATION_CALLS: &[&str] = &["validateContractCall", "validateContractCallAndMint"];
+
+/// Name fragments of helper functions and modifiers that validate their arguments.
+const VALIDATOR_NAME_HINTS: &[&str] = &["valid", "check", "verify", "trust", "allow", "auth"];
+
+#[derive(Debug, Default)]
+pub struct UnvalidatedCrossChainMessageDetector;
+
+impl Detector for UnvalidatedCrossChainMessageDetector {
+    fn id(&self) -> &'static str {
+        "unvalidated-cross-chain-message"
+    }
+
+    fn name(&self) -> &str {
+        "Cross-chain message handler does not validate the caller or the source"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::High
+    }
+
+    fn category(&self) -> Category {
+        Category::AccessControl
+    }
+
+    fn description(&self) -> &str {
+        "A receive-side handler of a messaging protocol (LayerZero `lzReceive`, CCIP \
+         `ccipReceive`, Axelar `execute`, Hyperlane `handle`, Wormhole \
+         `receiveWormholeMessages`) must check that `msg.sender` is the protocol's endpoint or \
+         router, and that the source chain and sending contract are trusted. Without the first \
+         check anyone can call the handler with a forged message; without the second, anyone can \
+         send a real message from an untrusted contract on any chain. Either way an attacker \
+         controls the payload the handler acts on, such as minting or releasing funds. Internal \
+         handlers (`_lzReceive`, `_ccipReceive`, ...) are only flagged for the checks that their \
+         usual base contract does not already perform."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad - anyone can call with any source
+function lzReceive(uint16 srcChainId, bytes calldata srcAddress, uint64, bytes calldata payload)
+    external
+{
+    _mint(abi.decode(payload, (address, uint256)));
+}
+
+// Good
+function lzReceive(uint16 srcChainId, bytes calldata srcAddress, uint64, bytes calldata payload)
+    external
+{
+    require(msg.sender == address(lzEndpoint), "invalid endpoint caller");
+    require(keccak256(srcAddress) == keccak256(trustedRemotes[srcChainId]), "untrusted source");
+    _mint(abi.decode(payload, (address, uint256)));
+}
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_contract(move |contract_def, file, context| {
+            let Some(contract_name) = contract_def.name.as_ref() else {
+                return Vec::new();
+            };
+
+            let qualified_name = context.get_qualified_name_for_contract(&contract_name.name);
+            let state_vars: HashSet<&str> = context
+                .get_all_state_variables(&qualified_name)
+                .into_iter()
+                .map(|v| v.name.as_str())
+                .collect();
+            let functions: HashSet<&str> = context
+                .get_all_functions(&qualified_name)
+                .into_iter()
+                .map(|f| f.name.as_str())
+                .collect();
+            let bases =
+                Self::base_names(contract_def, context.get_inheritance_chain(&qualified_name));
+
+            let mut findings = Vec::new();
+
+            for part in &contract_def.parts {
+                let ContractPart::FunctionDefinition(func_def) = part else {
+                    continue;
+                };
+                let Some(body) = &func_def.body else {
+                    continue;
+                };
+                let Some(handler) = Self::match_handler(func_def) else {
+                    continue;
+                };
+
+                let source_params: Vec<&str> = handler
+                    .source_params
+                    .iter()
+                    .filter_map(|&i| func_def.params.get(i)?.1.as_ref()?.name.as_ref())
+                    .map(|name| name.name.as_str())
+                    .collect();
+                let checks = Self::checks(body, file);
+
+                let inherited = |names: &[&str]| names.iter().any(|name| bases.contains(*name));
+                let caller_checked = inherited(handler.caller_checked_by)
+                    || Self::has_caller_check(func_def, &checks, file, &state_vars, &functions);
+                let source_checked = inherited(handler.source_checked_by)
+                    || Self::has_source_check(func_def, &checks, file, &source_params, &state_vars);
+
+                let missing = match (caller_checked, source_checked) {
+                    (true, true) => continue,
+                    (false, true) => "that `msg.sender` is the endpoint or router",
+                    (true, false) => "the source chain and sender against trusted values",
+                    (false, false) => {
+                        "that `msg.sender` is the endpoint or router, nor the source chain and \
+                         sender against trusted values"
+                    }
+                };
+                findings.push(FindingData {
+                    detector_id: self.id(),
+                    location: loc_to_location(&func_def.loc, file)
+                        .with_message(format!("`{}` does not check {}", handler.name, missing)),
+                });
+            }
+
+            findings
+        });
+    }
+}
+
+impl UnvalidatedCrossChainMessageDetector {
+    /// Names of the direct bases as written (resolved or not) and of every contract in the
+    /// resolved inheritance chain.
+    fn base_names(contract_def: &ContractDefinition, chain: Option<&[String]>) -> HashSet<String> {
+        let direct = contract_def
+            .base
+            .iter()
+            .filter_map(|base| base.name.identifiers.last())
+            .map(|id| id.name.clone());
+        let resolved = chain.unwrap_or_default().iter().map(|qualified| {
+            qualified
+                .rsplit(':')
+                .next()
+                .unwrap_or(qualified)
+                .to_string()
+        });
+        direct.chain(resolved).collect()
+    }
+
+    fn match_handler(func_def: &FunctionDefinition) -> Option<&'static MessageHandler> {
+        let name = func_def.name.as_ref()?.name.as_str();
+        let param_types: Vec<String> = func_def
+            .params
+            .iter()
+            .map(|(_, param)| {
+                param.as_ref().map_or_else(String::new, |param| {
+                    let ty = TypeInfo::from_expression(&param.ty).to_string();
+                    ty.rsplit('.').next().unwrap_or_default().to_string()
+                })
+            })
+            .collect();
+        MESSAGE_HANDLERS
+            .iter()
+            .find(|handler| handler.name == name && handler.params == param_types.as_slice())
+    }
+
+    /// Conditions of `if`, `require` and `assert`, and calls to validation helpers.
+    fn checks(body: &Statement, file: &SolidityFile) -> Vec<Expression> {
+        let mut checks = Vec::new();
+        find_statement_types(body, file, "", |stmt| {
+            if let Statement::If(_, cond, _, _) = stmt {
+                checks.push(cond.clone());
+            }
+            false
+        });
+
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            let Expression::FunctionCall(loc, func, args) = expr else {
+                return None;
+            };
+            match func.as_ref() {
+                Expression::Variable(id) if matches!(id.name.as_str(), "require" | "assert") => {
+                    checks.extend(args.first().cloned());
+                }
+                _ if Self::is_validator_call(expr) => checks.push(expr.clone()),
+                _ => return None,
+            }
+            Some(*loc)
+        };
+        let mut found = Vec::new();
+        find_locations_in_statement(body, file, &mut predicate, &mut found);
+        checks
+    }
+
+    /// `_validateSource(...)`, `gateway.validateContractCall(...)` and similar.
+    fn is_validator_call(expr: &Expression) -> bool {
+        let Expression::FunctionCall(_, func, _) = expr else {
+            return false;
+        };
+        let (Expression::Variable(id) | Expression::MemberAccess(_, _, id)) = func.as_ref() else {
+            return false;
+        };
+        let lower = id.name.to_lowercase();
+        VALIDATOR_NAME_HINTS.iter().any(|hint| lower.contains(hint))
+    }
+
+    /// `msg.sender` compared with a state variable or getter, an `only*` modifier, or a gateway
+    /// validation call.
+    fn has_caller_check(
+        func_def: &FunctionDefinition,
+        checks: &[Expression],
+        file: &SolidityFile,
+        state_vars: &HashSet<&str>,
+        functions: &HashSet<&str>,
+    ) -> bool {
+        let has_only_modifier = func_def.attributes.iter().any(|attr| match attr {
+            FunctionAttribute::BaseOrModifier(_, base) => base
+                .name
+                .identifiers
+                .last()
+                .is_some_and(|id| id.name.starts_with("only")),
+            _ => false,
+        });
+        if has_only_modifier {
+            return true;
+        }
+
+        let is_msg_sender = |expr: &Expression| {
+            matches!(expr, Expression::MemberAccess(_, base, member)
+                if member.name == "sender"
+                    && matches!(base.as_ref(), Expression::Variable(id) if id.name == "msg"))
+        };
+        let reads_contract_value = |expr: &Expression| {
+            let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+                match expr {
+                    Expression::Variable(id) if state_vars.contains(id.name.as_str()) => {
+                        Some(id.loc)
+                    }
+                    Expression::FunctionCall(loc, func, _) => match func.as_ref() {
+                        Expression::Variable(id) if functions.contains(id.name.as_str()) => {
+                            Some(*loc)
+                        }
+                        _ => None,
+                    },
+                    _ => None,
+                }
+            };
+            let mut found = Vec::new();
+            find_locations_in_expression(expr, file, &mut predicate, &mut found);
+            !found.is_empty()
+        };
+
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            match expr {
+                Expression::Equal(loc, left, right) | Expression::NotEqual(loc, left, right) => {
+                    let checked = (is_msg_sender(left) && reads_contract_value(right))
+                        || (is_msg_sender(right) && reads_contract_value(left));
+                    checked.then_some(*loc)
+                }
+                Expression::FunctionCall(loc, func, _) => match func.as_ref() {
+                    Expression::MemberAccess(_, _, id)
+                        if MESSAGE_VALIDATION_CALLS.contains(&id.name.as_str()) =>
+                    {
+                        Some(*loc)
+                    }
+                    _ => None,
+                },
+                _ => None,
+            }
+        };
+        checks.iter().any(|check| {
+            let mut found = Vec::new();
+            find_locations_in_expression(check, file, &mut predicate, &mut found);
+            !found.is_empty()
+        })
+    }
+
+    /// A check that reads a source parameter together with contract state (a trusted remote
+    /// lookup or comparison), or passes a source parameter to a validation helper or modifier.
+    fn has_source_check(
+        func_def: &FunctionDefinition,
+        checks: &[Expression],
+        file: &SolidityFile,
+        source_params: &[&str],
+        state_vars: &HashSet<&str>,
+    ) -> bool {
+        let reads = |expr: &Expression, names: &dyn Fn(&str) -> bool| {
+            let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+                match expr {
+                    Expression::Variable(id) if names(&id.name) => Some(id.loc),
+                    _ => None,
+                }
+            };
+            let mut found = Vec::new();
+            find_locations_in_expression(expr, file, &mut predicate, &mut found);
+            !found.is_empty()
+        };
+        let reads_source = |expr: &Expression| reads(expr, &|name| source_params.contains(&name));
+        let reads_state = |expr: &Expression| reads(expr, &|name| state_vars.contains(name));
+
+        let reads_source_in_modifier = func_def.attributes.iter().any(|attr| match attr {
+            FunctionAttribute::BaseOrModifier(_, base) => {
+                base.args.iter().flatten().any(reads_source)
+            }
+            _ => false,
+        });
+
+        reads_source_in_modifier
+            || checks.iter().any(|check| {
+                reads_source(check) && (reads_state(check) || Self::is_validator_call(check))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::run_detector_on_code;
+
+    #[test]
+    fn test_unvalidated_cross_chain_message() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            contract Bridge {
+                ILayerZeroEndpoint public lzEndpoint;
+                mapping(uint16 => bytes) public trustedRemotes;
+
+                function lzReceive(
+                    uint16 srcChainId,
+                    bytes calldata srcAddress,
+                    uint64 nonce,
+                    bytes calldata payload
+                ) external {
+                    require(msg.sender == address(lzEndpoint), "invalid endpoint caller");
+                    require(
+                        keccak256(srcAddress) == keccak256(trustedRemotes[srcChainId]),
+                        "untrusted source"
+                    );
+                    _credit(payload);
+                }
+
+                function _credit(bytes calldata payload) internal {}
+            }
+
+            contract OpenBridge {
+                function lzReceive(uint16, bytes calldata, uint64, bytes calldata payload)
+                    external
+                {
+                    _credit(payload);
+                }
+
+                function execute(address target, bytes calldata data) external {
+                    (bool ok, ) = target.call(data);
+                    require(ok);
+                }
+
+                function _credit(bytes calldata payload) internal {}
+            }
+
+            contract Receiver is CCIPReceiver {
+                mapping(uint64 => bool) public allowedChains;
+
+                function _ccipReceive(Client.Any2EVMMessage memory message) internal override {
+                    _credit(message.data);
+                }
+
+                function _credit(bytes memory data) internal {}
+            }
+
+            contract CheckedReceiver is CCIPReceiver {
+                mapping(uint64 => bool) public allowedChains;
+
+                function _ccipReceive(Client.Any2EVMMessage memory message) internal override {
+                    if (!allowedChains[message.sourceChainSelector]) revert();
+                }
+            }
+        "#;
+        let detector = Arc::new(UnvalidatedCrossChainMessageDetector::default());
+        let locations = run_detector_on_code(detector, code, "bridge.sol");
+
+        let lines: Vec<_> = locations.iter().map(|l| l.line).collect();
+        assert_eq!(lines, [26, 43]);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some(
+                "`lzReceive` does not check that `msg.sender` is the endpoint or router, nor the \
+                 source chain and sender against trusted values"
+            )
+        );
+        assert_eq!(
+            locations[1].message.as_deref(),
+            Some(
+                "`_ccipReceive` does not check the source chain and sender against trusted values"
+            )
+        );
+    }
+}
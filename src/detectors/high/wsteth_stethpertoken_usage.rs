@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::location::loc_to_location;
 use crate::{core::visitor::ASTVisitor, models::FindingData};
@@ -21,6 +22,10 @@ impl Detector for WstethStethPerTokenUsageDetector {
         Severity::High
     }
 
+    fn category(&self) -> Category {
+        Category::Oracle
+    }
+
     fn description(&self) -> &str {
         "The function `wstETH.stEthPerToken()` returns the amount of `stETH` per `wstETH`, not an ETH-equivalent value or rate. \
         Using this value directly in financial calculations assuming it represents ETH, or combining it incorrectly with ETH/USD price feeds, \
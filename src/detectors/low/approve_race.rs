@@ -0,0 +1,298 @@
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::category::Category;
+use crate::models::severity::Severity;
+use crate::models::{FindingData, SolidityFile};
+use crate::utils::ast_utils::{
+    expressions_equal, find_locations_in_expression, find_locations_in_statement,
+};
+use crate::utils::location::loc_to_location;
+use solang_parser::pt::{ContractPart, Expression, Loc, Statement};
+use std::sync::Arc;
+
+/// Allowance functions that change an allowance relative to its current value
+const ALLOWANCE_FUNCTIONS: &[&str] = &[
+    "increaseAllowance",
+    "decreaseAllowance",
+    "safeIncreaseAllowance",
+    "safeDecreaseAllowance",
+    "forceApprove",
+];
+
+/// An `approve(spender, amount)` call on a token.
+struct ApproveCall {
+    token: Expression,
+    spender: Expression,
+    resets: bool,
+    loc: Loc,
+}
+
+#[derive(Debug, Default)]
+pub struct ApproveRaceDetector;
+
+impl Detector for ApproveRaceDetector {
+    fn id(&self) -> &'static str {
+        "approve-race"
+    }
+
+    fn name(&self) -> &str {
+        "`approve()` changes an allowance without resetting it to zero first"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Low
+    }
+
+    fn category(&self) -> Category {
+        Category::Token
+    }
+
+    fn description(&self) -> &str {
+        "Changing an ERC20 allowance from one non-zero value to another with `approve` lets the \
+         spender front-run the change and spend both the old and the new allowance. This \
+         reports `approve(spender, amount)` calls with an amount other than a literal 0 that \
+         are not preceded, in the same block, by `approve(spender, 0)` on the same token and \
+         spender. Contracts that use `increaseAllowance`, `decreaseAllowance` or SafeERC20's \
+         `forceApprove` are skipped, as they already manage allowances relative to their \
+         current value. `unsafe-approve` flags every `approve` call for tokens that revert on \
+         such changes; on a line both report, only this finding is kept."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad - the spender can spend the old allowance before this lands, then the new one
+token.approve(router, amount);
+
+// Good - reset first, or use SafeERC20.forceApprove
+token.approve(router, 0);
+token.approve(router, amount);
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_contract(move |contract_def, file, _context| {
+            let bodies: Vec<&Statement> = contract_def
+                .parts
+                .iter()
+                .filter_map(|part| match part {
+                    ContractPart::FunctionDefinition(f) => f.body.as_ref(),
+                    _ => None,
+                })
+                .collect();
+
+            if bodies
+                .iter()
+                .any(|body| Self::uses_allowance_functions(body, file))
+            {
+                return Vec::new();
+            }
+
+            let mut locs = Vec::new();
+            for body in bodies {
+                Self::check_statement(body, file, &mut Vec::new(), &mut locs);
+            }
+            locs.into_iter()
+                .map(|loc| FindingData {
+                    detector_id: self.id(),
+                    location: loc_to_location(&loc, file),
+                })
+                .collect()
+        });
+    }
+}
+
+impl ApproveRaceDetector {
+    fn uses_allowance_functions(body: &Statement, file: &SolidityFile) -> bool {
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            if let Expression::FunctionCall(loc, callee, _) = expr {
+                if let Expression::MemberAccess(_, _, member) = callee.as_ref() {
+                    if ALLOWANCE_FUNCTIONS.contains(&member.name.as_str()) {
+                        return Some(*loc);
+                    }
+                }
+            }
+            None
+        };
+        let mut found = Vec::new();
+        find_locations_in_statement(body, file, &mut predicate, &mut found);
+        !found.is_empty()
+    }
+
+    /// Walk `stmt` in order, recording `approve(spender, 0)` resets. Resets made in a nested
+    /// block only cover the rest of that block.
+    fn check_statement(
+        stmt: &Statement,
+        file: &SolidityFile,
+        resets: &mut Vec<(Expression, Expression)>,
+        locs: &mut Vec<Loc>,
+    ) {
+        let outer = resets.len();
+        match stmt {
+            Statement::Block { statements, .. } => {
+                for stmt in statements {
+                    Self::check_statement(stmt, file, resets, locs);
+                }
+            }
+            Statement::If(_, cond, then_stmt, else_stmt) => {
+                Self::check_calls(Self::expression_calls(cond, file), resets, locs);
+                Self::check_statement(then_stmt, file, resets, locs);
+                resets.truncate(outer);
+                if let Some(else_stmt) = else_stmt {
+                    Self::check_statement(else_stmt, file, resets, locs);
+                }
+            }
+            Statement::While(_, cond, body) | Statement::DoWhile(_, body, cond) => {
+                Self::check_calls(Self::expression_calls(cond, file), resets, locs);
+                Self::check_statement(body, file, resets, locs);
+            }
+            Statement::For(_, init, cond, update, body) => {
+                if let Some(init) = init {
+                    Self::check_statement(init, file, resets, locs);
+                }
+                for expr in [cond, update].into_iter().flatten() {
+                    Self::check_calls(Self::expression_calls(expr, file), resets, locs);
+                }
+                if let Some(body) = body {
+                    Self::check_statement(body, file, resets, locs);
+                }
+            }
+            _ => {
+                let mut calls = Vec::new();
+                find_locations_in_statement(
+                    stmt,
+                    file,
+                    &mut Self::approve_predicate(&mut calls),
+                    &mut Vec::new(),
+                );
+                // Flat statements keep their resets for the statements after them
+                Self::check_calls(calls, resets, locs);
+                return;
+            }
+        }
+        resets.truncate(outer);
+    }
+
+    fn expression_calls(expr: &Expression, file: &SolidityFile) -> Vec<ApproveCall> {
+        let mut calls = Vec::new();
+        find_locations_in_expression(
+            expr,
+            file,
+            &mut Self::approve_predicate(&mut calls),
+            &mut Vec::new(),
+        );
+        calls
+    }
+
+    fn approve_predicate(
+        calls: &mut Vec<ApproveCall>,
+    ) -> impl FnMut(&Expression, &SolidityFile) -> Option<Loc> + '_ {
+        |expr, _| {
+            if let Expression::FunctionCall(loc, callee, args) = expr {
+                if let (Expression::MemberAccess(_, token, member), [spender, amount]) =
+                    (callee.as_ref(), args.as_slice())
+                {
+                    if member.name == "approve" {
+                        calls.push(ApproveCall {
+                            token: token.as_ref().clone(),
+                            spender: spender.clone(),
+                            resets: matches!(amount, Expression::NumberLiteral(_, value, exp, _)
+                                if value == "0" && exp.is_empty()),
+                            loc: *loc,
+                        });
+                    }
+                }
+            }
+            None
+        }
+    }
+
+    fn check_calls(
+        calls: Vec<ApproveCall>,
+        resets: &mut Vec<(Expression, Expression)>,
+        locs: &mut Vec<Loc>,
+    ) {
+        for call in calls {
+            if call.resets {
+                resets.push((call.token, call.spender));
+            } else if !resets.iter().any(|(token, spender)| {
+                expressions_equal(token, &call.token) && expressions_equal(spender, &call.spender)
+            }) {
+                locs.push(call.loc);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::run_detector_on_code;
+
+    #[test]
+    fn test_detects_approve_without_reset() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            contract Zap {
+                IERC20 token;
+
+                function direct(address router, uint256 amount) external {
+                    token.approve(router, amount);
+                }
+
+                function reset(address router, uint256 amount) external {
+                    token.approve(router, 0);
+                    token.approve(router, amount);
+                }
+
+                function resetOther(address router, address other) external {
+                    IERC20(token).approve(other, 0);
+                    require(IERC20(token).approve(router, 100));
+                }
+
+                function branch(address router, uint256 amount, bool flag) external {
+                    if (flag) {
+                        token.approve(router, 0);
+                    }
+                    token.approve(router, amount);
+                }
+
+                function revoke(address router) external {
+                    token.approve(router, 0);
+                }
+            }
+        "#;
+        let detector = Arc::new(ApproveRaceDetector::default());
+        let locations = run_detector_on_code(detector, code, "test.sol");
+        assert_eq!(locations.len(), 3);
+        assert_eq!(locations[0].line, 8, "approve without reset");
+        assert_eq!(locations[1].line, 18, "reset for another spender");
+        assert_eq!(locations[2].line, 25, "reset only in a branch");
+    }
+
+    #[test]
+    fn test_skips_contracts_using_relative_allowances() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            contract Vault {
+                using SafeERC20 for IERC20;
+                IERC20 token;
+
+                function setUp(address router, uint256 amount) external {
+                    token.approve(router, amount);
+                }
+
+                function grant(address router, uint256 amount) external {
+                    token.forceApprove(router, amount);
+                }
+            }
+        "#;
+        let detector = Arc::new(ApproveRaceDetector::default());
+        let locations = run_detector_on_code(detector, code, "test.sol");
+        assert_eq!(locations.len(), 0);
+    }
+}
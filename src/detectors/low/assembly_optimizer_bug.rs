@@ -1,5 +1,6 @@
 use crate::core::visitor::ASTVisitor;
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::models::FindingData;
 use crate::utils::location::loc_to_location;
@@ -24,6 +25,10 @@ impl Detector for AssemblyOptimizerBugDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::Compatibility
+    }
+
     fn description(&self) -> &str {
         "In Solidity versions 0.8.13 and 0.8.14, there is an optimizer bug where, if the use of \
          a variable is in a separate assembly block from the block in which it was stored, the \
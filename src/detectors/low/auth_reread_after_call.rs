@@ -0,0 +1,330 @@
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::category::Category;
+use crate::models::finding::Location;
+use crate::models::severity::Severity;
+use crate::models::{FindingData, SolidityFile};
+use crate::utils::ast_utils::{
+    find_locations_in_expression, find_locations_in_statement, find_variable_uses,
+    get_local_variable_names, is_external_call,
+};
+use solang_parser::pt::{ContractPart, Expression, Loc, Statement};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Name fragments that mark a state variable as access-control state.
+const AUTH_NAME_PARTS: &[&str] = &["owner", "admin", "role", "guardian", "governor", "auth"];
+
+#[derive(Debug, Default)]
+pub struct AuthRereadAfterCallDetector;
+
+impl Detector for AuthRereadAfterCallDetector {
+    fn id(&self) -> &'static str {
+        "auth-reread-after-call"
+    }
+
+    fn name(&self) -> &str {
+        "Access control state re-read after an external call"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Low
+    }
+
+    fn category(&self) -> Category {
+        Category::AccessControl
+    }
+
+    fn description(&self) -> &str {
+        "The function checks an access-control state variable (owner, admin, roles), makes an \
+         external call, and then reads the same storage variable again for a second decision. If \
+         the call re-enters the contract or a delegatecalled module changes the value, the two \
+         reads disagree and the function acts on an authorization that was never checked. Read the \
+         value once into a local variable and use it for every decision in the function."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad - `owner` may have changed during the external call
+function withdraw(address to) external {
+    require(msg.sender == owner);
+    vault.withdrawAll(to);
+    if (to == owner) _sendBonus(to);
+}
+
+// Good - a single cached read drives both decisions
+function withdraw(address to) external {
+    address currentOwner = owner;
+    require(msg.sender == currentOwner);
+    vault.withdrawAll(to);
+    if (to == currentOwner) _sendBonus(to);
+}
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_contract(move |contract_def, file, context| {
+            let Some(contract_name) = contract_def.name.as_ref() else {
+                return Vec::new();
+            };
+
+            let qualified_name = context.get_qualified_name_for_contract(&contract_name.name);
+            let auth_vars: Vec<&str> = context
+                .get_all_state_variables(&qualified_name)
+                .into_iter()
+                .map(|v| v.name.as_str())
+                .filter(|name| Self::is_auth_name(name))
+                .collect();
+
+            if auth_vars.is_empty() {
+                return Vec::new();
+            }
+
+            let mut findings = Vec::new();
+
+            for part in &contract_def.parts {
+                let ContractPart::FunctionDefinition(func_def) = part else {
+                    continue;
+                };
+                let Some(body) = &func_def.body else {
+                    continue;
+                };
+
+                let mut external_calls = Vec::new();
+                let mut call_predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+                    match expr {
+                        Expression::FunctionCall(loc, _, _) if is_external_call(expr) => Some(*loc),
+                        _ => None,
+                    }
+                };
+                find_locations_in_statement(body, file, &mut call_predicate, &mut external_calls);
+                if external_calls.is_empty() {
+                    continue;
+                }
+
+                let local_vars = get_local_variable_names(func_def, body);
+                let mut conditions = Vec::new();
+                Self::collect_conditions(body, &mut conditions);
+
+                for var in &auth_vars {
+                    if local_vars.contains(*var) {
+                        continue;
+                    }
+                    if let Some(reread) =
+                        Self::find_reread(var, body, file, &conditions, &external_calls)
+                    {
+                        findings.push(FindingData {
+                            detector_id: self.id(),
+                            location: reread,
+                        });
+                    }
+                }
+            }
+
+            findings
+        });
+    }
+}
+
+impl AuthRereadAfterCallDetector {
+    fn is_auth_name(name: &str) -> bool {
+        let lower = name.to_lowercase();
+        AUTH_NAME_PARTS.iter().any(|part| lower.contains(part))
+    }
+
+    fn position(location: &Location) -> (usize, usize) {
+        (location.line, location.column.unwrap_or(0))
+    }
+
+    /// Find the first storage read of `var` that follows an external call made after `var`
+    /// (or a local copy of it) was checked.
+    fn find_reread(
+        var: &str,
+        body: &Statement,
+        file: &SolidityFile,
+        conditions: &[&Expression],
+        external_calls: &[Location],
+    ) -> Option<Location> {
+        let mut aliases = HashSet::from([var.to_string()]);
+        Self::collect_aliases(body, var, &mut aliases);
+
+        let mut checks = Vec::new();
+        for condition in conditions {
+            let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+                match expr {
+                    Expression::Variable(id) if aliases.contains(&id.name) => Some(id.loc),
+                    _ => None,
+                }
+            };
+            find_locations_in_expression(condition, file, &mut predicate, &mut checks);
+        }
+        let first_check = checks.iter().map(Self::position).min()?;
+
+        let call_after_check = external_calls
+            .iter()
+            .map(Self::position)
+            .filter(|pos| *pos > first_check)
+            .min()?;
+
+        let writes = Self::collect_writes(body, var, file);
+        let reread = find_variable_uses(var, body, file)
+            .into_iter()
+            .filter(|loc| !writes.contains(&Self::position(loc)))
+            .find(|loc| Self::position(loc) > call_after_check)?;
+
+        let message = format!(
+            "`{}` was checked on line {} and is read again after the external call on line {}",
+            var, first_check.0, call_after_check.0
+        );
+        Some(reread.with_message(message))
+    }
+
+    /// Collect `require`/`assert` arguments and `if`/loop conditions in the function body.
+    fn collect_conditions<'a>(stmt: &'a Statement, conditions: &mut Vec<&'a Expression>) {
+        match stmt {
+            Statement::Block { statements, .. } => {
+                for s in statements {
+                    Self::collect_conditions(s, conditions);
+                }
+            }
+            Statement::Expression(_, Expression::FunctionCall(_, func, args)) => {
+                if let Expression::Variable(id) = func.as_ref() {
+                    if id.name == "require" || id.name == "assert" {
+                        conditions.extend(args.first());
+                    }
+                }
+            }
+            Statement::If(_, condition, then_stmt, else_stmt) => {
+                conditions.push(condition);
+                Self::collect_conditions(then_stmt, conditions);
+                if let Some(else_stmt) = else_stmt {
+                    Self::collect_conditions(else_stmt, conditions);
+                }
+            }
+            Statement::While(_, condition, body) | Statement::DoWhile(_, body, condition) => {
+                conditions.push(condition);
+                Self::collect_conditions(body, conditions);
+            }
+            Statement::For(_, _, condition, _, body) => {
+                conditions.extend(condition.as_deref());
+                if let Some(body) = body {
+                    Self::collect_conditions(body, conditions);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Local variables initialized directly from `var`, e.g. `address current = owner;`
+    fn collect_aliases(stmt: &Statement, var: &str, aliases: &mut HashSet<String>) {
+        match stmt {
+            Statement::Block { statements, .. } => {
+                for s in statements {
+                    Self::collect_aliases(s, var, aliases);
+                }
+            }
+            Statement::VariableDefinition(_, decl, Some(Expression::Variable(id)))
+                if id.name == var =>
+            {
+                if let Some(name) = &decl.name {
+                    aliases.insert(name.name.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Positions where `var` (or an element of it) is the target of an assignment.
+    fn collect_writes(body: &Statement, var: &str, file: &SolidityFile) -> HashSet<(usize, usize)> {
+        let mut writes = Vec::new();
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            let (Expression::Assign(_, target, _) | Expression::Delete(_, target)) = expr else {
+                return None;
+            };
+            let mut base = target.as_ref();
+            while let Expression::ArraySubscript(_, inner, _)
+            | Expression::MemberAccess(_, inner, _) = base
+            {
+                base = inner.as_ref();
+            }
+            match base {
+                Expression::Variable(id) if id.name == var => Some(id.loc),
+                _ => None,
+            }
+        };
+        find_locations_in_statement(body, file, &mut predicate, &mut writes);
+        writes.iter().map(Self::position).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::run_detector_on_code;
+
+    #[test]
+    fn test_auth_reread_after_call() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            contract Treasury {
+                address public owner;
+                mapping(address => bool) public admins;
+                IVault vault;
+
+                function withdraw(address to) external {
+                    require(msg.sender == owner, "not owner");
+                    vault.withdrawAll(to);
+                    if (to == owner) {                           // Positive
+                        _bonus(to);
+                    }
+                }
+
+                function sweep(address to) external {
+                    require(admins[msg.sender]);
+                    (bool ok, ) = to.call("");
+                    if (admins[to]) {                            // Positive
+                        _bonus(to);
+                    }
+                }
+
+                function cached(address to) external {
+                    address currentOwner = owner;
+                    require(msg.sender == currentOwner);
+                    vault.withdrawAll(to);
+                    if (to == currentOwner) _bonus(to);          // Negative: local copy
+                }
+
+                function noCallBetween(address to) external {
+                    require(msg.sender == owner);
+                    if (to == owner) _bonus(to);                 // Negative: no call in between
+                    vault.withdrawAll(to);
+                }
+
+                function transferOwnership(address to) external {
+                    require(msg.sender == owner);
+                    vault.withdrawAll(to);
+                    owner = to;                                  // Negative: write, not read
+                }
+
+                function _bonus(address to) internal {}
+            }
+        "#;
+
+        let detector = Arc::new(AuthRereadAfterCallDetector::default());
+        let locations = run_detector_on_code(detector, code, "treasury.sol");
+
+        assert_eq!(locations.len(), 2, "Should detect 2 re-reads");
+        assert_eq!(locations[0].line, 12);
+        assert_eq!(locations[0].snippet.as_deref(), Some("owner"));
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some("`owner` was checked on line 10 and is read again after the external call on line 11")
+        );
+        assert_eq!(locations[1].line, 20);
+        assert_eq!(locations[1].snippet.as_deref(), Some("admins"));
+    }
+}
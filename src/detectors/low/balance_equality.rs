@@ -0,0 +1,180 @@
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::category::Category;
+use crate::models::severity::Severity;
+use crate::models::{StructInfo, TypeInfo};
+use crate::utils::ast_utils::{
+    build_variable_type_map, find_in_statement, resolve_assignment_target_type,
+};
+use solang_parser::pt::{ContractPart, Expression, Type};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[derive(Debug, Default)]
+pub struct BalanceEqualityDetector;
+
+impl Detector for BalanceEqualityDetector {
+    fn id(&self) -> &'static str {
+        "balance-equality"
+    }
+
+    fn name(&self) -> &str {
+        "Strict equality check on an address's ether balance"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Low
+    }
+
+    fn category(&self) -> Category {
+        Category::Validation
+    }
+
+    fn description(&self) -> &str {
+        "Comparing `address.balance` with `==` or `!=` assumes the balance only changes through \
+         the contract's own logic. Anyone can force ether into an address with `selfdestruct` or \
+         as a block reward recipient, making an exact-balance `require` revert forever or an \
+         equality branch unreachable. Use `>=`/`<=`, or track deposits in a state variable."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad - 1 wei force-sent to the contract breaks this forever
+require(address(this).balance == totalDeposits, "accounting mismatch");
+
+// Good
+require(address(this).balance >= totalDeposits, "accounting mismatch");
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_contract(move |contract_def, file, context| {
+            let Some(contract_name) = contract_def.name.as_ref() else {
+                return Vec::new();
+            };
+
+            let qualified_name = context.get_qualified_name_for_contract(&contract_name.name);
+            let state_var_types: HashMap<String, TypeInfo> = context
+                .get_all_state_variables(&qualified_name)
+                .into_iter()
+                .map(|v| (v.name.clone(), v.type_info.clone()))
+                .collect();
+            let mut structs = context.get_all_structs(&qualified_name);
+            structs.extend(file.structs.iter());
+
+            let mut findings = Vec::new();
+
+            for part in &contract_def.parts {
+                let ContractPart::FunctionDefinition(func_def) = part else {
+                    continue;
+                };
+                let Some(body) = &func_def.body else {
+                    continue;
+                };
+
+                let var_types = build_variable_type_map(func_def, body, &state_var_types);
+                findings.extend(find_in_statement(body, file, self.id(), |expr| {
+                    let (Expression::Equal(_, left, right) | Expression::NotEqual(_, left, right)) =
+                        expr
+                    else {
+                        return false;
+                    };
+                    Self::is_address_balance(left, &var_types, &structs)
+                        || Self::is_address_balance(right, &var_types, &structs)
+                }));
+            }
+
+            findings
+        });
+    }
+}
+
+impl BalanceEqualityDetector {
+    /// `x.balance` where `x` is an address expression.
+    fn is_address_balance(
+        expr: &Expression,
+        var_types: &HashMap<String, TypeInfo>,
+        structs: &[&StructInfo],
+    ) -> bool {
+        match expr {
+            Expression::Parenthesis(_, inner) => {
+                Self::is_address_balance(inner, var_types, structs)
+            }
+            Expression::MemberAccess(_, base, member) if member.name == "balance" => {
+                Self::is_address(base, var_types, structs)
+            }
+            _ => false,
+        }
+    }
+
+    fn is_address(
+        expr: &Expression,
+        var_types: &HashMap<String, TypeInfo>,
+        structs: &[&StructInfo],
+    ) -> bool {
+        match expr {
+            Expression::Parenthesis(_, inner) => Self::is_address(inner, var_types, structs),
+            // address(x), payable(x)
+            Expression::FunctionCall(_, func, _) => matches!(
+                func.as_ref(),
+                Expression::Type(_, Type::Address | Type::AddressPayable | Type::Payable)
+            ),
+            // msg.sender, tx.origin, block.coinbase
+            Expression::MemberAccess(_, base, member) => {
+                let is_builtin = match base.as_ref() {
+                    Expression::Variable(id) => matches!(
+                        (id.name.as_str(), member.name.as_str()),
+                        ("msg", "sender") | ("tx", "origin") | ("block", "coinbase")
+                    ),
+                    _ => false,
+                };
+                is_builtin
+                    || resolve_assignment_target_type(expr, var_types, structs)
+                        .is_some_and(|ty| ty.is_address())
+            }
+            _ => resolve_assignment_target_type(expr, var_types, structs)
+                .is_some_and(|ty| ty.is_address()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::run_detector_on_code;
+
+    #[test]
+    fn test_balance_equality() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            contract Escrow {
+                struct Account { uint256 balance; address owner; }
+
+                uint256 totalDeposits;
+                address payable treasury;
+                mapping(address => Account) accounts;
+
+                function settle() external {
+                    require(address(this).balance == totalDeposits);    // Positive
+                    require(address(this).balance >= totalDeposits);    // Negative: inequality
+                    if (treasury.balance != 0) {                        // Positive
+                        return;
+                    }
+                    Account memory account = accounts[msg.sender];
+                    require(account.balance == totalDeposits);          // Negative: struct field
+                    require(accounts[msg.sender].owner.balance == 0);   // Positive
+                    require(msg.sender.balance <= 1 ether);             // Negative: inequality
+                }
+            }
+        "#;
+        let detector = Arc::new(BalanceEqualityDetector::default());
+        let locations = run_detector_on_code(detector, code, "escrow.sol");
+
+        let lines: Vec<_> = locations.iter().map(|l| l.line).collect();
+        assert_eq!(lines, [12, 14, 19]);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::location::loc_to_location;
 use crate::{core::visitor::ASTVisitor, models::FindingData};
@@ -21,6 +22,10 @@ impl Detector for BlockTimestampDeadlineDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::Cryptography
+    }
+
     fn description(&self) -> &str {
         "According to EIP-2612, signatures used on exactly the deadline timestamp are supposed to be allowed. \
          While the signature may or may not be used for the exact EIP-2612 use case (transfer approvals), \
@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::ast_utils::find_in_statement;
 use crate::core::visitor::ASTVisitor;
@@ -21,6 +22,10 @@ impl Detector for ConstantDecimalsDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::Token
+    }
+
     fn description(&self) -> &str {
         "The use of fixed decimal values such as `1e18` or `10 ** 18` in Solidity contracts can \
          lead to bugs and vulnerabilities when interacting with tokens having different decimal \
@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::location::loc_to_location;
 use crate::{core::visitor::ASTVisitor, models::FindingData};
@@ -21,6 +22,10 @@ impl Detector for CurveCalcTokenAmountDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::Validation
+    }
+
     fn description(&self) -> &str {
         "According to the Curve documentation (https://curve.readthedocs.io/_/downloads/en/latest/pdf/), \
          `StableSwap.calc_token_amount()` already includes slippage but not fees, so adding extra slippage \
@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::location::loc_to_location;
 use crate::{core::visitor::ASTVisitor, models::FindingData};
@@ -21,6 +22,10 @@ impl Detector for DecimalsTypeDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::Token
+    }
+
     fn description(&self) -> &str {
         "The `decimals` function or variable should return or be of type `uint8` according to the \
          ERC-20 standard, not `uint256` or other unsigned integer types."
@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::location::loc_to_location;
 use crate::{core::visitor::ASTVisitor, models::FindingData};
@@ -21,6 +22,10 @@ impl Detector for DeprecatedAbiEncoderV2Detector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::Compatibility
+    }
+
     fn description(&self) -> &str {
         "Use `pragma abicoder v2` instead of the deprecated `pragma experimental ABIEncoderV2`. \
          The experimental keyword is no longer needed as of Solidity 0.8.0. See: \
@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::ast_utils::find_in_statement;
 use crate::core::visitor::ASTVisitor;
@@ -21,6 +22,10 @@ impl Detector for DeprecatedApproveDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::Token
+    }
+
     fn description(&self) -> &str {
         "Due to the inheritance of ERC20's approve function, there's a vulnerability to the ERC20 \
          approve and double spend front running attack. Briefly, an authorized spender could spend \
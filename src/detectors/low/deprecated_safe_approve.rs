@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::ast_utils::find_in_statement;
 use crate::core::visitor::ASTVisitor;
@@ -21,6 +22,10 @@ impl Detector for DeprecatedSafeApproveDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::Token
+    }
+
     fn description(&self) -> &str {
         "OpenZeppelin's `safeApprove()` function has been deprecated in favor of `safeIncreaseAllowance()` \
          and `safeDecreaseAllowance()`. The `safeApprove()` function requires that the allowance is either \
@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::ast_utils::find_in_statement;
 use crate::core::visitor::ASTVisitor;
@@ -21,6 +22,10 @@ impl Detector for DeprecatedSetupRoleDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::AccessControl
+    }
+
     fn description(&self) -> &str {
         "The `_setupRole` function in OpenZeppelin's AccessControl has been deprecated in favor of \
          `_grantRole`. Since both functions are internal and `_setupRole` just calls `_grantRole`, \
@@ -0,0 +1,210 @@
+use crate::core::context::{AnalysisContext, UsingFunction};
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::category::Category;
+use crate::models::severity::Severity;
+use crate::models::{FindingData, FunctionMutability, SolidityFile};
+use crate::utils::ast_utils::find_statement_types;
+use crate::utils::location::loc_to_location;
+use solang_parser::pt::{CodeLocation, ContractPart, Expression, Loc, Statement};
+use std::sync::Arc;
+
+#[derive(Debug, Default)]
+pub struct DiscardedLibraryReturnDetector;
+
+impl Detector for DiscardedLibraryReturnDetector {
+    fn id(&self) -> &'static str {
+        "discarded-library-return"
+    }
+
+    fn name(&self) -> &str {
+        "Result of a `using ... for` library call is discarded"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Low
+    }
+
+    fn category(&self) -> Category {
+        Category::Validation
+    }
+
+    fn description(&self) -> &str {
+        "A function attached with `using ... for` is called as a statement, `amount.applyFee(fee);`, \
+         but it is a `pure`/`view` function that takes its first argument by value and returns \
+         the result. The call reads like it updates `amount` in place, yet it changes nothing and \
+         the result is thrown away. Assign the return value. Library functions taking a `storage` \
+         reference, like `EnumerableSet.add`, really modify their argument and are not reported."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+library FeeMath {
+    function applyFee(uint256 amount, uint256 bps) internal pure returns (uint256) {
+        return amount - amount * bps / 10_000;
+    }
+}
+
+// Bad - amount is unchanged
+amount.applyFee(feeBps);
+token.transfer(to, amount);
+
+// Good
+amount = amount.applyFee(feeBps);
+token.transfer(to, amount);
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_contract(move |contract_def, file, context| {
+            let Some(contract_name) = &contract_def.name else {
+                return Vec::new();
+            };
+            let qualified_name = context.get_qualified_name_for_contract(&contract_name.name);
+            if context.get_all_using_directives(&qualified_name).is_empty()
+                && file.using_directives.is_empty()
+            {
+                return Vec::new();
+            }
+
+            let mut findings = Vec::new();
+            for part in &contract_def.parts {
+                let ContractPart::FunctionDefinition(func_def) = part else {
+                    continue;
+                };
+                let Some(body) = &func_def.body else {
+                    continue;
+                };
+
+                let mut calls = Vec::new();
+                find_statement_types(body, file, self.id(), |stmt| {
+                    if let Statement::Expression(loc, Expression::FunctionCall(_, callee, args)) =
+                        stmt
+                    {
+                        if let Expression::MemberAccess(_, receiver, member) = callee.as_ref() {
+                            calls.push((*loc, receiver.loc(), member.name.clone(), args.len()));
+                        }
+                    }
+                    false
+                });
+
+                for (loc, receiver, member, arg_count) in calls {
+                    let Some(library) =
+                        Self::discarding_call(context, &qualified_name, &member, arg_count)
+                    else {
+                        continue;
+                    };
+                    let receiver_source = Self::source(receiver, file);
+                    findings.push(FindingData {
+                        detector_id: self.id(),
+                        location: loc_to_location(&loc, file).with_message(format!(
+                            "`{}.{}` returns a new value and leaves `{}` unchanged; its result is \
+                             discarded",
+                            library, member, receiver_source
+                        )),
+                    });
+                }
+            }
+            findings
+        });
+    }
+}
+
+impl DiscardedLibraryReturnDetector {
+    /// Name of the library (or `free function`) when a call to `member` with `arg_count`
+    /// arguments on a value resolves only to attached functions whose sole effect is their
+    /// return value. The arity check also leaves out direct `Lib.member(...)` calls.
+    fn discarding_call<'a>(
+        context: &'a AnalysisContext,
+        qualified_name: &str,
+        member: &str,
+        arg_count: usize,
+    ) -> Option<&'a str> {
+        let candidates: Vec<UsingFunction> = context
+            .resolve_using_function(qualified_name, member)
+            .into_iter()
+            .filter(|candidate| candidate.function.parameters.len() == arg_count + 1)
+            .collect();
+        if candidates.is_empty() {
+            return None;
+        }
+        let no_op = candidates.iter().all(|candidate| {
+            let function = candidate.function;
+            !function.return_parameters.is_empty()
+                && matches!(
+                    function.mutability,
+                    FunctionMutability::Pure | FunctionMutability::View
+                )
+                && function.parameters[0].storage_location.as_deref() != Some("storage")
+        });
+        no_op.then(|| candidates[0].library.unwrap_or("free function"))
+    }
+
+    fn source(loc: Loc, file: &SolidityFile) -> &str {
+        match loc {
+            Loc::File(_, start, end) => file.content.get(start..end).unwrap_or(""),
+            _ => "",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::run_detector_on_code;
+
+    #[test]
+    fn test_discarded_library_return() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            library FeeMath {
+                function applyFee(uint256 amount, uint256 bps) internal pure returns (uint256) {
+                    return amount - amount * bps / 10000;
+                }
+            }
+
+            library Queue {
+                struct Data {
+                    uint256[] items;
+                }
+
+                function enqueue(Data storage self, uint256 item) internal returns (uint256) {
+                    self.items.push(item);
+                    return self.items.length;
+                }
+            }
+
+            contract Vault {
+                using FeeMath for uint256;
+                using Queue for Queue.Data;
+
+                Queue.Data internal pending;
+                uint256 public fee = 30;
+
+                function deposit(uint256 amount) external returns (uint256) {
+                    amount.applyFee(fee);                       // Positive
+                    uint256 net = amount.applyFee(fee);         // Negative: assigned
+                    pending.enqueue(net);                       // Negative: storage reference
+                    FeeMath.applyFee(net, fee);                 // Negative: direct call, not attached
+                    return net;
+                }
+            }
+        "#;
+        let detector = Arc::new(DiscardedLibraryReturnDetector::default());
+        let locations = run_detector_on_code(detector, code, "vault.sol");
+
+        let lines: Vec<_> = locations.iter().map(|l| l.line).collect();
+        assert_eq!(lines, [29]);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some(
+                "`FeeMath.applyFee` returns a new value and leaves `amount` unchanged; its result \
+                 is discarded"
+            )
+        );
+    }
+}
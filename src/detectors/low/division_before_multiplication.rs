@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::ast_utils::find_in_statement;
 use crate::core::visitor::ASTVisitor;
@@ -21,6 +22,10 @@ impl Detector for DivisionBeforeMultiplicationDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::Arithmetic
+    }
+
     fn description(&self) -> &str {
         "Division operations can lead to a loss of precision as the fractional part is discarded. \
          When the result of such a division is then multiplied, this loss of precision can be magnified, \
@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::ast_utils::find_in_statement;
 use crate::core::visitor::ASTVisitor;
@@ -21,6 +22,10 @@ impl Detector for DivisionByZeroDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::Arithmetic
+    }
+
     fn description(&self) -> &str {
         "Division or modulo operations without zero-value checks on the divisor can cause runtime reverts. \
          The detector identifies divisions where the divisor is a variable that lacks validation (e.g., `require(b != 0)`, \
@@ -1,5 +1,6 @@
 use crate::core::visitor::ASTVisitor;
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::ast_utils::find_in_statement;
 use solang_parser::pt::Expression;
@@ -21,6 +22,10 @@ impl Detector for DivisionRoundingDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::Arithmetic
+    }
+
     fn description(&self) -> &str {
         "Division by large numbers (reserves, balances, supplies, totals, liquidity, stakes, \
          deposits, collateral, assets, TVL, debt, pool amounts) may result in the result being \
@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::ast_utils::find_in_statement;
 use crate::utils::location::loc_to_location;
@@ -22,6 +23,10 @@ impl Detector for DomainSeparatorReplayDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::Cryptography
+    }
+
     fn description(&self) -> &str {
         "The domain separator should be recalculated if the current `block.chainid` is not the cached chain ID \
          to protect against replay attacks in case of a future chain split. See EIP-2612 security considerations: \
@@ -1,5 +1,5 @@
 use crate::detectors::Detector;
-use crate::models::{severity::Severity, FindingData, Location};
+use crate::models::{category::Category, severity::Severity, FindingData, Location};
 use crate::core::visitor::ASTVisitor;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -20,6 +20,10 @@ impl Detector for DuplicateImportDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::CodeQuality
+    }
+
     fn description(&self) -> &str {
         "Multiple import statements import the same file. This is redundant and should be \
          consolidated into a single import statement to improve code clarity and reduce \
@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::location::loc_to_location;
 use crate::{core::visitor::ASTVisitor, models::FindingData};
@@ -21,6 +22,10 @@ impl Detector for EcrecoverMalleabilityDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::Cryptography
+    }
+
     fn description(&self) -> &str {
         "The built-in EVM precompile `ecrecover` is susceptible to signature malleability, \
          which could lead to replay attacks. References: https://swcregistry.io/docs/SWC-117, \
@@ -0,0 +1,349 @@
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::category::Category;
+use crate::models::severity::Severity;
+use crate::models::{FindingData, SolidityFile};
+use crate::utils::ast_utils::{find_locations_in_expression, find_locations_in_statement};
+use crate::utils::location::loc_to_location;
+use solang_parser::pt::{
+    Expression, Loc, Statement, YulExpression, YulStatement, YulSwitchOptions,
+};
+use std::sync::Arc;
+
+#[derive(Debug, Default)]
+pub struct EcrecoverVNormalizationDetector;
+
+impl Detector for EcrecoverVNormalizationDetector {
+    fn id(&self) -> &'static str {
+        "ecrecover-v-normalization"
+    }
+
+    fn name(&self) -> &str {
+        "`v` sliced from signature bytes reaches `ecrecover` without a 27/28 check"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Low
+    }
+
+    fn category(&self) -> Category {
+        Category::Cryptography
+    }
+
+    fn description(&self) -> &str {
+        "The recovery id `v` is read out of a packed signature (`sig[64]`, `abi.decode`, or \
+         `byte(0, mload(...))` in assembly) and passed to `ecrecover` without requiring it to be \
+         27 or 28. Signers and libraries disagree on encoding `v` as 0/1 or 27/28, so valid \
+         signatures fail to verify, and any other value makes `ecrecover` return `address(0)`, \
+         which matches an unset signer. Adding 27 only when `v < 27` still lets other values \
+         through. Normalize 0/1, then `require(v == 27 || v == 28)`, or use OpenZeppelin's \
+         `ECDSA.recover`."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad - v = 2 becomes 29, v = 29 passes as is
+uint8 v = uint8(signature[64]);
+if (v < 27) v += 27;
+address signer = ecrecover(hash, v, r, s);
+
+// Good
+uint8 v = uint8(signature[64]);
+if (v < 27) v += 27;
+require(v == 27 || v == 28, "invalid v");
+address signer = ecrecover(hash, v, r, s);
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_function(move |func_def, file, _context| {
+            let Some(body) = &func_def.body else {
+                return Vec::new();
+            };
+
+            let mut v_args = Vec::new();
+            let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+                let Expression::FunctionCall(loc, callee, args) = expr else {
+                    return None;
+                };
+                match (callee.as_ref(), args.get(1)) {
+                    (Expression::Variable(id), Some(v)) if id.name == "ecrecover" => {
+                        v_args.push((*loc, v.clone()));
+                        Some(*loc)
+                    }
+                    _ => None,
+                }
+            };
+            let mut found = Vec::new();
+            find_locations_in_statement(body, file, &mut predicate, &mut found);
+
+            let mut findings = Vec::new();
+            for (loc, v) in v_args {
+                let from_bytes = match &v {
+                    Expression::Variable(id) => Self::is_read_from_bytes(body, &id.name, file),
+                    other => Self::reads_bytes(other, file),
+                };
+                if !from_bytes {
+                    continue;
+                }
+                let v_name = match &v {
+                    Expression::Variable(id) => id.name.as_str(),
+                    _ => "",
+                };
+                if !v_name.is_empty() && Self::checks_27_28(body, v_name, file) {
+                    continue;
+                }
+
+                let message = if !v_name.is_empty() && Self::adds_27(body, v_name, file) {
+                    format!(
+                        "`{}` is shifted by 27 but values other than 0/1/27/28 still reach \
+                         `ecrecover`; require `{} == 27 || {} == 28` after normalizing",
+                        v_name, v_name, v_name
+                    )
+                } else {
+                    format!(
+                        "`{}` is read from signature bytes without checking it is 27 or 28",
+                        v
+                    )
+                };
+                findings.push(FindingData {
+                    detector_id: self.id(),
+                    location: loc_to_location(&loc, file).with_message(message),
+                });
+            }
+            findings
+        });
+    }
+}
+
+impl EcrecoverVNormalizationDetector {
+    /// Whether `v` is assigned from a byte slice or `abi.decode` of signature bytes, or in
+    /// assembly (which can only read it from memory or calldata).
+    fn is_read_from_bytes(body: &Statement, v: &str, file: &SolidityFile) -> bool {
+        let mut from_bytes = false;
+        Self::visit_statements(body, &mut |stmt| match stmt {
+            Statement::VariableDefinition(_, decl, Some(init))
+                if decl.name.as_ref().is_some_and(|n| n.name == v) =>
+            {
+                from_bytes |= Self::reads_bytes(init, file);
+            }
+            Statement::Expression(_, Expression::Assign(_, target, value))
+                if Self::assigns(target, v) =>
+            {
+                from_bytes |= Self::reads_bytes(value, file);
+            }
+            Statement::Assembly { block, .. } => {
+                from_bytes |= Self::yul_assigns(&block.statements, v);
+            }
+            _ => {}
+        });
+        from_bytes
+    }
+
+    /// `v = ...` or a tuple assignment/declaration with `v` among its targets.
+    fn assigns(target: &Expression, v: &str) -> bool {
+        match target {
+            Expression::Variable(id) => id.name == v,
+            Expression::List(_, params) => params.iter().any(|(_, param)| {
+                param.as_ref().is_some_and(|p| {
+                    p.name.as_ref().is_some_and(|n| n.name == v)
+                        || matches!(&p.ty, Expression::Variable(id) if id.name == v)
+                })
+            }),
+            _ => false,
+        }
+    }
+
+    /// An index into or slice of a byte array, or an `abi.decode` call.
+    fn reads_bytes(expr: &Expression, file: &SolidityFile) -> bool {
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            match expr {
+                Expression::ArraySubscript(loc, ..) | Expression::ArraySlice(loc, ..) => Some(*loc),
+                Expression::FunctionCall(loc, callee, _) => matches!(
+                    callee.as_ref(),
+                    Expression::MemberAccess(_, base, member)
+                        if member.name == "decode"
+                            && matches!(base.as_ref(), Expression::Variable(id) if id.name == "abi")
+                )
+                .then_some(*loc),
+                _ => None,
+            }
+        };
+        let mut found = Vec::new();
+        find_locations_in_expression(expr, file, &mut predicate, &mut found);
+        !found.is_empty()
+    }
+
+    fn yul_assigns(statements: &[YulStatement], v: &str) -> bool {
+        statements.iter().any(|stmt| match stmt {
+            YulStatement::Assign(_, targets, _) => targets
+                .iter()
+                .any(|t| matches!(t, YulExpression::Variable(id) if id.name == v)),
+            YulStatement::If(_, _, block) | YulStatement::Block(block) => {
+                Self::yul_assigns(&block.statements, v)
+            }
+            YulStatement::Switch(switch) => {
+                switch
+                    .cases
+                    .iter()
+                    .chain(&switch.default)
+                    .any(|option| match option {
+                        YulSwitchOptions::Case(_, _, block)
+                        | YulSwitchOptions::Default(_, block) => {
+                            Self::yul_assigns(&block.statements, v)
+                        }
+                    })
+            }
+            _ => false,
+        })
+    }
+
+    /// `v == 27`, `v != 28`, ... anywhere in the body, e.g. in a `require` or an `if` guarding
+    /// a revert.
+    fn checks_27_28(body: &Statement, v: &str, file: &SolidityFile) -> bool {
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            match expr {
+                Expression::Equal(loc, left, right) | Expression::NotEqual(loc, left, right) => {
+                    let compares = |a: &Expression, b: &Expression| {
+                        matches!(a, Expression::Variable(id) if id.name == v)
+                            && matches!(b, Expression::NumberLiteral(_, n, _, _) if n == "27" || n == "28")
+                    };
+                    (compares(left, right) || compares(right, left)).then_some(*loc)
+                }
+                _ => None,
+            }
+        };
+        let mut found = Vec::new();
+        find_locations_in_statement(body, file, &mut predicate, &mut found);
+        !found.is_empty()
+    }
+
+    /// `v += 27`, `v = v + 27` or `v + 27` in a ternary.
+    fn adds_27(body: &Statement, v: &str, file: &SolidityFile) -> bool {
+        let is_v = |e: &Expression| matches!(e, Expression::Variable(id) if id.name == v);
+        let is_27 =
+            |e: &Expression| matches!(e, Expression::NumberLiteral(_, n, _, _) if n == "27");
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            match expr {
+                Expression::AssignAdd(loc, target, value) if is_v(target) && is_27(value) => {
+                    Some(*loc)
+                }
+                Expression::Add(loc, left, right)
+                    if is_v(left) && is_27(right) || is_27(left) && is_v(right) =>
+                {
+                    Some(*loc)
+                }
+                _ => None,
+            }
+        };
+        let mut found = Vec::new();
+        find_locations_in_statement(body, file, &mut predicate, &mut found);
+        !found.is_empty()
+    }
+
+    fn visit_statements<F>(stmt: &Statement, callback: &mut F)
+    where
+        F: FnMut(&Statement),
+    {
+        callback(stmt);
+        match stmt {
+            Statement::Block { statements, .. } => {
+                for s in statements {
+                    Self::visit_statements(s, callback);
+                }
+            }
+            Statement::If(_, _, then_stmt, else_stmt) => {
+                Self::visit_statements(then_stmt, callback);
+                if let Some(else_stmt) = else_stmt {
+                    Self::visit_statements(else_stmt, callback);
+                }
+            }
+            Statement::For(_, init, _, _, body) => {
+                if let Some(init) = init {
+                    Self::visit_statements(init, callback);
+                }
+                if let Some(body) = body {
+                    Self::visit_statements(body, callback);
+                }
+            }
+            Statement::While(_, _, body) | Statement::DoWhile(_, body, _) => {
+                Self::visit_statements(body, callback);
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::run_detector_on_code;
+
+    #[test]
+    fn test_ecrecover_v_normalization() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            contract Verifier {
+                function checked(bytes32 hash, bytes calldata sig) external pure returns (address) {
+                    bytes32 r = bytes32(sig[0:32]);
+                    bytes32 s = bytes32(sig[32:64]);
+                    uint8 v = uint8(sig[64]);
+                    if (v < 27) v += 27;
+                    require(v == 27 || v == 28, "invalid v");
+                    return ecrecover(hash, v, r, s);                  // Negative: checked
+                }
+
+                function raw(bytes32 hash, bytes memory sig) external pure returns (address) {
+                    bytes32 r;
+                    bytes32 s;
+                    uint8 v;
+                    assembly {
+                        r := mload(add(sig, 32))
+                        s := mload(add(sig, 64))
+                        v := byte(0, mload(add(sig, 96)))
+                    }
+                    return ecrecover(hash, v, r, s);                  // Positive: assembly
+                }
+
+                function sliced(bytes32 hash, bytes calldata sig) external pure returns (address) {
+                    uint8 v = uint8(sig[64]);
+                    return ecrecover(hash, v, bytes32(sig[0:32]), bytes32(sig[32:64])); // Positive
+                }
+
+                function shifted(bytes32 hash, bytes memory sig) external pure returns (address) {
+                    (bytes32 r, bytes32 s, uint8 v) = abi.decode(sig, (bytes32, bytes32, uint8));
+                    if (v < 27) {
+                        v += 27;
+                    }
+                    return ecrecover(hash, v, r, s);                  // Positive: shift only
+                }
+
+                function params(bytes32 hash, uint8 v, bytes32 r, bytes32 s) external pure returns (address) {
+                    return ecrecover(hash, v, r, s);                  // Negative: not from bytes
+                }
+
+                function viaLibrary(bytes32 hash, bytes memory sig) external pure returns (address) {
+                    return ECDSA.recover(hash, sig);                  // Negative: no ecrecover
+                }
+            }
+        "#;
+        let detector = Arc::new(EcrecoverVNormalizationDetector::default());
+        let locations = run_detector_on_code(detector, code, "verifier.sol");
+
+        let lines: Vec<_> = locations.iter().map(|l| l.line).collect();
+        assert_eq!(lines, [23, 28, 36]);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some("`v` is read from signature bytes without checking it is 27 or 28")
+        );
+        assert!(locations[2]
+            .message
+            .as_deref()
+            .unwrap()
+            .starts_with("`v` is shifted by 27"));
+    }
+}
@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::location::loc_to_location;
 use crate::{core::visitor::ASTVisitor, models::FindingData};
@@ -21,6 +22,10 @@ impl Detector for EmptyEtherReceiverDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::Validation
+    }
+
     fn description(&self) -> &str {
         "If the intention is for the Ether to be used, the function should call another function, \
          otherwise it should revert (e.g. require(msg.sender == address(weth))). Having no access \
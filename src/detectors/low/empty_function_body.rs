@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::ast_utils::is_function_virtual;
 use crate::utils::location::loc_to_location;
@@ -22,6 +23,10 @@ impl Detector for EmptyFunctionBodyDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::CodeQuality
+    }
+
     fn description(&self) -> &str {
         "Functions with empty bodies should have comments explaining why they are empty. \
          Empty functions without documentation can indicate incomplete code or unclear intent. \
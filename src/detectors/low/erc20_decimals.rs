@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::location::loc_to_location;
 use crate::{core::visitor::ASTVisitor, models::FindingData};
@@ -21,6 +22,10 @@ impl Detector for Erc20DecimalsDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::Token
+    }
+
     fn description(&self) -> &str {
         "The `decimals()` function is not a part of the ERC-20 standard (https://eips.ethereum.org/EIPS/eip-20), \
          and was added later as an optional extension \
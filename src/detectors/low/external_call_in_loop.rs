@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::ast_utils::{find_in_statement, is_external_call};
 use crate::core::visitor::ASTVisitor;
@@ -21,6 +22,10 @@ impl Detector for ExternalCallInLoopDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::DenialOfService
+    }
+
     fn description(&self) -> &str {
         "Consider limiting the number of iterations in for-loops that make external calls. \
          External calls on array elements within unbounded loops can lead to denial-of-service \
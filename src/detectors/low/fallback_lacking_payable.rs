@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::location::loc_to_location;
 use crate::{core::visitor::ASTVisitor, models::FindingData};
@@ -21,6 +22,10 @@ impl Detector for FallbackLackingPayableDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::Validation
+    }
+
     fn description(&self) -> &str {
         "Fallback functions without the `payable` modifier cannot receive Ether sent directly \
          to the contract. If the fallback function is intended to receive Ether, add the `payable` \
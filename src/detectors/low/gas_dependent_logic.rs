@@ -0,0 +1,233 @@
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::category::Category;
+use crate::models::severity::Severity;
+use crate::models::{FindingData, SolidityFile};
+use crate::utils::ast_utils::{
+    find_locations_in_expression, find_locations_in_statement, find_statement_types,
+    is_function_readonly,
+};
+use solang_parser::pt::{Expression, Loc, Statement};
+use std::sync::Arc;
+
+#[derive(Debug, Default)]
+pub struct GasDependentLogicDetector;
+
+impl Detector for GasDependentLogicDetector {
+    fn id(&self) -> &'static str {
+        "gas-dependent-logic"
+    }
+
+    fn name(&self) -> &str {
+        "Economic or control-flow decisions based on `tx.gasprice` or `gasleft()`"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Low
+    }
+
+    fn category(&self) -> Category {
+        Category::Validation
+    }
+
+    fn description(&self) -> &str {
+        "`tx.gasprice` is chosen by the sender and `gasleft()` depends on the gas limit the caller \
+         provides, and both change with the gas schedule of the chain the contract runs on. Fees, \
+         refunds or limits computed from `tx.gasprice`, and branches of a state-changing function \
+         taken depending on `gasleft()`, can therefore be steered by the caller and behave \
+         differently after a repricing or on L2s. Forwarding a bounded amount of gas to a call \
+         (`{gas: gasleft() - reserve}`) is not affected and is not reported."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad - the caller picks the gas price and thus the fee
+uint256 fee = tx.gasprice * GAS_OVERHEAD;
+
+// Bad - the caller decides whether the update happens through the gas limit
+if (gasleft() < 50000) return;
+lastUpdate = block.timestamp;
+
+// Good - fee from a configured or oracle-provided price
+uint256 fee = gasPriceOracle.latest() * GAS_OVERHEAD;
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_function(move |func_def, file, _context| {
+            let Some(body) = &func_def.body else {
+                return Vec::new();
+            };
+            if is_function_readonly(func_def) {
+                return Vec::new();
+            }
+
+            let mut findings = Vec::new();
+
+            let mut gasprice_predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+                let (left, right) = Self::arithmetic_or_comparison(expr)?;
+                [left, right]
+                    .into_iter()
+                    .find_map(|operand| Self::tx_gasprice_loc(Self::strip_parentheses(operand)))
+            };
+            let mut gasprice_uses = Vec::new();
+            find_locations_in_statement(body, file, &mut gasprice_predicate, &mut gasprice_uses);
+            findings.extend(gasprice_uses.into_iter().map(|location| FindingData {
+                detector_id: self.id(),
+                location: location.with_message(
+                    "`tx.gasprice` is chosen by the sender; do not derive fees or limits from it",
+                ),
+            }));
+
+            // Only branch conditions: `gasleft()` in a call's gas option is legitimate
+            let mut conditions = Vec::new();
+            find_statement_types(body, file, self.id(), |stmt| {
+                match stmt {
+                    Statement::If(_, cond, _, _) | Statement::While(_, cond, _) => {
+                        conditions.push(cond.clone())
+                    }
+                    Statement::For(_, _, Some(cond), _, _) => {
+                        conditions.push(cond.as_ref().clone())
+                    }
+                    _ => {}
+                }
+                false
+            });
+
+            let mut gasleft_predicate = |expr: &Expression, file: &SolidityFile| -> Option<Loc> {
+                match expr {
+                    Expression::Less(loc, left, right)
+                    | Expression::More(loc, left, right)
+                    | Expression::LessEqual(loc, left, right)
+                    | Expression::MoreEqual(loc, left, right)
+                        if Self::contains_gasleft(left, file)
+                            || Self::contains_gasleft(right, file) =>
+                    {
+                        Some(*loc)
+                    }
+                    _ => None,
+                }
+            };
+            for cond in &conditions {
+                let mut gates = Vec::new();
+                find_locations_in_expression(cond, file, &mut gasleft_predicate, &mut gates);
+                findings.extend(gates.into_iter().map(|location| FindingData {
+                    detector_id: self.id(),
+                    location: location.with_message(
+                        "branch depends on `gasleft()`, which the caller controls through the gas \
+                         limit",
+                    ),
+                }));
+            }
+
+            findings
+        });
+    }
+}
+
+impl GasDependentLogicDetector {
+    fn arithmetic_or_comparison(expr: &Expression) -> Option<(&Expression, &Expression)> {
+        match expr {
+            Expression::Multiply(_, left, right)
+            | Expression::Divide(_, left, right)
+            | Expression::Modulo(_, left, right)
+            | Expression::Add(_, left, right)
+            | Expression::Subtract(_, left, right)
+            | Expression::Power(_, left, right)
+            | Expression::Less(_, left, right)
+            | Expression::More(_, left, right)
+            | Expression::LessEqual(_, left, right)
+            | Expression::MoreEqual(_, left, right)
+            | Expression::Equal(_, left, right)
+            | Expression::NotEqual(_, left, right) => Some((left, right)),
+            _ => None,
+        }
+    }
+
+    fn strip_parentheses(expr: &Expression) -> &Expression {
+        match expr {
+            Expression::Parenthesis(_, inner) => Self::strip_parentheses(inner),
+            _ => expr,
+        }
+    }
+
+    fn tx_gasprice_loc(expr: &Expression) -> Option<Loc> {
+        match expr {
+            Expression::MemberAccess(loc, base, member)
+                if member.name == "gasprice"
+                    && matches!(base.as_ref(), Expression::Variable(id) if id.name == "tx") =>
+            {
+                Some(*loc)
+            }
+            _ => None,
+        }
+    }
+
+    fn contains_gasleft(expr: &Expression, file: &SolidityFile) -> bool {
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            match expr {
+                Expression::FunctionCall(loc, func, args) if args.is_empty() => match func.as_ref()
+                {
+                    Expression::Variable(id) if id.name == "gasleft" => Some(*loc),
+                    _ => None,
+                },
+                _ => None,
+            }
+        };
+        let mut found = Vec::new();
+        find_locations_in_expression(expr, file, &mut predicate, &mut found);
+        !found.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::run_detector_on_code;
+
+    #[test]
+    fn test_gas_dependent_logic() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            contract Relayer {
+                uint256 constant OVERHEAD = 21000;
+                uint256 lastUpdate;
+                mapping(address => uint256) credits;
+
+                function relay(address target, bytes calldata data) external {
+                    uint256 fee = tx.gasprice * OVERHEAD;                       // Positive
+                    credits[msg.sender] -= fee;
+                    (bool ok, ) = target.call{gas: gasleft() - 5000}(data);     // Negative: forwarding
+                    require(ok);
+                }
+
+                function update() external {
+                    if (gasleft() < 50000) return;                              // Positive
+                    lastUpdate = block.timestamp;
+                }
+
+                function quote() external view returns (uint256) {
+                    return tx.gasprice * OVERHEAD;                              // Negative: view
+                }
+            }
+        "#;
+        let detector = Arc::new(GasDependentLogicDetector::default());
+        let locations = run_detector_on_code(detector, code, "relayer.sol");
+
+        assert_eq!(locations.len(), 2);
+        assert_eq!(locations[0].line, 10);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some("`tx.gasprice` is chosen by the sender; do not derive fees or limits from it")
+        );
+        assert_eq!(locations[1].line, 17);
+        assert_eq!(
+            locations[1].message.as_deref(),
+            Some("branch depends on `gasleft()`, which the caller controls through the gas limit")
+        );
+    }
+}
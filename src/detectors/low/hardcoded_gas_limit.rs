@@ -0,0 +1,143 @@
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::category::Category;
+use crate::models::severity::Severity;
+use crate::models::FindingData;
+use crate::utils::ast_utils::call_option;
+use crate::utils::location::loc_to_location;
+use solang_parser::pt::{CodeLocation, Expression};
+use std::sync::Arc;
+
+#[derive(Debug, Default)]
+pub struct HardcodedGasLimitDetector;
+
+impl Detector for HardcodedGasLimitDetector {
+    fn id(&self) -> &'static str {
+        "hardcoded-gas-limit"
+    }
+
+    fn name(&self) -> &str {
+        "External call with a hardcoded gas limit"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Low
+    }
+
+    fn category(&self) -> Category {
+        Category::Compatibility
+    }
+
+    fn description(&self) -> &str {
+        "A call sets its `gas:` option to a literal number. Gas costs of opcodes change with \
+         network upgrades (EIP-1884 and EIP-2929 repriced `SLOAD` and account access) and \
+         differ between chains, so a limit that works today can make the call fail later, or \
+         when the recipient is a contract wallet. Forward all gas and guard against reentrancy, \
+         or make the limit configurable. `.transfer` and `.send` are not reported here."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad - breaks if the recipient needs more than 2300 gas
+(bool ok, ) = recipient.call{value: amount, gas: 2300}("");
+
+// Good
+(bool ok, ) = recipient.call{value: amount}("");
+require(ok, "transfer failed");
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_expression(move |expr, file, _context| {
+            let Some(gas) = call_option(expr, "gas") else {
+                return Vec::new();
+            };
+            let Some(limit) = Self::literal_value(gas) else {
+                return Vec::new();
+            };
+
+            let message = match limit.as_str() {
+                "2300" => "gas limited to the 2300 stipend of `transfer`/`send`, which repricings \
+                           have already broken for contract recipients"
+                    .to_string(),
+                "5000" => "gas limited to 5000, which is below the cost of a single cold `SSTORE` \
+                           in the callee"
+                    .to_string(),
+                _ => format!(
+                    "gas limited to a hardcoded {}, which can become insufficient after gas \
+                     repricings",
+                    limit
+                ),
+            };
+            FindingData {
+                detector_id: self.id(),
+                location: loc_to_location(&expr.loc(), file).with_message(message),
+            }
+            .into()
+        });
+    }
+}
+
+impl HardcodedGasLimitDetector {
+    /// Digits of a decimal or hex literal, possibly parenthesized, with `_` separators removed.
+    fn literal_value(expr: &Expression) -> Option<String> {
+        match expr {
+            Expression::Parenthesis(_, inner) => Self::literal_value(inner),
+            Expression::NumberLiteral(_, value, exponent, None) => Some(if exponent.is_empty() {
+                value.replace('_', "")
+            } else {
+                format!("{}e{}", value.replace('_', ""), exponent)
+            }),
+            Expression::HexNumberLiteral(_, value, None) => Some(value.replace('_', "")),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::run_detector_on_code;
+
+    #[test]
+    fn test_hardcoded_gas_limit() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            contract Payout {
+                uint256 public callGas = 50_000;
+
+                function pay(address to, uint256 amount) external {
+                    (bool a, ) = to.call{value: amount, gas: 2300}("");       // Positive
+                    (bool b, ) = to.call{gas: 100_000}("");                    // Positive
+                    (bool c, ) = to.call{gas: callGas, value: amount}("");     // Negative: configurable
+                    (bool d, ) = to.call{value: amount}("");                   // Negative: no gas option
+                    payable(to).transfer(amount);                              // Negative: transfer
+                    require(a && b && c && d);
+                }
+            }
+        "#;
+        let detector = Arc::new(HardcodedGasLimitDetector::default());
+        let locations = run_detector_on_code(detector, code, "payout.sol");
+
+        let lines: Vec<_> = locations.iter().map(|l| l.line).collect();
+        assert_eq!(lines, [8, 9]);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some(
+                "gas limited to the 2300 stipend of `transfer`/`send`, which repricings have \
+                 already broken for contract recipients"
+            )
+        );
+        assert_eq!(
+            locations[1].message.as_deref(),
+            Some(
+                "gas limited to a hardcoded 100000, which can become insufficient after gas \
+                 repricings"
+            )
+        );
+    }
+}
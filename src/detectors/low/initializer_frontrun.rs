@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::location::loc_to_location;
 use crate::{core::visitor::ASTVisitor, models::FindingData};
@@ -21,6 +22,10 @@ impl Detector for InitializerFrontrunDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::Upgradeability
+    }
+
     fn description(&self) -> &str {
         "Initializers could be front-run, allowing an attacker to either set their own values, \
          take ownership of the contract, and in the best case forcing a re-deployment. Ensure \
@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::location::loc_to_location;
 use crate::{core::visitor::ASTVisitor, models::FindingData};
@@ -21,6 +22,10 @@ impl Detector for InitializerOnInternalDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::Upgradeability
+    }
+
     fn description(&self) -> &str {
         "Internal functions should use `onlyInitializing` modifier instead of `initializer`. \
          The `initializer` modifier is for public-facing functions only."
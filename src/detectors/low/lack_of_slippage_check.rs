@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::location::loc_to_location;
 use crate::{core::visitor::ASTVisitor, models::FindingData};
@@ -21,6 +22,10 @@ impl Detector for LackOfSlippageCheckDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::Validation
+    }
+
     fn description(&self) -> &str {
         "Setting `amountOutMin` to 0 in swap operations removes slippage protection, allowing \
          the transaction to complete with any output amount. This can lead to significant loss \
@@ -1,5 +1,6 @@
 use crate::core::visitor::ASTVisitor;
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::ast_utils::find_in_statement;
 use solang_parser::pt::{Expression, Identifier, Type};
@@ -21,6 +22,10 @@ impl Detector for LargeApprovalDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::Token
+    }
+
     fn description(&self) -> &str {
         "Not all IERC20 implementations are fully compliant. Some tokens (e.g. UNI, COMP) may \
         fail if the value passed to `approve` is larger than `uint96`. Using `type(uint256).max` \
@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::location::loc_to_location;
 use crate::{core::visitor::ASTVisitor, models::FindingData};
@@ -21,6 +22,10 @@ impl Detector for LowLevelCallGasGriefDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::DenialOfService
+    }
+
     fn description(&self) -> &str {
         "Capturing return data as bytes from low-level calls can cause gas grief attacks. A malicious \
          contract can return large data, causing expensive memory allocation via RETURNDATACOPY. Use \
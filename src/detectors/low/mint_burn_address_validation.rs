@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::ast_utils::find_in_statement;
 use crate::core::visitor::ASTVisitor;
@@ -21,6 +22,10 @@ impl Detector for MintBurnAddressValidationDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::Validation
+    }
+
     fn description(&self) -> &str {
         "Calls to mint/burn functions should validate that the address parameter is not address(0) \
          to prevent accidentally minting to or burning from the zero address. While most ERC20/ERC721 \
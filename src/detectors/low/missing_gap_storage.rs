@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::ast_utils::get_contract_info;
 use crate::utils::location::loc_to_location;
@@ -22,6 +23,10 @@ impl Detector for MissingGapStorageDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::Upgradeability
+    }
+
     fn description(&self) -> &str {
         "Upgradeable contracts should include a __gap storage variable to allow for new storage \
          variables in later versions without shifting storage slots. This protects against storage \
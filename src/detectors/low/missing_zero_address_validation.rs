@@ -1,9 +1,13 @@
+use crate::core::visitor::ASTVisitor;
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::models::{FindingData, Location, SolidityFile, TypeInfo};
-use crate::utils::ast_utils::{find_locations_in_statement, get_contract_info};
-use crate::core::visitor::ASTVisitor;
-use solang_parser::pt::{ContractPart, Expression, Loc, Statement};
+use crate::utils::ast_utils::{find_locations_in_statement, get_function_visibility};
+use crate::utils::location::loc_to_location;
+use solang_parser::pt::{
+    ContractPart, Expression, FunctionDefinition, FunctionTy, Loc, Statement, Type, Visibility,
+};
 use std::collections::HashSet;
 use std::sync::Arc;
 
@@ -23,11 +27,22 @@ impl Detector for MissingZeroAddressValidationDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::Validation
+    }
+
+    fn version(&self) -> u32 {
+        2
+    }
+
     fn description(&self) -> &str {
         "Address state variables should be checked for zero address before assignment to prevent \
          accidentally setting critical addresses to address(0). This can lead to loss of contract \
-         control or functionality. Always validate address parameters with `require(addr != address(0))` \
-         or similar checks before assigning them to state variables."
+         control or functionality, and an `immutable` set to address(0) in the constructor can \
+         never be fixed. This reports `address` parameters of constructors and public or external \
+         functions that are assigned to an address state variable, directly or through an \
+         internal helper of the same contract, when neither function compares the parameter to \
+         `address(0)` (in a `require`, `assert` or an `if` that reverts)."
     }
 
     fn example(&self) -> Option<String> {
@@ -36,18 +51,37 @@ impl Detector for MissingZeroAddressValidationDetector {
 // Bad - no zero address check
 contract Test {
     address public owner;
+    address public immutable treasury;
+
+    constructor(address _treasury) {
+        treasury = _treasury;  // Missing validation
+    }
 
     function setOwner(address newOwner) public {
-        owner = newOwner;  // Missing validation
+        _setOwner(newOwner);  // Missing validation in the caller and the helper
+    }
+
+    function _setOwner(address newOwner) internal {
+        owner = newOwner;
     }
 }
 
 // Good - with zero address check
 contract Test {
     address public owner;
+    address public immutable treasury;
+
+    constructor(address _treasury) {
+        if (_treasury == address(0)) revert ZeroAddress();
+        treasury = _treasury;
+    }
 
     function setOwner(address newOwner) public {
         require(newOwner != address(0), "Zero address");
+        _setOwner(newOwner);
+    }
+
+    function _setOwner(address newOwner) internal {
         owner = newOwner;
     }
 }
@@ -57,84 +91,195 @@ contract Test {
     }
 
     fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
-        visitor.on_contract(move |contract_def, file, _context| {
-            let Some(contract_info) = get_contract_info(contract_def, file) else {
+        visitor.on_contract(move |contract_def, file, context| {
+            let Some(contract_name) = contract_def.name.as_ref() else {
                 return Vec::new();
             };
 
-            let address_state_vars: HashSet<String> = contract_info
-                .state_variables
-                .iter()
-                .filter(|v| matches!(v.type_info, TypeInfo::Address | TypeInfo::AddressPayable))
-                .map(|v| v.name.clone())
+            let qualified_name = context.get_qualified_name_for_contract(&contract_name.name);
+            let address_state_vars: HashSet<&str> = context
+                .get_all_state_variables(&qualified_name)
+                .into_iter()
+                .filter(|v| {
+                    !v.is_constant
+                        && matches!(v.type_info, TypeInfo::Address | TypeInfo::AddressPayable)
+                })
+                .map(|v| v.name.as_str())
                 .collect();
 
             if address_state_vars.is_empty() {
                 return Vec::new();
             }
 
-            let mut findings = Vec::new();
+            let functions: Vec<(&FunctionDefinition, &Statement)> = contract_def
+                .parts
+                .iter()
+                .filter_map(|part| match part {
+                    ContractPart::FunctionDefinition(f) => Some((f.as_ref(), f.body.as_ref()?)),
+                    _ => None,
+                })
+                .collect();
+
+            let mut locations: Vec<Location> = Vec::new();
 
-            for part in &contract_def.parts {
-                if let ContractPart::FunctionDefinition(func_def) = part {
-                    let Some(body) = &func_def.body else {
+            for (func_def, body) in &functions {
+                let entry_point = match func_def.ty {
+                    FunctionTy::Constructor => true,
+                    // Functions without a visibility (before 0.5.0) are public
+                    FunctionTy::Function => !Self::is_internal(func_def),
+                    _ => false,
+                };
+                if !entry_point {
+                    continue;
+                }
+
+                for param in Self::address_params(func_def) {
+                    if Self::find_validation_recursive(body, param) {
                         continue;
-                    };
-
-                    let mut predicate = |expr: &Expression, _file_ref: &SolidityFile| -> Option<Loc> {
-                        if let Expression::Assign(loc, left, right) = expr {
-                            // Check if left side is an address state variable
-                            if let Expression::Variable(left_id) = left.as_ref() {
-                                if address_state_vars.contains(&left_id.name) {
-                                    // Check if right side is a variable (not a literal)
-                                    if let Expression::Variable(right_id) = right.as_ref() {
-                                        // Check if this variable has validation in the function
-                                        if !Self::find_validation_recursive(body, &right_id.name) {
-                                            return Some(loc.clone());
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                        None
-                    };
-                    
-                    let mut locations: Vec<Location> = Vec::new();
-                    find_locations_in_statement(body, file, &mut predicate, &mut locations);
+                    }
 
-                    findings.extend(locations.into_iter().map(|location| FindingData {
-                        detector_id: self.id(),
-                        location,
-                    }));
+                    locations.extend(Self::assignments(body, file, param, &address_state_vars));
+
+                    // Follow the parameter one level into internal helpers of this contract
+                    for (call_loc, helper, index) in Self::helper_calls(body, file, param) {
+                        let unguarded = functions.iter().any(|(helper_def, helper_body)| {
+                            let Some(helper_param) = Self::param_name(helper_def, index) else {
+                                return false;
+                            };
+                            helper_def.ty == FunctionTy::Function
+                                && Self::is_internal(helper_def)
+                                && helper_def.name.as_ref().map(|n| n.name.as_str())
+                                    == Some(helper.as_str())
+                                && !Self::find_validation_recursive(helper_body, helper_param)
+                                && !Self::assignments(
+                                    helper_body,
+                                    file,
+                                    helper_param,
+                                    &address_state_vars,
+                                )
+                                .is_empty()
+                        });
+                        if unguarded {
+                            locations.push(loc_to_location(&call_loc, file));
+                        }
+                    }
                 }
             }
 
-            findings
+            locations
+                .into_iter()
+                .map(|location| FindingData {
+                    detector_id: self.id(),
+                    location,
+                })
+                .collect()
         });
     }
 }
 
 impl MissingZeroAddressValidationDetector {
+    fn is_internal(func_def: &FunctionDefinition) -> bool {
+        matches!(
+            get_function_visibility(func_def),
+            Some(Visibility::Internal(_) | Visibility::Private(_))
+        )
+    }
+
+    /// Names of the function's parameters declared as `address` or `address payable`.
+    fn address_params(func_def: &FunctionDefinition) -> Vec<&str> {
+        func_def
+            .params
+            .iter()
+            .filter_map(|(_, param)| param.as_ref())
+            .filter(|param| {
+                matches!(
+                    param.ty,
+                    Expression::Type(_, Type::Address | Type::AddressPayable)
+                )
+            })
+            .filter_map(|param| param.name.as_ref().map(|n| n.name.as_str()))
+            .collect()
+    }
+
+    fn param_name(func_def: &FunctionDefinition, index: usize) -> Option<&str> {
+        let (_, param) = func_def.params.get(index)?;
+        param.as_ref()?.name.as_ref().map(|n| n.name.as_str())
+    }
+
+    /// Assignments of `param` to one of `state_vars` in `body`.
+    fn assignments(
+        body: &Statement,
+        file: &SolidityFile,
+        param: &str,
+        state_vars: &HashSet<&str>,
+    ) -> Vec<Location> {
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            if let Expression::Assign(loc, left, right) = expr {
+                if let (Expression::Variable(left_id), Expression::Variable(right_id)) =
+                    (left.as_ref(), right.as_ref())
+                {
+                    if state_vars.contains(left_id.name.as_str()) && right_id.name == param {
+                        return Some(*loc);
+                    }
+                }
+            }
+            None
+        };
+        let mut locations = Vec::new();
+        find_locations_in_statement(body, file, &mut predicate, &mut locations);
+        locations
+    }
+
+    /// Calls `helper(..., param, ...)` in `body`, with the position `param` is passed at.
+    fn helper_calls(
+        body: &Statement,
+        file: &SolidityFile,
+        param: &str,
+    ) -> Vec<(Loc, String, usize)> {
+        let mut calls = Vec::new();
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            if let Expression::FunctionCall(loc, callee, args) = expr {
+                if let Expression::Variable(helper) = callee.as_ref() {
+                    for (index, arg) in args.iter().enumerate() {
+                        if Self::is_variable_with_name(arg, param) {
+                            calls.push((*loc, helper.name.clone(), index));
+                        }
+                    }
+                }
+            }
+            None
+        };
+        find_locations_in_statement(body, file, &mut predicate, &mut Vec::new());
+        calls
+    }
+
     fn find_validation_recursive(stmt: &Statement, var_name: &str) -> bool {
         match stmt {
-            Statement::Block { statements, .. } => {
-                statements.iter().any(|s| Self::find_validation_recursive(s, var_name))
-            }
+            Statement::Block { statements, .. } => statements
+                .iter()
+                .any(|s| Self::find_validation_recursive(s, var_name)),
             Statement::Expression(_, expr) => Self::expr_has_validation(expr, var_name),
             Statement::If(_, cond, then_stmt, else_stmt) => {
                 Self::expr_has_validation(cond, var_name)
                     || Self::find_validation_recursive(then_stmt, var_name)
-                    || else_stmt.as_ref().map_or(false, |s| Self::find_validation_recursive(s, var_name))
+                    || else_stmt
+                        .as_ref()
+                        .map_or(false, |s| Self::find_validation_recursive(s, var_name))
             }
             Statement::While(_, cond, body) => {
-                Self::expr_has_validation(cond, var_name) || Self::find_validation_recursive(body, var_name)
+                Self::expr_has_validation(cond, var_name)
+                    || Self::find_validation_recursive(body, var_name)
             }
             Statement::DoWhile(_, body, cond) => {
-                Self::find_validation_recursive(body, var_name) || Self::expr_has_validation(cond, var_name)
+                Self::find_validation_recursive(body, var_name)
+                    || Self::expr_has_validation(cond, var_name)
             }
             Statement::For(_, _, cond, _, body) => {
-                cond.as_ref().map_or(false, |c| Self::expr_has_validation(c, var_name))
-                    || body.as_ref().map_or(false, |b| Self::find_validation_recursive(b, var_name))
+                cond.as_ref()
+                    .map_or(false, |c| Self::expr_has_validation(c, var_name))
+                    || body
+                        .as_ref()
+                        .map_or(false, |b| Self::find_validation_recursive(b, var_name))
             }
             Statement::Return(_, Some(expr)) => Self::expr_has_validation(expr, var_name),
             _ => false,
@@ -143,20 +288,21 @@ impl MissingZeroAddressValidationDetector {
 
     fn expr_has_validation(expr: &Expression, var_name: &str) -> bool {
         match expr {
-            // == or != 
+            // `var == address(0)` or `var != address(0)`, either way round
             Expression::Equal(_, left, right) | Expression::NotEqual(_, left, right) => {
-                Self::is_variable_with_name(left, var_name) || Self::is_variable_with_name(right, var_name)
+                (Self::is_variable_with_name(left, var_name) && Self::is_zero_address(right))
+                    || (Self::is_zero_address(left) && Self::is_variable_with_name(right, var_name))
             }
             // require/assert
-            Expression::FunctionCall(_, _, args) => {
-                args.iter().any(|arg| Self::expr_has_validation(arg, var_name))
-            }
+            Expression::FunctionCall(_, _, args) => args
+                .iter()
+                .any(|arg| Self::expr_has_validation(arg, var_name)),
             // negation
             Expression::Not(_, inner) => Self::expr_has_validation(inner, var_name),
             // logical operators (&&, ||)
-            Expression::And(_, left, right)
-            | Expression::Or(_, left, right) => {
-                Self::expr_has_validation(left, var_name) || Self::expr_has_validation(right, var_name)
+            Expression::And(_, left, right) | Expression::Or(_, left, right) => {
+                Self::expr_has_validation(left, var_name)
+                    || Self::expr_has_validation(right, var_name)
             }
             _ => false,
         }
@@ -165,6 +311,24 @@ impl MissingZeroAddressValidationDetector {
     fn is_variable_with_name(expr: &Expression, name: &str) -> bool {
         matches!(expr, Expression::Variable(id) if id.name == name)
     }
+
+    /// `address(0)`, also written `address(0x0)` or `payable(address(0))`
+    fn is_zero_address(expr: &Expression) -> bool {
+        let Expression::FunctionCall(_, callee, args) = expr else {
+            return false;
+        };
+        match (callee.as_ref(), args.as_slice()) {
+            (Expression::Type(_, Type::Address | Type::AddressPayable), [arg]) => match arg {
+                Expression::NumberLiteral(_, value, exp, _) => value == "0" && exp.is_empty(),
+                Expression::HexNumberLiteral(_, value, _) => {
+                    value.trim_start_matches("0x").chars().all(|c| c == '0')
+                }
+                _ => false,
+            },
+            (Expression::Variable(id), [arg]) if id.name == "payable" => Self::is_zero_address(arg),
+            _ => false,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -232,4 +396,65 @@ mod tests {
         let locations = run_detector_on_code(detector, code, "test.sol");
         assert_eq!(locations.len(), 0);
     }
+
+    #[test]
+    fn test_constructors_and_helpers() {
+        let code = r#"
+            contract Base {
+                address internal owner;
+            }
+
+            contract Vault is Base {
+                error ZeroAddress();
+
+                address public immutable treasury;
+                address public immutable token;
+                address public keeper;
+
+                constructor(address _treasury, address _token, address _owner) {
+                    treasury = _treasury;
+                    if (_token == address(0x0)) revert ZeroAddress();
+                    token = _token;
+                    owner = _owner;
+                }
+
+                function setKeeper(address newKeeper) external {
+                    _setKeeper(newKeeper);
+                }
+
+                function setKeeperChecked(address newKeeper) external {
+                    require(newKeeper != address(0), ZeroAddress());
+                    _setKeeper(newKeeper);
+                }
+
+                function setKeeperGuarded(address newKeeper) external {
+                    _setKeeperGuarded(newKeeper);
+                }
+
+                function setKeeperIfChanged(address newKeeper) external {
+                    require(newKeeper != keeper);
+                    keeper = newKeeper;
+                }
+
+                function _setKeeper(address newKeeper) internal {
+                    keeper = newKeeper;
+                }
+
+                function _setKeeperGuarded(address newKeeper) internal {
+                    if (newKeeper == address(0)) revert ZeroAddress();
+                    keeper = newKeeper;
+                }
+            }
+        "#;
+        let detector = Arc::new(MissingZeroAddressValidationDetector::default());
+        let locations = run_detector_on_code(detector, code, "test.sol");
+        assert_eq!(locations.len(), 4);
+        assert_eq!(locations[0].line, 14, "immutable treasury = _treasury");
+        assert_eq!(locations[1].line, 17, "inherited owner = _owner");
+        assert_eq!(locations[2].line, 21, "_setKeeper(newKeeper)");
+        assert_eq!(
+            locations[3].line, 35,
+            "comparison to another address is not a zero check"
+        );
+    }
 }
@@ -1,4 +1,7 @@
+pub mod approve_race;
 pub mod assembly_optimizer_bug;
+pub mod auth_reread_after_call;
+pub mod balance_equality;
 pub mod block_timestamp_deadline;
 pub mod constant_decimals;
 pub mod curve_calc_token_amount;
@@ -7,18 +10,22 @@ pub mod deprecated_abi_encoder_v2;
 pub mod deprecated_approve;
 pub mod deprecated_safe_approve;
 pub mod deprecated_setup_role;
+pub mod discarded_library_return;
 pub mod division_before_multiplication;
 pub mod division_by_zero;
 pub mod division_rounding;
 pub mod domain_separator_replay;
 pub mod duplicate_import;
 pub mod ecrecover_malleability;
+pub mod ecrecover_v_normalization;
 pub mod empty_ether_receiver;
 pub mod empty_function_body;
 pub mod erc20_decimals;
 pub mod erc20_symbol_not_standard;
 pub mod external_call_in_loop;
 pub mod fallback_lacking_payable;
+pub mod gas_dependent_logic;
+pub mod hardcoded_gas_limit;
 pub mod initializer_frontrun;
 pub mod initializer_on_internal;
 pub mod lack_of_slippage_check;
@@ -27,10 +34,18 @@ pub mod low_level_call_gas_grief;
 pub mod mint_burn_address_validation;
 pub mod missing_gap_storage;
 pub mod missing_zero_address_validation;
+pub mod msg_value_refund;
 pub mod nft_hard_fork;
+pub mod outdated_compiler_version;
+pub mod owner_token_enumeration;
+pub mod permit_front_running;
 pub mod push0_opcode;
 pub mod renounce_while_paused;
+pub mod shadowing;
+pub mod supports_interface_composition;
 pub mod sweep_token_accounting;
+pub mod timestamp_equality;
+pub mod truncating_assignment;
 pub mod two_step_ownership_transfer;
 pub mod uninitialized_implementation;
 pub mod uninitialized_upgradeable;
@@ -40,12 +55,19 @@ pub mod unsafe_abi_encode_packed;
 pub mod unsafe_downcast;
 pub mod unsafe_int_cast;
 pub mod unsafe_int_to_uint_cast;
+pub mod unvalidated_external_struct;
+pub mod unvalidated_privileged_array;
 pub mod year_365_days;
 pub mod unsafe_low_level_call;
 pub mod upgradable_token_interface;
 pub mod zero_value_transfer;
 
+use crate::detectors::Detector;
+
+pub use approve_race::ApproveRaceDetector;
 pub use assembly_optimizer_bug::AssemblyOptimizerBugDetector;
+pub use auth_reread_after_call::AuthRereadAfterCallDetector;
+pub use balance_equality::BalanceEqualityDetector;
 pub use block_timestamp_deadline::BlockTimestampDeadlineDetector;
 pub use constant_decimals::ConstantDecimalsDetector;
 pub use curve_calc_token_amount::CurveCalcTokenAmountDetector;
@@ -54,18 +76,22 @@ pub use deprecated_abi_encoder_v2::DeprecatedAbiEncoderV2Detector;
 pub use deprecated_approve::DeprecatedApproveDetector;
 pub use deprecated_safe_approve::DeprecatedSafeApproveDetector;
 pub use deprecated_setup_role::DeprecatedSetupRoleDetector;
+pub use discarded_library_return::DiscardedLibraryReturnDetector;
 pub use division_before_multiplication::DivisionBeforeMultiplicationDetector;
 pub use division_by_zero::DivisionByZeroDetector;
 pub use division_rounding::DivisionRoundingDetector;
 pub use domain_separator_replay::DomainSeparatorReplayDetector;
 pub use duplicate_import::DuplicateImportDetector;
 pub use ecrecover_malleability::EcrecoverMalleabilityDetector;
+pub use ecrecover_v_normalization::EcrecoverVNormalizationDetector;
 pub use empty_ether_receiver::EmptyEtherReceiverDetector;
 pub use empty_function_body::EmptyFunctionBodyDetector;
 pub use erc20_decimals::Erc20DecimalsDetector;
 pub use erc20_symbol_not_standard::Erc20SymbolNotStandardDetector;
 pub use external_call_in_loop::ExternalCallInLoopDetector;
 pub use fallback_lacking_payable::FallbackLackingPayableDetector;
+pub use gas_dependent_logic::GasDependentLogicDetector;
+pub use hardcoded_gas_limit::HardcodedGasLimitDetector;
 pub use initializer_frontrun::InitializerFrontrunDetector;
 pub use initializer_on_internal::InitializerOnInternalDetector;
 pub use lack_of_slippage_check::LackOfSlippageCheckDetector;
@@ -74,10 +100,18 @@ pub use low_level_call_gas_grief::LowLevelCallGasGriefDetector;
 pub use mint_burn_address_validation::MintBurnAddressValidationDetector;
 pub use missing_gap_storage::MissingGapStorageDetector;
 pub use missing_zero_address_validation::MissingZeroAddressValidationDetector;
+pub use msg_value_refund::MsgValueRefundDetector;
 pub use nft_hard_fork::NftHardForkDetector;
+pub use outdated_compiler_version::OutdatedCompilerVersionDetector;
+pub use owner_token_enumeration::OwnerTokenEnumerationDetector;
+pub use permit_front_running::PermitFrontRunningDetector;
 pub use push0_opcode::Push0OpcodeDetector;
 pub use renounce_while_paused::RenounceWhilePausedDetector;
+pub use shadowing::ShadowingDetector;
+pub use supports_interface_composition::SupportsInterfaceCompositionDetector;
 pub use sweep_token_accounting::SweepTokenAccountingDetector;
+pub use timestamp_equality::TimestampEqualityDetector;
+pub use truncating_assignment::TruncatingAssignmentDetector;
 pub use two_step_ownership_transfer::TwoStepOwnershipTransferDetector;
 pub use uninitialized_implementation::UninitializedImplementationDetector;
 pub use uninitialized_upgradeable::UninitializedUpgradeableDetector;
@@ -87,7 +121,79 @@ pub use unsafe_abi_encode_packed::UnsafeAbiEncodePackedDetector;
 pub use unsafe_downcast::UnsafeDowncastDetector;
 pub use unsafe_int_cast::UnsafeIntCastDetector;
 pub use unsafe_int_to_uint_cast::UnsafeIntToUintCastDetector;
+pub use unvalidated_external_struct::UnvalidatedExternalStructDetector;
+pub use unvalidated_privileged_array::UnvalidatedPrivilegedArrayDetector;
 pub use year_365_days::Year365DaysDetector;
 pub use unsafe_low_level_call::UnsafeLowLevelCallDetector;
 pub use upgradable_token_interface::UpgradableTokenInterfaceDetector;
 pub use zero_value_transfer::ZeroValueTransferDetector;
+
+/// Every Low detector, in registration order. A detector exported above but missing
+/// here never runs, which `test_every_exported_detector_is_built_in` catches.
+pub fn all() -> Vec<Box<dyn Detector>> {
+    vec![
+        Box::new(ApproveRaceDetector::default()),
+        Box::new(AssemblyOptimizerBugDetector::default()),
+        Box::new(AuthRereadAfterCallDetector::default()),
+        Box::new(BalanceEqualityDetector::default()),
+        Box::new(BlockTimestampDeadlineDetector::default()),
+        Box::new(ConstantDecimalsDetector::default()),
+        Box::new(CurveCalcTokenAmountDetector::default()),
+        Box::new(DecimalsTypeDetector::default()),
+        Box::new(DeprecatedAbiEncoderV2Detector::default()),
+        Box::new(DeprecatedApproveDetector::default()),
+        Box::new(DeprecatedSafeApproveDetector::default()),
+        Box::new(DeprecatedSetupRoleDetector::default()),
+        Box::new(DiscardedLibraryReturnDetector::default()),
+        Box::new(DivisionBeforeMultiplicationDetector::default()),
+        Box::new(DivisionByZeroDetector::default()),
+        Box::new(DivisionRoundingDetector::default()),
+        Box::new(DomainSeparatorReplayDetector::default()),
+        Box::new(DuplicateImportDetector::default()),
+        Box::new(EcrecoverMalleabilityDetector::default()),
+        Box::new(EcrecoverVNormalizationDetector::default()),
+        Box::new(EmptyEtherReceiverDetector::default()),
+        Box::new(EmptyFunctionBodyDetector::default()),
+        Box::new(Erc20DecimalsDetector::default()),
+        Box::new(Erc20SymbolNotStandardDetector::default()),
+        Box::new(ExternalCallInLoopDetector::default()),
+        Box::new(FallbackLackingPayableDetector::default()),
+        Box::new(GasDependentLogicDetector::default()),
+        Box::new(HardcodedGasLimitDetector::default()),
+        Box::new(InitializerFrontrunDetector::default()),
+        Box::new(InitializerOnInternalDetector::default()),
+        Box::new(LackOfSlippageCheckDetector::default()),
+        Box::new(LargeApprovalDetector::default()),
+        Box::new(LowLevelCallGasGriefDetector::default()),
+        Box::new(MintBurnAddressValidationDetector::default()),
+        Box::new(MissingGapStorageDetector::default()),
+        Box::new(MissingZeroAddressValidationDetector::default()),
+        Box::new(MsgValueRefundDetector::default()),
+        Box::new(NftHardForkDetector::default()),
+        Box::new(OutdatedCompilerVersionDetector::default()),
+        Box::new(OwnerTokenEnumerationDetector::default()),
+        Box::new(PermitFrontRunningDetector::default()),
+        Box::new(Push0OpcodeDetector::default()),
+        Box::new(RenounceWhilePausedDetector::default()),
+        Box::new(ShadowingDetector::default()),
+        Box::new(SupportsInterfaceCompositionDetector::default()),
+        Box::new(SweepTokenAccountingDetector::default()),
+        Box::new(TimestampEqualityDetector::default()),
+        Box::new(TruncatingAssignmentDetector::default()),
+        Box::new(TwoStepOwnershipTransferDetector::default()),
+        Box::new(UninitializedImplementationDetector::default()),
+        Box::new(UninitializedUpgradeableDetector::default()),
+        Box::new(UnlimitedGasCallDetector::default()),
+        Box::new(UnsafeAbiEncodePackedDetector::default()),
+        Box::new(UnsafeDowncastDetector::default()),
+        Box::new(UnsafeIntCastDetector::default()),
+        Box::new(UnsafeIntToUintCastDetector::default()),
+        Box::new(UnsafeLowLevelCallDetector::default()),
+        Box::new(UnspecificPragmaDetector::default()),
+        Box::new(UnvalidatedExternalStructDetector::default()),
+        Box::new(UnvalidatedPrivilegedArrayDetector::default()),
+        Box::new(UpgradableTokenInterfaceDetector::default()),
+        Box::new(Year365DaysDetector::default()),
+        Box::new(ZeroValueTransferDetector::default()),
+    ]
+}
@@ -0,0 +1,348 @@
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::category::Category;
+use crate::models::severity::Severity;
+use crate::models::{FindingData, SolidityFile};
+use crate::utils::ast_utils::find_locations_in_expression;
+use crate::utils::location::loc_to_location;
+use solang_parser::pt::{CodeLocation, Expression, Loc, Statement};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+#[derive(Debug, Default)]
+pub struct MsgValueRefundDetector;
+
+/// Guards and refund locals seen so far while walking a function body in order.
+#[derive(Default)]
+struct RefundScan {
+    /// Source text of values `msg.value` has been compared against
+    guarded: HashSet<String>,
+    /// Local variable name -> source text of the price subtracted from `msg.value`
+    refund_locals: HashMap<String, String>,
+}
+
+impl Detector for MsgValueRefundDetector {
+    fn id(&self) -> &'static str {
+        "msg-value-refund"
+    }
+
+    fn name(&self) -> &str {
+        "Unsafe refund of excess `msg.value`"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Low
+    }
+
+    fn category(&self) -> Category {
+        Category::ExternalCall
+    }
+
+    fn description(&self) -> &str {
+        "A refund of `msg.value - price` is sent back to the caller. Without a prior \
+         `require(msg.value >= price)` the subtraction underflows and the purchase reverts with \
+         an arithmetic error instead of a clear message (or silently wraps before 0.8). Sending \
+         the refund with `transfer` or `send` forwards only 2300 gas, so buyers that are contracts \
+         with a non-trivial `receive` cannot purchase at all. Check the payment first and refund \
+         with `call`, handling its result."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad
+function buy() external payable {
+    payable(msg.sender).transfer(msg.value - price);
+}
+
+// Good
+function buy() external payable {
+    require(msg.value >= price, "insufficient payment");
+    (bool ok, ) = payable(msg.sender).call{value: msg.value - price}("");
+    require(ok, "refund failed");
+}
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_function(move |func_def, file, _context| {
+            let Some(body) = &func_def.body else {
+                return Vec::new();
+            };
+
+            let mut scan = RefundScan::default();
+            let mut findings = Vec::new();
+            Self::scan_statement(body, file, &mut scan, &mut |loc, method, price, guarded| {
+                let location = loc_to_location(loc, file);
+                if !guarded {
+                    findings.push(FindingData {
+                        detector_id: self.id(),
+                        location: location.clone().with_message(format!(
+                            "refund of `msg.value - {}` without a prior `msg.value >= {}` check",
+                            price, price
+                        )),
+                    });
+                }
+                if method != "call" {
+                    findings.push(FindingData {
+                        detector_id: self.id(),
+                        location: location.with_message(format!(
+                            "refund sent with `.{}`, which forwards only 2300 gas; use `call` \
+                             and check the result",
+                            method
+                        )),
+                    });
+                }
+            });
+
+            findings
+        });
+    }
+}
+
+impl MsgValueRefundDetector {
+    /// Walk statements in source order so a guard only covers the refunds that follow it.
+    fn scan_statement<F>(
+        stmt: &Statement,
+        file: &SolidityFile,
+        scan: &mut RefundScan,
+        report: &mut F,
+    ) where
+        F: FnMut(&Loc, &str, &str, bool),
+    {
+        match stmt {
+            Statement::Block { statements, .. } => {
+                for s in statements {
+                    Self::scan_statement(s, file, scan, report);
+                }
+            }
+            Statement::If(_, condition, then_stmt, else_stmt) => {
+                Self::collect_guards(condition, file, scan);
+                Self::scan_statement(then_stmt, file, scan, report);
+                if let Some(else_stmt) = else_stmt {
+                    Self::scan_statement(else_stmt, file, scan, report);
+                }
+            }
+            Statement::While(_, _, body) | Statement::DoWhile(_, body, _) => {
+                Self::scan_statement(body, file, scan, report);
+            }
+            Statement::For(_, init, _, _, body) => {
+                if let Some(init) = init {
+                    Self::scan_statement(init, file, scan, report);
+                }
+                if let Some(body) = body {
+                    Self::scan_statement(body, file, scan, report);
+                }
+            }
+            Statement::VariableDefinition(_, decl, Some(init)) => {
+                if let (Some(name), Some(price)) =
+                    (&decl.name, Self::refund_price(init, file, scan))
+                {
+                    scan.refund_locals.insert(name.name.clone(), price);
+                }
+                Self::scan_expression(init, file, scan, report);
+            }
+            Statement::Expression(_, expr) => {
+                if let Expression::FunctionCall(_, func, args) = expr {
+                    let is_check = matches!(
+                        func.as_ref(),
+                        Expression::Variable(id) if id.name == "require" || id.name == "assert"
+                    );
+                    if let Some(condition) = args.first().filter(|_| is_check) {
+                        Self::collect_guards(condition, file, scan);
+                    }
+                }
+                Self::scan_expression(expr, file, scan, report);
+            }
+            Statement::Return(_, Some(expr)) => Self::scan_expression(expr, file, scan, report),
+            _ => {}
+        }
+    }
+
+    fn scan_expression<F>(expr: &Expression, file: &SolidityFile, scan: &RefundScan, report: &mut F)
+    where
+        F: FnMut(&Loc, &str, &str, bool),
+    {
+        let mut refunds = Vec::new();
+        let mut predicate = |expr: &Expression, file: &SolidityFile| -> Option<Loc> {
+            let (loc, method, amount) = Self::native_send(expr)?;
+            let price = Self::refund_price(amount, file, scan)?;
+            refunds.push((*loc, method.to_string(), price));
+            Some(*loc)
+        };
+        let mut locations = Vec::new();
+        find_locations_in_expression(expr, file, &mut predicate, &mut locations);
+
+        for (loc, method, price) in &refunds {
+            report(loc, method, price, scan.guarded.contains(price));
+        }
+    }
+
+    /// `to.transfer(amount)`, `to.send(amount)` or `to.call{value: amount}(...)`
+    fn native_send(expr: &Expression) -> Option<(&Loc, &str, &Expression)> {
+        let Expression::FunctionCall(loc, func, args) = expr else {
+            return None;
+        };
+        match func.as_ref() {
+            Expression::MemberAccess(_, _, member)
+                if args.len() == 1 && (member.name == "transfer" || member.name == "send") =>
+            {
+                Some((loc, member.name.as_str(), &args[0]))
+            }
+            Expression::FunctionCallBlock(_, target, block) => {
+                let Expression::MemberAccess(_, _, member) = target.as_ref() else {
+                    return None;
+                };
+                let Statement::Args(_, named_args) = block.as_ref() else {
+                    return None;
+                };
+                if member.name != "call" {
+                    return None;
+                }
+                named_args
+                    .iter()
+                    .find(|arg| arg.name.name == "value")
+                    .map(|arg| (loc, "call", &arg.expr))
+            }
+            _ => None,
+        }
+    }
+
+    /// Source text of `price` when `amount` is `msg.value - price` or a local holding it.
+    fn refund_price(amount: &Expression, file: &SolidityFile, scan: &RefundScan) -> Option<String> {
+        match amount {
+            Expression::Parenthesis(_, inner) => Self::refund_price(inner, file, scan),
+            Expression::Subtract(_, left, right) if Self::is_msg_value(left) => {
+                Some(Self::source_text(right, file))
+            }
+            Expression::Variable(id) => scan.refund_locals.get(&id.name).cloned(),
+            _ => None,
+        }
+    }
+
+    /// Record what `msg.value` is compared against in a `require`/`assert`/`if` condition.
+    fn collect_guards(condition: &Expression, file: &SolidityFile, scan: &mut RefundScan) {
+        match condition {
+            Expression::And(_, left, right) | Expression::Or(_, left, right) => {
+                Self::collect_guards(left, file, scan);
+                Self::collect_guards(right, file, scan);
+            }
+            Expression::Not(_, inner) | Expression::Parenthesis(_, inner) => {
+                Self::collect_guards(inner, file, scan);
+            }
+            Expression::MoreEqual(_, left, right)
+            | Expression::More(_, left, right)
+            | Expression::LessEqual(_, left, right)
+            | Expression::Less(_, left, right)
+            | Expression::Equal(_, left, right)
+            | Expression::NotEqual(_, left, right) => {
+                if Self::is_msg_value(left) {
+                    scan.guarded.insert(Self::source_text(right, file));
+                } else if Self::is_msg_value(right) {
+                    scan.guarded.insert(Self::source_text(left, file));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn is_msg_value(expr: &Expression) -> bool {
+        matches!(
+            expr,
+            Expression::MemberAccess(_, base, member)
+                if member.name == "value"
+                    && matches!(base.as_ref(), Expression::Variable(id) if id.name == "msg")
+        )
+    }
+
+    fn source_text(expr: &Expression, file: &SolidityFile) -> String {
+        loc_to_location(&expr.loc(), file)
+            .snippet
+            .unwrap_or_default()
+            .split_whitespace()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::run_detector_on_code;
+
+    #[test]
+    fn test_msg_value_refund_detector() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            contract Shop {
+                uint256 price;
+
+                function buy() external payable {
+                    payable(msg.sender).transfer(msg.value - price);        // Positive: both
+                }
+
+                function buyChecked() external payable {
+                    require(msg.value >= price, "underpaid");
+                    uint256 refund = msg.value - price;
+                    (bool ok, ) = payable(msg.sender).call{value: refund}(""); // Negative
+                    require(ok);
+                }
+
+                function buyGuardedTransfer() external payable {
+                    if (msg.value < price) revert();
+                    payable(msg.sender).transfer(msg.value - price);        // Positive: transfer
+                }
+
+                function pay(address payable to, uint256 amount) external {
+                    to.transfer(amount);                                    // Negative: not a refund
+                }
+            }
+        "#;
+
+        let detector = Arc::new(MsgValueRefundDetector::default());
+        let locations = run_detector_on_code(detector, code, "shop.sol");
+
+        assert_eq!(locations.len(), 3, "Should detect 3 refund issues");
+        assert_eq!(locations[0].line, 8);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some("refund of `msg.value - price` without a prior `msg.value >= price` check")
+        );
+        assert_eq!(locations[1].line, 8);
+        assert_eq!(
+            locations[1].message.as_deref(),
+            Some(
+                "refund sent with `.transfer`, which forwards only 2300 gas; use `call` and check the result"
+            )
+        );
+        assert_eq!(locations[2].line, 20);
+        assert_eq!(locations[1].message, locations[2].message);
+    }
+
+    #[test]
+    fn test_unguarded_call_refund() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            contract Sale {
+                function mint(uint256 amount) external payable {
+                    uint256 cost = amount * 1e15;
+                    (bool ok, ) = msg.sender.call{value: (msg.value - cost)}("");
+                    require(ok);
+                }
+            }
+        "#;
+
+        let detector = Arc::new(MsgValueRefundDetector::default());
+        let locations = run_detector_on_code(detector, code, "sale.sol");
+
+        assert_eq!(locations.len(), 1);
+        assert_eq!(locations[0].line, 7);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some("refund of `msg.value - cost` without a prior `msg.value >= cost` check")
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::models::FindingData;
 use crate::utils::ast_utils::find_in_statement;
@@ -23,6 +24,10 @@ impl Detector for NftHardForkDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::Compatibility
+    }
+
     fn description(&self) -> &str {
         "When there are hard forks, users often have to go through many hoops to ensure that they \
          control ownership on every fork. Consider adding `require(block.chainid == expectedChainId)` \
@@ -0,0 +1,272 @@
+use crate::config::DetectorSettings;
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::category::Category;
+use crate::models::severity::Severity;
+use crate::models::FindingData;
+use crate::output::style::errln;
+use crate::utils::location::loc_to_location;
+use solang_parser::pt::{
+    ContractTy, PragmaDirective, SourceUnitPart, VersionComparator, VersionOp,
+};
+use std::sync::Arc;
+
+type Version = (u32, u32, u32);
+
+const DEFAULT_MIN_VERSION: Version = (0, 8, 0);
+
+#[derive(Debug)]
+pub struct OutdatedCompilerVersionDetector {
+    min_version: Version,
+}
+
+impl Default for OutdatedCompilerVersionDetector {
+    fn default() -> Self {
+        Self {
+            min_version: DEFAULT_MIN_VERSION,
+        }
+    }
+}
+
+impl Detector for OutdatedCompilerVersionDetector {
+    fn id(&self) -> &'static str {
+        "outdated-compiler-version"
+    }
+
+    fn name(&self) -> &str {
+        "Pragma allows outdated compiler versions"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Low
+    }
+
+    fn category(&self) -> Category {
+        Category::Compatibility
+    }
+
+    fn description(&self) -> &str {
+        "Solidity versions before 0.8.0 have no built-in overflow and underflow checks, and older \
+         compilers miss fixes for known code generation bugs. A pragma whose lowest allowed \
+         version is below the minimum lets the code be compiled with them. The minimum is \
+         configurable via `min_version` under `[detectors.outdated-compiler-version]`. Files that \
+         only declare interfaces are skipped."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad - allows compilers without checked arithmetic
+pragma solidity ^0.7.6;
+pragma solidity >=0.6.0 <0.9.0;
+
+// Good
+pragma solidity ^0.8.20;
+```"#
+                .to_string(),
+        )
+    }
+
+    fn configure(&mut self, settings: &DetectorSettings) {
+        let Some(value) = settings.get_str(self.id(), "min_version") else {
+            return;
+        };
+        match parse_version(value) {
+            Some(version) => self.min_version = version,
+            None => errln!(
+                "Warning: [detectors.{}] min_version must be a version like \"0.8.0\", got \"{}\". \
+                 Using default.",
+                self.id(),
+                value
+            ),
+        }
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_source_unit(move |source_unit, file, _context| {
+            let has_implementation = source_unit.0.iter().any(|part| {
+                matches!(
+                    part,
+                    SourceUnitPart::ContractDefinition(contract)
+                        if !matches!(contract.ty, ContractTy::Interface(_))
+                )
+            });
+            if !has_implementation {
+                return Vec::new();
+            }
+
+            let mut findings = Vec::new();
+
+            for part in &source_unit.0 {
+                if let SourceUnitPart::PragmaDirective(pragma) = part {
+                    if let PragmaDirective::Version(loc, ident, version_req) = pragma.as_ref() {
+                        if ident.name != "solidity" {
+                            continue;
+                        }
+                        let lowest = Self::lowest_allowed(version_req);
+                        if lowest < self.min_version {
+                            let message = format!(
+                                "Allows Solidity {}, older than {}",
+                                display_version(lowest),
+                                display_version(self.min_version)
+                            );
+                            findings.push(FindingData {
+                                detector_id: self.id(),
+                                location: loc_to_location(loc, file).with_message(message),
+                            });
+                        }
+                    }
+                }
+            }
+
+            findings
+        });
+    }
+}
+
+impl OutdatedCompilerVersionDetector {
+    /// Lowest compiler version the requirement accepts. Comparators in a list must all hold, so
+    /// the highest of their lower bounds wins; `||` takes the lower of its sides.
+    fn lowest_allowed(version_req: &[VersionComparator]) -> Version {
+        version_req
+            .iter()
+            .map(Self::lower_bound)
+            .max()
+            .unwrap_or((0, 0, 0))
+    }
+
+    fn lower_bound(comp: &VersionComparator) -> Version {
+        match comp {
+            VersionComparator::Plain { version, .. } => version_floor(version),
+            VersionComparator::Operator { op, version, .. } => match op {
+                VersionOp::Exact | VersionOp::GreaterEq | VersionOp::Caret | VersionOp::Tilde => {
+                    version_floor(version)
+                }
+                VersionOp::Greater => {
+                    let (major, minor, patch) = version_floor(version);
+                    (major, minor, patch + 1)
+                }
+                VersionOp::Less | VersionOp::LessEq | VersionOp::Wildcard => (0, 0, 0),
+            },
+            VersionComparator::Range { from, .. } => version_floor(from),
+            VersionComparator::Or { left, right, .. } => {
+                Self::lower_bound(left).min(Self::lower_bound(right))
+            }
+        }
+    }
+}
+
+/// Lowest version a possibly partial version (`0.8`, `0.8.x`) stands for.
+fn version_floor(version: &[String]) -> Version {
+    let part = |index: usize| {
+        version
+            .get(index)
+            .and_then(|s| s.parse::<u32>().ok())
+            .unwrap_or(0)
+    };
+    (part(0), part(1), part(2))
+}
+
+fn parse_version(value: &str) -> Option<Version> {
+    let parts = value
+        .trim()
+        .split('.')
+        .map(|part| part.parse::<u32>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    match parts[..] {
+        [major, minor] => Some((major, minor, 0)),
+        [major, minor, patch] => Some((major, minor, patch)),
+        _ => None,
+    }
+}
+
+fn display_version((major, minor, patch): Version) -> String {
+    format!("{}.{}.{}", major, minor, patch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::run_detector_on_code;
+
+    fn flagged(pragma: &str, detector: OutdatedCompilerVersionDetector) -> bool {
+        let code = format!("pragma solidity {};\ncontract Test {{}}\n", pragma);
+        !run_detector_on_code(Arc::new(detector), &code, "test.sol").is_empty()
+    }
+
+    #[test]
+    fn test_detects_pragmas_allowing_old_compilers() {
+        for pragma in [
+            "0.7.6",
+            "=0.6.12",
+            "^0.7.0",
+            "~0.6.2",
+            ">=0.6.0 <0.9.0",
+            ">0.7.5",
+            "<0.8.20",
+            "0.6.0 - 0.8.20",
+            "^0.8.0 || ^0.7.0",
+        ] {
+            assert!(
+                flagged(pragma, OutdatedCompilerVersionDetector::default()),
+                "{}",
+                pragma
+            );
+        }
+
+        let code = r#"
+            pragma solidity ^0.7.0;
+            library Math {}
+        "#;
+        let detector = Arc::new(OutdatedCompilerVersionDetector::default());
+        let locations = run_detector_on_code(detector, code, "test.sol");
+        assert_eq!(locations.len(), 1);
+        assert_eq!(locations[0].line, 2);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some("Allows Solidity 0.7.0, older than 0.8.0")
+        );
+    }
+
+    #[test]
+    fn test_skips_current_pragmas_and_interfaces() {
+        for pragma in [
+            "0.8.20",
+            "=0.8.0",
+            "^0.8.0",
+            ">=0.8.0 <0.9.0",
+            ">=0.8.0 || >0.8.1",
+            "0.8.4 - 0.8.20",
+            "^0.8.0 || 0.8.4",
+            "0.8",
+        ] {
+            assert!(
+                !flagged(pragma, OutdatedCompilerVersionDetector::default()),
+                "{}",
+                pragma
+            );
+        }
+
+        let code = r#"
+            pragma solidity ^0.6.0;
+            interface IToken { function transfer(address to, uint256 amount) external; }
+        "#;
+        let detector = Arc::new(OutdatedCompilerVersionDetector::default());
+        let locations = run_detector_on_code(detector, code, "test.sol");
+        assert_eq!(locations.len(), 0);
+    }
+
+    #[test]
+    fn test_min_version_is_configurable() {
+        let settings: DetectorSettings = toml::from_str("min_version = \"0.8.4\"").unwrap();
+        let configured = || {
+            let mut detector = OutdatedCompilerVersionDetector::default();
+            detector.configure(&settings);
+            detector
+        };
+
+        assert!(flagged("^0.8.0", configured()));
+        assert!(!flagged("^0.8.4", configured()));
+        assert!(!flagged("0.8.20", configured()));
+    }
+}
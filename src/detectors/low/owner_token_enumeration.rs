@@ -0,0 +1,323 @@
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::category::Category;
+use crate::models::finding::Location;
+use crate::models::severity::Severity;
+use crate::models::{FindingData, SolidityFile};
+use crate::utils::ast_utils::{
+    find_locations_in_expression, find_locations_in_statement, find_statement_types,
+    get_function_visibility, is_function_readonly,
+};
+use solang_parser::pt::{Expression, Loc, Statement, Visibility};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+const ENUMERATION_CALLS: &[&str] = &["tokenOfOwnerByIndex", "tokenByIndex"];
+
+#[derive(Debug, Default)]
+pub struct OwnerTokenEnumerationDetector;
+
+impl Detector for OwnerTokenEnumerationDetector {
+    fn id(&self) -> &'static str {
+        "owner-token-enumeration"
+    }
+
+    fn name(&self) -> &str {
+        "ERC721 tokens enumerated in a loop bounded by `balanceOf`"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Low
+    }
+
+    fn category(&self) -> Category {
+        Category::DenialOfService
+    }
+
+    fn description(&self) -> &str {
+        "A loop bounded by `balanceOf(owner)` calls `tokenOfOwnerByIndex` or `tokenByIndex` on \
+         every iteration. Each call is an external call into the enumerable extension, so the \
+         cost grows with the number of tokens held and the transaction runs out of gas for large \
+         holders, who can also inflate it on purpose by receiving more tokens. Index tokens \
+         off-chain and pass the ids in, or track the ids that matter in a mapping. External \
+         view functions are not reported, as they are meant to be called off-chain."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad - one external call per token held
+function claimAll() external {
+    uint256 count = nft.balanceOf(msg.sender);
+    for (uint256 i; i < count; i++) {
+        _claim(nft.tokenOfOwnerByIndex(msg.sender, i));
+    }
+}
+
+// Good - the caller passes the ids, ownership is checked per id
+function claim(uint256[] calldata ids) external {
+    for (uint256 i; i < ids.length; i++) {
+        require(nft.ownerOf(ids[i]) == msg.sender, "not owner");
+        _claim(ids[i]);
+    }
+}
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_function(move |func_def, file, _context| {
+            let Some(body) = &func_def.body else {
+                return Vec::new();
+            };
+            // Enumerating view helpers (`tokensOfOwner`) are queried off-chain
+            if is_function_readonly(func_def)
+                && matches!(
+                    get_function_visibility(func_def),
+                    Some(Visibility::External(_)) | Some(Visibility::Public(_))
+                )
+            {
+                return Vec::new();
+            }
+
+            let balance_locals = Self::balance_locals(body, file);
+            let mut seen = HashSet::new();
+            let mut locations = Vec::new();
+            Self::check_loops(body, file, &balance_locals, &mut seen, &mut locations);
+
+            locations
+                .into_iter()
+                .map(|location| FindingData {
+                    detector_id: self.id(),
+                    location,
+                })
+                .collect()
+        });
+    }
+}
+
+impl OwnerTokenEnumerationDetector {
+    /// Locals declared or assigned from a `balanceOf` call, e.g. `uint256 n = nft.balanceOf(o)`,
+    /// mapped to that call.
+    fn balance_locals(body: &Statement, file: &SolidityFile) -> HashMap<String, String> {
+        let mut locals = HashMap::new();
+        find_statement_types(body, file, "", |stmt| {
+            let (name, value) = match stmt {
+                Statement::VariableDefinition(_, decl, Some(value)) => match &decl.name {
+                    Some(name) => (&name.name, value),
+                    None => return false,
+                },
+                Statement::Expression(_, Expression::Assign(_, target, value)) => {
+                    match target.as_ref() {
+                        Expression::Variable(name) => (&name.name, value.as_ref()),
+                        _ => return false,
+                    }
+                }
+                _ => return false,
+            };
+            if let Some(call) = Self::balance_of_call(value, file) {
+                locals.insert(name.clone(), call);
+            }
+            false
+        });
+        locals
+    }
+
+    fn check_loops(
+        stmt: &Statement,
+        file: &SolidityFile,
+        balance_locals: &HashMap<String, String>,
+        seen: &mut HashSet<(usize, Option<usize>)>,
+        locations: &mut Vec<Location>,
+    ) {
+        match stmt {
+            Statement::Block { statements, .. } => {
+                for s in statements {
+                    Self::check_loops(s, file, balance_locals, seen, locations);
+                }
+            }
+            Statement::If(_, _, then_stmt, else_stmt) => {
+                Self::check_loops(then_stmt, file, balance_locals, seen, locations);
+                if let Some(else_stmt) = else_stmt {
+                    Self::check_loops(else_stmt, file, balance_locals, seen, locations);
+                }
+            }
+            Statement::For(_, _, condition, _, Some(body)) => {
+                if let Some(condition) = condition {
+                    Self::check_loop(condition, body, file, balance_locals, seen, locations);
+                }
+                Self::check_loops(body, file, balance_locals, seen, locations);
+            }
+            Statement::While(_, condition, body) | Statement::DoWhile(_, body, condition) => {
+                Self::check_loop(condition, body, file, balance_locals, seen, locations);
+                Self::check_loops(body, file, balance_locals, seen, locations);
+            }
+            _ => {}
+        }
+    }
+
+    fn check_loop(
+        condition: &Expression,
+        body: &Statement,
+        file: &SolidityFile,
+        balance_locals: &HashMap<String, String>,
+        seen: &mut HashSet<(usize, Option<usize>)>,
+        locations: &mut Vec<Location>,
+    ) {
+        let Some(bound) = Self::balance_bound(condition, file, balance_locals) else {
+            return;
+        };
+        for location in Self::find_enumeration_calls(&bound, body, file) {
+            // Nested loops bounded by the same balance report a call once
+            if seen.insert((location.line, location.column)) {
+                locations.push(location);
+            }
+        }
+    }
+
+    /// The `balanceOf` call a loop condition is bounded by, directly or through a local.
+    fn balance_bound(
+        condition: &Expression,
+        file: &SolidityFile,
+        balance_locals: &HashMap<String, String>,
+    ) -> Option<String> {
+        if let Some(call) = Self::balance_of_call(condition, file) {
+            return Some(call);
+        }
+        let mut bound = None;
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            let Expression::Variable(id) = expr else {
+                return None;
+            };
+            let call = balance_locals.get(&id.name)?;
+            bound.get_or_insert_with(|| call.clone());
+            Some(id.loc)
+        };
+        let mut found = Vec::new();
+        find_locations_in_expression(condition, file, &mut predicate, &mut found);
+        bound
+    }
+
+    /// Source text of the first `balanceOf(...)` member call in `expr`.
+    fn balance_of_call(expr: &Expression, file: &SolidityFile) -> Option<String> {
+        let mut call = None;
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            let Expression::FunctionCall(loc, func, _) = expr else {
+                return None;
+            };
+            let Expression::MemberAccess(_, _, member) = func.as_ref() else {
+                return None;
+            };
+            if member.name != "balanceOf" {
+                return None;
+            }
+            call.get_or_insert_with(|| expr.to_string());
+            Some(*loc)
+        };
+        let mut found = Vec::new();
+        find_locations_in_expression(expr, file, &mut predicate, &mut found);
+        call
+    }
+
+    fn find_enumeration_calls(bound: &str, body: &Statement, file: &SolidityFile) -> Vec<Location> {
+        let mut messages = Vec::new();
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            let Expression::FunctionCall(loc, func, _) = expr else {
+                return None;
+            };
+            let Expression::MemberAccess(_, _, member) = func.as_ref() else {
+                return None;
+            };
+            if !ENUMERATION_CALLS.contains(&member.name.as_str()) {
+                return None;
+            }
+            messages.push(format!(
+                "`{}` called on every iteration of a loop bounded by `{}`",
+                member.name, bound
+            ));
+            Some(*loc)
+        };
+
+        let mut locations = Vec::new();
+        find_locations_in_statement(body, file, &mut predicate, &mut locations);
+        locations
+            .into_iter()
+            .zip(messages)
+            .map(|(location, message)| location.with_message(message))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::run_detector_on_code;
+
+    #[test]
+    fn test_owner_token_enumeration() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            interface IERC721Enumerable {
+                function balanceOf(address owner) external view returns (uint256);
+                function tokenOfOwnerByIndex(address owner, uint256 index) external view returns (uint256);
+                function tokenByIndex(uint256 index) external view returns (uint256);
+            }
+
+            contract Staking {
+                IERC721Enumerable nft;
+                mapping(uint256 => uint256) rewards;
+
+                function claimAll() external {
+                    for (uint256 i; i < nft.balanceOf(msg.sender); i++) {
+                        uint256 id = nft.tokenOfOwnerByIndex(msg.sender, i);  // Positive
+                        rewards[id] = 0;
+                    }
+                }
+
+                function sweep(address owner) external {
+                    uint256 count = nft.balanceOf(owner);
+                    uint256 i;
+                    while (i < count) {
+                        rewards[nft.tokenByIndex(i)] += 1;                    // Positive
+                        i++;
+                    }
+                }
+
+                function claimFirst() external {
+                    for (uint256 i; i < 10; i++) {
+                        rewards[nft.tokenOfOwnerByIndex(msg.sender, i)] = 0;  // Negative: constant bound
+                    }
+                }
+
+                function claimOne() external {
+                    rewards[nft.tokenOfOwnerByIndex(msg.sender, 0)] = 0;      // Negative: no loop
+                }
+
+                function tokensOf(address owner) external view returns (uint256[] memory ids) {
+                    ids = new uint256[](nft.balanceOf(owner));
+                    for (uint256 i; i < nft.balanceOf(owner); i++) {
+                        ids[i] = nft.tokenOfOwnerByIndex(owner, i);           // Negative: off-chain view
+                    }
+                }
+            }
+        "#;
+        let detector = Arc::new(OwnerTokenEnumerationDetector::default());
+        let locations = run_detector_on_code(detector, code, "staking.sol");
+
+        let lines: Vec<_> = locations.iter().map(|l| l.line).collect();
+        assert_eq!(lines, [16, 25]);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some(
+                "`tokenOfOwnerByIndex` called on every iteration of a loop bounded by \
+                 `nft.balanceOf(msg.sender)`"
+            )
+        );
+        assert_eq!(
+            locations[1].message.as_deref(),
+            Some("`tokenByIndex` called on every iteration of a loop bounded by `nft.balanceOf(owner)`")
+        );
+    }
+}
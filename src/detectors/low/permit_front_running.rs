@@ -0,0 +1,217 @@
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::category::Category;
+use crate::models::severity::Severity;
+use crate::models::{FindingData, SolidityFile};
+use crate::utils::ast_utils::{
+    expressions_equal, find_locations_in_statement, find_statement_types,
+};
+use crate::utils::location::loc_to_location;
+use solang_parser::pt::{CodeLocation, Expression, Loc, Statement};
+use std::sync::Arc;
+
+#[derive(Debug, Default)]
+pub struct PermitFrontRunningDetector;
+
+impl Detector for PermitFrontRunningDetector {
+    fn id(&self) -> &'static str {
+        "permit-front-running"
+    }
+
+    fn name(&self) -> &str {
+        "`permit` followed by `transferFrom` can be griefed by front-running"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Low
+    }
+
+    fn category(&self) -> Category {
+        Category::DenialOfService
+    }
+
+    fn description(&self) -> &str {
+        "The function calls `permit` and then `transferFrom` on the same token. The permit \
+         signature is public once the transaction is in the mempool, so anyone can submit it first; \
+         the allowance is still granted, but the victim's `permit` call reverts on the used nonce \
+         and takes the whole transaction with it. Wrap `permit` in a try/catch, or skip it when \
+         the allowance is already sufficient."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad - a front-run permit makes this revert
+function depositWithPermit(uint256 amount, uint256 deadline, uint8 v, bytes32 r, bytes32 s) external {
+    token.permit(msg.sender, address(this), amount, deadline, v, r, s);
+    token.transferFrom(msg.sender, address(this), amount);
+}
+
+// Good
+function depositWithPermit(uint256 amount, uint256 deadline, uint8 v, bytes32 r, bytes32 s) external {
+    try token.permit(msg.sender, address(this), amount, deadline, v, r, s) {} catch {}
+    token.transferFrom(msg.sender, address(this), amount);
+}
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_function(move |func_def, file, _context| {
+            let Some(body) = &func_def.body else {
+                return Vec::new();
+            };
+
+            let permits = Self::calls_to(body, file, &["permit"]);
+            if permits.is_empty() {
+                return Vec::new();
+            }
+            let transfers = Self::calls_to(body, file, &["transferFrom", "safeTransferFrom"]);
+            let allowance_checks = Self::calls_to(body, file, &["allowance"]);
+            let guarded = Self::try_expressions(body, file);
+
+            let mut findings = Vec::new();
+            for (permit_loc, token) in &permits {
+                if guarded
+                    .iter()
+                    .any(|loc| loc.start() <= permit_loc.start() && permit_loc.end() <= loc.end())
+                {
+                    continue;
+                }
+
+                let same_token = |other: &Expression| expressions_equal(other, token);
+                let griefable = transfers.iter().any(|(transfer_loc, transfer_token)| {
+                    transfer_loc.start() > permit_loc.start()
+                        && same_token(transfer_token)
+                        && !allowance_checks.iter().any(|(check_loc, check_token)| {
+                            check_loc.start() < transfer_loc.start() && same_token(check_token)
+                        })
+                });
+                if griefable {
+                    findings.push(FindingData {
+                        detector_id: self.id(),
+                        location: loc_to_location(permit_loc, file).with_message(format!(
+                            "`{}.permit` can be front-run with the same signature, reverting the \
+                             `transferFrom` that follows; wrap it in try/catch or check the \
+                             allowance first",
+                            token
+                        )),
+                    });
+                }
+            }
+            findings
+        });
+    }
+}
+
+impl PermitFrontRunningDetector {
+    /// Calls to any of `methods`, with the token they are called on (casts removed).
+    fn calls_to(body: &Statement, file: &SolidityFile, methods: &[&str]) -> Vec<(Loc, Expression)> {
+        let mut calls = Vec::new();
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            let Expression::FunctionCall(loc, callee, _) = expr else {
+                return None;
+            };
+            match callee.as_ref() {
+                Expression::MemberAccess(_, receiver, member)
+                    if methods.contains(&member.name.as_str()) =>
+                {
+                    calls.push((*loc, Self::strip_casts(receiver).clone()));
+                    Some(*loc)
+                }
+                _ => None,
+            }
+        };
+        let mut found = Vec::new();
+        find_locations_in_statement(body, file, &mut predicate, &mut found);
+        calls
+    }
+
+    /// Locations of the calls guarded by `try`.
+    fn try_expressions(body: &Statement, file: &SolidityFile) -> Vec<Loc> {
+        let mut locs = Vec::new();
+        find_statement_types(body, file, "", |stmt| {
+            if let Statement::Try(_, expr, _, _) = stmt {
+                locs.push(expr.loc());
+            }
+            false
+        });
+        locs
+    }
+
+    /// `IERC20Permit(address(token))` -> `token`.
+    fn strip_casts(expr: &Expression) -> &Expression {
+        match expr {
+            Expression::FunctionCall(_, func, args)
+                if args.len() == 1
+                    && matches!(
+                        func.as_ref(),
+                        Expression::Variable(_) | Expression::Type(..)
+                    ) =>
+            {
+                Self::strip_casts(&args[0])
+            }
+            Expression::Parenthesis(_, inner) => Self::strip_casts(inner),
+            _ => expr,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::run_detector_on_code;
+
+    #[test]
+    fn test_permit_front_running() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            contract Vault {
+                IERC20 token;
+                IERC20 other;
+
+                function bare(uint256 amount, uint256 deadline, uint8 v, bytes32 r, bytes32 s) external {
+                    token.permit(msg.sender, address(this), amount, deadline, v, r, s);
+                    token.transferFrom(msg.sender, address(this), amount);
+                }
+
+                function cast(address asset, uint256 amount, uint8 v, bytes32 r, bytes32 s) external {
+                    IERC20Permit(asset).permit(msg.sender, address(this), amount, block.timestamp, v, r, s);
+                    IERC20(asset).safeTransferFrom(msg.sender, address(this), amount);
+                }
+
+                function wrapped(uint256 amount, uint256 deadline, uint8 v, bytes32 r, bytes32 s) external {
+                    try token.permit(msg.sender, address(this), amount, deadline, v, r, s) {} catch {}
+                    token.transferFrom(msg.sender, address(this), amount);
+                }
+
+                function checked(uint256 amount, uint256 deadline, uint8 v, bytes32 r, bytes32 s) external {
+                    if (token.allowance(msg.sender, address(this)) < amount) {
+                        token.permit(msg.sender, address(this), amount, deadline, v, r, s);
+                    }
+                    token.transferFrom(msg.sender, address(this), amount);
+                }
+
+                function differentToken(uint256 amount, uint256 deadline, uint8 v, bytes32 r, bytes32 s) external {
+                    token.permit(msg.sender, address(this), amount, deadline, v, r, s);
+                    other.transferFrom(msg.sender, address(this), amount);
+                }
+            }
+        "#;
+        let detector = Arc::new(PermitFrontRunningDetector::default());
+        let locations = run_detector_on_code(detector, code, "vault.sol");
+
+        assert_eq!(locations.len(), 2);
+        assert_eq!(locations[0].line, 9);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some(
+                "`token.permit` can be front-run with the same signature, reverting the \
+                 `transferFrom` that follows; wrap it in try/catch or check the allowance first"
+            )
+        );
+        assert_eq!(locations[1].line, 14);
+    }
+}
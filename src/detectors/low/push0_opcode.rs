@@ -1,5 +1,6 @@
 use crate::core::visitor::ASTVisitor;
-use crate::detectors::Detector;
+use crate::detectors::{Detector, L2_CHAINS};
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::models::FindingData;
 use crate::utils::location::loc_to_location;
@@ -24,6 +25,10 @@ impl Detector for Push0OpcodeDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::Compatibility
+    }
+
     fn description(&self) -> &str {
         "Solidity 0.8.20 switches the default EVM version to Shanghai, which includes the PUSH0 \
          opcode. This opcode may not be implemented on all L2 chains, causing deployment failures. \
@@ -43,6 +48,10 @@ pragma solidity ^0.8.19;
         )
     }
 
+    fn relevant_chains(&self) -> &'static [&'static str] {
+        L2_CHAINS
+    }
+
     fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
         visitor.on_source_unit(move |source_unit, file, _context| {
             // Skip files with interfaces or abstract contracts (per original logic)
@@ -1,5 +1,6 @@
 use crate::core::visitor::ASTVisitor;
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::models::FindingData;
 use crate::utils::location::loc_to_location;
@@ -22,6 +23,10 @@ impl Detector for RenounceWhilePausedDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::AccessControl
+    }
+
     fn description(&self) -> &str {
         "The contract owner is not prevented from renouncing ownership while the contract is paused. \
          If the owner renounces while paused, any user assets stored in the protocol could be locked \
@@ -0,0 +1,305 @@
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::category::Category;
+use crate::models::scope::VariableVisibility;
+use crate::models::severity::Severity;
+use crate::models::FindingData;
+use crate::utils::ast_utils::collect_local_declaration_locs;
+use crate::utils::location::loc_to_location;
+use solang_parser::pt::ContractPart;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Globals and builtin functions a declaration can hide
+const BUILTINS: &[&str] = &[
+    "abi",
+    "addmod",
+    "assert",
+    "block",
+    "blockhash",
+    "ecrecover",
+    "gasleft",
+    "keccak256",
+    "msg",
+    "mulmod",
+    "now",
+    "require",
+    "revert",
+    "ripemd160",
+    "selfdestruct",
+    "sha256",
+    "sha3",
+    "suicide",
+    "super",
+    "this",
+    "tx",
+];
+
+#[derive(Debug, Default)]
+pub struct ShadowingDetector;
+
+impl Detector for ShadowingDetector {
+    fn id(&self) -> &'static str {
+        "shadowing"
+    }
+
+    fn name(&self) -> &str {
+        "Declarations shadowing state variables or builtin symbols"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Low
+    }
+
+    fn category(&self) -> Category {
+        Category::CodeQuality
+    }
+
+    fn version(&self) -> u32 {
+        2
+    }
+
+    fn description(&self) -> &str {
+        "A parameter, return variable or local variable named like a state variable of the \
+         contract or one of its bases hides that variable in the function, so an assignment \
+         meant for storage only changes the local copy. Naming one after a builtin such as \
+         `now`, `msg` or `require` hides the builtin in the same way. A state variable that \
+         redeclares a base contract's state variable (allowed before Solidity 0.6) is worse: \
+         the contract gets two storage slots with the same name, and the base's functions keep \
+         using the other one."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad - `owner` only assigns the parameter
+contract Vault is Ownable {
+    function setOwner(address owner) external {
+        owner = owner;
+    }
+}
+
+// Good
+contract Vault is Ownable {
+    function setOwner(address newOwner) external {
+        owner = newOwner;
+    }
+}
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_contract(move |contract_def, file, context| {
+            let Some(contract_name) = contract_def.name.as_ref() else {
+                return Vec::new();
+            };
+            let qualified_name = context.get_qualified_name_for_contract(&contract_name.name);
+            let Some(contract) = context.get_contract(&qualified_name) else {
+                return Vec::new();
+            };
+
+            // State variable names, with the contract declaring them. Private variables of
+            // bases are not visible here, so nothing can shadow them
+            let mut inherited: HashMap<&str, &str> = HashMap::new();
+            for base_name in &contract.inheritance_chain {
+                if let Some(base) = context.get_contract(base_name) {
+                    for var in &base.state_variables {
+                        if var.visibility != VariableVisibility::Private {
+                            inherited.insert(var.name.as_str(), base.name.as_str());
+                        }
+                    }
+                }
+            }
+
+            let mut findings = Vec::new();
+            let mut state_vars = inherited.clone();
+            for var in &contract.state_variables {
+                if let Some(base) = inherited.get(var.name.as_str()) {
+                    if !var.is_constant {
+                        findings.push(FindingData {
+                            detector_id: self.id(),
+                            location: var.loc.clone().with_message(format!(
+                                "State variable `{}` redeclares `{}.{}`: the contract has two \
+                                 storage slots named `{}`",
+                                var.name, base, var.name, var.name
+                            )),
+                        });
+                    }
+                }
+                state_vars.insert(var.name.as_str(), contract.name.as_str());
+            }
+
+            for part in &contract_def.parts {
+                let ContractPart::FunctionDefinition(func_def) = part else {
+                    continue;
+                };
+                let mut declarations: Vec<_> = func_def
+                    .params
+                    .iter()
+                    .chain(&func_def.returns)
+                    .filter_map(|(_, param)| param.as_ref()?.name.as_ref())
+                    .map(|name| (name.name.clone(), name.loc))
+                    .collect();
+                if let Some(body) = &func_def.body {
+                    collect_local_declaration_locs(body, &mut declarations);
+                }
+
+                for (name, loc) in declarations {
+                    let message = if BUILTINS.contains(&name.as_str()) {
+                        format!("`{}` shadows the builtin symbol", name)
+                    } else if let Some(declared_in) = state_vars.get(name.as_str()) {
+                        format!(
+                            "`{}` shadows state variable `{}.{}`",
+                            name, declared_in, name
+                        )
+                    } else {
+                        continue;
+                    };
+                    findings.push(FindingData {
+                        detector_id: self.id(),
+                        location: loc_to_location(&loc, file).with_message(message),
+                    });
+                }
+            }
+
+            findings
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::core::engine::AnalysisEngine;
+    use crate::utils::test_utils::{run_detector_on_code, write_fixture_project};
+
+    #[test]
+    fn test_detects_shadowed_state_variables_and_builtins() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            contract Vault {
+                address owner;
+                uint256 fee;
+
+                function setOwner(address owner) external {
+                    owner = owner;
+                }
+
+                function quote(uint256 amount) external view returns (uint256 fee) {
+                    uint256 now = block.timestamp;
+                    (uint256 a, uint256 msg) = (amount, 1);
+                    fee = amount + a + msg + now;
+                }
+
+                function setFee(uint256 newFee) external {
+                    fee = newFee;
+                }
+            }
+        "#;
+        let detector = Arc::new(ShadowingDetector::default());
+        let locations = run_detector_on_code(detector, code, "test.sol");
+        let found: Vec<_> = locations
+            .iter()
+            .map(|l| (l.line, l.message.as_deref().unwrap()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (8, "`owner` shadows state variable `Vault.owner`"),
+                (12, "`fee` shadows state variable `Vault.fee`"),
+                (13, "`now` shadows the builtin symbol"),
+                (14, "`msg` shadows the builtin symbol"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ignores_private_base_variables() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            contract Base {
+                uint256 private secret;
+                uint256 internal limit;
+            }
+
+            contract Vault is Base {
+                function set(uint256 secret, uint256 limit) external {}
+            }
+        "#;
+        let detector = Arc::new(ShadowingDetector::default());
+        let locations = run_detector_on_code(detector, code, "test.sol");
+        let found: Vec<_> = locations
+            .iter()
+            .map(|l| (l.line, l.message.as_deref().unwrap()))
+            .collect();
+        assert_eq!(found, [(10, "`limit` shadows state variable `Base.limit`")]);
+    }
+
+    #[test]
+    fn test_detects_shadowing_across_files() {
+        let root = write_fixture_project(
+            "shadowing",
+            &[
+                ("foundry.toml", "[profile.default]\n"),
+                (
+                    "src/Base.sol",
+                    "pragma solidity ^0.5.0;\n\
+                     contract Base {\n\
+                     address public owner;\n\
+                     uint256 internal totalShares;\n\
+                     }\n",
+                ),
+                (
+                    "src/Vault.sol",
+                    "pragma solidity ^0.5.0;\n\
+                     import \"./Base.sol\";\n\
+                     contract Vault is Base {\n\
+                     address public owner;\n\
+                     function deposit(uint256 totalShares) external {}\n\
+                     function withdraw(uint256 shares) external {}\n\
+                     }\n",
+                ),
+            ],
+        );
+        let config = Config {
+            scope: vec![root.join("src")],
+            ..Config::default()
+        };
+        let mut engine = AnalysisEngine::new(&config);
+        engine
+            .register_detector(Arc::new(ShadowingDetector::default()))
+            .unwrap();
+        let report = engine.analyze().expect("Analysis failed");
+
+        let mut found: Vec<_> = report
+            .findings
+            .iter()
+            .flat_map(|f| &f.locations)
+            .map(|l| (l.file.as_str(), l.line, l.message.as_deref().unwrap()))
+            .collect();
+        found.sort();
+        assert_eq!(
+            found,
+            [
+                (
+                    "src/Vault.sol",
+                    4,
+                    "State variable `owner` redeclares `Base.owner`: the contract has two storage \
+                     slots named `owner`"
+                ),
+                (
+                    "src/Vault.sol",
+                    5,
+                    "`totalShares` shadows state variable `Base.totalShares`"
+                ),
+            ]
+        );
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
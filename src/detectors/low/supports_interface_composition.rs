@@ -0,0 +1,310 @@
+use crate::core::context::AnalysisContext;
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::category::Category;
+use crate::models::severity::Severity;
+use crate::models::{FindingData, SolidityFile};
+use crate::utils::ast_utils::find_locations_in_statement;
+use crate::utils::location::loc_to_location;
+use solang_parser::pt::{ContractPart, ContractTy, Expression, Loc};
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+/// Base contract name prefixes of standards that register interface ids through ERC-165.
+/// `Upgradeable` variants are matched after stripping the suffix.
+const ERC165_STANDARDS: [&str; 8] = [
+    "ERC721",
+    "ERC1155",
+    "ERC2981",
+    "ERC4906",
+    "ERC1363",
+    "AccessControl",
+    "Governor",
+    "ERC1271",
+];
+
+#[derive(Debug, Default)]
+pub struct SupportsInterfaceCompositionDetector;
+
+impl Detector for SupportsInterfaceCompositionDetector {
+    fn id(&self) -> &'static str {
+        "supports-interface-composition"
+    }
+
+    fn name(&self) -> &str {
+        "`supportsInterface` does not combine all inherited standards"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Low
+    }
+
+    fn category(&self) -> Category {
+        Category::Compatibility
+    }
+
+    fn description(&self) -> &str {
+        "The contract inherits several standards that each answer ERC-165 queries (e.g. ERC721 and \
+         ERC2981), but either does not override `supportsInterface` or overrides it without \
+         calling `super.supportsInterface`. Interface ids of the skipped bases are then reported \
+         as unsupported, and marketplaces or other integrators silently ignore the feature \
+         (royalties, access control, ...). Override `supportsInterface` and delegate to \
+         `super.supportsInterface` so every base in the linearization is consulted."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad - ERC2981's interface id is never reported
+contract Collection is ERC721, ERC2981 {
+    function supportsInterface(bytes4 interfaceId)
+        public view override(ERC721, ERC2981) returns (bool)
+    {
+        return ERC721.supportsInterface(interfaceId);
+    }
+}
+
+// Good
+contract Collection is ERC721, ERC2981 {
+    function supportsInterface(bytes4 interfaceId)
+        public view override(ERC721, ERC2981) returns (bool)
+    {
+        return super.supportsInterface(interfaceId);
+    }
+}
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_contract(move |contract_def, file, context| {
+            if matches!(
+                contract_def.ty,
+                ContractTy::Interface(_) | ContractTy::Library(_)
+            ) {
+                return Vec::new();
+            }
+            let Some(contract_name) = &contract_def.name else {
+                return Vec::new();
+            };
+
+            // Standards reached through each direct base
+            let bases: Vec<(String, BTreeSet<&'static str>)> = contract_def
+                .base
+                .iter()
+                .filter_map(|base| base.name.identifiers.last())
+                .map(|id| (id.name.clone(), Self::standards_of(&id.name, context)))
+                .filter(|(_, standards)| !standards.is_empty())
+                .collect();
+            let combined: BTreeSet<&str> = bases.iter().flat_map(|(_, s)| s.clone()).collect();
+            if combined.len() < 2 {
+                return Vec::new();
+            }
+
+            let override_def = contract_def.parts.iter().find_map(|part| match part {
+                ContractPart::FunctionDefinition(func_def)
+                    if func_def
+                        .name
+                        .as_ref()
+                        .is_some_and(|n| n.name == "supportsInterface") =>
+                {
+                    Some(func_def)
+                }
+                _ => None,
+            });
+
+            let Some(func_def) = override_def else {
+                // A single base that already combines every standard needs no further override
+                if bases.iter().any(|(_, s)| s.len() == combined.len()) {
+                    return Vec::new();
+                }
+                let standards = combined.into_iter().collect::<Vec<_>>().join(", ");
+                return FindingData {
+                    detector_id: self.id(),
+                    location: loc_to_location(&contract_def.loc, file).with_message(format!(
+                        "`{}` inherits {} but does not override `supportsInterface` to combine \
+                         them",
+                        contract_name.name, standards
+                    )),
+                }
+                .into();
+            };
+            let Some(body) = &func_def.body else {
+                return Vec::new();
+            };
+
+            let called = Self::supports_interface_receivers(body, file);
+            if called.contains("super") {
+                return Vec::new();
+            }
+            let skipped: Vec<&str> = bases
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .filter(|name| !called.contains(*name))
+                .collect();
+            if skipped.is_empty() {
+                return Vec::new();
+            }
+
+            FindingData {
+                detector_id: self.id(),
+                location: loc_to_location(&func_def.loc, file).with_message(format!(
+                    "`supportsInterface` does not call `super.supportsInterface`; interfaces of {} \
+                     are not reported",
+                    skipped.join(", ")
+                )),
+            }
+            .into()
+        });
+    }
+}
+
+impl SupportsInterfaceCompositionDetector {
+    /// Standards implemented by `base` itself or anything it inherits.
+    fn standards_of(base: &str, context: &AnalysisContext) -> BTreeSet<&'static str> {
+        let qualified_name = context.get_qualified_name_for_contract(base);
+        let inherited = context
+            .get_contract(&qualified_name)
+            .map(|info| info.inheritance_chain.clone())
+            .unwrap_or_default();
+
+        std::iter::once(base)
+            .chain(inherited.iter().map(|q| q.rsplit(':').next().unwrap_or(q)))
+            .filter_map(Self::standard_of)
+            .collect()
+    }
+
+    fn standard_of(contract_name: &str) -> Option<&'static str> {
+        let name = contract_name
+            .strip_suffix("Upgradeable")
+            .unwrap_or(contract_name);
+        ERC165_STANDARDS
+            .iter()
+            .find(|standard| name.starts_with(*standard))
+            .copied()
+    }
+
+    /// Receivers of `X.supportsInterface(...)` calls, e.g. `super` or `ERC721`.
+    fn supports_interface_receivers(
+        body: &solang_parser::pt::Statement,
+        file: &SolidityFile,
+    ) -> BTreeSet<String> {
+        let mut receivers = BTreeSet::new();
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            match expr {
+                Expression::MemberAccess(loc, base, member)
+                    if member.name == "supportsInterface" =>
+                {
+                    if let Expression::Variable(id) = base.as_ref() {
+                        receivers.insert(id.name.clone());
+                    }
+                    Some(*loc)
+                }
+                _ => None,
+            }
+        };
+        let mut found = Vec::new();
+        find_locations_in_statement(body, file, &mut predicate, &mut found);
+        receivers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::run_detector_with_mock_inheritance;
+
+    const BASES: &str = r#"
+        pragma solidity ^0.8.0;
+
+        contract ERC165 {
+            function supportsInterface(bytes4 interfaceId) public view virtual returns (bool) {}
+        }
+        contract ERC721 is ERC165 {
+            function supportsInterface(bytes4 interfaceId) public view virtual override returns (bool) {}
+        }
+        contract ERC2981 is ERC165 {
+            function supportsInterface(bytes4 interfaceId) public view virtual override returns (bool) {}
+        }
+    "#;
+
+    fn run(contracts: &str, mock: Vec<(&str, Vec<&str>)>) -> Vec<crate::models::finding::Location> {
+        let mut mock_contracts = vec![("ERC721", vec!["ERC165"]), ("ERC2981", vec!["ERC165"])];
+        mock_contracts.extend(mock);
+        let detector = Arc::new(SupportsInterfaceCompositionDetector::default());
+        run_detector_with_mock_inheritance(
+            detector,
+            &format!("{}{}", BASES, contracts),
+            "collection.sol",
+            mock_contracts,
+        )
+    }
+
+    #[test]
+    fn test_combined_standards_without_super() {
+        let code = r#"
+        contract Missing is ERC721, ERC2981 {}
+
+        contract Partial is ERC721, ERC2981 {
+            function supportsInterface(bytes4 interfaceId)
+                public view override(ERC721, ERC2981) returns (bool)
+            {
+                return ERC721.supportsInterface(interfaceId);
+            }
+        }
+        "#;
+        let locations = run(
+            code,
+            vec![
+                ("Missing", vec!["ERC165", "ERC721", "ERC2981"]),
+                ("Partial", vec!["ERC165", "ERC721", "ERC2981"]),
+            ],
+        );
+
+        assert_eq!(locations.len(), 2);
+        assert_eq!(locations[0].line, 14);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some("`Missing` inherits ERC2981, ERC721 but does not override `supportsInterface` to combine them")
+        );
+        assert_eq!(locations[1].line, 17);
+        assert_eq!(
+            locations[1].message.as_deref(),
+            Some("`supportsInterface` does not call `super.supportsInterface`; interfaces of ERC2981 are not reported")
+        );
+    }
+
+    #[test]
+    fn test_skips_composed_and_single_standard() {
+        let code = r#"
+        contract Composed is ERC721, ERC2981 {
+            function supportsInterface(bytes4 interfaceId)
+                public view override(ERC721, ERC2981) returns (bool)
+            {
+                return super.supportsInterface(interfaceId);
+            }
+        }
+
+        contract Explicit is ERC721, ERC2981 {
+            function supportsInterface(bytes4 interfaceId)
+                public view override(ERC721, ERC2981) returns (bool)
+            {
+                return ERC721.supportsInterface(interfaceId) || ERC2981.supportsInterface(interfaceId);
+            }
+        }
+
+        contract Plain is ERC721 {}
+        "#;
+        let locations = run(
+            code,
+            vec![
+                ("Composed", vec!["ERC165", "ERC721", "ERC2981"]),
+                ("Explicit", vec!["ERC165", "ERC721", "ERC2981"]),
+                ("Plain", vec!["ERC165", "ERC721"]),
+            ],
+        );
+
+        assert!(locations.is_empty());
+    }
+}
@@ -1,5 +1,6 @@
 use crate::core::visitor::ASTVisitor;
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::models::FindingData;
 use crate::utils::location::loc_to_location;
@@ -21,6 +22,10 @@ impl Detector for SweepTokenAccountingDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::Token
+    }
+
     fn description(&self) -> &str {
         "There have been cases where tokens mistakenly had two addresses that could control their \
          balance, and transfers using one address impacted the balance of the other. Sweep/recover/rescue \
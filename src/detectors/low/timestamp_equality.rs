@@ -0,0 +1,112 @@
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::category::Category;
+use crate::models::severity::Severity;
+use crate::models::FindingData;
+use crate::utils::location::loc_to_location;
+use solang_parser::pt::Expression;
+use std::sync::Arc;
+
+#[derive(Debug, Default)]
+pub struct TimestampEqualityDetector;
+
+impl Detector for TimestampEqualityDetector {
+    fn id(&self) -> &'static str {
+        "timestamp-equality"
+    }
+
+    fn name(&self) -> &str {
+        "Strict equality comparison with `block.timestamp`"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Low
+    }
+
+    fn category(&self) -> Category {
+        Category::Validation
+    }
+
+    fn description(&self) -> &str {
+        "A transaction is only included in a block with a timestamp that matches an exact value \
+         by chance, and the validator can shift the timestamp by a few seconds. Code guarded by \
+         `block.timestamp == t` will almost never run. Compare with `>=` or `<=` to open a window \
+         that starts or ends at `t` instead."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad - only runs if a block lands exactly on unlockTime
+require(block.timestamp == unlockTime, "Locked");
+
+// Good
+require(block.timestamp >= unlockTime, "Locked");
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_expression(move |expr, file, _context| {
+            if let Expression::Equal(loc, left, right) = expr {
+                if Self::is_block_timestamp(left) || Self::is_block_timestamp(right) {
+                    return FindingData {
+                        detector_id: self.id(),
+                        location: loc_to_location(loc, file),
+                    }
+                    .into();
+                }
+            }
+            Vec::new()
+        });
+    }
+}
+
+impl TimestampEqualityDetector {
+    fn is_block_timestamp(expr: &Expression) -> bool {
+        match expr {
+            Expression::MemberAccess(_, obj, member) => {
+                matches!(obj.as_ref(), Expression::Variable(id) if id.name == "block")
+                    && member.name == "timestamp"
+            }
+            Expression::Variable(id) => id.name == "now",
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::run_detector_on_code;
+
+    #[test]
+    fn test_detects_timestamp_equality() {
+        let code = r#"
+            contract Vesting {
+                uint256 unlockTime;
+                uint256 deadline;
+                uint256 lastUpdate;
+
+                function release() external view {
+                    require(block.timestamp == unlockTime, "Locked");
+                    if (unlockTime + 1 days == block.timestamp) {}
+                }
+
+                function bid() external view {
+                    require(block.timestamp <= deadline, "Ended");
+                    require(block.timestamp != lastUpdate, "Same block");
+                }
+            }
+        "#;
+        let detector = Arc::new(TimestampEqualityDetector::default());
+        let locations = run_detector_on_code(detector, code, "test.sol");
+        assert_eq!(locations.len(), 2);
+        assert_eq!(locations[0].line, 8, "block.timestamp == unlockTime");
+        assert_eq!(
+            locations[1].line, 9,
+            "unlockTime + 1 days == block.timestamp"
+        );
+    }
+}
@@ -0,0 +1,249 @@
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::category::Category;
+use crate::models::severity::Severity;
+use crate::models::{FindingData, FunctionInfo, SolidityFile, StructInfo, TypeInfo};
+use crate::utils::ast_utils::{
+    build_variable_type_map, find_locations_in_statement, resolve_assignment_target_type,
+};
+use crate::utils::location::loc_to_location;
+use solang_parser::pt::{ContractPart, Expression, Loc};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[derive(Debug, Default)]
+pub struct TruncatingAssignmentDetector;
+
+impl Detector for TruncatingAssignmentDetector {
+    fn id(&self) -> &'static str {
+        "truncating-assignment"
+    }
+
+    fn name(&self) -> &str {
+        "Assignment of a wider integer expression to a narrower variable or struct field"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Low
+    }
+
+    fn category(&self) -> Category {
+        Category::Arithmetic
+    }
+
+    fn description(&self) -> &str {
+        "The value assigned is wider than the declared type of the variable or struct field it is \
+         stored in, typically full-width `uint256` math written into a packed `uint128`/`uint96` \
+         struct field through intermediate variables or helper functions. Values above the \
+         target's maximum are silently cut to their low bits. Bound the value first or convert \
+         it with SafeCast so an out-of-range result reverts."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+struct Position { uint96 shares; uint160 owner; }
+
+// Bad - the uint256 product does not fit in uint96
+pos.shares = amount * price;
+
+// Good
+pos.shares = SafeCast.toUint96(amount * price);
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_contract(move |contract_def, file, context| {
+            let Some(contract_name) = contract_def.name.as_ref() else {
+                return Vec::new();
+            };
+
+            let qualified_name = context.get_qualified_name_for_contract(&contract_name.name);
+            let state_var_types: HashMap<String, TypeInfo> = context
+                .get_all_state_variables(&qualified_name)
+                .into_iter()
+                .map(|v| (v.name.clone(), v.type_info.clone()))
+                .collect();
+            let mut structs = context.get_all_structs(&qualified_name);
+            structs.extend(file.structs.iter());
+            let functions = context.get_all_functions(&qualified_name);
+
+            let mut findings = Vec::new();
+
+            for part in &contract_def.parts {
+                let ContractPart::FunctionDefinition(func_def) = part else {
+                    continue;
+                };
+                let Some(body) = &func_def.body else {
+                    continue;
+                };
+
+                let var_types = build_variable_type_map(func_def, body, &state_var_types);
+                let resolver = TypeResolver {
+                    var_types: &var_types,
+                    structs: &structs,
+                    functions: &functions,
+                };
+
+                let mut truncations = Vec::new();
+                let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+                    let Expression::Assign(loc, target, value) = expr else {
+                        return None;
+                    };
+                    let target_type = resolver.target_type(target)?;
+                    let value_type = resolver.value_type(value)?;
+                    if !Self::is_narrowing(&value_type, &target_type) {
+                        return None;
+                    }
+                    truncations.push((*loc, target.to_string(), value_type, target_type));
+                    Some(*loc)
+                };
+                let mut found = Vec::new();
+                find_locations_in_statement(body, file, &mut predicate, &mut found);
+
+                for (loc, target, value_type, target_type) in truncations {
+                    findings.push(FindingData {
+                        detector_id: self.id(),
+                        location: loc_to_location(&loc, file).with_message(format!(
+                            "`{}` value is assigned to `{}` declared as `{}`",
+                            value_type, target, target_type
+                        )),
+                    });
+                }
+            }
+
+            findings
+        });
+    }
+}
+
+impl TruncatingAssignmentDetector {
+    fn is_narrowing(value: &TypeInfo, target: &TypeInfo) -> bool {
+        match (value, target) {
+            (TypeInfo::Uint(from), TypeInfo::Uint(to))
+            | (TypeInfo::Int(from), TypeInfo::Int(to)) => from > to,
+            _ => false,
+        }
+    }
+}
+
+struct TypeResolver<'a> {
+    var_types: &'a HashMap<String, TypeInfo>,
+    structs: &'a [&'a StructInfo],
+    functions: &'a [&'a FunctionInfo],
+}
+
+impl TypeResolver<'_> {
+    fn target_type(&self, target: &Expression) -> Option<TypeInfo> {
+        resolve_assignment_target_type(target, self.var_types, self.structs)
+    }
+
+    /// Type of an integer-valued expression, or `None` when it cannot be resolved. Literals are
+    /// unresolved since they take the type of the other operand.
+    fn value_type(&self, expr: &Expression) -> Option<TypeInfo> {
+        match expr {
+            Expression::Variable(_)
+            | Expression::MemberAccess(..)
+            | Expression::ArraySubscript(..) => self.target_type(expr),
+            Expression::Parenthesis(_, inner)
+            | Expression::UnaryPlus(_, inner)
+            | Expression::Negate(_, inner)
+            | Expression::BitwiseNot(_, inner) => self.value_type(inner),
+            // The left operand decides the result type of `**` and shifts
+            Expression::Power(_, left, _)
+            | Expression::ShiftLeft(_, left, _)
+            | Expression::ShiftRight(_, left, _) => self.value_type(left),
+            Expression::Add(_, left, right)
+            | Expression::Subtract(_, left, right)
+            | Expression::Multiply(_, left, right)
+            | Expression::Divide(_, left, right)
+            | Expression::Modulo(_, left, right)
+            | Expression::BitwiseAnd(_, left, right)
+            | Expression::BitwiseOr(_, left, right)
+            | Expression::BitwiseXor(_, left, right)
+            | Expression::ConditionalOperator(_, _, left, right) => {
+                Self::wider(self.value_type(left), self.value_type(right))
+            }
+            Expression::FunctionCall(_, func, _) => match func.as_ref() {
+                Expression::Type(_, ty) => Some(TypeInfo::from_solang_type(ty)),
+                Expression::Variable(id) => self.return_type(&id.name),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Single return type shared by every in-contract function named `name`.
+    fn return_type(&self, name: &str) -> Option<TypeInfo> {
+        let mut return_types = self.functions.iter().filter(|f| f.name == name).map(|f| {
+            match f.return_parameters.as_slice() {
+                [ret] => Some(TypeInfo::from_type_name(&ret.type_name)),
+                _ => None,
+            }
+        });
+        let first = return_types.next()??;
+        return_types
+            .all(|ty| ty.as_ref() == Some(&first))
+            .then_some(first)
+    }
+
+    fn wider(left: Option<TypeInfo>, right: Option<TypeInfo>) -> Option<TypeInfo> {
+        match (left, right) {
+            (Some(TypeInfo::Uint(l)), Some(TypeInfo::Uint(r))) => Some(TypeInfo::Uint(l.max(r))),
+            (Some(TypeInfo::Int(l)), Some(TypeInfo::Int(r))) => Some(TypeInfo::Int(l.max(r))),
+            (Some(ty), None) | (None, Some(ty)) => Some(ty),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::run_detector_on_code;
+
+    #[test]
+    fn test_truncating_assignment() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            contract Vault {
+                struct Position { uint96 shares; uint160 owner; }
+
+                mapping(uint256 => Position) positions;
+                uint128 totalShares;
+                uint256 totalAssets;
+
+                function deposit(uint256 id, uint256 amount, uint256 price) external {
+                    Position storage pos = positions[id];
+                    pos.shares = amount * price;                    // Positive
+                    positions[id].shares = uint96(amount);          // Negative: explicit cast
+                    totalShares = _shares(amount);                  // Positive
+                    totalAssets = amount * price;                   // Negative: same width
+                    totalShares += 1;                               // Negative: not plain assign
+                }
+
+                function sync(IPool pool) external {
+                    pool.config().shares = totalAssets;             // Negative: unknown target
+                    unknownVar = totalAssets;                       // Negative: unknown target
+                    totalShares = pool.totalShares();               // Negative: unknown value
+                }
+
+                function _shares(uint256 amount) internal pure returns (uint256) {
+                    return amount / 2;
+                }
+            }
+        "#;
+        let detector = Arc::new(TruncatingAssignmentDetector::default());
+        let locations = run_detector_on_code(detector, code, "vault.sol");
+
+        let lines: Vec<_> = locations.iter().map(|l| l.line).collect();
+        assert_eq!(lines, [13, 15]);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some("`uint256` value is assigned to `pos.shares` declared as `uint96`")
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::location::loc_to_location;
 use crate::{core::visitor::ASTVisitor, models::FindingData};
@@ -21,6 +22,10 @@ impl Detector for TwoStepOwnershipTransferDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::AccessControl
+    }
+
     fn description(&self) -> &str {
         "Recommend implementing a two step process where the owner nominates an account \
          and the nominated account needs to call an `acceptOwnership()` function for the \
@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::ast_utils::collect_function_calls;
 use crate::utils::location::loc_to_location;
@@ -23,6 +24,10 @@ impl Detector for UninitializedImplementationDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::Upgradeability
+    }
+
     fn description(&self) -> &str {
         "An uninitialized implementation contract can be taken over by an attacker, which may impact the proxy. \
          To prevent the implementation contract from being used, it's advisable to invoke the `_disableInitializers` \
@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::ast_utils::get_contract_info;
 use crate::utils::location::loc_to_location;
@@ -22,6 +23,10 @@ impl Detector for UninitializedUpgradeableDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::Upgradeability
+    }
+
     fn description(&self) -> &str {
         "Upgradeable contracts are initialized via an initializer function rather than by a \
          constructor. Leaving such a contract uninitialized may lead to it being taken over \
@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::location::loc_to_location;
 use crate::{core::visitor::ASTVisitor, models::FindingData};
@@ -21,6 +22,10 @@ impl Detector for UnlimitedGasCallDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::DenialOfService
+    }
+
     fn description(&self) -> &str {
         "There is no limit specified on the amount of gas used, so the recipient can use up all \
          of the transaction's gas, causing it to revert. Use `addr.call{gas: <amount>}(\"\")` or \
@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::location::loc_to_location;
 use crate::{core::visitor::ASTVisitor, models::FindingData};
@@ -21,6 +22,10 @@ impl Detector for UnsafeAbiEncodePackedDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::Cryptography
+    }
+
     fn description(&self) -> &str {
         "Use `abi.encode()` instead which will pad items to 32 bytes, which will prevent hash collisions \
          (e.g. `abi.encodePacked(0x123,0x456)` => `0x123456` => `abi.encodePacked(0x1,0x23456)`, but \
@@ -1,9 +1,10 @@
 use crate::core::visitor::ASTVisitor;
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::models::TypeInfo;
-use crate::utils::ast_utils::{collect_local_variables, find_in_statement, get_contract_info};
-use solang_parser::pt::{ContractPart, Expression, FunctionDefinition, FunctionTy, Statement};
+use crate::utils::ast_utils::{build_variable_type_map, find_in_statement, get_contract_info};
+use solang_parser::pt::{ContractPart, Expression, FunctionTy};
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -23,6 +24,10 @@ impl Detector for UnsafeDowncastDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::Arithmetic
+    }
+
     fn description(&self) -> &str {
         "Downcasting from `uint256`/`int256` in Solidity does not revert on overflow. This can result \
          in undesired exploitation or bugs, since developers usually assume that overflows raise errors. \
@@ -74,7 +79,7 @@ function convert(uint256 value) public pure returns (uint128) {
                     };
 
                     // Build variable type map
-                    let var_types = Self::build_variable_type_map(func_def, body, &state_var_types);
+                    let var_types = build_variable_type_map(func_def, body, &state_var_types);
 
                     let findings = find_in_statement(body, file, self.id(), |expr| {
                         Self::is_unsafe_downcast(expr, &var_types)
@@ -93,44 +98,6 @@ impl UnsafeDowncastDetector {
     /// Bit sizes for int/uint types
     const MAX_BITS: u16 = 256;
 
-    fn build_variable_type_map(
-        func_def: &FunctionDefinition,
-        body: &Statement,
-        state_var_types: &HashMap<String, TypeInfo>,
-    ) -> HashMap<String, TypeInfo> {
-        let mut var_types = state_var_types.clone();
-
-        // Add function parameters
-        for (_loc, param_opt) in &func_def.params {
-            if let Some(param) = param_opt {
-                if let Some(name) = &param.name {
-                    let type_info = TypeInfo::from_expression(&param.ty);
-                    var_types.insert(name.name.clone(), type_info);
-                }
-            }
-        }
-
-        // Add return parameters
-        for (_loc, return_param_opt) in &func_def.returns {
-            if let Some(return_param) = return_param_opt {
-                if let Some(name) = &return_param.name {
-                    let type_info = TypeInfo::from_expression(&return_param.ty);
-                    var_types.insert(name.name.clone(), type_info);
-                }
-            }
-        }
-
-        // Add local variable declarations
-        collect_local_variables(body, &mut |decl| {
-            if let Some(name) = &decl.name {
-                let type_info = TypeInfo::from_expression(&decl.ty);
-                var_types.insert(name.name.clone(), type_info);
-            }
-        });
-
-        var_types
-    }
-
     fn is_unsafe_downcast(expr: &Expression, var_types: &HashMap<String, TypeInfo>) -> bool {
         let Expression::FunctionCall(_, func, args) = expr else {
             return false;
@@ -1,9 +1,10 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
-use crate::models::{FindingData, SolidityFile, TypeInfo};
-use crate::utils::ast_utils::{collect_local_variables, find_locations_in_statement, get_contract_info};
+use crate::models::{FindingData, TypeInfo};
+use crate::utils::ast_utils::{build_variable_type_map, find_in_statement, get_contract_info};
 use crate::core::visitor::ASTVisitor;
-use solang_parser::pt::{ContractPart, Expression, FunctionDefinition, FunctionTy, Loc, Statement};
+use solang_parser::pt::{ContractPart, Expression, FunctionTy, Loc, Statement};
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -16,33 +17,36 @@ impl Detector for UnsafeIntCastDetector {
     }
 
     fn name(&self) -> &str {
-        "Unsafe cast from `int` to `uint`"
+        "Unsafe cast between signed and unsigned integers"
     }
 
     fn severity(&self) -> Severity {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::Arithmetic
+    }
+
     fn description(&self) -> &str {
         "Casting a signed integer (`int`, `int256`, etc.) to an unsigned integer (`uint`, `uint256`, etc.) \
          is unsafe when the signed value is negative. Negative values will overflow to very large unsigned values. \
-         Use OpenZeppelin's `SafeCast` library or check that the value is non-negative before casting."
+         Likewise, casting an unsigned value to a signed type of the same or smaller width wraps values above \
+         the signed maximum into negatives. Use OpenZeppelin's `SafeCast` library, or check the sign \
+         (`require(x >= 0)`) or range (`require(x <= uint256(type(int256).max))`) before casting."
     }
 
     fn example(&self) -> Option<String> {
         Some(
             r#"```solidity
-// Bad - direct cast can overflow
-function convert(int256 value) public pure returns (uint256) {
-    return uint256(value);
-}
+// Bad - a negative Chainlink answer becomes a huge price
+(, int256 answer, , , ) = priceFeed.latestRoundData();
+uint256 price = uint256(answer);
 
-// Good - use SafeCast library
-import "@openzeppelin/contracts/utils/math/SafeCast.sol";
-
-function convert(int256 value) public pure returns (uint256) {
-    return SafeCast.toUint256(value);  // Reverts if negative
-}
+// Good - check the sign first, or use SafeCast
+(, int256 answer, , , ) = priceFeed.latestRoundData();
+require(answer > 0, "Invalid price");
+uint256 price = uint256(answer);
 ```"#
                 .to_string(),
         )
@@ -72,35 +76,27 @@ function convert(int256 value) public pure returns (uint256) {
                         continue;
                     };
 
-                    // Build variable type map
-                    let var_types = Self::build_variable_type_map(func_def, body, &state_var_types);
-
-                    let mut findings = Vec::new();
-                    let mut predicate = |expr: &Expression, _file: &SolidityFile| -> Option<Loc> {
-                        if let Expression::FunctionCall(loc, func, args) = expr {
-                            if let Expression::Type(_, ty) = func.as_ref() {
-                                let cast_type = TypeInfo::from_solang_type(ty);
-                                if cast_type.is_uint() && !args.is_empty() {
-                                    // Check if argument is an int variable
-                                    if let Some(var_name) = Self::get_variable_name(&args[0]) {
-                                        if let Some(var_type) = var_types.get(var_name) {
-                                            if var_type.is_int() {
-                                                return Some(loc.clone());
-                                            }
-                                        }
-                                    }
-                                }
+                    let var_types = build_variable_type_map(func_def, body, &state_var_types);
+                    let mut checks = Vec::new();
+                    Self::collect_bound_checks(body, &mut checks);
+
+                    let mut messages = Vec::new();
+                    let findings = find_in_statement(body, file, self.id(), |expr| {
+                        match Self::check_cast(expr, &var_types, &checks) {
+                            Some(message) => {
+                                messages.push(message);
+                                true
                             }
+                            None => false,
                         }
-                        None
-                    };
+                    });
 
-                    find_locations_in_statement(body, file, &mut predicate, &mut findings);
-
-                    all_findings.extend(findings.into_iter().map(|loc| FindingData {
-                        detector_id: self.id(),
-                        location: loc,
-                    }));
+                    all_findings.extend(findings.into_iter().zip(messages).map(
+                        |(finding, message)| FindingData {
+                            location: finding.location.with_message(message),
+                            ..finding
+                        },
+                    ));
                 }
             }
 
@@ -109,50 +105,162 @@ function convert(int256 value) public pure returns (uint256) {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Bound {
+    /// `x >= 0` / `x > 0` - the value is known to be non-negative
+    NonNegative,
+    /// `x <= limit` / `x < limit` - the value is known to be bounded from above
+    Upper,
+}
+
+#[derive(Debug)]
+struct BoundCheck {
+    variable: String,
+    bound: Bound,
+    offset: usize,
+}
+
 impl UnsafeIntCastDetector {
-    fn build_variable_type_map(
-        func_def: &FunctionDefinition,
-        body: &Statement,
-        state_var_types: &HashMap<String, TypeInfo>,
-    ) -> HashMap<String, TypeInfo> {
-        // Clonestate variables
-        let mut var_types = state_var_types.clone();
-
-        // Add function parameters
-        for (_loc, param_opt) in &func_def.params {
-            if let Some(param) = param_opt {
-                if let Some(name) = &param.name {
-                    let type_info = TypeInfo::from_expression(&param.ty);
-                    var_types.insert(name.name.clone(), type_info);
-                }
-            }
+    /// Returns a message if `expr` is a sign-changing cast of an unchecked variable.
+    fn check_cast(
+        expr: &Expression,
+        var_types: &HashMap<String, TypeInfo>,
+        checks: &[BoundCheck],
+    ) -> Option<String> {
+        let Expression::FunctionCall(loc, func, args) = expr else {
+            return None;
+        };
+        let Expression::Type(_, ty) = func.as_ref() else {
+            return None;
+        };
+        let [Expression::Variable(var)] = args.as_slice() else {
+            return None;
+        };
+        let source = var_types.get(&var.name)?;
+        let target = TypeInfo::from_solang_type(ty);
+
+        let (required, what) = match (source, &target) {
+            (TypeInfo::Int(_), TypeInfo::Uint(_)) => (Bound::NonNegative, "sign check"),
+            // uint128 -> int256 always fits, uint256 -> int256 does not
+            (TypeInfo::Uint(from), TypeInfo::Int(to)) if from >= to => (Bound::Upper, "range check"),
+            _ => return None,
+        };
+
+        if Self::is_checked_before(&var.name, required, *loc, checks) {
+            return None;
         }
 
-        // Add return parameters
-        for (_loc, return_param_opt) in &func_def.returns {
-            if let Some(return_param) = return_param_opt {
-                if let Some(name) = &return_param.name {
-                    let type_info = TypeInfo::from_expression(&return_param.ty);
-                    var_types.insert(name.name.clone(), type_info);
+        Some(format!(
+            "`{}` value `{}` cast to `{}` without a {}",
+            source, var.name, target, what
+        ))
+    }
+
+    fn is_checked_before(variable: &str, bound: Bound, loc: Loc, checks: &[BoundCheck]) -> bool {
+        checks
+            .iter()
+            .any(|c| c.variable == variable && c.bound == bound && c.offset < loc.start())
+    }
+
+    /// Collect bounds asserted by `require`/`assert` and by `if (...) revert`/`return` guards.
+    fn collect_bound_checks(stmt: &Statement, checks: &mut Vec<BoundCheck>) {
+        match stmt {
+            Statement::Block { statements, .. } => {
+                for s in statements {
+                    Self::collect_bound_checks(s, checks);
+                }
+            }
+            Statement::Expression(loc, Expression::FunctionCall(_, func, args)) => {
+                if let (Expression::Variable(id), Some(condition)) = (func.as_ref(), args.first()) {
+                    if id.name == "require" || id.name == "assert" {
+                        Self::collect_from_condition(condition, false, loc.start(), checks);
+                    }
                 }
             }
+            Statement::If(loc, condition, then_stmt, else_stmt) => {
+                if Self::exits(then_stmt) {
+                    Self::collect_from_condition(condition, true, loc.start(), checks);
+                }
+                Self::collect_bound_checks(then_stmt, checks);
+                if let Some(else_stmt) = else_stmt {
+                    Self::collect_bound_checks(else_stmt, checks);
+                }
+            }
+            Statement::For(_, _, _, _, Some(body))
+            | Statement::While(_, _, body)
+            | Statement::DoWhile(_, body, _) => {
+                Self::collect_bound_checks(body, checks);
+            }
+            _ => {}
         }
+    }
 
-        // Add local variable declarations
-        collect_local_variables(body, &mut |decl| {
-            if let Some(name) = &decl.name {
-                let type_info = TypeInfo::from_expression(&decl.ty);
-                var_types.insert(name.name.clone(), type_info);
+    /// `negated` is true for `if` guards, where the condition describes the rejected values.
+    fn collect_from_condition(
+        condition: &Expression,
+        negated: bool,
+        offset: usize,
+        checks: &mut Vec<BoundCheck>,
+    ) {
+        let (greater, smaller) = match condition {
+            Expression::Parenthesis(_, inner) => {
+                return Self::collect_from_condition(inner, negated, offset, checks);
             }
-        });
+            Expression::Not(_, inner) => {
+                return Self::collect_from_condition(inner, !negated, offset, checks);
+            }
+            Expression::And(_, left, right) if !negated => {
+                Self::collect_from_condition(left, negated, offset, checks);
+                return Self::collect_from_condition(right, negated, offset, checks);
+            }
+            Expression::Or(_, left, right) if negated => {
+                Self::collect_from_condition(left, negated, offset, checks);
+                return Self::collect_from_condition(right, negated, offset, checks);
+            }
+            Expression::More(_, left, right) | Expression::MoreEqual(_, left, right) => {
+                (left.as_ref(), right.as_ref())
+            }
+            Expression::Less(_, left, right) | Expression::LessEqual(_, left, right) => {
+                (right.as_ref(), left.as_ref())
+            }
+            _ => return,
+        };
+
+        let (greater, smaller) = if negated {
+            (smaller, greater)
+        } else {
+            (greater, smaller)
+        };
+
+        if let (Expression::Variable(var), true) = (greater, Self::is_zero(smaller)) {
+            checks.push(BoundCheck {
+                variable: var.name.clone(),
+                bound: Bound::NonNegative,
+                offset,
+            });
+        }
+        if let Expression::Variable(var) = smaller {
+            checks.push(BoundCheck {
+                variable: var.name.clone(),
+                bound: Bound::Upper,
+                offset,
+            });
+        }
+    }
 
-        var_types
+    fn is_zero(expr: &Expression) -> bool {
+        matches!(expr, Expression::NumberLiteral(_, value, _, _) if value == "0")
     }
 
-    fn get_variable_name(expr: &Expression) -> Option<&str> {
-        match expr {
-            Expression::Variable(id) => Some(&id.name),
-            _ => None,
+    /// Whether a guard branch leaves the function (revert or return).
+    fn exits(stmt: &Statement) -> bool {
+        match stmt {
+            Statement::Revert(..) | Statement::RevertNamedArgs(..) | Statement::Return(..) => true,
+            Statement::Expression(_, Expression::FunctionCall(_, func, _)) => {
+                matches!(func.as_ref(), Expression::Variable(id) if id.name == "revert")
+            }
+            Statement::Block { statements, .. } => statements.iter().any(Self::exits),
+            _ => false,
         }
     }
 }
@@ -199,4 +307,78 @@ mod tests {
         let locations = run_detector_on_code(detector, code, "test.sol");
         assert_eq!(locations.len(), 0);
     }
+
+    #[test]
+    fn test_chainlink_answer_sign_check() {
+        let code = r#"
+            contract Oracle {
+                AggregatorV3Interface feed;
+
+                function noCheck() external view returns (uint256) {
+                    (, int256 answer, , , ) = feed.latestRoundData();
+                    return uint256(answer);
+                }
+
+                function checked() external view returns (uint256) {
+                    (, int256 answer, , , ) = feed.latestRoundData();
+                    require(answer > 0, "bad price");
+                    return uint256(answer);
+                }
+
+                function guarded() external view returns (uint256) {
+                    (, int256 answer, , , ) = feed.latestRoundData();
+                    if (answer <= 0) revert InvalidPrice();
+                    return uint256(answer);
+                }
+
+                function checkedTooLate() external view returns (uint256) {
+                    (, int256 answer, , , ) = feed.latestRoundData();
+                    uint256 price = uint256(answer);
+                    require(answer >= 0);
+                    return price;
+                }
+            }
+        "#;
+        let detector = Arc::new(UnsafeIntCastDetector::default());
+        let locations = run_detector_on_code(detector, code, "oracle.sol");
+        assert_eq!(locations.len(), 2);
+        assert_eq!(locations[0].line, 7, "uint256(answer) without a check");
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some("`int256` value `answer` cast to `uint256` without a sign check")
+        );
+        assert_eq!(locations[1].line, 24, "check after the cast does not count");
+    }
+
+    #[test]
+    fn test_uint_to_int_range_check() {
+        let code = r#"
+            contract Test {
+                function noCheck(uint256 x) public pure returns (int256) {
+                    return int256(x);
+                }
+
+                function checked(uint256 x) public pure returns (int256) {
+                    require(x <= uint256(type(int256).max));
+                    return int256(x);
+                }
+
+                function widening(uint128 x) public pure returns (int256) {
+                    return int256(x);
+                }
+
+                function sameSign(uint128 x) public pure returns (uint256) {
+                    return uint256(x);
+                }
+            }
+        "#;
+        let detector = Arc::new(UnsafeIntCastDetector::default());
+        let locations = run_detector_on_code(detector, code, "test.sol");
+        assert_eq!(locations.len(), 1);
+        assert_eq!(locations[0].line, 4);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some("`uint256` value `x` cast to `int256` without a range check")
+        );
+    }
 }
@@ -1,9 +1,10 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::models::{FindingData, SolidityFile, TypeInfo};
-use crate::utils::ast_utils::{collect_local_variables, find_locations_in_statement, get_contract_info};
+use crate::utils::ast_utils::{build_variable_type_map, find_locations_in_statement, get_contract_info};
 use crate::core::visitor::ASTVisitor;
-use solang_parser::pt::{ContractPart, Expression, FunctionDefinition, Loc, Statement};
+use solang_parser::pt::{ContractPart, Expression, Loc};
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -23,6 +24,10 @@ impl Detector for UnsafeIntToUintCastDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::Arithmetic
+    }
+
     fn description(&self) -> &str {
         "The code casts an `int` to a `uint` before passing it to `Math.max()`. It seems as though the \
          `Math.max()` call is attempting to prevent values from being negative, but since the `int` is being \
@@ -74,7 +79,7 @@ function calculate(int256 value) public pure returns (uint256) {
                     };
 
                     // Build variable type map
-                    let var_types = Self::build_variable_type_map(func_def, body, &state_var_types);
+                    let var_types = build_variable_type_map(func_def, body, &state_var_types);
 
                     let mut findings = Vec::new();
                     let mut predicate = |expr: &Expression, _file: &SolidityFile| -> Option<Loc> {
@@ -107,44 +112,6 @@ function calculate(int256 value) public pure returns (uint256) {
 }
 
 impl UnsafeIntToUintCastDetector {
-    fn build_variable_type_map(
-        func_def: &FunctionDefinition,
-        body: &Statement,
-        state_var_types: &HashMap<String, TypeInfo>,
-    ) -> HashMap<String, TypeInfo> {
-        let mut var_types = state_var_types.clone();
-
-        // Add function parameters
-        for (_loc, param_opt) in &func_def.params {
-            if let Some(param) = param_opt {
-                if let Some(name) = &param.name {
-                    let type_info = TypeInfo::from_expression(&param.ty);
-                    var_types.insert(name.name.clone(), type_info);
-                }
-            }
-        }
-
-        // Add return parameters
-        for (_loc, return_param_opt) in &func_def.returns {
-            if let Some(return_param) = return_param_opt {
-                if let Some(name) = &return_param.name {
-                    let type_info = TypeInfo::from_expression(&return_param.ty);
-                    var_types.insert(name.name.clone(), type_info);
-                }
-            }
-        }
-
-        // Add local variable declarations
-        collect_local_variables(body, &mut |decl| {
-            if let Some(name) = &decl.name {
-                let type_info = TypeInfo::from_expression(&decl.ty);
-                var_types.insert(name.name.clone(), type_info);
-            }
-        });
-
-        var_types
-    }
-
     fn is_uint_cast_of_int(expr: &Expression, var_types: &HashMap<String, TypeInfo>) -> bool {
         if let Expression::FunctionCall(_, func, args) = expr {
             if let Expression::Type(_, ty) = func.as_ref() {
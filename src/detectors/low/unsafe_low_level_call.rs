@@ -1,5 +1,6 @@
 use crate::core::visitor::ASTVisitor;
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::models::FindingData;
 use crate::utils::location::loc_to_location;
@@ -22,6 +23,10 @@ impl Detector for UnsafeLowLevelCallDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::ExternalCall
+    }
+
     fn description(&self) -> &str {
         "Low-level `.call()` operates on address types and bypasses several Solidity safety mechanisms: \
         (1) No EXTCODESIZE check - calls to non-existent contracts succeed silently instead of reverting, \
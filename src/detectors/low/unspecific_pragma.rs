@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::location::loc_to_location;
 use crate::{core::visitor::ASTVisitor, models::FindingData};
@@ -21,6 +22,10 @@ impl Detector for UnspecificPragmaDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::Compatibility
+    }
+
     fn description(&self) -> &str {
         "Using floating or range pragma versions (^, >, >=, ~) can lead to unexpected behavior \
          if the contract is compiled with a different version than intended. Consider locking \
@@ -0,0 +1,333 @@
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::category::Category;
+use crate::models::severity::Severity;
+use crate::models::{FindingData, SolidityFile};
+use crate::utils::ast_utils::{
+    find_locations_in_expression, find_locations_in_statement, find_statement_types,
+    get_local_variable_names, is_external_call,
+};
+use crate::utils::location::loc_to_location;
+use solang_parser::pt::{ContractPart, Expression, Loc, Statement, StorageLocation};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+#[derive(Debug, Default)]
+pub struct UnvalidatedExternalStructDetector;
+
+impl Detector for UnvalidatedExternalStructDetector {
+    fn id(&self) -> &'static str {
+        "unvalidated-external-struct"
+    }
+
+    fn name(&self) -> &str {
+        "Struct returned by an external call is stored without validation"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Low
+    }
+
+    fn category(&self) -> Category {
+        Category::Validation
+    }
+
+    fn description(&self) -> &str {
+        "A memory struct is filled from an external call (a registry, oracle or factory) and its \
+         fields are written to storage without any `require`, `if` or validation call on them in \
+         between. The contract then persists whatever the other contract returns: a misconfigured \
+         or compromised registry can set fees above 100%, zero addresses or stale prices that \
+         outlive the call. Check the ranges of the fields before storing them. The check is \
+         heuristic: validation done inside the called contract is not visible here."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad - the registry decides the stored fee
+Config memory c = registry.getConfig(id);
+fee = c.fee;
+
+// Good
+Config memory c = registry.getConfig(id);
+require(c.fee <= MAX_FEE, "fee too high");
+fee = c.fee;
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_contract(move |contract_def, file, context| {
+            let Some(contract_name) = contract_def.name.as_ref() else {
+                return Vec::new();
+            };
+
+            let qualified_name = context.get_qualified_name_for_contract(&contract_name.name);
+            let state_vars: HashSet<&str> = context
+                .get_all_state_variables(&qualified_name)
+                .into_iter()
+                .map(|v| v.name.as_str())
+                .collect();
+            if state_vars.is_empty() {
+                return Vec::new();
+            }
+            let mut validators: HashSet<&str> = context
+                .get_all_functions(&qualified_name)
+                .into_iter()
+                .map(|f| f.name.as_str())
+                .collect();
+            validators.extend(["require", "assert"]);
+
+            let mut findings = Vec::new();
+
+            for part in &contract_def.parts {
+                let ContractPart::FunctionDefinition(func_def) = part else {
+                    continue;
+                };
+                let Some(body) = &func_def.body else {
+                    continue;
+                };
+
+                let fetched = Self::fetched_structs(body, file);
+                if fetched.is_empty() {
+                    continue;
+                }
+
+                let local_vars = get_local_variable_names(func_def, body);
+                let storage_vars: HashSet<&str> = state_vars
+                    .iter()
+                    .copied()
+                    .filter(|name| !local_vars.contains(*name))
+                    .collect();
+                let checks = Self::checks(body, file, &validators);
+
+                for (var, fetch_loc, call) in &fetched {
+                    for (write_loc, fields) in Self::storage_writes(body, file, var, &storage_vars)
+                    {
+                        if write_loc.start() < fetch_loc.end() {
+                            continue;
+                        }
+                        let validated = checks.iter().any(|(check_loc, check)| {
+                            check_loc.start() > fetch_loc.end()
+                                && check_loc.start() < write_loc.start()
+                                && Self::checks_fields(check, var, &fields, file)
+                        });
+                        if validated {
+                            continue;
+                        }
+
+                        findings.push(FindingData {
+                            detector_id: self.id(),
+                            location: loc_to_location(&write_loc, file).with_message(format!(
+                                "`{}` comes from `{}` and is stored without validating it",
+                                var, call
+                            )),
+                        });
+                    }
+                }
+            }
+
+            findings
+        });
+    }
+}
+
+impl UnvalidatedExternalStructDetector {
+    /// `Config memory c = registry.getConfig(id);` -> (`c`, declaration loc, the call).
+    fn fetched_structs(body: &Statement, file: &SolidityFile) -> Vec<(String, Loc, Expression)> {
+        let mut fetched = Vec::new();
+        find_statement_types(body, file, "", |stmt| {
+            if let Statement::VariableDefinition(loc, decl, Some(init)) = stmt {
+                let is_struct = matches!(
+                    decl.ty,
+                    Expression::Variable(_) | Expression::MemberAccess(..)
+                ) && matches!(decl.storage, Some(StorageLocation::Memory(_)));
+                if let (true, Some(name)) = (is_struct && is_external_call(init), &decl.name) {
+                    fetched.push((name.name.clone(), *loc, init.clone()));
+                }
+            }
+            false
+        });
+        fetched
+    }
+
+    /// Assignments to storage whose value reads `var`, with the fields of `var` they read
+    /// (empty when the whole struct is used).
+    fn storage_writes(
+        body: &Statement,
+        file: &SolidityFile,
+        var: &str,
+        storage_vars: &HashSet<&str>,
+    ) -> Vec<(Loc, Vec<String>)> {
+        let mut writes = Vec::new();
+        let mut predicate = |expr: &Expression, file: &SolidityFile| -> Option<Loc> {
+            let Expression::Assign(loc, target, value) = expr else {
+                return None;
+            };
+            let mut root = target.as_ref();
+            while let Expression::ArraySubscript(_, inner, _)
+            | Expression::MemberAccess(_, inner, _) = root
+            {
+                root = inner.as_ref();
+            }
+            match root {
+                Expression::Variable(id) if storage_vars.contains(id.name.as_str()) => {}
+                _ => return None,
+            }
+
+            let fields = Self::fields_read(value, var, file)?;
+            writes.push((*loc, fields));
+            Some(*loc)
+        };
+        let mut found = Vec::new();
+        find_locations_in_statement(body, file, &mut predicate, &mut found);
+        writes
+    }
+
+    /// Fields of `var` read in `expr`, an empty list if `var` is read as a whole, or `None` if it
+    /// is not read at all.
+    fn fields_read(expr: &Expression, var: &str, file: &SolidityFile) -> Option<Vec<String>> {
+        let mut fields = Vec::new();
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            match expr {
+                Expression::MemberAccess(_, base, member) => {
+                    if let Expression::Variable(id) = base.as_ref() {
+                        if id.name == var {
+                            fields.push(member.name.clone());
+                        }
+                    }
+                    None
+                }
+                Expression::Variable(id) if id.name == var => Some(id.loc),
+                _ => None,
+            }
+        };
+        let mut reads = Vec::new();
+        find_locations_in_expression(expr, file, &mut predicate, &mut reads);
+
+        // The base of every `c.field` is a read of `c` too; any other read uses the whole struct
+        if reads.len() > fields.len() {
+            fields.clear();
+        }
+        (!reads.is_empty()).then_some(fields)
+    }
+
+    /// Conditions of `if` and loops, and calls to `require`, `assert` or the contract's own
+    /// functions (which may validate their arguments), with their locations.
+    fn checks(
+        body: &Statement,
+        file: &SolidityFile,
+        validators: &HashSet<&str>,
+    ) -> Vec<(Loc, Expression)> {
+        let mut checks = Vec::new();
+        find_statement_types(body, file, "", |stmt| {
+            match stmt {
+                Statement::If(loc, cond, _, _)
+                | Statement::While(loc, cond, _)
+                | Statement::DoWhile(loc, _, cond) => checks.push((*loc, cond.clone())),
+                Statement::For(loc, _, Some(cond), _, _) => {
+                    checks.push((*loc, cond.as_ref().clone()))
+                }
+                _ => {}
+            }
+            false
+        });
+
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            match expr {
+                Expression::FunctionCall(loc, func, _)
+                    if matches!(func.as_ref(), Expression::Variable(id)
+                        if validators.contains(id.name.as_str())) =>
+                {
+                    checks.push((*loc, expr.clone()));
+                    Some(*loc)
+                }
+                _ => None,
+            }
+        };
+        let mut found = Vec::new();
+        find_locations_in_statement(body, file, &mut predicate, &mut found);
+        checks
+    }
+
+    /// Whether `check` reads `var` as a whole or any of the stored `fields` (any field of `var`
+    /// when the whole struct is stored).
+    fn checks_fields(
+        check: &Expression,
+        var: &str,
+        fields: &[String],
+        file: &SolidityFile,
+    ) -> bool {
+        let Some(checked) = Self::fields_read(check, var, file) else {
+            return false;
+        };
+        checked.is_empty() || fields.is_empty() || checked.iter().any(|f| fields.contains(f))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::run_detector_on_code;
+
+    #[test]
+    fn test_unvalidated_external_struct() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            contract Pool {
+                struct Config { uint256 fee; address treasury; }
+
+                IRegistry registry;
+                uint256 fee;
+                address treasury;
+                Config config;
+
+                event ConfigSeen(uint256 fee, address treasury);
+
+                function syncValidated(uint256 id) external {
+                    Config memory c = registry.getConfig(id);
+                    require(c.fee <= 1000, "fee too high");
+                    fee = c.fee;                                    // Negative: checked
+                }
+
+                function sync(uint256 id) external {
+                    Config memory c = registry.getConfig(id);
+                    fee = c.fee;                                    // Positive
+                    config = c;                                     // Positive
+                }
+
+                function peek(uint256 id) external {
+                    Config memory c = registry.getConfig(id);
+                    emit ConfigSeen(c.fee, c.treasury);             // Negative: not stored
+                }
+
+                function syncHelper(uint256 id) external {
+                    Config memory c = registry.getConfig(id);
+                    _validate(c);
+                    config = c;                                     // Negative: validated
+                }
+
+                function syncOtherField(uint256 id) external {
+                    Config memory c = registry.getConfig(id);
+                    if (c.treasury == address(0)) revert();
+                    fee = c.fee;                                    // Positive: fee unchecked
+                }
+
+                function _validate(Config memory c) internal pure {
+                    require(c.fee <= 1000);
+                }
+            }
+        "#;
+        let detector = Arc::new(UnvalidatedExternalStructDetector::default());
+        let locations = run_detector_on_code(detector, code, "pool.sol");
+
+        let lines: Vec<_> = locations.iter().map(|l| l.line).collect();
+        assert_eq!(lines, [22, 23, 40]);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some("`c` comes from `registry.getConfig(id)` and is stored without validating it")
+        );
+    }
+}
@@ -0,0 +1,362 @@
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::category::Category;
+use crate::models::severity::Severity;
+use crate::models::{FindingData, SolidityFile};
+use crate::utils::ast_utils::{
+    expressions_equal, find_locations_in_expression, find_locations_in_statement,
+    find_statement_types,
+};
+use crate::utils::location::loc_to_location;
+use solang_parser::pt::{
+    Expression, FunctionAttribute, FunctionDefinition, FunctionTy, Loc, Statement,
+};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Calls that add an account to a privileged set.
+const GRANT_FUNCTIONS: &[&str] = &["_grantRole", "grantRole", "_setupRole"];
+
+/// Calls whose result tells whether an account is already in a set.
+const MEMBERSHIP_FUNCTIONS: &[&str] = &["hasRole", "contains", "add"];
+
+#[derive(Debug, Default)]
+pub struct UnvalidatedPrivilegedArrayDetector;
+
+impl Detector for UnvalidatedPrivilegedArrayDetector {
+    fn id(&self) -> &'static str {
+        "unvalidated-privileged-array"
+    }
+
+    fn name(&self) -> &str {
+        "Privileged accounts set from an array parameter without zero-address or duplicate checks"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Low
+    }
+
+    fn category(&self) -> Category {
+        Category::Validation
+    }
+
+    fn description(&self) -> &str {
+        "A constructor or initializer loops over an array parameter and grants a role or sets an \
+         approval-like mapping for each element, without checking the element against \
+         `address(0)` or checking that it is not already set. A zero address or a repeated entry \
+         in the deployment arguments then goes through silently: a signer set smaller than \
+         intended, a threshold that can never be reached, or a role held by nobody. These \
+         functions usually run once and cannot be corrected later without a redeploy."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad - address(0) or a duplicate entry is accepted silently
+constructor(address[] memory signers) {
+    for (uint256 i; i < signers.length; ++i) {
+        isSigner[signers[i]] = true;
+    }
+}
+
+// Good
+constructor(address[] memory signers) {
+    for (uint256 i; i < signers.length; ++i) {
+        address signer = signers[i];
+        require(signer != address(0), "zero signer");
+        require(!isSigner[signer], "duplicate signer");
+        isSigner[signer] = true;
+    }
+}
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_function(move |func_def, file, _context| {
+            let Some(body) = &func_def.body else {
+                return Vec::new();
+            };
+            if !Self::is_constructor_or_initializer(func_def) {
+                return Vec::new();
+            }
+
+            let array_params: HashSet<&str> = func_def
+                .params
+                .iter()
+                .filter_map(|(_, param)| param.as_ref())
+                .filter(|param| matches!(param.ty, Expression::ArraySubscript(..)))
+                .filter_map(|param| param.name.as_ref().map(|name| name.name.as_str()))
+                .collect();
+            if array_params.is_empty() {
+                return Vec::new();
+            }
+
+            let mut loop_bodies = Vec::new();
+            find_statement_types(body, file, self.id(), |stmt| {
+                match stmt {
+                    Statement::For(_, _, _, _, Some(loop_body))
+                    | Statement::While(_, _, loop_body)
+                    | Statement::DoWhile(_, loop_body, _) => loop_bodies.push(loop_body.clone()),
+                    _ => {}
+                }
+                false
+            });
+
+            let mut reported = HashSet::new();
+            let mut findings = Vec::new();
+            for loop_body in &loop_bodies {
+                let elements = Self::element_expressions(loop_body, file, &array_params);
+                let is_element = |expr: &Expression| {
+                    elements
+                        .iter()
+                        .any(|element| expressions_equal(expr, element))
+                };
+
+                let grants = Self::grants(loop_body, file, &is_element);
+                if grants.is_empty() {
+                    continue;
+                }
+                if Self::has_zero_check(loop_body, file, &is_element)
+                    || Self::has_duplicate_check(loop_body, file, &is_element)
+                {
+                    continue;
+                }
+
+                for (loc, element) in grants {
+                    if !reported.insert(loc.start()) {
+                        continue;
+                    }
+                    findings.push(FindingData {
+                        detector_id: self.id(),
+                        location: loc_to_location(&loc, file).with_message(format!(
+                            "`{}` is added without a zero-address or duplicate check",
+                            element
+                        )),
+                    });
+                }
+            }
+
+            findings
+        });
+    }
+}
+
+impl UnvalidatedPrivilegedArrayDetector {
+    fn is_constructor_or_initializer(func_def: &FunctionDefinition) -> bool {
+        if func_def.ty == FunctionTy::Constructor {
+            return true;
+        }
+        let is_init_name = func_def
+            .name
+            .as_ref()
+            .is_some_and(|name| name.name.starts_with("init"));
+        let has_initializer_modifier = func_def.attributes.iter().any(|attr| match attr {
+            FunctionAttribute::BaseOrModifier(_, base) => base
+                .name
+                .identifiers
+                .iter()
+                .any(|id| id.name == "initializer" || id.name == "reinitializer"),
+            _ => false,
+        });
+        is_init_name || has_initializer_modifier
+    }
+
+    /// Expressions denoting the current element of an array parameter: `admins[i]` and locals
+    /// initialized from it (`address admin = admins[i];`).
+    fn element_expressions(
+        loop_body: &Statement,
+        file: &SolidityFile,
+        array_params: &HashSet<&str>,
+    ) -> Vec<Expression> {
+        let is_param_element = |expr: &Expression| match expr {
+            Expression::ArraySubscript(_, array, Some(_)) => match array.as_ref() {
+                Expression::Variable(id) => array_params.contains(id.name.as_str()),
+                _ => false,
+            },
+            _ => false,
+        };
+
+        let mut elements = Vec::new();
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            if is_param_element(expr) {
+                elements.push(expr.clone());
+            }
+            None
+        };
+        let mut found = Vec::new();
+        find_locations_in_statement(loop_body, file, &mut predicate, &mut found);
+
+        find_statement_types(loop_body, file, "", |stmt| {
+            if let Statement::VariableDefinition(_, decl, Some(init)) = stmt {
+                if let (true, Some(name)) = (is_param_element(init), &decl.name) {
+                    elements.push(Expression::Variable(name.clone()));
+                }
+            }
+            false
+        });
+        elements
+    }
+
+    /// Role grants and `mapping[element] = true` writes keyed by an element, with the element.
+    fn grants<F>(loop_body: &Statement, file: &SolidityFile, is_element: &F) -> Vec<(Loc, String)>
+    where
+        F: Fn(&Expression) -> bool,
+    {
+        let mut grants = Vec::new();
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            match expr {
+                Expression::FunctionCall(loc, func, args)
+                    if GRANT_FUNCTIONS.contains(&Self::callee_name(func)?) =>
+                {
+                    let element = args.iter().find(|arg| is_element(arg))?;
+                    grants.push((*loc, element.to_string()));
+                    Some(*loc)
+                }
+                Expression::Assign(loc, target, value)
+                    if matches!(value.as_ref(), Expression::BoolLiteral(_, true)) =>
+                {
+                    let Expression::ArraySubscript(_, _, Some(key)) = target.as_ref() else {
+                        return None;
+                    };
+                    if !is_element(key) {
+                        return None;
+                    }
+                    grants.push((*loc, key.to_string()));
+                    Some(*loc)
+                }
+                _ => None,
+            }
+        };
+        let mut found = Vec::new();
+        find_locations_in_statement(loop_body, file, &mut predicate, &mut found);
+        grants
+    }
+
+    /// `element == x` or `element != x` anywhere in the loop body.
+    fn has_zero_check<F>(loop_body: &Statement, file: &SolidityFile, is_element: &F) -> bool
+    where
+        F: Fn(&Expression) -> bool,
+    {
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            match expr {
+                Expression::Equal(loc, left, right) | Expression::NotEqual(loc, left, right)
+                    if is_element(left) || is_element(right) =>
+                {
+                    Some(*loc)
+                }
+                _ => None,
+            }
+        };
+        let mut found = Vec::new();
+        find_locations_in_statement(loop_body, file, &mut predicate, &mut found);
+        !found.is_empty()
+    }
+
+    /// A read of `mapping[element]` or a membership call on the element inside a `require`,
+    /// `assert` or `if` condition.
+    fn has_duplicate_check<F>(loop_body: &Statement, file: &SolidityFile, is_element: &F) -> bool
+    where
+        F: Fn(&Expression) -> bool,
+    {
+        let mut conditions = Vec::new();
+        find_statement_types(loop_body, file, "", |stmt| {
+            match stmt {
+                Statement::If(_, cond, _, _) => conditions.push(cond.clone()),
+                Statement::Expression(_, Expression::FunctionCall(_, func, args))
+                    if matches!(Self::callee_name(func), Some("require" | "assert")) =>
+                {
+                    conditions.extend(args.first().cloned())
+                }
+                _ => {}
+            }
+            false
+        });
+
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            match expr {
+                Expression::ArraySubscript(loc, _, Some(key)) if is_element(key) => Some(*loc),
+                Expression::FunctionCall(loc, func, args)
+                    if MEMBERSHIP_FUNCTIONS.contains(&Self::callee_name(func)?)
+                        && args.iter().any(is_element) =>
+                {
+                    Some(*loc)
+                }
+                _ => None,
+            }
+        };
+        conditions.iter().any(|cond| {
+            let mut found = Vec::new();
+            find_locations_in_expression(cond, file, &mut predicate, &mut found);
+            !found.is_empty()
+        })
+    }
+
+    /// `f` for `f(...)`, `set.f(...)` and `Lib.f(...)`.
+    fn callee_name(func: &Expression) -> Option<&str> {
+        match func {
+            Expression::Variable(id) | Expression::MemberAccess(_, _, id) => Some(&id.name),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::run_detector_on_code;
+
+    #[test]
+    fn test_unvalidated_privileged_array() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            contract Multisig is AccessControl {
+                bytes32 constant SIGNER_ROLE = keccak256("SIGNER");
+                mapping(address => bool) public isSigner;
+                mapping(address => bool) public isGuardian;
+
+                constructor(address[] memory signers, address[] memory guardians) {
+                    for (uint256 i; i < signers.length; ++i) {
+                        address signer = signers[i];
+                        require(signer != address(0), "zero signer");
+                        require(!isSigner[signer], "duplicate signer");
+                        isSigner[signer] = true;                            // Negative: guarded
+                    }
+                    for (uint256 i; i < guardians.length; ++i) {
+                        isGuardian[guardians[i]] = true;                    // Positive
+                    }
+                }
+
+                function initialize(address[] calldata admins) external initializer {
+                    for (uint256 i; i < admins.length; ++i) {
+                        _grantRole(SIGNER_ROLE, admins[i]);                 // Positive
+                    }
+                }
+
+                function initializeDefaults() external initializer {
+                    address[2] memory defaults = [address(0x1234), address(0x5678)];
+                    for (uint256 i; i < defaults.length; ++i) {
+                        isGuardian[defaults[i]] = true;                     // Negative: fixed list
+                    }
+                }
+
+                function addGuardians(address[] calldata more) external {
+                    for (uint256 i; i < more.length; ++i) {
+                        isGuardian[more[i]] = true;                         // Negative: not init
+                    }
+                }
+            }
+        "#;
+        let detector = Arc::new(UnvalidatedPrivilegedArrayDetector::default());
+        let locations = run_detector_on_code(detector, code, "multisig.sol");
+
+        let lines: Vec<_> = locations.iter().map(|l| l.line).collect();
+        assert_eq!(lines, [17, 23]);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some("`guardians[i]` is added without a zero-address or duplicate check")
+        );
+    }
+}
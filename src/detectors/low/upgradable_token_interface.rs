@@ -1,5 +1,6 @@
 use crate::core::visitor::ASTVisitor;
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::models::FindingData;
 use crate::utils::location::loc_to_location;
@@ -22,6 +23,10 @@ impl Detector for UpgradableTokenInterfaceDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::Token
+    }
+
     fn description(&self) -> &str {
         "When casting addresses to token interfaces like IERC20, IERC721, or IERC1155, consider that \
         these tokens may be upgradable contracts. Upgradable tokens can change their behavior or \
@@ -1,5 +1,6 @@
 use crate::core::visitor::ASTVisitor;
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::ast_utils::{find_in_statement, is_likely_erc20_token};
 use solang_parser::pt::Expression;
@@ -21,6 +22,10 @@ impl Detector for ZeroValueTransferDetector {
         Severity::Low
     }
 
+    fn category(&self) -> Category {
+        Category::Token
+    }
+
     fn description(&self) -> &str {
         "Some ERC20 tokens (like LEND) revert when zero-value transfers are made, which may \
          cause batch operations to fail. Consider skipping the transfer if the amount is zero, \
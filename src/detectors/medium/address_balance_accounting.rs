@@ -0,0 +1,322 @@
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::category::Category;
+use crate::models::finding::Location;
+use crate::models::severity::Severity;
+use crate::models::{FindingData, SolidityFile};
+use crate::utils::ast_utils::{
+    contains_address_this, find_locations_in_expression, find_locations_in_statement,
+};
+use solang_parser::pt::{CodeLocation, ContractPart, Expression, Loc, Statement};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+#[derive(Debug, Default)]
+pub struct AddressBalanceAccountingDetector;
+
+impl Detector for AddressBalanceAccountingDetector {
+    fn id(&self) -> &'static str {
+        "address-balance-accounting"
+    }
+
+    fn name(&self) -> &str {
+        "`address(this).balance` used alongside a `msg.value` deposit accumulator"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Medium
+    }
+
+    fn category(&self) -> Category {
+        Category::Arithmetic
+    }
+
+    fn description(&self) -> &str {
+        "The contract tracks deposits in a state variable updated from `msg.value`, but also \
+         compares or does arithmetic with `address(this).balance`. ETH can be sent to any contract \
+         without calling it (`selfdestruct`, coinbase rewards, pre-funding the address), so the \
+         balance can exceed the tracked deposits at any time. Invariants such as \
+         `address(this).balance == totalDeposits` then break, and payouts computed from the balance \
+         hand out the force-sent surplus. Use the internal accounting variable consistently."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad - anyone can force-send 1 wei and brick withdrawals
+function deposit() external payable {
+    totalDeposits += msg.value;
+}
+
+function withdraw(uint256 amount) external {
+    require(address(this).balance == totalDeposits, "invariant");
+    // ...
+}
+
+// Good - rely on the internal accounting only
+function withdraw(uint256 amount) external {
+    require(amount <= totalDeposits, "insufficient");
+    totalDeposits -= amount;
+    // ...
+}
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_contract(move |contract_def, file, context| {
+            let Some(contract_name) = &contract_def.name else {
+                return Vec::new();
+            };
+
+            let qualified_name = context.get_qualified_name_for_contract(&contract_name.name);
+            let state_vars: HashSet<String> = context
+                .get_all_state_variables(&qualified_name)
+                .into_iter()
+                .map(|v| v.name.clone())
+                .collect();
+            if state_vars.is_empty() {
+                return Vec::new();
+            }
+
+            let bodies: Vec<&Statement> = contract_def
+                .parts
+                .iter()
+                .filter_map(|part| match part {
+                    ContractPart::FunctionDefinition(func_def) => func_def.body.as_ref(),
+                    _ => None,
+                })
+                .collect();
+
+            // Both sub-scans must hit before any balance read is reported
+            let Some(accumulator) = bodies.iter().find_map(|body| {
+                Self::deposit_accumulators(body, file, &state_vars)
+                    .into_iter()
+                    .next()
+            }) else {
+                return Vec::new();
+            };
+
+            let message = format!(
+                "`address(this).balance` used in accounting while `{}` tracks `msg.value`; \
+                 force-sent ETH makes the two diverge",
+                accumulator
+            );
+            bodies
+                .iter()
+                .flat_map(|body| Self::balance_reads(body, file))
+                .map(|location| FindingData {
+                    detector_id: self.id(),
+                    location: location.with_message(message.clone()),
+                })
+                .collect()
+        });
+    }
+}
+
+impl AddressBalanceAccountingDetector {
+    /// State variables increased by an amount derived from `msg.value`, e.g.
+    /// `totalDeposits += msg.value` or `balances[msg.sender] = balances[msg.sender] + msg.value`.
+    fn deposit_accumulators(
+        body: &Statement,
+        file: &SolidityFile,
+        state_vars: &HashSet<String>,
+    ) -> Vec<String> {
+        let mut names = Vec::new();
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            let (target, amount) = match expr {
+                Expression::AssignAdd(_, target, amount) => (target, amount.as_ref()),
+                Expression::Assign(_, target, value) => match value.as_ref() {
+                    Expression::Add(_, left, right) if Self::same_root(left, target) => {
+                        (target, right.as_ref())
+                    }
+                    Expression::Add(_, left, right) if Self::same_root(right, target) => {
+                        (target, left.as_ref())
+                    }
+                    _ => return None,
+                },
+                _ => return None,
+            };
+            let name = Self::root_name(target).filter(|n| state_vars.contains(*n))?;
+            if !Self::contains_msg_value(amount, file) {
+                return None;
+            }
+            names.push(name.to_string());
+            Some(expr.loc())
+        };
+
+        let mut found = Vec::new();
+        find_locations_in_statement(body, file, &mut predicate, &mut found);
+        names
+    }
+
+    /// `address(this).balance` used as an operand of a comparison or arithmetic expression,
+    /// which covers `require`/`if` conditions as well as computed payouts.
+    fn balance_reads(body: &Statement, file: &SolidityFile) -> Vec<Location> {
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            let (left, right) = match expr {
+                Expression::Add(_, left, right)
+                | Expression::Subtract(_, left, right)
+                | Expression::Multiply(_, left, right)
+                | Expression::Divide(_, left, right)
+                | Expression::Modulo(_, left, right)
+                | Expression::Less(_, left, right)
+                | Expression::More(_, left, right)
+                | Expression::LessEqual(_, left, right)
+                | Expression::MoreEqual(_, left, right)
+                | Expression::Equal(_, left, right)
+                | Expression::NotEqual(_, left, right) => (left, right),
+                _ => return None,
+            };
+            [left, right]
+                .into_iter()
+                .map(|operand| Self::strip_parentheses(operand))
+                .find(|operand| Self::is_this_balance(operand))
+                .map(|operand| operand.loc())
+        };
+
+        let mut locations = Vec::new();
+        find_locations_in_statement(body, file, &mut predicate, &mut locations);
+        locations
+    }
+
+    fn is_this_balance(expr: &Expression) -> bool {
+        matches!(
+            expr,
+            Expression::MemberAccess(_, base, member)
+                if member.name == "balance" && contains_address_this(base)
+        )
+    }
+
+    fn contains_msg_value(expr: &Expression, file: &SolidityFile) -> bool {
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            match expr {
+                Expression::MemberAccess(loc, base, member)
+                    if member.name == "value"
+                        && matches!(base.as_ref(), Expression::Variable(id) if id.name == "msg") =>
+                {
+                    Some(*loc)
+                }
+                _ => None,
+            }
+        };
+        let mut found = Vec::new();
+        find_locations_in_expression(expr, file, &mut predicate, &mut found);
+        !found.is_empty()
+    }
+
+    fn same_root(a: &Expression, b: &Expression) -> bool {
+        Self::root_name(a).is_some() && Self::root_name(a) == Self::root_name(b)
+    }
+
+    /// Variable an lvalue is rooted in, e.g. `balances[msg.sender]` -> `balances`.
+    fn root_name(expr: &Expression) -> Option<&str> {
+        match expr {
+            Expression::Variable(id) => Some(&id.name),
+            Expression::Parenthesis(_, inner)
+            | Expression::ArraySubscript(_, inner, _)
+            | Expression::MemberAccess(_, inner, _) => Self::root_name(inner),
+            _ => None,
+        }
+    }
+
+    fn strip_parentheses(expr: &Expression) -> &Expression {
+        match expr {
+            Expression::Parenthesis(_, inner) => Self::strip_parentheses(inner),
+            _ => expr,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::run_detector_on_code;
+
+    #[test]
+    fn test_balance_reads_with_deposit_accumulator() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            contract Pool {
+                uint256 public totalDeposits;
+                mapping(address => uint256) public shares;
+
+                function deposit() external payable {
+                    totalDeposits += msg.value;
+                    shares[msg.sender] = shares[msg.sender] + msg.value;
+                }
+
+                function withdraw(uint256 amount) external {
+                    require(address(this).balance == totalDeposits, "invariant"); // Positive
+                    totalDeposits -= amount;
+                    payable(msg.sender).transfer(amount);
+                }
+
+                function surplus() external view returns (uint256) {
+                    return (address(this).balance) - totalDeposits;             // Positive
+                }
+
+                function reserves() external view returns (uint256) {
+                    return address(this).balance;                               // Negative: plain read
+                }
+            }
+        "#;
+
+        let detector = Arc::new(AddressBalanceAccountingDetector::default());
+        let locations = run_detector_on_code(detector, code, "pool.sol");
+
+        assert_eq!(locations.len(), 2, "Should flag both balance reads");
+        assert_eq!(locations[0].line, 14);
+        assert_eq!(locations[1].line, 20);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some(
+                "`address(this).balance` used in accounting while `totalDeposits` tracks `msg.value`; \
+                 force-sent ETH makes the two diverge"
+            )
+        );
+    }
+
+    #[test]
+    fn test_skips_single_accounting_source() {
+        let internal_only = r#"
+            pragma solidity ^0.8.0;
+
+            contract Pool {
+                uint256 totalDeposits;
+
+                function deposit() external payable {
+                    totalDeposits += msg.value;
+                }
+
+                function withdraw(uint256 amount) external {
+                    require(amount <= totalDeposits);
+                    totalDeposits -= amount;
+                    payable(msg.sender).transfer(amount);
+                }
+            }
+        "#;
+        let balance_only = r#"
+            pragma solidity ^0.8.0;
+
+            contract Splitter {
+                uint256 released;
+
+                function release() external {
+                    uint256 total = address(this).balance + released;
+                    uint256 due = total / 2;
+                    released += due;
+                    payable(msg.sender).transfer(due);
+                }
+            }
+        "#;
+
+        for code in [internal_only, balance_only] {
+            let detector = Arc::new(AddressBalanceAccountingDetector::default());
+            let locations = run_detector_on_code(detector, code, "test.sol");
+            assert!(locations.is_empty());
+        }
+    }
+}
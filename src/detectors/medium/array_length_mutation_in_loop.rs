@@ -0,0 +1,343 @@
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::category::Category;
+use crate::models::finding::Location;
+use crate::models::severity::Severity;
+use crate::models::{FindingData, SolidityFile, TypeInfo};
+use crate::utils::ast_utils::{
+    expressions_equal, find_locations_in_expression, find_locations_in_statement,
+    get_local_variable_names,
+};
+use solang_parser::pt::{ContractPart, Expression, Loc, Statement};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+#[derive(Debug, Default)]
+pub struct ArrayLengthMutationInLoopDetector;
+
+impl Detector for ArrayLengthMutationInLoopDetector {
+    fn id(&self) -> &'static str {
+        "array-length-mutation-in-loop"
+    }
+
+    fn name(&self) -> &str {
+        "Storage array resized inside a loop bounded by its own length"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Medium
+    }
+
+    fn category(&self) -> Category {
+        Category::DenialOfService
+    }
+
+    fn description(&self) -> &str {
+        "The loop condition reads `arr.length` of a storage array while the loop body changes that \
+         length with `pop()`, `push()`, `delete arr` or a reassignment of the whole array. The bound \
+         is re-evaluated every iteration, so swap-and-pop removal skips the element moved into the \
+         current slot, and pushing can make the loop run until it runs out of gas. Iterate in \
+         reverse, or cache the length and adjust the index explicitly."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad - the element swapped into slot `i` is never checked
+for (uint256 i; i < holders.length; i++) {
+    if (balances[holders[i]] == 0) {
+        holders[i] = holders[holders.length - 1];
+        holders.pop();
+    }
+}
+
+// Good - iterate in reverse so swapped elements were already visited
+for (uint256 i = holders.length; i > 0; i--) {
+    if (balances[holders[i - 1]] == 0) {
+        holders[i - 1] = holders[holders.length - 1];
+        holders.pop();
+    }
+}
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_contract(move |contract_def, file, context| {
+            let Some(contract_name) = &contract_def.name else {
+                return Vec::new();
+            };
+
+            let qualified_name = context.get_qualified_name_for_contract(&contract_name.name);
+            // Dynamic arrays can live directly in storage, inside mappings or inside structs
+            let storage_vars: HashSet<String> = context
+                .get_all_state_variables(&qualified_name)
+                .into_iter()
+                .filter(|v| {
+                    matches!(
+                        v.type_info,
+                        TypeInfo::Array { size: None, .. }
+                            | TypeInfo::Mapping { .. }
+                            | TypeInfo::UserDefined(_)
+                    )
+                })
+                .map(|v| v.name.clone())
+                .collect();
+
+            if storage_vars.is_empty() {
+                return Vec::new();
+            }
+
+            let mut findings = Vec::new();
+
+            for part in &contract_def.parts {
+                let ContractPart::FunctionDefinition(func_def) = part else {
+                    continue;
+                };
+                let Some(body) = &func_def.body else {
+                    continue;
+                };
+
+                let locals = get_local_variable_names(func_def, body);
+                let arrays: HashSet<&str> = storage_vars
+                    .iter()
+                    .map(String::as_str)
+                    .filter(|name| !locals.contains(*name))
+                    .collect();
+                if arrays.is_empty() {
+                    continue;
+                }
+
+                let mut seen = HashSet::new();
+                let mut locations = Vec::new();
+                Self::check_loops(body, file, &arrays, &mut seen, &mut locations);
+
+                findings.extend(locations.into_iter().map(|location| FindingData {
+                    detector_id: self.id(),
+                    location,
+                }));
+            }
+
+            findings
+        });
+    }
+}
+
+impl ArrayLengthMutationInLoopDetector {
+    fn check_loops(
+        stmt: &Statement,
+        file: &SolidityFile,
+        arrays: &HashSet<&str>,
+        seen: &mut HashSet<(usize, Option<usize>)>,
+        locations: &mut Vec<Location>,
+    ) {
+        match stmt {
+            Statement::Block { statements, .. } => {
+                for s in statements {
+                    Self::check_loops(s, file, arrays, seen, locations);
+                }
+            }
+            Statement::If(_, _, then_stmt, else_stmt) => {
+                Self::check_loops(then_stmt, file, arrays, seen, locations);
+                if let Some(else_stmt) = else_stmt {
+                    Self::check_loops(else_stmt, file, arrays, seen, locations);
+                }
+            }
+            Statement::For(_, _, condition, _, Some(body)) => {
+                if let Some(condition) = condition {
+                    Self::check_loop(condition, body, file, arrays, seen, locations);
+                }
+                Self::check_loops(body, file, arrays, seen, locations);
+            }
+            Statement::While(_, condition, body) | Statement::DoWhile(_, body, condition) => {
+                Self::check_loop(condition, body, file, arrays, seen, locations);
+                Self::check_loops(body, file, arrays, seen, locations);
+            }
+            _ => {}
+        }
+    }
+
+    fn check_loop(
+        condition: &Expression,
+        body: &Statement,
+        file: &SolidityFile,
+        arrays: &HashSet<&str>,
+        seen: &mut HashSet<(usize, Option<usize>)>,
+        locations: &mut Vec<Location>,
+    ) {
+        for array in Self::length_bounds(condition, file, arrays) {
+            for location in Self::find_mutations(&array, body, file) {
+                // Nested loops bounded by the same array report a mutation once
+                if seen.insert((location.line, location.column)) {
+                    locations.push(location);
+                }
+            }
+        }
+    }
+
+    /// Storage arrays whose `.length` appears in the loop condition.
+    fn length_bounds(
+        condition: &Expression,
+        file: &SolidityFile,
+        arrays: &HashSet<&str>,
+    ) -> Vec<Expression> {
+        let mut bounds: Vec<Expression> = Vec::new();
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            let Expression::MemberAccess(loc, array, member) = expr else {
+                return None;
+            };
+            if member.name != "length"
+                || !Self::root_name(array).is_some_and(|n| arrays.contains(n))
+            {
+                return None;
+            }
+            if !bounds.iter().any(|b| expressions_equal(b, array)) {
+                bounds.push(array.as_ref().clone());
+            }
+            Some(*loc)
+        };
+        let mut found = Vec::new();
+        find_locations_in_expression(condition, file, &mut predicate, &mut found);
+        bounds
+    }
+
+    fn find_mutations(array: &Expression, body: &Statement, file: &SolidityFile) -> Vec<Location> {
+        let mut messages = Vec::new();
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            let (loc, mutation) = match expr {
+                Expression::FunctionCall(loc, func, _) => match func.as_ref() {
+                    Expression::MemberAccess(_, target, member)
+                        if (member.name == "pop" || member.name == "push")
+                            && expressions_equal(target, array) =>
+                    {
+                        let args = if member.name == "push" { "(...)" } else { "()" };
+                        (loc, format!("`{}.{}{}`", array, member.name, args))
+                    }
+                    _ => return None,
+                },
+                Expression::Delete(loc, target) if expressions_equal(target, array) => {
+                    (loc, format!("`delete {}`", array))
+                }
+                Expression::Assign(loc, target, _) if expressions_equal(target, array) => {
+                    (loc, format!("reassignment of `{}`", array))
+                }
+                _ => return None,
+            };
+            messages.push(format!(
+                "{} inside a loop bounded by `{}.length`",
+                mutation, array
+            ));
+            Some(*loc)
+        };
+
+        let mut locations = Vec::new();
+        find_locations_in_statement(body, file, &mut predicate, &mut locations);
+        locations
+            .into_iter()
+            .zip(messages)
+            .map(|(location, message)| location.with_message(message))
+            .collect()
+    }
+
+    /// Variable an array expression is rooted in, e.g. `pools[id].stakers` -> `pools`.
+    fn root_name(expr: &Expression) -> Option<&str> {
+        match expr {
+            Expression::Variable(id) => Some(&id.name),
+            Expression::Parenthesis(_, inner)
+            | Expression::ArraySubscript(_, inner, _)
+            | Expression::MemberAccess(_, inner, _) => Self::root_name(inner),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::run_detector_on_code;
+
+    #[test]
+    fn test_array_length_mutation_in_loop() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            contract Registry {
+                struct Pool { address[] stakers; }
+
+                address[] holders;
+                address[] archive;
+                mapping(uint256 => Pool) pools;
+                mapping(address => uint256) balances;
+
+                function prune() external {
+                    for (uint256 i; i < holders.length; i++) {
+                        if (balances[holders[i]] == 0) {
+                            holders[i] = holders[holders.length - 1];
+                            holders.pop();                              // Positive
+                        }
+                    }
+                }
+
+                function archiveAll() external {
+                    for (uint256 i; i < holders.length; i++) {
+                        archive.push(holders[i]);                       // Negative: other array
+                    }
+                }
+
+                function reversePrune() external {
+                    for (uint256 i = holders.length; i > 0; i--) {
+                        if (balances[holders[i - 1]] == 0) {
+                            holders[i - 1] = holders[holders.length - 1];
+                            holders.pop();                              // Negative: reverse loop
+                        }
+                    }
+                }
+
+                function drain(uint256 id) external {
+                    uint256 i;
+                    while (i < pools[id].stakers.length) {
+                        pools[id].stakers.push(msg.sender);             // Positive
+                        pools[id + 1].stakers.pop();                    // Negative: other pool
+                        i++;
+                    }
+                }
+
+                function reset() external {
+                    for (uint256 i; i < holders.length; i++) {
+                        if (holders[i] == msg.sender) delete holders;   // Positive
+                    }
+                }
+
+                function pruneCached() external {
+                    uint256 len = holders.length;
+                    for (uint256 i; i < len; i++) {
+                        if (balances[holders[i]] == 0) holders.pop();   // Negative: cached bound
+                    }
+                }
+            }
+        "#;
+
+        let detector = Arc::new(ArrayLengthMutationInLoopDetector::default());
+        let locations = run_detector_on_code(detector, code, "registry.sol");
+
+        assert_eq!(locations.len(), 3, "Should detect 3 length mutations");
+        assert_eq!(locations[0].line, 16);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some("`holders.pop()` inside a loop bounded by `holders.length`")
+        );
+        assert_eq!(locations[1].line, 39);
+        assert_eq!(
+            locations[1].message.as_deref(),
+            Some(
+                "`pools[id].stakers.push(...)` inside a loop bounded by `pools[id].stakers.length`"
+            )
+        );
+        assert_eq!(locations[2].line, 47);
+        assert_eq!(
+            locations[2].message.as_deref(),
+            Some("`delete holders` inside a loop bounded by `holders.length`")
+        );
+    }
+}
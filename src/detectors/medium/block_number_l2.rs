@@ -1,4 +1,5 @@
-use crate::detectors::Detector;
+use crate::detectors::{Detector, L2_CHAINS};
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::location::loc_to_location;
 use crate::{core::visitor::ASTVisitor, models::FindingData};
@@ -21,6 +22,10 @@ impl Detector for BlockNumberL2Detector {
         Severity::Medium
     }
 
+    fn category(&self) -> Category {
+        Category::Compatibility
+    }
+
     fn description(&self) -> &str {
         "Using `block.number` for timing or logic can cause inconsistencies across L2 chains. \
         On Optimism it returns the L2 block number, but on Arbitrum it returns the L1 block number. \
@@ -48,6 +53,10 @@ uint256 l2BlockNumber = ArbSys(address(100)).arbBlockNumber();
         )
     }
 
+    fn relevant_chains(&self) -> &'static [&'static str] {
+        L2_CHAINS
+    }
+
     fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
         visitor.on_expression(move |expr, file, _context| {
             if let Expression::MemberAccess(loc, base_expr, member) = expr {
@@ -1,37 +1,14 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
+use crate::utils::ast_utils::has_privileged_modifier;
 use crate::utils::location::loc_to_location;
 use crate::{core::visitor::ASTVisitor, models::FindingData};
-use solang_parser::pt::{FunctionAttribute, FunctionDefinition};
 use std::sync::Arc;
 
 #[derive(Debug, Default)]
 pub struct CentralizationRiskDetector;
 
-// Common access control modifiers that indicate centralization
-const PRIVILEGED_MODIFIERS: [&str; 20] = [
-    "onlyowner",      // onlyOwner - OpenZeppelin Ownable
-    "onlyadmin",      // onlyAdmin - General admin pattern
-    "onlygovernor",   // onlyGovernor - Governance contracts
-    "onlyguardian",   // onlyGuardian - Guardian pattern
-    "onlyoperator",   // onlyOperator - Operator pattern
-    "onlycontroller", // onlyController - Controller pattern
-    "onlymanager",    // onlyManager - Manager pattern
-    "onlyminter",     // onlyMinter - Minting privileges
-    "onlypauser",     // onlyPauser - Pause functionality
-    "onlyrole",       // onlyRole - OpenZeppelin AccessControl
-    "onlytimelock",   // onlyTimelock - Compound-style timelock
-    "onlymultisig",   // onlyMultisig - Multi-signature
-    "onlykeeper",     // onlyKeeper - Automation protocols
-    "onlystrategist", // onlyStrategist - Yield strategies
-    "onlyvault",      // onlyVault - Vault protocols
-    "onlybridge",     // onlyBridge - Bridge protocols
-    "onlyvalidator",  // onlyValidator - Validation protocols
-    "authorized",     // authorized - General authorization
-    "requiresauth",   // requiresAuth - Auth requirement
-    "hasrole",        // hasRole - OpenZeppelin AccessControl
-];
-
 impl Detector for CentralizationRiskDetector {
     fn id(&self) -> &'static str {
         "centralization-risk"
@@ -45,6 +22,10 @@ impl Detector for CentralizationRiskDetector {
         Severity::Medium
     }
 
+    fn category(&self) -> Category {
+        Category::AccessControl
+    }
+
     fn description(&self) -> &str {
         "Functions with privileged access control modifiers introduce centralization risk. \
         These functions can only be called by specific addresses (owners, admins, etc.) and require trust \
@@ -72,7 +53,7 @@ function setFee(uint256 _fee) external onlyGovernor { }  // Can change params
 
     fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
         visitor.on_function(move |func_def, file, _context| {
-            if self.has_privileged_modifier(func_def) {
+            if has_privileged_modifier(func_def) {
                 // Report finding at the function signature location
                 let loc = if let Some(name) = &func_def.name {
                     name.loc
@@ -91,29 +72,6 @@ function setFee(uint256 _fee) external onlyGovernor { }  // Can change params
     }
 }
 
-impl CentralizationRiskDetector {
-    fn has_privileged_modifier(&self, func_def: &FunctionDefinition) -> bool {
-        // Check attributes for modifier names
-        for attr in &func_def.attributes {
-            if let FunctionAttribute::BaseOrModifier(_, base) = attr {
-                // Get the modifier name from the identifier path
-                let modifier_name = base.name.identifiers
-                    .last()
-                    .map(|id| id.name.to_lowercase())
-                    .unwrap_or_default();
-                
-                // Check if modifier matches any privileged pattern
-                for pattern in PRIVILEGED_MODIFIERS {
-                    if modifier_name.contains(pattern) {
-                        return true;
-                    }
-                }
-            }
-        }
-        false
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::location::loc_to_location;
 use crate::{core::visitor::ASTVisitor, models::FindingData};
@@ -21,6 +22,10 @@ impl Detector for ChainlinkStalePriceDetector {
         Severity::Medium
     }
 
+    fn category(&self) -> Category {
+        Category::Oracle
+    }
+
     fn description(&self) -> &str {
         "latestRoundData() is used to fetch the asset price from a Chainlink aggregator, but it's missing additional validations \
         to ensure that the round is complete. If there is a problem with Chainlink starting a new round and finding consensus on \
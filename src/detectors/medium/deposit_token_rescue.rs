@@ -0,0 +1,423 @@
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::category::Category;
+use crate::models::scope::TypeInfo;
+use crate::models::severity::Severity;
+use crate::models::{FindingData, SolidityFile};
+use crate::utils::ast_utils::find_locations_in_statement;
+use crate::utils::location::loc_to_location;
+use solang_parser::pt::{
+    ContractPart, Expression, FunctionAttribute, FunctionDefinition, FunctionTy, Loc, Statement,
+    Type, Visibility,
+};
+use std::collections::{BTreeSet, HashSet};
+use std::sync::Arc;
+
+/// Function name fragments of admin paths that move tokens out of the contract.
+const RESCUE_PATTERNS: [&str; 6] = [
+    "rescue",
+    "sweep",
+    "recover",
+    "skim",
+    "withdraw",
+    "emergency",
+];
+
+#[derive(Debug, Default)]
+pub struct DepositTokenRescueDetector;
+
+impl Detector for DepositTokenRescueDetector {
+    fn id(&self) -> &'static str {
+        "deposit-token-rescue"
+    }
+
+    fn name(&self) -> &str {
+        "Privileged rescue function can withdraw the user deposit token"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Medium
+    }
+
+    fn category(&self) -> Category {
+        Category::AccessControl
+    }
+
+    fn description(&self) -> &str {
+        "The contract pulls a specific token from users in its deposit functions, and an \
+         owner-only rescue/sweep function transfers an arbitrary token given as a parameter \
+         without excluding that deposit token. Besides recovering tokens sent by mistake, the \
+         privileged account can drain every user deposit. Reject the deposit token (and any other \
+         token the contract accounts for) in the rescue path, e.g. \
+         `require(token != depositToken)`, or cap the amount to the balance in excess of \
+         recorded deposits."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad - the owner can sweep user deposits
+function rescueTokens(IERC20 token, uint256 amount) external onlyOwner {
+    token.safeTransfer(owner(), amount);
+}
+
+// Good - only tokens the vault does not account for can be rescued
+function rescueTokens(IERC20 token, uint256 amount) external onlyOwner {
+    require(token != asset, "deposit token");
+    token.safeTransfer(owner(), amount);
+}
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_contract(move |contract_def, file, context| {
+            let Some(contract_name) = &contract_def.name else {
+                return Vec::new();
+            };
+
+            let qualified_name = context.get_qualified_name_for_contract(&contract_name.name);
+            let token_vars: HashSet<String> = context
+                .get_all_state_variables(&qualified_name)
+                .into_iter()
+                .filter(|v| !v.is_constant && Self::is_token_type(&v.type_info))
+                .map(|v| v.name.clone())
+                .collect();
+            if token_vars.is_empty() {
+                return Vec::new();
+            }
+
+            let functions: Vec<&FunctionDefinition> = contract_def
+                .parts
+                .iter()
+                .filter_map(|part| match part {
+                    ContractPart::FunctionDefinition(f) if matches!(f.ty, FunctionTy::Function) => {
+                        Some(f.as_ref())
+                    }
+                    _ => None,
+                })
+                .collect();
+
+            // Token state variables users pay into through an unprivileged entry point
+            let mut deposit_tokens = BTreeSet::new();
+            for func_def in &functions {
+                let Some(body) = &func_def.body else {
+                    continue;
+                };
+                if !Self::is_externally_callable(func_def)
+                    || Self::is_privileged(func_def, body, file)
+                {
+                    continue;
+                }
+                deposit_tokens.extend(Self::pulled_tokens(body, &token_vars, file));
+            }
+            if deposit_tokens.is_empty() {
+                return Vec::new();
+            }
+
+            let mut findings = Vec::new();
+            for func_def in &functions {
+                let (Some(name), Some(body)) = (&func_def.name, &func_def.body) else {
+                    continue;
+                };
+                let lower = name.name.to_lowercase();
+                if !RESCUE_PATTERNS.iter().any(|p| lower.contains(p))
+                    || !Self::is_externally_callable(func_def)
+                    || !Self::is_privileged(func_def, body, file)
+                {
+                    continue;
+                }
+
+                for param in Self::token_params(func_def) {
+                    if !Self::transfers_param(body, &param, file) {
+                        continue;
+                    }
+                    let excluded = Self::compared_names(body, &param, file);
+                    let Some(deposit_token) =
+                        deposit_tokens.iter().find(|t| !excluded.contains(*t))
+                    else {
+                        continue;
+                    };
+                    findings.push(FindingData {
+                        detector_id: self.id(),
+                        location: loc_to_location(&func_def.loc, file).with_message(format!(
+                            "`{}` can transfer any `{}`, including the user deposit token `{}`; \
+                             exclude it with `require({} != {})`",
+                            name.name, param, deposit_token, param, deposit_token
+                        )),
+                    });
+                    break;
+                }
+            }
+            findings
+        });
+    }
+}
+
+impl DepositTokenRescueDetector {
+    /// `address` or an ERC20-like contract type (`IERC20`, `IERC20Metadata`, `IToken`, ...).
+    fn is_token_type(type_info: &TypeInfo) -> bool {
+        match type_info {
+            TypeInfo::Address => true,
+            TypeInfo::UserDefined(name) => Self::is_token_type_name(name),
+            _ => false,
+        }
+    }
+
+    fn is_token_type_name(name: &str) -> bool {
+        let name = name.rsplit('.').next().unwrap_or(name);
+        name.contains("ERC20") || name.contains("Token")
+    }
+
+    fn is_externally_callable(func_def: &FunctionDefinition) -> bool {
+        func_def.attributes.iter().any(|attr| {
+            matches!(
+                attr,
+                FunctionAttribute::Visibility(Visibility::External(_) | Visibility::Public(_))
+            )
+        })
+    }
+
+    /// Guarded by an `only*` modifier or by a `msg.sender` comparison in the body.
+    fn is_privileged(func_def: &FunctionDefinition, body: &Statement, file: &SolidityFile) -> bool {
+        let has_modifier = func_def.attributes.iter().any(|attr| {
+            matches!(
+                attr,
+                FunctionAttribute::BaseOrModifier(_, base)
+                    if base
+                        .name
+                        .identifiers
+                        .last()
+                        .is_some_and(|id| id.name.starts_with("only"))
+            )
+        });
+        if has_modifier {
+            return true;
+        }
+
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            match expr {
+                Expression::Equal(loc, left, right) | Expression::NotEqual(loc, left, right)
+                    if Self::is_msg_sender(left) || Self::is_msg_sender(right) =>
+                {
+                    Some(*loc)
+                }
+                _ => None,
+            }
+        };
+        let mut found = Vec::new();
+        find_locations_in_statement(body, file, &mut predicate, &mut found);
+        !found.is_empty()
+    }
+
+    fn is_msg_sender(expr: &Expression) -> bool {
+        matches!(
+            expr,
+            Expression::MemberAccess(_, base, member)
+                if member.name == "sender"
+                    && matches!(base.as_ref(), Expression::Variable(id) if id.name == "msg")
+        )
+    }
+
+    /// Token variables that are the receiver of a `transferFrom`/`safeTransferFrom` call.
+    fn pulled_tokens(
+        body: &Statement,
+        token_vars: &HashSet<String>,
+        file: &SolidityFile,
+    ) -> BTreeSet<String> {
+        let mut pulled = BTreeSet::new();
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            let Expression::FunctionCall(loc, callee, _) = expr else {
+                return None;
+            };
+            let Expression::MemberAccess(_, receiver, member) = callee.as_ref() else {
+                return None;
+            };
+            if member.name != "transferFrom" && member.name != "safeTransferFrom" {
+                return None;
+            }
+            let name = Self::token_name(receiver)?;
+            if token_vars.contains(name) {
+                pulled.insert(name.to_string());
+            }
+            Some(*loc)
+        };
+        let mut found = Vec::new();
+        find_locations_in_statement(body, file, &mut predicate, &mut found);
+        pulled
+    }
+
+    /// Parameters of type `address` or an ERC20-like contract type.
+    fn token_params(func_def: &FunctionDefinition) -> Vec<String> {
+        func_def
+            .params
+            .iter()
+            .filter_map(|(_, param)| {
+                let param = param.as_ref()?;
+                let is_token = match &param.ty {
+                    Expression::Type(_, Type::Address | Type::AddressPayable) => true,
+                    Expression::Variable(id) => Self::is_token_type_name(&id.name),
+                    _ => false,
+                };
+                if !is_token {
+                    return None;
+                }
+                param.name.as_ref().map(|n| n.name.clone())
+            })
+            .collect()
+    }
+
+    /// Whether the body calls `transfer`/`safeTransfer` on `param` (directly or through a cast).
+    fn transfers_param(body: &Statement, param: &str, file: &SolidityFile) -> bool {
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            let Expression::FunctionCall(loc, callee, _) = expr else {
+                return None;
+            };
+            match callee.as_ref() {
+                Expression::MemberAccess(_, receiver, member)
+                    if (member.name == "transfer" || member.name == "safeTransfer")
+                        && Self::token_name(receiver) == Some(param) =>
+                {
+                    Some(*loc)
+                }
+                _ => None,
+            }
+        };
+        let mut found = Vec::new();
+        find_locations_in_statement(body, file, &mut predicate, &mut found);
+        !found.is_empty()
+    }
+
+    /// Names `param` is compared against with `==` or `!=`.
+    fn compared_names(body: &Statement, param: &str, file: &SolidityFile) -> HashSet<String> {
+        let mut names = HashSet::new();
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            match expr {
+                Expression::Equal(loc, left, right) | Expression::NotEqual(loc, left, right) => {
+                    let (left, right) = (Self::token_name(left)?, Self::token_name(right)?);
+                    if left == param {
+                        names.insert(right.to_string());
+                    } else if right == param {
+                        names.insert(left.to_string());
+                    }
+                    Some(*loc)
+                }
+                _ => None,
+            }
+        };
+        let mut found = Vec::new();
+        find_locations_in_statement(body, file, &mut predicate, &mut found);
+        names
+    }
+
+    /// Variable name behind `token`, `IERC20(token)` or `address(token)`.
+    fn token_name(expr: &Expression) -> Option<&str> {
+        match expr {
+            Expression::Variable(id) => Some(&id.name),
+            Expression::FunctionCall(_, _, args) if args.len() == 1 => Self::token_name(&args[0]),
+            Expression::Parenthesis(_, inner) => Self::token_name(inner),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::run_detector_on_code;
+
+    #[test]
+    fn test_rescue_without_deposit_token_exclusion() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            contract Vault {
+                IERC20 public immutable asset;
+                address owner;
+                mapping(address => uint256) balances;
+
+                function deposit(uint256 amount) external {
+                    asset.safeTransferFrom(msg.sender, address(this), amount);
+                    balances[msg.sender] += amount;
+                }
+
+                function rescueTokens(IERC20 token, uint256 amount) external onlyOwner {
+                    token.safeTransfer(owner, amount);
+                }
+
+                function sweep(address token) external {
+                    require(msg.sender == owner);
+                    IERC20(token).transfer(owner, IERC20(token).balanceOf(address(this)));
+                }
+            }
+        "#;
+        let detector = Arc::new(DepositTokenRescueDetector::default());
+        let locations = run_detector_on_code(detector, code, "vault.sol");
+
+        assert_eq!(locations.len(), 2);
+        assert_eq!(locations[0].line, 14);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some(
+                "`rescueTokens` can transfer any `token`, including the user deposit token \
+                 `asset`; exclude it with `require(token != asset)`"
+            )
+        );
+        assert_eq!(locations[1].line, 18);
+    }
+
+    #[test]
+    fn test_rescue_with_deposit_token_exclusion() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            contract Vault {
+                IERC20 public immutable asset;
+                address owner;
+
+                function deposit(uint256 amount) external {
+                    asset.safeTransferFrom(msg.sender, address(this), amount);
+                }
+
+                function rescueTokens(IERC20 token, uint256 amount) external onlyOwner {
+                    require(token != asset, "deposit token");
+                    token.safeTransfer(owner, amount);
+                }
+
+                function sweep(address token) external onlyOwner {
+                    if (address(token) == address(asset)) revert();
+                    IERC20(token).transfer(owner, IERC20(token).balanceOf(address(this)));
+                }
+            }
+        "#;
+        let detector = Arc::new(DepositTokenRescueDetector::default());
+        let locations = run_detector_on_code(detector, code, "vault.sol");
+
+        assert!(locations.is_empty());
+    }
+
+    #[test]
+    fn test_skips_contract_without_user_deposits() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            contract Treasury {
+                IERC20 public rewardToken;
+                address owner;
+
+                function fund(uint256 amount) external onlyOwner {
+                    rewardToken.safeTransferFrom(msg.sender, address(this), amount);
+                }
+
+                function rescueTokens(IERC20 token, uint256 amount) external onlyOwner {
+                    token.safeTransfer(owner, amount);
+                }
+            }
+        "#;
+        let detector = Arc::new(DepositTokenRescueDetector::default());
+        let locations = run_detector_on_code(detector, code, "treasury.sol");
+
+        assert!(locations.is_empty());
+    }
+}
@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::location::loc_to_location;
 use crate::{core::visitor::ASTVisitor, models::FindingData};
@@ -21,6 +22,10 @@ impl Detector for DeprecatedChainlinkFunctionDetector {
         Severity::Medium
     }
 
+    fn category(&self) -> Category {
+        Category::Oracle
+    }
+
     fn description(&self) -> &str {
         "According to Chainlink's documentation, the latestAnswer() function is deprecated. \
         This function does not throw an error if no answer has been reached, but instead returns 0, \
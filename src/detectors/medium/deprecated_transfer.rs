@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::location::loc_to_location;
 use crate::{core::visitor::ASTVisitor, models::FindingData};
@@ -21,6 +22,10 @@ impl Detector for DeprecatedTransferDetector {
         Severity::Medium
     }
 
+    fn category(&self) -> Category {
+        Category::ExternalCall
+    }
+
     fn description(&self) -> &str {
         "The use of the deprecated transfer() function for an address may make the transaction fail \
         due to the 2300 gas stipend. Use call() instead which allows specifying gas and handles return values properly."
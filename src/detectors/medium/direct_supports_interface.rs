@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::location::loc_to_location;
 use crate::{core::visitor::ASTVisitor, models::FindingData};
@@ -21,6 +22,10 @@ impl Detector for DirectSupportsInterfaceDetector {
         Severity::Medium
     }
 
+    fn category(&self) -> Category {
+        Category::ExternalCall
+    }
+
     fn description(&self) -> &str {
         "Calling supportsInterface() on a contract that doesn't implement the ERC-165 standard will \
         result in the call reverting. Even if the caller does support the function, the contract may \
@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::{ast_utils, location::loc_to_location};
 use crate::{core::visitor::ASTVisitor, models::FindingData};
@@ -21,6 +22,10 @@ impl Detector for Eip712ComplianceDetector {
         Severity::Medium
     }
 
+    fn category(&self) -> Category {
+        Category::Cryptography
+    }
+
     fn description(&self) -> &str {
         "Directly using the actual variable instead of encoding the array values goes against the EIP-712 specification. \
         Arrays and structs should be encoded properly before hashing. Using keccak256 directly on complex types can lead to \
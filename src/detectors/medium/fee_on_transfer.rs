@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::{ast_utils, location::loc_to_location};
 use crate::{core::visitor::ASTVisitor, models::FindingData};
@@ -21,6 +22,10 @@ impl Detector for FeeOnTransferDetector {
         Severity::Medium
     }
 
+    fn category(&self) -> Category {
+        Category::Token
+    }
+
     fn description(&self) -> &str {
         "Consistently check account balance before and after transfers for Fee-On-Transfer discrepancies. \
         As arbitrary ERC20 tokens can be used, the amount here should be calculated every time to take into \
@@ -1,4 +1,5 @@
-use crate::detectors::Detector;
+use crate::detectors::{Detector, L2_CHAINS};
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::location::loc_to_location;
 use crate::{core::visitor::ASTVisitor, models::FindingData};
@@ -21,6 +22,10 @@ impl Detector for L2SequencerCheckDetector {
         Severity::Medium
     }
 
+    fn category(&self) -> Category {
+        Category::Oracle
+    }
+
     fn description(&self) -> &str {
         "Chainlink recommends that users using price oracles, check whether the Arbitrum/L2 Sequencer is active. \
         If the sequencer goes down, the Chainlink oracles will have stale prices from before the downtime, \
@@ -66,6 +71,10 @@ function getPrice() public view returns (int256) {
         )
     }
 
+    fn relevant_chains(&self) -> &'static [&'static str] {
+        L2_CHAINS
+    }
+
     fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
         visitor.on_statement(move |stmt, file, _context| {
             // Check for expression statements with tuple destructuring
@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::{ast_utils, location::loc_to_location};
 use crate::{core::visitor::ASTVisitor, models::FindingData};
@@ -21,6 +22,10 @@ impl Detector for LibraryFunctionVisibilityDetector {
         Severity::Medium
     }
 
+    fn category(&self) -> Category {
+        Category::CodeQuality
+    }
+
     fn description(&self) -> &str {
         "In a library, using external or public visibility means that we won't be going through \
         the library with a DELEGATECALL but with a CALL. This changes the context and should be done carefully. \
@@ -0,0 +1,372 @@
+use crate::core::context::AnalysisContext;
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::category::Category;
+use crate::models::severity::Severity;
+use crate::models::{FindingData, SolidityFile};
+use crate::utils::ast_utils::{find_locations_in_statement, find_statement_types};
+use crate::utils::location::loc_to_location;
+use solang_parser::pt::{
+    CodeLocation, ContractDefinition, ContractPart, ContractTy, Expression, FunctionAttribute,
+    FunctionTy, Mutability, SourceUnitPart, Statement,
+};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Library helpers that send ether, e.g. OpenZeppelin `Address.sendValue` and Solady/Solmate
+/// `SafeTransferLib.safeTransferETH`.
+const ETH_SEND_HELPERS: [&str; 4] = [
+    "sendvalue",
+    "safetransfereth",
+    "safetransferalleth",
+    "forcesafetransfereth",
+];
+
+/// Yul builtins that can move ether out, or hand control to code that can.
+const YUL_ETH_OUT: [&str; 6] = [
+    "call",
+    "callcode",
+    "delegatecall",
+    "selfdestruct",
+    "create",
+    "create2",
+];
+
+#[derive(Debug, Default)]
+pub struct LockedEtherDetector;
+
+impl Detector for LockedEtherDetector {
+    fn id(&self) -> &'static str {
+        "locked-ether"
+    }
+
+    fn name(&self) -> &str {
+        "Contract accepts ether but has no way to send it out"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Medium
+    }
+
+    fn category(&self) -> Category {
+        Category::Validation
+    }
+
+    fn description(&self) -> &str {
+        "The contract has a `receive()`, a payable `fallback()` or payable functions, but neither \
+         it nor any of its base contracts sends ether anywhere: no `.call{value: ...}`, \
+         `.transfer`, `.send`, `sendValue`/`safeTransferETH` or `selfdestruct`. Ether sent to it \
+         is locked forever. Add a withdrawal path, or remove `payable` from entry points that \
+         should not take ether. Contracts with base contracts outside the analyzed files are \
+         skipped, since the withdrawal may be inherited."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad - deposits can never leave
+contract Donations {
+    mapping(address => uint256) public donated;
+
+    function donate() external payable {
+        donated[msg.sender] += msg.value;
+    }
+}
+
+// Good
+contract Donations is Ownable {
+    mapping(address => uint256) public donated;
+
+    function donate() external payable {
+        donated[msg.sender] += msg.value;
+    }
+
+    function withdraw(address payable to) external onlyOwner {
+        (bool ok, ) = to.call{value: address(this).balance}("");
+        require(ok);
+    }
+}
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_contract(move |contract_def, file, context| {
+            if !matches!(contract_def.ty, ContractTy::Contract(_)) {
+                return Vec::new();
+            }
+            let Some(contract_name) = &contract_def.name else {
+                return Vec::new();
+            };
+
+            let entry_points: Vec<_> = contract_def
+                .parts
+                .iter()
+                .filter_map(|part| match part {
+                    ContractPart::FunctionDefinition(f) if Self::accepts_ether(f) => Some(f),
+                    _ => None,
+                })
+                .collect();
+            if entry_points.is_empty() {
+                return Vec::new();
+            }
+
+            if Self::sends_ether(contract_def, file) {
+                return Vec::new();
+            }
+            let qualified_name = context.get_qualified_name_for_contract(&contract_name.name);
+            match Self::bases_send_ether(contract_def, &qualified_name, context) {
+                Some(false) => {}
+                // A base sends ether, or could not be inspected
+                _ => return Vec::new(),
+            }
+
+            entry_points
+                .into_iter()
+                .map(|func_def| {
+                    let entry = match func_def.ty {
+                        FunctionTy::Receive => "`receive()`".to_string(),
+                        FunctionTy::Fallback => "`fallback()`".to_string(),
+                        _ => format!(
+                            "`{}`",
+                            func_def.name.as_ref().map_or("", |n| n.name.as_str())
+                        ),
+                    };
+                    FindingData {
+                        detector_id: self.id(),
+                        location: loc_to_location(&func_def.loc, file).with_message(format!(
+                            "{} accepts ether, but neither `{}` nor its base contracts ever send \
+                             ether out",
+                            entry, contract_name.name
+                        )),
+                    }
+                })
+                .collect()
+        });
+    }
+}
+
+impl LockedEtherDetector {
+    /// `receive()`, or a payable `fallback()` or function. Payable constructors are left out:
+    /// they are mostly a deployment gas optimization.
+    fn accepts_ether(func_def: &solang_parser::pt::FunctionDefinition) -> bool {
+        match func_def.ty {
+            FunctionTy::Receive => true,
+            FunctionTy::Fallback | FunctionTy::Function => func_def
+                .attributes
+                .iter()
+                .any(|attr| matches!(attr, FunctionAttribute::Mutability(Mutability::Payable(_)))),
+            _ => false,
+        }
+    }
+
+    /// Whether the bases in the contract's inheritance chain send ether. `None` if a base
+    /// could not be resolved, or its source is not available (out of scope, or released in
+    /// low-memory mode).
+    fn bases_send_ether(
+        contract_def: &ContractDefinition,
+        qualified_name: &str,
+        context: &AnalysisContext,
+    ) -> Option<bool> {
+        let chain = context.get_inheritance_chain(qualified_name)?;
+        let resolved_bases = chain.iter().filter(|base| *base != qualified_name).count();
+        if contract_def.base.len() > resolved_bases {
+            return None;
+        }
+
+        for base in chain.iter().filter(|base| *base != qualified_name) {
+            let info = context.get_contract(base)?;
+            if info.direct_bases.iter().any(|direct| {
+                context
+                    .get_contract(&context.get_qualified_name_for_contract(direct))
+                    .is_none()
+            }) {
+                return None;
+            }
+
+            let base_file = context.get_file_by_path(Path::new(&info.file_path))?;
+            let base_def = base_file.source_unit.0.iter().find_map(|part| match part {
+                SourceUnitPart::ContractDefinition(c)
+                    if c.name.as_ref().is_some_and(|n| n.name == info.name) =>
+                {
+                    Some(c)
+                }
+                _ => None,
+            })?;
+            if Self::sends_ether(base_def, base_file) {
+                return Some(true);
+            }
+        }
+        Some(false)
+    }
+
+    /// Whether any function or modifier of the contract sends ether or delegates to code that
+    /// could.
+    fn sends_ether(contract_def: &ContractDefinition, file: &SolidityFile) -> bool {
+        contract_def.parts.iter().any(|part| {
+            let body = match part {
+                ContractPart::FunctionDefinition(f) => f.body.as_ref(),
+                _ => None,
+            };
+            body.is_some_and(|body| {
+                let mut predicate = |expr: &Expression, _: &SolidityFile| {
+                    Self::is_ether_send(expr).then(|| expr.loc())
+                };
+                let mut found = Vec::new();
+                find_locations_in_statement(body, file, &mut predicate, &mut found);
+                !found.is_empty()
+                    || !find_statement_types(body, file, "locked-ether", |stmt| {
+                        Self::is_assembly_ether_send(stmt, file)
+                    })
+                    .is_empty()
+            })
+        })
+    }
+
+    fn is_ether_send(expr: &Expression) -> bool {
+        match expr {
+            // `to.call{value: x}(...)`, `new C{value: x}()`, `weth.deposit{value: x}()`
+            Expression::FunctionCallBlock(_, _, block) => matches!(
+                block.as_ref(),
+                Statement::Args(_, args) if args.iter().any(|arg| arg.name.name == "value")
+            ),
+            Expression::FunctionCall(_, callee, args) => match callee.as_ref() {
+                Expression::MemberAccess(_, _, member) => {
+                    let name = member.name.to_lowercase();
+                    // One-argument `transfer`/`send` are the address members; ERC20 takes two
+                    (matches!(name.as_str(), "transfer" | "send") && args.len() == 1)
+                        || name == "delegatecall"
+                        || ETH_SEND_HELPERS.iter().any(|h| name.ends_with(h))
+                }
+                Expression::Variable(id) => {
+                    let name = id.name.to_lowercase();
+                    matches!(name.as_str(), "selfdestruct" | "suicide")
+                        || ETH_SEND_HELPERS.iter().any(|h| name.ends_with(h))
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    fn is_assembly_ether_send(stmt: &Statement, file: &SolidityFile) -> bool {
+        let Statement::Assembly { loc, .. } = stmt else {
+            return false;
+        };
+        let Some(source) = file.content.get(loc.start()..loc.end()) else {
+            return false;
+        };
+        source
+            .split(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .any(|token| YUL_ETH_OUT.contains(&token))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::{run_detector_on_code, run_detector_with_mock_inheritance};
+
+    #[test]
+    fn test_locked_ether() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            contract Donations {
+                mapping(address => uint256) public donated;
+
+                receive() external payable {}                    // Positive
+
+                function donate() external payable {             // Positive
+                    donated[msg.sender] += msg.value;
+                }
+
+                function token() external {
+                    IERC20(address(0)).transfer(msg.sender, 1);   // ERC20, not ether
+                }
+            }
+
+            contract WETH {
+                mapping(address => uint256) public balanceOf;
+
+                function deposit() public payable {              // Negative: withdraw pays out
+                    balanceOf[msg.sender] += msg.value;
+                }
+
+                function withdraw(uint256 wad) external {
+                    balanceOf[msg.sender] -= wad;
+                    payable(msg.sender).transfer(wad);
+                }
+            }
+
+            contract Proxy {
+                fallback() external payable {                    // Negative: delegates
+                    assembly {
+                        calldatacopy(0, 0, calldatasize())
+                        let ok := delegatecall(gas(), sload(0), 0, calldatasize(), 0, 0)
+                    }
+                }
+            }
+
+            contract Deployed {
+                constructor() payable {}                         // Negative: constructor
+            }
+        "#;
+        let detector = Arc::new(LockedEtherDetector::default());
+        let locations = run_detector_on_code(detector, code, "donations.sol");
+
+        let lines: Vec<_> = locations.iter().map(|l| l.line).collect();
+        assert_eq!(lines, [7, 9]);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some(
+                "`receive()` accepts ether, but neither `Donations` nor its base contracts ever \
+                 send ether out"
+            )
+        );
+    }
+
+    #[test]
+    fn test_locked_ether_inheritance() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            abstract contract Withdrawable {
+                function withdraw() external virtual {
+                    (bool ok, ) = msg.sender.call{value: address(this).balance}("");
+                    require(ok);
+                }
+            }
+
+            abstract contract Accounting {
+                uint256 public total;
+            }
+
+            contract Vault is Withdrawable {
+                receive() external payable {}                    // Negative: inherited withdraw
+            }
+
+            contract Sink is Accounting {
+                receive() external payable {}                    // Positive
+            }
+
+            contract Pending is Treasury {
+                function fund() external payable {}              // Negative: unresolved base
+            }
+        "#;
+        let detector = Arc::new(LockedEtherDetector::default());
+        let locations = run_detector_with_mock_inheritance(
+            detector,
+            code,
+            "vault.sol",
+            vec![
+                ("Vault", vec!["vault.sol:Withdrawable"]),
+                ("Sink", vec!["vault.sol:Accounting"]),
+            ],
+        );
+
+        let lines: Vec<_> = locations.iter().map(|l| l.line).collect();
+        assert_eq!(lines, [20]);
+    }
+}
@@ -0,0 +1,308 @@
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::category::Category;
+use crate::models::severity::Severity;
+use crate::models::{FindingData, SolidityFile};
+use crate::utils::ast_utils::{
+    find_in_expression, find_in_statement, find_statement_types, is_likely_erc20_token,
+};
+use solang_parser::pt::{Expression, Identifier, Loc, Statement};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Name parts marking a variable as an amount of the token named by the rest of the name,
+/// e.g. `daiAmount`, `usdcBalance`, `amountWeth`.
+const AMOUNT_WORDS: &[&str] = &["amount", "balance", "reserve", "amt", "bal"];
+
+#[derive(Debug, Default)]
+pub struct MixedDecimalsArithmeticDetector;
+
+impl Detector for MixedDecimalsArithmeticDetector {
+    fn id(&self) -> &'static str {
+        "mixed-decimals-arithmetic"
+    }
+
+    fn name(&self) -> &str {
+        "Amounts of different tokens added or compared without normalizing decimals"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Medium
+    }
+
+    fn category(&self) -> Category {
+        Category::Token
+    }
+
+    fn description(&self) -> &str {
+        "Two raw amounts of different tokens are added, subtracted or compared directly. Tokens \
+         do not share a unit: 1 USDC is `1e6`, 1 DAI is `1e18`, so `daiAmount + usdcAmount` \
+         undervalues the USDC side by a factor of 10^12 and comparisons between them are \
+         meaningless. Scale both sides to a common precision (or price them in a common \
+         currency) first. This is a heuristic: the token behind an amount is guessed from \
+         `token.balanceOf(...)` calls and from names such as `usdcAmount` or `wethBalance`, \
+         and any multiplication, division or function call on an operand is taken as \
+         normalization. Tokens that happen to share decimals are still reported, so review \
+         each finding before acting on it."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad - USDC has 6 decimals, DAI has 18
+function totalValue() external view returns (uint256) {
+    return dai.balanceOf(address(this)) + usdc.balanceOf(address(this));
+}
+
+// Good
+function totalValue() external view returns (uint256) {
+    uint256 usdcAmount = usdc.balanceOf(address(this));
+    return dai.balanceOf(address(this)) + usdcAmount * 10 ** (18 - usdc.decimals());
+}
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_function(move |func_def, file, _context| {
+            let Some(body) = &func_def.body else {
+                return Vec::new();
+            };
+            let locals = Self::local_values(body, file);
+
+            let mut messages = Vec::new();
+            let findings = find_in_statement(body, file, self.id(), |expr| {
+                match Self::check_operands(expr, file, &locals) {
+                    Some(message) => {
+                        messages.push(message);
+                        true
+                    }
+                    None => false,
+                }
+            });
+            findings
+                .into_iter()
+                .zip(messages)
+                .map(|(finding, message)| FindingData {
+                    location: finding.location.with_message(message),
+                    ..finding
+                })
+                .collect()
+        });
+    }
+}
+
+impl MixedDecimalsArithmeticDetector {
+    /// Locals declared or assigned in the body, mapped to the last value given to them.
+    fn local_values(body: &Statement, file: &SolidityFile) -> HashMap<String, Expression> {
+        let mut values = HashMap::new();
+        find_statement_types(body, file, "", |stmt| {
+            match stmt {
+                Statement::VariableDefinition(_, decl, Some(value)) => {
+                    if let Some(name) = &decl.name {
+                        values.insert(name.name.clone(), value.clone());
+                    }
+                }
+                Statement::Expression(_, Expression::Assign(_, target, value)) => {
+                    if let Expression::Variable(name) = target.as_ref() {
+                        values.insert(name.name.clone(), value.as_ref().clone());
+                    }
+                }
+                _ => {}
+            }
+            false
+        });
+        values
+    }
+
+    fn check_operands(
+        expr: &Expression,
+        file: &SolidityFile,
+        locals: &HashMap<String, Expression>,
+    ) -> Option<String> {
+        let (left, right) = match expr {
+            Expression::Add(_, left, right)
+            | Expression::Subtract(_, left, right)
+            | Expression::AssignAdd(_, left, right)
+            | Expression::AssignSubtract(_, left, right)
+            | Expression::Less(_, left, right)
+            | Expression::More(_, left, right)
+            | Expression::LessEqual(_, left, right)
+            | Expression::MoreEqual(_, left, right)
+            | Expression::Equal(_, left, right)
+            | Expression::NotEqual(_, left, right) => (left, right),
+            _ => return None,
+        };
+        let left_token = Self::token_of(left, file, locals)?;
+        let right_token = Self::token_of(right, file, locals)?;
+        if left_token == right_token {
+            return None;
+        }
+        Some(format!(
+            "`{}` and `{}` look like amounts of different tokens (`{}`, `{}`) combined without \
+             normalizing decimals",
+            left, right, left_token, right_token
+        ))
+    }
+
+    /// The token an operand is a raw amount of, if it can be told.
+    fn token_of(
+        expr: &Expression,
+        file: &SolidityFile,
+        locals: &HashMap<String, Expression>,
+    ) -> Option<String> {
+        match expr {
+            Expression::Parenthesis(_, inner) => Self::token_of(inner, file, locals),
+            Expression::FunctionCall(..) => Self::balance_of_token(expr),
+            Expression::Variable(id) => match locals.get(&id.name) {
+                Some(value) => match Self::balance_of_token(value) {
+                    Some(token) => Some(token),
+                    // A local computed from a product, quotient or call may well be normalized
+                    None if Self::may_normalize(value, file) => None,
+                    None => Self::token_in_name(&id.name),
+                },
+                None => Self::token_in_name(&id.name),
+            },
+            Expression::MemberAccess(_, _, member) => Self::token_in_name(&member.name),
+            _ => None,
+        }
+    }
+
+    /// The token of `token.balanceOf(...)`, looking through casts such as
+    /// `IERC20(address(token)).balanceOf(...)`.
+    fn balance_of_token(expr: &Expression) -> Option<String> {
+        let Expression::FunctionCall(_, func, _) = expr else {
+            return None;
+        };
+        let Expression::MemberAccess(_, base, member) = func.as_ref() else {
+            return None;
+        };
+        if member.name != "balanceOf" {
+            return None;
+        }
+        Some(Self::uncast(base).to_string().to_lowercase())
+    }
+
+    /// `token` in `IERC20(address(token))`.
+    fn uncast(expr: &Expression) -> &Expression {
+        match expr {
+            Expression::FunctionCall(_, cast, args)
+                if args.len() == 1
+                    && matches!(
+                        cast.as_ref(),
+                        Expression::Variable(_) | Expression::Type(..)
+                    ) =>
+            {
+                Self::uncast(&args[0])
+            }
+            _ => expr,
+        }
+    }
+
+    fn may_normalize(expr: &Expression, file: &SolidityFile) -> bool {
+        !find_in_expression(expr, file, "", |e| {
+            matches!(
+                e,
+                Expression::Multiply(..)
+                    | Expression::Divide(..)
+                    | Expression::Power(..)
+                    | Expression::FunctionCall(..)
+            )
+        })
+        .is_empty()
+    }
+
+    /// The token named next to an amount word, e.g. `dai` in `daiAmount` or `weth` in
+    /// `amountWeth`, when it looks like a token.
+    fn token_in_name(name: &str) -> Option<String> {
+        let lower = name.to_lowercase();
+        let (start, word) = AMOUNT_WORDS
+            .iter()
+            .find_map(|word| lower.find(word).map(|start| (start, *word)))?;
+        let token = if start > 0 {
+            &lower[..start]
+        } else {
+            &lower[word.len()..]
+        };
+        let token = token.trim_matches('_');
+        let candidate = Expression::Variable(Identifier {
+            loc: Loc::Implicit,
+            name: token.to_string(),
+        });
+        is_likely_erc20_token(&candidate).then(|| token.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::run_detector_on_code;
+
+    #[test]
+    fn test_mixed_decimals_arithmetic() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            interface IERC20 {
+                function balanceOf(address account) external view returns (uint256);
+                function decimals() external view returns (uint8);
+            }
+
+            contract Portfolio {
+                IERC20 dai;
+                IERC20 usdc;
+                uint256 wethReserve;
+
+                function totalValue() external view returns (uint256) {
+                    return dai.balanceOf(address(this)) + usdc.balanceOf(address(this)); // Positive
+                }
+
+                function isBalanced(uint256 daiAmount, uint256 usdcAmount) external pure returns (bool) {
+                    return daiAmount >= usdcAmount;                                      // Positive
+                }
+
+                function addWeth(uint256 usdcAmount) external view returns (uint256) {
+                    uint256 held = IERC20(address(usdc)).balanceOf(address(this));
+                    return wethReserve - held + usdcAmount;                              // Positive
+                }
+
+                function daiHeld(uint256 daiAmount) external view returns (uint256) {
+                    uint256 daiBalance = dai.balanceOf(address(this));
+                    return daiBalance + daiAmount;                                       // Negative: same token
+                }
+
+                function totalNormalized() external view returns (uint256) {
+                    uint256 usdcAmount = usdc.balanceOf(address(this));
+                    uint256 usdcBalance = usdcAmount * 10 ** (18 - usdc.decimals());
+                    return dai.balanceOf(address(this)) + usdcAmount * 1e12               // Negative: scaled
+                        + (dai.balanceOf(address(this)) - usdcBalance);                  // Negative: scaled local
+                }
+
+                function amounts(uint256 amount0, uint256 amount1) external pure returns (uint256) {
+                    return amount0 + amount1;                                            // Negative: no token names
+                }
+            }
+        "#;
+        let detector = Arc::new(MixedDecimalsArithmeticDetector::default());
+        let locations = run_detector_on_code(detector, code, "portfolio.sol");
+
+        let lines: Vec<_> = locations.iter().map(|l| l.line).collect();
+        assert_eq!(lines, [15, 19, 24], "{:?}", locations);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some(
+                "`dai.balanceOf(address(this))` and `usdc.balanceOf(address(this))` look like \
+                 amounts of different tokens (`dai`, `usdc`) combined without normalizing \
+                 decimals"
+            )
+        );
+        assert_eq!(
+            locations[2].message.as_deref(),
+            Some(
+                "`wethReserve` and `held` look like amounts of different tokens (`weth`, \
+                 `usdc`) combined without normalizing decimals"
+            )
+        );
+    }
+}
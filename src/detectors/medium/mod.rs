@@ -1,6 +1,9 @@
+pub mod address_balance_accounting;
+pub mod array_length_mutation_in_loop;
 pub mod block_number_l2;
 pub mod centralization_risk;
 pub mod chainlink_stale_price;
+pub mod deposit_token_rescue;
 pub mod deprecated_chainlink_function;
 pub mod deprecated_transfer;
 pub mod direct_supports_interface;
@@ -8,22 +11,37 @@ pub mod eip712_compliance;
 pub mod fee_on_transfer;
 pub mod l2_sequencer_check;
 pub mod library_function_visibility;
+pub mod locked_ether;
+pub mod mixed_decimals_arithmetic;
+pub mod msg_value_misuse;
 pub mod nft_mint_asymmetry;
+pub mod packed_hash_mapping_key;
+pub mod proxy_upgrade_management;
+pub mod signature_replay;
 pub mod solady_safetransfer;
 pub mod solmate_safetransfer;
 pub mod tx_origin_usage;
 pub mod unbounded_fee;
-pub mod unchecked_low_level_call;
+pub mod unbounded_state_pricing;
 pub mod unchecked_transfer;
+pub mod unguarded_balance_delta;
+pub mod uninitialized_state_variable;
 pub mod unsafe_approve;
 pub mod unsafe_erc20_operations;
 pub mod unsafe_mint;
 pub mod unsafe_transferfrom;
+pub mod upgradeable_storage_collision;
 pub mod usdt_allowance;
+pub mod weak_randomness;
 
+use crate::detectors::Detector;
+
+pub use address_balance_accounting::AddressBalanceAccountingDetector;
+pub use array_length_mutation_in_loop::ArrayLengthMutationInLoopDetector;
 pub use block_number_l2::BlockNumberL2Detector;
 pub use centralization_risk::CentralizationRiskDetector;
 pub use chainlink_stale_price::ChainlinkStalePriceDetector;
+pub use deposit_token_rescue::DepositTokenRescueDetector;
 pub use deprecated_chainlink_function::DeprecatedChainlinkFunctionDetector;
 pub use deprecated_transfer::DeprecatedTransferDetector;
 pub use direct_supports_interface::DirectSupportsInterfaceDetector;
@@ -31,15 +49,67 @@ pub use eip712_compliance::Eip712ComplianceDetector;
 pub use fee_on_transfer::FeeOnTransferDetector;
 pub use l2_sequencer_check::L2SequencerCheckDetector;
 pub use library_function_visibility::LibraryFunctionVisibilityDetector;
+pub use locked_ether::LockedEtherDetector;
+pub use mixed_decimals_arithmetic::MixedDecimalsArithmeticDetector;
+pub use msg_value_misuse::MsgValueMisuseDetector;
 pub use nft_mint_asymmetry::NftMintAsymmetryDetector;
+pub use packed_hash_mapping_key::PackedHashMappingKeyDetector;
+pub use proxy_upgrade_management::ProxyUpgradeManagementDetector;
+pub use signature_replay::SignatureReplayDetector;
 pub use solady_safetransfer::SoladySafeTransferDetector;
 pub use solmate_safetransfer::SolmateSafeTransferDetector;
 pub use tx_origin_usage::TxOriginUsageDetector;
 pub use unbounded_fee::UnboundedFeeDetector;
-pub use unchecked_low_level_call::UncheckedLowLevelCallDetector;
+pub use unbounded_state_pricing::UnboundedStatePricingDetector;
 pub use unchecked_transfer::UncheckedTransferDetector;
+pub use unguarded_balance_delta::UnguardedBalanceDeltaDetector;
+pub use uninitialized_state_variable::UninitializedStateVariableDetector;
 pub use unsafe_approve::UnsafeApproveDetector;
 pub use unsafe_erc20_operations::UnsafeErc20OperationsDetector;
 pub use unsafe_mint::UnsafeMintDetector;
 pub use unsafe_transferfrom::UnsafeTransferFromDetector;
-pub use usdt_allowance::UsdtAllowanceDetector;
\ No newline at end of file
+pub use upgradeable_storage_collision::UpgradeableStorageCollisionDetector;
+pub use usdt_allowance::UsdtAllowanceDetector;
+pub use weak_randomness::WeakRandomnessDetector;
+
+/// Every Medium detector, in registration order. A detector exported above but missing
+/// here never runs, which `test_every_exported_detector_is_built_in` catches.
+pub fn all() -> Vec<Box<dyn Detector>> {
+    vec![
+        Box::new(AddressBalanceAccountingDetector::default()),
+        Box::new(ArrayLengthMutationInLoopDetector::default()),
+        Box::new(BlockNumberL2Detector::default()),
+        Box::new(CentralizationRiskDetector::default()),
+        Box::new(ChainlinkStalePriceDetector::default()),
+        Box::new(DepositTokenRescueDetector::default()),
+        Box::new(DeprecatedChainlinkFunctionDetector::default()),
+        Box::new(DeprecatedTransferDetector::default()),
+        Box::new(DirectSupportsInterfaceDetector::default()),
+        Box::new(Eip712ComplianceDetector::default()),
+        Box::new(FeeOnTransferDetector::default()),
+        Box::new(L2SequencerCheckDetector::default()),
+        Box::new(LibraryFunctionVisibilityDetector::default()),
+        Box::new(LockedEtherDetector::default()),
+        Box::new(MixedDecimalsArithmeticDetector::default()),
+        Box::new(MsgValueMisuseDetector::default()),
+        Box::new(NftMintAsymmetryDetector::default()),
+        Box::new(PackedHashMappingKeyDetector::default()),
+        Box::new(ProxyUpgradeManagementDetector::default()),
+        Box::new(SignatureReplayDetector::default()),
+        Box::new(SoladySafeTransferDetector::default()),
+        Box::new(SolmateSafeTransferDetector::default()),
+        Box::new(TxOriginUsageDetector::default()),
+        Box::new(UnboundedFeeDetector::default()),
+        Box::new(UnboundedStatePricingDetector::default()),
+        Box::new(UncheckedTransferDetector::default()),
+        Box::new(UnguardedBalanceDeltaDetector::default()),
+        Box::new(UninitializedStateVariableDetector::default()),
+        Box::new(UnsafeApproveDetector::default()),
+        Box::new(UnsafeErc20OperationsDetector::default()),
+        Box::new(UnsafeMintDetector::default()),
+        Box::new(UnsafeTransferFromDetector::default()),
+        Box::new(UpgradeableStorageCollisionDetector::default()),
+        Box::new(UsdtAllowanceDetector::default()),
+        Box::new(WeakRandomnessDetector::default()),
+    ]
+}
@@ -0,0 +1,375 @@
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::category::Category;
+use crate::models::finding::Location;
+use crate::models::severity::Severity;
+use crate::models::{FindingData, SolidityFile};
+use crate::utils::ast_utils::{
+    call_option, contains_address_this, find_locations_in_statement, find_statement_types,
+    get_function_visibility, has_privileged_modifier,
+};
+use crate::utils::location::loc_to_location;
+use solang_parser::pt::{
+    ContractPart, ContractTy, Expression, FunctionAttribute, FunctionDefinition, FunctionTy, Loc,
+    Mutability, Statement, Visibility,
+};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+
+/// What a function body does with the ether sent along with the call.
+#[derive(Debug, Default)]
+struct EtherUse {
+    /// Reads `msg.value`
+    reads_msg_value: bool,
+    /// Forwards ether with `{value: ...}`, reads `address(this).balance` or uses assembly
+    handles_ether: bool,
+    /// Functions called by plain name, with the location of the first call to each
+    callees: BTreeMap<String, Location>,
+}
+
+#[derive(Debug, Default)]
+pub struct MsgValueMisuseDetector;
+
+impl Detector for MsgValueMisuseDetector {
+    fn id(&self) -> &'static str {
+        "msg-value-misuse"
+    }
+
+    fn name(&self) -> &str {
+        "`msg.value` is always zero, or ether sent to a function is never used"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Medium
+    }
+
+    fn category(&self) -> Category {
+        Category::Validation
+    }
+
+    fn description(&self) -> &str {
+        "Reading `msg.value` directly in a non-payable public function does not compile, but an \
+         internal helper that reads it can be called from one. The helper then always sees 0, so \
+         payments it checks or records never happen. This reports calls from non-payable \
+         functions and constructors to functions of the same contract that read `msg.value`, \
+         directly or through further internal calls. It also reports payable functions that \
+         neither read `msg.value`, nor forward ether with `{value: ...}`, nor read \
+         `address(this).balance`, directly or through the functions they call: ether sent to \
+         them is not accounted for and usually gets stuck. Functions with an access-control \
+         modifier are left out of the second check, since admin functions are often made \
+         payable to save gas. `receive()` and `fallback()` are covered by `locked-ether`."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad - `msg.value` is always 0 in `_pay`, and ether sent to `tip` is never recorded
+function buy(uint256 id) external {
+    _pay(prices[id]);
+}
+
+function _pay(uint256 price) internal {
+    require(msg.value == price, "wrong price");
+}
+
+function tip() external payable {
+    emit Tipped(msg.sender);
+}
+
+// Good
+function buy(uint256 id) external payable {
+    _pay(prices[id]);
+}
+
+function tip() external payable {
+    tips[msg.sender] += msg.value;
+    emit Tipped(msg.sender);
+}
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_contract(move |contract_def, file, _context| {
+            if !matches!(
+                contract_def.ty,
+                ContractTy::Contract(_) | ContractTy::Abstract(_)
+            ) {
+                return Vec::new();
+            }
+
+            let functions: Vec<&FunctionDefinition> = contract_def
+                .parts
+                .iter()
+                .filter_map(|part| match part {
+                    ContractPart::FunctionDefinition(f) if f.body.is_some() => Some(f.as_ref()),
+                    _ => None,
+                })
+                .collect();
+
+            // Overloads share a name, as calls are matched by name only
+            let mut uses: HashMap<String, EtherUse> = HashMap::new();
+            for func_def in &functions {
+                let Some(name) = &func_def.name else {
+                    continue;
+                };
+                let found = Self::ether_use(func_def, file);
+                let merged = uses.entry(name.name.clone()).or_default();
+                merged.reads_msg_value |= found.reads_msg_value;
+                merged.handles_ether |= found.handles_ether;
+                for (callee, location) in found.callees {
+                    merged.callees.entry(callee).or_insert(location);
+                }
+            }
+
+            let mut findings = Vec::new();
+            for func_def in functions {
+                let payable = Self::is_payable(func_def);
+                let callable = match func_def.ty {
+                    FunctionTy::Function => !matches!(
+                        get_function_visibility(func_def),
+                        Some(Visibility::Internal(_) | Visibility::Private(_))
+                    ),
+                    FunctionTy::Constructor | FunctionTy::Fallback => true,
+                    _ => false,
+                };
+                if !callable {
+                    continue;
+                }
+                let function_name = func_def
+                    .name
+                    .as_ref()
+                    .map_or_else(|| func_def.ty.to_string(), |n| n.name.clone());
+
+                if !payable {
+                    let found = Self::ether_use(func_def, file);
+                    for (callee, location) in found.callees {
+                        if callee != function_name
+                            && Self::reaches(&callee, &uses, &mut HashSet::new(), |u| {
+                                u.reads_msg_value
+                            })
+                        {
+                            findings.push(FindingData {
+                                detector_id: self.id(),
+                                location: location.with_message(format!(
+                                    "`{}` is not payable, so `msg.value` read through `{}` is \
+                                     always 0",
+                                    function_name, callee
+                                )),
+                            });
+                        }
+                    }
+                } else if func_def.ty == FunctionTy::Function
+                    && !has_privileged_modifier(func_def)
+                    && !Self::reaches(&function_name, &uses, &mut HashSet::new(), |u| {
+                        u.reads_msg_value || u.handles_ether
+                    })
+                {
+                    findings.push(FindingData {
+                        detector_id: self.id(),
+                        location: loc_to_location(&func_def.loc, file).with_message(format!(
+                            "`{}` is payable, but never reads `msg.value` or forwards ether, so \
+                             ether sent to it is not accounted for",
+                            function_name
+                        )),
+                    });
+                }
+            }
+
+            findings
+        });
+    }
+}
+
+impl MsgValueMisuseDetector {
+    fn is_payable(func_def: &FunctionDefinition) -> bool {
+        func_def
+            .attributes
+            .iter()
+            .any(|attr| matches!(attr, FunctionAttribute::Mutability(Mutability::Payable(_))))
+    }
+
+    fn ether_use(func_def: &FunctionDefinition, file: &SolidityFile) -> EtherUse {
+        let Some(body) = &func_def.body else {
+            return EtherUse::default();
+        };
+        let mut found = EtherUse::default();
+        let mut callees = Vec::new();
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            match expr {
+                Expression::MemberAccess(_, base, member) if member.name == "value" => {
+                    found.reads_msg_value |=
+                        matches!(base.as_ref(), Expression::Variable(id) if id.name == "msg");
+                }
+                Expression::MemberAccess(_, base, member) if member.name == "balance" => {
+                    found.handles_ether |= contains_address_this(base);
+                }
+                Expression::FunctionCallBlock(..) => {
+                    found.handles_ether |= call_option(expr, "value").is_some();
+                }
+                Expression::FunctionCall(loc, callee, _) => {
+                    if let Expression::Variable(id) = callee.as_ref() {
+                        callees.push(id.name.clone());
+                        return Some(*loc);
+                    }
+                }
+                _ => {}
+            }
+            None
+        };
+        let mut call_locations = Vec::new();
+        find_locations_in_statement(body, file, &mut predicate, &mut call_locations);
+        for (callee, location) in callees.into_iter().zip(call_locations) {
+            found.callees.entry(callee).or_insert(location);
+        }
+
+        // Assembly can read `callvalue()` or `selfbalance()`, which this check does not follow
+        found.handles_ether |= !find_statement_types(body, file, "msg-value-misuse", |stmt| {
+            matches!(stmt, Statement::Assembly { .. })
+        })
+        .is_empty();
+        found
+    }
+
+    /// Whether `name`, or a function it calls in the same contract, matches `predicate`.
+    fn reaches<'a>(
+        name: &'a str,
+        uses: &'a HashMap<String, EtherUse>,
+        visited: &mut HashSet<&'a str>,
+        predicate: fn(&EtherUse) -> bool,
+    ) -> bool {
+        if !visited.insert(name) {
+            return false;
+        }
+        let Some(ether_use) = uses.get(name) else {
+            return false;
+        };
+        predicate(ether_use)
+            || ether_use
+                .callees
+                .keys()
+                .any(|callee| Self::reaches(callee, uses, visited, predicate))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::run_detector_on_code;
+
+    #[test]
+    fn test_detects_msg_value_read_from_non_payable_caller() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            contract Shop {
+                mapping(uint256 => uint256) prices;
+                mapping(address => uint256) paid;
+
+                function buy(uint256 id) external {
+                    _checkout(id);
+                }
+
+                function buyPayable(uint256 id) external payable {
+                    _checkout(id);
+                }
+
+                function _checkout(uint256 id) internal {
+                    _pay(prices[id]);
+                }
+
+                function _pay(uint256 price) internal {
+                    require(msg.value == price, "wrong price");
+                    paid[msg.sender] += msg.value;
+                }
+
+                function _log() internal {}
+
+                function refresh() external {
+                    _log();
+                }
+            }
+        "#;
+        let detector = Arc::new(MsgValueMisuseDetector::default());
+        let locations = run_detector_on_code(detector, code, "test.sol");
+        assert_eq!(locations.len(), 1);
+        assert_eq!(locations[0].line, 9);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some("`buy` is not payable, so `msg.value` read through `_checkout` is always 0")
+        );
+    }
+
+    #[test]
+    fn test_detects_payable_function_ignoring_ether() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            contract Tips {
+                event Tipped(address from);
+                mapping(address => uint256) tips;
+                address owner;
+
+                function tip() external payable {
+                    emit Tipped(msg.sender);
+                }
+
+                function tipRecorded() external payable {
+                    _record();
+                }
+
+                function forward(address to) external payable {
+                    (bool ok, ) = to.call{value: address(this).balance}("");
+                    require(ok);
+                }
+
+                function setOwner(address o) external payable onlyOwner {
+                    owner = o;
+                }
+
+                function _record() internal {
+                    tips[msg.sender] += msg.value;
+                }
+            }
+        "#;
+        let detector = Arc::new(MsgValueMisuseDetector::default());
+        let locations = run_detector_on_code(detector, code, "test.sol");
+        assert_eq!(locations.len(), 1);
+        assert_eq!(locations[0].line, 9);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some(
+                "`tip` is payable, but never reads `msg.value` or forwards ether, so ether sent \
+                 to it is not accounted for"
+            )
+        );
+    }
+
+    #[test]
+    fn test_skips_recursion_and_unknown_callees() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            contract Vault is Base {
+                function sync() external {
+                    _inherited();
+                    require(_count(3) > 0);
+                }
+
+                function _count(uint256 n) internal returns (uint256) {
+                    return n == 0 ? 0 : _count(n - 1);
+                }
+
+                function deposit() external payable {
+                    assembly {
+                        sstore(0, callvalue())
+                    }
+                }
+            }
+        "#;
+        let detector = Arc::new(MsgValueMisuseDetector::default());
+        let locations = run_detector_on_code(detector, code, "test.sol");
+        assert_eq!(locations.len(), 0);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::location::loc_to_location;
 use crate::{core::visitor::ASTVisitor, models::FindingData};
@@ -20,6 +21,10 @@ impl Detector for NftMintAsymmetryDetector {
         Severity::Medium
     }
 
+    fn category(&self) -> Category {
+        Category::Token
+    }
+
     fn description(&self) -> &str {
         "If one of the functions is implemented, the other should be as well. \
         The _mint() variant is supposed to skip onERC721Received() checks, whereas _safeMint() does not. \
@@ -0,0 +1,368 @@
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::category::Category;
+use crate::models::scope::TypeInfo;
+use crate::models::severity::Severity;
+use crate::models::{FindingData, SolidityFile};
+use crate::utils::ast_utils::{
+    build_variable_type_map, find_in_expression, find_in_statement, find_statement_types,
+    get_contract_info, resolve_assignment_target_type,
+};
+use solang_parser::pt::{ContractPart, Expression, FunctionDefinition, Statement};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+#[derive(Debug, Default)]
+pub struct PackedHashMappingKeyDetector;
+
+impl Detector for PackedHashMappingKeyDetector {
+    fn id(&self) -> &'static str {
+        "packed-hash-mapping-key"
+    }
+
+    fn name(&self) -> &str {
+        "Mapping keyed by `keccak256(abi.encodePacked(...))` of several dynamic values"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Medium
+    }
+
+    fn category(&self) -> Category {
+        Category::Cryptography
+    }
+
+    fn description(&self) -> &str {
+        "A mapping is indexed with `keccak256(abi.encodePacked(...))` over two or more dynamic \
+         values (`string`, `bytes`, dynamic arrays), at least one of them taken from a function \
+         parameter. Packed encoding does not record where one dynamic value ends, so \
+         `(\"ab\", \"c\")` and `(\"a\", \"bc\")` hash to the same key: a caller can read or \
+         overwrite the entry registered for a different pair of inputs, e.g. hijack a \
+         name/symbol registration. Use `abi.encode(...)`, which length-prefixes each value. \
+         String literals are fixed and do not count as dynamic values here."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad - register("ab", "c") and register("a", "bc") write the same entry
+function register(string calldata name, string calldata symbol) external {
+    bytes32 key = keccak256(abi.encodePacked(name, symbol));
+    require(owners[key] == address(0), "taken");
+    owners[key] = msg.sender;
+}
+
+// Good
+function register(string calldata name, string calldata symbol) external {
+    bytes32 key = keccak256(abi.encode(name, symbol));
+    require(owners[key] == address(0), "taken");
+    owners[key] = msg.sender;
+}
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_contract(move |contract_def, file, _context| {
+            let Some(contract_info) = get_contract_info(contract_def, file) else {
+                return Vec::new();
+            };
+            let state_var_types: HashMap<String, TypeInfo> = contract_info
+                .state_variables
+                .iter()
+                .map(|v| (v.name.clone(), v.type_info.clone()))
+                .collect();
+            if !state_var_types
+                .values()
+                .any(|ty| matches!(ty, TypeInfo::Mapping { .. }))
+            {
+                return Vec::new();
+            }
+
+            let mut all_findings = Vec::new();
+            for part in &contract_def.parts {
+                let ContractPart::FunctionDefinition(func_def) = part else {
+                    continue;
+                };
+                let Some(body) = &func_def.body else {
+                    continue;
+                };
+                let var_types = build_variable_type_map(func_def, body, &state_var_types);
+                let locals = Self::local_values(body, file);
+                let tainted = Self::parameter_tainted(func_def, file, &locals);
+
+                let mut messages = Vec::new();
+                let findings = find_in_statement(body, file, self.id(), |expr| {
+                    match Self::check_subscript(expr, file, &var_types, &locals, &tainted) {
+                        Some(message) => {
+                            messages.push(message);
+                            true
+                        }
+                        None => false,
+                    }
+                });
+                all_findings.extend(findings.into_iter().zip(messages).map(
+                    |(finding, message)| FindingData {
+                        location: finding.location.with_message(message),
+                        ..finding
+                    },
+                ));
+            }
+            all_findings
+        });
+    }
+}
+
+impl PackedHashMappingKeyDetector {
+    /// Locals declared or assigned in the body, mapped to the last value given to them.
+    fn local_values(body: &Statement, file: &SolidityFile) -> HashMap<String, Expression> {
+        let mut values = HashMap::new();
+        find_statement_types(body, file, "", |stmt| {
+            match stmt {
+                Statement::VariableDefinition(_, decl, Some(value)) => {
+                    if let Some(name) = &decl.name {
+                        values.insert(name.name.clone(), value.clone());
+                    }
+                }
+                Statement::Expression(_, Expression::Assign(_, target, value)) => {
+                    if let Expression::Variable(name) = target.as_ref() {
+                        values.insert(name.name.clone(), value.as_ref().clone());
+                    }
+                }
+                _ => {}
+            }
+            false
+        });
+        values
+    }
+
+    /// Parameters, plus locals whose value reads a parameter or another such local.
+    fn parameter_tainted(
+        func_def: &FunctionDefinition,
+        file: &SolidityFile,
+        locals: &HashMap<String, Expression>,
+    ) -> HashSet<String> {
+        let mut tainted: HashSet<String> = func_def
+            .params
+            .iter()
+            .filter_map(|(_, param)| param.as_ref()?.name.as_ref())
+            .map(|name| name.name.clone())
+            .collect();
+        loop {
+            let before = tainted.len();
+            for (name, value) in locals {
+                if !tainted.contains(name) && Self::reads_any(value, file, &tainted) {
+                    tainted.insert(name.clone());
+                }
+            }
+            if tainted.len() == before {
+                return tainted;
+            }
+        }
+    }
+
+    fn reads_any(expr: &Expression, file: &SolidityFile, names: &HashSet<String>) -> bool {
+        !find_in_expression(
+            expr,
+            file,
+            "",
+            |e| matches!(e, Expression::Variable(id) if names.contains(&id.name)),
+        )
+        .is_empty()
+    }
+
+    fn check_subscript(
+        expr: &Expression,
+        file: &SolidityFile,
+        var_types: &HashMap<String, TypeInfo>,
+        locals: &HashMap<String, Expression>,
+        tainted: &HashSet<String>,
+    ) -> Option<String> {
+        let Expression::ArraySubscript(_, base, Some(index)) = expr else {
+            return None;
+        };
+        if !matches!(
+            resolve_assignment_target_type(base, var_types, &[]),
+            Some(TypeInfo::Mapping { .. })
+        ) {
+            return None;
+        }
+
+        let packed_args = Self::packed_hash_args(index, locals)?;
+        let dynamic: Vec<&Expression> = packed_args
+            .iter()
+            .filter(|arg| Self::is_dynamic(arg, var_types))
+            .collect();
+        if dynamic.len() < 2
+            || !dynamic
+                .iter()
+                .any(|arg| Self::reads_any(arg, file, tainted))
+        {
+            return None;
+        }
+
+        let names: Vec<String> = dynamic.iter().map(|arg| arg.to_string()).collect();
+        Some(format!(
+            "`{}` is keyed by a packed hash of the dynamic values `{}`, so different inputs can \
+             share a key",
+            base,
+            names.join("`, `")
+        ))
+    }
+
+    /// Arguments of `abi.encodePacked` in `keccak256(abi.encodePacked(...))`, written inline or
+    /// stored in a local first.
+    fn packed_hash_args<'a>(
+        index: &'a Expression,
+        locals: &'a HashMap<String, Expression>,
+    ) -> Option<&'a [Expression]> {
+        match index {
+            Expression::Parenthesis(_, inner) => Self::packed_hash_args(inner, locals),
+            // Only a local holding the hash itself is followed, so aliases can't loop
+            Expression::Variable(id) => match locals.get(&id.name)? {
+                value @ Expression::FunctionCall(..) => Self::packed_hash_args(value, locals),
+                _ => None,
+            },
+            Expression::FunctionCall(_, func, args) => {
+                let Expression::Variable(id) = func.as_ref() else {
+                    return None;
+                };
+                if id.name != "keccak256" || args.len() != 1 {
+                    return None;
+                }
+                let Expression::FunctionCall(_, inner, packed_args) = &args[0] else {
+                    return None;
+                };
+                match inner.as_ref() {
+                    Expression::MemberAccess(_, abi, member)
+                        if member.name == "encodePacked"
+                            && matches!(abi.as_ref(), Expression::Variable(v) if v.name == "abi") =>
+                    {
+                        Some(packed_args)
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// `string`, `bytes` and dynamic array values, including `string(...)` / `bytes(...)` casts.
+    fn is_dynamic(expr: &Expression, var_types: &HashMap<String, TypeInfo>) -> bool {
+        match expr {
+            Expression::Parenthesis(_, inner) => Self::is_dynamic(inner, var_types),
+            Expression::FunctionCall(_, func, _) => matches!(
+                func.as_ref(),
+                Expression::Type(_, solang_parser::pt::Type::String)
+                    | Expression::Type(_, solang_parser::pt::Type::DynamicBytes)
+            ),
+            _ => matches!(
+                resolve_assignment_target_type(expr, var_types, &[]),
+                Some(TypeInfo::String)
+                    | Some(TypeInfo::DynamicBytes)
+                    | Some(TypeInfo::Array { size: None, .. })
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detectors::low::UnsafeAbiEncodePackedDetector;
+    use crate::utils::test_utils::run_detector_on_code;
+
+    #[test]
+    fn test_packed_hash_mapping_key() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            contract TokenRegistry {
+                mapping(bytes32 => address) public owners;
+                mapping(address => mapping(bytes32 => bool)) public claimed;
+                string public prefix;
+
+                function register(string calldata name, string calldata symbol) external {
+                    owners[keccak256(abi.encodePacked(name, symbol))] = msg.sender;  // Positive
+                }
+
+                function claim(bytes memory data, string memory tag) external {
+                    bytes32 key = keccak256(abi.encodePacked(data, tag));
+                    claimed[msg.sender][key] = true;                                  // Positive
+                }
+
+                function registerPrefixed(string calldata name) external {
+                    owners[keccak256(abi.encodePacked(prefix, name))] = msg.sender;  // Positive
+                }
+
+                function registerLabel(string calldata name) external {
+                    owners[keccak256(abi.encodePacked("label:", name))] = msg.sender; // Negative: literal
+                }
+
+                function registerId(string calldata name, uint256 id) external {
+                    owners[keccak256(abi.encodePacked(name, id))] = msg.sender;     // Negative: one dynamic
+                }
+
+                function registerEncoded(string calldata name, string calldata symbol) external {
+                    owners[keccak256(abi.encode(name, symbol))] = msg.sender;       // Negative: abi.encode
+                }
+
+                function registerDefault() external {
+                    owners[keccak256(abi.encodePacked(prefix, prefix))] = msg.sender; // Negative: no parameter
+                }
+            }
+        "#;
+        let detector = Arc::new(PackedHashMappingKeyDetector::default());
+        let locations = run_detector_on_code(detector, code, "registry.sol");
+
+        let lines: Vec<_> = locations.iter().map(|l| l.line).collect();
+        assert_eq!(lines, [10, 15, 19]);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some(
+                "`owners` is keyed by a packed hash of the dynamic values `name`, `symbol`, so \
+                 different inputs can share a key"
+            )
+        );
+        assert_eq!(
+            locations[1].message.as_deref(),
+            Some(
+                "`claimed[msg.sender]` is keyed by a packed hash of the dynamic values `data`, \
+                 `tag`, so different inputs can share a key"
+            )
+        );
+    }
+
+    #[test]
+    fn test_only_mapping_keys_are_reported() {
+        let code = r#"
+            contract NameIndex {
+                mapping(bytes32 => uint256) public ids;
+
+                function nameHash(string calldata first, string calldata last) external pure returns (bytes32) {
+                    return keccak256(abi.encodePacked(first, last));
+                }
+
+                function index(string calldata first, string calldata last, uint256 id) external {
+                    ids[keccak256(abi.encodePacked(first, last))] = id;
+                }
+            }
+        "#;
+        let general = run_detector_on_code(
+            Arc::new(UnsafeAbiEncodePackedDetector::default()),
+            code,
+            "index.sol",
+        );
+        let general_lines: Vec<_> = general.iter().map(|l| l.line).collect();
+        assert_eq!(general_lines, [6, 10]);
+
+        let locations = run_detector_on_code(
+            Arc::new(PackedHashMappingKeyDetector::default()),
+            code,
+            "index.sol",
+        );
+        let lines: Vec<_> = locations.iter().map(|l| l.line).collect();
+        assert_eq!(lines, [10]);
+    }
+}
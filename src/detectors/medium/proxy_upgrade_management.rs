@@ -0,0 +1,460 @@
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::category::Category;
+use crate::models::severity::Severity;
+use crate::models::{FindingData, SolidityFile};
+use crate::utils::ast_utils::find_locations_in_statement;
+use crate::utils::location::loc_to_location;
+use solang_parser::pt::{
+    CodeLocation, ContractDefinition, ContractPart, Expression, FunctionAttribute,
+    FunctionDefinition, FunctionTy, Loc, Mutability, Statement, Type, Visibility,
+};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Leading digits of the EIP-1967 implementation, admin and beacon slots.
+const EIP1967_SLOT_PREFIXES: [&str; 3] = ["0x360894a13", "0xb53127684", "0xa3f0ad74e"];
+
+/// Source fragments that check an address has deployed code.
+const CODE_CHECKS: [&str; 3] = [".code.length", "extcodesize", "isContract"];
+
+/// Modifier name fragments, besides the `only` prefix, that restrict the caller.
+const GUARD_MODIFIERS: [&str; 2] = ["ifadmin", "auth"];
+
+/// Called function name fragments that check the caller.
+const GUARD_CALLS: [&str; 5] = ["auth", "checkowner", "checkrole", "checkadmin", "hasrole"];
+
+#[derive(Debug, Default)]
+pub struct ProxyUpgradeManagementDetector;
+
+impl Detector for ProxyUpgradeManagementDetector {
+    fn id(&self) -> &'static str {
+        "proxy-upgrade-management"
+    }
+
+    fn name(&self) -> &str {
+        "Proxy upgrade or admin change function is unguarded, unvalidated or single-step"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Medium
+    }
+
+    fn category(&self) -> Category {
+        Category::Upgradeability
+    }
+
+    fn description(&self) -> &str {
+        "In a proxy (EIP-1967 slot constants, `delegatecall` in the fallback, or a `*Proxy`/UUPS \
+         base), the functions that swap the implementation or the admin control all of the \
+         proxy's storage and funds. An upgrade function without an access-control modifier or \
+         `msg.sender` check lets anyone replace the logic; one that does not check the new \
+         implementation has code can point the proxy at an EOA, after which every call succeeds \
+         silently and the proxy may be bricked; and an admin change applied in one step loses \
+         upgrade control for good if the new address is wrong. Restrict these functions, require \
+         `newImplementation.code.length > 0`, and have a pending admin accept the role."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad
+function setImplementation(address newImplementation) external {
+    StorageSlot.getAddressSlot(IMPLEMENTATION_SLOT).value = newImplementation;
+}
+
+// Good
+function setImplementation(address newImplementation) external onlyAdmin {
+    require(newImplementation.code.length > 0, "not a contract");
+    StorageSlot.getAddressSlot(IMPLEMENTATION_SLOT).value = newImplementation;
+}
+
+function changeAdmin(address newAdmin) external onlyAdmin {
+    pendingAdmin = newAdmin;
+}
+
+function acceptAdmin() external {
+    require(msg.sender == pendingAdmin);
+    _setAdmin(pendingAdmin);
+}
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_contract(move |contract_def, file, context| {
+            let Some(contract_name) = &contract_def.name else {
+                return Vec::new();
+            };
+
+            let qualified_name = context.get_qualified_name_for_contract(&contract_name.name);
+            let has_proxy_base = context
+                .get_inheritance_chain(&qualified_name)
+                .unwrap_or_default()
+                .iter()
+                .map(|base| base.rsplit(['.', ':']).next().unwrap_or(base))
+                .filter(|base| *base != contract_name.name)
+                .any(|base| base.ends_with("Proxy") || base.contains("UUPS"));
+            if !has_proxy_base && !Self::looks_like_proxy(contract_def, file) {
+                return Vec::new();
+            }
+
+            let functions: Vec<&FunctionDefinition> = contract_def
+                .parts
+                .iter()
+                .filter_map(|part| match part {
+                    ContractPart::FunctionDefinition(f) if f.ty == FunctionTy::Function => {
+                        Some(f.as_ref())
+                    }
+                    _ => None,
+                })
+                .collect();
+            let names: HashSet<String> = context
+                .get_all_functions(&qualified_name)
+                .iter()
+                .map(|f| f.name.to_lowercase())
+                .collect();
+            let has_accept_admin = names.iter().any(|name| {
+                let name = name.trim_start_matches('_');
+                (name.starts_with("accept") || name.starts_with("claim"))
+                    && (name.contains("admin") || name.contains("ownership"))
+            });
+
+            let mut findings = Vec::new();
+            for func_def in &functions {
+                let (Some(name), Some(body)) = (&func_def.name, &func_def.body) else {
+                    continue;
+                };
+                if Self::is_read_only(func_def) {
+                    continue;
+                }
+                let lower = name.name.to_lowercase();
+                let is_authorize = lower.contains("authorize");
+                let is_upgrade = lower.contains("upgrade") || lower.contains("implementation");
+                let is_admin_change = lower.contains("admin")
+                    && ["change", "set", "transfer", "update"]
+                        .iter()
+                        .any(|verb| lower.trim_start_matches('_').starts_with(verb));
+                if !is_upgrade && !is_admin_change {
+                    continue;
+                }
+                let location = || loc_to_location(&func_def.loc, file);
+
+                if (is_authorize || Self::is_externally_callable(func_def))
+                    && !Self::is_guarded(func_def, body, file)
+                {
+                    findings.push(FindingData {
+                        detector_id: self.id(),
+                        location: location().with_message(format!(
+                            "`{}` has no access-control modifier or `msg.sender` check",
+                            name.name
+                        )),
+                    });
+                }
+
+                if is_upgrade && !is_authorize {
+                    if let Some(param) = Self::address_param(func_def) {
+                        if !Self::checks_code(body, file, &functions, &mut HashSet::new()) {
+                            findings.push(FindingData {
+                                detector_id: self.id(),
+                                location: location().with_message(format!(
+                                    "`{}` does not check that `{}` has code \
+                                     (`{}.code.length > 0`)",
+                                    name.name, param, param
+                                )),
+                            });
+                        }
+                    }
+                }
+
+                if is_admin_change && !has_accept_admin && Self::is_externally_callable(func_def) {
+                    findings.push(FindingData {
+                        detector_id: self.id(),
+                        location: location().with_message(format!(
+                            "`{}` replaces the admin in one step; store a pending admin that \
+                             must accept the role",
+                            name.name
+                        )),
+                    });
+                }
+            }
+
+            findings
+        });
+    }
+}
+
+impl ProxyUpgradeManagementDetector {
+    /// EIP-1967 slot literals anywhere in the contract, or `delegatecall` in its fallback.
+    fn looks_like_proxy(contract_def: &ContractDefinition, file: &SolidityFile) -> bool {
+        let source = Self::source(&contract_def.loc, file).to_lowercase();
+        if EIP1967_SLOT_PREFIXES
+            .iter()
+            .any(|slot| source.contains(slot))
+        {
+            return true;
+        }
+
+        contract_def.parts.iter().any(|part| match part {
+            ContractPart::FunctionDefinition(f) if f.ty == FunctionTy::Fallback => f
+                .body
+                .as_ref()
+                .is_some_and(|body| Self::source(&body.loc(), file).contains("delegatecall")),
+            _ => false,
+        })
+    }
+
+    fn source<'a>(loc: &Loc, file: &'a SolidityFile) -> &'a str {
+        match loc {
+            Loc::File(_, start, end) => file.content.get(*start..*end).unwrap_or_default(),
+            _ => "",
+        }
+    }
+
+    fn is_read_only(func_def: &FunctionDefinition) -> bool {
+        func_def.attributes.iter().any(|attr| {
+            matches!(
+                attr,
+                FunctionAttribute::Mutability(Mutability::View(_) | Mutability::Pure(_))
+            )
+        })
+    }
+
+    fn is_externally_callable(func_def: &FunctionDefinition) -> bool {
+        func_def.attributes.iter().any(|attr| {
+            matches!(
+                attr,
+                FunctionAttribute::Visibility(Visibility::External(_) | Visibility::Public(_))
+            )
+        })
+    }
+
+    /// Guarded by an `only*`/`ifAdmin`/`auth` modifier, a `msg.sender` comparison or a call to
+    /// an authorization helper (`_checkOwner()`, `_authorizeUpgrade(...)`, `hasRole(...)`).
+    fn is_guarded(func_def: &FunctionDefinition, body: &Statement, file: &SolidityFile) -> bool {
+        let has_modifier = func_def.attributes.iter().any(|attr| {
+            let FunctionAttribute::BaseOrModifier(_, base) = attr else {
+                return false;
+            };
+            base.name.identifiers.last().is_some_and(|id| {
+                let lower = id.name.to_lowercase();
+                lower.starts_with("only") || GUARD_MODIFIERS.iter().any(|m| lower.contains(m))
+            })
+        });
+        if has_modifier {
+            return true;
+        }
+
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            match expr {
+                Expression::Equal(loc, left, right) | Expression::NotEqual(loc, left, right)
+                    if Self::is_msg_sender(left) || Self::is_msg_sender(right) =>
+                {
+                    Some(*loc)
+                }
+                Expression::FunctionCall(loc, callee, _) => {
+                    let name = match callee.as_ref() {
+                        Expression::Variable(id) | Expression::MemberAccess(_, _, id) => &id.name,
+                        _ => return None,
+                    };
+                    let lower = name.to_lowercase();
+                    GUARD_CALLS
+                        .iter()
+                        .any(|call| lower.contains(call))
+                        .then_some(*loc)
+                }
+                _ => None,
+            }
+        };
+        let mut found = Vec::new();
+        find_locations_in_statement(body, file, &mut predicate, &mut found);
+        !found.is_empty()
+    }
+
+    fn is_msg_sender(expr: &Expression) -> bool {
+        matches!(
+            expr,
+            Expression::MemberAccess(_, base, member)
+                if member.name == "sender"
+                    && matches!(base.as_ref(), Expression::Variable(id) if id.name == "msg")
+        )
+    }
+
+    /// Name of the first `address` parameter.
+    fn address_param(func_def: &FunctionDefinition) -> Option<String> {
+        func_def.params.iter().find_map(|(_, param)| {
+            let param = param.as_ref()?;
+            match &param.ty {
+                Expression::Type(_, Type::Address | Type::AddressPayable) => Some(
+                    param
+                        .name
+                        .as_ref()
+                        .map_or("address", |n| n.name.as_str())
+                        .to_string(),
+                ),
+                _ => None,
+            }
+        })
+    }
+
+    /// Whether `body` checks an address for code, directly or through a function it calls.
+    /// Calls to functions defined in this contract are followed; inherited helpers named like
+    /// upgrade or implementation setters (OpenZeppelin's `_upgradeToAndCall`,
+    /// `_setImplementation`) and external `upgradeTo` calls on a proxy are assumed to check.
+    fn checks_code(
+        body: &Statement,
+        file: &SolidityFile,
+        functions: &[&FunctionDefinition],
+        visited: &mut HashSet<String>,
+    ) -> bool {
+        let source = Self::source(&body.loc(), file);
+        if CODE_CHECKS.iter().any(|check| source.contains(check)) {
+            return true;
+        }
+
+        let mut callees = Vec::new();
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            let Expression::FunctionCall(loc, callee, _) = expr else {
+                return None;
+            };
+            let name = match callee.as_ref() {
+                Expression::Variable(id) | Expression::MemberAccess(_, _, id) => &id.name,
+                _ => return None,
+            };
+            callees.push(name.clone());
+            Some(*loc)
+        };
+        let mut found = Vec::new();
+        find_locations_in_statement(body, file, &mut predicate, &mut found);
+
+        callees.into_iter().any(|callee| {
+            let local: Vec<&Statement> = functions
+                .iter()
+                .filter(|f| f.name.as_ref().is_some_and(|n| n.name == callee))
+                .filter_map(|f| f.body.as_ref())
+                .collect();
+            if local.is_empty() {
+                let lower = callee.to_lowercase();
+                return lower.contains("upgrade") || lower.contains("implementation");
+            }
+            visited.insert(callee)
+                && local
+                    .into_iter()
+                    .any(|body| Self::checks_code(body, file, functions, visited))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::run_detector_with_mock_inheritance;
+
+    #[test]
+    fn test_proxy_upgrade_management() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            contract Vault is UUPSUpgradeable, OwnableUpgradeable {
+                function _authorizeUpgrade(address newImplementation) internal override onlyOwner {}
+            }
+
+            contract OpenVault is UUPSUpgradeable {
+                function _authorizeUpgrade(address) internal override {}
+            }
+
+            contract MyProxy {
+                bytes32 internal constant IMPLEMENTATION_SLOT =
+                    0x360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc;
+                address public admin;
+
+                function setImplementation(address newImplementation) external {
+                    assembly {
+                        sstore(IMPLEMENTATION_SLOT, newImplementation)
+                    }
+                }
+
+                function changeAdmin(address newAdmin) external {
+                    require(msg.sender == admin, "not admin");
+                    admin = newAdmin;
+                }
+
+                function implementation() external view returns (address impl) {
+                    assembly {
+                        impl := sload(IMPLEMENTATION_SLOT)
+                    }
+                }
+            }
+
+            contract SafeProxy is ERC1967Proxy {
+                address public pendingAdmin;
+
+                function upgradeTo(address newImplementation) external onlyAdmin {
+                    _upgradeToAndCall(newImplementation, "");
+                }
+
+                function setImplementation(address newImplementation) external onlyAdmin {
+                    _store(newImplementation);
+                }
+
+                function changeAdmin(address newAdmin) external onlyAdmin {
+                    pendingAdmin = newAdmin;
+                }
+
+                function acceptAdmin() external {
+                    require(msg.sender == pendingAdmin);
+                }
+
+                function _store(address impl) internal {
+                    require(impl.code.length > 0, "not a contract");
+                }
+            }
+
+            contract Registry {
+                function setImplementation(address newImplementation) external {}
+            }
+        "#;
+        let mock_inheritance = vec![
+            (
+                "Vault",
+                vec!["UUPSUpgradeable", "OwnableUpgradeable", "Vault"],
+            ),
+            ("OpenVault", vec!["UUPSUpgradeable", "OpenVault"]),
+            ("MyProxy", vec!["MyProxy"]),
+            ("SafeProxy", vec!["ERC1967Proxy", "SafeProxy"]),
+            ("Registry", vec!["Registry"]),
+        ];
+        let detector = Arc::new(ProxyUpgradeManagementDetector::default());
+        let locations =
+            run_detector_with_mock_inheritance(detector, code, "proxy.sol", mock_inheritance);
+
+        let found: Vec<_> = locations
+            .iter()
+            .map(|l| (l.line, l.message.as_deref().unwrap()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (
+                    9,
+                    "`_authorizeUpgrade` has no access-control modifier or `msg.sender` check"
+                ),
+                (
+                    17,
+                    "`setImplementation` has no access-control modifier or `msg.sender` check"
+                ),
+                (
+                    17,
+                    "`setImplementation` does not check that `newImplementation` has code \
+                     (`newImplementation.code.length > 0`)"
+                ),
+                (
+                    23,
+                    "`changeAdmin` replaces the admin in one step; store a pending admin that \
+                     must accept the role"
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,410 @@
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::category::Category;
+use crate::models::severity::Severity;
+use crate::models::{FindingData, SolidityFile};
+use crate::utils::ast_utils::{
+    find_locations_in_expression, find_locations_in_statement, find_statement_types,
+    get_local_variable_names, is_function_readonly,
+};
+use crate::utils::location::loc_to_location;
+use solang_parser::pt::{ContractPart, Expression, Loc, Statement};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Calls that check a signature against a digest.
+const VERIFY_CALLS: &[&str] = &[
+    "ecrecover",
+    "recover",
+    "tryRecover",
+    "isValidSignature",
+    "isValidSignatureNow",
+    "isValidSignatureNowCalldata",
+    "isValidERC1271SignatureNow",
+];
+
+/// Calls that build the signed digest.
+const HASH_CALLS: &[&str] = &[
+    "keccak256",
+    "_hashTypedDataV4",
+    "hashTypedData",
+    "toTypedDataHash",
+    "toEthSignedMessageHash",
+];
+
+/// Prefixes of helpers that consume a nonce, e.g. `_useNonce(owner)`, `_useCheckedNonce`.
+const CONSUME_PREFIXES: &[&str] = &["use", "consume", "increment", "invalidate", "spend", "mark"];
+
+#[derive(Debug, Default)]
+pub struct SignatureReplayDetector;
+
+impl Detector for SignatureReplayDetector {
+    fn id(&self) -> &'static str {
+        "signature-replay"
+    }
+
+    fn name(&self) -> &str {
+        "Signature can be replayed: nonce never consumed"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Medium
+    }
+
+    fn category(&self) -> Category {
+        Category::Cryptography
+    }
+
+    fn description(&self) -> &str {
+        "A state-changing function verifies a signature over a digest it builds itself, but \
+         nothing marks the signature as used: a nonce in the digest is never written back \
+         (`nonces[signer]++`, `usedNonces[nonce] = true`, `_useNonce(signer)`) and the digest \
+         is not recorded either. Anyone who sees the signature can submit it again. When the \
+         digest has no nonce at all the finding is less certain, since some signed actions are \
+         idempotent, but most need a nonce or a record of used digests."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad - the nonce is signed but never consumed
+function claim(uint256 amount, uint256 nonce, bytes calldata sig) external {
+    bytes32 digest = _hashTypedDataV4(keccak256(abi.encode(CLAIM_TYPEHASH, msg.sender, amount, nonce)));
+    require(ECDSA.recover(digest, sig) == signer, "bad signature");
+    token.transfer(msg.sender, amount);
+}
+
+// Good - the nonce can only be used once
+function claim(uint256 amount, uint256 nonce, bytes calldata sig) external {
+    require(!usedNonces[msg.sender][nonce], "nonce used");
+    usedNonces[msg.sender][nonce] = true;
+    bytes32 digest = _hashTypedDataV4(keccak256(abi.encode(CLAIM_TYPEHASH, msg.sender, amount, nonce)));
+    require(ECDSA.recover(digest, sig) == signer, "bad signature");
+    token.transfer(msg.sender, amount);
+}
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_contract(move |contract_def, file, context| {
+            let Some(contract_name) = &contract_def.name else {
+                return Vec::new();
+            };
+            let qualified_name = context.get_qualified_name_for_contract(&contract_name.name);
+            let state_vars: HashSet<String> = context
+                .get_all_state_variables(&qualified_name)
+                .into_iter()
+                .map(|v| v.name.clone())
+                .collect();
+
+            let mut findings = Vec::new();
+            for part in &contract_def.parts {
+                let ContractPart::FunctionDefinition(func_def) = part else {
+                    continue;
+                };
+                let Some(body) = &func_def.body else {
+                    continue;
+                };
+                if is_function_readonly(func_def) {
+                    continue;
+                }
+
+                let Some(verify_loc) = Self::first_verify_call(body, file) else {
+                    continue;
+                };
+                let hash_locals = Self::hash_locals(body, file);
+                let Some(signed_nonces) = Self::signed_nonces(body, file) else {
+                    // The digest is built elsewhere; the caller may consume the nonce
+                    continue;
+                };
+
+                let locals = get_local_variable_names(func_def, body);
+                let storage: HashSet<&str> = state_vars
+                    .iter()
+                    .map(String::as_str)
+                    .filter(|name| !locals.contains(*name))
+                    .collect();
+                if Self::consumes_signature(body, file, &storage, &hash_locals) {
+                    continue;
+                }
+
+                let message = match signed_nonces.first() {
+                    Some(nonce) => format!(
+                        "`{}` is part of the signed digest but is never marked as used, so the \
+                         signature can be replayed",
+                        nonce
+                    ),
+                    None => "the signed digest contains no nonce and is not recorded as used; \
+                             unless the signed action is idempotent, the signature can be \
+                             replayed"
+                        .to_string(),
+                };
+                findings.push(FindingData {
+                    detector_id: self.id(),
+                    location: loc_to_location(&verify_loc, file).with_message(message),
+                });
+            }
+            findings
+        });
+    }
+}
+
+impl SignatureReplayDetector {
+    fn call_name(func: &Expression) -> Option<&str> {
+        match func {
+            Expression::Variable(id) => Some(&id.name),
+            Expression::MemberAccess(_, _, member) => Some(&member.name),
+            _ => None,
+        }
+    }
+
+    fn is_call_to(expr: &Expression, names: &[&str]) -> bool {
+        matches!(
+            expr,
+            Expression::FunctionCall(_, func, _)
+                if Self::call_name(func).is_some_and(|name| names.contains(&name))
+        )
+    }
+
+    fn is_nonce_like(name: &str) -> bool {
+        name.to_lowercase().contains("nonce")
+    }
+
+    fn first_verify_call(body: &Statement, file: &SolidityFile) -> Option<Loc> {
+        let mut first = None;
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            if let Expression::FunctionCall(loc, _, _) = expr {
+                if first.is_none() && Self::is_call_to(expr, VERIFY_CALLS) {
+                    first = Some(*loc);
+                }
+            }
+            None
+        };
+        find_locations_in_statement(body, file, &mut predicate, &mut Vec::new());
+        first
+    }
+
+    /// Nonce-like names (`nonce`, `nonces`, `order.nonce`) passed to the calls building the
+    /// digest, in source order. `None` when the function builds no digest.
+    fn signed_nonces(body: &Statement, file: &SolidityFile) -> Option<Vec<String>> {
+        let mut hash_calls = 0;
+        let mut nonces: Vec<String> = Vec::new();
+        let mut predicate = |expr: &Expression, file: &SolidityFile| -> Option<Loc> {
+            let Expression::FunctionCall(loc, _, args) = expr else {
+                return None;
+            };
+            if !Self::is_call_to(expr, HASH_CALLS) {
+                return None;
+            }
+            hash_calls += 1;
+            for arg in args {
+                for name in Self::identifiers(arg, file) {
+                    if Self::is_nonce_like(&name) && !nonces.contains(&name) {
+                        nonces.push(name);
+                    }
+                }
+            }
+            Some(*loc)
+        };
+        find_locations_in_statement(body, file, &mut predicate, &mut Vec::new());
+        (hash_calls > 0).then_some(nonces)
+    }
+
+    /// Locals holding a digest, e.g. `bytes32 digest = _hashTypedDataV4(...)`.
+    fn hash_locals(body: &Statement, file: &SolidityFile) -> HashSet<String> {
+        let mut locals = HashSet::new();
+        find_statement_types(body, file, "", |stmt| {
+            let (name, value) = match stmt {
+                Statement::VariableDefinition(_, decl, Some(value)) => match &decl.name {
+                    Some(name) => (&name.name, value),
+                    None => return false,
+                },
+                Statement::Expression(_, Expression::Assign(_, target, value)) => {
+                    match target.as_ref() {
+                        Expression::Variable(name) => (&name.name, value.as_ref()),
+                        _ => return false,
+                    }
+                }
+                _ => return false,
+            };
+            if Self::contains_call(value, file, HASH_CALLS) {
+                locals.insert(name.clone());
+            }
+            false
+        });
+        locals
+    }
+
+    /// Whether the function records the signature as used: a storage write whose target names a
+    /// nonce, a digest or a signature, or a call to a nonce-consuming helper.
+    fn consumes_signature(
+        body: &Statement,
+        file: &SolidityFile,
+        storage: &HashSet<&str>,
+        hash_locals: &HashSet<String>,
+    ) -> bool {
+        let mut consumed = false;
+        let mut predicate = |expr: &Expression, file: &SolidityFile| -> Option<Loc> {
+            let target = match expr {
+                Expression::Assign(_, target, _)
+                | Expression::AssignOr(_, target, _)
+                | Expression::AssignAnd(_, target, _)
+                | Expression::AssignXor(_, target, _)
+                | Expression::AssignShiftLeft(_, target, _)
+                | Expression::AssignShiftRight(_, target, _)
+                | Expression::AssignAdd(_, target, _)
+                | Expression::AssignSubtract(_, target, _)
+                | Expression::AssignMultiply(_, target, _)
+                | Expression::AssignDivide(_, target, _)
+                | Expression::AssignModulo(_, target, _)
+                | Expression::PreIncrement(_, target)
+                | Expression::PostIncrement(_, target)
+                | Expression::PreDecrement(_, target)
+                | Expression::PostDecrement(_, target)
+                | Expression::Delete(_, target) => target,
+                Expression::FunctionCall(_, func, _) => {
+                    if Self::call_name(func).is_some_and(Self::is_consume_helper) {
+                        consumed = true;
+                    }
+                    return None;
+                }
+                _ => return None,
+            };
+            if !Self::root_name(target).is_some_and(|root| storage.contains(root)) {
+                return None;
+            }
+            let names_signature = Self::identifiers(target, file).iter().any(|name| {
+                let lower = name.to_lowercase();
+                lower.contains("nonce") || lower.contains("sig") || hash_locals.contains(name)
+            });
+            if names_signature || Self::contains_call(target, file, HASH_CALLS) {
+                consumed = true;
+            }
+            None
+        };
+        find_locations_in_statement(body, file, &mut predicate, &mut Vec::new());
+        consumed
+    }
+
+    fn is_consume_helper(name: &str) -> bool {
+        let lower = name.trim_start_matches('_').to_lowercase();
+        lower.contains("nonce")
+            && CONSUME_PREFIXES
+                .iter()
+                .any(|prefix| lower.starts_with(prefix))
+    }
+
+    /// Variable and member names in `expr`.
+    fn identifiers(expr: &Expression, file: &SolidityFile) -> Vec<String> {
+        let mut names = Vec::new();
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            match expr {
+                Expression::Variable(id) => names.push(id.name.clone()),
+                Expression::MemberAccess(_, _, member) => names.push(member.name.clone()),
+                _ => {}
+            }
+            None
+        };
+        find_locations_in_expression(expr, file, &mut predicate, &mut Vec::new());
+        names
+    }
+
+    fn contains_call(expr: &Expression, file: &SolidityFile, names: &[&str]) -> bool {
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            match expr {
+                Expression::FunctionCall(loc, _, _) if Self::is_call_to(expr, names) => Some(*loc),
+                _ => None,
+            }
+        };
+        let mut found = Vec::new();
+        find_locations_in_expression(expr, file, &mut predicate, &mut found);
+        !found.is_empty()
+    }
+
+    /// Variable an assignment target is rooted in, e.g. `usedNonces[signer][nonce]` -> `usedNonces`.
+    fn root_name(expr: &Expression) -> Option<&str> {
+        match expr {
+            Expression::Variable(id) => Some(&id.name),
+            Expression::Parenthesis(_, inner)
+            | Expression::ArraySubscript(_, inner, _)
+            | Expression::MemberAccess(_, inner, _) => Self::root_name(inner),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::run_detector_on_code;
+
+    #[test]
+    fn test_signature_replay() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            library ECDSA {
+                function recover(bytes32 hash, bytes memory sig) internal pure returns (address) {}
+            }
+
+            contract Claims {
+                bytes32 constant CLAIM_TYPEHASH = keccak256("Claim(address to,uint256 amount,uint256 nonce)");
+                address signer;
+                mapping(address => uint256) public nonces;
+                mapping(address => mapping(uint256 => bool)) usedNonces;
+                mapping(address => uint256) claimed;
+
+                function claimConsumed(uint256 amount, uint256 nonce, bytes calldata sig) external {
+                    require(!usedNonces[msg.sender][nonce], "used");
+                    usedNonces[msg.sender][nonce] = true;
+                    bytes32 digest = keccak256(abi.encode(CLAIM_TYPEHASH, msg.sender, amount, nonce));
+                    require(ECDSA.recover(digest, sig) == signer);        // Negative: nonce consumed
+                    claimed[msg.sender] += amount;
+                }
+
+                function claimIncremented(uint256 amount, bytes calldata sig) external {
+                    bytes32 digest = keccak256(abi.encode(CLAIM_TYPEHASH, msg.sender, amount, nonces[msg.sender]++));
+                    require(ECDSA.recover(digest, sig) == signer);        // Negative: nonce incremented
+                    claimed[msg.sender] += amount;
+                }
+
+                function claimReplayable(uint256 amount, uint256 nonce, bytes calldata sig) external {
+                    bytes32 digest = keccak256(abi.encode(CLAIM_TYPEHASH, msg.sender, amount, nonce));
+                    require(ECDSA.recover(digest, sig) == signer);        // Positive: nonce not consumed
+                    claimed[msg.sender] += amount;
+                }
+
+                function claimWithoutNonce(uint256 amount, uint8 v, bytes32 r, bytes32 s) external {
+                    bytes32 digest = keccak256(abi.encodePacked(msg.sender, amount));
+                    require(ecrecover(digest, v, r, s) == signer);        // Positive: no nonce
+                    claimed[msg.sender] += amount;
+                }
+
+                function verify(bytes32 digest, bytes calldata sig) external view returns (bool) {
+                    return ECDSA.recover(digest, sig) == signer;          // Negative: view helper
+                }
+            }
+        "#;
+        let detector = Arc::new(SignatureReplayDetector::default());
+        let locations = run_detector_on_code(detector, code, "claims.sol");
+
+        let lines: Vec<_> = locations.iter().map(|l| l.line).collect();
+        assert_eq!(lines, [31, 37]);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some(
+                "`nonce` is part of the signed digest but is never marked as used, so the \
+                 signature can be replayed"
+            )
+        );
+        assert_eq!(
+            locations[1].message.as_deref(),
+            Some(
+                "the signed digest contains no nonce and is not recorded as used; unless the \
+                 signed action is idempotent, the signature can be replayed"
+            )
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::ast_utils;
 use crate::{core::visitor::ASTVisitor, models::FindingData};
@@ -21,6 +22,10 @@ impl Detector for SolmateSafeTransferDetector {
         Severity::Medium
     }
 
+    fn category(&self) -> Category {
+        Category::Token
+    }
+
     fn description(&self) -> &str {
         "There is a subtle difference between the implementation of solmate's SafeTransferLib and OZ's SafeERC20: \
         OZ's SafeERC20 checks if the token is a contract or not, solmate's SafeTransferLib does not. \
@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::location::loc_to_location;
 use crate::{core::visitor::ASTVisitor, models::FindingData};
@@ -21,6 +22,10 @@ impl Detector for TxOriginUsageDetector {
         Severity::Medium
     }
 
+    fn category(&self) -> Category {
+        Category::AccessControl
+    }
+
     fn description(&self) -> &str {
         "Using `tx.origin` for authorization is vulnerable to phishing attacks and may break with future Ethereum upgrades. \
         According to Vitalik Buterin, contracts should not assume that `tx.origin` will continue to be usable or meaningful. \
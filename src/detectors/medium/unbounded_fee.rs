@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::{ast_utils, location::loc_to_location};
 use crate::{core::visitor::ASTVisitor, models::FindingData};
@@ -21,6 +22,10 @@ impl Detector for UnboundedFeeDetector {
         Severity::Medium
     }
 
+    fn category(&self) -> Category {
+        Category::Validation
+    }
+
     fn description(&self) -> &str {
         "There should be an upper limit to reasonable fees. A malicious owner can keep the fee rate at zero, \
         but if a large value transfer enters the mempool, the owner can jack the rate up to the maximum \
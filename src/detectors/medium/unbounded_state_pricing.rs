@@ -0,0 +1,314 @@
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::category::Category;
+use crate::models::severity::Severity;
+use crate::models::{FindingData, Location, SolidityFile};
+use crate::utils::ast_utils::{
+    find_locations_in_expression, find_locations_in_statement, find_statement_types,
+};
+use solang_parser::pt::{
+    ContractPart, Expression, FunctionAttribute, FunctionDefinition, FunctionTy, Loc, Mutability,
+    Statement, Visibility,
+};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Parameter name fragments that suggest a caller-supplied price or amount bound.
+const BOUND_PARAM_PATTERNS: [&str; 5] = ["min", "max", "limit", "slippage", "expected"];
+
+#[derive(Debug, Default)]
+pub struct UnboundedStatePricingDetector;
+
+impl Detector for UnboundedStatePricingDetector {
+    fn id(&self) -> &'static str {
+        "unbounded-state-pricing"
+    }
+
+    fn name(&self) -> &str {
+        "Price derived from mutable state without a caller-supplied bound"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Medium
+    }
+
+    fn category(&self) -> Category {
+        Category::Oracle
+    }
+
+    fn description(&self) -> &str {
+        "An externally callable function computes the amount the caller receives or pays from a \
+         ratio of state variables (reserves, total supply, total assets, ...) that any other \
+         transaction can change in the same block, and takes no caller-supplied bound such as \
+         `minOut` or `maxIn`. A front-runner can move the ratio right before the call and back \
+         after it, so the caller trades at whatever price the attacker chose. Accept a minimum \
+         output or maximum input parameter and revert when the computed amount violates it. \
+         Swaps routed through external DEX routers are covered by `lack-of-slippage-check`; this \
+         detector only looks at the contract's own pricing math."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad - the price is whatever the reserves are when the transaction lands
+function buy() external payable {
+    uint256 amountOut = msg.value * reserveToken / reserveEth;
+    token.transfer(msg.sender, amountOut);
+}
+
+// Good - the caller bounds the price
+function buy(uint256 minOut) external payable {
+    uint256 amountOut = msg.value * reserveToken / reserveEth;
+    require(amountOut >= minOut, "slippage");
+    token.transfer(msg.sender, amountOut);
+}
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_contract(move |contract_def, file, context| {
+            let Some(contract_name) = &contract_def.name else {
+                return Vec::new();
+            };
+
+            let qualified_name = context.get_qualified_name_for_contract(&contract_name.name);
+            let mutable_state: HashSet<String> = context
+                .get_all_state_variables(&qualified_name)
+                .into_iter()
+                .filter(|v| !v.is_constant && !v.is_immutable)
+                .map(|v| v.name.clone())
+                .collect();
+            if mutable_state.is_empty() {
+                return Vec::new();
+            }
+
+            let mut findings = Vec::new();
+            for part in &contract_def.parts {
+                let ContractPart::FunctionDefinition(func_def) = part else {
+                    continue;
+                };
+                let Some(body) = &func_def.body else {
+                    continue;
+                };
+                if !Self::is_user_entry_point(func_def)
+                    || Self::has_bound_param(func_def, body, file)
+                {
+                    continue;
+                }
+
+                // Locals copied from state (`uint256 r0 = reserve0;`) carry the same risk
+                let mut state_derived = mutable_state.clone();
+                find_statement_types(body, file, self.id(), |stmt| {
+                    if let Statement::VariableDefinition(_, decl, Some(init)) = stmt {
+                        if let Some(name) = &decl.name {
+                            if Self::references_any(init, &state_derived, file) {
+                                state_derived.insert(name.name.clone());
+                            }
+                        }
+                    }
+                    false
+                });
+
+                if let Some(ratio) = Self::state_ratio(body, &state_derived, file) {
+                    let function_name = func_def.name.as_ref().map_or("", |n| n.name.as_str());
+                    findings.push(FindingData {
+                        detector_id: self.id(),
+                        location: ratio.with_message(format!(
+                            "`{}` prices from state other transactions can move but takes no \
+                             caller-supplied bound such as `minOut` or `maxIn`",
+                            function_name
+                        )),
+                    });
+                }
+            }
+            findings
+        });
+    }
+}
+
+impl UnboundedStatePricingDetector {
+    /// External or public, state-changing, and not restricted to an admin by an `only*` modifier.
+    fn is_user_entry_point(func_def: &FunctionDefinition) -> bool {
+        if !matches!(func_def.ty, FunctionTy::Function) {
+            return false;
+        }
+
+        let mut is_external = false;
+        for attr in &func_def.attributes {
+            match attr {
+                FunctionAttribute::Visibility(Visibility::External(_) | Visibility::Public(_)) => {
+                    is_external = true
+                }
+                FunctionAttribute::Mutability(Mutability::View(_) | Mutability::Pure(_)) => {
+                    return false
+                }
+                FunctionAttribute::BaseOrModifier(_, base)
+                    if base
+                        .name
+                        .identifiers
+                        .last()
+                        .is_some_and(|id| id.name.starts_with("only")) =>
+                {
+                    return false
+                }
+                _ => {}
+            }
+        }
+        is_external
+    }
+
+    /// A parameter named like a bound (`minOut`, `maxPrice`, `limit`) that is compared against
+    /// something in the body.
+    fn has_bound_param(
+        func_def: &FunctionDefinition,
+        body: &Statement,
+        file: &SolidityFile,
+    ) -> bool {
+        let bounds: HashSet<String> = func_def
+            .params
+            .iter()
+            .filter_map(|(_, param)| param.as_ref()?.name.as_ref())
+            .filter(|name| {
+                let lower = name.name.to_lowercase();
+                BOUND_PARAM_PATTERNS.iter().any(|p| lower.contains(p))
+            })
+            .map(|name| name.name.clone())
+            .collect();
+        if bounds.is_empty() {
+            return false;
+        }
+
+        let mut predicate = |expr: &Expression, file: &SolidityFile| -> Option<Loc> {
+            match expr {
+                Expression::Less(loc, left, right)
+                | Expression::More(loc, left, right)
+                | Expression::LessEqual(loc, left, right)
+                | Expression::MoreEqual(loc, left, right)
+                    if Self::references_any(left, &bounds, file)
+                        || Self::references_any(right, &bounds, file) =>
+                {
+                    Some(*loc)
+                }
+                _ => None,
+            }
+        };
+        let mut found = Vec::new();
+        find_locations_in_statement(body, file, &mut predicate, &mut found);
+        !found.is_empty()
+    }
+
+    /// First division whose numerator and denominator both read mutable state,
+    /// e.g. `amountIn * reserve1 / reserve0`.
+    fn state_ratio(
+        body: &Statement,
+        state: &HashSet<String>,
+        file: &SolidityFile,
+    ) -> Option<Location> {
+        let mut predicate = |expr: &Expression, file: &SolidityFile| -> Option<Loc> {
+            match expr {
+                Expression::Divide(loc, numerator, denominator)
+                    if Self::references_any(numerator, state, file)
+                        && Self::references_any(denominator, state, file) =>
+                {
+                    Some(*loc)
+                }
+                _ => None,
+            }
+        };
+        let mut found = Vec::new();
+        find_locations_in_statement(body, file, &mut predicate, &mut found);
+        found.into_iter().min_by_key(|l| (l.line, l.column))
+    }
+
+    fn references_any(expr: &Expression, names: &HashSet<String>, file: &SolidityFile) -> bool {
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            match expr {
+                Expression::Variable(id) if names.contains(&id.name) => Some(id.loc),
+                _ => None,
+            }
+        };
+        let mut found = Vec::new();
+        find_locations_in_expression(expr, file, &mut predicate, &mut found);
+        !found.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detectors::low::LackOfSlippageCheckDetector;
+    use crate::utils::test_utils::run_detector_on_code;
+
+    /// Own pricing math and a router swap side by side; each detector must only see its half.
+    const AMM: &str = r#"
+        pragma solidity ^0.8.0;
+
+        contract Amm {
+            uint256 reserveEth;
+            uint256 reserveToken;
+            IERC20 token;
+            IRouter router;
+
+            function buy() external payable {
+                uint256 amountOut = msg.value * reserveToken / reserveEth;     // Positive
+                reserveEth += msg.value;
+                reserveToken -= amountOut;
+                token.transfer(msg.sender, amountOut);
+            }
+
+            function sell(uint256 amountIn) external {
+                uint256 rEth = reserveEth;
+                uint256 out = amountIn * rEth / (reserveToken + amountIn);     // Positive: via local
+                payable(msg.sender).transfer(out);
+            }
+
+            function buyBounded(uint256 minOut) external payable {
+                uint256 amountOut = msg.value * reserveToken / reserveEth;     // Negative: bounded
+                if (amountOut < minOut) revert();
+                token.transfer(msg.sender, amountOut);
+            }
+
+            function quote(uint256 amountIn) external view returns (uint256) {
+                return amountIn * reserveToken / reserveEth;                   // Negative: view
+            }
+
+            function swapOut(uint256 amountIn, address[] calldata path) external {
+                router.swapExactTokensForTokens({
+                    amountIn: amountIn,
+                    amountOutMin: 0,
+                    path: path,
+                    to: msg.sender,
+                    deadline: block.timestamp
+                });
+            }
+        }
+    "#;
+
+    #[test]
+    fn test_unbounded_state_pricing() {
+        let detector = Arc::new(UnboundedStatePricingDetector::default());
+        let locations = run_detector_on_code(detector, AMM, "amm.sol");
+
+        assert_eq!(locations.len(), 2);
+        assert_eq!(locations[0].line, 11);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some(
+                "`buy` prices from state other transactions can move but takes no \
+                 caller-supplied bound such as `minOut` or `maxIn`"
+            )
+        );
+        assert_eq!(locations[1].line, 19);
+    }
+
+    #[test]
+    fn test_router_swaps_left_to_slippage_detector() {
+        let detector = Arc::new(LackOfSlippageCheckDetector::default());
+        let locations = run_detector_on_code(detector, AMM, "amm.sol");
+
+        assert_eq!(locations.len(), 1);
+        assert_eq!(locations[0].line, 36, "only the router swap in `swapOut`");
+    }
+}
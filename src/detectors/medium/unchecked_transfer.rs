@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::ast_utils::is_likely_erc20_token;
 use crate::utils::location::loc_to_location;
@@ -22,6 +23,10 @@ impl Detector for UncheckedTransferDetector {
         Severity::Medium
     }
 
+    fn category(&self) -> Category {
+        Category::Token
+    }
+
     fn description(&self) -> &str {
         "Not all ERC20 implementations revert() when there's a failure in transfer()/transferFrom(). \
         The function signature has a boolean return value and they indicate errors that way instead. \
@@ -0,0 +1,476 @@
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::category::Category;
+use crate::models::finding::Location;
+use crate::models::severity::Severity;
+use crate::models::{FindingData, SolidityFile};
+use crate::utils::ast_utils::{
+    contains_address_this, find_locations_in_expression, find_locations_in_statement,
+    find_statement_types, has_reentrancy_guard, is_external_call, is_function_readonly,
+};
+use crate::utils::location::loc_to_location;
+use solang_parser::pt::{CodeLocation, Expression, Loc, Statement};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+#[derive(Debug, Default)]
+pub struct UnguardedBalanceDeltaDetector;
+
+impl Detector for UnguardedBalanceDeltaDetector {
+    fn id(&self) -> &'static str {
+        "unguarded-balance-delta"
+    }
+
+    fn name(&self) -> &str {
+        "Balance delta measured around an external call is credited without a reentrancy guard"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Medium
+    }
+
+    fn category(&self) -> Category {
+        Category::ExternalCall
+    }
+
+    fn description(&self) -> &str {
+        "The function reads `token.balanceOf(address(this))` before and after an external call \
+         and credits the difference to `msg.sender` or a parameter-supplied recipient, but has no \
+         `nonReentrant` guard. If the call hands control back (ERC777/ERC1363 hooks, a malicious \
+         token, or a callback from the callee), the caller can re-enter the function while the \
+         first measurement is still open: the inner deposit is counted again by the outer \
+         delta, and every token sharing the hook can inflate the credit. Add a reentrancy guard \
+         to functions that do delta accounting."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad - a re-entrant deposit is counted by both the inner and the outer delta
+function deposit(uint256 amount) external {
+    uint256 before = asset.balanceOf(address(this));
+    asset.transferFrom(msg.sender, address(this), amount);
+    shares[msg.sender] += asset.balanceOf(address(this)) - before;
+}
+
+// Good
+function deposit(uint256 amount) external nonReentrant {
+    uint256 before = asset.balanceOf(address(this));
+    asset.transferFrom(msg.sender, address(this), amount);
+    shares[msg.sender] += asset.balanceOf(address(this)) - before;
+}
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_function(move |func_def, file, _context| {
+            let Some(body) = &func_def.body else {
+                return Vec::new();
+            };
+            if is_function_readonly(func_def) || has_reentrancy_guard(func_def) {
+                return Vec::new();
+            }
+
+            let calls = Self::external_call_positions(body, file);
+            if calls.is_empty() {
+                return Vec::new();
+            }
+            let snapshots = Self::balance_snapshots(body, file);
+            let deltas = Self::find_deltas(body, file, &snapshots, &calls);
+            if deltas.is_empty() {
+                return Vec::new();
+            }
+            let delta_locals = Self::delta_locals(body, file, &deltas);
+            let params: HashSet<String> = func_def
+                .params
+                .iter()
+                .filter_map(|(_, param)| param.as_ref()?.name.as_ref())
+                .map(|name| name.name.clone())
+                .collect();
+
+            Self::find_credits(body, file, &deltas, &delta_locals, &params)
+                .into_iter()
+                .map(|location| FindingData {
+                    detector_id: self.id(),
+                    location,
+                })
+                .collect()
+        });
+    }
+}
+
+/// A `balanceOf(address(this))` read: the token it reads and where.
+#[derive(Debug, Clone)]
+struct BalanceRead {
+    token: String,
+    position: (usize, usize),
+}
+
+/// A `after - before` balance subtraction spanning an external call.
+#[derive(Debug)]
+struct Delta {
+    loc: Loc,
+    token: String,
+}
+
+impl UnguardedBalanceDeltaDetector {
+    fn position(location: &Location) -> (usize, usize) {
+        (location.line, location.column.unwrap_or(0))
+    }
+
+    /// External calls other than the balance reads themselves.
+    fn external_call_positions(body: &Statement, file: &SolidityFile) -> Vec<(usize, usize)> {
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            match expr {
+                Expression::FunctionCall(loc, func, _) if is_external_call(expr) => {
+                    match func.as_ref() {
+                        Expression::MemberAccess(_, _, member) if member.name == "balanceOf" => {
+                            None
+                        }
+                        _ => Some(*loc),
+                    }
+                }
+                _ => None,
+            }
+        };
+        let mut calls = Vec::new();
+        find_locations_in_statement(body, file, &mut predicate, &mut calls);
+        calls.iter().map(Self::position).collect()
+    }
+
+    /// The token of a `token.balanceOf(address(this))` call.
+    fn balance_read_token(expr: &Expression) -> Option<String> {
+        let Expression::FunctionCall(_, func, args) = expr else {
+            return None;
+        };
+        let Expression::MemberAccess(_, token, member) = func.as_ref() else {
+            return None;
+        };
+        if member.name != "balanceOf" || args.len() != 1 || !contains_address_this(&args[0]) {
+            return None;
+        }
+        Some(token.to_string())
+    }
+
+    /// Locals holding a balance read, e.g. `uint256 before = token.balanceOf(address(this))`.
+    fn balance_snapshots(body: &Statement, file: &SolidityFile) -> HashMap<String, BalanceRead> {
+        let mut snapshots = HashMap::new();
+        find_statement_types(body, file, "", |stmt| {
+            let (name, value) = match stmt {
+                Statement::VariableDefinition(_, decl, Some(value)) => match &decl.name {
+                    Some(name) => (&name.name, value),
+                    None => return false,
+                },
+                Statement::Expression(_, Expression::Assign(_, target, value)) => {
+                    match target.as_ref() {
+                        Expression::Variable(name) => (&name.name, value.as_ref()),
+                        _ => return false,
+                    }
+                }
+                _ => return false,
+            };
+            if let Some(token) = Self::balance_read_token(value) {
+                let position = Self::position(&loc_to_location(&value.loc(), file));
+                snapshots.insert(name.clone(), BalanceRead { token, position });
+            }
+            false
+        });
+        snapshots
+    }
+
+    /// A balance read written inline or through a snapshot local.
+    fn balance_operand(
+        expr: &Expression,
+        file: &SolidityFile,
+        snapshots: &HashMap<String, BalanceRead>,
+    ) -> Option<BalanceRead> {
+        match expr {
+            Expression::Parenthesis(_, inner) => Self::balance_operand(inner, file, snapshots),
+            Expression::Variable(id) => snapshots.get(&id.name).cloned(),
+            _ => Some(BalanceRead {
+                token: Self::balance_read_token(expr)?,
+                position: Self::position(&loc_to_location(&expr.loc(), file)),
+            }),
+        }
+    }
+
+    fn find_deltas(
+        body: &Statement,
+        file: &SolidityFile,
+        snapshots: &HashMap<String, BalanceRead>,
+        calls: &[(usize, usize)],
+    ) -> Vec<Delta> {
+        let mut deltas = Vec::new();
+        let mut predicate = |expr: &Expression, file: &SolidityFile| -> Option<Loc> {
+            let Expression::Subtract(loc, after, before) = expr else {
+                return None;
+            };
+            let after = Self::balance_operand(after, file, snapshots)?;
+            let before = Self::balance_operand(before, file, snapshots)?;
+            let spans_call = calls
+                .iter()
+                .any(|call| before.position < *call && *call < after.position);
+            if after.token != before.token || !spans_call {
+                return None;
+            }
+            deltas.push(Delta {
+                loc: *loc,
+                token: after.token,
+            });
+            Some(*loc)
+        };
+        let mut found = Vec::new();
+        find_locations_in_statement(body, file, &mut predicate, &mut found);
+        deltas
+    }
+
+    /// Locals assigned from an expression containing a delta, mapped to its token.
+    fn delta_locals(
+        body: &Statement,
+        file: &SolidityFile,
+        deltas: &[Delta],
+    ) -> HashMap<String, String> {
+        let mut locals = HashMap::new();
+        find_statement_types(body, file, "", |stmt| {
+            let (name, value) = match stmt {
+                Statement::VariableDefinition(_, decl, Some(value)) => match &decl.name {
+                    Some(name) => (&name.name, value),
+                    None => return false,
+                },
+                Statement::Expression(_, Expression::Assign(_, target, value)) => {
+                    match target.as_ref() {
+                        Expression::Variable(name) => (&name.name, value.as_ref()),
+                        _ => return false,
+                    }
+                }
+                _ => return false,
+            };
+            if let Some(token) = Self::delta_in(value, file, deltas, &HashMap::new()) {
+                locals.insert(name.clone(), token);
+            }
+            false
+        });
+        locals
+    }
+
+    /// The token of the first delta `expr` contains or reads through a delta local.
+    fn delta_in(
+        expr: &Expression,
+        file: &SolidityFile,
+        deltas: &[Delta],
+        delta_locals: &HashMap<String, String>,
+    ) -> Option<String> {
+        let mut token = None;
+        let mut predicate = |e: &Expression, _: &SolidityFile| -> Option<Loc> {
+            let found = match e {
+                Expression::Variable(id) => delta_locals.get(&id.name).cloned(),
+                Expression::Subtract(loc, _, _) => deltas
+                    .iter()
+                    .find(|delta| delta.loc == *loc)
+                    .map(|delta| delta.token.clone()),
+                _ => None,
+            }?;
+            token.get_or_insert(found);
+            Some(e.loc())
+        };
+        let mut found = Vec::new();
+        find_locations_in_expression(expr, file, &mut predicate, &mut found);
+        token
+    }
+
+    /// `msg.sender` or a parameter.
+    fn user_controlled(expr: &Expression, params: &HashSet<String>) -> Option<String> {
+        match expr {
+            Expression::Parenthesis(_, inner) => Self::user_controlled(inner, params),
+            Expression::Variable(id) if params.contains(&id.name) => Some(id.name.clone()),
+            Expression::MemberAccess(_, base, member)
+                if member.name == "sender"
+                    && matches!(base.as_ref(), Expression::Variable(v) if v.name == "msg") =>
+            {
+                Some("msg.sender".to_string())
+            }
+            _ => None,
+        }
+    }
+
+    /// The user-controlled key a mapping write is indexed by, at any depth.
+    fn credited_key(target: &Expression, params: &HashSet<String>) -> Option<String> {
+        match target {
+            Expression::ArraySubscript(_, base, Some(index)) => {
+                Self::user_controlled(index, params).or_else(|| Self::credited_key(base, params))
+            }
+            Expression::MemberAccess(_, base, _) => Self::credited_key(base, params),
+            _ => None,
+        }
+    }
+
+    /// Mapping writes keyed by a user-controlled recipient and calls passing one, whose value
+    /// comes from a delta.
+    fn find_credits(
+        body: &Statement,
+        file: &SolidityFile,
+        deltas: &[Delta],
+        delta_locals: &HashMap<String, String>,
+        params: &HashSet<String>,
+    ) -> Vec<Location> {
+        let mut messages = Vec::new();
+        let mut predicate = |expr: &Expression, file: &SolidityFile| -> Option<Loc> {
+            let (loc, recipient, token) = match expr {
+                Expression::Assign(loc, target, value)
+                | Expression::AssignAdd(loc, target, value) => (
+                    loc,
+                    Self::credited_key(target, params)?,
+                    Self::delta_in(value, file, deltas, delta_locals)?,
+                ),
+                Expression::FunctionCall(loc, _, args) => (
+                    loc,
+                    args.iter()
+                        .find_map(|arg| Self::user_controlled(arg, params))?,
+                    args.iter()
+                        .find_map(|arg| Self::delta_in(arg, file, deltas, delta_locals))?,
+                ),
+                _ => return None,
+            };
+            messages.push(format!(
+                "`{}` is credited from the `{}` balance delta measured around an external call, \
+                 and the function has no reentrancy guard",
+                recipient, token
+            ));
+            Some(*loc)
+        };
+        let mut locations = Vec::new();
+        find_locations_in_statement(body, file, &mut predicate, &mut locations);
+        locations
+            .into_iter()
+            .zip(messages)
+            .map(|(location, message)| location.with_message(message))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detectors::medium::FeeOnTransferDetector;
+    use crate::utils::test_utils::run_detector_on_code;
+
+    #[test]
+    fn test_unguarded_balance_delta() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            interface IAsset {
+                function balanceOf(address account) external view returns (uint256);
+                function transferFrom(address from, address to, uint256 amount) external returns (bool);
+            }
+
+            interface IRouter {
+                function swap(address to, uint256 amountIn) external;
+            }
+
+            contract Pool {
+                IAsset asset;
+                IAsset quote;
+                IRouter router;
+                mapping(address => uint256) shares;
+
+                modifier nonReentrant() {
+                    _;
+                }
+
+                function deposit(uint256 amount) external {
+                    uint256 before = asset.balanceOf(address(this));
+                    asset.transferFrom(msg.sender, address(this), amount);
+                    uint256 received = asset.balanceOf(address(this)) - before;
+                    shares[msg.sender] += received;                       // Positive
+                }
+
+                function depositFor(address account, uint256 amount) external {
+                    uint256 before = quote.balanceOf(address(this));
+                    router.swap(address(this), amount);
+                    _mint(account, quote.balanceOf(address(this)) - before); // Positive
+                }
+
+                function depositGuarded(uint256 amount) external nonReentrant {
+                    uint256 before = asset.balanceOf(address(this));
+                    asset.transferFrom(msg.sender, address(this), amount);
+                    shares[msg.sender] += asset.balanceOf(address(this)) - before; // Negative: guarded
+                }
+
+                function depositOnce(uint256 amount) external {
+                    asset.transferFrom(msg.sender, address(this), amount);
+                    shares[msg.sender] += asset.balanceOf(address(this));  // Negative: single read
+                }
+
+                function depositUnmeasured(uint256 amount) external {
+                    uint256 before = asset.balanceOf(address(this));
+                    uint256 current = asset.balanceOf(address(this));
+                    asset.transferFrom(msg.sender, address(this), amount);
+                    shares[msg.sender] += current - before;               // Negative: no call between reads
+                }
+
+                function _mint(address to, uint256 amount) internal {
+                    shares[to] += amount;
+                }
+            }
+        "#;
+        let detector = Arc::new(UnguardedBalanceDeltaDetector::default());
+        let locations = run_detector_on_code(detector, code, "pool.sol");
+
+        let lines: Vec<_> = locations.iter().map(|l| l.line).collect();
+        assert_eq!(lines, [27, 33]);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some(
+                "`msg.sender` is credited from the `asset` balance delta measured around an \
+                 external call, and the function has no reentrancy guard"
+            )
+        );
+        assert_eq!(
+            locations[1].message.as_deref(),
+            Some(
+                "`account` is credited from the `quote` balance delta measured around an \
+                 external call, and the function has no reentrancy guard"
+            )
+        );
+    }
+
+    #[test]
+    fn test_disjoint_from_fee_on_transfer() {
+        let code = r#"
+            interface IERC20 {
+                function balanceOf(address account) external view returns (uint256);
+                function transferFrom(address from, address to, uint256 amount) external returns (bool);
+            }
+
+            contract Vault {
+                IERC20 token;
+                mapping(address => uint256) deposits;
+
+                function deposit(uint256 amount) external {
+                    token.transferFrom(msg.sender, address(this), amount);
+                    deposits[msg.sender] += amount;
+                }
+
+                function depositMeasured(uint256 amount) external {
+                    uint256 balanceBefore = token.balanceOf(address(this));
+                    token.transferFrom(msg.sender, address(this), amount);
+                    deposits[msg.sender] += token.balanceOf(address(this)) - balanceBefore;
+                }
+            }
+        "#;
+        let fee = run_detector_on_code(Arc::new(FeeOnTransferDetector), code, "vault.sol");
+        let fee_lines: Vec<_> = fee.iter().map(|l| l.line).collect();
+        assert_eq!(fee_lines, [12, 18]);
+
+        let locations = run_detector_on_code(
+            Arc::new(UnguardedBalanceDeltaDetector::default()),
+            code,
+            "vault.sol",
+        );
+        let lines: Vec<_> = locations.iter().map(|l| l.line).collect();
+        assert_eq!(lines, [19]);
+    }
+}
@@ -0,0 +1,452 @@
+use crate::core::context::AnalysisContext;
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::category::Category;
+use crate::models::severity::Severity;
+use crate::models::{FindingData, SolidityFile};
+use crate::utils::ast_utils::{
+    find_locations_in_statement, find_statement_types, find_variable_uses, get_local_variable_names,
+};
+use crate::utils::location::loc_to_location;
+use solang_parser::pt::{
+    CodeLocation, ContractDefinition, ContractPart, ContractTy, Expression, FunctionDefinition,
+    Loc, SourceUnitPart, Statement, StorageLocation, VariableAttribute,
+};
+use std::collections::{BTreeSet, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+
+#[derive(Debug, Default)]
+pub struct UninitializedStateVariableDetector;
+
+impl Detector for UninitializedStateVariableDetector {
+    fn id(&self) -> &'static str {
+        "uninitialized-state-variable"
+    }
+
+    fn name(&self) -> &str {
+        "State variable is read but never assigned"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Medium
+    }
+
+    fn category(&self) -> Category {
+        Category::Validation
+    }
+
+    fn description(&self) -> &str {
+        "The state variable has no initializer and is never assigned by the contract, its bases \
+         or the contracts deriving from it in scope, yet a function reads it. Every read sees \
+         the zero value: an owner of `address(0)`, a fee of zero, an empty oracle address. This \
+         often means an initializer of an upgradeable contract forgot to set it. Assign it in \
+         the constructor or initializer, or remove it. Writes through storage pointers, storage \
+         parameters, library calls on structs, mappings and arrays, and assembly mentioning the \
+         variable count as assignments. Abstract contracts and contracts with bases that are \
+         not in scope are skipped."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad - `treasury` is never set, fees are sent to address(0)
+address treasury;
+
+function initialize(address _owner) external initializer {
+    owner = _owner;
+}
+
+// Good
+function initialize(address _owner, address _treasury) external initializer {
+    owner = _owner;
+    treasury = _treasury;
+}
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_contract(move |contract_def, file, context| {
+            let Some(contract_name) = contract_def.name.as_ref() else {
+                return Vec::new();
+            };
+            if !matches!(contract_def.ty, ContractTy::Contract(_)) {
+                return Vec::new();
+            }
+
+            let candidates: Vec<_> = contract_def
+                .parts
+                .iter()
+                .filter_map(|part| match part {
+                    ContractPart::VariableDefinition(var) => Some(var),
+                    _ => None,
+                })
+                .filter(|var| {
+                    var.initializer.is_none()
+                        && !var.attrs.iter().any(|attr| {
+                            matches!(
+                                attr,
+                                VariableAttribute::Constant(_) | VariableAttribute::Immutable(_)
+                            )
+                        })
+                })
+                .collect();
+            if candidates.is_empty() {
+                return Vec::new();
+            }
+
+            let qualified_name = context.get_qualified_name_for_contract(&contract_name.name);
+            let Some(family) = Self::family(contract_def, file, &qualified_name, context) else {
+                return Vec::new();
+            };
+            // Functions taking a storage parameter may write to the state variable passed in
+            let storage_param_functions: HashSet<&str> = family
+                .iter()
+                .filter_map(|(def, _)| def.name.as_ref())
+                .flat_map(|name| {
+                    context.get_all_functions(&context.get_qualified_name_for_contract(&name.name))
+                })
+                .filter(|f| {
+                    f.parameters
+                        .iter()
+                        .any(|p| p.storage_location.as_deref() == Some("storage"))
+                })
+                .map(|f| f.name.as_str())
+                .collect();
+
+            let mut findings = Vec::new();
+            for var in candidates {
+                let Some(name) = &var.name else {
+                    continue;
+                };
+                let structured = Self::is_structured_type(&var.ty, &qualified_name, context);
+                let mut reader = None;
+                let mut assigned = false;
+                for (def, def_file) in &family {
+                    for func_def in Self::functions(def) {
+                        let Some(body) = &func_def.body else {
+                            continue;
+                        };
+                        if get_local_variable_names(func_def, body).contains(&name.name) {
+                            continue;
+                        }
+                        if Self::assigns(
+                            body,
+                            def_file,
+                            &name.name,
+                            structured,
+                            &storage_param_functions,
+                        ) {
+                            assigned = true;
+                            break;
+                        }
+                        if reader.is_none()
+                            && !find_variable_uses(&name.name, body, def_file).is_empty()
+                        {
+                            reader = Some(Self::function_name(func_def));
+                        }
+                    }
+                    if assigned {
+                        break;
+                    }
+                }
+
+                if let (false, Some(reader)) = (assigned, reader) {
+                    findings.push(FindingData {
+                        detector_id: self.id(),
+                        location: loc_to_location(&var.loc, file).with_message(format!(
+                            "`{}` is never assigned, but `{}` reads it",
+                            name.name, reader
+                        )),
+                    });
+                }
+            }
+            findings
+        });
+    }
+}
+
+impl UninitializedStateVariableDetector {
+    /// The contract, its bases and the contracts deriving from it, with their files. `None`
+    /// when one of them has a base that did not resolve, or its source is not available (out
+    /// of scope, or released in low-memory mode).
+    fn family<'a>(
+        contract_def: &'a ContractDefinition,
+        file: &'a SolidityFile,
+        qualified_name: &str,
+        context: &'a AnalysisContext,
+    ) -> Option<Vec<(&'a ContractDefinition, &'a SolidityFile)>> {
+        let chain = context.get_inheritance_chain(qualified_name)?;
+        let derived = context
+            .contracts
+            .iter()
+            .filter(|(name, info)| {
+                name.as_str() != qualified_name
+                    && info
+                        .inheritance_chain
+                        .iter()
+                        .any(|base| base == qualified_name)
+            })
+            .map(|(name, _)| name)
+            .collect::<BTreeSet<_>>();
+
+        let mut family = vec![(contract_def, file)];
+        for member in chain.iter().chain(derived) {
+            let info = context.get_contract(member)?;
+            let resolved_bases = info
+                .inheritance_chain
+                .iter()
+                .filter(|base| *base != member)
+                .count();
+            if info.direct_bases.len() > resolved_bases {
+                return None;
+            }
+
+            let member_file = context.get_file_by_path(Path::new(&info.file_path))?;
+            let member_def = member_file
+                .source_unit
+                .0
+                .iter()
+                .find_map(|part| match part {
+                    SourceUnitPart::ContractDefinition(c)
+                        if c.name.as_ref().is_some_and(|n| n.name == info.name) =>
+                    {
+                        Some(c.as_ref())
+                    }
+                    _ => None,
+                })?;
+            family.push((member_def, member_file));
+        }
+        Some(family)
+    }
+
+    /// Functions, constructors and modifiers of a contract.
+    fn functions(contract_def: &ContractDefinition) -> impl Iterator<Item = &FunctionDefinition> {
+        contract_def.parts.iter().filter_map(|part| match part {
+            ContractPart::FunctionDefinition(func_def) => Some(func_def.as_ref()),
+            _ => None,
+        })
+    }
+
+    fn function_name(func_def: &FunctionDefinition) -> String {
+        match &func_def.name {
+            Some(name) => name.name.clone(),
+            None => func_def.ty.to_string(),
+        }
+    }
+
+    /// Mappings, arrays and structs, which library functions (`set.add(x)`) can write to.
+    fn is_structured_type(
+        ty: &Expression,
+        qualified_name: &str,
+        context: &AnalysisContext,
+    ) -> bool {
+        match ty {
+            Expression::Type(_, solang_parser::pt::Type::Mapping { .. })
+            | Expression::ArraySubscript(..)
+            // Library types such as `EnumerableSet.AddressSet`
+            | Expression::MemberAccess(..) => true,
+            Expression::Variable(id) => {
+                context
+                    .get_all_structs(qualified_name)
+                    .iter()
+                    .any(|s| s.name == id.name)
+                    || context
+                        .files
+                        .iter()
+                        .any(|f| f.structs.iter().any(|s| s.name == id.name))
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether `body` writes to the state variable `name`.
+    fn assigns(
+        body: &Statement,
+        file: &SolidityFile,
+        name: &str,
+        structured: bool,
+        storage_param_functions: &HashSet<&str>,
+    ) -> bool {
+        let refers = |expr: &Expression| root_variable(expr) == Some(name);
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            let written = match expr {
+                Expression::Assign(_, target, _)
+                | Expression::AssignOr(_, target, _)
+                | Expression::AssignAnd(_, target, _)
+                | Expression::AssignXor(_, target, _)
+                | Expression::AssignShiftLeft(_, target, _)
+                | Expression::AssignShiftRight(_, target, _)
+                | Expression::AssignAdd(_, target, _)
+                | Expression::AssignSubtract(_, target, _)
+                | Expression::AssignMultiply(_, target, _)
+                | Expression::AssignDivide(_, target, _)
+                | Expression::AssignModulo(_, target, _) => match target.as_ref() {
+                    // `(a, b) = f()`
+                    Expression::List(_, params) => params
+                        .iter()
+                        .any(|(_, param)| param.as_ref().is_some_and(|p| refers(&p.ty))),
+                    target => refers(target),
+                },
+                Expression::PreIncrement(_, target)
+                | Expression::PostIncrement(_, target)
+                | Expression::PreDecrement(_, target)
+                | Expression::PostDecrement(_, target)
+                | Expression::Delete(_, target) => refers(target),
+                Expression::FunctionCall(_, func, args) => match func.as_ref() {
+                    Expression::MemberAccess(_, base, member) => {
+                        refers(base)
+                            && (structured || member.name == "push" || member.name == "pop")
+                    }
+                    Expression::Variable(callee) => {
+                        storage_param_functions.contains(callee.name.as_str())
+                            && args.iter().any(refers)
+                    }
+                    _ => false,
+                },
+                _ => false,
+            };
+            written.then(|| expr.loc())
+        };
+        let mut writes = Vec::new();
+        find_locations_in_statement(body, file, &mut predicate, &mut writes);
+        if !writes.is_empty() {
+            return true;
+        }
+
+        // `Config storage config = configs;` and assembly such as `sstore(x.slot, v)`
+        !find_statement_types(body, file, "", |stmt| match stmt {
+            Statement::VariableDefinition(_, decl, Some(value)) => {
+                matches!(decl.storage, Some(StorageLocation::Storage(_))) && refers(value)
+            }
+            Statement::Assembly { loc, .. } => file
+                .content
+                .get(loc.start()..loc.end())
+                .is_some_and(|text| text.contains(name)),
+            _ => false,
+        })
+        .is_empty()
+    }
+}
+
+/// `configs` in `configs[id].fee`.
+fn root_variable(expr: &Expression) -> Option<&str> {
+    match expr {
+        Expression::Variable(id) => Some(&id.name),
+        Expression::ArraySubscript(_, base, _)
+        | Expression::ArraySlice(_, base, _, _)
+        | Expression::MemberAccess(_, base, _)
+        | Expression::Parenthesis(_, base) => root_variable(base),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::run_detector_on_code;
+
+    #[test]
+    fn test_uninitialized_state_variable() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            library EnumerableSet {
+                struct AddressSet { address[] values; }
+                function add(AddressSet storage set, address value) internal returns (bool) {
+                    set.values.push(value);
+                    return true;
+                }
+            }
+
+            contract Base {
+                address internal guardian;
+
+                function setGuardian(address g) external {
+                    guardian = g;
+                }
+            }
+
+            contract Vault is Base {
+                using EnumerableSet for EnumerableSet.AddressSet;
+
+                struct Config { uint256 fee; }
+
+                address public owner;
+                address public treasury;
+                uint256 public fee = 100;
+                uint256 public immutable cap;
+                uint256 public constant MAX = 1e18;
+                uint256 public unused;
+                mapping(address => uint256) public balances;
+                mapping(address => uint256) public credits;
+                EnumerableSet.AddressSet internal members;
+                Config internal config;
+                uint256 internal total;
+
+                constructor(address _owner, uint256 _cap) {
+                    owner = _owner;
+                    cap = _cap;
+                }
+
+                function pay(uint256 amount) external {
+                    require(msg.sender == guardian || msg.sender == owner);
+                    balances[treasury] += amount * fee / MAX;
+                    uint256 credit = credits[msg.sender];
+                    members.add(msg.sender);
+                    _setFee(config, amount);
+                    assembly { sstore(total.slot, credit) }
+                    require(amount <= cap && config.fee < total);
+                }
+
+                function _setFee(Config storage c, uint256 value) internal {
+                    c.fee = value;
+                }
+            }
+        "#;
+        let detector = Arc::new(UninitializedStateVariableDetector::default());
+        let locations = run_detector_on_code(detector, code, "vault.sol");
+
+        let lines: Vec<_> = locations.iter().map(|l| l.line).collect();
+        // treasury and credits; the rest are initialized, assigned somewhere or never read
+        assert_eq!(lines, [26, 32], "{:?}", locations);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some("`treasury` is never assigned, but `pay` reads it")
+        );
+    }
+
+    #[test]
+    fn test_assigned_in_derived_contract() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            contract Pool {
+                address public oracle;
+
+                function price() external view returns (uint256) {
+                    return uint256(uint160(oracle));
+                }
+            }
+
+            contract ConfiguredPool is Pool {
+                function initialize(address _oracle) external {
+                    oracle = _oracle;
+                }
+            }
+
+            abstract contract Strategy {
+                address public vault;
+
+                function report() external view returns (address) {
+                    return vault;
+                }
+            }
+        "#;
+        let detector = Arc::new(UninitializedStateVariableDetector::default());
+        let locations = run_detector_on_code(detector, code, "pool.sol");
+        assert!(locations.is_empty(), "{:?}", locations);
+    }
+}
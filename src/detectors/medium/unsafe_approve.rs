@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::location::loc_to_location;
 use crate::{core::visitor::ASTVisitor, models::FindingData};
@@ -21,11 +22,16 @@ impl Detector for UnsafeApproveDetector {
         Severity::Medium
     }
 
+    fn category(&self) -> Category {
+        Category::Token
+    }
+
     fn description(&self) -> &str {
         "Some tokens (like USDT) revert when changing allowance from non-zero to non-zero value. \
         This is a protection against front-running attacks. These tokens require first setting approval to 0, \
         then to the desired value. Additionally, OpenZeppelin's `safeApprove` will revert with \
-        'SafeERC20: approve from non-zero to non-zero allowance'. Always reset approval to zero before setting a new value."
+        'SafeERC20: approve from non-zero to non-zero allowance'. Always reset approval to zero before setting a new value. \
+        Calls that `approve-race` reports as changing an allowance without a reset are left to it."
     }
 
     fn example(&self) -> Option<String> {
@@ -0,0 +1,371 @@
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::category::Category;
+use crate::models::severity::Severity;
+use crate::models::{FindingData, SolidityFile};
+use crate::utils::ast_utils::{find_locations_in_statement, get_local_variable_names};
+use crate::utils::location::loc_to_location;
+use solang_parser::pt::{
+    CodeLocation, ContractPart, ContractTy, Expression, FunctionAttribute, FunctionDefinition,
+    FunctionTy, Loc, VariableAttribute,
+};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Base contract names marking a contract as deployed behind a proxy (`UUPSUpgradeable`
+/// matches `Upgradeable`).
+const UPGRADEABLE_BASES: [&str; 2] = ["Upgradeable", "Initializable"];
+
+/// Modifiers that stop `initialize` from running more than once.
+const INITIALIZER_MODIFIERS: [&str; 3] = ["initializer", "reinitializer", "onlyInitializing"];
+
+#[derive(Debug, Default)]
+pub struct UpgradeableStorageCollisionDetector;
+
+impl Detector for UpgradeableStorageCollisionDetector {
+    fn id(&self) -> &'static str {
+        "upgradeable-storage-collision"
+    }
+
+    fn name(&self) -> &str {
+        "Upgradeable contract state is not set through the proxy"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Medium
+    }
+
+    fn category(&self) -> Category {
+        Category::Upgradeability
+    }
+
+    fn description(&self) -> &str {
+        "A contract deployed behind a proxy keeps its state in the proxy's storage, but its \
+         constructor and the initial values of its state variables only run when the \
+         implementation is deployed, so they write to the implementation's storage. In contracts \
+         inheriting from an `Upgradeable` or `Initializable` base, this reports constructors that \
+         assign mutable state variables, state variables with a value at their declaration, and \
+         `initialize` functions without an `initializer` or `reinitializer` modifier, which \
+         anyone can call again to reset the state. Constants and immutables are part of the code \
+         and are not reported."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad - the proxy never sees these values, and initialize can be called again
+contract Vault is UUPSUpgradeable {
+    uint256 public fee = 100;
+    address public owner;
+
+    constructor() {
+        owner = msg.sender;
+    }
+
+    function initialize(address _owner) external {
+        owner = _owner;
+    }
+}
+
+// Good
+contract Vault is UUPSUpgradeable {
+    uint256 public fee;
+    address public owner;
+
+    constructor() {
+        _disableInitializers();
+    }
+
+    function initialize(address _owner) external initializer {
+        owner = _owner;
+        fee = 100;
+    }
+}
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_contract(move |contract_def, file, context| {
+            let Some(contract_name) = contract_def.name.as_ref() else {
+                return Vec::new();
+            };
+            if !matches!(
+                contract_def.ty,
+                ContractTy::Contract(_) | ContractTy::Abstract(_)
+            ) || !UPGRADEABLE_BASES
+                .iter()
+                .any(|base| context.contract_inherits_from(contract_def, file, base))
+            {
+                return Vec::new();
+            }
+
+            let qualified_name = context.get_qualified_name_for_contract(&contract_name.name);
+            let mutable_state_vars: HashSet<&str> = context
+                .get_all_state_variables(&qualified_name)
+                .into_iter()
+                .filter(|v| !v.is_constant && !v.is_immutable)
+                .map(|v| v.name.as_str())
+                .collect();
+
+            let mut findings = Vec::new();
+
+            for part in &contract_def.parts {
+                match part {
+                    ContractPart::VariableDefinition(var) if var.initializer.is_some() => {
+                        let Some(name) = &var.name else {
+                            continue;
+                        };
+                        let in_code = var.attrs.iter().any(|attr| {
+                            matches!(
+                                attr,
+                                VariableAttribute::Constant(_) | VariableAttribute::Immutable(_)
+                            )
+                        });
+                        if !in_code {
+                            findings.push(FindingData {
+                                detector_id: self.id(),
+                                location: loc_to_location(&var.loc, file).with_message(format!(
+                                    "`{}` is initialized at its declaration, which only sets it \
+                                     in the implementation's storage",
+                                    name.name
+                                )),
+                            });
+                        }
+                    }
+                    ContractPart::FunctionDefinition(func_def) => {
+                        match func_def.ty {
+                            FunctionTy::Constructor => findings.extend(
+                                self.constructor_assignments(func_def, file, &mutable_state_vars),
+                            ),
+                            FunctionTy::Function
+                                if func_def.body.is_some()
+                                    && func_def
+                                        .name
+                                        .as_ref()
+                                        .is_some_and(|n| n.name == "initialize")
+                                    && !Self::has_initializer_modifier(func_def) =>
+                            {
+                                let message = "`initialize` has no `initializer` or \
+                                               `reinitializer` modifier and can be called again";
+                                findings.push(FindingData {
+                                    detector_id: self.id(),
+                                    location: loc_to_location(&func_def.loc, file)
+                                        .with_message(message),
+                                });
+                            }
+                            _ => {}
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            findings
+        });
+    }
+}
+
+impl UpgradeableStorageCollisionDetector {
+    /// The first assignment to each mutable state variable in a constructor.
+    fn constructor_assignments(
+        &self,
+        func_def: &FunctionDefinition,
+        file: &SolidityFile,
+        state_vars: &HashSet<&str>,
+    ) -> Vec<FindingData> {
+        let Some(body) = &func_def.body else {
+            return Vec::new();
+        };
+        let mut state_vars = state_vars.clone();
+        for local in get_local_variable_names(func_def, body) {
+            state_vars.remove(local.as_str());
+        }
+
+        let mut assigned = Vec::new();
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            let target = match expr {
+                Expression::Assign(_, target, _)
+                | Expression::AssignOr(_, target, _)
+                | Expression::AssignAnd(_, target, _)
+                | Expression::AssignXor(_, target, _)
+                | Expression::AssignShiftLeft(_, target, _)
+                | Expression::AssignShiftRight(_, target, _)
+                | Expression::AssignAdd(_, target, _)
+                | Expression::AssignSubtract(_, target, _)
+                | Expression::AssignMultiply(_, target, _)
+                | Expression::AssignDivide(_, target, _)
+                | Expression::AssignModulo(_, target, _)
+                | Expression::PreIncrement(_, target)
+                | Expression::PostIncrement(_, target)
+                | Expression::PreDecrement(_, target)
+                | Expression::PostDecrement(_, target) => target,
+                _ => return None,
+            };
+            let name = root_variable(target).filter(|name| state_vars.contains(name))?;
+            assigned.push(name.to_string());
+            Some(expr.loc())
+        };
+        let mut locations = Vec::new();
+        find_locations_in_statement(body, file, &mut predicate, &mut locations);
+
+        let mut reported = HashSet::new();
+        locations
+            .into_iter()
+            .zip(assigned)
+            .filter(|(_, name)| reported.insert(name.clone()))
+            .map(|(location, name)| FindingData {
+                detector_id: self.id(),
+                location: location.with_message(format!(
+                    "The constructor assigns `{}`, which only sets it in the implementation's \
+                     storage; assign it in an initializer",
+                    name
+                )),
+            })
+            .collect()
+    }
+
+    fn has_initializer_modifier(func_def: &FunctionDefinition) -> bool {
+        func_def.attributes.iter().any(|attr| {
+            matches!(
+                attr,
+                FunctionAttribute::BaseOrModifier(_, base)
+                    if base.name.identifiers.last().is_some_and(
+                        |id| INITIALIZER_MODIFIERS.contains(&id.name.as_str())
+                    )
+            )
+        })
+    }
+}
+
+/// `balances` in `balances[user].amount`.
+fn root_variable(expr: &Expression) -> Option<&str> {
+    match expr {
+        Expression::Variable(id) => Some(&id.name),
+        Expression::ArraySubscript(_, base, _)
+        | Expression::MemberAccess(_, base, _)
+        | Expression::Parenthesis(_, base) => root_variable(base),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::run_detector_on_code;
+
+    const BASES: &str = r#"
+        abstract contract Initializable {}
+        abstract contract UUPSUpgradeable is Initializable {}
+    "#;
+
+    fn run(code: &str) -> Vec<crate::models::finding::Location> {
+        let code = format!("{}{}", BASES, code);
+        let detector = Arc::new(UpgradeableStorageCollisionDetector::default());
+        run_detector_on_code(detector, &code, "test.sol")
+    }
+
+    #[test]
+    fn test_detects_constructor_assigning_state() {
+        let locations = run(r#"
+            contract Vault is UUPSUpgradeable {
+                address public owner;
+                mapping(address => uint256) public limits;
+                address public immutable token;
+
+                constructor(address _token) {
+                    token = _token;
+                    owner = msg.sender;
+                    limits[msg.sender] = 1;
+                    owner = _token;
+                }
+            }
+        "#);
+        let lines: Vec<usize> = locations.iter().map(|l| l.line).collect();
+        assert_eq!(lines, [12, 13]);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some(
+                "The constructor assigns `owner`, which only sets it in the implementation's \
+                 storage; assign it in an initializer"
+            )
+        );
+    }
+
+    #[test]
+    fn test_detects_unprotected_initialize() {
+        let locations = run(r#"
+            contract Vault is Initializable {
+                address public owner;
+
+                function initialize(address _owner) external {
+                    owner = _owner;
+                }
+            }
+
+            contract VaultV2 is Initializable {
+                address public owner;
+
+                function initialize(address _owner) external reinitializer(2) {
+                    owner = _owner;
+                }
+            }
+        "#);
+        assert_eq!(locations.len(), 1);
+        assert_eq!(locations[0].line, 8);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some("`initialize` has no `initializer` or `reinitializer` modifier and can be called again")
+        );
+    }
+
+    #[test]
+    fn test_detects_state_variable_initialized_at_declaration() {
+        let locations = run(r#"
+            contract Vault is UUPSUpgradeable {
+                uint256 public fee = 100;
+                uint256 public constant MAX_FEE = 1000;
+                address public immutable token = address(1);
+                bool public paused;
+            }
+        "#);
+        assert_eq!(locations.len(), 1);
+        assert_eq!(locations[0].line, 6);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some(
+                "`fee` is initialized at its declaration, which only sets it in the \
+                 implementation's storage"
+            )
+        );
+    }
+
+    #[test]
+    fn test_skips_non_upgradeable_and_correct_contracts() {
+        let locations = run(r#"
+            contract Token {
+                address public owner = msg.sender;
+
+                constructor() {
+                    owner = msg.sender;
+                }
+
+                function initialize() external {}
+            }
+
+            contract Vault is UUPSUpgradeable {
+                address public owner;
+
+                constructor() {
+                    _disableInitializers();
+                }
+
+                function initialize(address _owner) external initializer {
+                    owner = _owner;
+                }
+            }
+        "#);
+        assert_eq!(locations.len(), 0);
+    }
+}
@@ -0,0 +1,358 @@
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::category::Category;
+use crate::models::severity::Severity;
+use crate::models::{FindingData, SolidityFile};
+use crate::utils::ast_utils::{find_locations_in_expression, find_locations_in_statement};
+use crate::utils::location::loc_to_location;
+use solang_parser::pt::{CodeLocation, Expression, Loc, Statement};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+
+/// `block` members that whoever builds the block knows, or can pick, before the transaction runs
+const BLOCK_MEMBERS: &[&str] = &[
+    "timestamp",
+    "number",
+    "prevrandao",
+    "difficulty",
+    "coinbase",
+];
+
+/// Hash functions whose output is used as a random number
+const HASH_FUNCTIONS: &[&str] = &["keccak256", "sha256"];
+
+/// Local variables holding block values, by name, with the block values they hold
+type Sources = HashMap<String, BTreeSet<String>>;
+
+#[derive(Debug, Default)]
+pub struct WeakRandomnessDetector;
+
+impl Detector for WeakRandomnessDetector {
+    fn id(&self) -> &'static str {
+        "weak-randomness"
+    }
+
+    fn name(&self) -> &str {
+        "Randomness derived from block values"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Medium
+    }
+
+    fn category(&self) -> Category {
+        Category::Cryptography
+    }
+
+    fn description(&self) -> &str {
+        "`block.timestamp`, `block.number`, `block.prevrandao`, `block.coinbase` and `blockhash` \
+         are known to, or chosen by, the validator building the block, and any contract can read \
+         them in the same transaction. Hashing them does not make them random: a caller can \
+         compute the outcome beforehand and only play when they win. This reports `%` \
+         operations and comparisons on a `keccak256`/`sha256` hash of such values, directly or \
+         through the local variables they are stored in. Use a VRF such as Chainlink VRF, or a \
+         commit-reveal scheme."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad - anyone can compute the winner before calling
+function draw() external {
+    uint256 seed = uint256(keccak256(abi.encodePacked(block.timestamp, block.prevrandao)));
+    winner = players[seed % players.length];
+}
+
+// Good - take the random number from a VRF callback
+function fulfillRandomWords(uint256, uint256[] calldata randomWords) internal override {
+    winner = players[randomWords[0] % players.length];
+}
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_function(move |func_def, file, _context| {
+            let Some(body) = &func_def.body else {
+                return Vec::new();
+            };
+
+            let mut assignments = Vec::new();
+            Self::collect_assignments(body, &mut assignments);
+
+            // Follow block values and their hashes through local variables, in source order
+            let mut block_vars = Sources::new();
+            let mut hash_vars = Sources::new();
+            for (name, value) in assignments {
+                let hashed = Self::hashed_sources(value, file, &block_vars, &hash_vars);
+                if !hashed.is_empty() {
+                    hash_vars.insert(name.to_string(), hashed);
+                    continue;
+                }
+                let sources = Self::block_sources(value, file, &block_vars);
+                if !sources.is_empty() {
+                    block_vars.insert(name.to_string(), sources);
+                }
+            }
+
+            let mut uses: Vec<(Loc, BTreeSet<String>)> = Vec::new();
+            let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+                let sources = match expr {
+                    Expression::Modulo(_, left, _) => {
+                        Self::hashed_sources(left, file, &block_vars, &hash_vars)
+                    }
+                    Expression::Equal(_, left, right)
+                    | Expression::NotEqual(_, left, right)
+                    | Expression::Less(_, left, right)
+                    | Expression::More(_, left, right)
+                    | Expression::LessEqual(_, left, right)
+                    | Expression::MoreEqual(_, left, right) => {
+                        // Only the hash itself, so `seed % n == 0` is reported once, for the `%`
+                        let mut sources = BTreeSet::new();
+                        for side in [left, right] {
+                            let side = Self::strip_casts(side);
+                            if Self::is_hash_call(side)
+                                || matches!(side, Expression::Variable(id) if hash_vars.contains_key(&id.name))
+                            {
+                                sources.extend(Self::hashed_sources(
+                                    side,
+                                    file,
+                                    &block_vars,
+                                    &hash_vars,
+                                ));
+                            }
+                        }
+                        sources
+                    }
+                    _ => return None,
+                };
+                if !sources.is_empty() {
+                    uses.push((expr.loc(), sources));
+                }
+                None
+            };
+            find_locations_in_statement(body, file, &mut predicate, &mut Vec::new());
+
+            uses.into_iter()
+                .map(|(loc, sources)| {
+                    let sources: Vec<String> =
+                        sources.iter().map(|s| format!("`{}`", s)).collect();
+                    FindingData {
+                        detector_id: self.id(),
+                        location: loc_to_location(&loc, file).with_message(format!(
+                            "Derived from {}",
+                            sources.join(", ")
+                        )),
+                    }
+                })
+                .collect()
+        });
+    }
+}
+
+impl WeakRandomnessDetector {
+    /// `name = value` assignments and initialized declarations of local variables, in order.
+    fn collect_assignments<'a>(stmt: &'a Statement, out: &mut Vec<(&'a str, &'a Expression)>) {
+        match stmt {
+            Statement::VariableDefinition(_, decl, Some(value)) => {
+                if let Some(name) = &decl.name {
+                    out.push((name.name.as_str(), value));
+                }
+            }
+            Statement::Expression(_, Expression::Assign(_, left, value)) => {
+                if let Expression::Variable(id) = left.as_ref() {
+                    out.push((id.name.as_str(), value));
+                }
+            }
+            Statement::Block { statements, .. } => {
+                for s in statements {
+                    Self::collect_assignments(s, out);
+                }
+            }
+            Statement::If(_, _, then_stmt, else_stmt) => {
+                Self::collect_assignments(then_stmt, out);
+                if let Some(else_stmt) = else_stmt {
+                    Self::collect_assignments(else_stmt, out);
+                }
+            }
+            Statement::For(_, init, _, _, body) => {
+                if let Some(init) = init {
+                    Self::collect_assignments(init, out);
+                }
+                if let Some(body) = body {
+                    Self::collect_assignments(body, out);
+                }
+            }
+            Statement::While(_, _, body) | Statement::DoWhile(_, body, _) => {
+                Self::collect_assignments(body, out);
+            }
+            _ => {}
+        }
+    }
+
+    /// Block values `expr` reads, directly or through `block_vars`.
+    fn block_sources(
+        expr: &Expression,
+        file: &SolidityFile,
+        block_vars: &Sources,
+    ) -> BTreeSet<String> {
+        let mut sources = BTreeSet::new();
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            if let Some(value) = Self::block_value(expr) {
+                sources.insert(value);
+            } else if let Expression::Variable(id) = expr {
+                if let Some(held) = block_vars.get(&id.name) {
+                    sources.extend(held.iter().cloned());
+                }
+            }
+            None
+        };
+        find_locations_in_expression(expr, file, &mut predicate, &mut Vec::new());
+        sources
+    }
+
+    /// Block values hashed somewhere in `expr`, directly or through `hash_vars`.
+    fn hashed_sources(
+        expr: &Expression,
+        file: &SolidityFile,
+        block_vars: &Sources,
+        hash_vars: &Sources,
+    ) -> BTreeSet<String> {
+        let mut sources = BTreeSet::new();
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            match expr {
+                Expression::FunctionCall(_, _, args) if Self::is_hash_call(expr) => {
+                    for arg in args {
+                        sources.extend(Self::block_sources(arg, file, block_vars));
+                    }
+                }
+                Expression::Variable(id) => {
+                    if let Some(held) = hash_vars.get(&id.name) {
+                        sources.extend(held.iter().cloned());
+                    }
+                }
+                _ => {}
+            }
+            None
+        };
+        find_locations_in_expression(expr, file, &mut predicate, &mut Vec::new());
+        sources
+    }
+
+    /// `block.timestamp` and the other block members, `blockhash(n)` and `now`.
+    fn block_value(expr: &Expression) -> Option<String> {
+        match expr {
+            Expression::MemberAccess(_, base, member)
+                if matches!(base.as_ref(), Expression::Variable(id) if id.name == "block")
+                    && BLOCK_MEMBERS.contains(&member.name.as_str()) =>
+            {
+                Some(format!("block.{}", member.name))
+            }
+            Expression::FunctionCall(_, callee, _) if matches!(callee.as_ref(), Expression::Variable(id) if id.name == "blockhash") => {
+                Some("blockhash".to_string())
+            }
+            Expression::Variable(id) if id.name == "now" => Some("now".to_string()),
+            _ => None,
+        }
+    }
+
+    fn is_hash_call(expr: &Expression) -> bool {
+        matches!(expr, Expression::FunctionCall(_, callee, _)
+            if matches!(callee.as_ref(), Expression::Variable(id)
+                if HASH_FUNCTIONS.contains(&id.name.as_str())))
+    }
+
+    /// `uint256(x)` -> `x`
+    fn strip_casts(expr: &Expression) -> &Expression {
+        match expr {
+            Expression::FunctionCall(_, callee, args)
+                if matches!(callee.as_ref(), Expression::Type(..)) && args.len() == 1 =>
+            {
+                Self::strip_casts(&args[0])
+            }
+            Expression::Parenthesis(_, inner) => Self::strip_casts(inner),
+            _ => expr,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::run_detector_on_code;
+
+    #[test]
+    fn test_detects_lottery_patterns() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            contract Lottery {
+                address[] players;
+                address winner;
+
+                function draw() external {
+                    uint256 seed = uint256(keccak256(abi.encodePacked(block.timestamp, block.prevrandao)));
+                    winner = players[seed % players.length];
+                }
+
+                function drawInline() external {
+                    uint256 index = uint256(keccak256(abi.encode(blockhash(block.number - 1)))) % 10;
+                    winner = players[index];
+                }
+
+                function guess(bytes32 answer) external {
+                    uint256 nonce = block.number;
+                    bytes32 hash = keccak256(abi.encodePacked(nonce, msg.sender));
+                    if (hash == answer) {
+                        winner = msg.sender;
+                    }
+                }
+            }
+        "#;
+        let detector = Arc::new(WeakRandomnessDetector::default());
+        let locations = run_detector_on_code(detector, code, "test.sol");
+        assert_eq!(locations.len(), 3);
+        assert_eq!(locations[0].line, 10, "seed % players.length");
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some("Derived from `block.prevrandao`, `block.timestamp`")
+        );
+        assert_eq!(locations[1].line, 14, "inline hash modulo");
+        assert_eq!(locations[2].line, 21, "hash == answer");
+        assert_eq!(
+            locations[2].message.as_deref(),
+            Some("Derived from `block.number`")
+        );
+    }
+
+    #[test]
+    fn test_skips_deadlines_and_other_hashes() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            contract Auction {
+                uint256 deadline;
+                uint256 epochLength;
+                mapping(bytes32 => bool) used;
+
+                function bid() external view {
+                    require(block.timestamp <= deadline, "Auction ended");
+                }
+
+                function epoch() external view returns (uint256) {
+                    return block.number % epochLength;
+                }
+
+                function claim(bytes32 secret, uint256 slots) external returns (uint256) {
+                    bytes32 id = keccak256(abi.encode(msg.sender, secret));
+                    used[id] = true;
+                    return uint256(id) % slots;
+                }
+            }
+        "#;
+        let detector = Arc::new(WeakRandomnessDetector::default());
+        let locations = run_detector_on_code(detector, code, "test.sol");
+        assert_eq!(locations.len(), 0);
+    }
+}
@@ -1,5 +1,6 @@
+use crate::config::DetectorSettings;
 use crate::core::visitor::ASTVisitor;
-use crate::models::Severity;
+use crate::models::{Category, Severity};
 use std::fmt;
 use std::sync::Arc;
 
@@ -9,6 +10,19 @@ pub mod low;
 pub mod medium;
 pub mod nc;
 
+/// Rollups and L2s used to tag detectors whose findings only matter off mainnet.
+pub const L2_CHAINS: &[&str] = &[
+    "arbitrum", "optimism", "base", "zksync", "scroll", "linea", "blast", "mantle",
+];
+
+/// Detectors that can flag the same expression for related reasons, most specific first. When
+/// several detectors of a group report the same line of a file, only the findings of the one
+/// listed first are kept.
+pub const OVERLAP_GROUPS: &[&[&str]] = &[
+    &["approve-race", "unsafe-approve"],
+    &["naming-convention", "underscore-prefix"],
+];
+
 pub trait Detector: Send + Sync + 'static {
     fn id(&self) -> &'static str;
     fn name(&self) -> &str;
@@ -16,6 +30,29 @@ pub trait Detector: Send + Sync + 'static {
     fn description(&self) -> &str;
     fn example(&self) -> Option<String>;
 
+    /// What the findings are about, for grouping and `--category` filtering. Defaults to
+    /// [`Category::for_severity`]; High/Medium/Low detectors are expected to override it.
+    fn category(&self) -> Category {
+        Category::for_severity(&self.severity())
+    }
+
+    /// Revision of the detector's logic. Bump it whenever a change can alter the findings on
+    /// unchanged source, including changes to shared helpers the detector relies on, so that
+    /// results recorded by an older build are not reused. `tests/detector_versions.rs` fails
+    /// when a detector's source changes until the change is acknowledged.
+    fn version(&self) -> u32 {
+        1
+    }
+
+    /// Chains this detector is relevant for, matched against `target_chains`.
+    /// An empty slice means the detector applies to every chain.
+    fn relevant_chains(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Apply options from the detector's `[detectors.<id>]` config table.
+    fn configure(&mut self, _settings: &DetectorSettings) {}
+
     /// Register callbacks with the AST visitor.
     fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor);
 }
@@ -23,12 +60,17 @@ pub trait Detector: Send + Sync + 'static {
 impl fmt::Display for dyn Detector {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut msg = format!(
-            "Name: {}\nSeverity: {}\nDescription: {}",
+            "Name: {}\nSeverity: {}\nCategory: {}\nDescription: {}",
             self.name(),
             self.severity(),
+            self.category(),
             self.description()
         );
 
+        if !self.relevant_chains().is_empty() {
+            msg += &format!("\nChains: {}", self.relevant_chains().join(", "));
+        }
+
         if let Some(example) = self.example() {
             msg += &format!("\nExample: {}", example);
         }
@@ -36,3 +78,39 @@ impl fmt::Display for dyn Detector {
         write!(f, "{}", msg)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_every_exported_detector_is_built_in() {
+        let modules = [
+            (include_str!("high/mod.rs"), high::all()),
+            (include_str!("medium/mod.rs"), medium::all()),
+            (include_str!("low/mod.rs"), low::all()),
+            (include_str!("gas/mod.rs"), gas::all()),
+            (include_str!("nc/mod.rs"), nc::all()),
+        ];
+        let mut ids = HashSet::new();
+        for (source, detectors) in modules {
+            let exported: Vec<&str> = source
+                .lines()
+                .filter_map(|line| line.strip_prefix("pub use ")?.strip_suffix(';'))
+                .filter_map(|path| path.rsplit("::").next())
+                .collect();
+            for detector in &exported {
+                assert!(
+                    source.contains(&format!("Box::new({}::default())", detector)),
+                    "{} is exported but missing from all()",
+                    detector
+                );
+            }
+            assert_eq!(exported.len(), detectors.len());
+            for detector in &detectors {
+                assert!(ids.insert(detector.id()), "duplicate id {}", detector.id());
+            }
+        }
+    }
+}
@@ -0,0 +1,334 @@
+use crate::core::context::AnalysisContext;
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::severity::Severity;
+use crate::models::{FindingData, Location, SolidityFile};
+use crate::utils::ast_utils::{
+    find_locations_in_expression, find_locations_in_statement, get_local_variable_names,
+};
+use crate::utils::location::loc_to_location;
+use solang_parser::pt::{
+    ContractDefinition, ContractPart, Expression, FunctionAttribute, FunctionDefinition,
+    Identifier, Loc, Mutability, SourceUnitPart,
+};
+use std::sync::Arc;
+
+#[derive(Debug, Default)]
+pub struct DeprecatedConstructsDetector;
+
+impl Detector for DeprecatedConstructsDetector {
+    fn id(&self) -> &'static str {
+        "deprecated-constructs"
+    }
+
+    fn name(&self) -> &str {
+        "Use of deprecated Solidity constructs"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::NC
+    }
+
+    fn version(&self) -> u32 {
+        2
+    }
+
+    fn description(&self) -> &str {
+        "Constructs such as `now`, `sha3()`, `suicide()`, `msg.gas`, the `constant` function \
+         modifier and the `years` time unit were removed in Solidity 0.5 or later. They do not \
+         compile with modern pragmas and usually indicate outdated or copy-pasted code. \
+         Replace them with their modern equivalents. `now` is only reported where no \
+         variable or function of that name is in scope."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad
+function expiry() public constant returns (uint256) {
+    return now + 1 years;
+}
+
+// Good
+function expiry() public view returns (uint256) {
+    return block.timestamp + 365 days;
+}
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        let self_clone = Arc::clone(&self);
+        visitor.on_contract(move |contract_def, file, context| {
+            if Self::contract_declares_now(contract_def, context) {
+                return Vec::new();
+            }
+            let mut locations = Vec::new();
+            for part in &contract_def.parts {
+                match part {
+                    ContractPart::FunctionDefinition(func_def) => {
+                        Self::find_builtin_now(func_def, file, &mut locations)
+                    }
+                    ContractPart::VariableDefinition(var_def) => {
+                        if let Some(initializer) = &var_def.initializer {
+                            find_locations_in_expression(
+                                initializer,
+                                file,
+                                &mut Self::now_variable,
+                                &mut locations,
+                            );
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            self_clone.now_findings(locations)
+        });
+
+        let self_clone = Arc::clone(&self);
+        visitor.on_source_unit_part(move |part, file, _context| {
+            let SourceUnitPart::FunctionDefinition(func_def) = part else {
+                return Vec::new();
+            };
+            let mut locations = Vec::new();
+            Self::find_builtin_now(func_def, file, &mut locations);
+            self_clone.now_findings(locations)
+        });
+
+        let self_clone = Arc::clone(&self);
+        visitor.on_expression(move |expr, file, _context| {
+            let Some((loc, message)) = Self::check_expression(expr) else {
+                return Vec::new();
+            };
+
+            FindingData {
+                detector_id: self_clone.id(),
+                location: loc_to_location(&loc, file).with_message(message),
+            }
+            .into()
+        });
+
+        visitor.on_function(move |func_def, file, _context| {
+            func_def
+                .attributes
+                .iter()
+                .filter_map(|attr| match attr {
+                    FunctionAttribute::Mutability(Mutability::Constant(loc)) => Some(FindingData {
+                        detector_id: self.id(),
+                        location: loc_to_location(loc, file).with_message(
+                            "`constant` function modifier is deprecated, use `view` or `pure`",
+                        ),
+                    }),
+                    _ => None,
+                })
+                .collect()
+        });
+    }
+}
+
+impl DeprecatedConstructsDetector {
+    /// Whether the contract or one of its bases declares a state variable or function named
+    /// `now`, which is what the name refers to in the contract then.
+    fn contract_declares_now(contract_def: &ContractDefinition, context: &AnalysisContext) -> bool {
+        let declared_here = contract_def.parts.iter().any(|part| match part {
+            ContractPart::VariableDefinition(var_def) => {
+                var_def.name.as_ref().is_some_and(|name| name.name == "now")
+            }
+            ContractPart::FunctionDefinition(func_def) => func_def
+                .name
+                .as_ref()
+                .is_some_and(|name| name.name == "now"),
+            _ => false,
+        });
+        let Some(name) = &contract_def.name else {
+            return declared_here;
+        };
+        let qualified_name = context.get_qualified_name_for_contract(&name.name);
+        let Some(contract) = context.get_contract(&qualified_name) else {
+            return declared_here;
+        };
+        declared_here
+            || contract
+                .inheritance_chain
+                .iter()
+                .filter_map(|base| context.get_contract(base))
+                .any(|base| {
+                    base.state_variables.iter().any(|var| var.name == "now")
+                        || base.function_definitions.iter().any(|f| f.name == "now")
+                })
+    }
+
+    /// Uses of `now` in a function and its modifier arguments, unless a parameter, return
+    /// variable or local variable of that name hides the builtin.
+    fn find_builtin_now(
+        func_def: &FunctionDefinition,
+        file: &SolidityFile,
+        locations: &mut Vec<Location>,
+    ) {
+        let Some(body) = &func_def.body else {
+            return;
+        };
+        if get_local_variable_names(func_def, body).contains("now") {
+            return;
+        }
+        for attr in &func_def.attributes {
+            if let FunctionAttribute::BaseOrModifier(_, base) = attr {
+                for arg in base.args.iter().flatten() {
+                    find_locations_in_expression(arg, file, &mut Self::now_variable, locations);
+                }
+            }
+        }
+        find_locations_in_statement(body, file, &mut Self::now_variable, locations);
+    }
+
+    fn now_variable(expr: &Expression, _file: &SolidityFile) -> Option<Loc> {
+        match expr {
+            Expression::Variable(Identifier { loc, name }) if name == "now" => Some(*loc),
+            _ => None,
+        }
+    }
+
+    fn now_findings(&self, locations: Vec<Location>) -> Vec<FindingData> {
+        locations
+            .into_iter()
+            .map(|location| FindingData {
+                detector_id: self.id(),
+                location: location.with_message("`now` is deprecated, use `block.timestamp`"),
+            })
+            .collect()
+    }
+
+    fn check_expression(expr: &Expression) -> Option<(Loc, &'static str)> {
+        match expr {
+            Expression::FunctionCall(loc, func, _) => match func.as_ref() {
+                Expression::Variable(id) if id.name == "sha3" => {
+                    Some((*loc, "`sha3()` is deprecated, use `keccak256()`"))
+                }
+                Expression::Variable(id) if id.name == "suicide" => {
+                    Some((*loc, "`suicide()` is deprecated, use `selfdestruct()`"))
+                }
+                _ => None,
+            },
+            Expression::MemberAccess(loc, base, member) if member.name == "gas" => {
+                match base.as_ref() {
+                    Expression::Variable(id) if id.name == "msg" => {
+                        Some((*loc, "`msg.gas` is deprecated, use `gasleft()`"))
+                    }
+                    _ => None,
+                }
+            }
+            Expression::NumberLiteral(loc, _, _, Some(unit))
+            | Expression::RationalNumberLiteral(loc, _, _, _, Some(unit))
+            | Expression::HexNumberLiteral(loc, _, Some(unit))
+                if unit.name == "years" =>
+            {
+                Some((
+                    *loc,
+                    "`years` time unit is deprecated, use `365 days` explicitly",
+                ))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::run_detector_on_code;
+
+    #[test]
+    fn test_deprecated_constructs_detector() {
+        let code = r#"
+            pragma solidity ^0.4.24;
+
+            contract Legacy {
+                uint256 public lockTime = 1 years;               // Positive: years
+
+                function stamp() public constant returns (uint256) { // Positive: constant
+                    return now;                                  // Positive: now
+                }
+
+                function hash(bytes memory data) public returns (bytes32) {
+                    return sha3(data);                           // Positive: sha3
+                }
+
+                function kill() public {
+                    suicide(msg.sender);                         // Positive: suicide
+                }
+
+                function remaining() public returns (uint256) {
+                    return msg.gas;                              // Positive: msg.gas
+                }
+
+                function modern() public view returns (uint256) {
+                    uint256 now_ = block.timestamp;              // Negative
+                    return now_ + 365 days + gasleft();          // Negative
+                }
+            }
+        "#;
+
+        let detector = Arc::new(DeprecatedConstructsDetector::default());
+        let locations = run_detector_on_code(detector, code, "legacy.sol");
+
+        assert_eq!(locations.len(), 6, "Should detect 6 deprecated constructs");
+
+        let find = |line: usize| {
+            locations
+                .iter()
+                .find(|l| l.line == line)
+                .and_then(|l| l.message.clone())
+                .unwrap_or_default()
+        };
+
+        assert!(find(5).contains("365 days"));
+        assert!(find(7).contains("`view` or `pure`"));
+        assert!(find(8).contains("block.timestamp"));
+        assert!(find(12).contains("keccak256"));
+        assert!(find(16).contains("selfdestruct"));
+        assert!(find(20).contains("gasleft"));
+
+        assert!(
+            !locations.iter().any(|l| l.line == 24 || l.line == 25),
+            "Modern code and `now_` should not be flagged"
+        );
+    }
+
+    #[test]
+    fn test_declared_now_is_not_the_builtin() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            contract Clock {
+                function elapsed(uint256 now) public view returns (uint256) {
+                    return block.timestamp - now;
+                }
+
+                function later() public view returns (uint256 delay) {
+                    uint256 now = block.timestamp;
+                    delay = now + 1 days;
+                }
+            }
+
+            contract Schedule {
+                uint256 public now;
+
+                function next() public view returns (uint256) {
+                    return now + 1 days;
+                }
+            }
+
+            contract Legacy {
+                function stamp() public view returns (uint256) {
+                    return now;
+                }
+            }
+        "#;
+
+        let detector = Arc::new(DeprecatedConstructsDetector::default());
+        let locations = run_detector_on_code(detector, code, "clock.sol");
+        let lines: Vec<_> = locations.iter().map(|l| l.line).collect();
+        assert_eq!(lines, [25]);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::core::visitor::ASTVisitor;
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::models::FindingData;
 use crate::utils::location::loc_to_location;
@@ -22,6 +23,10 @@ impl Detector for EcrecoverVCheckDetector {
         Severity::NC
     }
 
+    fn category(&self) -> Category {
+        Category::Cryptography
+    }
+
     fn description(&self) -> &str {
         "The EVM precompile for ecrecover already checks if v is 27 or 28. There is no need to \
          perform this check on the caller side. See: https://twitter.com/alexberegszaszi/status/1534461421454606336"
@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::location::loc_to_location;
 use crate::{core::visitor::ASTVisitor, models::FindingData};
@@ -21,6 +22,10 @@ impl Detector for FloatingPragmaDetector {
         Severity::NC
     }
 
+    fn category(&self) -> Category {
+        Category::Compatibility
+    }
+
     fn description(&self) -> &str {
         "Floating pragmas may lead to unintended vulnerabilities due to different compiler \
          versions. It is recommended to lock the Solidity version in pragma statements for \
@@ -50,6 +55,10 @@ library MyLibrary {}
         )
     }
 
+    fn version(&self) -> u32 {
+        2
+    }
+
     fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
         visitor.on_source_unit(move |source_unit, file, _context| {
             let mut floating_pragma_location = None;
@@ -92,16 +101,24 @@ impl FloatingPragmaDetector {
     fn is_floating_pragma(version_req: &[VersionComparator]) -> bool {
         for comp in version_req {
             match comp {
-                VersionComparator::Plain { .. } => {}
-                VersionComparator::Operator { op, .. } => match op {
-                    VersionOp::Exact => {}
+                VersionComparator::Plain { version, .. } => {
+                    if Self::is_partial(version) {
+                        return true;
+                    }
+                }
+                VersionComparator::Operator { op, version, .. } => match op {
+                    VersionOp::Exact => {
+                        if Self::is_partial(version) {
+                            return true;
+                        }
+                    }
                     VersionOp::Caret
                     | VersionOp::Tilde
                     | VersionOp::Greater
                     | VersionOp::GreaterEq
                     | VersionOp::Less
-                    | VersionOp::LessEq => return true,
-                    _ => {}
+                    | VersionOp::LessEq
+                    | VersionOp::Wildcard => return true,
                 },
                 VersionComparator::Range { .. } => return true,
                 VersionComparator::Or { left, right, .. } => {
@@ -115,6 +132,11 @@ impl FloatingPragmaDetector {
         }
         false
     }
+
+    /// `0.8` or `0.8.x` match every patch release, like `^`
+    fn is_partial(version: &[String]) -> bool {
+        version.len() < 3 || version.iter().any(|part| part.parse::<u32>().is_err())
+    }
 }
 
 #[cfg(test)]
@@ -133,6 +155,12 @@ mod tests {
 
         assert_eq!(locations.len(), 1, "Should detect 1 issue");
         assert_eq!(locations[0].line, 2, "floating pragma");
+
+        for pragma in [">=0.8.0", ">=0.8.0 <0.9.0", "0.8.19 || ^0.8.20", "0.8", "=0.8"] {
+            let code = format!("pragma solidity {};\ncontract Test {{}}\n", pragma);
+            let locations = run_detector_on_code(Arc::new(FloatingPragmaDetector::default()), &code, "test.sol");
+            assert_eq!(locations.len(), 1, "{}", pragma);
+        }
     }
 
     #[test]
@@ -145,6 +173,12 @@ mod tests {
         let locations = run_detector_on_code(detector, code, "test.sol");
         assert_eq!(locations.len(), 0, "Should not detect fixed pragma");
 
+        for pragma in ["=0.8.20", "0.8.19 || 0.8.20"] {
+            let code = format!("pragma solidity {};\ncontract Test {{}}\n", pragma);
+            let locations = run_detector_on_code(Arc::new(FloatingPragmaDetector::default()), &code, "test.sol");
+            assert_eq!(locations.len(), 0, "{}", pragma);
+        }
+
         // Library with floating pragma - OK
         let code2 = r#"
             pragma solidity ^0.8.0;
@@ -0,0 +1,209 @@
+use crate::config::DetectorSettings;
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::severity::Severity;
+use crate::models::FindingData;
+use crate::utils::ast_utils::max_nesting_depth;
+use crate::utils::location::loc_to_location;
+use solang_parser::pt::{FunctionTy, Loc, Statement};
+use std::sync::Arc;
+
+const DEFAULT_MAX_PARAMS: usize = 6;
+const DEFAULT_MAX_DEPTH: usize = 4;
+
+#[derive(Debug)]
+pub struct FunctionComplexityDetector {
+    max_params: usize,
+    max_depth: usize,
+}
+
+impl Default for FunctionComplexityDetector {
+    fn default() -> Self {
+        Self {
+            max_params: DEFAULT_MAX_PARAMS,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+}
+
+impl Detector for FunctionComplexityDetector {
+    fn id(&self) -> &'static str {
+        "function-complexity"
+    }
+
+    fn name(&self) -> &str {
+        "Function has too many parameters or deeply nested code"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::NC
+    }
+
+    fn description(&self) -> &str {
+        "Functions with a long parameter list or deeply nested control flow are hard to read, \
+         review and test. Consider grouping parameters into a struct and extracting nested \
+         branches into helper functions or early returns. Limits are configurable via \
+         `max_params` and `max_depth` under `[detectors.function-complexity]`."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad
+function process(uint256[] calldata ids) external {
+    for (uint256 i; i < ids.length; i++) {
+        if (ids[i] != 0) {
+            if (owners[ids[i]] == msg.sender) {
+                if (!locked[ids[i]]) {
+                    if (balances[msg.sender] > 0) {
+                        _process(ids[i]);
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Good
+function process(uint256[] calldata ids) external {
+    for (uint256 i; i < ids.length; i++) {
+        if (_canProcess(ids[i])) _process(ids[i]);
+    }
+}
+```"#
+                .to_string(),
+        )
+    }
+
+    fn configure(&mut self, settings: &DetectorSettings) {
+        if let Some(max_params) = settings.get_usize(self.id(), "max_params") {
+            self.max_params = max_params;
+        }
+        if let Some(max_depth) = settings.get_usize(self.id(), "max_depth") {
+            self.max_depth = max_depth;
+        }
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_function(move |func_def, file, _context| {
+            if matches!(func_def.ty, FunctionTy::Modifier) {
+                return Vec::new();
+            }
+            let Some(body @ Statement::Block { loc: body_loc, .. }) = &func_def.body else {
+                return Vec::new();
+            };
+
+            let issue_loc = Loc::default()
+                .with_start(func_def.loc.start())
+                .with_end(body_loc.start());
+
+            let mut findings = Vec::new();
+
+            let param_count = func_def.params.len();
+            if param_count > self.max_params {
+                findings.push(FindingData {
+                    detector_id: self.id(),
+                    location: loc_to_location(&issue_loc, file).with_message(format!(
+                        "{} parameters exceed the limit of {}",
+                        param_count, self.max_params
+                    )),
+                });
+            }
+
+            let depth = max_nesting_depth(body);
+            if depth > self.max_depth {
+                findings.push(FindingData {
+                    detector_id: self.id(),
+                    location: loc_to_location(&issue_loc, file).with_message(format!(
+                        "nesting depth {} exceeds the limit of {}",
+                        depth, self.max_depth
+                    )),
+                });
+            }
+
+            findings
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::run_detector_on_code;
+
+    const CODE: &str = r#"
+        pragma solidity ^0.8.0;
+
+        contract Test {
+            mapping(uint256 => bool) flags;
+
+            function manyParams(uint a, uint b, uint c, uint d, uint e, uint f, uint g, uint h) external {} // Positive: 8 params
+
+            function deep(uint256 x) external {                  // Positive: depth 5
+                for (uint256 i; i < x; i++) {
+                    if (flags[i]) {
+                        while (x > 10) {
+                            if (x % 2 == 0) {
+                                if (x % 3 == 0) {
+                                    x--;
+                                }
+                            }
+                            x--;
+                        }
+                    }
+                }
+            }
+
+            function normal(uint a, uint b) external returns (uint) { // Negative
+                if (a > b) {
+                    return a;
+                } else if (a == b) {
+                    return 0;
+                } else {
+                    return b;
+                }
+            }
+        }
+    "#;
+
+    #[test]
+    fn test_function_complexity_detector() {
+        let detector = Arc::new(FunctionComplexityDetector::default());
+        let locations = run_detector_on_code(detector, CODE, "complexity.sol");
+
+        assert_eq!(locations.len(), 2, "Should detect 2 issues");
+
+        assert_eq!(locations[0].line, 7);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some("8 parameters exceed the limit of 6")
+        );
+
+        assert_eq!(locations[1].line, 9);
+        assert_eq!(
+            locations[1].message.as_deref(),
+            Some("nesting depth 5 exceeds the limit of 4")
+        );
+    }
+
+    #[test]
+    fn test_function_complexity_threshold_override() {
+        let settings: DetectorSettings = toml::from_str("max_params = 1\nmax_depth = 5").unwrap();
+        let mut detector = FunctionComplexityDetector::default();
+        detector.configure(&settings);
+
+        let locations = run_detector_on_code(Arc::new(detector), CODE, "complexity.sol");
+
+        assert_eq!(locations.len(), 2, "Should detect 2 issues");
+        assert_eq!(locations[0].line, 7);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some("8 parameters exceed the limit of 1")
+        );
+        assert_eq!(locations[1].line, 24);
+        assert_eq!(
+            locations[1].message.as_deref(),
+            Some("2 parameters exceed the limit of 1")
+        );
+    }
+}
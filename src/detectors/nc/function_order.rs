@@ -1,11 +1,10 @@
 use crate::core::visitor::ASTVisitor;
 use crate::detectors::Detector;
 use crate::models::severity::Severity;
-use crate::models::FindingData;
-use crate::utils::location::loc_to_location;
-use solang_parser::pt::{
-    ContractPart, FunctionAttribute, FunctionDefinition, FunctionTy, Visibility,
+use crate::models::{
+    FindingData, FunctionInfo, FunctionMutability, FunctionType, FunctionVisibility,
 };
+use crate::utils::ast_utils::get_contract_info;
 use std::sync::Arc;
 
 #[derive(Debug, Default)]
@@ -26,7 +25,10 @@ impl Detector for FunctionOrderDetector {
 
     fn description(&self) -> &str {
         "According to the Solidity style guide, functions should be laid out in the following \
-         order: constructor, receive, fallback, external, public, internal, private."
+         order: constructor, receive, fallback, external, public, internal, private, with `view` \
+         and `pure` functions last within each visibility group. The first function declared \
+         before one it should follow is reported for each contract. Teams that don't follow the \
+         style guide can disable this check with `-x function-order` or `exclude_detectors`."
     }
 
     fn example(&self) -> Option<String> {
@@ -35,12 +37,14 @@ impl Detector for FunctionOrderDetector {
 // Bad
 contract Example {
     function foo() private {}
+    function total() external view returns (uint256) {}
     function bar() external {}
 }
 
 // Good
 contract Example {
     function bar() external {}
+    function total() external view returns (uint256) {}
     function foo() private {}
 }
 ```"#
@@ -50,67 +54,82 @@ contract Example {
 
     fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
         visitor.on_contract(move |contract_def, file, _context| {
-            let mut functions: Vec<(u8, &FunctionDefinition)> = Vec::new();
-
-            for part in &contract_def.parts {
-                if let ContractPart::FunctionDefinition(func) = part {
-                    let order = Self::get_function_order(func);
-                    functions.push((order, func));
-                }
-            }
-
-            let mut sorted_orders: Vec<u8> = functions.iter().map(|(o, _)| *o).collect();
-            sorted_orders.sort();
-
-            let mut findings = Vec::new();
-            for (i, (order, func)) in functions.iter().enumerate() {
-                if *order != sorted_orders[i] {
-                    let loc = func
-                        .name
-                        .as_ref()
-                        .map(|n| loc_to_location(&n.loc, file))
-                        .unwrap_or_else(|| loc_to_location(&func.loc, file));
-
-                    findings.push(FindingData {
-                        detector_id: self.id(),
-                        location: loc,
-                    });
+            let Some(contract_info) = get_contract_info(contract_def, file) else {
+                return Vec::new();
+            };
+            let functions = &contract_info.function_definitions;
+            let ranks: Vec<(u8, bool)> = functions.iter().map(Self::rank).collect();
+
+            // The first function with something declared after it that belongs before it
+            for (i, func) in functions.iter().enumerate() {
+                let Some(expected_after) = (i + 1..functions.len())
+                    .rev()
+                    .find(|&j| ranks[j] < ranks[i])
+                    .map(|j| &functions[j])
+                else {
+                    continue;
+                };
+
+                return FindingData {
+                    detector_id: self.id(),
+                    location: func.loc.clone().with_message(format!(
+                        "{} should be declared after {} (line {})",
+                        Self::describe(func),
+                        Self::describe(expected_after),
+                        expected_after.loc.line
+                    )),
                 }
+                .into();
             }
 
-            findings
+            Vec::new()
         });
     }
 }
 
 impl FunctionOrderDetector {
-    fn get_function_order(func: &FunctionDefinition) -> u8 {
-        // Order based on Solidity style guide:
-        // 1. constructor
-        // 2. receive
-        // 3. fallback
-        // 4. external
-        // 5. public
-        // 6. internal
-        // 7. private
-        match func.ty {
-            FunctionTy::Constructor => 1,
-            FunctionTy::Receive => 2,
-            FunctionTy::Fallback => 3,
-            FunctionTy::Function | FunctionTy::Modifier => {
-                // Get visibility
-                for attr in &func.attributes {
-                    if let FunctionAttribute::Visibility(vis) = attr {
-                        return match vis {
-                            Visibility::External(_) => 4,
-                            Visibility::Public(_) => 5,
-                            Visibility::Internal(_) => 6,
-                            Visibility::Private(_) => 7,
-                        };
-                    }
-                }
-                // Default to public if no visibility specified
-                5
+    /// Position in the style guide order: constructor, receive, fallback, external, public,
+    /// internal, private; within a visibility group, `view`/`pure` functions come last.
+    fn rank(func: &FunctionInfo) -> (u8, bool) {
+        let group = match func.function_type {
+            FunctionType::Constructor => 0,
+            FunctionType::Receive => 1,
+            FunctionType::Fallback => 2,
+            FunctionType::Function => match func.visibility {
+                FunctionVisibility::External => 3,
+                FunctionVisibility::Public => 4,
+                FunctionVisibility::Internal => 5,
+                FunctionVisibility::Private => 6,
+            },
+        };
+        (group, group > 2 && Self::is_read_only(func))
+    }
+
+    fn is_read_only(func: &FunctionInfo) -> bool {
+        matches!(
+            func.mutability,
+            FunctionMutability::View | FunctionMutability::Pure
+        )
+    }
+
+    fn describe(func: &FunctionInfo) -> String {
+        let visibility = match func.visibility {
+            FunctionVisibility::External => "external",
+            FunctionVisibility::Public => "public",
+            FunctionVisibility::Internal => "internal",
+            FunctionVisibility::Private => "private",
+        };
+        let mutability = match func.mutability {
+            FunctionMutability::View => " view",
+            FunctionMutability::Pure => " pure",
+            _ => "",
+        };
+        match func.function_type {
+            FunctionType::Constructor => "the constructor".to_string(),
+            FunctionType::Receive => "`receive`".to_string(),
+            FunctionType::Fallback => "`fallback`".to_string(),
+            FunctionType::Function => {
+                format!("{}{} function `{}`", visibility, mutability, func.name)
             }
         }
     }
@@ -136,13 +155,55 @@ mod tests {
         "#;
         let detector = Arc::new(FunctionOrderDetector::default());
         let locations = run_detector_on_code(detector, code, "test.sol");
-        assert_eq!(locations.len(), 6);
+        assert_eq!(
+            locations.len(),
+            1,
+            "Only the first out-of-order function is reported"
+        );
         assert_eq!(locations[0].line, 3, "privateFn");
-        assert_eq!(locations[1].line, 4, "internalFn");
-        assert_eq!(locations[2].line, 5, "publicFn");
-        assert_eq!(locations[3].line, 7, "fallback");
-        assert_eq!(locations[4].line, 8, "receive");
-        assert_eq!(locations[5].line, 9, "constructor");
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some("private function `privateFn` should be declared after the constructor (line 9)")
+        );
+    }
+
+    #[test]
+    fn test_detects_external_after_private() {
+        let code = r#"
+            contract Test {
+                function deposit() external {}
+                function _accrue() private {}
+                function withdraw() external {}
+            }
+        "#;
+        let detector = Arc::new(FunctionOrderDetector::default());
+        let locations = run_detector_on_code(detector, code, "test.sol");
+        assert_eq!(locations.len(), 1);
+        assert_eq!(locations[0].line, 4);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some("private function `_accrue` should be declared after external function `withdraw` (line 5)")
+        );
+    }
+
+    #[test]
+    fn test_detects_view_before_non_view() {
+        let code = r#"
+            contract Test {
+                function totalAssets() external view returns (uint256) {}
+                function deposit() external {}
+                function _price() internal pure returns (uint256) {}
+                function _accrue() internal {}
+            }
+        "#;
+        let detector = Arc::new(FunctionOrderDetector::default());
+        let locations = run_detector_on_code(detector, code, "test.sol");
+        assert_eq!(locations.len(), 1);
+        assert_eq!(locations[0].line, 3);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some("external view function `totalAssets` should be declared after external function `deposit` (line 4)")
+        );
     }
 
     #[test]
@@ -153,9 +214,12 @@ mod tests {
                 receive() external payable {}
                 fallback() external {}
                 function externalFn() external {}
+                function externalView() external view {}
                 function publicFn() public {}
+                function publicPure() public pure {}
                 function internalFn() internal {}
                 function privateFn() private {}
+                function privateView() private view {}
             }
         "#;
         let detector = Arc::new(FunctionOrderDetector::default());
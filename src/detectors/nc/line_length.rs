@@ -57,6 +57,9 @@ uint256 public reasonableVariableName;
                             line_end: None,
                             column_end: None,
                             snippet: None,
+                            message: None,
+                            url: None,
+                            context: None,
                         },
                     });
                 }
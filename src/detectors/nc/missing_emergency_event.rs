@@ -0,0 +1,284 @@
+use crate::config::DetectorSettings;
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::severity::Severity;
+use crate::models::{FindingData, SolidityFile};
+use crate::utils::ast_utils::{
+    collect_function_calls, find_statement_types, get_function_visibility, is_function_readonly,
+};
+use crate::utils::location::loc_to_location;
+use solang_parser::pt::{
+    ContractDefinition, ContractPart, ContractTy, FunctionDefinition, FunctionTy, Statement,
+    Visibility,
+};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+const DEFAULT_PATTERNS: &[&str] = &["pause", "emergency", "shutdown", "halt"];
+
+/// OpenZeppelin `Pausable` hooks, which emit `Paused` / `Unpaused` themselves.
+const PAUSABLE_HOOKS: &[&str] = &["_pause", "_unpause"];
+
+#[derive(Debug)]
+pub struct MissingEmergencyEventDetector {
+    patterns: Vec<String>,
+}
+
+impl Default for MissingEmergencyEventDetector {
+    fn default() -> Self {
+        Self {
+            patterns: DEFAULT_PATTERNS.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+}
+
+impl Detector for MissingEmergencyEventDetector {
+    fn id(&self) -> &'static str {
+        "missing-emergency-event"
+    }
+
+    fn name(&self) -> &str {
+        "Emergency function does not emit an event"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::NC
+    }
+
+    fn description(&self) -> &str {
+        "Pausing, halting or shutting down a protocol is exactly the kind of state change that \
+         monitoring and users need to react to, yet the function emits no event. Emit one such as \
+         `Paused(msg.sender)`, even when the function is restricted to an admin. Functions whose \
+         name contains one of the configured patterns (`pause`, `emergency`, `shutdown`, `halt` \
+         by default, case-insensitive) are checked; an event emitted by a helper in the same \
+         contract, or by an inherited `Pausable` `_pause` / `_unpause`, counts. The list is \
+         configurable via `patterns` under `[detectors.missing-emergency-event]`."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad
+function halt() external onlyOwner {
+    halted = true;
+}
+
+// Good
+event Halted(address indexed by);
+
+function halt() external onlyOwner {
+    halted = true;
+    emit Halted(msg.sender);
+}
+```"#
+                .to_string(),
+        )
+    }
+
+    fn configure(&mut self, settings: &DetectorSettings) {
+        if let Some(patterns) = settings.get_str_list(self.id(), "patterns") {
+            self.patterns = patterns.iter().map(|p| p.to_lowercase()).collect();
+        }
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_contract(move |contract_def, file, context| {
+            if matches!(contract_def.ty, ContractTy::Interface(_)) {
+                return Vec::new();
+            }
+            let inherits_pausable = context.contract_inherits_from(contract_def, file, "Pausable");
+
+            Self::functions(contract_def)
+                .filter(|func_def| self.is_emergency_function(func_def))
+                .filter(|func_def| {
+                    let mut visited = HashSet::new();
+                    !Self::emits(
+                        func_def,
+                        contract_def,
+                        file,
+                        inherits_pausable,
+                        &mut visited,
+                    )
+                })
+                .map(|func_def| {
+                    let name = func_def.name.as_ref().map_or("", |n| n.name.as_str());
+                    FindingData {
+                        detector_id: self.id(),
+                        location: loc_to_location(&func_def.loc, file)
+                            .with_message(format!("`{}` does not emit an event", name)),
+                    }
+                })
+                .collect()
+        });
+    }
+}
+
+impl MissingEmergencyEventDetector {
+    fn functions(contract_def: &ContractDefinition) -> impl Iterator<Item = &FunctionDefinition> {
+        contract_def.parts.iter().filter_map(|part| match part {
+            ContractPart::FunctionDefinition(func_def) => Some(func_def.as_ref()),
+            _ => None,
+        })
+    }
+
+    /// Externally callable, state-changing functions whose name matches a pattern.
+    /// Internal helpers are reached through their callers instead.
+    fn is_emergency_function(&self, func_def: &FunctionDefinition) -> bool {
+        if !matches!(func_def.ty, FunctionTy::Function) || func_def.body.is_none() {
+            return false;
+        }
+        if is_function_readonly(func_def)
+            || !matches!(
+                get_function_visibility(func_def),
+                Some(Visibility::External(_)) | Some(Visibility::Public(_))
+            )
+        {
+            return false;
+        }
+        let Some(name) = &func_def.name else {
+            return false;
+        };
+        let name = name.name.to_lowercase();
+        self.patterns
+            .iter()
+            .any(|pattern| name.contains(pattern.as_str()))
+    }
+
+    /// Whether the body emits, directly or through a function of the same contract it calls.
+    fn emits(
+        func_def: &FunctionDefinition,
+        contract_def: &ContractDefinition,
+        file: &SolidityFile,
+        inherits_pausable: bool,
+        visited: &mut HashSet<String>,
+    ) -> bool {
+        let Some(body) = &func_def.body else {
+            return false;
+        };
+        let mut emits = false;
+        find_statement_types(body, file, "", |stmt| {
+            emits |= matches!(stmt, Statement::Emit(..));
+            false
+        });
+        if emits {
+            return true;
+        }
+
+        let mut calls = HashSet::new();
+        collect_function_calls(body, &mut calls);
+        calls.into_iter().any(|call| {
+            if !visited.insert(call.clone()) {
+                return false;
+            }
+            let mut callees = Self::functions(contract_def)
+                .filter(|f| f.name.as_ref().is_some_and(|n| n.name == call))
+                .peekable();
+            if callees.peek().is_none() {
+                return inherits_pausable && PAUSABLE_HOOKS.contains(&call.as_str());
+            }
+            callees
+                .any(|callee| Self::emits(callee, contract_def, file, inherits_pausable, visited))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::run_detector_with_mock_inheritance;
+
+    #[test]
+    fn test_missing_emergency_event() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            abstract contract Pausable {
+                event Paused(address account);
+                function _pause() internal virtual;
+                function _unpause() internal virtual;
+            }
+
+            contract Vault is Pausable {
+                event Halted(address by);
+                bool halted;
+                bool stopped;
+
+                function pause() external {
+                    _pause();
+                }
+
+                function unpause() external {
+                    _unpause();
+                }
+
+                function halt() external {
+                    halted = true;
+                }
+
+                function shutdown() external {
+                    _stop();
+                }
+
+                function emergencyHalt() external {
+                    _halt();
+                }
+
+                function isPaused() external view returns (bool) {
+                    return halted;
+                }
+
+                function _halt() internal {
+                    halted = true;
+                    emit Halted(msg.sender);
+                }
+
+                function _stop() internal {
+                    stopped = true;
+                }
+            }
+
+            interface IVault {
+                function halt() external;
+            }
+        "#;
+        let detector = Arc::new(MissingEmergencyEventDetector::default());
+        let locations = run_detector_with_mock_inheritance(
+            detector,
+            code,
+            "vault.sol",
+            vec![("Vault", vec!["Pausable"])],
+        );
+
+        let lines: Vec<_> = locations.iter().map(|l| l.line).collect();
+        assert_eq!(lines, [23, 27]);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some("`halt` does not emit an event")
+        );
+    }
+
+    #[test]
+    fn test_missing_emergency_event_custom_patterns() {
+        let code = r#"
+            contract Market {
+                bool frozen;
+
+                function freeze() external {
+                    frozen = true;
+                }
+
+                function halt() external {
+                    frozen = true;
+                }
+            }
+        "#;
+        let settings: DetectorSettings = toml::from_str("patterns = [\"Freeze\"]").unwrap();
+        let mut detector = MissingEmergencyEventDetector::default();
+        detector.configure(&settings);
+        let locations =
+            run_detector_with_mock_inheritance(Arc::new(detector), code, "market.sol", vec![]);
+
+        let lines: Vec<_> = locations.iter().map(|l| l.line).collect();
+        assert_eq!(lines, [5]);
+    }
+}
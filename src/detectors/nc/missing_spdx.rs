@@ -53,6 +53,9 @@ pragma solidity ^0.8.0;
                         line_end: None,
                         column_end: None,
                         snippet: None,
+                        message: None,
+                        url: None,
+                        context: None,
                     },
                 }
                 .into();
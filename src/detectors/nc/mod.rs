@@ -16,6 +16,7 @@ pub mod custom_error_no_args;
 pub mod default_visibility;
 pub mod delete_instead_of_false;
 pub mod delete_instead_of_zero;
+pub mod deprecated_constructs;
 pub mod deprecated_safemath;
 pub mod draft_dependency;
 pub mod duplicate_require;
@@ -28,6 +29,7 @@ pub mod event_args_indexing;
 pub mod external_call_in_modifier;
 pub mod explicit_num_types;
 pub mod floating_pragma;
+pub mod function_complexity;
 pub mod function_length;
 pub mod function_order;
 pub mod hardcoded_address;
@@ -44,6 +46,7 @@ pub mod magic_numbers;
 pub mod many_function_params;
 pub mod many_return_values;
 pub mod mapping_style;
+pub mod missing_emergency_event;
 pub mod missing_error_message;
 pub mod missing_event_setter;
 pub mod missing_spdx;
@@ -55,6 +58,7 @@ pub mod multiple_libraries;
 pub mod named_function_args;
 pub mod named_mappings;
 pub mod named_returns;
+pub mod naming_convention;
 pub mod nonreentrant_before_modifiers;
 pub mod numeric_underscores;
 pub mod prefer_concat;
@@ -65,6 +69,7 @@ pub mod public_to_external;
 pub mod redundant_else;
 pub mod redundant_return;
 pub mod renounce_ownership;
+pub mod require_side_effects;
 pub mod scientific_notation;
 pub mod sensitive_terms;
 pub mod setter_event_old_value;
@@ -77,13 +82,17 @@ pub mod type_max_literal;
 pub mod type_max_value;
 pub mod underscore_prefix;
 pub mod unnamed_revert;
+pub mod unused_import;
 pub mod unused_override_params;
 pub mod unused_private_function;
+pub mod unused_state_variable;
 pub mod uppercase_non_constant;
 pub mod while_true_loop;
 pub mod zero_argument;
 pub mod zero_initialization;
 
+use crate::detectors::Detector;
+
 pub use abstract_in_separate_file::AbstractInSeparateFileDetector;
 pub use abi_encode_call::AbiEncodeCallDetector;
 pub use abicoder_v2::UnnecessaryAbiCoderV2Detector;
@@ -102,6 +111,7 @@ pub use custom_error_no_args::CustomErrorNoArgsDetector;
 pub use default_visibility::DefaultVisibilityDetector;
 pub use delete_instead_of_false::DeleteInsteadOfFalseDetector;
 pub use delete_instead_of_zero::DeleteInsteadOfZeroDetector;
+pub use deprecated_constructs::DeprecatedConstructsDetector;
 pub use deprecated_safemath::DeprecatedSafeMathDetector;
 pub use draft_dependency::DraftDependencyDetector;
 pub use duplicate_require::DuplicateRequireDetector;
@@ -114,6 +124,7 @@ pub use event_args_indexing::EventMissingIndexedArgsDetector;
 pub use external_call_in_modifier::ExternalCallInModifierDetector;
 pub use explicit_num_types::ExplicitNumTypesDetector;
 pub use floating_pragma::FloatingPragmaDetector;
+pub use function_complexity::FunctionComplexityDetector;
 pub use function_length::FunctionLengthDetector;
 pub use function_order::FunctionOrderDetector;
 pub use hardcoded_address::HardcodedAddressDetector;
@@ -130,6 +141,7 @@ pub use magic_numbers::MagicNumberDetector;
 pub use many_function_params::ManyFunctionParamsDetector;
 pub use many_return_values::ManyReturnValuesDetector;
 pub use mapping_style::MappingStyleDetector;
+pub use missing_emergency_event::MissingEmergencyEventDetector;
 pub use missing_error_message::MissingErrorMessageDetector;
 pub use missing_event_setter::MissingEventSetterDetector;
 pub use missing_spdx::MissingSpdxDetector;
@@ -141,6 +153,7 @@ pub use multiple_libraries::MultipleLibrariesDetector;
 pub use named_function_args::NamedFunctionArgsDetector;
 pub use named_mappings::NamedMappingsDetector;
 pub use named_returns::NamedReturnsDetector;
+pub use naming_convention::NamingConventionDetector;
 pub use nonreentrant_before_modifiers::NonReentrantBeforeModifiersDetector;
 pub use numeric_underscores::NumericUnderscoresDetector;
 pub use prefer_concat::PreferConcatDetector;
@@ -151,6 +164,7 @@ pub use public_to_external::PublicToExternalDetector;
 pub use redundant_else::RedundantElseDetector;
 pub use redundant_return::RedundantReturnDetector;
 pub use renounce_ownership::RenounceOwnershipDetector;
+pub use require_side_effects::RequireSideEffectsDetector;
 pub use scientific_notation::ScientificNotationDetector;
 pub use sensitive_terms::SensitiveTermsDetector;
 pub use setter_event_old_value::SetterEventOldValueDetector;
@@ -163,9 +177,110 @@ pub use type_max_literal::TypeMaxLiteralDetector;
 pub use type_max_value::TypeMaxValueDetector;
 pub use underscore_prefix::UnderscorePrefixDetector;
 pub use unnamed_revert::UnnamedRevertDetector;
+pub use unused_import::UnusedImportDetector;
 pub use unused_override_params::UnusedOverrideParamsDetector;
 pub use unused_private_function::UnusedPrivateFunctionDetector;
+pub use unused_state_variable::UnusedStateVariableDetector;
 pub use uppercase_non_constant::UppercaseNonConstantDetector;
 pub use while_true_loop::WhileTrueLoopDetector;
 pub use zero_argument::ZeroArgumentDetector;
 pub use zero_initialization::ZeroInitializationDetector;
+
+/// Every NC detector, in registration order. A detector exported above but missing
+/// here never runs, which `test_every_exported_detector_is_built_in` catches.
+pub fn all() -> Vec<Box<dyn Detector>> {
+    vec![
+        Box::new(AbiEncodeCallDetector::default()),
+        Box::new(AbstractInSeparateFileDetector::default()),
+        Box::new(ArrayIndicesDetector::default()),
+        Box::new(ArrayRangedGetterDetector::default()),
+        Box::new(BoolInitFalseDetector::default()),
+        Box::new(CombineMappingsDetector::default()),
+        Box::new(ComplexRequireDetector::default()),
+        Box::new(ConsoleLogImportDetector::default()),
+        Box::new(ConstantCaseDetector::default()),
+        Box::new(ConstantExpressionDetector::default()),
+        Box::new(ConstructorEmitEventDetector::default()),
+        Box::new(ContractLayoutDetector::default()),
+        Box::new(ControlStructureStyleDetector::default()),
+        Box::new(CustomErrorNoArgsDetector::default()),
+        Box::new(DefaultVisibilityDetector::default()),
+        Box::new(DeleteInsteadOfFalseDetector::default()),
+        Box::new(DeleteInsteadOfZeroDetector::default()),
+        Box::new(DeprecatedConstructsDetector::default()),
+        Box::new(DeprecatedSafeMathDetector::default()),
+        Box::new(DraftDependencyDetector::default()),
+        Box::new(DuplicateRequireDetector::default()),
+        Box::new(DuplicateStringLiteralDetector::default()),
+        Box::new(EcrecoverVCheckDetector::default()),
+        Box::new(EmptyBlocksDetector::default()),
+        Box::new(ErrorDefinitionNoArgsDetector::default()),
+        Box::new(EventArgsDetector::default()),
+        Box::new(EventMissingIndexedArgsDetector::default()),
+        Box::new(ExplicitNumTypesDetector::default()),
+        Box::new(ExternalCallInModifierDetector::default()),
+        Box::new(FloatingPragmaDetector::default()),
+        Box::new(FunctionComplexityDetector::default()),
+        Box::new(FunctionLengthDetector::default()),
+        Box::new(FunctionOrderDetector::default()),
+        Box::new(HardcodedAddressDetector::default()),
+        Box::new(InitialismCapitalizationDetector::default()),
+        Box::new(InitializerEmitEventDetector::default()),
+        Box::new(InterfaceInSeparateFileDetector::default()),
+        Box::new(InterfaceNamingDetector::default()),
+        Box::new(InterfacesContractsSameFileDetector::default()),
+        Box::new(LargeLiteralDetector::default()),
+        Box::new(LibraryInSeparateFileDetector::default()),
+        Box::new(LineLengthDetector::default()),
+        Box::new(LongCalculationsDetector::default()),
+        Box::new(MagicNumberDetector::default()),
+        Box::new(ManyFunctionParamsDetector::default()),
+        Box::new(ManyReturnValuesDetector::default()),
+        Box::new(MappingStyleDetector::default()),
+        Box::new(MissingEmergencyEventDetector::default()),
+        Box::new(MissingErrorMessageDetector::default()),
+        Box::new(MissingEventSetterDetector::default()),
+        Box::new(MissingSpdxDetector::default()),
+        Box::new(MixedIntUintStyleDetector::default()),
+        Box::new(MultipleAbstractContractsDetector::default()),
+        Box::new(MultipleContractsDetector::default()),
+        Box::new(MultipleInterfacesDetector::default()),
+        Box::new(MultipleLibrariesDetector::default()),
+        Box::new(NamedFunctionArgsDetector::default()),
+        Box::new(NamedMappingsDetector::default()),
+        Box::new(NamedReturnsDetector::default()),
+        Box::new(NamingConventionDetector::default()),
+        Box::new(NonReentrantBeforeModifiersDetector::default()),
+        Box::new(NumericUnderscoresDetector::default()),
+        Box::new(PreferConcatDetector::default()),
+        Box::new(PreferCustomErrorsDetector::default()),
+        Box::new(PreferModifierDetector::default()),
+        Box::new(PreferRequireDetector::default()),
+        Box::new(PublicToExternalDetector::default()),
+        Box::new(RedundantElseDetector::default()),
+        Box::new(RedundantReturnDetector::default()),
+        Box::new(RenounceOwnershipDetector::default()),
+        Box::new(RequireSideEffectsDetector::default()),
+        Box::new(ScientificNotationDetector::default()),
+        Box::new(SensitiveTermsDetector::default()),
+        Box::new(SetterEventOldValueDetector::default()),
+        Box::new(SetterNoCheckDetector::default()),
+        Box::new(StringQuotesDetector::default()),
+        Box::new(TimeUnitsDetector::default()),
+        Box::new(TodoLeftDetector::default()),
+        Box::new(TwoStepCriticalChangesDetector::default()),
+        Box::new(TypeMaxLiteralDetector::default()),
+        Box::new(TypeMaxValueDetector::default()),
+        Box::new(UnderscorePrefixDetector::default()),
+        Box::new(UnnamedRevertDetector::default()),
+        Box::new(UnnecessaryAbiCoderV2Detector::default()),
+        Box::new(UnusedImportDetector::default()),
+        Box::new(UnusedOverrideParamsDetector::default()),
+        Box::new(UnusedPrivateFunctionDetector::default()),
+        Box::new(UnusedStateVariableDetector::default()),
+        Box::new(UppercaseNonConstantDetector::default()),
+        Box::new(WhileTrueLoopDetector::default()),
+        Box::new(ZeroArgumentDetector::default()),
+        Box::new(ZeroInitializationDetector::default()),
+    ]
+}
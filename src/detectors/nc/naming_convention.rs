@@ -0,0 +1,254 @@
+use crate::config::DetectorSettings;
+use crate::core::context::AnalysisContext;
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::scope::{ContractType, FunctionInfo, FunctionType, FunctionVisibility};
+use crate::models::severity::Severity;
+use crate::models::{ContractInfo, FindingData};
+use crate::utils::ast_utils::get_contract_info;
+use std::sync::Arc;
+
+const DEFAULT_INTERNAL_PREFIX: &str = "_";
+
+#[derive(Debug)]
+pub struct NamingConventionDetector {
+    internal_prefix: String,
+}
+
+impl Default for NamingConventionDetector {
+    fn default() -> Self {
+        Self {
+            internal_prefix: DEFAULT_INTERNAL_PREFIX.to_string(),
+        }
+    }
+}
+
+impl Detector for NamingConventionDetector {
+    fn id(&self) -> &'static str {
+        "naming-convention"
+    }
+
+    fn name(&self) -> &str {
+        "Inconsistent `_` prefix between internal and public functions"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::NC
+    }
+
+    fn description(&self) -> &str {
+        "Internal and private functions should start with an underscore and public or external \
+         functions should not, so a function's reachability is visible at every call site. Mixing \
+         the two conventions slows down review. Overrides and functions whose name is declared by \
+         an inherited contract or interface are skipped. The prefix is configurable via \
+         `internal_prefix` under `[detectors.naming-convention]`; an empty prefix flags any \
+         underscore-prefixed function instead. On a line `underscore-prefix` also reports, only \
+         this finding is kept."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad
+function _deposit(uint256 amount) external {}
+function accrue() internal {}
+
+// Good
+function deposit(uint256 amount) external {}
+function _accrue() internal {}
+```"#
+                .to_string(),
+        )
+    }
+
+    fn configure(&mut self, settings: &DetectorSettings) {
+        if let Some(prefix) = settings.get_str(self.id(), "internal_prefix") {
+            self.internal_prefix = prefix.to_string();
+        }
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_contract(move |contract_def, file, context| {
+            let Some(contract_info) = get_contract_info(contract_def, file) else {
+                return Vec::new();
+            };
+            // Interfaces declare the external API; library internals are called as `Lib.fn()`
+            if matches!(
+                contract_info.contract_type,
+                ContractType::Interface | ContractType::Library
+            ) {
+                return Vec::new();
+            }
+
+            let mut findings = Vec::new();
+
+            for func_info in &contract_info.function_definitions {
+                if !matches!(func_info.function_type, FunctionType::Function)
+                    || func_info.is_override
+                    || Self::is_inherited_name(&func_info.name, contract_info, context)
+                {
+                    continue;
+                }
+
+                if let Some(message) = self.violation(func_info) {
+                    findings.push(FindingData {
+                        detector_id: self.id(),
+                        location: func_info.loc.clone().with_message(message),
+                    });
+                }
+            }
+
+            findings
+        });
+    }
+}
+
+impl NamingConventionDetector {
+    fn violation(&self, func_info: &FunctionInfo) -> Option<String> {
+        let name = &func_info.name;
+        let is_internal = matches!(
+            func_info.visibility,
+            FunctionVisibility::Internal | FunctionVisibility::Private
+        );
+
+        if self.internal_prefix.is_empty() {
+            return name
+                .starts_with('_')
+                .then(|| format!("`{}` should not start with `_`", name));
+        }
+
+        let has_prefix = name.starts_with(&self.internal_prefix);
+        if is_internal && !has_prefix {
+            Some(format!(
+                "{} function `{}` should start with `{}`",
+                Self::visibility_name(&func_info.visibility),
+                name,
+                self.internal_prefix
+            ))
+        } else if !is_internal && has_prefix {
+            Some(format!(
+                "{} function `{}` should not start with `{}`",
+                Self::visibility_name(&func_info.visibility),
+                name,
+                self.internal_prefix
+            ))
+        } else {
+            None
+        }
+    }
+
+    fn visibility_name(visibility: &FunctionVisibility) -> &'static str {
+        match visibility {
+            FunctionVisibility::Public => "public",
+            FunctionVisibility::External => "external",
+            FunctionVisibility::Internal => "internal",
+            FunctionVisibility::Private => "private",
+        }
+    }
+
+    /// Names declared by a base contract or interface are mandated by it, even without `override`.
+    fn is_inherited_name(
+        name: &str,
+        contract_info: &ContractInfo,
+        context: &AnalysisContext,
+    ) -> bool {
+        let qualified_name = context.get_qualified_name_for_contract(&contract_info.name);
+        let Some(chain) = context.get_inheritance_chain(&qualified_name) else {
+            return false;
+        };
+
+        chain
+            .iter()
+            .filter_map(|base| context.contracts.get(base))
+            .filter(|base| base.name != contract_info.name)
+            .any(|base| base.function_definitions.iter().any(|f| f.name == name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::{run_detector_on_code, run_detector_with_mock_inheritance};
+
+    const CODE: &str = r#"
+        pragma solidity ^0.8.0;
+
+        contract Vault {
+            function deposit() external {}                     // Negative
+            function _withdraw() external {}                   // Positive: external with prefix
+            function _accrue() internal {}                     // Negative
+            function accrue() private {}                       // Positive: private without prefix
+            function _sync() public {}                         // Positive: public with prefix
+            constructor() {}                                   // Negative: special function
+            receive() external payable {}                      // Negative: special function
+        }
+    "#;
+
+    #[test]
+    fn test_naming_convention_detector() {
+        let detector = Arc::new(NamingConventionDetector::default());
+        let locations = run_detector_on_code(detector, CODE, "vault.sol");
+
+        assert_eq!(locations.len(), 3, "Should detect 3 inconsistent names");
+        assert_eq!(locations[0].line, 6);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some("external function `_withdraw` should not start with `_`")
+        );
+        assert_eq!(locations[1].line, 8);
+        assert_eq!(
+            locations[1].message.as_deref(),
+            Some("private function `accrue` should start with `_`")
+        );
+        assert_eq!(locations[2].line, 9);
+    }
+
+    #[test]
+    fn test_naming_convention_empty_prefix() {
+        let settings: DetectorSettings = toml::from_str("internal_prefix = \"\"").unwrap();
+        let mut detector = NamingConventionDetector::default();
+        detector.configure(&settings);
+
+        let locations = run_detector_on_code(Arc::new(detector), CODE, "vault.sol");
+
+        let lines: Vec<_> = locations.iter().map(|l| l.line).collect();
+        assert_eq!(lines, [6, 7, 9]);
+        assert_eq!(
+            locations[1].message.as_deref(),
+            Some("`_accrue` should not start with `_`")
+        );
+    }
+
+    #[test]
+    fn test_naming_convention_skips_inherited_names() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            interface IHooks {
+                function _beforeSwap() external;
+            }
+
+            abstract contract Base {
+                function _afterSwap() public virtual;
+            }
+
+            contract Hooks is IHooks, Base {
+                function _beforeSwap() external {}              // Negative: mandated by IHooks
+                function _afterSwap() public override {}        // Negative: override
+                function _unrelated() external {}               // Positive
+            }
+        "#;
+
+        let detector = Arc::new(NamingConventionDetector::default());
+        let locations = run_detector_with_mock_inheritance(
+            detector,
+            code,
+            "hooks.sol",
+            vec![("Hooks", vec!["hooks.sol:IHooks", "hooks.sol:Base"])],
+        );
+
+        // Base._afterSwap is itself public with a prefix
+        let lines: Vec<_> = locations.iter().map(|l| l.line).collect();
+        assert_eq!(lines, [9, 15]);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::location::loc_to_location;
 use crate::{core::visitor::ASTVisitor, models::FindingData};
@@ -21,6 +22,10 @@ impl Detector for RenounceOwnershipDetector {
         Severity::NC
     }
 
+    fn category(&self) -> Category {
+        Category::AccessControl
+    }
+
     fn description(&self) -> &str {
         "Leaving `renounceOwnership()` enabled on Ownable/Ownable2Step contracts without a specific plan to use it introduces risk. If renouncing ownership is not intended, consider overriding the function to disable it (e.g., by reverting)."
     }
@@ -0,0 +1,256 @@
+use crate::core::context::AnalysisContext;
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::severity::Severity;
+use crate::models::{FindingData, FunctionMutability, SolidityFile};
+use crate::utils::ast_utils::{find_locations_in_expression, is_external_call};
+use crate::utils::location::loc_to_location;
+use solang_parser::pt::{Expression, Loc, Statement};
+use std::sync::Arc;
+
+/// Token calls wrapped in `require` are covered by the ERC20 detectors.
+const TOKEN_METHODS: &[&str] = &[
+    "transfer",
+    "transferFrom",
+    "approve",
+    "increaseAllowance",
+    "decreaseAllowance",
+    "safeTransfer",
+    "safeTransferFrom",
+    "safeApprove",
+    "permit",
+    "send",
+];
+
+/// Getters commonly used in conditions when the target interface is not in scope.
+const VIEW_METHODS: &[&str] = &[
+    "balanceOf",
+    "allowance",
+    "totalSupply",
+    "decimals",
+    "ownerOf",
+    "owner",
+    "paused",
+    "supportsInterface",
+    "latestRoundData",
+    "latestAnswer",
+    "getReserves",
+];
+
+const VIEW_PREFIXES: &[&str] = &["get", "is", "has", "preview", "quote"];
+
+#[derive(Debug, Default)]
+pub struct RequireSideEffectsDetector;
+
+impl Detector for RequireSideEffectsDetector {
+    fn id(&self) -> &'static str {
+        "require-side-effects"
+    }
+
+    fn name(&self) -> &str {
+        "`require`/`assert` condition has side effects"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::NC
+    }
+
+    fn description(&self) -> &str {
+        "The condition of a `require` or `assert` contains an assignment, an increment/decrement \
+         or a state-changing external call. Hiding state changes inside assertions makes the code \
+         harder to review, since readers expect conditions to be pure checks. Perform the state \
+         change in its own statement and check the result afterwards."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad
+require(oracle.update(), "update failed");
+require(nonce++ < maxNonce);
+
+// Good
+bool updated = oracle.update();
+require(updated, "update failed");
+require(nonce < maxNonce);
+nonce++;
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_statement(move |stmt, file, context| {
+            let Statement::Expression(loc, Expression::FunctionCall(_, func, args)) = stmt else {
+                return Vec::new();
+            };
+            let Expression::Variable(id) = func.as_ref() else {
+                return Vec::new();
+            };
+            if id.name != "require" && id.name != "assert" {
+                return Vec::new();
+            }
+            let Some(condition) = args.first() else {
+                return Vec::new();
+            };
+
+            let Some(side_effect) = Self::find_side_effect(condition, file, context) else {
+                return Vec::new();
+            };
+
+            FindingData {
+                detector_id: self.id(),
+                location: loc_to_location(loc, file)
+                    .with_message(format!("{} in `{}` condition", side_effect, id.name)),
+            }
+            .into()
+        });
+    }
+}
+
+impl RequireSideEffectsDetector {
+    fn find_side_effect(
+        condition: &Expression,
+        file: &SolidityFile,
+        context: &AnalysisContext,
+    ) -> Option<String> {
+        let mut side_effects = Vec::new();
+        let mut predicate = |expr: &Expression, _: &SolidityFile| -> Option<Loc> {
+            let (loc, side_effect) = match expr {
+                Expression::PreIncrement(loc, _)
+                | Expression::PostIncrement(loc, _)
+                | Expression::PreDecrement(loc, _)
+                | Expression::PostDecrement(loc, _) => (*loc, "increment/decrement".to_string()),
+                Expression::Assign(loc, _, _)
+                | Expression::AssignAdd(loc, _, _)
+                | Expression::AssignSubtract(loc, _, _)
+                | Expression::AssignMultiply(loc, _, _)
+                | Expression::AssignDivide(loc, _, _)
+                | Expression::AssignModulo(loc, _, _)
+                | Expression::AssignOr(loc, _, _)
+                | Expression::AssignAnd(loc, _, _)
+                | Expression::AssignXor(loc, _, _)
+                | Expression::AssignShiftLeft(loc, _, _)
+                | Expression::AssignShiftRight(loc, _, _) => (*loc, "assignment".to_string()),
+                Expression::Delete(loc, _) => (*loc, "`delete`".to_string()),
+                Expression::FunctionCall(loc, func, _) if is_external_call(expr) => {
+                    let Expression::MemberAccess(_, _, member) = func.as_ref() else {
+                        return None;
+                    };
+                    if Self::is_read_only_or_token_call(&member.name, context) {
+                        return None;
+                    }
+                    (*loc, format!("state-changing call `{}()`", member.name))
+                }
+                _ => return None,
+            };
+            side_effects.push(side_effect);
+            Some(loc)
+        };
+
+        let mut locations = Vec::new();
+        find_locations_in_expression(condition, file, &mut predicate, &mut locations);
+        side_effects.into_iter().next()
+    }
+
+    fn is_read_only_or_token_call(name: &str, context: &AnalysisContext) -> bool {
+        if TOKEN_METHODS.contains(&name) || VIEW_METHODS.contains(&name) {
+            return true;
+        }
+
+        // Resolve against every function with this name in scope
+        let mut mutabilities = context
+            .contracts
+            .values()
+            .flat_map(|c| c.function_definitions.iter())
+            .filter(|f| f.name == name)
+            .map(|f| &f.mutability)
+            .peekable();
+        if mutabilities.peek().is_some() {
+            return mutabilities
+                .all(|m| matches!(m, FunctionMutability::View | FunctionMutability::Pure));
+        }
+
+        VIEW_PREFIXES.iter().any(|prefix| {
+            name.strip_prefix(prefix)
+                .and_then(|rest| rest.chars().next())
+                .is_some_and(|c| c.is_ascii_uppercase())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detectors::medium::UnsafeErc20OperationsDetector;
+    use crate::utils::test_utils::run_detector_on_code;
+
+    const CODE: &str = r#"
+        pragma solidity ^0.8.0;
+
+        interface IOracle {
+            function update() external returns (bool);
+            function fresh() external view returns (bool);
+        }
+
+        contract Test {
+            IOracle oracle;
+            uint256 counter;
+
+            function run(uint256 n, uint256 amt, address token, address to) external {
+                require(counter++ < n);                                 // Positive: increment
+                require(address(this).balance >= amt);                  // Negative
+                require(oracle.update(), "stale");                      // Positive: external call
+                require(oracle.fresh());                                // Negative: view in scope
+                assert((counter = n) > 0);                              // Positive: assignment
+                require(oracle.getPrice() > 0);                         // Negative: getter name
+                require(IERC20(token).transferFrom(msg.sender, to, amt)); // Negative: token call
+            }
+        }
+    "#;
+
+    #[test]
+    fn test_require_side_effects_detector() {
+        let detector = Arc::new(RequireSideEffectsDetector::default());
+        let locations = run_detector_on_code(detector, CODE, "require.sol");
+
+        assert_eq!(
+            locations.len(),
+            3,
+            "Should detect 3 side-effectful conditions"
+        );
+        assert_eq!(locations[0].line, 14);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some("increment/decrement in `require` condition")
+        );
+        assert_eq!(locations[1].line, 16);
+        assert_eq!(
+            locations[1].message.as_deref(),
+            Some("state-changing call `update()` in `require` condition")
+        );
+        assert_eq!(locations[2].line, 18);
+        assert_eq!(
+            locations[2].message.as_deref(),
+            Some("assignment in `assert` condition")
+        );
+    }
+
+    #[test]
+    fn test_disjoint_from_erc20_detectors() {
+        let ours = run_detector_on_code(
+            Arc::new(RequireSideEffectsDetector::default()),
+            CODE,
+            "require.sol",
+        );
+        let erc20 = run_detector_on_code(
+            Arc::new(UnsafeErc20OperationsDetector::default()),
+            CODE,
+            "require.sol",
+        );
+
+        assert_eq!(erc20.len(), 1);
+        assert_eq!(erc20[0].line, 20);
+        assert!(ours.iter().all(|ours| ours.line != erc20[0].line));
+    }
+}
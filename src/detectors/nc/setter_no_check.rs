@@ -1,5 +1,6 @@
 use crate::core::visitor::ASTVisitor;
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::models::FindingData;
 use crate::utils::ast_utils::{find_in_statement, find_statement_types};
@@ -23,6 +24,10 @@ impl Detector for SetterNoCheckDetector {
         Severity::NC
     }
 
+    fn category(&self) -> Category {
+        Category::Validation
+    }
+
     fn description(&self) -> &str {
         "Setter functions should include validation checks such as sanity checks (e.g., checks \
          against zero values) or authorization checks. Using require, revert, or if statements \
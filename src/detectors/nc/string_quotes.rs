@@ -67,6 +67,9 @@ import "./Base.sol";
                             line_end: None,
                             column_end: None,
                             snippet: None,
+                            message: None,
+                            url: None,
+                            context: None,
                         },
                     });
                 }
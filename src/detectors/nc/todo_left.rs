@@ -57,6 +57,9 @@ function validate() external {
                             line_end: None,
                             column_end: None,
                             snippet: None,
+                            message: None,
+                            url: None,
+                            context: None,
                         },
                     });
                 }
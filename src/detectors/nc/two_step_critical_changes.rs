@@ -1,4 +1,5 @@
 use crate::detectors::Detector;
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use crate::utils::location::loc_to_location;
 use crate::{core::visitor::ASTVisitor, models::FindingData};
@@ -27,6 +28,10 @@ impl Detector for TwoStepCriticalChangesDetector {
         Severity::NC // Can be Low/Medium depending on context, but NC is safe baseline
     }
 
+    fn category(&self) -> Category {
+        Category::AccessControl
+    }
+
     fn description(&self) -> &str {
         "Functions that change critical addresses like owner or admin in a single step are prone to errors (e.g., setting the wrong address). Consider implementing a two-step process (e.g., propose/accept) for safer changes."
     }
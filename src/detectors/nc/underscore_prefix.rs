@@ -22,9 +22,15 @@ impl Detector for UnderscorePrefixDetector {
         Severity::NC
     }
 
+    fn version(&self) -> u32 {
+        2
+    }
+
     fn description(&self) -> &str {
         "According to the Solidity Style Guide, non-external variable and function names should \
-         begin with an underscore to clearly indicate their visibility scope."
+         begin with an underscore to clearly indicate their visibility scope. `naming-convention` \
+         also checks function names, with a configurable prefix; on a line both report, only its \
+         finding is kept."
     }
 
     fn example(&self) -> Option<String> {
@@ -0,0 +1,314 @@
+use crate::core::context::AnalysisContext;
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::severity::Severity;
+use crate::models::FindingData;
+use crate::utils::ast_utils::source_identifiers;
+use crate::utils::location::loc_to_location;
+use solang_parser::pt::{Import, ImportPath, SourceUnitPart};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+#[derive(Debug, Default)]
+pub struct UnusedImportDetector;
+
+impl Detector for UnusedImportDetector {
+    fn id(&self) -> &'static str {
+        "unused-import"
+    }
+
+    fn name(&self) -> &str {
+        "Unused imports can be removed"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::NC
+    }
+
+    fn description(&self) -> &str {
+        "Imported symbols that the file never uses add noise and compile time, and hide which \
+         dependencies the file really has. Named imports (`import {A, B as C} from ...`) and \
+         `import ... as X` are checked symbol by symbol; a plain `import \"File.sol\";` is \
+         flagged when nothing the imported file declares or imports is used. Files that only \
+         import (re-export hubs) are skipped."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad - SafeCast is never used
+import {IERC20} from "./interfaces/IERC20.sol";
+import {SafeCast} from "./libraries/SafeCast.sol";
+
+// Good
+import {IERC20} from "./interfaces/IERC20.sol";
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_source_unit(move |source_unit, file, context| {
+            let imports: Vec<_> = source_unit
+                .0
+                .iter()
+                .filter_map(|part| match part {
+                    SourceUnitPart::ImportDirective(import) => Some(import),
+                    _ => None,
+                })
+                .collect();
+            // Files made only of pragmas and imports are re-export hubs
+            let declares_anything = source_unit.0.iter().any(|part| {
+                !matches!(
+                    part,
+                    SourceUnitPart::PragmaDirective(_) | SourceUnitPart::ImportDirective(_)
+                )
+            });
+            if imports.is_empty() || !declares_anything {
+                return Vec::new();
+            }
+
+            // Identifiers outside the import directives themselves
+            let import_spans: Vec<(usize, usize)> = imports
+                .iter()
+                .map(|import| {
+                    let loc = match import {
+                        Import::Plain(_, loc)
+                        | Import::GlobalSymbol(_, _, loc)
+                        | Import::Rename(_, _, loc) => loc,
+                    };
+                    (loc.start(), loc.end())
+                })
+                .collect();
+            let used: HashSet<&str> = source_identifiers(&file.content)
+                .into_iter()
+                .filter(|(offset, _)| {
+                    !import_spans
+                        .iter()
+                        .any(|(start, end)| (*start..*end).contains(offset))
+                })
+                .map(|(_, name)| name)
+                .collect();
+
+            let mut findings = Vec::new();
+            let mut report = |loc, message: String| {
+                findings.push(FindingData {
+                    detector_id: self.id(),
+                    location: loc_to_location(loc, file).with_message(message),
+                })
+            };
+            for import in imports {
+                match import {
+                    Import::Rename(path, symbols, _) => {
+                        for (symbol, alias) in symbols {
+                            let local = alias.as_ref().unwrap_or(symbol);
+                            if !used.contains(local.name.as_str()) {
+                                report(
+                                    &local.loc,
+                                    format!(
+                                        "`{}` is imported from `{}` but never used",
+                                        local.name,
+                                        import_path(path)
+                                    ),
+                                );
+                            }
+                        }
+                    }
+                    Import::GlobalSymbol(path, symbol, _) => {
+                        if !used.contains(symbol.name.as_str()) {
+                            report(
+                                &symbol.loc,
+                                format!(
+                                    "`{}` is imported from `{}` but never used",
+                                    symbol.name,
+                                    import_path(path)
+                                ),
+                            );
+                        }
+                    }
+                    Import::Plain(path, loc) => {
+                        let Some(exported) = Self::exported_names(
+                            &import_path(path),
+                            &file.path,
+                            context,
+                            &mut HashSet::new(),
+                        ) else {
+                            continue;
+                        };
+                        if !exported.iter().any(|name| used.contains(name.as_str())) {
+                            report(
+                                loc,
+                                format!("Nothing imported from `{}` is used", import_path(path)),
+                            );
+                        }
+                    }
+                }
+            }
+            findings
+        });
+    }
+}
+
+impl UnusedImportDetector {
+    /// Names a plain import of `path` brings into scope: what the file declares at the top
+    /// level and, transitively, what it imports. `None` if the import does not resolve to a
+    /// loaded file.
+    fn exported_names(
+        path: &str,
+        importing_file: &Path,
+        context: &AnalysisContext,
+        visited: &mut HashSet<PathBuf>,
+    ) -> Option<HashSet<String>> {
+        let resolved = context
+            .get_import_resolver()?
+            .resolve_import(path, importing_file)
+            .ok()?;
+        let imported = context.get_file_by_path(&resolved)?;
+        let mut names = HashSet::new();
+        if !visited.insert(resolved.clone()) {
+            return Some(names);
+        }
+
+        names.extend(imported.contract_definitions.iter().map(|c| c.name.clone()));
+        names.extend(imported.structs.iter().map(|s| s.name.clone()));
+        names.extend(imported.enums.iter().map(|e| e.name.clone()));
+        names.extend(imported.errors.iter().map(|e| e.name.clone()));
+        names.extend(imported.events.iter().map(|e| e.name.clone()));
+        names.extend(imported.type_definitions.iter().map(|t| t.name.clone()));
+        names.extend(imported.functions.iter().map(|f| f.name.clone()));
+        names.extend(imported.variables.iter().map(|v| v.name.clone()));
+        for import in &imported.imports {
+            if import.symbols.is_empty() {
+                names.extend(Self::exported_names(
+                    &import.import_path,
+                    &resolved,
+                    context,
+                    visited,
+                )?);
+            } else {
+                names.extend(import.symbols.iter().cloned());
+            }
+        }
+        Some(names)
+    }
+}
+
+fn import_path(path: &ImportPath) -> String {
+    match path {
+        ImportPath::Filename(literal) => literal.string.clone(),
+        ImportPath::Path(path) => path.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::core::engine::AnalysisEngine;
+    use crate::utils::test_utils::{run_detector_on_code, write_fixture_project};
+
+    #[test]
+    fn test_unused_named_imports() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            import {IERC20, IERC721} from "./interfaces/Tokens.sol";
+            import {SafeCast as Cast, Math as M} from "./libraries/Math.sol";
+            import "./Oracle.sol" as Oracles;
+            import * as Errors from "./Errors.sol";
+            import {IVault} from "./IVault.sol";
+
+            contract Vault is IVault {
+                IERC20 public token;
+
+                /// @inheritdoc IVault
+                function deposit(uint256 amount) external {
+                    uint256 shares = M.mulDiv(amount, 1, 2);
+                    // IERC721 and Cast only appear in comments and strings
+                    require(shares > 0, "IERC721");
+                    revert Errors.Failed();
+                }
+            }
+        "#;
+        let detector = Arc::new(UnusedImportDetector::default());
+        let locations = run_detector_on_code(detector, code, "vault.sol");
+
+        let found: Vec<_> = locations
+            .iter()
+            .map(|l| (l.line, l.message.as_deref().unwrap_or_default()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (
+                    4,
+                    "`IERC721` is imported from `./interfaces/Tokens.sol` but never used"
+                ),
+                (
+                    5,
+                    "`Cast` is imported from `./libraries/Math.sol` but never used"
+                ),
+                (
+                    6,
+                    "`Oracles` is imported from `./Oracle.sol` but never used"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unused_plain_imports() {
+        let root = write_fixture_project(
+            "unused-plain-import",
+            &[
+                ("foundry.toml", "[profile.default]\n"),
+                (
+                    "src/Vault.sol",
+                    "pragma solidity ^0.8.0;\n\
+                     import \"./Base.sol\";\n\
+                     import \"./Errors.sol\";\n\
+                     import \"./Unused.sol\";\n\
+                     contract Vault is Base {\n\
+                     function f() external pure { revert Failed(); }\n\
+                     }\n",
+                ),
+                (
+                    "src/Base.sol",
+                    "pragma solidity ^0.8.0;\ncontract Base {}\n",
+                ),
+                (
+                    "src/Errors.sol",
+                    "pragma solidity ^0.8.0;\nimport \"./Shared.sol\";\n",
+                ),
+                (
+                    "src/Shared.sol",
+                    "pragma solidity ^0.8.0;\nerror Failed();\n",
+                ),
+                (
+                    "src/Unused.sol",
+                    "pragma solidity ^0.8.0;\nlibrary Helpers {}\n",
+                ),
+            ],
+        );
+        let config = Config {
+            scope: vec![root.join("src")],
+            ..Config::default()
+        };
+        let mut engine = AnalysisEngine::new(&config);
+        engine
+            .register_detector(Arc::new(UnusedImportDetector::default()))
+            .unwrap();
+        let report = engine.analyze().expect("Analysis failed");
+
+        let locations: Vec<_> = report.findings.iter().flat_map(|f| &f.locations).collect();
+        assert_eq!(locations.len(), 1, "{:?}", locations);
+        assert_eq!(locations[0].line, 4);
+        assert_eq!(
+            locations[0].message.as_deref(),
+            Some("Nothing imported from `./Unused.sol` is used")
+        );
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,224 @@
+use crate::core::context::AnalysisContext;
+use crate::core::visitor::ASTVisitor;
+use crate::detectors::Detector;
+use crate::models::scope::VariableVisibility;
+use crate::models::severity::Severity;
+use crate::models::{FindingData, SolidityFile};
+use crate::utils::ast_utils::{get_contract_info, source_identifiers};
+use solang_parser::pt::{ContractDefinition, ContractPart, ContractTy, SourceUnitPart};
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
+
+#[derive(Debug, Default)]
+pub struct UnusedStateVariableDetector;
+
+impl Detector for UnusedStateVariableDetector {
+    fn id(&self) -> &'static str {
+        "unused-state-variable"
+    }
+
+    fn name(&self) -> &str {
+        "Unused state variables can be removed"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::NC
+    }
+
+    fn description(&self) -> &str {
+        "Private and internal state variables that are never referenced by the contract (or, \
+         for internal ones, by the contracts deriving from it) are dead code. Remove them, or \
+         check whether the logic that was meant to use them is missing. Public variables are \
+         skipped since their getters are part of the interface."
+    }
+
+    fn example(&self) -> Option<String> {
+        Some(
+            r#"```solidity
+// Bad - `lastUpdate` is never used
+contract Example {
+    uint256 private lastUpdate;
+    uint256 public total;
+
+    function add(uint256 amount) external {
+        total += amount;
+    }
+}
+
+// Good
+contract Example {
+    uint256 public total;
+
+    function add(uint256 amount) external {
+        total += amount;
+    }
+}
+```"#
+                .to_string(),
+        )
+    }
+
+    fn register_callbacks(self: Arc<Self>, visitor: &mut ASTVisitor) {
+        visitor.on_contract(move |contract_def, file, context| {
+            if matches!(contract_def.ty, ContractTy::Interface(_)) {
+                return Vec::new();
+            }
+            let Some(contract_info) = get_contract_info(contract_def, file) else {
+                return Vec::new();
+            };
+
+            let candidates: Vec<_> = contract_info
+                .state_variables
+                .iter()
+                .filter(|v| v.visibility != VariableVisibility::Public)
+                .collect();
+            if candidates.is_empty() {
+                return Vec::new();
+            }
+
+            // Identifiers in the contract, apart from the declared names themselves
+            let declarations: HashSet<usize> = contract_def
+                .parts
+                .iter()
+                .filter_map(|part| match part {
+                    ContractPart::VariableDefinition(var) => var.name.as_ref(),
+                    _ => None,
+                })
+                .map(|name| name.loc.start())
+                .collect();
+            let (start, end) = (contract_def.loc.start(), contract_def.loc.end());
+            let used: HashSet<&str> = source_identifiers(&file.content[start..end])
+                .into_iter()
+                .filter(|(offset, _)| !declarations.contains(&(start + offset)))
+                .map(|(_, name)| name)
+                .collect();
+
+            let mut derived_uses = None;
+            let mut findings = Vec::new();
+            for var in candidates {
+                if used.contains(var.name.as_str()) {
+                    continue;
+                }
+                if var.visibility != VariableVisibility::Private {
+                    let derived = derived_uses.get_or_insert_with(|| {
+                        Self::derived_identifiers(contract_def, file, context)
+                    });
+                    match derived {
+                        Some(names) if !names.contains(&var.name) => {}
+                        _ => continue,
+                    }
+                }
+                findings.push(FindingData {
+                    detector_id: self.id(),
+                    location: var.loc.clone(),
+                });
+            }
+            findings
+        });
+    }
+}
+
+impl UnusedStateVariableDetector {
+    /// Identifiers in the contracts deriving from this one. `None` when internal variables
+    /// may be used out of sight: the contract is abstract with no derived contract in scope,
+    /// or a derived contract's source is not available.
+    fn derived_identifiers(
+        contract_def: &ContractDefinition,
+        file: &SolidityFile,
+        context: &AnalysisContext,
+    ) -> Option<HashSet<String>> {
+        let name = contract_def.name.as_ref()?;
+        let qualified_name = context.get_qualified_name_for_contract(&name.name);
+        let derived: Vec<_> = context
+            .contracts
+            .iter()
+            .filter(|(name, info)| {
+                name.as_str() != qualified_name && info.inheritance_chain.contains(&qualified_name)
+            })
+            .map(|(_, info)| info)
+            .collect();
+        if derived.is_empty() && matches!(contract_def.ty, ContractTy::Abstract(_)) {
+            return None;
+        }
+
+        let mut names = HashSet::new();
+        for info in derived {
+            let derived_file = if Path::new(&info.file_path) == file.path {
+                file
+            } else {
+                context.get_file_by_path(Path::new(&info.file_path))?
+            };
+            let derived_def = derived_file
+                .source_unit
+                .0
+                .iter()
+                .find_map(|part| match part {
+                    SourceUnitPart::ContractDefinition(c)
+                        if c.name.as_ref().is_some_and(|n| n.name == info.name) =>
+                    {
+                        Some(c)
+                    }
+                    _ => None,
+                })?;
+            let source = &derived_file.content[derived_def.loc.start()..derived_def.loc.end()];
+            names.extend(
+                source_identifiers(source)
+                    .into_iter()
+                    .map(|(_, name)| name.to_string()),
+            );
+        }
+        Some(names)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::run_detector_on_code;
+
+    #[test]
+    fn test_unused_state_variable() {
+        let code = r#"
+            pragma solidity ^0.8.0;
+
+            contract Base {
+                uint256 private lastUpdate;                 // Positive
+                uint256 internal fee;
+                uint256 internal unusedInternal;            // Positive
+                uint256 public total;                       // Negative: public getter
+                uint256 private constant MAX = 100;
+                uint256 private cap = MAX * 2;              // Positive
+                address private owner;
+                bytes32 private slotted;
+
+                modifier onlyOwner() {
+                    require(msg.sender == owner);
+                    _;
+                }
+
+                function slot() external pure returns (bytes32 s) {
+                    assembly { s := slotted.slot }
+                }
+            }
+
+            contract Vault is Base {
+                // total is a local here and the string mentions lastUpdate
+                function charge(uint256 total) external onlyOwner returns (string memory) {
+                    total = total * fee;
+                    return "lastUpdate";
+                }
+            }
+
+            abstract contract Module {
+                uint256 internal shared;                    // Negative: no derived contract in scope
+                uint256 private hidden;                     // Positive
+            }
+        "#;
+        let detector = Arc::new(UnusedStateVariableDetector::default());
+        let locations = run_detector_on_code(detector, code, "vault.sol");
+
+        let lines: Vec<_> = locations.iter().map(|l| l.line).collect();
+        assert_eq!(lines, [5, 7, 10, 34], "{:?}", locations);
+    }
+}
@@ -0,0 +1,20 @@
+//! Static analysis for Solidity.
+//!
+//! Besides the `weasel` binary, the crate can be used as a library: build a [`Config`], register
+//! detectors on an [`AnalysisEngine`] and call [`AnalysisEngine::analyze`] to get a [`Report`].
+//! See `examples/analyze_directory.rs`.
+
+pub mod cli;
+pub mod config;
+pub mod core;
+pub mod detectors;
+pub mod models;
+pub mod output;
+pub mod utils;
+
+pub use crate::config::{load_config, Config, ConfigOverrides};
+pub use crate::core::engine::AnalysisEngine;
+pub use crate::core::error::WeaselError;
+pub use crate::core::snippet::{analyze_snippet, FindingInstance};
+pub use crate::detectors::Detector;
+pub use crate::models::{Finding, Report, Severity};
@@ -1,97 +1,56 @@
-mod cli;
-mod config;
-mod core;
-mod detectors;
-mod models;
-mod output;
-mod utils;
-
-use clap::{Parser, Subcommand};
-use std::path::PathBuf;
-
-#[derive(Parser)]
-#[command(name = "weasel")]
-#[command(about = "Smart Contract Static Analysis Tool for Solidity")]
-#[command(version = core::version())]
-struct Cli {
-    #[command(subcommand)]
-    command: Commands,
-}
-
-#[derive(Subcommand)]
-enum Commands {
-    Init,
-    Run {
-        #[arg(short, long)]
-        scope: Option<Vec<PathBuf>>,
-
-        #[arg(short, long)]
-        exclude: Option<Vec<PathBuf>>,
-
-        #[arg(short, long)]
-        min_severity: Option<String>,
-
-        #[arg(short, long)]
-        format: Option<String>,
-
-        #[arg(short, long, value_name = "REPORT_FILE_NAME")]
-        output: Option<PathBuf>,
-
-        #[arg(short, long, value_name = "PATH_TO_CONFIG")]
-        config: Option<PathBuf>,
-
-        #[arg(short, long)]
-        remappings: Option<Vec<String>>,
-
-        #[arg(short = 'x', long, value_name = "DETECTOR_ID")]
-        exclude_detectors: Option<Vec<String>>,
-    },
-    Detectors {
-        #[arg(short, long)]
-        severity: Option<String>,
-
-        #[arg(short, long)]
-        details: Option<String>,
-    },
-    Mcp {
-        #[command(subcommand)]
-        command: cli::mcp::McpCommands,
-    },
-}
+use clap::{CommandFactory, FromArgMatches};
+use weasel::cli::{self, Cli, Commands};
+use weasel::output::style;
 
 fn main() {
-    let cli = Cli::parse();
+    // Decided before parsing so clap's own help and error messages are plain as well
+    style::set_plain(style::plain_from_env() || std::env::args_os().any(|arg| arg == "--plain"));
+    let matches = Cli::command().color(style::color_choice()).get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+
+    if cli.version {
+        cli::version::handle_version_command(cli.json);
+        return;
+    }
+    let Some(command) = cli.command else {
+        let _ = Cli::command().color(style::color_choice()).print_help();
+        return;
+    };
 
-    match cli.command {
+    match command {
         Commands::Init => {
             cli::init::handle_init_command();
         }
-        Commands::Run {
-            scope,
-            exclude,
-            min_severity,
-            format,
-            output,
-            config,
-            remappings,
-            exclude_detectors,
-        } => {
-            cli::run::handle_run_command(
-                scope,
-                exclude,
-                min_severity,
-                format,
-                output,
-                config,
-                remappings,
-                exclude_detectors,
-            );
+        Commands::Run(args) => {
+            cli::run::handle_run_command(*args);
         }
-        Commands::Detectors { severity, details } => {
-            cli::detectors::handle_detectors_command(severity, details);
+        Commands::Detectors {
+            severity,
+            details,
+            export_docs,
+        } => {
+            cli::detectors::handle_detectors_command(severity, details, export_docs);
         }
         Commands::Mcp { command } => {
             cli::mcp::handle_mcp_command(command);
         }
+        Commands::Verify { manifest, report } => {
+            cli::verify::handle_verify_command(&manifest, &report);
+        }
+        Commands::Inspect(args) => {
+            cli::inspect::handle_inspect_command(args);
+        }
+        Commands::Resolve(args) => {
+            cli::resolve::handle_resolve_command(args);
+        }
+        Commands::Graph(args) => {
+            cli::graph::handle_graph_command(args);
+        }
+        Commands::Completions { shell } => {
+            cli::completions::handle_completions_command(shell);
+        }
+        Commands::Man => {
+            cli::completions::handle_man_command();
+        }
     }
 }
@@ -0,0 +1,125 @@
+use crate::models::severity::Severity;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// What a finding is about, independent of how severe it is.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Default,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum Category {
+    AccessControl,
+    Arithmetic,
+    Oracle,
+    Upgradeability,
+    /// Integration with ERC20/ERC721 tokens and their non-standard variants
+    Token,
+    /// Low-level calls, ether transfers and handling of external call results
+    ExternalCall,
+    /// Signatures, hashing and replay protection
+    Cryptography,
+    /// Missing checks on inputs, state or protocol assumptions
+    Validation,
+    DenialOfService,
+    /// Compiler versions, EVM features and chain differences
+    Compatibility,
+    Gas,
+    CodeQuality,
+    /// High/Medium/Low detectors that have not been assigned a category
+    #[default]
+    Uncategorized,
+}
+
+impl Category {
+    pub const ALL: [Category; 13] = [
+        Category::AccessControl,
+        Category::Arithmetic,
+        Category::Oracle,
+        Category::Upgradeability,
+        Category::Token,
+        Category::ExternalCall,
+        Category::Cryptography,
+        Category::Validation,
+        Category::DenialOfService,
+        Category::Compatibility,
+        Category::Gas,
+        Category::CodeQuality,
+        Category::Uncategorized,
+    ];
+
+    /// Category of a detector that does not choose one: gas optimizations and NC findings
+    /// are categories in their own right, anything else is uncategorized.
+    pub fn for_severity(severity: &Severity) -> Self {
+        match severity {
+            Severity::Gas => Category::Gas,
+            Severity::NC => Category::CodeQuality,
+            Severity::Critical | Severity::High | Severity::Medium | Severity::Low => {
+                Category::Uncategorized
+            }
+        }
+    }
+
+    /// Kebab-case name, as used on the command line and in JSON.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Category::AccessControl => "access-control",
+            Category::Arithmetic => "arithmetic",
+            Category::Oracle => "oracle",
+            Category::Upgradeability => "upgradeability",
+            Category::Token => "token",
+            Category::ExternalCall => "external-call",
+            Category::Cryptography => "cryptography",
+            Category::Validation => "validation",
+            Category::DenialOfService => "denial-of-service",
+            Category::Compatibility => "compatibility",
+            Category::Gas => "gas",
+            Category::CodeQuality => "code-quality",
+            Category::Uncategorized => "uncategorized",
+        }
+    }
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Category::AccessControl => write!(f, "Access Control"),
+            Category::Arithmetic => write!(f, "Arithmetic"),
+            Category::Oracle => write!(f, "Oracle"),
+            Category::Upgradeability => write!(f, "Upgradeability"),
+            Category::Token => write!(f, "Token Integration"),
+            Category::ExternalCall => write!(f, "External Calls"),
+            Category::Cryptography => write!(f, "Cryptography"),
+            Category::Validation => write!(f, "Validation"),
+            Category::DenialOfService => write!(f, "Denial of Service"),
+            Category::Compatibility => write!(f, "Compatibility"),
+            Category::Gas => write!(f, "Gas"),
+            Category::CodeQuality => write!(f, "Code Quality"),
+            Category::Uncategorized => write!(f, "Uncategorized"),
+        }
+    }
+}
+
+impl FromStr for Category {
+    type Err = String;
+
+    /// Accepts the kebab-case name in any case, with `_` or ` ` as separators
+    /// (`access-control`, `Access Control`, `access_control`), and `dos`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = s.trim().to_lowercase().replace(['_', ' '], "-");
+        if normalized == "dos" {
+            return Ok(Category::DenialOfService);
+        }
+        Category::ALL
+            .into_iter()
+            .find(|category| category.as_str() == normalized)
+            .ok_or_else(|| {
+                let names: Vec<&str> = Category::ALL.iter().map(Category::as_str).collect();
+                format!(
+                    "Invalid category: {} (expected one of: {})",
+                    s,
+                    names.join(", ")
+                )
+            })
+    }
+}
@@ -1,3 +1,4 @@
+use crate::models::category::Category;
 use crate::models::severity::Severity;
 use serde::{Deserialize, Serialize};
 
@@ -9,18 +10,58 @@ pub struct Location {
     pub line_end: Option<usize>,
     pub column_end: Option<usize>,
     pub snippet: Option<String>,
+    /// Instance-specific note, for detectors whose findings differ per location
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Link to the line in the hosted repository, when `source_link_template` is configured
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Source lines around the flagged line, when `context_lines` is configured
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<Vec<ContextLine>>,
+}
+
+/// One line of the source window around a location.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ContextLine {
+    pub line: usize,
+    pub text: String,
+}
+
+impl Location {
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Finding {
     pub detector_id: String,
+    #[serde(with = "crate::models::severity::lowercase")]
     pub severity: Severity,
+    #[serde(default)]
+    pub category: Category,
     pub title: String,
     pub description: String,
+    /// Remediation example, only included when the report is generated with `explain`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub example: Option<String>,
     pub locations: Vec<Location>,
 }
 
+/// One location of a finding in report form (report-relative path, source link), handed out
+/// while the analysis runs by `AnalysisEngine::analyze_streaming`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamedFinding {
+    pub detector_id: String,
+    pub severity: Severity,
+    #[serde(default)]
+    pub category: Category,
+    #[serde(flatten)]
+    pub location: Location,
+}
+
 pub struct FindingData {
     pub detector_id: &'static str,
     pub location: Location,
@@ -1,10 +1,13 @@
+pub mod category;
 pub mod finding;
 pub mod report;
 pub mod scope;
 pub mod severity;
+pub mod suppression;
 
-pub use finding::{Finding, FindingData, Location};
-pub use report::Report;
+pub use category::Category;
+pub use finding::{ContextLine, Finding, FindingData, Location, StreamedFinding};
+pub use report::{FileFinding, Report, ScopeEntry, Summary};
 pub use scope::{
     ContractInfo, ContractType, EnumInfo, ErrorInfo, ErrorParameter, EventInfo, EventParameter,
     FunctionInfo, FunctionMutability, FunctionParameter, FunctionType, FunctionVisibility,
@@ -13,3 +16,4 @@ pub use scope::{
     VariableVisibility,
 };
 pub use severity::Severity;
+pub use suppression::Suppressions;
@@ -1,9 +1,13 @@
-use crate::models::finding::Finding;
+use crate::models::category::Category;
+use crate::models::finding::{Finding, Location};
+use crate::models::severity::Severity;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Summary {
+    #[serde(default)]
+    pub critical: usize,
     pub high: usize,
     pub medium: usize,
     pub low: usize,
@@ -12,22 +16,76 @@ pub struct Summary {
     pub total: usize,
 }
 
+impl Summary {
+    fn count(&mut self, severity: &Severity) {
+        match severity {
+            Severity::Critical => self.critical += 1,
+            Severity::High => self.high += 1,
+            Severity::Medium => self.medium += 1,
+            Severity::Low => self.low += 1,
+            Severity::Gas => self.gas += 1,
+            Severity::NC => self.nc += 1,
+        }
+        self.total += 1;
+    }
+}
+
+/// Heading of reports without a configured title.
+pub const DEFAULT_REPORT_TITLE: &str = "Weasel Static Analysis Report";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Report {
+    /// Heading configured in `[report]`, see [`Report::title`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
     pub comment: String,
     pub footnote: String,
     pub findings: Vec<Finding>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub metadata: Option<HashMap<String, String>>,
+    pub metadata: Option<BTreeMap<String, String>>,
+    /// In-scope contracts with size and function counts, when `scope_appendix` is enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope_appendix: Option<Vec<ScopeEntry>>,
+}
+
+/// The locations of one finding in one file, see [`Report::findings_by_file`].
+#[derive(Debug, Clone)]
+pub struct FileFinding<'a> {
+    /// Position of the finding in `Report::findings`
+    pub index: usize,
+    pub finding: &'a Finding,
+    pub locations: Vec<&'a Location>,
+}
+
+/// One in-scope contract of the scope appendix.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScopeEntry {
+    pub contract: String,
+    pub file: String,
+    /// Non-empty, non-comment source lines of the contract definition
+    pub nsloc: usize,
+    pub external_functions: usize,
+    pub public_functions: usize,
+    /// Internal and private functions
+    pub internal_functions: usize,
+    pub has_inheritance: bool,
+}
+
+impl Default for Report {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Report {
     pub fn new() -> Self {
         Self {
+            title: None,
             comment: String::new(),
             footnote: String::new(),
             findings: Vec::new(),
             metadata: None,
+            scope_appendix: None,
         }
     }
 
@@ -35,6 +93,11 @@ impl Report {
         self.findings.push(finding);
     }
 
+    /// The configured title, or [`DEFAULT_REPORT_TITLE`].
+    pub fn title(&self) -> &str {
+        self.title.as_deref().unwrap_or(DEFAULT_REPORT_TITLE)
+    }
+
     pub fn with_comment(mut self, comment: &str) -> Self {
         self.comment = comment.to_string();
         self
@@ -47,7 +110,7 @@ impl Report {
 
     pub fn add_metadata(&mut self, key: &str, value: &str) {
         if self.metadata.is_none() {
-            self.metadata = Some(HashMap::new());
+            self.metadata = Some(BTreeMap::new());
         }
 
         if let Some(metadata) = &mut self.metadata {
@@ -56,25 +119,73 @@ impl Report {
     }
 
     pub fn summary(&self) -> Summary {
-        let mut summary = Summary {
-            high: 0,
-            medium: 0,
-            low: 0,
-            gas: 0,
-            nc: 0,
-            total: self.findings.len(),
-        };
+        let mut summary = Summary::default();
+        for finding in &self.findings {
+            summary.count(&finding.severity);
+        }
+        summary
+    }
 
+    /// `summary` per category with findings, in category order.
+    pub fn summary_by_category(&self) -> BTreeMap<Category, Summary> {
+        let mut by_category: BTreeMap<Category, Summary> = BTreeMap::new();
         for finding in &self.findings {
-            match finding.severity {
-                crate::models::Severity::High => summary.high += 1,
-                crate::models::Severity::Medium => summary.medium += 1,
-                crate::models::Severity::Low => summary.low += 1,
-                crate::models::Severity::Gas => summary.gas += 1,
-                crate::models::Severity::NC => summary.nc += 1,
+            by_category
+                .entry(finding.category)
+                .or_default()
+                .count(&finding.severity);
+        }
+        by_category
+    }
+
+    /// `summary` per file, counting each finding once in every file it has a location in.
+    pub fn summary_by_file(&self) -> BTreeMap<&str, Summary> {
+        let mut by_file: BTreeMap<&str, Summary> = BTreeMap::new();
+        for (file, file_findings) in self.findings_by_file() {
+            let summary = by_file.entry(file).or_default();
+            for file_finding in file_findings {
+                summary.count(&file_finding.finding.severity);
             }
         }
+        by_file
+    }
 
-        summary
+    /// The findings pivoted by file, in path order. Each file lists the findings with a
+    /// location in it, most severe first, and their locations in that file.
+    pub fn findings_by_file(&self) -> BTreeMap<&str, Vec<FileFinding<'_>>> {
+        let mut by_file: BTreeMap<&str, Vec<FileFinding>> = BTreeMap::new();
+        for (index, finding) in self.findings.iter().enumerate() {
+            for location in &finding.locations {
+                let file_findings = by_file.entry(location.file.as_str()).or_default();
+                match file_findings.last_mut() {
+                    Some(last) if last.index == index => last.locations.push(location),
+                    _ => file_findings.push(FileFinding {
+                        index,
+                        finding,
+                        locations: vec![location],
+                    }),
+                }
+            }
+        }
+        for file_findings in by_file.values_mut() {
+            // Stable, so findings of the same severity keep their report order
+            file_findings.sort_by_key(|f| std::cmp::Reverse(f.finding.severity.as_value()));
+        }
+        by_file
+    }
+
+    /// Number of finding locations of `severity` or higher.
+    pub fn count_at_or_above(&self, severity: &Severity) -> usize {
+        self.findings
+            .iter()
+            .filter(|finding| finding.severity.as_value() >= severity.as_value())
+            .map(|finding| finding.locations.len())
+            .sum()
+    }
+
+    /// Keep only findings in one of `categories`.
+    pub fn retain_categories(&mut self, categories: &[Category]) {
+        self.findings
+            .retain(|finding| categories.contains(&finding.category));
     }
 }
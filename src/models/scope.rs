@@ -1,15 +1,19 @@
 use serde::{Deserialize, Serialize};
-use solang_parser::pt::{ContractTy, Expression, SourceUnit, SourceUnitPart, Type};
+use solang_parser::parse;
+use solang_parser::pt::{ContractTy, Expression, Loc, SourceUnit, SourceUnitPart, Type};
 use std::fmt;
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
 
 use crate::models::finding::Location;
+use crate::models::suppression::Suppressions;
 use crate::utils::ast_utils::{
     extract_contract_info, extract_enum_info, extract_error_info, extract_event_info,
     extract_function_info, extract_solidity_version_from_pragma, extract_struct_info,
     extract_type_definition_info, extract_using_directive_info, extract_variable_info,
     process_import_directive,
 };
+use crate::utils::location::offset_to_line_col;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContractDefinitionInfo {
@@ -38,16 +42,14 @@ pub struct SolidityFile {
     pub source_unit: SourceUnit,
     #[serde(skip)]
     pub line_starts: Vec<usize>,
+    /// `weasel-disable` comments; kept by `release_source`
+    #[serde(skip)]
+    pub suppressions: Suppressions,
 }
 
 impl SolidityFile {
     pub fn new(path: PathBuf, content: String, source_unit: SourceUnit) -> Self {
-        let mut line_starts = vec![0]; // Line 1 starts at offset 0
-        for (i, byte) in content.bytes().enumerate() {
-            if byte == b'\n' {
-                line_starts.push(i + 1);
-            }
-        }
+        let line_starts = line_starts(&content);
 
         Self {
             path,
@@ -65,9 +67,47 @@ impl SolidityFile {
             variables: Vec::new(),
             functions: Vec::new(),
             line_starts,
+            suppressions: Suppressions::default(),
         }
     }
 
+    /// Read and parse a Solidity file from disk, extracting its metadata.
+    pub fn from_path(file_path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(file_path)
+            .map_err(|e| format!("Failed to read file '{}': {}", file_path.display(), e))?;
+
+        let (source_unit, comments) = parse(&content, 0).map_err(|diagnostics| {
+            let line_starts = line_starts(&content);
+            let errors: Vec<String> = diagnostics
+                .iter()
+                .map(|diagnostic| match diagnostic.loc {
+                    Loc::File(_, start, _) => format!(
+                        "{}:{}: {}",
+                        file_path.display(),
+                        offset_to_line_col(start, &line_starts).0,
+                        diagnostic.message
+                    ),
+                    _ => format!("{}: {}", file_path.display(), diagnostic.message),
+                })
+                .collect();
+            format!("Failed to parse {}", errors.join("; "))
+        })?;
+
+        let mut solidity_file = Self::new(file_path.to_path_buf(), content, source_unit);
+        solidity_file.suppressions =
+            Suppressions::from_comments(&comments, &solidity_file.line_starts);
+        solidity_file.extract_metadata();
+        Ok(solidity_file)
+    }
+
+    /// Drop the source text and AST, keeping the extracted metadata.
+    /// In low-memory mode files are re-parsed with `from_path` one at a time for traversal.
+    pub fn release_source(&mut self) {
+        self.content = String::new();
+        self.source_unit = SourceUnit(Vec::new());
+        self.line_starts = vec![0];
+    }
+
     pub fn extract_metadata(&mut self) {
         let metadata = Self::collect_metadata(&self.source_unit, self);
 
@@ -151,6 +191,17 @@ struct FileMetadata {
     functions: Vec<FunctionInfo>,
 }
 
+/// Byte offsets at which each line of `content` starts; line 1 starts at offset 0.
+fn line_starts(content: &str) -> Vec<usize> {
+    let mut line_starts = vec![0];
+    for (i, byte) in content.bytes().enumerate() {
+        if byte == b'\n' {
+            line_starts.push(i + 1);
+        }
+    }
+    line_starts
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum ContractType {
     Contract,
@@ -356,6 +407,48 @@ impl TypeInfo {
         }
     }
 
+    /// Convert a type name as recorded in `StructField`/`FunctionParameter` (`uint96`,
+    /// `address[]`, `Config`) to TypeInfo. Mappings and function types become `Unknown`.
+    pub fn from_type_name(name: &str) -> Self {
+        if let Some(base) = name.strip_suffix(']') {
+            let Some((base, size)) = base.rsplit_once('[') else {
+                return TypeInfo::Unknown;
+            };
+            return TypeInfo::Array {
+                base: Box::new(TypeInfo::from_type_name(base)),
+                size: size.parse().ok(),
+            };
+        }
+
+        let sized = |prefix: &str| name.strip_prefix(prefix).and_then(|s| s.parse().ok());
+        if let Some(bits) = sized("uint") {
+            return TypeInfo::Uint(bits);
+        }
+        if let Some(bits) = sized("int") {
+            return TypeInfo::Int(bits);
+        }
+        if let Some(size) = sized("bytes") {
+            return TypeInfo::Bytes(size as u8);
+        }
+        match name {
+            "address" => TypeInfo::Address,
+            "address payable" => TypeInfo::AddressPayable,
+            "bool" => TypeInfo::Bool,
+            "string" => TypeInfo::String,
+            "bytes" => TypeInfo::DynamicBytes,
+            "uint" => TypeInfo::Uint(256),
+            "int" => TypeInfo::Int(256),
+            _ if !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '$' | '.')) =>
+            {
+                TypeInfo::UserDefined(name.to_string())
+            }
+            _ => TypeInfo::Unknown,
+        }
+    }
+
     pub fn is_int(&self) -> bool {
         matches!(self, TypeInfo::Int(_))
     }
@@ -445,6 +538,9 @@ pub struct FunctionInfo {
 pub struct FunctionParameter {
     pub name: Option<String>,
     pub type_name: String,
+    /// Declared data location: `memory`, `storage` or `calldata`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage_location: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -4,6 +4,8 @@ use std::str::FromStr;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
 pub enum Severity {
+    /// Above High, for overrides, custom detectors and API users; no built-in detector uses it
+    Critical,
     High,
     Medium,
     Low,
@@ -13,8 +15,19 @@ pub enum Severity {
 }
 
 impl Severity {
+    /// Every severity, most severe first
+    pub const ALL: [Severity; 6] = [
+        Severity::Critical,
+        Severity::High,
+        Severity::Medium,
+        Severity::Low,
+        Severity::Gas,
+        Severity::NC,
+    ];
+
     pub fn as_value(&self) -> u8 {
         match self {
+            Severity::Critical => 5,
             Severity::High => 4,
             Severity::Medium => 3,
             Severity::Low => 2,
@@ -22,11 +35,24 @@ impl Severity {
             Severity::NC => 0,
         }
     }
+
+    /// Lowercase name, as accepted on the command line and in config files.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Critical => "critical",
+            Severity::High => "high",
+            Severity::Medium => "medium",
+            Severity::Low => "low",
+            Severity::Gas => "gas",
+            Severity::NC => "nc",
+        }
+    }
 }
 
 impl fmt::Display for Severity {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            Severity::Critical => write!(f, "Critical"),
             Severity::High => write!(f, "High"),
             Severity::Medium => write!(f, "Medium"),
             Severity::Low => write!(f, "Low"),
@@ -36,17 +62,30 @@ impl fmt::Display for Severity {
     }
 }
 
+/// Serde helpers writing a severity as its lowercase name (`"high"`) and reading it in any case,
+/// for `#[serde(with = "crate::models::severity::lowercase")]` fields.
+pub mod lowercase {
+    use super::Severity;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(severity: &Severity, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(severity.as_str())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Severity, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(serde::de::Error::custom)
+    }
+}
+
 impl FromStr for Severity {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
-            "high" => Ok(Severity::High),
-            "medium" => Ok(Severity::Medium),
-            "low" => Ok(Severity::Low),
-            "gas" => Ok(Severity::Gas),
-            "nc" => Ok(Severity::NC),
-            _ => Err(format!("Invalid severity: {}", s)),
-        }
+        let name = s.to_lowercase();
+        Severity::ALL
+            .into_iter()
+            .find(|severity| severity.as_str() == name)
+            .ok_or_else(|| format!("Invalid severity: {}", s))
     }
 }
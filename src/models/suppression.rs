@@ -0,0 +1,225 @@
+//! Inline suppression comments:
+//!
+//! - `// weasel-disable-next-line [ids]` silences the line after the comment
+//! - `// weasel-disable-line [ids]` silences the comment's own line
+//! - `// weasel-disable [ids]` ... `// weasel-enable [ids]` silences the lines in between
+//!   (to the end of the file when never re-enabled)
+//!
+//! Detector ids are separated by spaces or commas; without ids every detector is silenced.
+//! `weasel-enable <ids>` re-enables those ids in the open regions naming them, whose other ids
+//! stay disabled, and without ids closes every open region.
+
+use crate::utils::location::offset_to_line_col;
+use solang_parser::pt::Comment;
+
+/// One suppressed line range, for some detectors or all of them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Suppression {
+    /// `None` for every detector
+    detectors: Option<Vec<String>>,
+    first_line: usize,
+    last_line: usize,
+}
+
+/// The suppression comments of one file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Suppressions {
+    rules: Vec<Suppression>,
+}
+
+impl Suppressions {
+    /// Collect the suppressions among a file's comments, as returned by `solang_parser::parse`.
+    pub fn from_comments(comments: &[Comment], line_starts: &[usize]) -> Self {
+        let mut rules = Vec::new();
+        // Open `weasel-disable` regions: ids (None for all) and the line they start on
+        let mut open: Vec<(Option<Vec<String>>, usize)> = Vec::new();
+
+        for comment in comments {
+            let (Comment::Line(loc, text) | Comment::Block(loc, text)) = comment else {
+                continue;
+            };
+            let Some((directive, detectors)) = parse_directive(text) else {
+                continue;
+            };
+            let (line, _) = offset_to_line_col(loc.start(), line_starts);
+            let (end_line, _) = offset_to_line_col(loc.end(), line_starts);
+
+            match directive {
+                Directive::Line => rules.push(Suppression {
+                    detectors,
+                    first_line: line,
+                    last_line: line,
+                }),
+                Directive::NextLine => rules.push(Suppression {
+                    detectors,
+                    first_line: end_line + 1,
+                    last_line: end_line + 1,
+                }),
+                Directive::Disable => open.push((detectors, line)),
+                Directive::Enable => {
+                    let closes = |ids: &Option<Vec<String>>| match (&detectors, ids) {
+                        (None, _) => true,
+                        (Some(enabled), Some(disabled)) => {
+                            disabled.iter().any(|id| enabled.contains(id))
+                        }
+                        (Some(_), None) => false,
+                    };
+                    let (closed, still_open) = open.into_iter().partition(|(ids, _)| closes(ids));
+                    open = still_open;
+                    for (disabled, first_line) in closed {
+                        // Ids the comment does not name stay disabled in a region reopened after it
+                        if let (Some(enabled), Some(disabled)) = (&detectors, &disabled) {
+                            let remaining: Vec<String> = disabled
+                                .iter()
+                                .filter(|id| !enabled.contains(id))
+                                .cloned()
+                                .collect();
+                            if !remaining.is_empty() {
+                                open.push((Some(remaining), end_line + 1));
+                            }
+                        }
+                        rules.push(Suppression {
+                            detectors: disabled,
+                            first_line,
+                            last_line: end_line,
+                        });
+                    }
+                }
+            }
+        }
+
+        rules.extend(open.into_iter().map(|(detectors, first_line)| Suppression {
+            detectors,
+            first_line,
+            last_line: usize::MAX,
+        }));
+        Self { rules }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Whether a finding of `detector_id` starting on `line` is suppressed.
+    pub fn suppresses(&self, detector_id: &str, line: usize) -> bool {
+        self.rules.iter().any(|rule| {
+            (rule.first_line..=rule.last_line).contains(&line)
+                && rule
+                    .detectors
+                    .as_ref()
+                    .is_none_or(|ids| ids.iter().any(|id| id == detector_id))
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Directive {
+    Line,
+    NextLine,
+    Disable,
+    Enable,
+}
+
+/// The directive and detector ids of a suppression comment, e.g.
+/// `// weasel-disable-next-line magic-numbers, unused-import`.
+fn parse_directive(text: &str) -> Option<(Directive, Option<Vec<String>>)> {
+    let body = text
+        .trim_start_matches('/')
+        .trim_start_matches('*')
+        .trim_end()
+        .trim_end_matches("*/")
+        .trim();
+    let mut words = body
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|word| !word.is_empty());
+    let directive = match words.next()? {
+        "weasel-disable-line" => Directive::Line,
+        "weasel-disable-next-line" => Directive::NextLine,
+        "weasel-disable" => Directive::Disable,
+        "weasel-enable" => Directive::Enable,
+        _ => return None,
+    };
+    let ids: Vec<String> = words.map(str::to_string).collect();
+    Some((directive, (!ids.is_empty()).then_some(ids)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn suppressions(code: &str) -> Suppressions {
+        let (_, comments) = solang_parser::parse(code, 0).unwrap();
+        let mut line_starts = vec![0];
+        line_starts.extend(code.match_indices('\n').map(|(i, _)| i + 1));
+        Suppressions::from_comments(&comments, &line_starts)
+    }
+
+    #[test]
+    fn test_line_and_next_line() {
+        let s = suppressions(
+            "contract A {\n\
+             // weasel-disable-next-line magic-numbers, unused-import\n\
+             uint256 a = 42;\n\
+             uint256 b = 7; // weasel-disable-line\n\
+             uint256 c = 1; /* weasel-disable-line magic-numbers */\n\
+             // a comment mentioning weasel-disable-line\n\
+             uint256 d = 3;\n\
+             }\n",
+        );
+        assert!(s.suppresses("magic-numbers", 3));
+        assert!(s.suppresses("unused-import", 3));
+        assert!(!s.suppresses("state-var-immutable", 3));
+        assert!(!s.suppresses("magic-numbers", 2));
+        assert!(s.suppresses("state-var-immutable", 4));
+        assert!(s.suppresses("magic-numbers", 5));
+        assert!(!s.suppresses("state-var-immutable", 5));
+        assert!(!s.suppresses("magic-numbers", 7));
+    }
+
+    #[test]
+    fn test_disable_enable_regions() {
+        let s = suppressions(
+            "contract A {\n\
+             // weasel-disable magic-numbers\n\
+             // weasel-disable\n\
+             uint256 a = 42;\n\
+             // weasel-enable magic-numbers\n\
+             uint256 b = 7;\n\
+             // weasel-enable\n\
+             uint256 c = 1;\n\
+             // weasel-disable unused-import\n\
+             uint256 d = 3;\n\
+             }\n",
+        );
+        assert!(s.suppresses("magic-numbers", 4));
+        // Enabling one detector closes only its own region; the unscoped one runs to line 7
+        assert!(s.suppresses("magic-numbers", 6));
+        assert!(s.suppresses("any-detector", 6));
+        assert!(!s.suppresses("any-detector", 8));
+        assert!(!s.suppresses("magic-numbers", 8));
+        // Never re-enabled: suppressed to the end of the file
+        assert!(s.suppresses("unused-import", 10));
+        assert!(s.suppresses("unused-import", 11));
+        assert!(!s.suppresses("magic-numbers", 10));
+        assert!(suppressions("contract A {}\n").is_empty());
+    }
+
+    #[test]
+    fn test_partial_enable_keeps_other_ids_disabled() {
+        let s = suppressions(
+            "contract A {\n\
+             // weasel-disable unlimited-gas-call, unchecked-low-level-call\n\
+             uint256 a = 42;\n\
+             // weasel-enable unlimited-gas-call\n\
+             uint256 b = 7;\n\
+             // weasel-enable unchecked-low-level-call\n\
+             uint256 c = 1;\n\
+             }\n",
+        );
+        assert!(s.suppresses("unlimited-gas-call", 3));
+        assert!(s.suppresses("unchecked-low-level-call", 3));
+        assert!(!s.suppresses("unlimited-gas-call", 5));
+        assert!(s.suppresses("unchecked-low-level-call", 5));
+        assert!(!s.suppresses("unchecked-low-level-call", 7));
+    }
+}
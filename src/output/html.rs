@@ -0,0 +1,584 @@
+use super::format_count;
+use crate::models::finding::{ContextLine, Location};
+use crate::models::report::Summary;
+use crate::models::{Report, ScopeEntry, Severity};
+use std::collections::BTreeMap;
+
+const SEVERITIES: [Severity; 6] = [
+    Severity::Critical,
+    Severity::High,
+    Severity::Medium,
+    Severity::Low,
+    Severity::Gas,
+    Severity::NC,
+];
+
+const KEYWORDS: &[&str] = &[
+    "abstract",
+    "address",
+    "anonymous",
+    "as",
+    "assembly",
+    "bool",
+    "break",
+    "bytes",
+    "calldata",
+    "catch",
+    "constant",
+    "constructor",
+    "continue",
+    "contract",
+    "delete",
+    "do",
+    "else",
+    "emit",
+    "enum",
+    "error",
+    "event",
+    "external",
+    "fallback",
+    "false",
+    "for",
+    "function",
+    "if",
+    "immutable",
+    "import",
+    "indexed",
+    "interface",
+    "internal",
+    "is",
+    "library",
+    "mapping",
+    "memory",
+    "modifier",
+    "new",
+    "override",
+    "payable",
+    "pragma",
+    "private",
+    "public",
+    "pure",
+    "receive",
+    "return",
+    "returns",
+    "revert",
+    "storage",
+    "string",
+    "struct",
+    "true",
+    "try",
+    "type",
+    "unchecked",
+    "using",
+    "view",
+    "virtual",
+    "while",
+];
+
+const STYLE: &str = "\
+body { font-family: -apple-system, BlinkMacSystemFont, \"Segoe UI\", Helvetica, Arial, sans-serif; max-width: 1100px; margin: 2em auto; padding: 0 1em; color: #1f2328; line-height: 1.5; }
+table { border-collapse: collapse; margin: 1em 0; }
+th, td { border: 1px solid #d0d7de; padding: 4px 12px; text-align: left; }
+th { background: #f6f8fa; }
+td.count { text-align: right; }
+pre { background: #f6f8fa; border-radius: 6px; padding: 12px; overflow-x: auto; font-size: 13px; }
+details.finding { border: 1px solid #d0d7de; border-left-width: 6px; border-radius: 6px; margin: 1em 0; padding: 0 1em; }
+details.finding > summary { cursor: pointer; font-weight: 600; padding: 0.6em 0; }
+details.finding[open] > summary { border-bottom: 1px solid #d0d7de; margin-bottom: 0.6em; }
+.file { font-weight: 600; margin: 1em 0 0.3em; }
+.ln { color: #6e7781; user-select: none; }
+.kw { color: #cf222e; }
+.ty { color: #8250df; }
+.st { color: #0a3069; }
+.nu { color: #0550ae; }
+.cm, .msg { color: #6e7781; font-style: italic; }
+.flag { background: #fff8c5; }
+.filters label { margin-right: 1em; }
+.sev-critical { border-left-color: #8b0000; }
+.sev-high { border-left-color: #cf222e; }
+.sev-medium { border-left-color: #bc4c00; }
+.sev-low { border-left-color: #9a6700; }
+.sev-gas { border-left-color: #1a7f37; }
+.sev-nc { border-left-color: #6e7781; }
+";
+
+/// Shows the findings of the checked severities, and opens the finding a `#finding-N` link
+/// points to.
+const SCRIPT: &str = "\
+function applyFilters() {
+  var shown = {};
+  document.querySelectorAll('.filters input').forEach(function (box) { shown[box.value] = box.checked; });
+  document.querySelectorAll('details.finding').forEach(function (finding) {
+    finding.hidden = !shown[finding.dataset.severity];
+  });
+}
+function openTarget() {
+  var target = location.hash && document.getElementById(location.hash.slice(1));
+  if (target && target.tagName === 'DETAILS') { target.hidden = false; target.open = true; }
+}
+document.querySelectorAll('.filters input').forEach(function (box) { box.addEventListener('change', applyFilters); });
+window.addEventListener('hashchange', openTarget);
+openTarget();
+";
+
+/// Generate a standalone HTML report: inline CSS and script, no external assets
+pub fn generate_html_report(report: &Report) -> String {
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    let title = escape(report.title());
+    html.push_str(&format!(
+        "<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{}</title>\n",
+        title
+    ));
+    html.push_str(&format!("<style>\n{}</style>\n</head>\n<body>\n", STYLE));
+    html.push_str(&format!("<h1>{}</h1>\n", title));
+
+    if !report.comment.is_empty() {
+        html.push_str(&format!(
+            "<h2>Overview</h2>\n<p>{}</p>\n",
+            escape(&report.comment)
+        ));
+    }
+
+    // Critical is only listed when used, like in the markdown report
+    let summary = report.summary();
+    let severities: Vec<&Severity> = SEVERITIES
+        .iter()
+        .filter(|severity| **severity != Severity::Critical || summary.critical > 0)
+        .collect();
+    html.push_str("<h2>Summary</h2>\n<table class=\"summary\">\n");
+    html.push_str("<tr><th>Severity</th><th>Findings</th></tr>\n");
+    for severity in &severities {
+        html.push_str(&format!(
+            "<tr><td>{}</td><td class=\"count\">{}</td></tr>\n",
+            severity,
+            format_count(severity_count(&summary, severity))
+        ));
+    }
+    html.push_str(&format!(
+        "<tr><th>Total</th><th class=\"count\">{}</th></tr>\n</table>\n",
+        format_count(summary.total)
+    ));
+
+    let by_category = report.summary_by_category();
+    if !by_category.is_empty() {
+        html.push_str("<h3>By Category</h3>\n<table class=\"categories\">\n<tr><th>Category</th>");
+        for severity in &severities {
+            html.push_str(&format!("<th>{}</th>", severity));
+        }
+        html.push_str("<th>Total</th></tr>\n");
+        for (category, counts) in &by_category {
+            html.push_str(&format!("<tr><td>{}</td>", category));
+            for severity in &severities {
+                html.push_str(&format!(
+                    "<td class=\"count\">{}</td>",
+                    format_count(severity_count(counts, severity))
+                ));
+            }
+            html.push_str(&format!(
+                "<td class=\"count\">{}</td></tr>\n",
+                format_count(counts.total)
+            ));
+        }
+        html.push_str("</table>\n");
+    }
+
+    html.push_str("<h2>Findings</h2>\n");
+    if report.findings.is_empty() {
+        html.push_str("<p>No issues found.</p>\n");
+    } else {
+        html.push_str("<div class=\"filters\">Show:\n");
+        for severity in &severities {
+            html.push_str(&format!(
+                "<label><input type=\"checkbox\" value=\"{}\" checked> {}</label>\n",
+                severity_class(severity),
+                severity
+            ));
+        }
+        html.push_str("</div>\n");
+
+        for (i, finding) in report.findings.iter().enumerate() {
+            html.push_str(&format!(
+                "<details class=\"finding sev-{class}\" id=\"finding-{n}\" \
+                 data-severity=\"{class}\">\n<summary>[{}-{n}] {}</summary>\n",
+                finding.severity,
+                inline_code(&finding.title),
+                class = severity_class(&finding.severity),
+                n = i + 1,
+            ));
+            html.push_str(&format!(
+                "<p><strong>Description</strong>: {}</p>\n",
+                inline_code(&finding.description)
+            ));
+
+            match &finding.example {
+                Some(example) => html.push_str(&format!(
+                    "<p><strong>Recommendation</strong>:</p>\n{}",
+                    render_example(example)
+                )),
+                None => html.push_str(&format!(
+                    "<p><strong>Recommendation</strong>: see \
+                     <code>weasel detectors --details {}</code></p>\n",
+                    escape(&finding.detector_id)
+                )),
+            }
+
+            if !finding.locations.is_empty() {
+                // Group locations by file path, in path order so reports are reproducible
+                let mut locations_by_file: BTreeMap<&str, Vec<&Location>> = BTreeMap::new();
+                for loc in &finding.locations {
+                    locations_by_file.entry(&loc.file).or_default().push(loc);
+                }
+
+                let num_files = locations_by_file.len();
+                let total_instances = finding.locations.len();
+                html.push_str(&format!(
+                    "<p><em>{} {} in {} {}</em></p>\n",
+                    format_count(total_instances),
+                    if total_instances == 1 {
+                        "instance"
+                    } else {
+                        "instances"
+                    },
+                    format_count(num_files),
+                    if num_files == 1 { "file" } else { "files" }
+                ));
+
+                for (file_path, locations_in_file) in &locations_by_file {
+                    html.push_str(&format!(
+                        "<div class=\"file\">File: {}</div>\n<pre><code>",
+                        escape(file_path)
+                    ));
+                    for (i, loc) in locations_in_file.iter().enumerate() {
+                        if let Some(context) = &loc.context {
+                            if i > 0 {
+                                html.push('\n');
+                            }
+                            html.push_str(&render_context_window(loc, context));
+                            continue;
+                        }
+                        html.push_str(&format!(
+                            "<span class=\"ln\">{}:</span> {}",
+                            loc.line,
+                            highlight(loc.snippet.as_deref().unwrap_or("..."))
+                        ));
+                        if let Some(message) = &loc.message {
+                            html.push_str(&format!(
+                                " <span class=\"msg\">// {}</span>",
+                                escape(message)
+                            ));
+                        }
+                        html.push('\n');
+                    }
+                    html.push_str("</code></pre>\n");
+
+                    let links: Vec<String> = locations_in_file
+                        .iter()
+                        .filter_map(|loc| {
+                            let url = loc.url.as_ref()?;
+                            Some(format!(
+                                "<li><a href=\"{}\">{}:{}</a></li>\n",
+                                escape(url),
+                                escape(file_path),
+                                loc.line
+                            ))
+                        })
+                        .collect();
+                    if !links.is_empty() {
+                        html.push_str(&format!("<ul>\n{}</ul>\n", links.concat()));
+                    }
+                }
+            }
+
+            html.push_str("</details>\n");
+        }
+    }
+
+    if let Some(entries) = &report.scope_appendix {
+        html.push_str(&generate_scope_appendix(entries));
+    }
+
+    if let Some(metadata) = &report.metadata {
+        html.push_str("<h2>Metadata</h2>\n<ul>\n");
+        for (key, value) in metadata {
+            html.push_str(&format!(
+                "<li><strong>{}</strong>: {}</li>\n",
+                escape(key),
+                escape(value)
+            ));
+        }
+        html.push_str("</ul>\n");
+    }
+
+    if !report.footnote.is_empty() {
+        html.push_str(&format!(
+            "<h2>Note</h2>\n<p>{}</p>\n",
+            escape(&report.footnote)
+        ));
+    }
+
+    html.push_str(&format!(
+        "<script>\n{}</script>\n</body>\n</html>\n",
+        SCRIPT
+    ));
+    html
+}
+
+/// Source lines around `loc`, with the reported line marked with `>`, shaded and followed by
+/// the instance message.
+fn render_context_window(loc: &Location, context: &[ContextLine]) -> String {
+    let width = context.last().map_or(0, |l| l.line.to_string().len());
+    let mut html = String::new();
+    for context_line in context {
+        let flagged = context_line.line == loc.line;
+        let mut line = format!(
+            "<span class=\"ln\">{} {:>width$}:</span> {}",
+            if flagged { "&gt;" } else { " " },
+            context_line.line,
+            highlight(&context_line.text),
+            width = width
+        );
+        if flagged {
+            if let Some(message) = &loc.message {
+                line.push_str(&format!(
+                    " <span class=\"msg\">// {}</span>",
+                    escape(message)
+                ));
+            }
+            line = format!("<span class=\"flag\">{}</span>", line);
+        }
+        html.push_str(&line);
+        html.push('\n');
+    }
+    html
+}
+
+fn severity_count(summary: &Summary, severity: &Severity) -> usize {
+    match severity {
+        Severity::Critical => summary.critical,
+        Severity::High => summary.high,
+        Severity::Medium => summary.medium,
+        Severity::Low => summary.low,
+        Severity::Gas => summary.gas,
+        Severity::NC => summary.nc,
+    }
+}
+
+fn severity_class(severity: &Severity) -> &'static str {
+    match severity {
+        Severity::Critical => "critical",
+        Severity::High => "high",
+        Severity::Medium => "medium",
+        Severity::Low => "low",
+        Severity::Gas => "gas",
+        Severity::NC => "nc",
+    }
+}
+
+/// HTML table of the in-scope contracts, with a totals row.
+fn generate_scope_appendix(entries: &[ScopeEntry]) -> String {
+    let mut html = String::from(
+        "<h2>Appendix: Scope</h2>\n<table class=\"scope\">\n<tr><th>Contract</th><th>File</th>\
+         <th>nSLOC</th><th>External</th><th>Public</th><th>Internal</th><th>Inherits</th></tr>\n",
+    );
+    for entry in entries {
+        html.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td class=\"count\">{}</td><td class=\"count\">{}</td>\
+             <td class=\"count\">{}</td><td class=\"count\">{}</td><td>{}</td></tr>\n",
+            escape(&entry.contract),
+            escape(&entry.file),
+            entry.nsloc,
+            entry.external_functions,
+            entry.public_functions,
+            entry.internal_functions,
+            if entry.has_inheritance { "yes" } else { "no" }
+        ));
+    }
+    html.push_str("</table>\n");
+    html
+}
+
+/// Escape text for use in element content and quoted attribute values
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escape text, rendering markdown `code spans` as `<code>`
+fn inline_code(text: &str) -> String {
+    text.split('`')
+        .enumerate()
+        .map(|(i, part)| {
+            // Odd parts sit between backticks; an unpaired trailing backtick stays literal
+            if i % 2 == 1 && text.matches('`').count() > i {
+                format!("<code>{}</code>", escape(part))
+            } else if i % 2 == 1 {
+                format!("`{}", escape(part))
+            } else {
+                escape(part)
+            }
+        })
+        .collect()
+}
+
+/// Render a detector example: fenced code blocks become highlighted `<pre>` blocks and the
+/// text around them paragraphs.
+fn render_example(example: &str) -> String {
+    let mut html = String::new();
+    let mut code: Option<Vec<&str>> = None;
+    let mut text: Vec<&str> = Vec::new();
+    for line in example.lines() {
+        if line.trim_start().starts_with("```") {
+            match code.take() {
+                Some(lines) => {
+                    html.push_str(&format!(
+                        "<pre><code>{}</code></pre>\n",
+                        highlight(&lines.join("\n"))
+                    ));
+                }
+                None => {
+                    push_paragraph(&mut html, &text);
+                    text.clear();
+                    code = Some(Vec::new());
+                }
+            }
+        } else if let Some(lines) = code.as_mut() {
+            lines.push(line);
+        } else {
+            text.push(line);
+        }
+    }
+    // An unclosed fence still shows its code
+    if let Some(lines) = code {
+        html.push_str(&format!(
+            "<pre><code>{}</code></pre>\n",
+            highlight(&lines.join("\n"))
+        ));
+    }
+    push_paragraph(&mut html, &text);
+    html
+}
+
+fn push_paragraph(html: &mut String, lines: &[&str]) {
+    let text = lines.join("\n");
+    if !text.trim().is_empty() {
+        html.push_str(&format!("<p>{}</p>\n", inline_code(text.trim())));
+    }
+}
+
+/// Escape Solidity source, wrapping keywords, elementary types, literals and comments in
+/// spans. A lexical approximation that also copes with the partial lines found in snippets.
+fn highlight(code: &str) -> String {
+    let chars: Vec<char> = code.chars().collect();
+    let mut html = String::with_capacity(code.len());
+    let mut i = 0;
+    let span = |class: &str, text: &[char]| {
+        format!(
+            "<span class=\"{}\">{}</span>",
+            class,
+            escape(&text.iter().collect::<String>())
+        )
+    };
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        if c == '/' && chars.get(i + 1) == Some(&'/') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            html.push_str(&span("cm", &chars[start..i]));
+        } else if c == '/' && chars.get(i + 1) == Some(&'*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                i += 1;
+            }
+            i = (i + 2).min(chars.len());
+            html.push_str(&span("cm", &chars[start..i]));
+        } else if c == '"' || c == '\'' {
+            i += 1;
+            while i < chars.len() && chars[i] != c && chars[i] != '\n' {
+                if chars[i] == '\\' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            i = (i + 1).min(chars.len());
+            html.push_str(&span("st", &chars[start..i]));
+        } else if c.is_ascii_digit() {
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            html.push_str(&span("nu", &chars[start..i]));
+        } else if c.is_ascii_alphabetic() || c == '_' || c == '$' {
+            while i < chars.len()
+                && (chars[i].is_ascii_alphanumeric() || chars[i] == '_' || chars[i] == '$')
+            {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            if KEYWORDS.contains(&word.as_str()) {
+                html.push_str(&span("kw", &chars[start..i]));
+            } else if is_elementary_type(&word) {
+                html.push_str(&span("ty", &chars[start..i]));
+            } else {
+                html.push_str(&word);
+            }
+        } else {
+            html.push_str(&escape(&c.to_string()));
+            i += 1;
+        }
+    }
+    html
+}
+
+/// `uint`, `uint256`, `int8`, `bytes32` and friends
+fn is_elementary_type(word: &str) -> bool {
+    if word == "uint" || word == "int" {
+        return true;
+    }
+    ["uint", "int", "bytes"].iter().any(|prefix| {
+        word.strip_prefix(prefix)
+            .is_some_and(|bits| !bits.is_empty() && bits.chars().all(|c| c.is_ascii_digit()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highlight_escapes_and_marks_tokens() {
+        assert_eq!(
+            highlight("if (a < 1e18) return \"<x>\"; // done"),
+            "<span class=\"kw\">if</span> (a &lt; <span class=\"nu\">1e18</span>) \
+             <span class=\"kw\">return</span> <span class=\"st\">&quot;&lt;x&gt;&quot;</span>; \
+             <span class=\"cm\">// done</span>"
+        );
+        assert_eq!(
+            highlight("uint256 x; uint y; /* a */ z"),
+            "<span class=\"ty\">uint256</span> x; <span class=\"ty\">uint</span> y; \
+             <span class=\"cm\">/* a */</span> z"
+        );
+    }
+
+    #[test]
+    fn test_inline_code_spans() {
+        assert_eq!(
+            inline_code("Use `calldata` <here>"),
+            "Use <code>calldata</code> &lt;here&gt;"
+        );
+        assert_eq!(inline_code("a ` b"), "a ` b");
+    }
+}
@@ -0,0 +1,35 @@
+use crate::models::{Report, Summary};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// The JSON report: every field of [`Report`] plus the weasel version, severity totals and
+/// per-detector instance counts, so consumers don't recompute them. Deserializes back into
+/// `Report`, which ignores the extra fields.
+#[derive(Debug, Serialize)]
+pub struct JsonReport<'a> {
+    pub weasel_version: &'static str,
+    /// Findings (detectors with at least one instance) per severity
+    pub summary: Summary,
+    /// Detector id -> number of reported instances
+    pub detector_counts: BTreeMap<&'a str, usize>,
+    #[serde(flatten)]
+    pub report: &'a Report,
+}
+
+impl<'a> JsonReport<'a> {
+    pub fn new(report: &'a Report) -> Self {
+        let mut detector_counts = BTreeMap::new();
+        for finding in &report.findings {
+            *detector_counts
+                .entry(finding.detector_id.as_str())
+                .or_default() += finding.locations.len();
+        }
+
+        Self {
+            weasel_version: crate::core::version(),
+            summary: report.summary(),
+            detector_counts,
+            report,
+        }
+    }
+}
@@ -0,0 +1,231 @@
+use crate::models::{Report, StreamedFinding};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+/// One line of a `--format jsonl` stream.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum JsonlRecord {
+    Finding(StreamedFinding),
+    Summary(JsonlSummary),
+}
+
+/// Last line of a `--format jsonl` stream, totalling the finding lines before it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonlSummary {
+    pub weasel_version: String,
+    pub total_findings: usize,
+    pub by_severity: BTreeMap<String, usize>,
+}
+
+/// Writes finding instances as one JSON object per line, flushing after every batch so
+/// consumers see each file's findings as soon as they are written.
+pub struct JsonlWriter<W: Write> {
+    out: W,
+    total_findings: usize,
+    by_severity: BTreeMap<String, usize>,
+}
+
+impl<W: Write> JsonlWriter<W> {
+    pub fn new(out: W) -> Self {
+        Self {
+            out,
+            total_findings: 0,
+            by_severity: BTreeMap::new(),
+        }
+    }
+
+    pub fn write_findings(&mut self, findings: &[StreamedFinding]) -> io::Result<()> {
+        for finding in findings {
+            *self
+                .by_severity
+                .entry(finding.severity.to_string())
+                .or_default() += 1;
+            self.total_findings += 1;
+            self.write_record(&JsonlRecord::Finding(finding.clone()))?;
+        }
+        self.out.flush()
+    }
+
+    /// Write the summary line and return the underlying writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        let summary = JsonlSummary {
+            weasel_version: crate::core::version().to_string(),
+            total_findings: self.total_findings,
+            by_severity: std::mem::take(&mut self.by_severity),
+        };
+        self.write_record(&JsonlRecord::Summary(summary))?;
+        self.out.flush()?;
+        Ok(self.out)
+    }
+
+    fn write_record(&mut self, record: &JsonlRecord) -> io::Result<()> {
+        serde_json::to_writer(&mut self.out, record)?;
+        self.out.write_all(b"\n")
+    }
+}
+
+/// Write a complete report as a JSONL stream, in report order.
+pub fn write_jsonl_report<W: Write>(report: &Report, out: W) -> io::Result<()> {
+    let mut writer = JsonlWriter::new(out);
+    for finding in &report.findings {
+        let instances: Vec<StreamedFinding> = finding
+            .locations
+            .iter()
+            .map(|location| StreamedFinding {
+                detector_id: finding.detector_id.clone(),
+                severity: finding.severity.clone(),
+                category: finding.category,
+                location: location.clone(),
+            })
+            .collect();
+        writer.write_findings(&instances)?;
+    }
+    writer.finish().map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::core::engine::AnalysisEngine;
+    use crate::models::{Category, Finding, Location, Severity};
+    use crate::utils::test_utils::write_fixture_project;
+    use std::fs;
+    use std::sync::Mutex;
+
+    const VAULT: &str = r#"
+        pragma solidity ^0.8.0;
+
+        contract Vault {
+            address owner;
+
+            function withdraw() external {
+                require(tx.origin == owner);
+                payable(msg.sender).transfer(address(this).balance);
+            }
+        }
+    "#;
+
+    const TOKEN: &str = r#"
+        pragma solidity ^0.8.0;
+
+        contract Token {
+            function burn(address from) external {
+                require(tx.origin == from);
+            }
+        }
+    "#;
+
+    fn parse_stream(stream: &[u8]) -> Vec<JsonlRecord> {
+        String::from_utf8(stream.to_vec())
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("every line is standalone JSON"))
+            .collect()
+    }
+
+    #[test]
+    fn test_streamed_findings_match_summary_and_report() {
+        let root = write_fixture_project(
+            "jsonl-stream",
+            &[
+                ("foundry.toml", "[profile.default]\n"),
+                ("src/Vault.sol", VAULT),
+                ("src/Token.sol", TOKEN),
+            ],
+        );
+        let config = Config {
+            scope: vec![root.join("src")],
+            ..Config::default()
+        };
+        let mut engine = AnalysisEngine::new(&config);
+        engine.register_built_in_detectors();
+
+        let writer = Mutex::new(JsonlWriter::new(Vec::new()));
+        let report = engine
+            .analyze_streaming(&|findings| writer.lock().unwrap().write_findings(findings).unwrap())
+            .expect("Analysis failed");
+        let stream = writer.into_inner().unwrap().finish().unwrap();
+
+        let records = parse_stream(&stream);
+        let (summary, findings) = records.split_last().unwrap();
+        let JsonlRecord::Summary(summary) = summary else {
+            panic!("last line must be the summary");
+        };
+        let findings: Vec<&StreamedFinding> = findings
+            .iter()
+            .map(|record| match record {
+                JsonlRecord::Finding(finding) => finding,
+                JsonlRecord::Summary(_) => panic!("summary before the end of the stream"),
+            })
+            .collect();
+
+        let report_instances: usize = report.findings.iter().map(|f| f.locations.len()).sum();
+        assert_eq!(summary.total_findings, findings.len());
+        assert_eq!(summary.total_findings, report_instances);
+        assert_eq!(summary.by_severity.values().sum::<usize>(), findings.len());
+        assert!(findings
+            .iter()
+            .any(|f| f.detector_id == "tx-origin-usage" && f.location.file == "src/Token.sol"));
+
+        // Each file's findings arrive together, ordered by line
+        for file in ["src/Vault.sol", "src/Token.sol"] {
+            let positions: Vec<usize> = (0..findings.len())
+                .filter(|&i| findings[i].location.file == file)
+                .collect();
+            assert!(positions.windows(2).all(|pair| pair[1] == pair[0] + 1));
+            let lines: Vec<usize> = positions
+                .iter()
+                .map(|&i| findings[i].location.line)
+                .collect();
+            assert!(lines.windows(2).all(|pair| pair[0] <= pair[1]));
+        }
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_jsonl_report_lines() {
+        let mut report = Report::new();
+        report.add_finding(Finding {
+            detector_id: "tx-origin-usage".to_string(),
+            severity: Severity::Medium,
+            category: Category::AccessControl,
+            title: "Use of `tx.origin`".to_string(),
+            description: String::new(),
+            example: None,
+            locations: vec![Location {
+                file: "src/Vault.sol".to_string(),
+                line: 7,
+                column: Some(17),
+                line_end: Some(7),
+                column_end: Some(36),
+                snippet: Some("tx.origin == owner".to_string()),
+                message: None,
+                url: None,
+                context: None,
+            }],
+        });
+
+        let mut stream = Vec::new();
+        write_jsonl_report(&report, &mut stream).unwrap();
+        let lines: Vec<serde_json::Value> = String::from_utf8(stream)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["type"], "finding");
+        assert_eq!(lines[0]["detector_id"], "tx-origin-usage");
+        assert_eq!(lines[0]["severity"], "Medium");
+        assert_eq!(lines[0]["file"], "src/Vault.sol");
+        assert_eq!(lines[0]["line"], 7);
+        assert_eq!(lines[0]["snippet"], "tx.origin == owner");
+        assert_eq!(lines[1]["type"], "summary");
+        assert_eq!(lines[1]["total_findings"], 1);
+        assert_eq!(lines[1]["by_severity"]["Medium"], 1);
+    }
+}
@@ -1,21 +1,42 @@
-use crate::models::finding::Location;
-use crate::models::Report;
-use serde::Deserialize;
-use std::collections::HashMap;
+use crate::models::finding::{ContextLine, Location};
+use crate::models::{Finding, Report, ScopeEntry, Summary};
+use crate::utils::fsx::{write_atomic, AtomicFile};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
 use std::fmt;
-use std::fs::File;
-use std::io::{self, Write};
+use std::io;
 use std::path::PathBuf;
 use std::str::FromStr;
 
+mod html;
+mod json;
+mod jsonl;
+mod progress;
+mod redact;
 mod sarif;
+pub mod style;
+mod timestamp;
 
-#[derive(Debug, Clone, Deserialize, Default)]
+pub use html::generate_html_report;
+pub use json::JsonReport;
+pub use jsonl::{write_jsonl_report, JsonlRecord, JsonlSummary, JsonlWriter};
+pub use progress::{Progress, Verbosity};
+pub use redact::redact_report;
+pub use sarif::generate_fingerprint;
+pub use timestamp::TimestampMode;
+
+use style::outln;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
 pub enum ReportFormat {
     Json,
     #[default]
     Markdown,
     Sarif,
+    /// One JSON object per finding instance, streamed while the analysis runs
+    Jsonl,
+    /// Standalone HTML page with inline CSS and a severity filter
+    Html,
 }
 
 impl FromStr for ReportFormat {
@@ -26,6 +47,8 @@ impl FromStr for ReportFormat {
             "json" => Ok(ReportFormat::Json),
             "md" | "markdown" => Ok(ReportFormat::Markdown),
             "sarif" => Ok(ReportFormat::Sarif),
+            "jsonl" => Ok(ReportFormat::Jsonl),
+            "html" => Ok(ReportFormat::Html),
             _ => Err(format!("Invalid report format: {}", s)),
         }
     }
@@ -37,38 +60,74 @@ impl fmt::Display for ReportFormat {
             ReportFormat::Json => write!(f, "Json"),
             ReportFormat::Markdown => write!(f, "Markdown"),
             ReportFormat::Sarif => write!(f, "Sarif"),
+            ReportFormat::Jsonl => write!(f, "Jsonl"),
+            ReportFormat::Html => write!(f, "Html"),
+        }
+    }
+}
+
+/// How the findings section of the markdown report is organized.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum GroupBy {
+    /// One section per finding, listing the files it occurs in
+    #[default]
+    Finding,
+    /// One section per file with the findings in it, and a count table per file
+    File,
+}
+
+impl FromStr for GroupBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "finding" => Ok(GroupBy::Finding),
+            "file" => Ok(GroupBy::File),
+            _ => Err(format!("Invalid grouping: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for GroupBy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GroupBy::Finding => write!(f, "finding"),
+            GroupBy::File => write!(f, "file"),
         }
     }
 }
 
+/// Write the report to `output` with the format's extension, or to stdout.
+/// `group_by` only applies to markdown. Returns the path of the written file, which the caller
+/// reports, so stdout only ever carries the report itself.
 pub fn generate_report(
     report: &Report,
     format: &ReportFormat,
+    group_by: GroupBy,
     output: Option<PathBuf>,
-) -> io::Result<()> {
+) -> io::Result<Option<PathBuf>> {
     match format {
         ReportFormat::Json => {
             if let Some(path) = output {
                 let path_with_extension = path.with_extension("json");
-                let file = File::create(&path_with_extension)?;
-                serde_json::to_writer_pretty(file, report)?;
-                println!("Report saved: {}", path_with_extension.display());
+                let mut file = AtomicFile::create(&path_with_extension)?;
+                serde_json::to_writer_pretty(&mut file, &JsonReport::new(report))?;
+                file.commit()?;
+                return Ok(Some(path_with_extension));
             } else {
-                let stdout = io::stdout();
-                let handle = stdout.lock();
-                serde_json::to_writer_pretty(handle, report)?;
+                serde_json::to_writer_pretty(style::json_stdout(), &JsonReport::new(report))?;
             }
         }
         ReportFormat::Markdown => {
-            let markdown = generate_markdown_report(report);
+            let markdown = generate_markdown_report(report, group_by);
 
             if let Some(path) = output {
                 let path_with_extension = path.with_extension("md");
-                let mut file = File::create(&path_with_extension)?;
-                write!(file, "{}", markdown)?;
-                println!("Report saved: {}", path_with_extension.display());
+                write_atomic(&path_with_extension, markdown)?;
+                return Ok(Some(path_with_extension));
             } else {
-                println!("{}", markdown);
+                outln!("{}", markdown);
             }
         }
         ReportFormat::Sarif => {
@@ -76,26 +135,209 @@ pub fn generate_report(
 
             if let Some(path) = output {
                 let path_with_extension = path.with_extension("sarif");
-                let file = File::create(&path_with_extension)?;
-                serde_json::to_writer_pretty(file, &sarif_report)?;
-                println!("Report saved: {}", path_with_extension.display());
+                let mut file = AtomicFile::create(&path_with_extension)?;
+                serde_json::to_writer_pretty(&mut file, &sarif_report)?;
+                file.commit()?;
+                return Ok(Some(path_with_extension));
+            } else {
+                serde_json::to_writer_pretty(style::json_stdout(), &sarif_report)?;
+            }
+        }
+        ReportFormat::Jsonl => {
+            if let Some(path) = output {
+                let path_with_extension = path.with_extension("jsonl");
+                let mut file = AtomicFile::create(&path_with_extension)?;
+                write_jsonl_report(report, &mut file)?;
+                file.commit()?;
+                return Ok(Some(path_with_extension));
+            } else {
+                write_jsonl_report(report, style::json_stdout())?;
+            }
+        }
+        ReportFormat::Html => {
+            let html = generate_html_report(report);
+
+            if let Some(path) = output {
+                let path_with_extension = path.with_extension("html");
+                write_atomic(&path_with_extension, html)?;
+                return Ok(Some(path_with_extension));
             } else {
-                let stdout = io::stdout();
-                let handle = stdout.lock();
-                serde_json::to_writer_pretty(handle, &sarif_report)?;
+                outln!("{}", html);
             }
         }
     }
 
-    Ok(())
+    Ok(None)
+}
+
+/// Markdown table of the in-scope contracts, with a totals row.
+fn generate_scope_appendix(entries: &[ScopeEntry]) -> String {
+    let mut markdown = String::from("## Appendix: Scope\n\n");
+    markdown.push_str("| Contract | File | nSLOC | External | Public | Internal | Inherits |\n");
+    markdown.push_str("| -------- | ---- | ----- | -------- | ------ | -------- | -------- |\n");
+
+    for entry in entries {
+        markdown.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} | {} |\n",
+            entry.contract,
+            entry.file,
+            entry.nsloc,
+            entry.external_functions,
+            entry.public_functions,
+            entry.internal_functions,
+            if entry.has_inheritance { "yes" } else { "no" }
+        ));
+    }
+
+    let total = |count: fn(&ScopeEntry) -> usize| entries.iter().map(count).sum::<usize>();
+    markdown.push_str(&format!(
+        "| **Total** | {} files | {} | {} | {} | {} | |\n\n",
+        entries
+            .iter()
+            .map(|e| e.file.as_str())
+            .collect::<HashSet<_>>()
+            .len(),
+        total(|e| e.nsloc),
+        total(|e| e.external_functions),
+        total(|e| e.public_functions),
+        total(|e| e.internal_functions),
+    ));
+    markdown
+}
+
+/// `count` with comma thousands separators, e.g. `12,345`.
+fn format_count(count: usize) -> String {
+    let digits = count.to_string();
+    let mut formatted = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            formatted.push(',');
+        }
+        formatted.push(digit);
+    }
+    formatted
+}
+
+/// Markdown table of finding counts per severity, one row per `rows` entry.
+fn summary_table<'a>(
+    first_column: &str,
+    rows: impl Iterator<Item = (String, &'a Summary)>,
+    with_critical: bool,
+) -> String {
+    let mut markdown = format!(
+        "| {} |{} High | Medium | Low | Gas | NC | Total |\n",
+        first_column,
+        if with_critical { " Critical |" } else { "" }
+    );
+    markdown.push_str(&format!(
+        "|{}|{}------|--------|-----|-----|----|-------|\n",
+        "-".repeat(first_column.len() + 2),
+        if with_critical { "----------|" } else { "" }
+    ));
+    for (name, counts) in rows {
+        let critical = if with_critical {
+            format!(" {} |", format_count(counts.critical))
+        } else {
+            String::new()
+        };
+        markdown.push_str(&format!(
+            "| {} |{} {} | {} | {} | {} | {} | {} |\n",
+            name,
+            critical,
+            format_count(counts.high),
+            format_count(counts.medium),
+            format_count(counts.low),
+            format_count(counts.gas),
+            format_count(counts.nc),
+            format_count(counts.total)
+        ));
+    }
+    markdown.push('\n');
+    markdown
+}
+
+/// Description and recommendation of a finding.
+fn push_finding_details(markdown: &mut String, finding: &Finding) {
+    // Description
+    markdown.push_str(&format!("**Description**:\n{}\n\n", finding.description));
+
+    // Example code when explaining, otherwise a pointer to it
+    match &finding.example {
+        Some(example) => markdown.push_str(&format!("**Recommendation**:\n{}\n\n", example)),
+        None => markdown.push_str(&format!(
+            "**Recommendation**: see `weasel detectors --details {}`\n\n",
+            finding.detector_id
+        )),
+    }
+}
+
+/// Code block with a finding's locations in one file, followed by their source links.
+fn push_file_locations(markdown: &mut String, file_path: &str, locations: &[&Location]) {
+    markdown.push_str("```solidity\n"); // Start code block for the file
+    markdown.push_str(&format!("File: {}\n\n", file_path));
+
+    // Print each location within the file
+    for (i, loc) in locations.iter().enumerate() {
+        if let Some(context) = &loc.context {
+            if i > 0 {
+                markdown.push('\n');
+            }
+            push_context_window(markdown, loc, context);
+            continue;
+        }
+        let snippet = loc.snippet.as_deref().unwrap_or("..."); // Use snippet or fallback
+        match &loc.message {
+            Some(message) => {
+                markdown.push_str(&format!("{}: {} // {}\n", loc.line, snippet, message))
+            }
+            None => markdown.push_str(&format!("{}: {}\n", loc.line, snippet)),
+        }
+    }
+
+    markdown.push_str("```\n"); // End code block for the file
+    markdown.push_str("\n"); // Add a newline after the code block
+
+    // Links cannot live inside the code block, so list them after it
+    let links: Vec<String> = locations
+        .iter()
+        .filter_map(|loc| {
+            let url = loc.url.as_ref()?;
+            Some(format!("- [{}:{}]({})\n", file_path, loc.line, url))
+        })
+        .collect();
+    if !links.is_empty() {
+        markdown.push_str(&links.concat());
+        markdown.push('\n');
+    }
+}
+
+/// Source lines around `loc`, with the reported line marked with `>` and followed by the
+/// instance message.
+fn push_context_window(markdown: &mut String, loc: &Location, context: &[ContextLine]) {
+    let width = context.last().map_or(0, |l| l.line.to_string().len());
+    for context_line in context {
+        let flagged = context_line.line == loc.line;
+        let mut line = format!(
+            "{} {:>width$}: {}",
+            if flagged { '>' } else { ' ' },
+            context_line.line,
+            context_line.text,
+            width = width
+        );
+        if let Some(message) = loc.message.as_ref().filter(|_| flagged) {
+            line.push_str(&format!(" // {}", message));
+        }
+        markdown.push_str(line.trim_end());
+        markdown.push('\n');
+    }
 }
 
 /// Generate a markdown report
-fn generate_markdown_report(report: &Report) -> String {
+fn generate_markdown_report(report: &Report, group_by: GroupBy) -> String {
     let mut markdown = String::new();
 
     // Add title
-    markdown.push_str("# Weasel Static Analysis Report\n\n");
+    markdown.push_str(&format!("# {}\n\n", report.title()));
 
     // Add comment if present
     if !report.comment.is_empty() {
@@ -105,17 +347,58 @@ fn generate_markdown_report(report: &Report) -> String {
     // Add summary
     let summary = report.summary();
     markdown.push_str("## Summary\n\n");
-    markdown.push_str(&format!("- **High**: {}\n", summary.high));
-    markdown.push_str(&format!("- **Medium**: {}\n", summary.medium));
-    markdown.push_str(&format!("- **Low**: {}\n", summary.low));
-    markdown.push_str(&format!("- **Gas**: {}\n", summary.gas));
-    markdown.push_str(&format!("- **NC**: {}\n", summary.nc));
-    markdown.push_str(&format!("- **Total**: {}\n\n", summary.total));
+    // Critical is only listed when used, so reports on the usual scale stay as they were
+    let with_critical = summary.critical > 0;
+    if with_critical {
+        markdown.push_str(&format!(
+            "- **Critical**: {}\n",
+            format_count(summary.critical)
+        ));
+    }
+    markdown.push_str(&format!("- **High**: {}\n", format_count(summary.high)));
+    markdown.push_str(&format!("- **Medium**: {}\n", format_count(summary.medium)));
+    markdown.push_str(&format!("- **Low**: {}\n", format_count(summary.low)));
+    markdown.push_str(&format!("- **Gas**: {}\n", format_count(summary.gas)));
+    markdown.push_str(&format!("- **NC**: {}\n", format_count(summary.nc)));
+    markdown.push_str(&format!("- **Total**: {}\n\n", format_count(summary.total)));
 
-    // Add findings
-    if !report.findings.is_empty() {
-        markdown.push_str("## Findings\n\n");
+    let by_category = report.summary_by_category();
+    if !by_category.is_empty() {
+        markdown.push_str("### By Category\n\n");
+        let rows = by_category
+            .iter()
+            .map(|(category, counts)| (category.to_string(), counts));
+        markdown.push_str(&summary_table("Category", rows, with_critical));
+    }
+    if group_by == GroupBy::File && !report.findings.is_empty() {
+        markdown.push_str("### By File\n\n");
+        let by_file = report.summary_by_file();
+        let rows = by_file
+            .iter()
+            .map(|(file, counts)| (file.to_string(), counts));
+        markdown.push_str(&summary_table("File", rows, with_critical));
+    }
 
+    // Add findings
+    markdown.push_str("## Findings\n\n");
+    if report.findings.is_empty() {
+        markdown.push_str("No issues found.\n\n");
+    } else if group_by == GroupBy::File {
+        for (file_path, file_findings) in report.findings_by_file() {
+            markdown.push_str(&format!("### {}\n\n", file_path));
+            for file_finding in file_findings {
+                markdown.push_str(&format!(
+                    "#### [{}-{}] {}\n\n",
+                    file_finding.finding.severity,
+                    file_finding.index + 1,
+                    file_finding.finding.title
+                ));
+                push_finding_details(&mut markdown, file_finding.finding);
+                push_file_locations(&mut markdown, file_path, &file_finding.locations);
+            }
+            markdown.push_str("---\n\n");
+        }
+    } else {
         for (i, finding) in report.findings.iter().enumerate() {
             // Finding header with severity
             markdown.push_str(&format!(
@@ -124,19 +407,12 @@ fn generate_markdown_report(report: &Report) -> String {
                 i + 1,
                 finding.title
             ));
-
-            // Description
-            markdown.push_str(&format!("**Description**:\n{}\n\n", finding.description));
-
-            // Example code if present
-            if let Some(example) = &finding.example {
-                markdown.push_str(&format!("**Recommendation**:\n{}\n\n", example));
-            }
+            push_finding_details(&mut markdown, finding);
 
             // **Locations - Grouped by file**
             if !finding.locations.is_empty() {
-                // Group locations by file path
-                let mut locations_by_file: HashMap<String, Vec<&Location>> = HashMap::new();
+                // Group locations by file path, in path order so reports are reproducible
+                let mut locations_by_file: BTreeMap<String, Vec<&Location>> = BTreeMap::new();
                 for loc in &finding.locations {
                     locations_by_file
                         .entry(loc.file.clone())
@@ -156,22 +432,15 @@ fn generate_markdown_report(report: &Report) -> String {
                 // Use <details> for collapsibility
                 markdown.push_str(&format!(
                     "<details>\n<summary><i>{} {} in {} {}</i></summary>\n\n",
-                    total_instances, instance_plural, num_files, file_plural
+                    format_count(total_instances),
+                    instance_plural,
+                    format_count(num_files),
+                    file_plural
                 ));
 
                 // Iterate through each file group
                 for (file_path, locations_in_file) in &locations_by_file {
-                    markdown.push_str("```solidity\n"); // Start code block for the file
-                    markdown.push_str(&format!("File: {}\n\n", file_path));
-
-                    // Print each location within the file
-                    for loc in locations_in_file {
-                        let snippet = loc.snippet.as_deref().unwrap_or("..."); // Use snippet or fallback
-                        markdown.push_str(&format!("{}: {}\n", loc.line, snippet));
-                    }
-
-                    markdown.push_str("```\n"); // End code block for the file
-                    markdown.push_str("\n"); // Add a newline after the code block
+                    push_file_locations(&mut markdown, file_path, locations_in_file);
                 }
 
                 markdown.push_str("</details>\n\n"); // Close details tag
@@ -179,9 +448,10 @@ fn generate_markdown_report(report: &Report) -> String {
 
             markdown.push_str("---\n\n");
         }
-    } else {
-        markdown.push_str("## Findings\n\n");
-        markdown.push_str("No issues found.\n\n");
+    }
+
+    if let Some(entries) = &report.scope_appendix {
+        markdown.push_str(&generate_scope_appendix(entries));
     }
 
     // Add metadata if present
@@ -200,3 +470,255 @@ fn generate_markdown_report(report: &Report) -> String {
 
     markdown
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Category, Finding, Severity};
+
+    fn report_with_example(example: Option<&str>) -> Report {
+        let mut report = Report::new();
+        report.add_finding(Finding {
+            detector_id: "calldata-instead-of-memory".to_string(),
+            severity: Severity::Gas,
+            category: Category::Gas,
+            title: "Use `calldata` instead of `memory`".to_string(),
+            description: "Read-only external parameters can be `calldata`.".to_string(),
+            example: example.map(str::to_string),
+            locations: vec![Location {
+                file: "src/Vault.sol".to_string(),
+                line: 12,
+                column: None,
+                line_end: None,
+                column_end: None,
+                snippet: Some("function f(uint256[] memory ids) external".to_string()),
+                message: None,
+                url: None,
+                context: None,
+            }],
+        });
+        report
+    }
+
+    #[test]
+    fn test_explained_report_includes_example() {
+        let example = "```solidity\nfunction f(uint256[] calldata ids) external\n```";
+        let report = report_with_example(Some(example));
+
+        let markdown = generate_markdown_report(&report, GroupBy::Finding);
+        assert!(markdown.contains(&format!("**Recommendation**:\n{}\n\n", example)));
+        assert!(!markdown.contains("weasel detectors --details"));
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["findings"][0]["example"], example);
+    }
+
+    #[test]
+    fn test_unexplained_report_points_to_details() {
+        let report = report_with_example(None);
+
+        let markdown = generate_markdown_report(&report, GroupBy::Finding);
+        assert!(markdown.contains(
+            "**Recommendation**: see `weasel detectors --details calldata-instead-of-memory`"
+        ));
+        assert!(!markdown.contains("```solidity\nfunction f(uint256[] calldata"));
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert!(json["findings"][0].get("example").is_none());
+    }
+
+    #[test]
+    fn test_source_links_render_after_snippets() {
+        let mut report = report_with_example(None);
+        let json = serde_json::to_value(&report).unwrap();
+        assert!(json["findings"][0]["locations"][0].get("url").is_none());
+
+        let url = "https://github.com/org/repo/blob/4f2a9c1/src/Vault.sol#L12";
+        report.findings[0].locations[0].url = Some(url.to_string());
+
+        let markdown = generate_markdown_report(&report, GroupBy::Finding);
+        assert!(markdown.contains(&format!("```\n\n- [src/Vault.sol:12]({})\n", url)));
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["findings"][0]["locations"][0]["url"], url);
+    }
+
+    #[test]
+    fn test_context_window_marks_reported_line() {
+        let mut report = report_with_example(None);
+        let json = serde_json::to_value(&report).unwrap();
+        assert!(json["findings"][0]["locations"][0].get("context").is_none());
+
+        let location = &mut report.findings[0].locations[0];
+        location.message = Some("`ids` is only read".to_string());
+        location.context = Some(
+            [
+                (10, ""),
+                (11, "uint256 total;"),
+                (12, "function f(uint256[] memory ids) external {"),
+            ]
+            .iter()
+            .map(|(line, text)| ContextLine {
+                line: *line,
+                text: text.to_string(),
+            })
+            .collect(),
+        );
+
+        let markdown = generate_markdown_report(&report, GroupBy::Finding);
+        assert!(markdown.contains(
+            "File: src/Vault.sol\n\n\
+             \x20 10:\n\
+             \x20 11: uint256 total;\n\
+             > 12: function f(uint256[] memory ids) external { // `ids` is only read\n```"
+        ));
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(
+            json["findings"][0]["locations"][0]["context"][2],
+            serde_json::json!({"line": 12, "text": "function f(uint256[] memory ids) external {"})
+        );
+    }
+
+    #[test]
+    fn test_configured_title_overview_and_footnote() {
+        let mut report = report_with_example(None);
+        assert!(generate_markdown_report(&report, GroupBy::Finding)
+            .starts_with("# Weasel Static Analysis Report\n\n"));
+
+        report.title = Some("Acme Vault Review".to_string());
+        report.comment = "Contracts at commit 4f2a9c1.".to_string();
+        report.footnote = "Unverified findings.".to_string();
+        report.add_metadata("Client", "Acme");
+
+        let markdown = generate_markdown_report(&report, GroupBy::Finding);
+        assert!(markdown
+            .starts_with("# Acme Vault Review\n\n## Overview\n\nContracts at commit 4f2a9c1.\n\n"));
+        assert!(markdown.contains("- **Client**: Acme\n"));
+        assert!(markdown.ends_with("## Note\n\nUnverified findings.\n"));
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["title"], "Acme Vault Review");
+    }
+
+    #[test]
+    fn test_summary_breaks_down_by_category() {
+        let mut report = report_with_example(None);
+        let mut oracle = report.findings[0].clone();
+        oracle.detector_id = "chainlink-stale-price".to_string();
+        oracle.severity = Severity::Medium;
+        oracle.category = Category::Oracle;
+        report.add_finding(oracle);
+
+        let markdown = generate_markdown_report(&report, GroupBy::Finding);
+        assert!(markdown.contains(
+            "| Category | High | Medium | Low | Gas | NC | Total |\n\
+             |----------|------|--------|-----|-----|----|-------|\n\
+             | Oracle | 0 | 1 | 0 | 0 | 0 | 1 |\n\
+             | Gas | 0 | 0 | 0 | 1 | 0 | 1 |\n"
+        ));
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["findings"][1]["category"], "oracle");
+
+        report.retain_categories(&["oracle".parse().unwrap()]);
+        assert_eq!(report.findings.len(), 1);
+        assert!(!generate_markdown_report(&report, GroupBy::Finding).contains("| Gas | 0 |"));
+    }
+
+    #[test]
+    fn test_critical_is_listed_only_when_present() {
+        let mut report = report_with_example(None);
+        let markdown = generate_markdown_report(&report, GroupBy::Finding);
+        assert!(!markdown.contains("Critical"));
+
+        let mut critical = report.findings[0].clone();
+        critical.detector_id = "custom-drain".to_string();
+        critical.severity = Severity::Critical;
+        critical.category = Category::AccessControl;
+        report.add_finding(critical);
+
+        let markdown = generate_markdown_report(&report, GroupBy::Finding);
+        assert!(markdown.contains("- **Critical**: 1\n- **High**: 0\n"));
+        assert!(markdown.contains(
+            "| Category | Critical | High | Medium | Low | Gas | NC | Total |\n\
+             |----------|----------|------|--------|-----|-----|----|-------|\n\
+             | Access Control | 1 | 0 | 0 | 0 | 0 | 0 | 1 |\n\
+             | Gas | 0 | 0 | 0 | 0 | 1 | 0 | 1 |\n"
+        ));
+        assert!(markdown.contains("### [Critical-2] "));
+    }
+
+    #[test]
+    fn test_summary_counts_have_thousands_separators() {
+        assert_eq!(format_count(0), "0");
+        assert_eq!(format_count(999), "999");
+        assert_eq!(format_count(1_000), "1,000");
+        assert_eq!(format_count(1_234_567), "1,234,567");
+
+        let mut report = report_with_example(None);
+        let location = report.findings[0].locations[0].clone();
+        report.findings[0].locations = vec![location; 1_200];
+
+        let markdown = generate_markdown_report(&report, GroupBy::Finding);
+        assert!(markdown.contains("- **Gas**: 1\n"));
+        assert!(markdown.contains("<summary><i>1,200 instances in 1 file</i></summary>"));
+    }
+
+    #[test]
+    fn test_group_by_file_keeps_every_location() {
+        let mut report = report_with_example(None);
+        let mut vault = report.findings[0].locations[0].clone();
+        vault.line = 30;
+        vault.snippet = Some("function g(bytes memory data) external".to_string());
+        let mut pool = vault.clone();
+        pool.file = "src/Pool.sol".to_string();
+        pool.line = 7;
+        report.findings[0].locations.extend([pool.clone(), vault]);
+
+        let mut oracle = report.findings[0].clone();
+        oracle.detector_id = "chainlink-stale-price".to_string();
+        oracle.severity = Severity::Medium;
+        oracle.category = Category::Oracle;
+        oracle.title = "Stale Chainlink price".to_string();
+        pool.line = 21;
+        pool.snippet = Some("(, int256 price, , , ) = feed.latestRoundData();".to_string());
+        oracle.locations = vec![pool];
+        report.add_finding(oracle);
+
+        let by_finding = generate_markdown_report(&report, GroupBy::Finding);
+        let by_file = generate_markdown_report(&report, GroupBy::File);
+
+        // Same summary, and every location rendered exactly once in both
+        let summary =
+            |markdown: &str| markdown[..markdown.find("### By Category").unwrap()].to_string();
+        assert_eq!(summary(&by_finding), summary(&by_file));
+        for finding in &report.findings {
+            for location in &finding.locations {
+                let line = format!(
+                    "{}: {}\n",
+                    location.line,
+                    location.snippet.as_ref().unwrap()
+                );
+                assert_eq!(by_finding.matches(&line).count(), 1, "{}", line);
+                assert_eq!(by_file.matches(&line).count(), 1, "{}", line);
+            }
+        }
+        assert!(!by_finding.contains("### By File"));
+
+        assert!(by_file.contains(
+            "### By File\n\n\
+             | File | High | Medium | Low | Gas | NC | Total |\n\
+             |------|------|--------|-----|-----|----|-------|\n\
+             | src/Pool.sol | 0 | 1 | 0 | 1 | 0 | 2 |\n\
+             | src/Vault.sol | 0 | 0 | 0 | 1 | 0 | 1 |\n"
+        ));
+        // Files in path order, the Medium finding first within a file, numbered as in the
+        // default grouping
+        let pool = by_file.find("### src/Pool.sol").unwrap();
+        let vault = by_file.find("### src/Vault.sol").unwrap();
+        let medium = by_file
+            .find("#### [Medium-2] Stale Chainlink price")
+            .unwrap();
+        let gas = by_file[pool..].find("#### [Gas-1] ").unwrap() + pool;
+        assert!(pool < medium && medium < gas && gas < vault);
+        assert!(by_finding.contains("### [Medium-2] Stale Chainlink price"));
+        assert!(by_file[vault..].contains("File: src/Vault.sol\n\n12: "));
+    }
+}
@@ -0,0 +1,64 @@
+//! How much weasel prints to stderr besides warnings and errors, and the file counters shown
+//! while it parses and analyzes. Stdout only ever carries the report.
+
+use std::io::{self, IsTerminal, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Diagnostics printed to stderr. Warnings and errors are printed at every level.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// No notes, status lines or progress counters (`--quiet`)
+    Quiet,
+    /// Notes, status lines and progress counters
+    #[default]
+    Normal,
+    /// Also the project setup and the inheritance of every contract (`--verbose`)
+    Verbose,
+}
+
+/// A `label: done/total files` counter redrawn in place on stderr and erased when dropped.
+/// Only drawn when stderr is a terminal, so redirected output and CI logs never contain it.
+#[derive(Debug)]
+pub struct Progress {
+    label: &'static str,
+    total: usize,
+    done: AtomicUsize,
+    visible: bool,
+}
+
+impl Progress {
+    pub fn new(label: &'static str, total: usize, verbosity: Verbosity) -> Self {
+        Self {
+            label,
+            total,
+            done: AtomicUsize::new(0),
+            visible: verbosity > Verbosity::Quiet && total > 0 && io::stderr().is_terminal(),
+        }
+    }
+
+    /// Count one more file. Safe to call from several threads.
+    pub fn tick(&self) {
+        if !self.visible {
+            self.done.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        // Counting under the lock keeps the redrawn numbers increasing
+        let mut stderr = io::stderr().lock();
+        let done = self.done.fetch_add(1, Ordering::Relaxed) + 1;
+        let _ = write!(stderr, "\r{}", self.line(done));
+        let _ = stderr.flush();
+    }
+
+    fn line(&self, done: usize) -> String {
+        format!("{}: {}/{} files", self.label, done, self.total)
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        if self.visible {
+            let width = self.line(self.total).len();
+            eprint!("\r{:width$}\r", "", width = width);
+        }
+    }
+}
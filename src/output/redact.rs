@@ -0,0 +1,181 @@
+use crate::models::Report;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Metadata keys that carry no information about the analyzed code.
+const SAFE_METADATA_KEYS: &[&str] = &["Version:", "Timestamp:", "Total Findings:"];
+
+/// Strip client-identifying data from a report so it can be shared externally.
+///
+/// File paths are replaced with stable pseudonyms (`contract-01.sol`, ...) assigned in
+/// sorted path order, snippets, source context, instance messages and source links are
+/// removed, and only known-safe metadata is kept. The configured title and the scope appendix
+/// are dropped. Detector ids, severities, detector-authored text and line numbers are
+/// preserved. Returns the redacted report and the pseudonym -> original path map.
+pub fn redact_report(report: &Report) -> (Report, BTreeMap<String, String>) {
+    let files: BTreeSet<&str> = report
+        .findings
+        .iter()
+        .flat_map(|f| f.locations.iter().map(|l| l.file.as_str()))
+        .collect();
+
+    let width = files.len().to_string().len().max(2);
+    let pseudonyms: BTreeMap<&str, String> = files
+        .into_iter()
+        .enumerate()
+        .map(|(i, file)| {
+            (
+                file,
+                format!("contract-{:0width$}.sol", i + 1, width = width),
+            )
+        })
+        .collect();
+
+    let mut redacted = report.clone();
+    redacted.title = None;
+    redacted.comment.clear();
+    redacted.footnote.clear();
+    redacted.scope_appendix = None;
+    if let Some(metadata) = &mut redacted.metadata {
+        metadata.retain(|key, _| SAFE_METADATA_KEYS.contains(&key.as_str()));
+    }
+
+    for finding in &mut redacted.findings {
+        for location in &mut finding.locations {
+            location.file = pseudonyms[location.file.as_str()].clone();
+            location.snippet = None;
+            location.message = None;
+            location.url = None;
+            location.context = None;
+        }
+    }
+
+    let mapping = pseudonyms
+        .into_iter()
+        .map(|(file, pseudonym)| (pseudonym, file.to_string()))
+        .collect();
+
+    (redacted, mapping)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::finding::{ContextLine, Finding, Location};
+    use crate::models::{Category, ScopeEntry, Severity};
+
+    fn location(file: &str, line: usize, snippet: &str) -> Location {
+        Location {
+            file: file.to_string(),
+            line,
+            column: Some(5),
+            line_end: Some(line),
+            column_end: Some(30),
+            snippet: Some(snippet.to_string()),
+            message: Some(format!("`{}` is unchecked", snippet)),
+            url: Some(format!(
+                "https://github.com/acme/secret-protocol/blob/main/{}#L{}",
+                file, line
+            )),
+            context: Some(vec![ContextLine {
+                line,
+                text: format!("acmeTreasury.call({});", snippet),
+            }]),
+        }
+    }
+
+    fn client_report() -> Report {
+        let mut report = Report::new();
+        report.title = Some("Acme Secret Protocol Review".to_string());
+        report.comment = "Audit of AcmeSecretVault".to_string();
+        report.add_finding(Finding {
+            detector_id: "tx-origin-usage".to_string(),
+            severity: Severity::Medium,
+            category: Category::AccessControl,
+            title: "Use of `tx.origin`".to_string(),
+            description: "Avoid `tx.origin` for authorization.".to_string(),
+            example: Some("```solidity\nrequire(msg.sender == owner);\n```".to_string()),
+            locations: vec![
+                location(
+                    "/home/acme/secret-protocol/src/AcmeVault.sol",
+                    12,
+                    "drainAcmeTreasury()",
+                ),
+                location(
+                    "/home/acme/secret-protocol/src/AcmeVault.sol",
+                    40,
+                    "acmeOwner",
+                ),
+                location(
+                    "/home/acme/secret-protocol/src/AcmeRouter.sol",
+                    7,
+                    "routeAcme",
+                ),
+            ],
+        });
+        report.scope_appendix = Some(vec![ScopeEntry {
+            contract: "AcmeVault".to_string(),
+            file: "src/AcmeVault.sol".to_string(),
+            nsloc: 120,
+            external_functions: 4,
+            public_functions: 1,
+            internal_functions: 3,
+            has_inheritance: true,
+        }]);
+        report.add_metadata("Version:", "0.5.0");
+        report.add_metadata("Git Commit:", "acme1234");
+        report.add_metadata("Project Root:", "/home/acme/secret-protocol");
+        report
+    }
+
+    #[test]
+    fn test_redact_report_leaks_nothing() {
+        let (redacted, _) = redact_report(&client_report());
+
+        let outputs = [
+            serde_json::to_string(&redacted).unwrap(),
+            super::super::generate_markdown_report(&redacted, super::super::GroupBy::default()),
+            serde_json::to_string(&super::super::sarif::generate_sarif_report(&redacted)).unwrap(),
+        ];
+
+        for output in &outputs {
+            assert!(
+                !output.to_lowercase().contains("acme"),
+                "leaked: {}",
+                output
+            );
+            assert!(!output.contains("secret-protocol"));
+            assert!(output.contains("contract-01.sol"));
+        }
+    }
+
+    #[test]
+    fn test_redact_report_keeps_structure() {
+        let report = client_report();
+        let (redacted, mapping) = redact_report(&report);
+
+        let finding = &redacted.findings[0];
+        assert_eq!(finding.severity, Severity::Medium);
+        assert_eq!(finding.example, report.findings[0].example);
+
+        let files: Vec<_> = finding.locations.iter().map(|l| l.file.as_str()).collect();
+        assert_eq!(
+            files,
+            ["contract-02.sol", "contract-02.sol", "contract-01.sol"]
+        );
+        let lines: Vec<_> = finding.locations.iter().map(|l| l.line).collect();
+        assert_eq!(lines, [12, 40, 7]);
+
+        assert_eq!(
+            mapping["contract-01.sol"],
+            "/home/acme/secret-protocol/src/AcmeRouter.sol"
+        );
+        assert_eq!(
+            mapping["contract-02.sol"],
+            "/home/acme/secret-protocol/src/AcmeVault.sol"
+        );
+
+        let metadata = redacted.metadata.unwrap();
+        assert_eq!(metadata.len(), 1);
+        assert_eq!(metadata["Version:"], "0.5.0");
+    }
+}
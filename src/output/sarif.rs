@@ -11,7 +11,7 @@ use std::hash::{Hash, Hasher};
 /// Convert Weasel Severity to SARIF ResultLevel
 fn severity_to_level(severity: &Severity) -> ResultLevel {
     match severity {
-        Severity::High => ResultLevel::Error,
+        Severity::Critical | Severity::High => ResultLevel::Error,
         Severity::Medium => ResultLevel::Warning,
         Severity::Low => ResultLevel::Note,
         Severity::Gas => ResultLevel::Note,
@@ -22,6 +22,7 @@ fn severity_to_level(severity: &Severity) -> ResultLevel {
 /// Convert Weasel Severity to security-severity score (for GitHub)
 fn severity_to_score(severity: &Severity) -> &'static str {
     match severity {
+        Severity::Critical => "10.0",
         Severity::High => "9.0",
         Severity::Medium => "6.0",
         Severity::Low => "3.0",
@@ -33,7 +34,7 @@ fn severity_to_score(severity: &Severity) -> &'static str {
 /// Generate a fingerprint hash for tracking results across runs
 /// Uses detector_id + file + line + snippet to create stable identifier
 /// Note: Uses FnvHasher for stability across Rust versions (DefaultHasher is not guaranteed stable)
-fn generate_fingerprint(detector_id: &str, file: &str, line: usize, snippet: Option<&str>) -> String {
+pub fn generate_fingerprint(detector_id: &str, file: &str, line: usize, snippet: Option<&str>) -> String {
     let mut hasher = FnvHasher::default();
     detector_id.hash(&mut hasher);
     file.hash(&mut hasher);
@@ -87,7 +88,12 @@ pub fn generate_sarif_report(report: &Report) -> Sarif {
                             props.insert("precision".to_string(), serde_json::json!("high"));
                             props.insert(
                                 "tags".to_string(),
-                                serde_json::json!(["security", "solidity", "smart-contract"]),
+                                serde_json::json!([
+                                    "security",
+                                    "solidity",
+                                    "smart-contract",
+                                    finding.category.as_str()
+                                ]),
                             );
                             props
                         })
@@ -139,7 +145,11 @@ pub fn generate_sarif_report(report: &Report) -> Sarif {
                 .rule_id(&finding.detector_id)
                 .rule_index(*rule_indices.get(&finding.detector_id).unwrap())
                 .level(severity_to_level(&finding.severity))
-                .message(Message::builder().text(&finding.description).build())
+                .message(
+                    Message::builder()
+                        .text(location.message.as_deref().unwrap_or(&finding.description))
+                        .build(),
+                )
                 .locations(vec![sarif_location])
                 .partial_fingerprints(partial_fingerprints)
                 .build();
@@ -174,15 +184,18 @@ pub fn generate_sarif_report(report: &Report) -> Sarif {
 mod tests {
     use super::*;
     use crate::models::finding::{Finding, Location};
+    use crate::models::Category;
 
     #[test]
     fn test_sarif_generation_basic() {
         let report = Report {
+            title: None,
             comment: String::new(),
             footnote: String::new(),
             findings: vec![Finding {
                 detector_id: "test-detector".to_string(),
                 severity: Severity::High,
+                category: Category::Oracle,
                 title: "Test Finding".to_string(),
                 description: "Test description".to_string(),
                 example: None,
@@ -193,9 +206,13 @@ mod tests {
                     line_end: Some(10),
                     column_end: Some(20),
                     snippet: Some("uint x = 1;".to_string()),
+                    message: None,
+                    url: None,
+                    context: None,
                 }],
             }],
             metadata: None,
+            scope_appendix: None,
         };
 
         let sarif = generate_sarif_report(&report);
@@ -207,6 +224,10 @@ mod tests {
         assert_eq!(run.tool.driver.name, "Weasel");
         assert_eq!(run.tool.driver.rules.as_ref().unwrap().len(), 1);
         assert_eq!(run.results.as_ref().unwrap().len(), 1);
+
+        let rule = &run.tool.driver.rules.as_ref().unwrap()[0];
+        let tags = &rule.properties.as_ref().unwrap().additional_properties["tags"];
+        assert!(tags.as_array().unwrap().contains(&serde_json::json!("oracle")));
     }
 
     #[test]
@@ -228,6 +249,59 @@ mod tests {
             ResultLevel::Note
         ));
         assert!(matches!(severity_to_level(&Severity::NC), ResultLevel::Note));
+        assert!(matches!(
+            severity_to_level(&Severity::Critical),
+            ResultLevel::Error
+        ));
+    }
+
+    #[test]
+    fn test_sarif_result_mapping() {
+        let location = |file: &str, line: usize| Location {
+            file: file.to_string(),
+            line,
+            column: None,
+            line_end: None,
+            column_end: None,
+            snippet: None,
+            message: None,
+            url: None,
+            context: None,
+        };
+        let mut report = Report::new();
+        report.add_finding(Finding {
+            detector_id: "chainlink-stale-price".to_string(),
+            severity: Severity::Medium,
+            category: Category::Oracle,
+            title: "Stale price".to_string(),
+            description: "Price is not checked for staleness".to_string(),
+            example: None,
+            locations: vec![location("./src/Oracle.sol", 12), location("src/Pool.sol", 40)],
+        });
+
+        let json = serde_json::to_value(generate_sarif_report(&report)).unwrap();
+        let run = &json["runs"][0];
+        assert_eq!(run["tool"]["driver"]["rules"][0]["id"], "chainlink-stale-price");
+        assert_eq!(
+            run["tool"]["driver"]["rules"][0]["fullDescription"]["text"],
+            "Price is not checked for staleness"
+        );
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 2);
+        for result in results {
+            assert_eq!(result["ruleId"], "chainlink-stale-price");
+            assert_eq!(result["ruleIndex"], 0);
+            assert_eq!(result["level"], "warning");
+        }
+        let physical = &results[0]["locations"][0]["physicalLocation"];
+        assert_eq!(physical["artifactLocation"]["uri"], "src/Oracle.sol");
+        assert_eq!(physical["region"]["startLine"], 12);
+        assert_eq!(physical["region"]["startColumn"], 1);
+        assert_eq!(
+            results[1]["locations"][0]["physicalLocation"]["region"]["startLine"],
+            40
+        );
     }
 
     #[test]
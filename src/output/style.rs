@@ -0,0 +1,197 @@
+//! Process-wide console output style. Everything weasel prints goes through this module:
+//! text through [`outln!`]/[`errln!`], JSON streams through [`json_stdout`]. In plain mode
+//! (`--plain` or `WEASEL_PLAIN=1`) text is folded to ASCII with ANSI escapes removed, and
+//! JSON gets `\uXXXX` escapes for non-ASCII characters so it stays lossless.
+
+use std::borrow::Cow;
+use std::env;
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static PLAIN: AtomicBool = AtomicBool::new(false);
+
+/// Switch plain mode on or off for the rest of the process.
+pub fn set_plain(plain: bool) {
+    PLAIN.store(plain, Ordering::Relaxed);
+}
+
+pub fn is_plain() -> bool {
+    PLAIN.load(Ordering::Relaxed)
+}
+
+/// Whether `WEASEL_PLAIN` is set to something other than `0`, `false` or `no`.
+pub fn plain_from_env() -> bool {
+    env::var("WEASEL_PLAIN").is_ok_and(|value| {
+        let value = value.trim().to_lowercase();
+        !matches!(value.as_str(), "" | "0" | "false" | "no")
+    })
+}
+
+/// Color setting for clap's help and error output.
+pub fn color_choice() -> clap::ColorChoice {
+    if is_plain() {
+        clap::ColorChoice::Never
+    } else {
+        clap::ColorChoice::Auto
+    }
+}
+
+/// `text` as it should be printed: unchanged, or folded to ASCII in plain mode.
+pub fn console(text: &str) -> Cow<'_, str> {
+    if is_plain() && (!text.is_ascii() || text.contains('\x1b')) {
+        Cow::Owned(to_ascii(text))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// `println!` through [`console`].
+macro_rules! outln {
+    () => {
+        println!()
+    };
+    ($($arg:tt)*) => {
+        println!("{}", $crate::output::style::console(&format!($($arg)*)))
+    };
+}
+
+/// `eprintln!` through [`console`].
+macro_rules! errln {
+    () => {
+        eprintln!()
+    };
+    ($($arg:tt)*) => {
+        eprintln!("{}", $crate::output::style::console(&format!($($arg)*)))
+    };
+}
+
+pub(crate) use {errln, outln};
+
+/// Stdout for machine-readable JSON output, escaping non-ASCII characters in plain mode.
+pub fn json_stdout() -> Box<dyn Write + Send> {
+    if is_plain() {
+        Box::new(AsciiJsonWriter::new(io::stdout()))
+    } else {
+        Box::new(io::stdout())
+    }
+}
+
+/// Drop ANSI escape sequences and replace non-ASCII characters with ASCII look-alikes, or `?`.
+fn to_ascii(text: &str) -> String {
+    let mut ascii = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            // CSI sequences (`ESC [ ... final`) and two-character escapes
+            '\x1b' => {
+                if chars.next() == Some('[') {
+                    chars.find(|c| ('@'..='~').contains(c));
+                }
+            }
+            c if c.is_ascii() => ascii.push(c),
+            c => ascii.push_str(ascii_equivalent(c)),
+        }
+    }
+    ascii
+}
+
+fn ascii_equivalent(c: char) -> &'static str {
+    match c {
+        '→' | '⇒' | '➜' => "->",
+        '←' | '⇐' => "<-",
+        '±' => "+/-",
+        '≥' => ">=",
+        '≤' => "<=",
+        '≠' => "!=",
+        '×' => "x",
+        '‐' | '–' | '—' | '─' | '━' | '═' => "-",
+        '│' | '┃' | '║' => "|",
+        '┌' | '┐' | '└' | '┘' | '├' | '┤' | '┬' | '┴' | '┼' | '╭' | '╮' | '╯' | '╰' => {
+            "+"
+        }
+        '‘' | '’' | '′' => "'",
+        '“' | '”' | '″' => "\"",
+        '…' => "...",
+        '•' | '·' | '◦' => "*",
+        '✓' | '✔' => "ok",
+        '✗' | '✘' => "x",
+        '\u{a0}' | '\u{2009}' | '\u{202f}' => " ",
+        _ => "?",
+    }
+}
+
+/// Writer that replaces every non-ASCII character of UTF-8 JSON text with its `\uXXXX`
+/// escape. Non-ASCII characters only occur inside JSON strings, so the output decodes to the
+/// same value. A character split across writes is held until its remaining bytes arrive.
+struct AsciiJsonWriter<W: Write> {
+    out: W,
+    pending: Vec<u8>,
+}
+
+impl<W: Write> AsciiJsonWriter<W> {
+    fn new(out: W) -> Self {
+        Self {
+            out,
+            pending: Vec::new(),
+        }
+    }
+}
+
+impl<W: Write> Write for AsciiJsonWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.pending.extend_from_slice(buf);
+        let (valid, rest) = match std::str::from_utf8(&self.pending) {
+            Ok(text) => (text, 0),
+            // An incomplete character at the end waits for the next write
+            Err(e) if e.error_len().is_none() => (
+                // The bytes up to `valid_up_to` are known to be valid UTF-8
+                std::str::from_utf8(&self.pending[..e.valid_up_to()]).unwrap(),
+                self.pending.len() - e.valid_up_to(),
+            ),
+            Err(e) => return Err(io::Error::new(io::ErrorKind::InvalidData, e)),
+        };
+
+        let mut escaped = String::with_capacity(valid.len());
+        for c in valid.chars() {
+            if c.is_ascii() {
+                escaped.push(c);
+            } else {
+                for unit in c.encode_utf16(&mut [0; 2]) {
+                    escaped.push_str(&format!("\\u{:04x}", unit));
+                }
+            }
+        }
+        self.out.write_all(escaped.as_bytes())?;
+        self.pending.drain(..self.pending.len() - rest);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_text_is_ascii_without_escapes() {
+        let text = "\x1b[1;31mHigh\x1b[0m ── `uint` → `int` ± 1 … ✓";
+        assert_eq!(to_ascii(text), "High -- `uint` -> `int` +/- 1 ... ok");
+    }
+
+    #[test]
+    fn test_json_escapes_survive_split_writes() {
+        let json = serde_json::to_string(&serde_json::json!({"snippet": "a → b 🦀"})).unwrap();
+        let mut writer = AsciiJsonWriter::new(Vec::new());
+        for chunk in json.as_bytes().chunks(3) {
+            writer.write_all(chunk).unwrap();
+        }
+
+        let escaped = String::from_utf8(writer.out).unwrap();
+        assert!(escaped.is_ascii(), "{}", escaped);
+        let value: serde_json::Value = serde_json::from_str(&escaped).unwrap();
+        assert_eq!(value["snippet"], "a → b 🦀");
+    }
+}
@@ -0,0 +1,102 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{de, Deserialize, Deserializer, Serialize};
+use std::env;
+use std::fmt;
+use std::str::FromStr;
+
+/// Timestamp written to the report metadata.
+#[derive(Debug, Clone, Copy, Serialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TimestampMode {
+    /// Time of the run
+    #[default]
+    Now,
+    /// `SOURCE_DATE_EPOCH`, for byte-identical reports across runs
+    Fixed,
+    /// No timestamp
+    None,
+}
+
+impl FromStr for TimestampMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "now" => Ok(TimestampMode::Now),
+            "fixed" => Ok(TimestampMode::Fixed),
+            "none" => Ok(TimestampMode::None),
+            _ => Err(format!("Invalid timestamp mode: {}", s)),
+        }
+    }
+}
+
+/// A mode name, or a boolean: `timestamp = false` in `weasel.toml` is `none`, `true` is `now`.
+impl<'de> Deserialize<'de> for TimestampMode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Value {
+            Enabled(bool),
+            Mode(String),
+        }
+
+        match Value::deserialize(deserializer)? {
+            Value::Enabled(true) => Ok(TimestampMode::Now),
+            Value::Enabled(false) => Ok(TimestampMode::None),
+            Value::Mode(mode) => mode.parse().map_err(de::Error::custom),
+        }
+    }
+}
+
+impl fmt::Display for TimestampMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimestampMode::Now => write!(f, "now"),
+            TimestampMode::Fixed => write!(f, "fixed"),
+            TimestampMode::None => write!(f, "none"),
+        }
+    }
+}
+
+impl TimestampMode {
+    /// RFC 3339 timestamp for the report, or `None` when it is omitted. `Fixed` fails when
+    /// `SOURCE_DATE_EPOCH` is unset or not a Unix timestamp.
+    pub fn resolve(self) -> Result<Option<String>, String> {
+        let time = match self {
+            TimestampMode::Now => Utc::now(),
+            TimestampMode::Fixed => {
+                let value = env::var("SOURCE_DATE_EPOCH").map_err(|_| {
+                    "--timestamp fixed requires SOURCE_DATE_EPOCH to be set".to_string()
+                })?;
+                parse_source_date_epoch(&value)?
+            }
+            TimestampMode::None => return Ok(None),
+        };
+        Ok(Some(time.to_rfc3339_opts(SecondsFormat::Secs, true)))
+    }
+}
+
+fn parse_source_date_epoch(value: &str) -> Result<DateTime<Utc>, String> {
+    value
+        .trim()
+        .parse()
+        .ok()
+        .and_then(|seconds| DateTime::from_timestamp(seconds, 0))
+        .ok_or_else(|| format!("SOURCE_DATE_EPOCH '{}' is not a Unix timestamp", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_date_epoch_formats_as_rfc3339() {
+        let time = parse_source_date_epoch("1700000000").unwrap();
+        assert_eq!(
+            time.to_rfc3339_opts(SecondsFormat::Secs, true),
+            "2023-11-14T22:13:20Z"
+        );
+        assert!(parse_source_date_epoch("yesterday").is_err());
+        assert_eq!(TimestampMode::None.resolve(), Ok(None));
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use crate::{
     models::{
@@ -12,7 +12,7 @@ use crate::{
     utils::location::loc_to_location,
 };
 use solang_parser::pt::{
-    CatchClause, ContractDefinition, ContractPart, EnumDefinition, ErrorDefinition,
+    CatchClause, Comment, ContractDefinition, ContractPart, EnumDefinition, ErrorDefinition,
     EventDefinition, Expression, FunctionAttribute, FunctionDefinition, FunctionTy, Import, Loc,
     Mutability, PragmaDirective, Statement, StructDefinition, Type, TypeDefinition, Using,
     UsingList, VariableDeclaration, VariableDefinition, VersionComparator, VersionOp, Visibility,
@@ -568,6 +568,47 @@ fn find_statement_types_recursive<F>(
     }
 }
 
+/// Compute the deepest nesting of control-flow blocks (if/for/while/do-while/try) in a statement.
+/// `else if` chains count as a single level, plain blocks do not add depth.
+pub fn max_nesting_depth(stmt: &Statement) -> usize {
+    match stmt {
+        Statement::Block { statements, .. } => {
+            statements.iter().map(max_nesting_depth).max().unwrap_or(0)
+        }
+        Statement::If(_, _, then_stmt, else_stmt_opt) => {
+            let then_depth = 1 + max_nesting_depth(then_stmt);
+            let else_depth = match else_stmt_opt.as_deref() {
+                Some(else_if @ Statement::If(..)) => max_nesting_depth(else_if),
+                Some(else_stmt) => 1 + max_nesting_depth(else_stmt),
+                None => 0,
+            };
+            then_depth.max(else_depth)
+        }
+        Statement::While(_, _, body) | Statement::DoWhile(_, body, _) => {
+            1 + max_nesting_depth(body)
+        }
+        Statement::For(_, _, _, _, body_opt) => {
+            1 + body_opt.as_deref().map_or(0, max_nesting_depth)
+        }
+        Statement::Try(_, _, returns_opt, catch_clauses) => {
+            let returns_depth = returns_opt
+                .as_ref()
+                .map_or(0, |(_, stmt)| max_nesting_depth(stmt));
+            let catch_depth = catch_clauses
+                .iter()
+                .map(|clause| match clause {
+                    CatchClause::Simple(_, _, stmt) | CatchClause::Named(_, _, _, stmt) => {
+                        max_nesting_depth(stmt)
+                    }
+                })
+                .max()
+                .unwrap_or(0);
+            1 + returns_depth.max(catch_depth)
+        }
+        _ => 0,
+    }
+}
+
 /// Helper function to get location from any statement
 fn get_statement_location(stmt: &Statement) -> Option<Loc> {
     match stmt {
@@ -635,6 +676,41 @@ pub fn find_variable_uses(var_name: &str, body: &Statement, file: &SolidityFile)
     occurrences
 }
 
+/// Identifiers in Solidity source with their byte offsets, skipping comments and string
+/// literals. Contracts named by `@inheritdoc` in doc comments are included, as the compiler
+/// resolves them like code references.
+pub fn source_identifiers(source: &str) -> Vec<(usize, &str)> {
+    let mut comments = Vec::new();
+    let mut errors = Vec::new();
+    let mut spans: Vec<(usize, usize)> =
+        solang_parser::lexer::Lexer::new(source, 0, &mut comments, &mut errors)
+            .filter_map(|(start, token, end)| match token {
+                solang_parser::lexer::Token::Identifier(_) => Some((start, end)),
+                _ => None,
+            })
+            .collect();
+
+    for comment in &comments {
+        let (Comment::DocLine(loc, text) | Comment::DocBlock(loc, text)) = comment else {
+            continue;
+        };
+        for (at, tag) in text.match_indices("@inheritdoc") {
+            let rest = &text[at + tag.len()..];
+            let name_start = at + tag.len() + rest.len() - rest.trim_start().len();
+            let name_len = text[name_start..]
+                .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+                .unwrap_or(text.len() - name_start);
+            if name_len > 0 {
+                spans.push((loc.start() + name_start, loc.start() + name_start + name_len));
+            }
+        }
+    }
+    spans
+        .into_iter()
+        .map(|(start, end)| (start, &source[start..end]))
+        .collect()
+}
+
 /// Get all local variable names in a function (parameters + return params + declarations)
 pub fn get_local_variable_names(
     func_def: &FunctionDefinition,
@@ -675,6 +751,15 @@ pub fn collect_local_declarations(stmt: &Statement, local_vars: &mut HashSet<Str
     });
 }
 
+/// Collect local variable declarations from a statement with the location of each name
+pub fn collect_local_declaration_locs(stmt: &Statement, declarations: &mut Vec<(String, Loc)>) {
+    collect_local_variables(stmt, &mut |decl| {
+        if let Some(name) = &decl.name {
+            declarations.push((name.name.clone(), name.loc));
+        }
+    });
+}
+
 /// The callback receives the full VariableDeclaration and can extract any needed info.
 pub fn collect_local_variables<F>(stmt: &Statement, callback: &mut F)
 where
@@ -684,6 +769,21 @@ where
         Statement::VariableDefinition(_, decl, _) => {
             callback(decl);
         }
+        // Tuple declarations: `(, int256 answer, , , ) = feed.latestRoundData();`
+        Statement::Expression(_, Expression::Assign(_, lhs, _)) => {
+            if let Expression::List(_, params) = lhs.as_ref() {
+                for param in params.iter().filter_map(|(_, p)| p.as_ref()) {
+                    if param.name.is_some() {
+                        callback(&VariableDeclaration {
+                            loc: param.loc,
+                            ty: param.ty.clone(),
+                            storage: param.storage.clone(),
+                            name: param.name.clone(),
+                        });
+                    }
+                }
+            }
+        }
         Statement::Block { statements, .. } => {
             for s in statements {
                 collect_local_variables(s, callback);
@@ -710,6 +810,76 @@ where
     }
 }
 
+/// Map variable names visible in a function body to their types: state variables,
+/// parameters, named return values and local declarations (later entries shadow earlier ones).
+pub fn build_variable_type_map(
+    func_def: &FunctionDefinition,
+    body: &Statement,
+    state_var_types: &HashMap<String, TypeInfo>,
+) -> HashMap<String, TypeInfo> {
+    let mut var_types = state_var_types.clone();
+
+    let named_params = func_def
+        .params
+        .iter()
+        .chain(func_def.returns.iter())
+        .filter_map(|(_, param)| param.as_ref());
+    for param in named_params {
+        if let Some(name) = &param.name {
+            var_types.insert(name.name.clone(), TypeInfo::from_expression(&param.ty));
+        }
+    }
+
+    collect_local_variables(body, &mut |decl| {
+        if let Some(name) = &decl.name {
+            var_types.insert(name.name.clone(), TypeInfo::from_expression(&decl.ty));
+        }
+    });
+
+    var_types
+}
+
+/// Declared type of an assignment target: a variable from `var_types`, a field of a known
+/// struct (`pos.amount`, `positions[id].amount`), or a mapping value / array element.
+/// Returns `None` when any step cannot be resolved.
+pub fn resolve_assignment_target_type(
+    target: &Expression,
+    var_types: &HashMap<String, TypeInfo>,
+    structs: &[&StructInfo],
+) -> Option<TypeInfo> {
+    let resolved = match target {
+        Expression::Variable(id) => var_types.get(&id.name)?.clone(),
+        Expression::Parenthesis(_, inner) => {
+            return resolve_assignment_target_type(inner, var_types, structs)
+        }
+        Expression::ArraySubscript(_, base, Some(_)) => {
+            match resolve_assignment_target_type(base, var_types, structs)? {
+                TypeInfo::Mapping { value, .. } => *value,
+                TypeInfo::Array { base, .. } => *base,
+                _ => return None,
+            }
+        }
+        Expression::MemberAccess(_, base, member) => {
+            let TypeInfo::UserDefined(name) =
+                resolve_assignment_target_type(base, var_types, structs)?
+            else {
+                return None;
+            };
+            // `Lib.Position` and `Position` name the same struct definition
+            let struct_name = name.rsplit('.').next().unwrap_or(&name);
+            let field = structs
+                .iter()
+                .find(|s| s.name == struct_name)?
+                .fields
+                .iter()
+                .find(|f| f.name.as_deref() == Some(member.name.as_str()))?;
+            TypeInfo::from_type_name(&field.type_name)
+        }
+        _ => return None,
+    };
+    (resolved != TypeInfo::Unknown).then_some(resolved)
+}
+
 /// Collect all function call names from a statement
 pub fn collect_function_calls(stmt: &Statement, calls: &mut HashSet<String>) {
     match stmt {
@@ -1057,7 +1227,7 @@ pub fn extract_contract_enums(
 fn extract_type_name(type_expr: &Expression) -> String {
     match type_expr {
         Expression::Variable(ident) => ident.name.clone(),
-        Expression::Type(_, ty) => format!("{:?}", ty),
+        Expression::Type(_, ty) => ty.to_string(),
         Expression::ArraySubscript(_, base, size) => {
             let base_type = extract_type_name(base);
             if let Some(size_expr) = size {
@@ -1373,6 +1543,7 @@ pub fn extract_function_info(func_def: &FunctionDefinition, file: &SolidityFile)
             param_opt.as_ref().map(|param| FunctionParameter {
                 name: param.name.as_ref().map(|id| id.name.clone()),
                 type_name: extract_type_name(&param.ty),
+                storage_location: param.storage.as_ref().map(|s| s.to_string()),
             })
         })
         .collect();
@@ -1385,6 +1556,7 @@ pub fn extract_function_info(func_def: &FunctionDefinition, file: &SolidityFile)
             param_opt.as_ref().map(|param| FunctionParameter {
                 name: param.name.as_ref().map(|id| id.name.clone()),
                 type_name: extract_type_name(&param.ty),
+                storage_location: param.storage.as_ref().map(|s| s.to_string()),
             })
         })
         .collect();
@@ -1574,6 +1746,56 @@ pub fn is_function_readonly(func_def: &FunctionDefinition) -> bool {
     })
 }
 
+/// Check if a function carries a reentrancy guard modifier (`nonReentrant`, Uniswap's `lock`)
+pub fn has_reentrancy_guard(func_def: &FunctionDefinition) -> bool {
+    func_def.attributes.iter().any(|attr| {
+        let FunctionAttribute::BaseOrModifier(_, base) = attr else {
+            return false;
+        };
+        base.name.identifiers.last().is_some_and(|id| {
+            let name = id.name.to_lowercase();
+            name.contains("nonreentrant") || name == "lock"
+        })
+    })
+}
+
+/// Common access control modifiers, matched as fragments of lowercased modifier names
+pub const PRIVILEGED_MODIFIERS: [&str; 20] = [
+    "onlyowner",      // onlyOwner - OpenZeppelin Ownable
+    "onlyadmin",      // onlyAdmin - General admin pattern
+    "onlygovernor",   // onlyGovernor - Governance contracts
+    "onlyguardian",   // onlyGuardian - Guardian pattern
+    "onlyoperator",   // onlyOperator - Operator pattern
+    "onlycontroller", // onlyController - Controller pattern
+    "onlymanager",    // onlyManager - Manager pattern
+    "onlyminter",     // onlyMinter - Minting privileges
+    "onlypauser",     // onlyPauser - Pause functionality
+    "onlyrole",       // onlyRole - OpenZeppelin AccessControl
+    "onlytimelock",   // onlyTimelock - Compound-style timelock
+    "onlymultisig",   // onlyMultisig - Multi-signature
+    "onlykeeper",     // onlyKeeper - Automation protocols
+    "onlystrategist", // onlyStrategist - Yield strategies
+    "onlyvault",      // onlyVault - Vault protocols
+    "onlybridge",     // onlyBridge - Bridge protocols
+    "onlyvalidator",  // onlyValidator - Validation protocols
+    "authorized",     // authorized - General authorization
+    "requiresauth",   // requiresAuth - Auth requirement
+    "hasrole",        // hasRole - OpenZeppelin AccessControl
+];
+
+/// Check if a function carries an access control modifier (`onlyOwner`, `onlyRole(...)`, ...)
+pub fn has_privileged_modifier(func_def: &FunctionDefinition) -> bool {
+    func_def.attributes.iter().any(|attr| {
+        let FunctionAttribute::BaseOrModifier(_, base) = attr else {
+            return false;
+        };
+        base.name.identifiers.last().is_some_and(|id| {
+            let name = id.name.to_lowercase();
+            PRIVILEGED_MODIFIERS.iter().any(|pattern| name.contains(pattern))
+        })
+    })
+}
+
 /// Get the visibility of a function (if specified)
 pub fn get_function_visibility(func_def: &FunctionDefinition) -> Option<&Visibility> {
     func_def.attributes.iter().find_map(|attr| {
@@ -1746,3 +1968,55 @@ pub fn is_external_call(expr: &Expression) -> bool {
 
     true
 }
+
+/// The value of a call option (`gas`, `value`, `salt`) set on a call-options expression:
+/// the `target.call{value: v, gas: g}` callee of `target.call{value: v, gas: g}(data)`.
+pub fn call_option<'a>(expr: &'a Expression, name: &str) -> Option<&'a Expression> {
+    let Expression::FunctionCallBlock(_, _, block) = expr else {
+        return None;
+    };
+    let Statement::Args(_, args) = block.as_ref() else {
+        return None;
+    };
+    args.iter()
+        .find(|arg| arg.name.name == name)
+        .map(|arg| &arg.expr)
+}
+
+/// Check if two expressions are structurally equal, ignoring source locations and parentheses.
+/// `users[id].stakes` in a loop condition equals `users[id].stakes` in the loop body.
+pub fn expressions_equal(a: &Expression, b: &Expression) -> bool {
+    match (a, b) {
+        (Expression::Parenthesis(_, inner), _) => expressions_equal(inner, b),
+        (_, Expression::Parenthesis(_, inner)) => expressions_equal(a, inner),
+        (Expression::Variable(a_id), Expression::Variable(b_id)) => a_id.name == b_id.name,
+        (
+            Expression::MemberAccess(_, a_base, a_member),
+            Expression::MemberAccess(_, b_base, b_member),
+        ) => a_member.name == b_member.name && expressions_equal(a_base, b_base),
+        (
+            Expression::ArraySubscript(_, a_base, a_index),
+            Expression::ArraySubscript(_, b_base, b_index),
+        ) => {
+            expressions_equal(a_base, b_base)
+                && match (a_index, b_index) {
+                    (Some(a_index), Some(b_index)) => expressions_equal(a_index, b_index),
+                    (None, None) => true,
+                    _ => false,
+                }
+        }
+        (
+            Expression::FunctionCall(_, a_func, a_args),
+            Expression::FunctionCall(_, b_func, b_args),
+        ) => {
+            expressions_equal(a_func, b_func)
+                && a_args.len() == b_args.len()
+                && a_args
+                    .iter()
+                    .zip(b_args)
+                    .all(|(a_arg, b_arg)| expressions_equal(a_arg, b_arg))
+        }
+        // Literals and operators: the parser's canonical rendering carries no locations
+        _ => std::mem::discriminant(a) == std::mem::discriminant(b) && a.to_string() == b.to_string(),
+    }
+}
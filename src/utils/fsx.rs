@@ -0,0 +1,249 @@
+//! File system helpers for running several weasel processes side by side: an advisory
+//! per-project lock and atomic (temp file + rename) writes.
+
+use std::fmt;
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::{self, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Per-project directory for state shared between runs, such as the lock file
+pub const CACHE_DIR: &str = ".weasel-cache";
+
+const LOCK_FILE: &str = "lock";
+
+const LOCK_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Create the project's `.weasel-cache` directory, ignored by git, if it does not exist yet.
+pub fn create_cache_dir(root: &Path) -> io::Result<PathBuf> {
+    let dir = root.join(CACHE_DIR);
+    fs::create_dir_all(&dir)?;
+    let gitignore = dir.join(".gitignore");
+    if !gitignore.exists() {
+        fs::write(&gitignore, "*\n")?;
+    }
+    Ok(dir)
+}
+
+/// Advisory lock on a project's `.weasel-cache` directory, released when dropped (or when the
+/// process exits, however it exits).
+#[derive(Debug)]
+pub struct ProjectLock {
+    _file: File,
+    path: PathBuf,
+}
+
+#[derive(Debug)]
+pub enum LockError {
+    /// Another process still held the lock when the timeout ran out
+    Busy {
+        path: PathBuf,
+        holder: Option<u32>,
+    },
+    Io(PathBuf, io::Error),
+}
+
+impl fmt::Display for LockError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LockError::Busy { path, holder } => {
+                write!(f, "another weasel process is running")?;
+                if let Some(pid) = holder {
+                    write!(f, " (pid {})", pid)?;
+                }
+                write!(f, " and holds {}", path.display())
+            }
+            LockError::Io(path, e) => write!(f, "failed to lock {}: {}", path.display(), e),
+        }
+    }
+}
+
+impl ProjectLock {
+    /// Take the lock of the project at `root`, waiting up to `timeout` for another process to
+    /// release it. Creates `.weasel-cache` (ignored by git) if needed.
+    pub fn acquire(root: &Path, timeout: Duration) -> Result<Self, LockError> {
+        let path = root.join(CACHE_DIR).join(LOCK_FILE);
+        let io_error = |e| LockError::Io(path.clone(), e);
+
+        create_cache_dir(root).map_err(io_error)?;
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .map_err(io_error)?;
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            match file.try_lock() {
+                Ok(()) => break,
+                Err(TryLockError::WouldBlock) if Instant::now() < deadline => {
+                    thread::sleep(LOCK_POLL_INTERVAL);
+                }
+                Err(TryLockError::WouldBlock) => {
+                    let mut holder = String::new();
+                    let _ = file.read_to_string(&mut holder);
+                    return Err(LockError::Busy {
+                        path,
+                        holder: holder.trim().parse().ok(),
+                    });
+                }
+                Err(TryLockError::Error(e)) => return Err(io_error(e)),
+            }
+        }
+
+        // Record the holder for the error message of the next process
+        file.set_len(0)
+            .and_then(|_| file.seek(SeekFrom::Start(0)))
+            .and_then(|_| writeln!(file, "{}", std::process::id()))
+            .map_err(io_error)?;
+        Ok(Self { _file: file, path })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// A file written under a temporary name in its destination directory and renamed into place
+/// by [`AtomicFile::commit`], so readers and concurrent writers never see a partial file.
+/// Dropping it without committing removes the temporary file.
+#[derive(Debug)]
+pub struct AtomicFile {
+    writer: Option<BufWriter<File>>,
+    temp: PathBuf,
+    dest: PathBuf,
+}
+
+impl AtomicFile {
+    pub fn create(dest: &Path) -> io::Result<Self> {
+        let dir = match dest.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        let name = dest
+            .file_name()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "not a file path"))?;
+        // The pid keeps concurrent writers of the same destination apart
+        let temp = dir.join(format!(
+            ".{}.{}.tmp",
+            name.to_string_lossy(),
+            std::process::id()
+        ));
+        let file = File::create(&temp)?;
+        Ok(Self {
+            writer: Some(BufWriter::new(file)),
+            temp,
+            dest: dest.to_path_buf(),
+        })
+    }
+
+    /// Flush the contents and move them to the destination.
+    pub fn commit(mut self) -> io::Result<()> {
+        if let Some(writer) = self.writer.take() {
+            let file = writer.into_inner().map_err(|e| e.into_error())?;
+            file.sync_all()?;
+        }
+        fs::rename(&self.temp, &self.dest)
+    }
+}
+
+impl Write for AtomicFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match &mut self.writer {
+            Some(writer) => writer.write(buf),
+            None => Err(io::Error::other("file already committed")),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match &mut self.writer {
+            Some(writer) => writer.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for AtomicFile {
+    fn drop(&mut self) {
+        if self.writer.take().is_some() {
+            let _ = fs::remove_file(&self.temp);
+        }
+    }
+}
+
+/// Write `contents` to `path` atomically.
+pub fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    let mut file = AtomicFile::create(path)?;
+    file.write_all(contents.as_ref())?;
+    file.commit()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("weasel-fsx-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_project_lock_times_out_while_held() {
+        let root = temp_dir("lock");
+        let lock = ProjectLock::acquire(&root, Duration::ZERO).unwrap();
+        assert_eq!(lock.path(), root.join(".weasel-cache/lock"));
+        assert_eq!(
+            fs::read_to_string(root.join(".weasel-cache/.gitignore")).unwrap(),
+            "*\n"
+        );
+
+        // A second handle on the same file conflicts like another process would
+        let err = ProjectLock::acquire(&root, Duration::from_millis(250)).unwrap_err();
+        match &err {
+            LockError::Busy { holder, .. } => assert_eq!(*holder, Some(std::process::id())),
+            other => panic!("unexpected error: {}", other),
+        }
+        assert!(err
+            .to_string()
+            .starts_with("another weasel process is running (pid "));
+
+        drop(lock);
+        assert!(ProjectLock::acquire(&root, Duration::ZERO).is_ok());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_atomic_file_replaces_destination_on_commit() {
+        let dir = temp_dir("atomic");
+        let dest = dir.join("report.json");
+        fs::write(&dest, "old").unwrap();
+
+        let mut file = AtomicFile::create(&dest).unwrap();
+        file.write_all(b"new").unwrap();
+        assert_eq!(fs::read_to_string(&dest).unwrap(), "old");
+        file.commit().unwrap();
+        assert_eq!(fs::read_to_string(&dest).unwrap(), "new");
+
+        let mut abandoned = AtomicFile::create(&dest).unwrap();
+        abandoned.write_all(b"partial").unwrap();
+        drop(abandoned);
+        assert_eq!(fs::read_to_string(&dest).unwrap(), "new");
+
+        write_atomic(&dest, "final").unwrap();
+        assert_eq!(fs::read_to_string(&dest).unwrap(), "final");
+        let leftovers: Vec<_> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .filter(|name| name != "report.json")
+            .collect();
+        assert!(leftovers.is_empty(), "{:?}", leftovers);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
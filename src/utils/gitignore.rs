@@ -0,0 +1,140 @@
+//! `.gitignore` matching for file discovery: the rules of every `.gitignore` from the project
+//! root down to the directory being walked, with git's precedence (deeper files and later
+//! lines win, `!` re-includes).
+
+use crate::utils::path::to_forward_slashes;
+use globset::{GlobBuilder, GlobMatcher};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug)]
+struct Rule {
+    /// Directory of the `.gitignore` the rule comes from
+    base: PathBuf,
+    matcher: GlobMatcher,
+    negated: bool,
+    dir_only: bool,
+}
+
+/// Rules of the `.gitignore` files loaded so far, see [`Gitignore::load_dir`].
+#[derive(Debug)]
+pub struct Gitignore {
+    root: PathBuf,
+    rules: Vec<Rule>,
+    loaded: HashSet<PathBuf>,
+}
+
+impl Gitignore {
+    /// Rules of `.gitignore` files at and below the canonical project `root`.
+    pub fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            rules: Vec::new(),
+            loaded: HashSet::new(),
+        }
+    }
+
+    /// Read the `.gitignore` of canonical directory `dir`, and of its ancestors up to the
+    /// project root, unless already read. Directories outside the root have no rules.
+    pub fn load_dir(&mut self, dir: &Path) {
+        if !dir.starts_with(&self.root) || self.loaded.contains(dir) {
+            return;
+        }
+        if let Some(parent) = dir.parent().filter(|_| dir != self.root) {
+            self.load_dir(parent);
+        }
+        self.loaded.insert(dir.to_path_buf());
+        if let Ok(content) = fs::read_to_string(dir.join(".gitignore")) {
+            self.rules
+                .extend(content.lines().filter_map(|line| parse_rule(dir, line)));
+        }
+    }
+
+    /// Whether the canonical `path` is ignored by the rules of its directory's ancestors.
+    /// Rules are only matched against the path itself; callers skip ignored directories.
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        // Rules of deeper files were loaded later, and later rules take precedence
+        self.rules
+            .iter()
+            .rev()
+            .filter(|rule| !rule.dir_only || is_dir)
+            .find(|rule| {
+                path.strip_prefix(&rule.base)
+                    .is_ok_and(|relative| rule.matcher.is_match(to_forward_slashes(relative)))
+            })
+            .is_some_and(|rule| !rule.negated)
+    }
+}
+
+/// One line of a `.gitignore` in `base`, or `None` for blank lines, comments and patterns
+/// globset cannot compile.
+fn parse_rule(base: &Path, line: &str) -> Option<Rule> {
+    let line = line.trim_end();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let (negated, pattern) = match line.strip_prefix('!') {
+        Some(rest) => (true, rest),
+        None => (false, line.strip_prefix('\\').unwrap_or(line)),
+    };
+    let (dir_only, pattern) = match pattern.strip_suffix('/') {
+        Some(rest) => (true, rest),
+        None => (false, pattern),
+    };
+    // A slash anywhere but at the end anchors the pattern to the .gitignore's directory
+    let pattern = match pattern.strip_prefix('/') {
+        Some(anchored) => anchored.to_string(),
+        None if pattern.contains('/') => pattern.to_string(),
+        None => format!("**/{}", pattern),
+    };
+    if pattern.is_empty() {
+        return None;
+    }
+    let matcher = GlobBuilder::new(&pattern)
+        .literal_separator(true)
+        .build()
+        .ok()?
+        .compile_matcher();
+    Some(Rule {
+        base: base.to_path_buf(),
+        matcher,
+        negated,
+        dir_only,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::write_fixture_project;
+
+    #[test]
+    fn test_gitignore_rules() {
+        let root = write_fixture_project(
+            "gitignore-rules",
+            &[
+                (
+                    ".gitignore",
+                    "# build output\nout/\n*.gen.sol\n/generated\n!keep.gen.sol\n",
+                ),
+                ("src/.gitignore", "mocks/*.sol\n!mocks/Kept.sol\n"),
+            ],
+        );
+        let mut ignore = Gitignore::new(&root);
+        ignore.load_dir(&root.join("src/mocks"));
+
+        assert!(ignore.is_ignored(&root.join("out"), true));
+        assert!(ignore.is_ignored(&root.join("src/out"), true));
+        assert!(!ignore.is_ignored(&root.join("out"), false));
+        assert!(ignore.is_ignored(&root.join("src/Token.gen.sol"), false));
+        assert!(!ignore.is_ignored(&root.join("src/keep.gen.sol"), false));
+        assert!(ignore.is_ignored(&root.join("generated"), true));
+        assert!(!ignore.is_ignored(&root.join("src/generated"), true));
+        assert!(ignore.is_ignored(&root.join("src/mocks/Mock.sol"), false));
+        assert!(!ignore.is_ignored(&root.join("src/mocks/Kept.sol"), false));
+        assert!(!ignore.is_ignored(&root.join("src/Vault.sol"), false));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
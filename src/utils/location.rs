@@ -44,6 +44,9 @@ pub fn loc_to_location(loc: &Loc, file: &SolidityFile) -> Location {
                 line_end: Some(end_line),
                 column_end: Some(end_col),
                 snippet: Some(snippet),
+                message: None,
+                url: None,
+                context: None,
             }
         }
         // Handle non-file locations by returning a default
@@ -54,6 +57,9 @@ pub fn loc_to_location(loc: &Loc, file: &SolidityFile) -> Location {
             line_end: Some(1),
             column_end: Some(0),
             snippet: Some(FALLBACK_SNIPPET.to_string()),
+            message: None,
+            url: None,
+            context: None,
         },
     }
 }
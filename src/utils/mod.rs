@@ -1,5 +1,11 @@
 pub mod ast_utils;
+pub mod fsx;
+pub mod gitignore;
 pub mod location;
+pub mod nsloc;
+pub mod path;
+pub mod source_context;
+pub mod source_link;
 #[cfg(test)]
 pub mod test_utils;
 pub mod version;
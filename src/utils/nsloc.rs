@@ -0,0 +1,130 @@
+#[derive(Clone, Copy, PartialEq)]
+enum State {
+    Code,
+    LineComment,
+    BlockComment,
+    String(char),
+}
+
+/// For each line of `source`, whether it holds code: anything other than whitespace and
+/// `//` or `/* */` comments. Comment markers inside string literals count as code.
+pub fn code_lines(source: &str) -> Vec<bool> {
+    let mut lines = Vec::new();
+    let mut has_code = false;
+    let mut state = State::Code;
+    let mut chars = source.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '\n' {
+            lines.push(has_code);
+            has_code = false;
+            if state == State::LineComment {
+                state = State::Code;
+            }
+            continue;
+        }
+
+        match state {
+            State::Code => match (c, chars.peek()) {
+                ('/', Some('/')) => {
+                    chars.next();
+                    state = State::LineComment;
+                }
+                ('/', Some('*')) => {
+                    chars.next();
+                    state = State::BlockComment;
+                }
+                _ if c.is_whitespace() => {}
+                _ => {
+                    has_code = true;
+                    if c == '"' || c == '\'' {
+                        state = State::String(c);
+                    }
+                }
+            },
+            State::LineComment => {}
+            State::BlockComment => {
+                if c == '*' && chars.peek() == Some(&'/') {
+                    chars.next();
+                    state = State::Code;
+                }
+            }
+            State::String(quote) => {
+                has_code = true;
+                if c == '\\' {
+                    // An escaped newline still ends the line
+                    if chars.peek() != Some(&'\n') {
+                        chars.next();
+                    }
+                } else if c == quote {
+                    state = State::Code;
+                }
+            }
+        }
+    }
+
+    if !source.is_empty() && !source.ends_with('\n') {
+        lines.push(has_code);
+    }
+    lines
+}
+
+/// Number of code lines between `first_line` and `last_line` (1-based, inclusive), given the
+/// result of `code_lines` for the file.
+pub fn count_nsloc(code_lines: &[bool], first_line: usize, last_line: usize) -> usize {
+    code_lines
+        .iter()
+        .take(last_line)
+        .skip(first_line.saturating_sub(1))
+        .filter(|is_code| **is_code)
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_lines_skips_comments_and_blanks() {
+        let source = r#"// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+/**
+ * @notice Vault
+ */
+contract Vault { /* inline */
+    uint256 a; // trailing comment
+
+    /* block */ uint256 b;
+    /* spans
+       lines */
+
+    function f() external {}
+}"#;
+
+        assert_eq!(
+            code_lines(source),
+            [
+                false, true, false, false, false, false, true, true, false, true, false, false,
+                false, true, true
+            ]
+        );
+        assert_eq!(count_nsloc(&code_lines(source), 7, 15), 5);
+    }
+
+    #[test]
+    fn test_comment_markers_inside_strings_are_code() {
+        let source = concat!(
+            "string s = \"http://example.com\";\n",
+            "string t = 'a /* not a comment';\n",
+            "string u = \"escaped \\\" // still a string\";\n",
+            "/* \"quoted\" in a comment\n",
+            "*/\n",
+            "x; /* a */ y;\n",
+        );
+
+        assert_eq!(code_lines(source), [true, true, true, false, false, true]);
+        assert_eq!(count_nsloc(&code_lines(source), 1, 6), 4);
+        assert_eq!(count_nsloc(&code_lines(source), 4, 5), 0);
+    }
+}
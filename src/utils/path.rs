@@ -0,0 +1,293 @@
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::str::FromStr;
+
+/// Base directory that reported file paths are made relative to.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PathBase {
+    Absolute,
+    #[default]
+    Project,
+    Cwd,
+}
+
+impl FromStr for PathBase {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "absolute" => Ok(PathBase::Absolute),
+            "project" => Ok(PathBase::Project),
+            "cwd" => Ok(PathBase::Cwd),
+            _ => Err(format!("Invalid path base: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for PathBase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathBase::Absolute => write!(f, "absolute"),
+            PathBase::Project => write!(f, "project"),
+            PathBase::Cwd => write!(f, "cwd"),
+        }
+    }
+}
+
+impl PathBase {
+    /// Resolve the directory paths are made relative to. `None` means absolute paths.
+    pub fn resolve_root(&self, project_root: &Path) -> Option<PathBuf> {
+        let root = match self {
+            PathBase::Absolute => return None,
+            PathBase::Project if project_root.as_os_str().is_empty() => PathBuf::from("."),
+            PathBase::Project => project_root.to_path_buf(),
+            PathBase::Cwd => std::env::current_dir().ok()?,
+        };
+        Some(fs::canonicalize(&root).unwrap_or(root))
+    }
+}
+
+/// Rewrite a file path relative to `root` using forward slashes.
+/// Paths outside of `root` (or with no root) are reported as absolute paths. An empty root
+/// would be a prefix of every relative path, so it counts as no root.
+pub fn normalize_report_path(file: &str, root: Option<&Path>) -> String {
+    let path = Path::new(file);
+    // Missing files can't be canonicalized; `absolute` at least drops their `./` segments
+    let absolute = fs::canonicalize(path)
+        .or_else(|_| std::path::absolute(path))
+        .unwrap_or_else(|_| path.to_path_buf());
+
+    let root = root.filter(|r| !r.as_os_str().is_empty());
+    match root.and_then(|r| absolute.strip_prefix(r).ok()) {
+        Some(relative) => to_forward_slashes(relative),
+        None => absolute.to_string_lossy().to_string(),
+    }
+}
+
+/// Join path components with `/` regardless of the host platform.
+pub fn to_forward_slashes(path: &Path) -> String {
+    path.components()
+        .filter_map(|c| match c {
+            Component::Normal(part) => Some(part.to_string_lossy().to_string()),
+            Component::ParentDir => Some("..".to_string()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Scope or exclude entries. Literal paths match themselves and everything below them.
+/// Entries with glob metacharacters (`**/mocks/**`, `src/**/test/*.sol`) are anchored at
+/// their literal leading directories (the working directory when there are none) and matched
+/// against whole paths with `/` separators, `\` being read as a separator too. `*` and `?`
+/// stay within one path component, `**` spans any number of them.
+#[derive(Debug, Clone)]
+pub struct PathPatterns {
+    literals: Vec<PathBuf>,
+    globs: GlobSet,
+}
+
+impl PathPatterns {
+    /// `kind` names the entries in error messages, e.g. "exclude".
+    pub fn new(entries: &[PathBuf], kind: &str) -> Result<Self, String> {
+        let mut literals = Vec::new();
+        let mut builder = GlobSetBuilder::new();
+        for entry in entries {
+            if !is_glob_pattern(entry) {
+                // Entries that do not exist cannot match anything
+                literals.extend(fs::canonicalize(entry).ok());
+                continue;
+            }
+            let pattern = anchored_pattern(entry);
+            let glob = GlobBuilder::new(&pattern)
+                .literal_separator(true)
+                .build()
+                .map_err(|e| {
+                    format!(
+                        "Invalid {} pattern '{}': {}",
+                        kind,
+                        entry.display(),
+                        e.kind()
+                    )
+                })?;
+            builder.add(glob);
+        }
+        let globs = builder
+            .build()
+            .map_err(|e| format!("Invalid {} patterns: {}", kind, e))?;
+        Ok(Self { literals, globs })
+    }
+
+    /// Whether an existing path is, or is inside, a literal entry, or matches a glob.
+    pub fn is_match(&self, path: &Path) -> bool {
+        let Ok(canonical) = fs::canonicalize(path) else {
+            return false;
+        };
+        self.literals
+            .iter()
+            .any(|literal| canonical.starts_with(literal))
+            || (!self.globs.is_empty() && self.globs.is_match(to_forward_slashes(&canonical)))
+    }
+}
+
+/// Whether a scope or exclude entry contains glob metacharacters.
+pub fn is_glob_pattern(entry: &Path) -> bool {
+    entry.to_string_lossy().contains(['*', '?', '[', '{'])
+}
+
+/// The leading components of a glob pattern that contain no metacharacters, i.e. the
+/// directory its matches are under. `.` when the pattern starts with a glob. Literal entries
+/// are returned unchanged.
+pub fn glob_base(entry: &Path) -> PathBuf {
+    if !is_glob_pattern(entry) {
+        return entry.to_path_buf();
+    }
+    let normalized = entry.to_string_lossy().replace('\\', "/");
+    let literal: Vec<&str> = normalized
+        .split('/')
+        .take_while(|part| !is_glob_pattern(Path::new(part)))
+        .collect();
+    match literal.join("/") {
+        base if base.is_empty() && normalized.starts_with('/') => PathBuf::from("/"),
+        base if base.is_empty() => PathBuf::from("."),
+        base => PathBuf::from(base),
+    }
+}
+
+/// A glob entry rewritten against the canonical form of its base directory, in the form
+/// `to_forward_slashes` gives canonical paths.
+fn anchored_pattern(entry: &Path) -> String {
+    let base = glob_base(entry);
+    let normalized = entry.to_string_lossy().replace('\\', "/");
+    let base_str = base.to_string_lossy().replace('\\', "/");
+    let rest = match normalized.strip_prefix(base_str.as_str()) {
+        Some(rest) if rest.starts_with('/') || base_str.ends_with('/') => {
+            rest.trim_start_matches('/')
+        }
+        // No leading directories: the `.` base is implied
+        _ => normalized.as_str(),
+    };
+    let canonical_base = fs::canonicalize(&base).unwrap_or(base);
+    let anchor = globset::escape(&to_forward_slashes(&canonical_base));
+    if anchor.is_empty() {
+        rest.to_string()
+    } else {
+        format!("{}/{}", anchor, rest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_report_path() {
+        let root = std::env::temp_dir().join(format!("weasel-path-{}", std::process::id()));
+        let file = root.join("src").join("Vault.sol");
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        fs::write(&file, "").unwrap();
+        let root = fs::canonicalize(&root).unwrap();
+
+        let file_str = file.to_string_lossy().to_string();
+        assert_eq!(
+            normalize_report_path(&file_str, Some(&root)),
+            "src/Vault.sol"
+        );
+
+        let absolute = normalize_report_path(&file_str, None);
+        assert!(Path::new(&absolute).is_absolute());
+
+        let nested = root.join("src");
+        assert_eq!(normalize_report_path(&file_str, Some(&nested)), "Vault.sol");
+        let unrelated = normalize_report_path(&file_str, Some(Path::new("/nonexistent")));
+        assert!(Path::new(&unrelated).is_absolute());
+        let empty = normalize_report_path(&file_str, Some(Path::new("")));
+        assert!(Path::new(&empty).is_absolute());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_path_base_from_str() {
+        assert_eq!("Project".parse::<PathBase>(), Ok(PathBase::Project));
+        assert_eq!("cwd".parse::<PathBase>(), Ok(PathBase::Cwd));
+        assert_eq!("absolute".parse::<PathBase>(), Ok(PathBase::Absolute));
+        assert!("repo".parse::<PathBase>().is_err());
+    }
+
+    fn pattern_fixture(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("weasel-{}-{}", name, std::process::id()));
+        for file in [
+            "src/Vault.sol",
+            "src/mocks/MockToken.sol",
+            "src/core/mocks/deep/MockOracle.sol",
+            "src/core/test/Helper.sol",
+            "src/core/test/nested/Other.sol",
+        ] {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, "").unwrap();
+        }
+        root
+    }
+
+    #[test]
+    fn test_path_patterns_globs() {
+        let root = pattern_fixture("path-patterns");
+        let recursive = PathPatterns::new(&[root.join("**/mocks/**")], "exclude").unwrap();
+        assert!(recursive.is_match(&root.join("src/mocks/MockToken.sol")));
+        assert!(recursive.is_match(&root.join("src/core/mocks/deep/MockOracle.sol")));
+        assert!(!recursive.is_match(&root.join("src/Vault.sol")));
+
+        // `*` stays within the `test` directory
+        let single = PathPatterns::new(&[root.join("src/**/test/*.sol")], "exclude").unwrap();
+        assert!(single.is_match(&root.join("src/core/test/Helper.sol")));
+        assert!(!single.is_match(&root.join("src/core/test/nested/Other.sol")));
+
+        // Backslash separators are normalized
+        let windows = format!("{}\\src\\*.sol", root.display());
+        let windows = PathPatterns::new(&[PathBuf::from(windows)], "exclude").unwrap();
+        assert!(windows.is_match(&root.join("src/Vault.sol")));
+        assert!(!windows.is_match(&root.join("src/mocks/MockToken.sol")));
+
+        let mixed = PathPatterns::new(
+            &[root.join("src/mocks"), root.join("**/Helper.sol")],
+            "exclude",
+        )
+        .unwrap();
+        assert!(mixed.is_match(&root.join("src/mocks/MockToken.sol")));
+        assert!(mixed.is_match(&root.join("src/core/test/Helper.sol")));
+        assert!(!mixed.is_match(&root.join("src/core/mocks/deep/MockOracle.sol")));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_path_patterns_invalid_glob() {
+        let err = PathPatterns::new(&[PathBuf::from("src/[mocks/*.sol")], "exclude").unwrap_err();
+        assert!(
+            err.starts_with("Invalid exclude pattern 'src/[mocks/*.sol': "),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_glob_base() {
+        assert_eq!(glob_base(Path::new("src/**/test/*.sol")), Path::new("src"));
+        assert_eq!(glob_base(Path::new("**/mocks/**")), Path::new("."));
+        assert_eq!(glob_base(Path::new("/a/b/*.sol")), Path::new("/a/b"));
+        assert_eq!(
+            glob_base(Path::new("src\\core\\*.sol")),
+            Path::new("src/core")
+        );
+        assert_eq!(
+            glob_base(Path::new("src/Vault.sol")),
+            Path::new("src/Vault.sol")
+        );
+    }
+}
@@ -0,0 +1,136 @@
+//! Source lines around a reported location, read from disk so low-memory runs, which drop file
+//! contents, show the same context.
+
+use crate::models::ContextLine;
+use std::collections::HashMap;
+use std::fs;
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// Spaces a tab is expanded to
+const TAB_WIDTH: usize = 4;
+
+/// Windows of source lines around flagged lines. Each file is read once per report.
+#[derive(Debug)]
+pub struct SourceContext {
+    /// Lines shown before and after the flagged line
+    lines: usize,
+    /// Longest line in characters before it is truncated, 0 for no limit
+    width: usize,
+    files: Mutex<HashMap<String, Option<Arc<Vec<String>>>>>,
+}
+
+impl SourceContext {
+    pub fn new(lines: usize, width: usize) -> Self {
+        Self {
+            lines,
+            width,
+            files: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Lines `line - lines ..= line + lines` of `file`, clamped to the start and end of the
+    /// file, with tabs expanded and their common indentation removed. `None` if the file cannot
+    /// be read or has no such line.
+    pub fn window(&self, file: &str, line: usize) -> Option<Vec<ContextLine>> {
+        let source = self.source(file)?;
+        if line == 0 || line > source.len() {
+            return None;
+        }
+        let first = line.saturating_sub(self.lines).max(1);
+        let last = line.saturating_add(self.lines).min(source.len());
+        let window = &source[first - 1..last];
+
+        let indent = window
+            .iter()
+            .filter(|text| !text.is_empty())
+            .map(|text| text.len() - text.trim_start_matches(' ').len())
+            .min()
+            .unwrap_or(0);
+        Some(
+            window
+                .iter()
+                .zip(first..)
+                .map(|(text, line)| ContextLine {
+                    line,
+                    text: truncate(text.get(indent..).unwrap_or(""), self.width),
+                })
+                .collect(),
+        )
+    }
+
+    fn source(&self, file: &str) -> Option<Arc<Vec<String>>> {
+        let mut files = self.files.lock().unwrap_or_else(PoisonError::into_inner);
+        files
+            .entry(file.to_string())
+            .or_insert_with(|| {
+                let content = fs::read_to_string(file).ok()?;
+                Some(Arc::new(
+                    content
+                        .lines()
+                        .map(|line| {
+                            line.replace('\t', &" ".repeat(TAB_WIDTH))
+                                .trim_end()
+                                .to_string()
+                        })
+                        .collect(),
+                ))
+            })
+            .clone()
+    }
+}
+
+/// `text` cut to `width` characters, ending in `...` when shortened.
+fn truncate(text: &str, width: usize) -> String {
+    if width == 0 || text.chars().count() <= width {
+        return text.to_string();
+    }
+    let kept: String = text.chars().take(width.saturating_sub(3)).collect();
+    format!("{}...", kept)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::write_fixture_project;
+
+    fn lines(window: &[ContextLine]) -> Vec<(usize, &str)> {
+        window.iter().map(|l| (l.line, l.text.as_str())).collect()
+    }
+
+    #[test]
+    fn test_window_around_line() {
+        let root = write_fixture_project(
+            "source-context",
+            &[(
+                "Vault.sol",
+                "contract Vault {\n\tfunction f() external {\n\t\tx = 1;  \n\n\t\ty = 2;\n\t}\n}\n",
+            )],
+        );
+        let file = root.join("Vault.sol").to_string_lossy().to_string();
+        let context = SourceContext::new(1, 0);
+
+        // Tabs are expanded, trailing spaces trimmed and the common indentation removed
+        assert_eq!(
+            lines(&context.window(&file, 4).unwrap()),
+            [(3, "x = 1;"), (4, ""), (5, "y = 2;")]
+        );
+        assert_eq!(
+            lines(&context.window(&file, 1).unwrap()),
+            [(1, "contract Vault {"), (2, "    function f() external {")]
+        );
+        assert_eq!(
+            lines(&context.window(&file, 7).unwrap()),
+            [(6, "    }"), (7, "}")]
+        );
+        assert!(context.window(&file, 8).is_none());
+        assert!(context.window("missing.sol", 1).is_none());
+
+        let narrow = SourceContext::new(0, 12);
+        assert_eq!(
+            lines(&narrow.window(&file, 2).unwrap()),
+            [(2, "function ...")]
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
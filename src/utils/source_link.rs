@@ -0,0 +1,135 @@
+use crate::utils::path::to_forward_slashes;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Placeholders a source link template may use.
+const PLACEHOLDERS: [&str; 3] = ["commit", "path", "line"];
+
+/// Check that `template` only uses known `{placeholder}`s and has balanced braces.
+pub fn validate_template(template: &str) -> Result<(), String> {
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let after = &rest[start + 1..];
+        let end = after
+            .find('}')
+            .ok_or_else(|| format!("unclosed `{{` in source link template '{}'", template))?;
+        let name = &after[..end];
+        if !PLACEHOLDERS.contains(&name) {
+            return Err(format!(
+                "unknown placeholder `{{{}}}` in source link template '{}'; expected {{commit}}, \
+                 {{path}} or {{line}}",
+                name, template
+            ));
+        }
+        rest = &after[end + 1..];
+    }
+    if rest.contains('}') {
+        return Err(format!("unmatched `}}` in source link template '{}'", template));
+    }
+    Ok(())
+}
+
+/// Builds links to source lines from a template such as
+/// `https://github.com/org/repo/blob/{commit}/{path}#L{line}`.
+#[derive(Debug, Clone)]
+pub struct SourceLinker {
+    template: String,
+    commit: String,
+    repo_root: PathBuf,
+}
+
+impl SourceLinker {
+    /// Fill `{commit}` from the git checkout containing `project_root` and make paths relative
+    /// to its top level. Outside a git checkout, `fallback_ref` and `project_root` are used.
+    pub fn new(template: &str, project_root: &Path, fallback_ref: &str) -> Self {
+        let (repo_root, commit) = match git_metadata(project_root) {
+            Some((root, commit)) => (root, commit),
+            None => (project_root.to_path_buf(), fallback_ref.to_string()),
+        };
+        Self::with_commit(template, &repo_root, &commit)
+    }
+
+    pub fn with_commit(template: &str, repo_root: &Path, commit: &str) -> Self {
+        Self {
+            template: template.to_string(),
+            commit: commit.to_string(),
+            repo_root: fs::canonicalize(repo_root).unwrap_or_else(|_| repo_root.to_path_buf()),
+        }
+    }
+
+    /// Link to `line` of `file`, or `None` for files outside the repository.
+    pub fn url(&self, file: &str, line: usize) -> Option<String> {
+        let path = Path::new(file);
+        let absolute = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        let relative = absolute.strip_prefix(&self.repo_root).ok()?;
+
+        Some(
+            self.template
+                .replace("{commit}", &self.commit)
+                .replace("{path}", &to_forward_slashes(relative))
+                .replace("{line}", &line.to_string()),
+        )
+    }
+}
+
+/// Top-level directory and `HEAD` commit of the git checkout containing `dir`.
+fn git_metadata(dir: &Path) -> Option<(PathBuf, String)> {
+    let git = |args: &[&str]| -> Option<String> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(args)
+            .output()
+            .ok()?;
+        let value = String::from_utf8(output.stdout).ok()?.trim().to_string();
+        (output.status.success() && !value.is_empty()).then_some(value)
+    };
+
+    let root = git(&["rev-parse", "--show-toplevel"])?;
+    let commit = git(&["rev-parse", "HEAD"])?;
+    Some((PathBuf::from(root), commit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::write_fixture_project;
+
+    const TEMPLATE: &str = "https://github.com/org/repo/blob/{commit}/{path}#L{line}";
+
+    #[test]
+    fn test_validate_template() {
+        assert!(validate_template(TEMPLATE).is_ok());
+        assert!(validate_template("https://example.com/src").is_ok());
+        assert_eq!(
+            validate_template("https://example.com/{branch}/{path}"),
+            Err("unknown placeholder `{branch}` in source link template \
+                 'https://example.com/{branch}/{path}'; expected {commit}, {path} or {line}"
+                .to_string())
+        );
+        assert!(validate_template("https://example.com/{path").is_err());
+        assert!(validate_template("https://example.com/path}").is_err());
+    }
+
+    #[test]
+    fn test_url_outside_git_uses_fallback_ref() {
+        let root = write_fixture_project("source-link", &[("src/vault/Vault.sol", "")]);
+        let file = root.join("src").join("vault").join("Vault.sol");
+
+        let linker = SourceLinker::new(TEMPLATE, &root, "main");
+        assert_eq!(
+            linker.url(&file.to_string_lossy(), 42).as_deref(),
+            Some("https://github.com/org/repo/blob/main/src/vault/Vault.sol#L42")
+        );
+
+        let linker = SourceLinker::with_commit(TEMPLATE, &root, "4f2a9c1");
+        assert_eq!(
+            linker.url(&file.to_string_lossy(), 7).as_deref(),
+            Some("https://github.com/org/repo/blob/4f2a9c1/src/vault/Vault.sol#L7")
+        );
+        assert_eq!(linker.url("/elsewhere/Other.sol", 1), None);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
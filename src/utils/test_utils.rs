@@ -93,3 +93,20 @@ pub fn run_detector_with_mock_inheritance(
     // Extract locations from findings
     findings.iter().map(|f| f.location.clone()).collect()
 }
+
+/// Write a throwaway project to a fresh temp directory and return its root.
+/// `files` are (relative path, contents) pairs; parent directories are created.
+pub fn write_fixture_project(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("weasel-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&root);
+
+    for (relative, content) in files {
+        let path = root.join(relative);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).expect("Failed to create fixture directory");
+        }
+        std::fs::write(&path, content).expect("Failed to write fixture file");
+    }
+
+    std::fs::canonicalize(&root).expect("Failed to canonicalize fixture root")
+}
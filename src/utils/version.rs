@@ -1,3 +1,4 @@
+use crate::output::style::errln;
 use semver::{Version, VersionReq};
 
 /// "^0.8.0" -> "^0.8.0"
@@ -17,7 +18,7 @@ fn extract_and_parse_min_version(req_str: &str) -> Option<Version> {
         .trim_start_matches(|c: char| !c.is_digit(10));
 
     if min_ver_str.is_empty() {
-        eprintln!(
+        errln!(
             "Warning: Could not extract minimum version digits from '{}'",
             req_str
         );
@@ -27,9 +28,11 @@ fn extract_and_parse_min_version(req_str: &str) -> Option<Version> {
     match Version::parse(min_ver_str) {
         Ok(ver) => Some(ver),
         Err(e) => {
-            eprintln!(
+            errln!(
                 "Warning: Could not parse minimum version '{}' from '{}': {}",
-                min_ver_str, req_str, e
+                min_ver_str,
+                req_str,
+                e
             );
             None
         }
@@ -45,9 +48,10 @@ pub fn solidity_version_req_matches(
     let solidity_req = match VersionReq::parse(&cleaned_solidity_req_str) {
         Ok(req) => req,
         Err(e) => {
-            eprintln!(
+            errln!(
                 "Error parsing Solidity version requirement '{}': {}",
-                cleaned_solidity_req_str, e
+                cleaned_solidity_req_str,
+                e
             );
             return false;
         }
@@ -56,9 +60,10 @@ pub fn solidity_version_req_matches(
     let check_req = match VersionReq::parse(required_req_str) {
         Ok(req) => req,
         Err(e) => {
-            eprintln!(
+            errln!(
                 "Error parsing required version requirement '{}': {}",
-                required_req_str, e
+                required_req_str,
+                e
             );
             return false;
         }
@@ -84,10 +89,71 @@ pub fn solidity_version_req_matches(
     }
 }
 
+/// Check the running weasel version against a project's `required_version` pin. Comparators
+/// may be separated by commas or whitespace (`">=0.5.0, <0.7"`, `">=0.5.0 <0.7"`).
+pub fn check_required_version(required: &str, current: &str) -> Result<(), String> {
+    let req = VersionReq::parse(required.trim())
+        .or_else(|e| {
+            let comparators: Vec<&str> = required.split_whitespace().collect();
+            VersionReq::parse(&comparators.join(", ")).map_err(|_| e)
+        })
+        .map_err(|e| format!("invalid required_version '{}': {}", required, e))?;
+    let current_version = Version::parse(current)
+        .map_err(|e| format!("invalid weasel version '{}': {}", current, e))?;
+
+    if req.matches(&current_version) {
+        Ok(())
+    } else {
+        Err(format!(
+            "this project requires weasel {} but {} is installed; run `weaselup` to switch \
+             versions or adjust `required_version` in weasel.toml",
+            required, current
+        ))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_check_required_version() {
+        // Exact and caret pins
+        assert!(check_required_version("0.5.0", "0.5.0").is_ok());
+        assert!(check_required_version("=0.5.0", "0.5.0").is_ok());
+        assert!(check_required_version("^0.5", "0.5.3").is_ok());
+        assert!(check_required_version("^0.5.0", "0.5.0").is_ok());
+        assert!(check_required_version("^0.4", "0.5.0").is_err());
+        assert!(check_required_version("0.6", "0.5.0").is_err());
+        assert!(check_required_version("=1.0.0", "0.5.0").is_err());
+
+        // Tilde and wildcards
+        assert!(check_required_version("~0.5", "0.5.9").is_ok());
+        assert!(check_required_version("~0.5.1", "0.5.0").is_err());
+        assert!(check_required_version("0.5.*", "0.5.2").is_ok());
+        assert!(check_required_version("0.*", "0.5.0").is_ok());
+        assert!(check_required_version("*", "0.5.0").is_ok());
+
+        // Ranges, comma or whitespace separated
+        assert!(check_required_version(">=0.4", "0.5.0").is_ok());
+        assert!(check_required_version(">0.5.0", "0.5.0").is_err());
+        assert!(check_required_version(">=0.5.0, <0.6", "0.5.0").is_ok());
+        assert!(check_required_version(">=0.5.0 <0.6", "0.5.0").is_ok());
+        assert!(check_required_version(">=0.5.1 <0.6", "0.5.0").is_err());
+        assert!(check_required_version(" ^0.5 ", "0.5.0").is_ok());
+
+        assert_eq!(
+            check_required_version("^0.6", "0.5.0"),
+            Err(
+                "this project requires weasel ^0.6 but 0.5.0 is installed; run `weaselup` to \
+                 switch versions or adjust `required_version` in weasel.toml"
+                    .to_string()
+            )
+        );
+        let invalid = check_required_version("latest", "0.5.0").unwrap_err();
+        assert!(invalid.starts_with("invalid required_version 'latest'"));
+    }
+
     #[test]
     fn test_version_req_matches() {
         // Exact match
@@ -0,0 +1,67 @@
+//! Throwaway Solidity projects for the CLI integration tests, and a runner for the `weasel`
+//! binary built alongside them.
+
+use std::env;
+use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+/// A `weasel-<name>-<pid>` directory in the system temp dir, removed when dropped so neither
+/// passing nor failing tests leave projects behind.
+pub struct TempProject {
+    dir: PathBuf,
+}
+
+impl TempProject {
+    /// Create the project with `files`, given as paths relative to its root and contents.
+    /// Whatever an earlier run with the same name left behind is removed first.
+    pub fn new(name: &str, files: &[(&str, &str)]) -> Self {
+        let dir = env::temp_dir().join(format!("weasel-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        for (path, contents) in files {
+            let path = dir.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+        Self { dir }
+    }
+}
+
+impl Deref for TempProject {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.dir
+    }
+}
+
+impl AsRef<Path> for TempProject {
+    fn as_ref(&self) -> &Path {
+        &self.dir
+    }
+}
+
+impl Drop for TempProject {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// Run `weasel <args>` in `dir`. `WEASEL_*` variables from the caller's environment are
+/// cleared so they can't change the run; `envs` are set on top.
+pub fn weasel(dir: &Path, args: &[&str], envs: &[(&str, &str)]) -> Output {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_weasel"));
+    for (key, _) in env::vars_os() {
+        if key.to_string_lossy().starts_with("WEASEL_") {
+            command.env_remove(key);
+        }
+    }
+    command
+        .args(args)
+        .envs(envs.iter().copied())
+        .current_dir(dir)
+        .output()
+        .expect("Failed to run weasel")
+}
@@ -0,0 +1,239 @@
+//! Deterministic generator for synthetic Solidity projects, shared by the performance smoke
+//! test and the benchmarks so no large fixture has to be committed.
+//!
+//! Every file holds an interface, an abstract base and a contract inheriting from the base of
+//! an earlier file, with state variables, mappings, modifiers, loops, external calls and
+//! arithmetic, so inheritance lookups and most detectors have something to do. The same seed
+//! always produces the same sources.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// xorshift64*: small, fast and stable across platforms and Rust versions.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Self(seed.max(1))
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+        items[self.below(items.len())]
+    }
+}
+
+const UINT_TYPES: &[&str] = &["uint256", "uint128", "uint64", "uint32"];
+const OPS: &[&str] = &["+", "-", "*", "/", "%"];
+const COMPARISONS: &[&str] = &["<", ">", "<=", ">=", "!="];
+
+/// Write `files` contracts under `dir/src` and return the sources' total size in bytes.
+pub fn write_project(dir: &Path, files: usize, seed: u64) -> usize {
+    let src = dir.join("src");
+    fs::create_dir_all(&src).expect("Failed to create synthetic project");
+    let mut rng = Rng::new(seed);
+    let mut total = 0;
+    for index in 0..files {
+        let source = contract_file(&mut rng, index);
+        total += source.len();
+        fs::write(src.join(format!("Unit{}.sol", index)), source)
+            .expect("Failed to write synthetic contract");
+    }
+    total
+}
+
+fn contract_file(rng: &mut Rng, index: usize) -> String {
+    let mut s = String::new();
+    writeln!(s, "// SPDX-License-Identifier: MIT").unwrap();
+    writeln!(s, "pragma solidity ^0.8.{};", 4 + rng.below(20)).unwrap();
+    writeln!(s).unwrap();
+
+    // Inherit from the base of an earlier file to build chains across files
+    let parent = (index > 0).then(|| rng.below(index));
+    if let Some(parent) = parent {
+        writeln!(s, "import \"./Unit{}.sol\";", parent).unwrap();
+        writeln!(s).unwrap();
+    }
+
+    writeln!(s, "interface IUnit{} {{", index).unwrap();
+    writeln!(
+        s,
+        "    function balanceOf(address account) external view returns (uint256);"
+    )
+    .unwrap();
+    writeln!(
+        s,
+        "    function transfer(address to, uint256 amount) external returns (bool);"
+    )
+    .unwrap();
+    writeln!(s, "}}").unwrap();
+    writeln!(s).unwrap();
+
+    match parent {
+        Some(parent) => writeln!(s, "abstract contract Base{} is Base{} {{", index, parent),
+        None => writeln!(s, "abstract contract Base{} {{", index),
+    }
+    .unwrap();
+    writeln!(s, "    address public owner{};", index).unwrap();
+    writeln!(
+        s,
+        "    mapping(address => uint256) internal credits{};",
+        index
+    )
+    .unwrap();
+    writeln!(s).unwrap();
+    writeln!(s, "    modifier onlyOwner{}() {{", index).unwrap();
+    writeln!(
+        s,
+        "        require(msg.sender == owner{}, \"not owner\");",
+        index
+    )
+    .unwrap();
+    writeln!(s, "        _;").unwrap();
+    writeln!(s, "    }}").unwrap();
+    writeln!(s).unwrap();
+    writeln!(
+        s,
+        "    function _credit{}(address account, uint256 amount) internal virtual {{",
+        index
+    )
+    .unwrap();
+    writeln!(s, "        credits{}[account] += amount;", index).unwrap();
+    writeln!(s, "    }}").unwrap();
+    writeln!(s, "}}").unwrap();
+    writeln!(s).unwrap();
+
+    writeln!(s, "contract Unit{} is Base{} {{", index, index).unwrap();
+    let types: Vec<&str> = (0..3 + rng.below(4))
+        .map(|_| rng.pick(UINT_TYPES))
+        .collect();
+    for (var, ty) in types.iter().enumerate() {
+        writeln!(s, "    {} public value{};", ty, var).unwrap();
+    }
+    writeln!(s, "    uint256[] public items;").unwrap();
+    writeln!(s, "    IUnit{} public token;", index).unwrap();
+    writeln!(
+        s,
+        "    event Updated(address indexed account, uint256 value);"
+    )
+    .unwrap();
+    writeln!(s).unwrap();
+    writeln!(s, "    constructor(IUnit{} token_) {{", index).unwrap();
+    writeln!(s, "        token = token_;").unwrap();
+    writeln!(s, "        owner{} = msg.sender;", index).unwrap();
+    writeln!(s, "    }}").unwrap();
+
+    let functions = 4 + rng.below(4);
+    for function in 0..functions {
+        writeln!(s).unwrap();
+        function_body(&mut s, rng, index, function, &types);
+    }
+    writeln!(s, "}}").unwrap();
+    s
+}
+
+fn function_body(s: &mut String, rng: &mut Rng, index: usize, function: usize, types: &[&str]) {
+    let var = rng.below(types.len());
+    match rng.below(4) {
+        // Loop over storage with arithmetic and a storage write per iteration
+        0 => {
+            writeln!(
+                s,
+                "    function sum{}(uint256 limit) external returns (uint256 total) {{",
+                function
+            )
+            .unwrap();
+            writeln!(s, "        for (uint256 i = 0; i < items.length; i++) {{").unwrap();
+            writeln!(
+                s,
+                "            if (items[i] {} limit) {{",
+                rng.pick(COMPARISONS)
+            )
+            .unwrap();
+            writeln!(
+                s,
+                "                total = total {} items[i] * {} / {};",
+                rng.pick(&["+", "-"]),
+                1 + rng.below(1000),
+                1 + rng.below(100)
+            )
+            .unwrap();
+            writeln!(s, "            }}").unwrap();
+            writeln!(s, "            value{} = {}(total);", var, types[var]).unwrap();
+            writeln!(s, "        }}").unwrap();
+            writeln!(s, "    }}").unwrap();
+        }
+        // External call, then state update and event
+        1 => {
+            writeln!(
+                s,
+                "    function pay{}(address to, uint256 amount) external onlyOwner{} {{",
+                function, index
+            )
+            .unwrap();
+            writeln!(s, "        require(amount > 0, \"zero\");").unwrap();
+            writeln!(s, "        token.transfer(to, amount);").unwrap();
+            writeln!(s, "        _credit{}(to, amount);", index).unwrap();
+            writeln!(s, "        emit Updated(to, amount);").unwrap();
+            writeln!(s, "    }}").unwrap();
+        }
+        // Nested expressions with casts and a ternary
+        2 => {
+            writeln!(
+                s,
+                "    function quote{}(uint256 a, uint256 b) public view returns (uint256) {{",
+                function
+            )
+            .unwrap();
+            writeln!(
+                s,
+                "        uint256 x = (a {} b) {} (uint256(value{}) + {});",
+                rng.pick(OPS),
+                rng.pick(OPS),
+                var,
+                1 + rng.below(10_000)
+            )
+            .unwrap();
+            writeln!(
+                s,
+                "        return x {} b ? x * {} / 10000 : block.timestamp + x;",
+                rng.pick(COMPARISONS),
+                rng.below(10_000)
+            )
+            .unwrap();
+            writeln!(s, "    }}").unwrap();
+        }
+        // While loop with push and a low-level call
+        _ => {
+            writeln!(
+                s,
+                "    function grow{}(uint256 n) external payable {{",
+                function
+            )
+            .unwrap();
+            writeln!(s, "        uint256 i;").unwrap();
+            writeln!(s, "        while (i < n) {{").unwrap();
+            writeln!(s, "            items.push(i * {});", 1 + rng.below(50)).unwrap();
+            writeln!(s, "            i++;").unwrap();
+            writeln!(s, "        }}").unwrap();
+            writeln!(
+                s,
+                "        (bool ok, ) = payable(msg.sender).call{{value: msg.value}}(\"\");"
+            )
+            .unwrap();
+            writeln!(s, "        require(ok);").unwrap();
+            writeln!(s, "    }}").unwrap();
+        }
+    }
+}
@@ -0,0 +1,143 @@
+//! Several weasel processes on the same project must not corrupt each other's output: runs
+//! wait for the project lock, and reports are renamed into place once complete.
+
+#[path = "common/project.rs"]
+mod project;
+
+use project::TempProject;
+use std::fs::{self, OpenOptions};
+use std::path::Path;
+use std::process::Output;
+use std::thread;
+
+const CONTRACT: &str = r#"// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract Vault {
+    bool public paused;
+    mapping(address => uint256) public balances;
+
+    function withdraw(uint256 amount) external {
+        require(balances[msg.sender] >= amount);
+        (bool ok, ) = msg.sender.call{value: amount}("");
+        require(ok);
+        balances[msg.sender] -= amount;
+    }
+}
+"#;
+
+fn project(name: &str) -> TempProject {
+    TempProject::new(
+        &format!("concurrent-{}", name),
+        &[
+            ("foundry.toml", "[profile.default]\n"),
+            ("src/Vault.sol", CONTRACT),
+        ],
+    )
+}
+
+fn weasel(dir: &Path, args: &[&str], envs: &[(&str, &str)]) -> Output {
+    let args: Vec<_> = ["run"].iter().chain(args).copied().collect();
+    let mut envs = envs.to_vec();
+    envs.push(("WEASEL_PLAIN", "1"));
+    project::weasel(dir, &args, &envs)
+}
+
+fn assert_valid_report(path: &Path) {
+    let content = fs::read_to_string(path).unwrap();
+    let report: serde_json::Value = serde_json::from_str(&content)
+        .unwrap_or_else(|e| panic!("invalid report {}: {}", path.display(), e));
+    assert!(report["findings"].as_array().is_some_and(|f| !f.is_empty()));
+}
+
+#[test]
+fn test_concurrent_runs_produce_valid_reports() {
+    let dir = project("reports");
+
+    // Two runs at once, writing the same report and their own
+    let runs: Vec<_> = ["shared", "shared", "first", "second"]
+        .into_iter()
+        .map(|name| {
+            let dir = dir.to_path_buf();
+            thread::spawn(move || weasel(&dir, &["-f", "json", "-o", name], &[]))
+        })
+        .collect();
+    for run in runs {
+        let output = run.join().unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    for name in ["shared", "first", "second"] {
+        assert_valid_report(&dir.join(name).with_extension("json"));
+    }
+    let leftovers: Vec<_> = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .filter(|name| name.ends_with(".tmp"))
+        .collect();
+    assert!(leftovers.is_empty(), "{:?}", leftovers);
+}
+
+#[test]
+fn test_held_lock_times_out_unless_concurrency_is_allowed() {
+    let dir = project("held");
+    fs::create_dir_all(dir.join(".weasel-cache")).unwrap();
+    let lock = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(dir.join(".weasel-cache/lock"))
+        .unwrap();
+    lock.lock().unwrap();
+
+    let blocked = weasel(
+        &dir,
+        &["-f", "json", "-o", "blocked"],
+        &[("WEASEL_LOCK_TIMEOUT", "0")],
+    );
+    assert!(!blocked.status.success());
+    let stderr = String::from_utf8_lossy(&blocked.stderr);
+    assert!(
+        stderr.contains("another weasel process is running"),
+        "{}",
+        stderr
+    );
+    assert!(!dir.join("blocked.json").exists());
+
+    let allowed = weasel(
+        &dir,
+        &["-f", "json", "-o", "allowed", "--allow-concurrent"],
+        &[],
+    );
+    assert!(
+        allowed.status.success(),
+        "{}",
+        String::from_utf8_lossy(&allowed.stderr)
+    );
+    assert!(String::from_utf8_lossy(&allowed.stderr).contains("--allow-concurrent"));
+    assert_valid_report(&dir.join("allowed.json"));
+}
+
+#[test]
+fn test_lock_is_taken_in_the_project_root_not_the_cwd() {
+    let dir = project("outside");
+    let cwd = TempProject::new("concurrent-cwd", &[]);
+    let scope = dir.join("src");
+
+    let output = weasel(
+        &cwd,
+        &["-s", scope.to_str().unwrap(), "-f", "json", "-o", "report"],
+        &[],
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(dir.join(".weasel-cache/lock").exists());
+    assert!(!cwd.join(".weasel-cache").exists());
+}
@@ -0,0 +1,195 @@
+//! Keeps `Detector::version` honest: `tests/detector_versions.json` records, for every built-in
+//! detector, its version and a hash of its source file (without the `#[cfg(test)]` module).
+//! Editing a detector makes this test fail until the change is acknowledged:
+//!
+//! - if the change can alter findings, bump the detector's `version()`;
+//! - then regenerate the record and commit it with the change:
+//!
+//! ```sh
+//! WEASEL_UPDATE_DETECTOR_VERSIONS=1 cargo test --test detector_versions
+//! ```
+//!
+//! The regenerated file shows the new hash next to the version in review, so a logic change
+//! without a bump stands out. Versions may never decrease.
+//!
+//! It also checks the other direction: every detector defined under `src/detectors/` must be
+//! registered by `register_built_in_detectors`, so an implemented detector can't silently
+//! never run.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use weasel::config::Config;
+use weasel::core::engine::AnalysisEngine;
+
+const SEVERITY_DIRS: [&str; 5] = ["high", "medium", "low", "gas", "nc"];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Recorded {
+    version: u32,
+    source_hash: String,
+}
+
+fn record_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/detector_versions.json")
+}
+
+/// The string literal returned by `fn id`, if the file defines a detector.
+fn declared_id(source: &str) -> Option<&str> {
+    let after = &source[source.find("fn id(&self)")?..];
+    let start = after.find('"')? + 1;
+    let end = start + after[start..].find('"')?;
+    Some(&after[start..end])
+}
+
+/// Path and contents of every file under `src/detectors/<severity>/` that implements `Detector`.
+fn detector_sources() -> Vec<(PathBuf, String)> {
+    let detectors_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/detectors");
+    let mut sources = Vec::new();
+    for dir in SEVERITY_DIRS {
+        let entries = fs::read_dir(detectors_dir.join(dir))
+            .unwrap_or_else(|e| panic!("Failed to read src/detectors/{}: {}", dir, e));
+        for path in entries.filter_map(|entry| entry.ok().map(|e| e.path())) {
+            if path.extension().is_none_or(|ext| ext != "rs") {
+                continue;
+            }
+            let source = fs::read_to_string(&path).expect("Failed to read detector source");
+            if source.contains("impl Detector for ") {
+                sources.push((path, source));
+            }
+        }
+    }
+    sources.sort();
+    sources
+}
+
+/// Detector id -> SHA-256 of its source, ignoring the test module and line endings.
+fn source_hashes() -> BTreeMap<String, String> {
+    let mut hashes = BTreeMap::new();
+    for (_, source) in detector_sources() {
+        let Some(id) = declared_id(&source) else {
+            continue;
+        };
+        let logic = source
+            .split("#[cfg(test)]")
+            .next()
+            .unwrap_or_default()
+            .replace("\r\n", "\n");
+        hashes.insert(id.to_string(), format!("{:x}", Sha256::digest(logic)));
+    }
+    hashes
+}
+
+#[test]
+fn detector_versions_are_acknowledged() {
+    let config = Config::default();
+    let mut engine = AnalysisEngine::new(&config);
+    engine.register_built_in_detectors();
+    let hashes = source_hashes();
+
+    let mut current = BTreeMap::new();
+    for detector in engine.registry().get_all() {
+        let source_hash = hashes
+            .get(detector.id())
+            .unwrap_or_else(|| panic!("No source file declares detector '{}'", detector.id()));
+        current.insert(
+            detector.id().to_string(),
+            Recorded {
+                version: detector.version(),
+                source_hash: source_hash.clone(),
+            },
+        );
+    }
+
+    let path = record_path();
+    let recorded: BTreeMap<String, Recorded> = fs::read_to_string(&path)
+        .ok()
+        .map(|content| {
+            serde_json::from_str(&content)
+                .unwrap_or_else(|e| panic!("Invalid {}: {}", path.display(), e))
+        })
+        .unwrap_or_default();
+
+    let decreased: Vec<String> = current
+        .iter()
+        .filter_map(|(id, now)| {
+            let before = recorded.get(id)?;
+            (now.version < before.version)
+                .then(|| format!("  {}: version {} -> {}", id, before.version, now.version))
+        })
+        .collect();
+    assert!(
+        decreased.is_empty(),
+        "Detector versions must not decrease:\n{}",
+        decreased.join("\n")
+    );
+
+    if std::env::var_os("WEASEL_UPDATE_DETECTOR_VERSIONS").is_some() {
+        let json = serde_json::to_string_pretty(&current).unwrap();
+        fs::write(&path, json + "\n").expect("Failed to write detector versions");
+        return;
+    }
+
+    let mut failures = Vec::new();
+    for (id, now) in &current {
+        match recorded.get(id) {
+            None => failures.push(format!("  {}: not recorded", id)),
+            Some(before) if before.source_hash != now.source_hash => {
+                failures.push(if before.version == now.version {
+                    format!(
+                        "  {}: source changed, still version {} (bump `version()` if findings \
+                         can change)",
+                        id, now.version
+                    )
+                } else {
+                    format!(
+                        "  {}: source changed, version {} -> {}",
+                        id, before.version, now.version
+                    )
+                })
+            }
+            Some(_) => {}
+        }
+    }
+    for id in recorded.keys().filter(|id| !current.contains_key(*id)) {
+        failures.push(format!("  {}: recorded but no longer registered", id));
+    }
+
+    assert!(
+        failures.is_empty(),
+        "Detector sources differ from tests/detector_versions.json (rerun with \
+         WEASEL_UPDATE_DETECTOR_VERSIONS=1 and commit the result):\n{}",
+        failures.join("\n")
+    );
+}
+
+#[test]
+fn every_detector_source_is_registered() {
+    let config = Config::default();
+    let mut engine = AnalysisEngine::new(&config);
+    engine.register_built_in_detectors();
+    let registered: BTreeSet<&str> = engine.registry().get_all().iter().map(|d| d.id()).collect();
+
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let mut missing = Vec::new();
+    for (path, source) in detector_sources() {
+        let path = path
+            .strip_prefix(root)
+            .unwrap_or(&path)
+            .display()
+            .to_string();
+        match declared_id(&source) {
+            Some(id) if registered.contains(id) => {}
+            Some(id) => missing.push(format!("  {} ({})", id, path)),
+            None => missing.push(format!("  no `fn id` found ({})", path)),
+        }
+    }
+    assert!(
+        missing.is_empty(),
+        "Detectors not registered in `register_built_in_detectors` (or not declared in their \
+         severity's `mod.rs`):\n{}",
+        missing.join("\n")
+    );
+}
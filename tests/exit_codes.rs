@@ -0,0 +1,108 @@
+//! `weasel run` exit codes: 0 unless a finding meets `--fail-on` (1), 2 on errors.
+
+#[path = "common/project.rs"]
+mod project;
+
+use project::TempProject;
+use std::fs;
+use std::path::Path;
+use std::process::Output;
+
+const CONTRACT: &str = r#"// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract Vault {
+    uint256 public total;
+
+    function deposit(uint256 amount) external {
+        total = total * 10000 / amount;
+    }
+}
+"#;
+
+fn project(name: &str) -> TempProject {
+    TempProject::new(&format!("exit-{}", name), &[("src/Vault.sol", CONTRACT)])
+}
+
+fn weasel(dir: &Path, args: &[&str]) -> Output {
+    let args: Vec<_> = ["run"].iter().chain(args).copied().collect();
+    project::weasel(dir, &args, &[])
+}
+
+fn exit_code(output: &Output) -> i32 {
+    output.status.code().expect("weasel was killed by a signal")
+}
+
+#[test]
+fn findings_fail_the_run_only_at_or_above_the_threshold() {
+    let dir = project("threshold");
+
+    assert_eq!(exit_code(&weasel(&dir, &["-s", "src"])), 0);
+
+    let output = weasel(&dir, &["-s", "src", "--fail-on", "nc"]);
+    assert_eq!(exit_code(&output), 1);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("at or above NC (--fail-on)") && !stderr.contains(" 0 findings at"),
+        "{}",
+        stderr
+    );
+
+    // No built-in detector reports Critical findings
+    let output = weasel(&dir, &["-s", "src", "--fail-on", "critical"]);
+    assert_eq!(exit_code(&output), 0);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("0 findings at or above Critical (--fail-on)"),
+        "{}",
+        stderr
+    );
+
+    let jsonl = weasel(&dir, &["-s", "src", "--format", "jsonl", "--fail-on", "NC"]);
+    assert_eq!(exit_code(&jsonl), 1);
+
+    fs::write(dir.join("weasel.toml"), "fail_on = \"NC\"\n").unwrap();
+    assert_eq!(exit_code(&weasel(&dir, &["-s", "src"])), 1);
+    // The command line overrides the config file
+    assert_eq!(
+        exit_code(&weasel(&dir, &["-s", "src", "--fail-on", "critical"])),
+        0
+    );
+}
+
+#[test]
+fn fail_on_env_sits_between_the_cli_and_the_config_file() {
+    let dir = project("env");
+    fs::write(dir.join("weasel.toml"), "fail_on = \"NC\"\n").unwrap();
+    assert_eq!(exit_code(&weasel(&dir, &["-s", "src"])), 1);
+
+    let with_env = |args: &[&str], fail_on: &str| {
+        let args: Vec<_> = ["run"].iter().chain(args).copied().collect();
+        project::weasel(&dir, &args, &[("WEASEL_FAIL_ON", fail_on)])
+    };
+    assert_eq!(exit_code(&with_env(&["-s", "src"], "critical")), 0);
+    assert_eq!(
+        exit_code(&with_env(&["-s", "src", "--fail-on", "nc"], "critical")),
+        1
+    );
+
+    // An invalid value is ignored with a warning, leaving the config file's threshold
+    let output = with_env(&["-s", "src"], "severe");
+    assert_eq!(exit_code(&output), 1);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Warning: WEASEL_FAIL_ON: "));
+}
+
+#[test]
+fn errors_exit_with_a_distinct_code() {
+    let dir = project("errors");
+
+    let output = weasel(&dir, &["-s", "src", "--fail-on", "severe"]);
+    assert_eq!(exit_code(&output), 2);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Invalid severity: severe"));
+
+    fs::write(dir.join("weasel.toml"), "scope = [\n").unwrap();
+    assert_eq!(
+        exit_code(&weasel(&dir, &["-s", "src", "--fail-on", "nc"])),
+        2
+    );
+}
@@ -0,0 +1,11 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.28;
+
+contract Lock {
+    uint256 transient locked;
+
+    function enter() external {
+        require(locked == 0);
+        locked = 1;
+    }
+}
@@ -0,0 +1,36 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.28;
+
+interface IVault {
+    function deposit(uint256 amount) external;
+}
+
+abstract contract Owned {
+    address public owner;
+
+    modifier onlyOwner() {
+        require(msg.sender == owner);
+        _;
+    }
+}
+
+contract Vault is IVault, Owned {
+    uint256 public constant FEE_BPS = 30;
+    mapping(address => uint256) internal balances;
+    address[] private depositors;
+    bytes32 immutable salt = keccak256("vault");
+
+    function deposit(uint256 amount) external override {
+        balances[msg.sender] += amount;
+        depositors.push(msg.sender);
+    }
+
+    function sweep(address payable to) external onlyOwner returns (uint256 swept) {
+        swept = address(this).balance;
+        to.transfer(swept);
+    }
+
+    function _fee(uint256 amount) internal pure virtual returns (uint256) {
+        return amount * FEE_BPS / 10_000;
+    }
+}
@@ -0,0 +1,145 @@
+//! Golden-report tests: every `tests/golden/*.sol` fixture is analyzed with all built-in
+//! detectors and its findings are compared with `<Fixture>.expected.json`.
+//!
+//! After an intended change in findings, regenerate the expectations and review the diff:
+//!
+//! ```sh
+//! WEASEL_UPDATE_GOLDENS=1 cargo test --test golden
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use weasel::config::Config;
+use weasel::core::engine::AnalysisEngine;
+use weasel::utils::path::PathBase;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+struct ExpectedFinding {
+    line: usize,
+    detector: String,
+}
+
+fn golden_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden")
+}
+
+fn expected_path(fixture: &Path) -> PathBuf {
+    fixture.with_extension("expected.json")
+}
+
+/// Findings per fixture file name, sorted by line then detector id.
+fn analyze_goldens(dir: &Path) -> BTreeMap<String, Vec<ExpectedFinding>> {
+    let config = Config {
+        scope: vec![dir.to_path_buf()],
+        exclude: Vec::new(),
+        path_base: PathBase::Absolute,
+        ..Config::default()
+    };
+    let mut engine = AnalysisEngine::new(&config);
+    engine.register_built_in_detectors();
+    let report = engine
+        .analyze()
+        .expect("Analysis of golden fixtures failed");
+
+    let mut actual: BTreeMap<String, Vec<ExpectedFinding>> = BTreeMap::new();
+    for finding in &report.findings {
+        for location in &finding.locations {
+            let file = Path::new(&location.file)
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| location.file.clone());
+            actual.entry(file).or_default().push(ExpectedFinding {
+                line: location.line,
+                detector: finding.detector_id.clone(),
+            });
+        }
+    }
+    for findings in actual.values_mut() {
+        findings.sort();
+    }
+    actual
+}
+
+/// Items of `a` not matched by an item of `b`, counting duplicates.
+fn subtract(a: &[ExpectedFinding], b: &[ExpectedFinding]) -> Vec<ExpectedFinding> {
+    let mut remaining = b.to_vec();
+    a.iter()
+        .filter(|item| match remaining.iter().position(|r| r == *item) {
+            Some(index) => {
+                remaining.swap_remove(index);
+                false
+            }
+            None => true,
+        })
+        .cloned()
+        .collect()
+}
+
+#[test]
+fn golden_reports_match() {
+    let dir = golden_dir();
+    let mut fixtures: Vec<PathBuf> = fs::read_dir(&dir)
+        .expect("Failed to read tests/golden")
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "sol"))
+        .collect();
+    fixtures.sort();
+    assert!(!fixtures.is_empty(), "No fixtures in {}", dir.display());
+
+    let actual = analyze_goldens(&dir);
+    let update = std::env::var_os("WEASEL_UPDATE_GOLDENS").is_some();
+    let mut failures = Vec::new();
+
+    for fixture in &fixtures {
+        let name = fixture.file_name().unwrap().to_string_lossy().to_string();
+        let found = actual.get(&name).cloned().unwrap_or_default();
+        let expected_file = expected_path(fixture);
+
+        if update {
+            let json = serde_json::to_string_pretty(&found).unwrap();
+            fs::write(&expected_file, json + "\n").expect("Failed to write expectations");
+            continue;
+        }
+
+        let Ok(content) = fs::read_to_string(&expected_file) else {
+            failures.push(format!(
+                "{}: no {} (run with WEASEL_UPDATE_GOLDENS=1 to create it)",
+                name,
+                expected_file.file_name().unwrap().to_string_lossy()
+            ));
+            continue;
+        };
+        let expected: Vec<ExpectedFinding> = serde_json::from_str(&content)
+            .unwrap_or_else(|e| panic!("Invalid {}: {}", expected_file.display(), e));
+
+        let missing = subtract(&expected, &found);
+        let unexpected = subtract(&found, &expected);
+        if missing.is_empty() && unexpected.is_empty() {
+            continue;
+        }
+
+        let mut message = format!("{}:", name);
+        for finding in &missing {
+            message.push_str(&format!(
+                "\n  - missing    {}:{}",
+                finding.detector, finding.line
+            ));
+        }
+        for finding in &unexpected {
+            message.push_str(&format!(
+                "\n  + unexpected {}:{}",
+                finding.detector, finding.line
+            ));
+        }
+        failures.push(message);
+    }
+
+    assert!(
+        failures.is_empty(),
+        "Golden reports differ (if intended, rerun with WEASEL_UPDATE_GOLDENS=1 and review the \
+         diff):\n{}",
+        failures.join("\n")
+    );
+}
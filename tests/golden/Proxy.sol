@@ -0,0 +1,25 @@
+pragma solidity 0.8.20;
+
+contract Proxy {
+    address public implementation;
+    address[] public targets;
+
+    function setImplementation(address impl) external {
+        implementation = impl;
+    }
+
+    function callAll(bytes calldata data) external {
+        for (uint256 i; i < targets.length; ++i) {
+            targets[i].delegatecall(data);
+        }
+    }
+
+    function forward(address to, bytes memory data) external payable {
+        (bool ok, ) = to.call{value: msg.value}(data);
+        ok;
+    }
+
+    function timestampDeadline() external view returns (bool) {
+        return block.timestamp + 365 days > 0;
+    }
+}
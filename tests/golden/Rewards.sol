@@ -0,0 +1,33 @@
+pragma solidity >=0.8.0;
+
+interface IOracle {
+    function getPrice(address token) external view returns (uint256);
+}
+
+contract Rewards {
+    uint256 public rate;
+    uint256[] public stakes;
+    bool public paused;
+    IOracle public oracle;
+
+    function setRate(uint256 newRate) external {
+        rate = newRate;
+    }
+
+    function total(address token) external view returns (uint256 sum) {
+        for (uint256 i = 0; i < stakes.length; i++) {
+            sum += stakes[i] * oracle.getPrice(token);
+        }
+    }
+
+    function reward(uint256 amount, uint256 bps) public view returns (uint256) {
+        return amount / 10000 * bps * rate;
+    }
+
+    function isActive() public view returns (bool) {
+        if (paused == false) {
+            return true;
+        }
+        return false;
+    }
+}
@@ -0,0 +1,34 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+interface IERC20 {
+    function transfer(address to, uint256 amount) external returns (bool);
+    function balanceOf(address account) external view returns (uint256);
+}
+
+contract Vault {
+    address public owner;
+    uint256 public totalDeposits;
+    mapping(address => uint256) public balances;
+
+    constructor() {
+        owner = msg.sender;
+    }
+
+    function deposit() external payable {
+        totalDeposits += msg.value;
+        balances[msg.sender] += msg.value;
+    }
+
+    function withdraw(uint256 amount) external {
+        require(tx.origin == owner, "not owner");
+        require(address(this).balance == totalDeposits, "invariant");
+        balances[msg.sender] -= amount;
+        totalDeposits -= amount;
+        payable(msg.sender).transfer(amount);
+    }
+
+    function sweep(IERC20 token, address to) external {
+        token.transfer(to, token.balanceOf(address(this)));
+    }
+}
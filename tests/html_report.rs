@@ -0,0 +1,129 @@
+//! Golden-file test for the HTML report: a small hand-built report is rendered and compared
+//! byte for byte with `tests/golden/html/report.expected.html`.
+//!
+//! After an intended change in the HTML output, regenerate it and review the diff:
+//!
+//! ```sh
+//! WEASEL_UPDATE_GOLDENS=1 cargo test --test html_report
+//! ```
+
+use std::fs;
+use std::path::Path;
+use weasel::models::{Category, ContextLine, Finding, Location, Report, ScopeEntry, Severity};
+use weasel::output::generate_html_report;
+
+fn location(file: &str, line: usize, snippet: &str) -> Location {
+    Location {
+        file: file.to_string(),
+        line,
+        column: None,
+        line_end: None,
+        column_end: None,
+        snippet: Some(snippet.to_string()),
+        message: None,
+        url: None,
+        context: None,
+    }
+}
+
+fn fixture_report() -> Report {
+    let mut report = Report::new();
+    report.comment = "Audit of the <Vault> contracts & friends.".to_string();
+    report.footnote = "Generated for review.".to_string();
+
+    let mut call = location(
+        "src/Vault.sol",
+        42,
+        "(bool ok, ) = msg.sender.call{value: amount}(\"\");",
+    );
+    call.url = Some("https://github.com/org/repo/blob/4f2a9c1/src/Vault.sol#L42".to_string());
+    let mut route = location(
+        "src/Router.sol",
+        7,
+        "function g(bytes memory data) external returns (uint256)",
+    );
+    route.context = Some(
+        [
+            (6, "// Forwards <data> to the target"),
+            (
+                7,
+                "function g(bytes memory data) external returns (uint256) {",
+            ),
+            (8, "    return _route(data);"),
+        ]
+        .iter()
+        .map(|(line, text)| ContextLine {
+            line: *line,
+            text: text.to_string(),
+        })
+        .collect(),
+    );
+    report.add_finding(Finding {
+        detector_id: "reentrancy".to_string(),
+        severity: Severity::High,
+        category: Category::ExternalCall,
+        title: "State written after an external call".to_string(),
+        description: "`balances` is updated after the call, so the callee can re-enter."
+            .to_string(),
+        example: Some(
+            "Update state before calling out:\n\n```solidity\nbalances[msg.sender] = 0; // effects first\n(bool ok, ) = msg.sender.call{value: amount}(\"\");\n```"
+                .to_string(),
+        ),
+        locations: vec![
+            call,
+            location("src/Vault.sol", 43, "balances[msg.sender] = 0;")
+                .with_message("`balances` is written after the external call on line 42"),
+        ],
+    });
+    report.add_finding(Finding {
+        detector_id: "calldata-instead-of-memory".to_string(),
+        severity: Severity::Gas,
+        category: Category::Gas,
+        title: "Use `calldata` instead of `memory`".to_string(),
+        description: "Read-only external parameters can be `calldata`.".to_string(),
+        example: None,
+        locations: vec![
+            location(
+                "src/Vault.sol",
+                12,
+                "function f(uint256[] memory ids) external",
+            ),
+            route,
+        ],
+    });
+    report.scope_appendix = Some(vec![ScopeEntry {
+        contract: "Vault".to_string(),
+        file: "src/Vault.sol".to_string(),
+        nsloc: 120,
+        external_functions: 4,
+        public_functions: 1,
+        internal_functions: 3,
+        has_inheritance: true,
+    }]);
+    report.add_metadata("Weasel Version", "0.0.0");
+    report
+}
+
+#[test]
+fn html_report_matches_golden() {
+    let expected_file =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden/html/report.expected.html");
+    let html = generate_html_report(&fixture_report());
+
+    if std::env::var_os("WEASEL_UPDATE_GOLDENS").is_some() {
+        fs::write(&expected_file, &html).expect("Failed to write expected HTML");
+        return;
+    }
+    let expected = fs::read_to_string(&expected_file).unwrap_or_else(|_| {
+        panic!(
+            "No {} (run with WEASEL_UPDATE_GOLDENS=1 to create it)",
+            expected_file.display()
+        )
+    });
+    assert!(
+        html == expected,
+        "HTML report differs from {} (if intended, rerun with WEASEL_UPDATE_GOLDENS=1 and \
+         review the diff)",
+        expected_file.display()
+    );
+}
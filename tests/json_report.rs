@@ -0,0 +1,68 @@
+//! Schema test for the JSON report: the file written by `-f json` carries the machine fields
+//! (detector ids, lowercase severities, summary, per-detector counts, weasel version) and
+//! deserializes back into `Report` without losing findings.
+
+use std::fs;
+use std::path::Path;
+use weasel::config::Config;
+use weasel::core::engine::AnalysisEngine;
+use weasel::models::Report;
+use weasel::output::{self, GroupBy, ReportFormat, TimestampMode};
+
+#[test]
+fn json_report_round_trips_into_report() {
+    let config = Config {
+        scope: vec![Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden")],
+        exclude: Vec::new(),
+        timestamp: TimestampMode::None,
+        ..Config::default()
+    };
+    let mut engine = AnalysisEngine::new(&config);
+    engine.register_built_in_detectors();
+    let report = engine
+        .analyze()
+        .expect("Analysis of golden fixtures failed");
+    assert!(!report.findings.is_empty());
+
+    let out = std::env::temp_dir().join(format!("weasel-json-report-{}", std::process::id()));
+    fs::create_dir_all(&out).unwrap();
+    let path = output::generate_report(
+        &report,
+        &ReportFormat::Json,
+        GroupBy::default(),
+        Some(out.join("report")),
+    )
+    .expect("Failed to write report")
+    .expect("Report was not written to a file");
+    let content = fs::read_to_string(&path).expect("Failed to read report");
+    fs::remove_dir_all(&out).ok();
+
+    let json: serde_json::Value = serde_json::from_str(&content).unwrap();
+    assert_eq!(json["weasel_version"], weasel::core::version());
+    let summary = report.summary();
+    assert_eq!(json["summary"]["total"], summary.total);
+    assert_eq!(json["summary"]["high"], summary.high);
+    assert_eq!(json["summary"]["nc"], summary.nc);
+    for finding in &report.findings {
+        assert_eq!(
+            json["detector_counts"][&finding.detector_id],
+            finding.locations.len(),
+            "{}",
+            finding.detector_id
+        );
+    }
+    for finding in json["findings"].as_array().unwrap() {
+        let severity = finding["severity"].as_str().unwrap();
+        assert_eq!(severity, severity.to_lowercase());
+        assert!(finding["detector_id"].is_string());
+    }
+
+    let parsed: Report = serde_json::from_str(&content).expect("JSON report is not a Report");
+    assert_eq!(parsed.findings.len(), report.findings.len());
+    for (parsed, original) in parsed.findings.iter().zip(&report.findings) {
+        assert_eq!(parsed.detector_id, original.detector_id);
+        assert_eq!(parsed.severity, original.severity);
+        assert_eq!(parsed.category, original.category);
+        assert_eq!(parsed.locations, original.locations);
+    }
+}
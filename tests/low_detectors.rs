@@ -0,0 +1,53 @@
+//! Low severity detectors are registered: `weasel run -m low` reports them and
+//! `weasel detectors` lists them.
+
+#[path = "common/project.rs"]
+mod project;
+
+use project::{weasel, TempProject};
+use std::fs;
+
+const CONTRACT: &str = r#"// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+contract Splitter {
+    function share(uint256 amount, uint256 holders) external pure returns (uint256) {
+        return amount / holders;
+    }
+}
+"#;
+
+#[test]
+fn min_severity_low_reports_division_by_zero() {
+    let dir = TempProject::new("low-detectors", &[("src/Splitter.sol", CONTRACT)]);
+
+    let output = weasel(
+        &dir,
+        &[
+            "run", "-s", "src", "-m", "low", "-f", "json", "-o", "report",
+        ],
+        &[],
+    );
+    assert!(output.status.success(), "{:?}", output);
+    let report = fs::read_to_string(dir.join("report.json")).unwrap();
+    let report: serde_json::Value = serde_json::from_str(&report).unwrap();
+    let finding = report["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|f| f["detector_id"] == "division-by-zero")
+        .expect("No division-by-zero finding");
+    assert_eq!(finding["severity"], "low");
+    assert_eq!(finding["locations"][0]["line"], 6);
+    // Gas and NC detectors did not run
+    assert_eq!(report["summary"]["gas"], 0);
+    assert_eq!(report["summary"]["nc"], 0);
+
+    let listing = weasel(&dir, &["detectors", "--severity", "low"], &[]);
+    let listing = String::from_utf8_lossy(&listing.stdout);
+    assert!(
+        listing.contains("(Low) - division-by-zero: "),
+        "{}",
+        listing
+    );
+}
@@ -0,0 +1,47 @@
+//! Wall-clock guard against grotesque performance regressions, for CI runs that don't run the
+//! benchmarks in `benches/`. The ceiling is far above the normal time of a debug build so
+//! slow machines don't fail it; a detector that makes analysis quadratic in the project size
+//! does.
+
+#[path = "common/solgen.rs"]
+mod solgen;
+
+use std::env;
+use std::fs;
+use std::time::{Duration, Instant};
+use weasel::config::Config;
+use weasel::core::engine::AnalysisEngine;
+
+/// Medium project: 100 generated files, about 220 KiB of Solidity.
+const FILES: usize = 100;
+const CEILING: Duration = Duration::from_secs(60);
+
+#[test]
+fn medium_project_analyzes_within_ceiling() {
+    let dir = env::temp_dir().join(format!("weasel-perf-smoke-{}", std::process::id()));
+    solgen::write_project(&dir, FILES, 0x5eed);
+
+    let config = Config {
+        scope: vec![dir.join("src")],
+        exclude: Vec::new(),
+        ..Config::default()
+    };
+    let started = Instant::now();
+    let mut engine = AnalysisEngine::new(&config);
+    engine.register_built_in_detectors();
+    let report = engine
+        .analyze()
+        .expect("Analysis of synthetic project failed");
+    let elapsed = started.elapsed();
+    fs::remove_dir_all(&dir).ok();
+
+    assert_eq!(engine.analyzed_files().count(), FILES);
+    assert!(!report.findings.is_empty());
+    assert!(
+        elapsed < CEILING,
+        "analysis of {} files took {:?}, ceiling is {:?}",
+        FILES,
+        elapsed,
+        CEILING
+    );
+}
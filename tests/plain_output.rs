@@ -0,0 +1,81 @@
+//! `--plain` / `WEASEL_PLAIN=1` output must be ASCII-only with no ANSI escapes on every CLI
+//! surface, including reports printed to stdout and clap's own errors.
+
+#[path = "common/project.rs"]
+mod project;
+
+use project::TempProject;
+use std::path::Path;
+
+const CONTRACT: &str = r#"// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+/// Vault — deposits → shares, ±1 wei rounding
+contract Vault {
+    uint256 public total;
+
+    function deposit(uint256 amount) external {
+        require(amount > 0, "amount ≥ 1");
+        total = total * 10000 / amount; // basis points ✓
+    }
+}
+"#;
+
+/// Stdout and stderr of `weasel <args>` run in `dir`.
+fn weasel(dir: &Path, args: &[&str], envs: &[(&str, &str)]) -> Vec<u8> {
+    let output = project::weasel(dir, args, envs);
+    let mut captured = output.stdout;
+    captured.extend(output.stderr);
+    captured
+}
+
+fn assert_plain(captured: &[u8], args: &[&str]) {
+    let text = String::from_utf8_lossy(captured);
+    assert!(!captured.is_empty(), "no output for {:?}", args);
+    assert!(
+        !captured.contains(&0x1b),
+        "ESC byte in output of {:?}:\n{}",
+        args,
+        text
+    );
+    assert!(
+        captured.is_ascii(),
+        "non-ASCII output of {:?}:\n{}",
+        args,
+        text
+    );
+}
+
+#[test]
+fn plain_output_is_ascii_without_escapes() {
+    let dir = TempProject::new("plain-flag", &[("src/Vault.sol", CONTRACT)]);
+    let runs: [&[&str]; 7] = [
+        &["run", "--plain", "-s", "src"],
+        &["run", "--plain", "-s", "src", "-f", "json"],
+        &["run", "--plain", "-s", "src", "-f", "sarif"],
+        &["run", "--plain", "-s", "src", "-f", "jsonl"],
+        &["--plain", "detectors"],
+        &["--plain", "detectors", "--details", "uint-gt-zero"],
+        &["--plain", "run", "--no-such-flag"],
+    ];
+    for args in runs {
+        assert_plain(&weasel(&dir, args, &[]), args);
+    }
+
+    // The fixture and detector names do produce non-ASCII output without the flag
+    let captured = weasel(&dir, &["run", "-s", "src"], &[]);
+    assert!(!captured.is_ascii());
+
+    let args = ["run", "-s", "src"];
+    assert_plain(&weasel(&dir, &args, &[("WEASEL_PLAIN", "1")]), &args);
+
+    // JSON keeps the original characters as escapes. Stdout comes first in `captured` and
+    // holds only the report, with the status lines from stderr after it
+    let captured = weasel(&dir, &["run", "--plain", "-s", "src", "-f", "json"], &[]);
+    let report: serde_json::Value = serde_json::Deserializer::from_slice(&captured)
+        .into_iter()
+        .next()
+        .unwrap()
+        .unwrap();
+    assert!(report.to_string().contains('≥'));
+}
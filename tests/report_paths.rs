@@ -0,0 +1,55 @@
+//! Reported file paths are relative to the project root whether the scope is given as a
+//! relative or an absolute path.
+
+#[path = "common/project.rs"]
+mod project;
+
+use project::{weasel, TempProject};
+
+const CONTRACT: &str = r#"// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+contract Splitter {
+    function share(uint256 amount, uint256 holders) external pure returns (uint256) {
+        return amount / holders;
+    }
+}
+"#;
+
+fn reported_files(stdout: &[u8]) -> Vec<String> {
+    let report: serde_json::Value = serde_json::from_slice(stdout).unwrap();
+    let mut files: Vec<String> = report["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .flat_map(|f| f["locations"].as_array().unwrap())
+        .map(|l| l["file"].as_str().unwrap().to_string())
+        .collect();
+    files.sort();
+    files.dedup();
+    files
+}
+
+#[test]
+fn relative_scope_reports_project_relative_paths() {
+    let dir = TempProject::new(
+        "report-paths",
+        &[
+            ("foundry.toml", "[profile.default]\n"),
+            ("src/Splitter.sol", CONTRACT),
+        ],
+    );
+
+    let relative = weasel(&dir, &["run", "-s", "src", "-f", "json"], &[]);
+    assert!(relative.status.success(), "{:?}", relative);
+    assert_eq!(reported_files(&relative.stdout), ["src/Splitter.sol"]);
+
+    let scope = dir.join("src");
+    let absolute = weasel(
+        &dir,
+        &["run", "-s", scope.to_str().unwrap(), "-f", "json"],
+        &[],
+    );
+    assert!(absolute.status.success(), "{:?}", absolute);
+    assert_eq!(reported_files(&absolute.stdout), ["src/Splitter.sol"]);
+}
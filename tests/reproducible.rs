@@ -0,0 +1,68 @@
+//! Reports of the same inputs with `timestamp = "fixed"` must be byte-identical, so a
+//! snapshot comparison in CI only fails when findings change. Any other source of
+//! nondeterminism (map iteration order, thread scheduling) in a report format fails this too.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use weasel::config::Config;
+use weasel::core::engine::AnalysisEngine;
+use weasel::output::{self, GroupBy, ReportFormat, TimestampMode};
+
+/// Analyze the golden fixtures and write the report in `format` under `dir`.
+fn write_report(dir: &Path, format: &ReportFormat) -> Vec<u8> {
+    let config = Config {
+        scope: vec![Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden")],
+        exclude: Vec::new(),
+        timestamp: TimestampMode::Fixed,
+        scope_appendix: true,
+        ..Config::default()
+    };
+    let mut engine = AnalysisEngine::new(&config);
+    engine.register_built_in_detectors();
+    let report = engine
+        .analyze()
+        .expect("Analysis of golden fixtures failed");
+
+    let path = output::generate_report(
+        &report,
+        format,
+        GroupBy::default(),
+        Some(dir.join("report")),
+    )
+    .expect("Failed to write report")
+    .expect("Report was not written to a file");
+    fs::read(path).expect("Failed to read report")
+}
+
+#[test]
+fn fixed_timestamp_reports_are_byte_identical() {
+    // The only test in this binary, so setting the variable cannot race with another test
+    env::set_var("SOURCE_DATE_EPOCH", "1700000000");
+
+    let out = env::temp_dir().join(format!("weasel-reproducible-{}", std::process::id()));
+    let (first, second) = (out.join("first"), out.join("second"));
+    fs::create_dir_all(&first).unwrap();
+    fs::create_dir_all(&second).unwrap();
+
+    for format in [
+        ReportFormat::Markdown,
+        ReportFormat::Json,
+        ReportFormat::Sarif,
+        ReportFormat::Html,
+    ] {
+        let a = write_report(&first, &format);
+        let b = write_report(&second, &format);
+        assert!(
+            a == b,
+            "{} reports of the same input differ between runs",
+            format
+        );
+        if format == ReportFormat::Markdown {
+            let markdown = String::from_utf8(a).unwrap();
+            assert!(markdown.contains("- **Timestamp:**: 2023-11-14T22:13:20Z\n"));
+        }
+    }
+
+    fs::remove_dir_all(&out).ok();
+}
@@ -0,0 +1,89 @@
+//! Reports written to stdout are the only thing on stdout: status lines, notes and the
+//! `--verbose` dump go to stderr, and `--quiet` leaves stderr empty on a clean run.
+
+#[path = "common/project.rs"]
+mod project;
+
+use project::TempProject;
+use std::path::Path;
+use std::process::Output;
+
+const CONTRACT: &str = r#"// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+contract Base {
+    uint256 internal total;
+}
+
+contract Vault is Base {
+    function share(uint256 amount, uint256 holders) external view returns (uint256) {
+        return amount * total / holders;
+    }
+}
+"#;
+
+fn project(name: &str) -> TempProject {
+    TempProject::new(&format!("stdout-{}", name), &[("src/Vault.sol", CONTRACT)])
+}
+
+fn weasel(dir: &Path, args: &[&str]) -> Output {
+    project::weasel(dir, args, &[])
+}
+
+#[test]
+fn json_on_stdout_parses_without_leading_noise() {
+    let dir = project("json");
+    for format in ["json", "sarif"] {
+        let output = weasel(&dir, &["run", "-s", "src", "-f", format]);
+        assert!(output.status.success(), "{:?}", output);
+        let report: Result<serde_json::Value, _> = serde_json::from_slice(&output.stdout);
+        assert!(
+            report.is_ok(),
+            "{} on stdout is not JSON:\n{}",
+            format,
+            String::from_utf8_lossy(&output.stdout)
+        );
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("Analysis complete:"), "{}", stderr);
+    }
+
+    let output = weasel(&dir, &["run", "-s", "src", "-f", "jsonl"]);
+    assert!(output.status.success(), "{:?}", output);
+    for line in String::from_utf8(output.stdout).unwrap().lines() {
+        serde_json::from_str::<serde_json::Value>(line).unwrap();
+    }
+}
+
+#[test]
+fn quiet_and_verbose_only_change_stderr() {
+    let dir = project("verbosity");
+    let run = |flag: Option<&str>| {
+        let mut args = vec!["run", "-s", "src", "-f", "json", "--timestamp", "none"];
+        args.extend(flag);
+        weasel(&dir, &args)
+    };
+    let normal = run(None);
+    let quiet = run(Some("-q"));
+    let verbose = run(Some("-v"));
+    for output in [&normal, &quiet, &verbose] {
+        assert!(output.status.success(), "{:?}", output);
+    }
+    assert_eq!(quiet.stdout, normal.stdout);
+    assert_eq!(verbose.stdout, normal.stdout);
+
+    assert_eq!(String::from_utf8_lossy(&quiet.stderr), "");
+    let verbose = String::from_utf8_lossy(&verbose.stderr);
+    assert!(
+        verbose.contains("Inheritance:\n  src/Vault.sol:Vault -> Base\n"),
+        "{}",
+        verbose
+    );
+    assert!(
+        verbose.contains("Files in scope: 1, contracts: 2"),
+        "{}",
+        verbose
+    );
+
+    let conflict = weasel(&dir, &["run", "-s", "src", "-q", "-v"]);
+    assert!(!conflict.status.success());
+}